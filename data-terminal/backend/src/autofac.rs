@@ -4,17 +4,47 @@ use shaku::{module, HasProvider};
 use crate::repositories::project::ProjectRepoImpl;
 use crate::repositories::resource::ResourceRepoImpl;
 use crate::repositories::datasource::DataSourceRepoImpl;
+use crate::repositories::collection::CollectionRepoImpl;
+use crate::repositories::task_run::TaskRunRepoImpl;
+use crate::repositories::schedule::ScheduleRepoImpl;
+use crate::repositories::pipeline::PipelineRepoImpl;
+use crate::repositories::transform::TransformRepoImpl;
+use crate::repositories::query::QueryHistoryRepoImpl;
+use crate::repositories::catalog::CatalogRepoImpl;
+use crate::repositories::lineage::LineageRepoImpl;
+use crate::repositories::user::UserRepoImpl;
+use crate::repositories::auth::AuthRepoImpl;
 use crate::services::project::ProjectServiceImpl;
 use crate::services::resource::ResourceServiceImpl;
 use crate::services::datasource::DataSourceServiceImpl;
-use crate::services::{ProjectService, ResourceService, DataSourceService};
+use crate::services::collection::CollectionServiceImpl;
+use crate::services::schedule::ScheduleServiceImpl;
+use crate::services::task_run::TaskRunServiceImpl;
+use crate::services::pipeline::PipelineServiceImpl;
+use crate::services::transform::TransformServiceImpl;
+use crate::services::query::QueryServiceImpl;
+use crate::services::catalog::CatalogServiceImpl;
+use crate::services::lineage::LineageServiceImpl;
+use crate::services::user::UserServiceImpl;
+use crate::services::auth::AuthServiceImpl;
+use crate::services::{ProjectService, ResourceService, DataSourceService, CollectionService, ScheduleService, TaskRunService, PipelineService, TransformService, QueryService, CatalogService, LineageService, UserService, AuthService};
 
 module! {
     pub AutoFacModule {
         components = [],
         providers = [ProjectRepoImpl, ProjectServiceImpl, 
         ResourceRepoImpl, ResourceServiceImpl, 
-        DataSourceRepoImpl, DataSourceServiceImpl]
+        DataSourceRepoImpl, DataSourceServiceImpl,
+        CollectionRepoImpl, CollectionServiceImpl,
+        TaskRunRepoImpl, TaskRunServiceImpl,
+        ScheduleRepoImpl, ScheduleServiceImpl,
+        PipelineRepoImpl, PipelineServiceImpl,
+        TransformRepoImpl, TransformServiceImpl,
+        QueryHistoryRepoImpl, QueryServiceImpl,
+        CatalogRepoImpl, CatalogServiceImpl,
+        LineageRepoImpl, LineageServiceImpl,
+        UserRepoImpl, UserServiceImpl,
+        AuthRepoImpl, AuthServiceImpl]
     }
 }
 
@@ -76,4 +106,54 @@ impl AppState {
     pub fn get_datasource_service(&self) -> Box<dyn DataSourceService> {
         self.module.provide().unwrap()
     }
+
+    /// 获取 CollectionService 实例
+    pub fn get_collection_service(&self) -> Box<dyn CollectionService> {
+        self.module.provide().unwrap()
+    }
+
+    /// 获取 ScheduleService 实例
+    pub fn get_schedule_service(&self) -> Box<dyn ScheduleService> {
+        self.module.provide().unwrap()
+    }
+
+    /// 获取 TaskRunService 实例
+    pub fn get_task_run_service(&self) -> Box<dyn TaskRunService> {
+        self.module.provide().unwrap()
+    }
+
+    /// 获取 PipelineService 实例
+    pub fn get_pipeline_service(&self) -> Box<dyn PipelineService> {
+        self.module.provide().unwrap()
+    }
+
+    /// 获取 TransformService 实例
+    pub fn get_transform_service(&self) -> Box<dyn TransformService> {
+        self.module.provide().unwrap()
+    }
+
+    /// 获取 QueryService 实例
+    pub fn get_query_service(&self) -> Box<dyn QueryService> {
+        self.module.provide().unwrap()
+    }
+
+    /// 获取 CatalogService 实例
+    pub fn get_catalog_service(&self) -> Box<dyn CatalogService> {
+        self.module.provide().unwrap()
+    }
+
+    /// 获取 LineageService 实例
+    pub fn get_lineage_service(&self) -> Box<dyn LineageService> {
+        self.module.provide().unwrap()
+    }
+
+    /// 获取 UserService 实例
+    pub fn get_user_service(&self) -> Box<dyn UserService> {
+        self.module.provide().unwrap()
+    }
+
+    /// 获取 AuthService 实例
+    pub fn get_auth_service(&self) -> Box<dyn AuthService> {
+        self.module.provide().unwrap()
+    }
 }
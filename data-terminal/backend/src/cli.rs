@@ -0,0 +1,107 @@
+use crate::services::provision;
+use crate::utils::database::{get_config_db, get_migration_db};
+use crate::utils::migration::{self, MigrationStatus, MigrationTarget};
+
+const MIGRATE_USAGE: &str = "usage: backend migrate <run|dry-run|status> [--config | --project <code>]";
+
+// migrate 子命令作用的范围
+enum Scope {
+    All,
+    Config,
+    Project(String),
+}
+
+/// 处理命令行子命令，返回 None 表示正常启动服务
+pub async fn run(args: &[String]) -> Option<i32> {
+    match args.first().map(String::as_str) {
+        Some("migrate") => Some(migrate(&args[1..]).await),
+        _ => None,
+    }
+}
+
+async fn migrate(args: &[String]) -> i32 {
+    let action = args.first().map(String::as_str).unwrap_or("status");
+    let scope = match args.get(1).map(String::as_str) {
+        None => Scope::All,
+        Some("--config") => Scope::Config,
+        Some("--project") => match args.get(2) {
+            Some(code) => Scope::Project(code.clone()),
+            None => {
+                eprintln!("{}", MIGRATE_USAGE);
+                return 2;
+            }
+        },
+        Some(_) => {
+            eprintln!("{}", MIGRATE_USAGE);
+            return 2;
+        }
+    };
+
+    let mut targets = Vec::new();
+    if matches!(scope, Scope::All | Scope::Config) {
+        targets.push((MigrationTarget::Config, None));
+    }
+    match scope {
+        Scope::All => match provision::migratable_projects().await {
+            Ok(codes) => targets.extend(codes.into_iter().map(|c| (MigrationTarget::Project, Some(c)))),
+            Err(e) => {
+                eprintln!("failed to list projects: {}", e);
+                return 1;
+            }
+        },
+        Scope::Project(code) => targets.push((MigrationTarget::Project, Some(code))),
+        Scope::Config => {}
+    }
+
+    let mut exit_code = 0;
+    for (target, code) in targets {
+        let name = code.clone().unwrap_or_else(|| "config".to_string());
+        let pool = match &code {
+            Some(code) => get_migration_db(code).await,
+            None => get_config_db().await,
+        };
+        let pool = match pool {
+            Ok(pool) => pool,
+            Err(e) => {
+                eprintln!("[{}] connect failed: {}", name, e);
+                exit_code = 1;
+                continue;
+            }
+        };
+
+        let result = match action {
+            "run" => migration::migrate(&pool, target, false).await,
+            "dry-run" => migration::migrate(&pool, target, true).await,
+            "status" => migration::status(&pool, target).await,
+            _ => {
+                eprintln!("{}", MIGRATE_USAGE);
+                return 2;
+            }
+        };
+
+        match result {
+            Ok(statuses) => print_statuses(&name, action, &statuses),
+            Err(e) => {
+                eprintln!("[{}] {}", name, e);
+                exit_code = 1;
+            }
+        }
+    }
+    exit_code
+}
+
+fn print_statuses(name: &str, action: &str, statuses: &[MigrationStatus]) {
+    if statuses.is_empty() {
+        println!("[{}] up to date", name);
+        return;
+    }
+    for s in statuses {
+        let state = match action {
+            "run" => "applied".to_string(),
+            "dry-run" => "would apply".to_string(),
+            _ => s.state.to_string(),
+        };
+        let applied_at = s.applied_at.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default();
+        println!("[{}] {:<8} {:<24} {:<18} {} {}", name, s.version, s.script, state, &s.checksum[..12], applied_at);
+    }
+}
@@ -4,14 +4,22 @@ mod services;
 mod utils;
 mod repositories;
 mod autofac;
+mod cli;
+
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
 #[tokio::main]
 async fn main() {
     // initialize configuration
     utils::config::Setting::init();
 
-    // initialize tracing
-    tracing_subscriber::fmt::init();
+    // initialize tracing, events inside a task run are also captured as the run log
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::filter::LevelFilter::INFO)
+        .with(tracing_subscriber::fmt::layer())
+        .with(services::run_log::RunLogLayer)
+        .init();
 
     // initialize database
     utils::database::config_db_init().await;
@@ -19,6 +27,43 @@ async fn main() {
     // initialize global app state
     autofac::init_global_app_state();
 
+    // handle command line subcommands such as `migrate`
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = cli::run(&args).await {
+        std::process::exit(code);
+    }
+
+    // upgrade config and project databases to the embedded schema version
+    if let Err(e) = utils::migration::migrate_config_db(false).await {
+        panic!("配置库迁移失败: {}", e);
+    }
+    services::provision::migrate_all_projects().await;
+
+    // create the first administrator from the config file when there is no user yet
+    if let Err(e) = autofac::get_global_app_state_ref().get_user_service().ensure_admin().await {
+        panic!("初始化管理员失败: {}", e);
+    }
+
+    // resume project provisioning interrupted by a restart
+    services::provision::resume_unfinished().await;
+    services::provision::spawn_archive_purge();
+    services::auth::spawn_token_purge();
+
+    // start writing captured run logs to project databases
+    services::run_log::spawn_flusher();
+
+    // resume stream collection tasks from their checkpoints
+    services::collect::resume_streams().await;
+
+    // start the cron scheduler of tasks
+    services::scheduler::spawn_scheduler();
+
+    // start background datasource health check
+    services::health_check::spawn_health_check();
+
+    // start periodic metadata catalog snapshots
+    services::catalog_sync::spawn_catalog_sync();
+
     // build our application with a route
     let app = routes::router();
 
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+// 服务端保存的刷新 token，token 原文只在签发时返回给浏览器
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct RefreshToken {
+    pub id: String,
+    pub user_id: String,
+    // 同一次登录轮换出的 token 属于同一族，发现重放时整族作废
+    pub family_id: String,
+    #[serde(skip)]
+    pub token_hash: String,
+    pub project_code: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub replaced_by: Option<String>,
+    // 作废后在宽限期内被并发请求再次使用的时间
+    #[serde(default)]
+    pub reused_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+// 签发的刷新 token 原文及其所属用户和项目
+#[derive(Debug, Clone)]
+pub struct IssuedRefreshToken {
+    pub token: String,
+    pub user_id: String,
+    pub project_code: String,
+}
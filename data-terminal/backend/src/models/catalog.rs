@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use crate::models::web::PageQuery;
+
+use crate::impl_sqlx_for_string_enum;
+
+// 元数据来源：项目数据源或数据库类资源
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogSourceType {
+    Datasource,
+    Resource,
+}
+
+impl Default for CatalogSourceType {
+    fn default() -> Self {
+        Self::Datasource
+    }
+}
+
+impl_sqlx_for_string_enum!(CatalogSourceType);
+
+// 相邻快照之间的结构变更类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DriftType {
+    TableAdded,
+    TableRemoved,
+    ColumnAdded,
+    ColumnRemoved,
+    ColumnChanged,
+}
+
+impl Default for DriftType {
+    fn default() -> Self {
+        Self::ColumnChanged
+    }
+}
+
+impl_sqlx_for_string_enum!(DriftType);
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct CatalogSnapshot {
+    pub id: String,
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+    pub source_name: String,
+    pub version: i32,
+    pub is_latest: bool,
+    pub table_count: i32,
+    pub column_count: i32,
+    pub drift_count: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct CatalogTable {
+    pub id: String,
+    pub snapshot_id: String,
+    pub schema_name: String,
+    pub table_name: String,
+    pub table_type: String,
+    pub comment: String,
+    pub approx_rows: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone, PartialEq)]
+pub struct CatalogColumn {
+    pub id: String,
+    pub snapshot_id: String,
+    pub schema_name: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub ordinal: i32,
+    pub column_type: String,
+    pub data_type: String,
+    pub nullable: bool,
+    pub default_value: Option<String>,
+    pub comment: String,
+    pub primary_key: bool,
+}
+
+impl CatalogColumn {
+    // 用于比较与展示的列定义，注释变化不视为结构变更
+    pub fn definition(&self) -> String {
+        let mut text = self.column_type.clone();
+        if !self.nullable {
+            text.push_str(" NOT NULL");
+        }
+        if let Some(default_value) = &self.default_value {
+            text.push_str(&format!(" DEFAULT {}", default_value));
+        }
+        if self.primary_key {
+            text.push_str(" PRIMARY KEY");
+        }
+        text
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct CatalogDrift {
+    pub id: String,
+    pub snapshot_id: String,
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+    pub from_version: i32,
+    pub to_version: i32,
+    pub schema_name: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub drift_type: DriftType,
+    pub before_value: Option<String>,
+    pub after_value: Option<String>,
+    pub detected_at: chrono::DateTime<chrono::Utc>,
+}
+
+// 单次快照的结果，changed 为 false 表示结构未变化，沿用最新版本
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct SnapshotResult {
+    pub snapshot: CatalogSnapshot,
+    pub changed: bool,
+    pub drifts: Vec<CatalogDrift>,
+}
+
+// 快照中的一张表及其列
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CatalogTableDetail {
+    #[serde(flatten)]
+    pub table: CatalogTable,
+    pub columns: Vec<CatalogColumn>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CatalogSnapshotDetail {
+    pub snapshot: CatalogSnapshot,
+    pub tables: Vec<CatalogTableDetail>,
+}
+
+// 手动触发单个来源的快照
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct CatalogSnapshotRequest {
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct CatalogSourceQuery {
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+impl From<&CatalogSourceQuery> for PageQuery {
+    fn from(query: &CatalogSourceQuery) -> Self {
+        PageQuery {
+            keyword: None,
+            page: query.page,
+            page_size: query.page_size,
+        }
+    }
+}
+
+// 在各来源最新快照中搜索表与列，关键字匹配表名、列名与注释
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct CatalogSearchQuery {
+    pub keyword: Option<String>,
+    pub source_type: Option<CatalogSourceType>,
+    pub source_id: Option<String>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+// 搜索结果，column_name 为空时表示命中的是表
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct CatalogSearchHit {
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+    pub source_name: String,
+    pub version: i32,
+    pub schema_name: String,
+    pub table_name: String,
+    pub table_type: String,
+    pub column_name: String,
+    pub column_type: String,
+    pub comment: String,
+}
@@ -0,0 +1,420 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use crate::models::Validator;
+use crate::models::Error;
+use crate::models::datasource::DataSourceCategory;
+
+use crate::impl_sqlx_for_string_enum;
+
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum CollectionCategory {
+    Database,
+    Api,
+    Crawler,
+}
+
+impl Default for CollectionCategory {
+    fn default() -> Self {
+        Self::Database
+    }
+}
+
+impl CollectionCategory {
+    // 采集分类需与数据源分类匹配，爬虫类采集不依赖特定数据源分类
+    pub fn accepts(&self, category: &DataSourceCategory) -> bool {
+        match self {
+            Self::Database => *category == DataSourceCategory::Database,
+            Self::Api => *category == DataSourceCategory::Api,
+            Self::Crawler => true,
+        }
+    }
+}
+
+impl_sqlx_for_string_enum!(CollectionCategory);
+
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum CollectType {
+    Full,
+    Incremental,
+    // 基于数据库日志的实时采集（CDC）
+    Stream,
+}
+
+impl Default for CollectType {
+    fn default() -> Self {
+        Self::Full
+    }
+}
+
+impl_sqlx_for_string_enum!(CollectType);
+
+// 内部使用的完整采集任务模型
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct CollectionTask {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub category: CollectionCategory,
+    #[serde(default)]
+    pub collect_type: CollectType,
+    pub datasource_id: String,
+    pub resource_id: String,
+    #[sqlx(json)]
+    pub rule: serde_json::Value,
+    #[serde(default)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Validator for CollectionTask {
+    fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() {
+            return Err(Error::EmptyValue("id".to_string()));
+        }
+        if self.name.is_empty() {
+            return Err(Error::EmptyValue("name".to_string()));
+        }
+        if self.name.len() > 64 {
+            return Err(Error::InvalidValue("name length must be less than 64 characters".to_string()));
+        }
+
+        if self.description.len() > 255 {
+            return Err(Error::InvalidValue("description length must be less than 255 characters".to_string()));
+        }
+
+        if self.datasource_id.is_empty() {
+            return Err(Error::EmptyValue("datasource_id".to_string()));
+        }
+        if self.resource_id.is_empty() {
+            return Err(Error::EmptyValue("resource_id".to_string()));
+        }
+
+        // 采集规则必须是 JSON 对象
+        if !self.rule.is_object() {
+            return Err(Error::InvalidValue("rule must be a json object".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+// Web 对外接口使用的只读采集任务模型
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct CollectionTaskReadOnly {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub category: CollectionCategory,
+    pub collect_type: CollectType,
+    pub datasource_id: String,
+    pub resource_id: String,
+    pub rule: serde_json::Value,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+// 用于创建和更新的采集任务模型
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct CollectionTaskCreateUpdate {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub category: CollectionCategory,
+    pub collect_type: CollectType,
+    pub datasource_id: String,
+    pub resource_id: String,
+    pub rule: serde_json::Value,
+}
+
+impl From<CollectionTaskCreateUpdate> for CollectionTask {
+    fn from(task: CollectionTaskCreateUpdate) -> Self {
+        Self {
+            id: task.id,
+            name: task.name,
+            description: task.description,
+            category: task.category,
+            collect_type: task.collect_type,
+            datasource_id: task.datasource_id,
+            resource_id: task.resource_id,
+            rule: task.rule,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+}
+
+impl From<CollectionTask> for CollectionTaskReadOnly {
+    fn from(task: CollectionTask) -> Self {
+        Self {
+            id: task.id,
+            name: task.name,
+            description: task.description,
+            category: task.category,
+            collect_type: task.collect_type,
+            datasource_id: task.datasource_id,
+            resource_id: task.resource_id,
+            rule: task.rule,
+            created_at: task.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            updated_at: task.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+fn default_batch_size() -> usize {
+    1000
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_replication_num() -> u32 {
+    1
+}
+
+// 数据库采集中单张表的规则
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TableRule {
+    pub source_table: String,
+    // 目标表名，为空时与源表同名
+    #[serde(default)]
+    pub target_table: String,
+    // 需要采集的列，为空时采集全部列
+    #[serde(default)]
+    pub columns: Vec<String>,
+    // 增量采集的水位列（自增ID或更新时间），需单调递增
+    #[serde(default)]
+    pub watermark_column: String,
+}
+
+impl TableRule {
+    pub fn target_table(&self) -> &str {
+        if self.target_table.is_empty() { &self.source_table } else { &self.target_table }
+    }
+}
+
+// 数据库类采集任务的 rule 字段
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DatabaseCollectRule {
+    pub tables: Vec<TableRule>,
+    // 每批读取/写入的行数
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    // 目标库名，为空时使用资源配置中的第一个库
+    #[serde(default)]
+    pub target_database: String,
+    // 全量采集前是否清空目标表
+    #[serde(default = "default_true")]
+    pub truncate_target: bool,
+    // 目标为 Doris 时建表使用的副本数
+    #[serde(default = "default_replication_num")]
+    pub doris_replication_num: u32,
+}
+
+impl DatabaseCollectRule {
+    pub fn parse(rule: &serde_json::Value) -> Result<Self, Error> {
+        let rule = serde_json::from_value::<Self>(rule.clone())
+            .map_err(|e| Error::InvalidValue(format!("invalid collection rule: {}", e)))?;
+        if rule.tables.is_empty() {
+            return Err(Error::EmptyValue("rule.tables".to_string()));
+        }
+        if rule.tables.iter().any(|t| t.source_table.is_empty()) {
+            return Err(Error::EmptyValue("rule.tables.source_table".to_string()));
+        }
+        if rule.tables.iter().any(|t| t.watermark_column.len() > 64) {
+            return Err(Error::InvalidValue("rule.tables.watermark_column is too long".to_string()));
+        }
+        if rule.batch_size == 0 {
+            return Err(Error::InvalidValue("rule.batch_size must be greater than 0".to_string()));
+        }
+        Ok(rule)
+    }
+}
+
+fn default_flush_interval_ms() -> u64 {
+    1000
+}
+
+// 实时采集（CDC）任务的 rule 字段
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StreamCollectRule {
+    // 只使用 source_table、target_table、columns
+    pub tables: Vec<TableRule>,
+    // 目标为数据库时的库名，为空时使用资源配置中的第一个库
+    #[serde(default)]
+    pub target_database: String,
+    // 目标为 Kafka 时的 topic，为空时按 "库名.表名" 生成
+    #[serde(default)]
+    pub topic: String,
+    // 伪装为从库时使用的 server_id，为空时按任务ID生成
+    #[serde(default)]
+    pub server_id: Option<u32>,
+    // 是否按 GTID 定位，否则按 binlog 文件名与位置定位
+    #[serde(default)]
+    pub use_gtid: bool,
+    // PostgreSQL 逻辑复制使用的复制槽与发布名，为空时按任务ID生成
+    #[serde(default)]
+    pub slot_name: String,
+    // 缓冲的变更达到该数量时立即写出
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    // 缓冲的变更最长等待时间
+    #[serde(default = "default_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+    #[serde(default = "default_replication_num")]
+    pub doris_replication_num: u32,
+}
+
+impl StreamCollectRule {
+    pub fn parse(rule: &serde_json::Value) -> Result<Self, Error> {
+        let rule = serde_json::from_value::<Self>(rule.clone())
+            .map_err(|e| Error::InvalidValue(format!("invalid collection rule: {}", e)))?;
+        if rule.tables.is_empty() {
+            return Err(Error::EmptyValue("rule.tables".to_string()));
+        }
+        if rule.tables.iter().any(|t| t.source_table.is_empty()) {
+            return Err(Error::EmptyValue("rule.tables.source_table".to_string()));
+        }
+        if rule.batch_size == 0 {
+            return Err(Error::InvalidValue("rule.batch_size must be greater than 0".to_string()));
+        }
+        if rule.flush_interval_ms == 0 {
+            return Err(Error::InvalidValue("rule.flush_interval_ms must be greater than 0".to_string()));
+        }
+        if rule.server_id == Some(0) {
+            return Err(Error::InvalidValue("rule.server_id must be greater than 0".to_string()));
+        }
+        // 复制槽名只能包含小写字母、数字和下划线
+        if rule.slot_name.len() > 63
+            || !rule.slot_name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(Error::InvalidValue(
+                "rule.slot_name must be at most 63 lowercase letters, digits or underscores".to_string(),
+            ));
+        }
+        Ok(rule)
+    }
+}
+
+// 实时采集的消费位点，checkpoint 的结构由具体的日志读取器决定
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct CollectionCheckpoint {
+    pub task_id: String,
+    #[sqlx(json)]
+    pub checkpoint: serde_json::Value,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+// Web 对外接口使用的只读位点模型
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CollectionCheckpointReadOnly {
+    pub checkpoint: serde_json::Value,
+    pub updated_at: String,
+}
+
+impl From<CollectionCheckpoint> for CollectionCheckpointReadOnly {
+    fn from(checkpoint: CollectionCheckpoint) -> Self {
+        Self {
+            checkpoint: checkpoint.checkpoint,
+            updated_at: checkpoint.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+// 增量采集水位值，按列类型保存以便原样绑定回查询条件
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum WatermarkValue {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+    Date(chrono::NaiveDate),
+    DateTime(chrono::NaiveDateTime),
+    Timestamp(chrono::DateTime<chrono::Utc>),
+}
+
+// 采集任务在单张源表上的水位
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct CollectionWatermark {
+    pub task_id: String,
+    pub source_table: String,
+    pub watermark_column: String,
+    #[sqlx(json)]
+    pub watermark: WatermarkValue,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+// Web 对外接口使用的只读水位模型
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CollectionWatermarkReadOnly {
+    pub source_table: String,
+    pub watermark_column: String,
+    pub watermark: serde_json::Value,
+    pub updated_at: String,
+}
+
+impl From<CollectionWatermark> for CollectionWatermarkReadOnly {
+    fn from(watermark: CollectionWatermark) -> Self {
+        Self {
+            source_table: watermark.source_table,
+            watermark_column: watermark.watermark_column,
+            watermark: serde_json::to_value(&watermark.watermark).unwrap_or_default(),
+            updated_at: watermark.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+// 重置水位的查询参数，未指定源表时重置任务的全部水位
+#[derive(Debug, Deserialize, Default)]
+pub struct WatermarkResetQuery {
+    pub source_table: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::utils::migration::MIGRATIONS;
+
+    #[test]
+    fn stream_rule_round_trip() {
+        let raw = serde_json::json!({
+            "collect_type": "stream",
+            "rule": {"tables": [{"source_table": "orders"}], "use_gtid": true},
+        });
+        let collect_type = serde_json::from_value::<CollectType>(raw["collect_type"].clone()).unwrap();
+        assert_eq!(collect_type, CollectType::Stream);
+        assert_eq!(CollectType::from_str(&collect_type.to_string()).unwrap(), CollectType::Stream);
+
+        let rule = StreamCollectRule::parse(&raw["rule"]).unwrap();
+        let rule = StreamCollectRule::parse(&serde_json::to_value(&rule).unwrap()).unwrap();
+        assert_eq!(rule.tables[0].source_table, "orders");
+        assert!(rule.use_gtid);
+    }
+
+    #[test]
+    fn collect_type_enum_covers_all_variants() {
+        // 以最后一次修改 collect_type 的迁移脚本为准
+        let definition = MIGRATIONS
+            .iter()
+            .rev()
+            .flat_map(|m| m.sql.lines())
+            .find(|line| line.contains("collect_type") && line.contains("enum("))
+            .expect("collect_type enum definition");
+        for variant in [CollectType::Full, CollectType::Incremental, CollectType::Stream] {
+            assert!(definition.contains(&format!("'{}'", variant)), "missing {} in {}", variant, definition);
+        }
+    }
+}
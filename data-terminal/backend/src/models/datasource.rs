@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use crate::models::Validator;
@@ -59,6 +60,17 @@ impl Default for ConnectionStatus {
     }
 }
 
+impl From<&PingDiagnostic> for ConnectionStatus {
+    fn from(diagnostic: &PingDiagnostic) -> Self {
+        match diagnostic.error_kind {
+            None if diagnostic.success => Self::Connected,
+            // 网络不可达视为断开，其余（认证、配置、协议等）视为错误
+            Some(PingErrorKind::Dns) | Some(PingErrorKind::Connection) | Some(PingErrorKind::Timeout) => Self::Disconnected,
+            _ => Self::Error,
+        }
+    }
+}
+
 impl_sqlx_for_string_enum!(ConnectionStatus);
 
 // 内部使用的完整 DataSource 模型
@@ -77,6 +89,10 @@ pub struct DataSource {
     #[serde(default)]
     pub connection_status: ConnectionStatus,
     #[serde(default)]
+    pub last_checked_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub last_error: String,
+    #[serde(default)]
     pub created_at: chrono::DateTime<chrono::Utc>,
     #[serde(default)]
     pub updated_at: chrono::DateTime<chrono::Utc>,
@@ -117,6 +133,8 @@ pub struct DataSourceReadOnly {
     pub datasource_type: DataSourceType,
     pub connection_config: serde_json::Value,
     pub connection_status: ConnectionStatus,
+    pub last_checked_at: String,
+    pub last_error: String,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -142,6 +160,8 @@ impl From<DataSourceCreateUpdate> for DataSource {
             datasource_type: datasource.datasource_type,
             connection_config: datasource.connection_config,
             connection_status: ConnectionStatus::Disconnected,
+            last_checked_at: None,
+            last_error: String::new(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         }
@@ -158,8 +178,130 @@ impl From<DataSource> for DataSourceReadOnly {
             datasource_type: datasource.datasource_type,
             connection_config: datasource.connection_config,
             connection_status: datasource.connection_status,
+            last_checked_at: datasource
+                .last_checked_at
+                .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default(),
+            last_error: datasource.last_error,
             created_at: datasource.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
             updated_at: datasource.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
         }
     }
 }
+
+// MySQL 数据源连接配置（与前端 MysqlConfig 字段保持一致）
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct MysqlConnectionConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    pub database: String,
+}
+
+// PostgreSQL 数据源连接配置（catalog 对应 schema）
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PostgresConnectionConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    pub catalog: String,
+    pub database: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiHttpMethod {
+    #[default]
+    Get,
+    Post,
+    Put,
+    Delete,
+    Patch,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiBodyType {
+    #[default]
+    None,
+    Json,
+    FormData,
+}
+
+// 查询 API 数据源连接配置
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct QueryApiConnectionConfig {
+    pub method: ApiHttpMethod,
+    pub domain: String,
+    pub path: String,
+    pub path_params: HashMap<String, String>,
+    pub query_params: HashMap<String, String>,
+    pub headers: HashMap<String, String>,
+    pub body_type: ApiBodyType,
+    pub body_content: String,
+    pub response_schema: String,
+}
+
+// 订阅 API 数据源连接配置（仅包含探测所需字段）
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SubscribeApiConnectionConfig {
+    pub path: String,
+    pub webhook_url: String,
+    pub timeout_seconds: u32,
+    pub tokens: Vec<serde_json::Value>,
+}
+
+// 连接探测失败的原因分类
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PingErrorKind {
+    InvalidConfig,
+    Dns,
+    Connection,
+    Auth,
+    Tls,
+    Timeout,
+    Database,
+    Http,
+    Schema,
+    Unknown,
+}
+
+// 连接探测结果
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct PingDiagnostic {
+    pub success: bool,
+    pub latency_ms: u64,
+    pub server_version: Option<String>,
+    pub error_kind: Option<PingErrorKind>,
+    pub message: String,
+}
+
+impl PingDiagnostic {
+    pub fn ok(latency_ms: u64, server_version: Option<String>, message: impl Into<String>) -> Self {
+        Self {
+            success: true,
+            latency_ms,
+            server_version,
+            error_kind: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn failed(error_kind: PingErrorKind, latency_ms: u64, message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            latency_ms,
+            server_version: None,
+            error_kind: Some(error_kind),
+            message: message.into(),
+        }
+    }
+}
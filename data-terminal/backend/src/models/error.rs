@@ -55,7 +55,43 @@ pub enum Error {
     JsonSerializationError(String),
 
     #[error("internal error: {0}")]
-    InternalError(String)
+    InternalError(String),
+
+    #[error("username or password is incorrect")]
+    InvalidCredentials,
+
+    #[error("account is disabled")]
+    AccountDisabled,
+
+    #[error("account is locked until {0}")]
+    AccountLocked(String),
+
+    #[error("permission denied: {0}")]
+    PermissionDenied(String),
+
+    #[error("session is expired or revoked")]
+    InvalidSession,
+
+    #[error("refresh token was just rotated by a concurrent request")]
+    RefreshConflict,
+}
+
+impl Error {
+    /// 错误链：本错误及其底层原因，由外到内
+    pub fn chain(&self) -> Vec<String> {
+        let mut chain = vec![self.to_string()];
+        if let Error::DbError(inner) = self {
+            let mut source: Option<&dyn std::error::Error> = Some(inner);
+            while let Some(e) = source {
+                let message = e.to_string();
+                if chain.last() != Some(&message) {
+                    chain.push(message);
+                }
+                source = e.source();
+            }
+        }
+        chain
+    }
 }
 
 impl PartialEq for Error {
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use crate::models::catalog::CatalogSourceType;
+use crate::models::task_run::TaskType;
+
+// 血缘图中的一个节点：数据源或资源上的一张表或一列，column_name 为空时表示整张表
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq, Hash)]
+pub struct LineageNode {
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+    // 数据源或资源名称，仅用于展示
+    #[serde(default)]
+    pub source_name: String,
+    pub schema_name: String,
+    pub table_name: String,
+    #[serde(default)]
+    pub column_name: String,
+}
+
+impl LineageNode {
+    // 比较用的键，库表列名不区分大小写
+    pub fn key(&self) -> (CatalogSourceType, String, String, String, String) {
+        (
+            self.source_type,
+            self.source_id.clone(),
+            self.schema_name.to_lowercase(),
+            self.table_name.to_lowercase(),
+            self.column_name.to_lowercase(),
+        )
+    }
+
+    // 查询节点是否落在血缘边的一端；任意一方为表级节点时按表匹配
+    pub fn matches(&self, other: &LineageNode) -> bool {
+        self.source_type == other.source_type
+            && self.source_id == other.source_id
+            && self.schema_name.eq_ignore_ascii_case(&other.schema_name)
+            && self.table_name.eq_ignore_ascii_case(&other.table_name)
+            && (self.column_name.is_empty() || other.column_name.is_empty() || self.column_name.eq_ignore_ascii_case(&other.column_name))
+    }
+}
+
+// 一条血缘边：source 端的列经由任务写入 target 端的列
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct LineageEdge {
+    pub id: String,
+    pub task_type: TaskType,
+    pub task_id: String,
+    pub task_name: String,
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+    pub source_schema: String,
+    pub source_table: String,
+    pub source_column: String,
+    pub target_type: CatalogSourceType,
+    pub target_id: String,
+    pub target_schema: String,
+    pub target_table: String,
+    pub target_column: String,
+    // 目标列的计算表达式，直接映射时为空
+    pub expression: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl LineageEdge {
+    pub fn source_node(&self) -> LineageNode {
+        LineageNode {
+            source_type: self.source_type,
+            source_id: self.source_id.clone(),
+            source_name: String::new(),
+            schema_name: self.source_schema.clone(),
+            table_name: self.source_table.clone(),
+            column_name: self.source_column.clone(),
+        }
+    }
+
+    pub fn target_node(&self) -> LineageNode {
+        LineageNode {
+            source_type: self.target_type,
+            source_id: self.target_id.clone(),
+            source_name: String::new(),
+            schema_name: self.target_schema.clone(),
+            table_name: self.target_table.clone(),
+            column_name: self.target_column.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LineageDirection {
+    Upstream,
+    Downstream,
+}
+
+// 从一张表或一列出发追溯血缘，column_name 为空时按表追溯
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct LineageQuery {
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+    pub schema_name: String,
+    pub table_name: String,
+    pub column_name: Option<String>,
+    // 追溯的层数，默认 10
+    pub depth: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct LineageTaskQuery {
+    pub task_type: TaskType,
+    pub task_id: String,
+}
+
+// 追溯结果，nodes 包含起点与各条边两端的节点
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct LineageGraph {
+    pub root: LineageNode,
+    pub nodes: Vec<LineageNode>,
+    pub edges: Vec<LineageEdge>,
+}
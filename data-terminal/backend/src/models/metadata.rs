@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+// 库/schema 下的表概要，approx_rows 为统计信息中的估算行数，视图或未统计时为空
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TableSummary {
+    pub name: String,
+    // table 或 view
+    pub table_type: String,
+    pub comment: String,
+    pub approx_rows: Option<i64>,
+}
+
+// MySQL 的库或 PostgreSQL 的 schema
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SchemaMeta {
+    pub name: String,
+    pub tables: Vec<TableSummary>,
+}
+
+// 数据源的库表目录
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DataSourceCatalog {
+    pub datasource_id: String,
+    // 连接配置中的库
+    pub database: String,
+    // 采集任务读取源表时使用的库（MySQL）或 schema（PostgreSQL）
+    pub default_schema: String,
+    pub schemas: Vec<SchemaMeta>,
+    pub refreshed_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ColumnInfo {
+    pub name: String,
+    // 完整类型，例如 varchar(64)、numeric(10,2)
+    pub column_type: String,
+    // 基础类型，例如 varchar、numeric
+    pub data_type: String,
+    pub nullable: bool,
+    pub default_value: Option<String>,
+    pub comment: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IndexInfo {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub unique: bool,
+    pub primary: bool,
+}
+
+// 表结构详情
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TableDetail {
+    pub schema: String,
+    pub name: String,
+    pub table_type: String,
+    pub comment: String,
+    pub approx_rows: Option<i64>,
+    pub columns: Vec<ColumnInfo>,
+    pub primary_key: Vec<String>,
+    pub indexes: Vec<IndexInfo>,
+    pub refreshed_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct CatalogQuery {
+    // 为 true 时忽略缓存重新读取
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TableDetailQuery {
+    pub schema: String,
+    pub table: String,
+    #[serde(default)]
+    pub refresh: bool,
+}
@@ -3,10 +3,45 @@ pub mod error;
 pub mod project;
 pub mod datasource;
 pub mod resource;
+pub mod collection;
+pub mod task_run;
+pub mod schedule;
+pub mod pipeline;
+pub mod transform;
+pub mod query;
+pub mod metadata;
+pub mod catalog;
+pub mod lineage;
+pub mod user;
+pub mod auth;
 pub mod web;
 
 pub use error::Error;
 
 pub trait Validator {
     fn validate(&self) -> Result<(), Error>;
-}
\ No newline at end of file
+}
+// 只读接口中替代敏感配置的占位符
+pub const SECRET_MASK: &str = "******";
+
+// 连接配置中视为敏感信息的字段名（小写包含匹配）
+const SECRET_KEYS: &[&str] = &["password", "secret", "token", "authorization", "api_key", "apikey"];
+
+/// 隐藏连接配置中的密码、token 等敏感字段，字段结构保持不变
+pub fn mask_secrets(value: &mut serde_json::Value) {
+    mask_value(value, false);
+}
+
+fn mask_value(value: &mut serde_json::Value, secret: bool) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                let key = key.to_lowercase();
+                mask_value(v, secret || SECRET_KEYS.iter().any(|k| key.contains(k)));
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|v| mask_value(v, secret)),
+        serde_json::Value::String(s) if secret && !s.is_empty() => *s = SECRET_MASK.to_string(),
+        _ => {}
+    }
+}
@@ -0,0 +1,422 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use crate::models::Validator;
+use crate::models::Error;
+use crate::models::task_run::{TaskRunReadOnly, TaskType};
+
+use crate::impl_sqlx_for_string_enum;
+
+
+// 流水线运行中节点的状态
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum NodeStatus {
+    Pending,
+    Running,
+    Success,
+    Failed,
+    // 上游节点失败或跳过，本节点不再执行
+    Skipped,
+    Cancelled,
+}
+
+impl Default for NodeStatus {
+    fn default() -> Self {
+        Self::Pending
+    }
+}
+
+impl_sqlx_for_string_enum!(NodeStatus);
+
+fn default_retry_interval() -> u64 {
+    60
+}
+
+fn default_max_parallel() -> i32 {
+    4
+}
+
+// 流水线中的任务节点
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PipelineNode {
+    // 节点ID，在流水线内唯一
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    pub task_type: TaskType,
+    pub task_id: String,
+    // 失败后的重试次数
+    #[serde(default)]
+    pub retries: u32,
+    #[serde(default = "default_retry_interval")]
+    pub retry_interval_secs: u64,
+    // 设计器画布中的位置
+    #[serde(default)]
+    pub x: f64,
+    #[serde(default)]
+    pub y: f64,
+}
+
+// 依赖边：from 节点成功后才执行 to 节点
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PipelineEdge {
+    pub from: String,
+    pub to: String,
+}
+
+// 内部使用的完整流水线模型
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct Pipeline {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[sqlx(json)]
+    pub nodes: Vec<PipelineNode>,
+    #[sqlx(json)]
+    pub edges: Vec<PipelineEdge>,
+    pub max_parallel: i32,
+    #[serde(default)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Validator for Pipeline {
+    fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() {
+            return Err(Error::EmptyValue("id".to_string()));
+        }
+        if self.name.is_empty() {
+            return Err(Error::EmptyValue("name".to_string()));
+        }
+        if self.name.len() > 64 {
+            return Err(Error::InvalidValue("name length must be less than 64 characters".to_string()));
+        }
+        if self.description.len() > 255 {
+            return Err(Error::InvalidValue("description length must be less than 255 characters".to_string()));
+        }
+        if !(1..=32).contains(&self.max_parallel) {
+            return Err(Error::InvalidValue("max_parallel must be between 1 and 32".to_string()));
+        }
+        if self.nodes.is_empty() {
+            return Err(Error::EmptyValue("nodes".to_string()));
+        }
+
+        let mut node_ids = HashSet::new();
+        let mut tasks = HashSet::new();
+        for node in &self.nodes {
+            if node.id.is_empty() {
+                return Err(Error::EmptyValue("nodes.id".to_string()));
+            }
+            if node.id.len() > 64 {
+                return Err(Error::InvalidValue(format!("node id {} must be less than 64 characters", node.id)));
+            }
+            if !node_ids.insert(node.id.as_str()) {
+                return Err(Error::InvalidValue(format!("duplicate node id {}", node.id)));
+            }
+            if node.task_id.is_empty() {
+                return Err(Error::EmptyValue(format!("nodes.{}.task_id", node.id)));
+            }
+            // 同一任务不能并发运行，也没有在一次运行中重复执行的意义
+            if !tasks.insert((node.task_type, node.task_id.as_str())) {
+                return Err(Error::InvalidValue(format!("task {} appears in more than one node", node.task_id)));
+            }
+            if node.retries > 10 {
+                return Err(Error::InvalidValue(format!("retries of node {} must be at most 10", node.id)));
+            }
+            if node.retry_interval_secs > 3600 {
+                return Err(Error::InvalidValue(format!("retry interval of node {} must be at most 3600 seconds", node.id)));
+            }
+        }
+
+        let mut edges = HashSet::new();
+        for edge in &self.edges {
+            for end in [&edge.from, &edge.to] {
+                if !node_ids.contains(end.as_str()) {
+                    return Err(Error::InvalidValue(format!("edge references unknown node {}", end)));
+                }
+            }
+            if edge.from == edge.to {
+                return Err(Error::InvalidValue(format!("node {} cannot depend on itself", edge.from)));
+            }
+            if !edges.insert(edge) {
+                return Err(Error::InvalidValue(format!("duplicate edge {} -> {}", edge.from, edge.to)));
+            }
+        }
+
+        self.topo_order().map(|_| ())
+    }
+}
+
+impl Pipeline {
+    // 节点ID -> 节点下标
+    fn node_index(&self) -> HashMap<&str, usize> {
+        self.nodes.iter().enumerate().map(|(i, n)| (n.id.as_str(), i)).collect()
+    }
+
+    /// 每个节点的上游节点下标
+    pub fn upstream(&self) -> Vec<Vec<usize>> {
+        let index = self.node_index();
+        let mut upstream = vec![Vec::new(); self.nodes.len()];
+        for edge in &self.edges {
+            if let (Some(&from), Some(&to)) = (index.get(edge.from.as_str()), index.get(edge.to.as_str())) {
+                upstream[to].push(from);
+            }
+        }
+        upstream
+    }
+
+    /// 每个节点的下游节点下标
+    pub fn downstream(&self) -> Vec<Vec<usize>> {
+        let index = self.node_index();
+        let mut downstream = vec![Vec::new(); self.nodes.len()];
+        for edge in &self.edges {
+            if let (Some(&from), Some(&to)) = (index.get(edge.from.as_str()), index.get(edge.to.as_str())) {
+                downstream[from].push(to);
+            }
+        }
+        downstream
+    }
+
+    /// 节点的拓扑顺序，存在环时返回环上的节点
+    pub fn topo_order(&self) -> Result<Vec<usize>, Error> {
+        let downstream = self.downstream();
+        // 0 未访问，1 访问中，2 已完成
+        let mut state = vec![0u8; self.nodes.len()];
+        let mut path = Vec::new();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        for start in 0..self.nodes.len() {
+            if state[start] != 0 {
+                continue;
+            }
+            if let Some(cycle) = visit(start, &downstream, &mut state, &mut path, &mut order) {
+                let names: Vec<&str> = cycle.iter().map(|&i| self.nodes[i].id.as_str()).collect();
+                return Err(Error::InvalidValue(format!("pipeline has a dependency cycle: {}", names.join(" -> "))));
+            }
+        }
+        order.reverse();
+        Ok(order)
+    }
+}
+
+// 深度优先遍历，后序加入 order；遇到访问中的节点即找到环，返回环上的路径
+fn visit(node: usize, downstream: &[Vec<usize>], state: &mut [u8], path: &mut Vec<usize>, order: &mut Vec<usize>) -> Option<Vec<usize>> {
+    state[node] = 1;
+    path.push(node);
+    for &next in &downstream[node] {
+        match state[next] {
+            0 => {
+                if let Some(cycle) = visit(next, downstream, state, path, order) {
+                    return Some(cycle);
+                }
+            }
+            1 => {
+                let start = path.iter().position(|&n| n == next).unwrap_or(0);
+                let mut cycle = path[start..].to_vec();
+                cycle.push(next);
+                return Some(cycle);
+            }
+            _ => {}
+        }
+    }
+    path.pop();
+    state[node] = 2;
+    order.push(node);
+    None
+}
+
+// Web 对外接口使用的只读流水线模型
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct PipelineReadOnly {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub nodes: Vec<PipelineNode>,
+    pub edges: Vec<PipelineEdge>,
+    pub max_parallel: i32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<Pipeline> for PipelineReadOnly {
+    fn from(pipeline: Pipeline) -> Self {
+        Self {
+            id: pipeline.id,
+            name: pipeline.name,
+            description: pipeline.description,
+            nodes: pipeline.nodes,
+            edges: pipeline.edges,
+            max_parallel: pipeline.max_parallel,
+            created_at: pipeline.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            updated_at: pipeline.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+// 用于创建和更新的流水线模型
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct PipelineCreateUpdate {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub nodes: Vec<PipelineNode>,
+    #[serde(default)]
+    pub edges: Vec<PipelineEdge>,
+    #[serde(default = "default_max_parallel")]
+    pub max_parallel: i32,
+}
+
+impl From<PipelineCreateUpdate> for Pipeline {
+    fn from(pipeline: PipelineCreateUpdate) -> Self {
+        Self {
+            id: pipeline.id,
+            name: pipeline.name,
+            description: pipeline.description,
+            nodes: pipeline.nodes,
+            edges: pipeline.edges,
+            max_parallel: pipeline.max_parallel,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+}
+
+// 一次流水线运行中单个节点的状态
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct PipelineNodeRun {
+    pub run_id: String,
+    pub node_id: String,
+    pub task_type: TaskType,
+    pub task_id: String,
+    pub status: NodeStatus,
+    pub attempts: i32,
+    pub task_run_id: Option<String>,
+    pub error_message: Option<String>,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl PipelineNodeRun {
+    pub fn pending(run_id: &str, node: &PipelineNode) -> Self {
+        Self {
+            run_id: run_id.to_string(),
+            node_id: node.id.clone(),
+            task_type: node.task_type,
+            task_id: node.task_id.clone(),
+            status: NodeStatus::Pending,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct PipelineNodeRunReadOnly {
+    pub node_id: String,
+    pub task_type: TaskType,
+    pub task_id: String,
+    pub status: NodeStatus,
+    pub attempts: i32,
+    pub task_run_id: String,
+    pub error_message: String,
+    pub started_at: String,
+    pub finished_at: String,
+}
+
+impl From<PipelineNodeRun> for PipelineNodeRunReadOnly {
+    fn from(run: PipelineNodeRun) -> Self {
+        let format = |t: Option<chrono::DateTime<chrono::Utc>>| t.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default();
+        Self {
+            node_id: run.node_id,
+            task_type: run.task_type,
+            task_id: run.task_id,
+            status: run.status,
+            attempts: run.attempts,
+            task_run_id: run.task_run_id.unwrap_or_default(),
+            error_message: run.error_message.unwrap_or_default(),
+            started_at: format(run.started_at),
+            finished_at: format(run.finished_at),
+        }
+    }
+}
+
+// 流水线运行详情：运行记录及各节点状态
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct PipelineRunDetail {
+    pub run: TaskRunReadOnly,
+    pub nodes: Vec<PipelineNodeRunReadOnly>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipeline(nodes: &[&str], edges: &[(&str, &str)]) -> Pipeline {
+        Pipeline {
+            id: "p1".to_string(),
+            name: "pipeline".to_string(),
+            nodes: nodes
+                .iter()
+                .map(|id| PipelineNode { id: id.to_string(), task_id: format!("task_{}", id), ..Default::default() })
+                .collect(),
+            edges: edges.iter().map(|(from, to)| PipelineEdge { from: from.to_string(), to: to.to_string() }).collect(),
+            max_parallel: 4,
+            ..Default::default()
+        }
+    }
+
+    fn position(order: &[usize], node: usize) -> usize {
+        order.iter().position(|&n| n == node).unwrap()
+    }
+
+    #[test]
+    fn topo_order_respects_edges() {
+        let p = pipeline(&["a", "b", "c", "d"], &[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d")]);
+        p.validate().unwrap();
+        let order = p.topo_order().unwrap();
+        assert_eq!(order.len(), 4);
+        assert!(position(&order, 0) < position(&order, 1));
+        assert!(position(&order, 0) < position(&order, 2));
+        assert!(position(&order, 1) < position(&order, 3));
+        assert!(position(&order, 2) < position(&order, 3));
+    }
+
+    #[test]
+    fn cycle_is_rejected_with_path() {
+        let p = pipeline(&["a", "b", "c"], &[("a", "b"), ("b", "c"), ("c", "a")]);
+        let err = p.validate().unwrap_err().to_string();
+        assert!(err.contains("dependency cycle"), "{}", err);
+        assert!(err.contains("a -> b -> c -> a"), "{}", err);
+    }
+
+    #[test]
+    fn self_loop_is_rejected() {
+        let p = pipeline(&["a", "b"], &[("a", "b"), ("b", "b")]);
+        let err = p.validate().unwrap_err().to_string();
+        assert!(err.contains("cannot depend on itself"), "{}", err);
+    }
+
+    #[test]
+    fn disconnected_nodes_start_immediately() {
+        // 与调度时的初始就绪队列一致：没有上游的节点都可以直接运行
+        let p = pipeline(&["a", "b", "c", "d"], &[("a", "b")]);
+        p.validate().unwrap();
+        assert_eq!(p.topo_order().unwrap().len(), 4);
+        let ready: Vec<usize> = p.upstream().iter().enumerate().filter(|(_, u)| u.is_empty()).map(|(i, _)| i).collect();
+        assert_eq!(ready, vec![0, 2, 3]);
+        assert_eq!(p.downstream()[0], vec![1]);
+        assert!(p.downstream()[3].is_empty());
+    }
+
+    #[test]
+    fn edge_to_unknown_node_is_rejected() {
+        let p = pipeline(&["a"], &[("a", "missing")]);
+        let err = p.validate().unwrap_err().to_string();
+        assert!(err.contains("unknown node missing"), "{}", err);
+    }
+}
@@ -6,6 +6,7 @@ use regex::Regex;
 
 use crate::models::Validator;
 use crate::models::Error;
+use crate::utils::database::is_reserved_project_code;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString, sqlx::Type)]
 #[strum(serialize_all = "snake_case")]
@@ -15,6 +16,8 @@ pub enum CreateStatus {
     Running,
     Success,
     Fail,
+    // 删除中，中断后按记录的删除方式继续
+    Deleting,
 }
 
 impl Default for CreateStatus {
@@ -40,6 +43,17 @@ pub struct Project {
     pub created_at: chrono::DateTime<chrono::Utc>,
     #[serde(default)]
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    // 当前用户在项目中的角色，仅在项目列表中返回
+    #[sqlx(default)]
+    #[serde(default)]
+    pub role: Option<ProjectRole>,
+    // 删除中的项目记录的删除方式与归档库名
+    #[sqlx(default)]
+    #[serde(skip)]
+    pub delete_mode: Option<DeleteMode>,
+    #[sqlx(default)]
+    #[serde(skip)]
+    pub archive_db: String,
 }
 
 impl Validator for Project {
@@ -52,6 +66,10 @@ impl Validator for Project {
         if !re.is_match(&self.code) {
             return Err(Error::InvalidProjectCode("code".to_string()));
         }
+        // 拼接后指向配置库或模版库的编码不可用
+        if is_reserved_project_code(&self.code) {
+            return Err(Error::InvalidProjectCode(self.code.clone()));
+        }
 
 
         if self.name.is_empty() {
@@ -60,3 +78,77 @@ impl Validator for Project {
         Ok(())
     }
 }
+
+
+// 删除项目时对项目库的处理方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString, sqlx::Type)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum DeleteMode {
+    // 直接删除项目库
+    Drop,
+    // 迁移到归档库，保留期过后删除
+    Archive,
+}
+
+impl Default for DeleteMode {
+    fn default() -> Self {
+        Self::Archive
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct ProjectDeleteQuery {
+    #[serde(default)]
+    pub mode: DeleteMode,
+    pub retention_days: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct ProjectArchive {
+    pub archive_db: String,
+    pub code: String,
+    pub archived_at: chrono::DateTime<chrono::Utc>,
+    pub expire_at: chrono::DateTime<chrono::Utc>,
+}
+
+
+// 项目角色，权限按 viewer < developer < owner 递增
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, strum::Display, strum::EnumString, sqlx::Type)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum ProjectRole {
+    // 只读查看项目内的配置、运行记录，执行只读查询
+    Viewer,
+    // 维护数据源、任务、调度并运行任务
+    Developer,
+    // 删除数据源和项目，管理成员
+    Owner,
+}
+
+impl Default for ProjectRole {
+    fn default() -> Self {
+        Self::Viewer
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct ProjectMember {
+    pub project_code: String,
+    pub user_id: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub display_name: String,
+    pub role: ProjectRole,
+    #[serde(default)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct ProjectMemberForm {
+    pub user_id: String,
+    pub role: ProjectRole,
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use crate::models::Validator;
+use crate::models::Error;
+use crate::models::task_run::RunStatus;
+
+use crate::impl_sqlx_for_string_enum;
+
+// 默认与最大返回行数
+pub const DEFAULT_MAX_ROWS: u32 = 1000;
+pub const LIMIT_MAX_ROWS: u32 = 10000;
+// 默认与最长语句超时（秒）
+pub const DEFAULT_TIMEOUT_SECS: u32 = 30;
+pub const LIMIT_TIMEOUT_SECS: u32 = 600;
+
+// 查询对象：项目数据源或数据库类资源
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum QueryTargetType {
+    Datasource,
+    Resource,
+}
+
+impl Default for QueryTargetType {
+    fn default() -> Self {
+        Self::Datasource
+    }
+}
+
+impl_sqlx_for_string_enum!(QueryTargetType);
+
+// 执行查询的请求
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct QueryRequest {
+    // 执行前通过 /query/reserve 申请，用于取消正在执行的查询；为空时由后端生成
+    #[serde(default)]
+    pub query_id: String,
+    pub target_type: QueryTargetType,
+    pub target_id: String,
+    pub sql: String,
+    #[serde(default)]
+    pub max_rows: Option<u32>,
+    #[serde(default)]
+    pub timeout_secs: Option<u32>,
+}
+
+impl Validator for QueryRequest {
+    fn validate(&self) -> Result<(), Error> {
+        if self.target_id.is_empty() {
+            return Err(Error::EmptyValue("target_id".to_string()));
+        }
+        if self.sql.trim().is_empty() {
+            return Err(Error::EmptyValue("sql".to_string()));
+        }
+        if self.max_rows.is_some_and(|n| n == 0 || n > LIMIT_MAX_ROWS) {
+            return Err(Error::InvalidValue(format!("max_rows must be between 1 and {}", LIMIT_MAX_ROWS)));
+        }
+        if self.timeout_secs.is_some_and(|n| n == 0 || n > LIMIT_TIMEOUT_SECS) {
+            return Err(Error::InvalidValue(format!("timeout_secs must be between 1 and {}", LIMIT_TIMEOUT_SECS)));
+        }
+        Ok(())
+    }
+}
+
+// 结果列，type_name 为数据库返回的列类型，例如 BIGINT、VARCHAR、INT8
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct QueryColumn {
+    pub name: String,
+    pub type_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct QueryResult {
+    pub query_id: String,
+    pub columns: Vec<QueryColumn>,
+    // 按列顺序排列的单元格，decimal 与时间类型以字符串表示
+    pub rows: Vec<Vec<serde_json::Value>>,
+    // 结果超过行数上限，只返回了前 max_rows 行
+    pub truncated: bool,
+    pub duration_ms: i64,
+}
+
+// 查询历史
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct QueryHistory {
+    pub id: String,
+    pub user_name: String,
+    pub target_type: QueryTargetType,
+    pub target_id: String,
+    pub sql_text: String,
+    pub status: RunStatus,
+    pub row_count: i32,
+    pub truncated: bool,
+    pub duration_ms: i64,
+    pub error_message: Option<String>,
+    pub executed_at: chrono::DateTime<chrono::Utc>,
+}
@@ -159,3 +159,50 @@ impl From<Resource> for ResourceReadOnly {
     }
 }
 
+// 数据库类资源（Mysql/Postgres/Doris）的连接配置，与前端 DatabaseConfigForm 字段一致
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DatabaseResourceConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    // 逗号分隔的库名
+    pub databases: String,
+}
+
+impl DatabaseResourceConfig {
+    pub fn default_database(&self) -> Option<String> {
+        self.databases
+            .split(',')
+            .map(|s| s.trim())
+            .find(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    }
+}
+
+// 消息队列类资源（Kafka）的连接配置，与前端 QueueConfigForm 字段一致
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct KafkaResourceConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub ssl_enabled: bool,
+    pub sasl_enabled: bool,
+    pub sasl_mechanism: Option<String>,
+}
+
+impl KafkaResourceConfig {
+    // host 可填写逗号分隔的多个地址，未带端口的使用 port
+    pub fn bootstrap_servers(&self) -> String {
+        self.host
+            .split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| if s.contains(':') { s.to_string() } else { format!("{}:{}", s, self.port) })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
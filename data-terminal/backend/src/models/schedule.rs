@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use crate::models::Validator;
+use crate::models::Error;
+use crate::models::task_run::TaskType;
+
+use crate::impl_sqlx_for_string_enum;
+
+
+// 服务停机等原因错过触发时间后的处理方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MisfirePolicy {
+    // 跳过错过的触发，等待下一次
+    Skip,
+    // 不论错过多少次，立即补跑一次
+    FireOnce,
+}
+
+impl Default for MisfirePolicy {
+    fn default() -> Self {
+        Self::Skip
+    }
+}
+
+impl_sqlx_for_string_enum!(MisfirePolicy);
+
+fn default_timezone() -> String {
+    "Asia/Shanghai".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// 内部使用的任务调度计划，每个任务最多一个
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct TaskSchedule {
+    pub task_id: String,
+    pub task_type: TaskType,
+    pub cron: String,
+    pub timezone: String,
+    pub misfire_policy: MisfirePolicy,
+    pub enabled: bool,
+    pub next_fire_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_fire_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Validator for TaskSchedule {
+    fn validate(&self) -> Result<(), Error> {
+        if self.task_id.is_empty() {
+            return Err(Error::EmptyValue("task_id".to_string()));
+        }
+        if self.cron.is_empty() {
+            return Err(Error::EmptyValue("cron".to_string()));
+        }
+        if self.cron.len() > 128 {
+            return Err(Error::InvalidValue("cron length must be less than 128 characters".to_string()));
+        }
+        if self.timezone.is_empty() {
+            return Err(Error::EmptyValue("timezone".to_string()));
+        }
+        if self.timezone.len() > 64 {
+            return Err(Error::InvalidValue("timezone length must be less than 64 characters".to_string()));
+        }
+        Ok(())
+    }
+}
+
+// Web 对外接口使用的只读调度计划，时间按调度时区展示
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct TaskScheduleReadOnly {
+    pub task_id: String,
+    pub task_type: TaskType,
+    pub cron: String,
+    pub timezone: String,
+    pub misfire_policy: MisfirePolicy,
+    pub enabled: bool,
+    pub next_fire_at: String,
+    pub last_fire_at: String,
+    // 接下来的若干次触发时间
+    pub next_fire_times: Vec<String>,
+    pub updated_at: String,
+}
+
+// 用于创建和更新的调度计划模型
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskScheduleCreateUpdate {
+    #[serde(default)]
+    pub task_type: TaskType,
+    pub cron: String,
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    #[serde(default)]
+    pub misfire_policy: MisfirePolicy,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+// 预览 cron 表达式触发时间的查询参数
+#[derive(Debug, Deserialize, Default)]
+pub struct SchedulePreviewQuery {
+    pub cron: String,
+    pub timezone: Option<String>,
+    pub count: Option<usize>,
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::impl_sqlx_for_string_enum;
+
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TaskType {
+    Collection,
+    Sync,
+    Transform,
+    Pipeline,
+}
+
+impl Default for TaskType {
+    fn default() -> Self {
+        Self::Collection
+    }
+}
+
+impl_sqlx_for_string_enum!(TaskType);
+
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    Running,
+    Success,
+    Failed,
+    Cancelled,
+}
+
+impl Default for RunStatus {
+    fn default() -> Self {
+        Self::Running
+    }
+}
+
+impl_sqlx_for_string_enum!(RunStatus);
+
+// 任务运行记录
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct TaskRun {
+    pub id: String,
+    pub task_id: String,
+    pub task_type: TaskType,
+    pub status: RunStatus,
+    pub rows_read: i64,
+    pub rows_written: i64,
+    pub bytes: i64,
+    pub duration_ms: i64,
+    pub error_message: Option<String>,
+    pub error_chain: Option<sqlx::types::Json<Vec<String>>>,
+    pub detail: Option<sqlx::types::Json<serde_json::Value>>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TaskRun {
+    pub fn start(task_id: String, task_type: TaskType) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            task_id,
+            task_type,
+            status: RunStatus::Running,
+            started_at: chrono::Utc::now(),
+            ..Default::default()
+        }
+    }
+}
+
+// Web 对外接口使用的只读运行记录
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct TaskRunReadOnly {
+    pub id: String,
+    pub task_id: String,
+    pub task_name: String,
+    pub task_type: TaskType,
+    pub status: RunStatus,
+    pub rows_read: i64,
+    pub rows_written: i64,
+    pub bytes: i64,
+    pub duration_ms: i64,
+    pub error_message: String,
+    pub error_chain: Vec<String>,
+    pub detail: serde_json::Value,
+    pub started_at: String,
+    pub finished_at: String,
+}
+
+impl From<TaskRun> for TaskRunReadOnly {
+    fn from(run: TaskRun) -> Self {
+        Self {
+            id: run.id,
+            task_id: run.task_id,
+            task_name: String::new(),
+            task_type: run.task_type,
+            status: run.status,
+            rows_read: run.rows_read,
+            rows_written: run.rows_written,
+            bytes: run.bytes,
+            duration_ms: run.duration_ms,
+            error_message: run.error_message.unwrap_or_default(),
+            error_chain: run.error_chain.map(|c| c.0).unwrap_or_default(),
+            detail: run.detail.map(|d| d.0).unwrap_or(serde_json::Value::Null),
+            started_at: run.started_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            finished_at: run
+                .finished_at
+                .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// 运行记录查询条件，时间格式为 %Y-%m-%d %H:%M:%S
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TaskRunQuery {
+    pub task_id: Option<String>,
+    pub task_type: Option<TaskType>,
+    pub status: Option<RunStatus>,
+    pub started_from: Option<String>,
+    pub started_to: Option<String>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+// 运行日志的一行
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct TaskRunLog {
+    pub run_id: String,
+    pub seq: i64,
+    pub level: String,
+    pub message: String,
+    pub logged_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct TaskRunLogReadOnly {
+    pub seq: i64,
+    pub level: String,
+    pub message: String,
+    pub logged_at: String,
+}
+
+impl From<TaskRunLog> for TaskRunLogReadOnly {
+    fn from(log: TaskRunLog) -> Self {
+        Self {
+            seq: log.seq,
+            level: log.level,
+            message: log.message,
+            logged_at: log.logged_at.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+        }
+    }
+}
+
+// 增量拉取日志的查询条件：返回序号大于 after 的日志
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TaskRunLogQuery {
+    pub after: Option<i64>,
+    pub limit: Option<u32>,
+}
+
+// 增量拉取的日志，next 作为下一次请求的 after；运行结束且已读完时 finished 为 true
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct TaskRunLogPage {
+    pub lines: Vec<TaskRunLogReadOnly>,
+    pub next: i64,
+    pub finished: bool,
+}
+
+// 推送给订阅者的运行事件：新的日志行或运行状态
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RunEvent {
+    Log(TaskRunLogReadOnly),
+    Status(TaskRunReadOnly),
+}
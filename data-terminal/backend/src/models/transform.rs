@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use crate::models::Validator;
+use crate::models::Error;
+
+use crate::impl_sqlx_for_string_enum;
+
+
+// 查询结果写入输出表的方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Materialization {
+    // 用本次结果整体替换输出表，表结构随 SQL 变化
+    Replace,
+    // 追加到输出表
+    Append,
+    // 按键列合并，键相同的行被覆盖
+    Merge,
+}
+
+impl Default for Materialization {
+    fn default() -> Self {
+        Self::Replace
+    }
+}
+
+impl_sqlx_for_string_enum!(Materialization);
+
+fn default_replication_num() -> i32 {
+    1
+}
+
+// 内部使用的完整加工任务模型
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct TransformTask {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub resource_id: String,
+    // 输出表所在库，为空时使用资源配置中的第一个库
+    #[serde(default)]
+    pub target_database: String,
+    pub output_table: String,
+    pub materialization: Materialization,
+    #[sqlx(json)]
+    pub merge_keys: Vec<String>,
+    pub sql_text: String,
+    // 自定义参数，值中可以引用内置参数
+    #[sqlx(json)]
+    pub params: BTreeMap<String, String>,
+    // 保存时从 SQL 中解析出的引用表
+    #[sqlx(json)]
+    pub referenced_tables: Vec<String>,
+    pub doris_replication_num: i32,
+    #[serde(default)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Validator for TransformTask {
+    fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() {
+            return Err(Error::EmptyValue("id".to_string()));
+        }
+        if self.name.is_empty() {
+            return Err(Error::EmptyValue("name".to_string()));
+        }
+        if self.name.len() > 64 {
+            return Err(Error::InvalidValue("name length must be less than 64 characters".to_string()));
+        }
+        if self.description.len() > 255 {
+            return Err(Error::InvalidValue("description length must be less than 255 characters".to_string()));
+        }
+        if self.resource_id.is_empty() {
+            return Err(Error::EmptyValue("resource_id".to_string()));
+        }
+        if self.target_database.len() > 64 {
+            return Err(Error::InvalidValue("target_database length must be less than 64 characters".to_string()));
+        }
+        if self.output_table.is_empty() {
+            return Err(Error::EmptyValue("output_table".to_string()));
+        }
+        if self.output_table.len() > 64 {
+            return Err(Error::InvalidValue("output_table length must be less than 64 characters".to_string()));
+        }
+        if self.materialization == Materialization::Merge && self.merge_keys.is_empty() {
+            return Err(Error::EmptyValue("merge_keys".to_string()));
+        }
+        if self.merge_keys.iter().any(|k| k.is_empty() || k.len() > 64) {
+            return Err(Error::InvalidValue("merge key must be 1 to 64 characters".to_string()));
+        }
+        if self.sql_text.trim().is_empty() {
+            return Err(Error::EmptyValue("sql_text".to_string()));
+        }
+        if let Some(name) = self.params.keys().find(|name| !is_param_name(name)) {
+            return Err(Error::InvalidValue(format!("invalid parameter name {}", name)));
+        }
+        if self.doris_replication_num < 1 {
+            return Err(Error::InvalidValue("doris_replication_num must be at least 1".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// 参数名只能由字母、数字和下划线组成，且不以数字开头
+pub fn is_param_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// Web 对外接口使用的只读加工任务模型
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct TransformTaskReadOnly {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub resource_id: String,
+    pub target_database: String,
+    pub output_table: String,
+    pub materialization: Materialization,
+    pub merge_keys: Vec<String>,
+    pub sql_text: String,
+    pub params: BTreeMap<String, String>,
+    pub referenced_tables: Vec<String>,
+    pub doris_replication_num: i32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<TransformTask> for TransformTaskReadOnly {
+    fn from(task: TransformTask) -> Self {
+        Self {
+            id: task.id,
+            name: task.name,
+            description: task.description,
+            resource_id: task.resource_id,
+            target_database: task.target_database,
+            output_table: task.output_table,
+            materialization: task.materialization,
+            merge_keys: task.merge_keys,
+            sql_text: task.sql_text,
+            params: task.params,
+            referenced_tables: task.referenced_tables,
+            doris_replication_num: task.doris_replication_num,
+            created_at: task.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            updated_at: task.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+// 用于创建和更新的加工任务模型
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct TransformTaskCreateUpdate {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub resource_id: String,
+    #[serde(default)]
+    pub target_database: String,
+    pub output_table: String,
+    #[serde(default)]
+    pub materialization: Materialization,
+    #[serde(default)]
+    pub merge_keys: Vec<String>,
+    pub sql_text: String,
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+    #[serde(default = "default_replication_num")]
+    pub doris_replication_num: i32,
+}
+
+impl From<TransformTaskCreateUpdate> for TransformTask {
+    fn from(task: TransformTaskCreateUpdate) -> Self {
+        Self {
+            id: task.id,
+            name: task.name,
+            description: task.description,
+            resource_id: task.resource_id,
+            target_database: task.target_database,
+            output_table: task.output_table,
+            materialization: task.materialization,
+            merge_keys: task.merge_keys,
+            sql_text: task.sql_text,
+            params: task.params,
+            referenced_tables: Vec::new(),
+            doris_replication_num: task.doris_replication_num,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+}
+
+// SQL 解析结果
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct TransformCheck {
+    // 引用的表，带库名时为 "库.表"
+    pub referenced_tables: Vec<String>,
+    // SQL 中使用的参数
+    pub params: Vec<String>,
+    // 按当前业务日期替换参数后的 SQL
+    pub rendered_sql: String,
+}
+
+// 手动运行时可以指定业务日期，用于补数
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct TransformRunQuery {
+    pub biz_date: Option<String>,
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use regex::Regex;
+
+use crate::models::Validator;
+use crate::models::Error;
+use crate::utils::config::Setting;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString, sqlx::Type)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum UserStatus {
+    Active,
+    Disabled,
+}
+
+impl Default for UserStatus {
+    fn default() -> Self {
+        Self::Active
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct User {
+    #[serde(default)]
+    pub id: String,
+    pub username: String,
+    #[serde(default)]
+    pub display_name: String,
+    // 密码哈希不返回给前端
+    #[serde(skip)]
+    pub password_hash: String,
+    #[serde(default)]
+    pub is_admin: bool,
+    #[serde(default)]
+    pub status: UserStatus,
+    #[serde(default)]
+    pub failed_attempts: i32,
+    #[serde(default)]
+    pub locked_until: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub last_login_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl User {
+    pub fn is_locked(&self) -> bool {
+        self.locked_until.is_some_and(|t| t > chrono::Utc::now())
+    }
+}
+
+// 添加项目成员时可选的用户，只包含公开信息
+#[derive(Debug, Serialize, Deserialize, FromRow, Default, Clone)]
+pub struct UserOption {
+    pub id: String,
+    pub username: String,
+    pub display_name: String,
+}
+
+// 管理员新增或编辑用户，编辑时 password 为空表示不修改密码
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct UserForm {
+    #[serde(default)]
+    pub id: String,
+    pub username: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub password: String,
+    #[serde(default)]
+    pub is_admin: bool,
+    #[serde(default)]
+    pub status: UserStatus,
+}
+
+impl Validator for UserForm {
+    fn validate(&self) -> Result<(), Error> {
+        if self.username.is_empty() {
+            return Err(Error::EmptyValue("username".to_string()));
+        }
+        // 登录名仅支持英文、数字、下划线、点和横线
+        let re = Regex::new(r"^[a-zA-Z0-9_.\-]{2,64}$").unwrap();
+        if !re.is_match(&self.username) {
+            return Err(Error::InvalidValue(format!("username {}", self.username)));
+        }
+        if self.display_name.chars().count() > 64 {
+            return Err(Error::InvalidValue("display_name is longer than 64 characters".to_string()));
+        }
+        if self.id.is_empty() || !self.password.is_empty() {
+            validate_password(&self.password)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct PasswordChangeRequest {
+    pub old_password: String,
+    pub new_password: String,
+}
+
+impl Validator for PasswordChangeRequest {
+    fn validate(&self) -> Result<(), Error> {
+        if self.old_password.is_empty() {
+            return Err(Error::EmptyValue("old_password".to_string()));
+        }
+        validate_password(&self.new_password)
+    }
+}
+
+pub fn validate_password(password: &str) -> Result<(), Error> {
+    if password.is_empty() {
+        return Err(Error::EmptyValue("password".to_string()));
+    }
+    let min_length = Setting::get().auth.min_password_length;
+    if password.chars().count() < min_length {
+        return Err(Error::InvalidValue(format!("password must be at least {} characters", min_length)));
+    }
+    Ok(())
+}
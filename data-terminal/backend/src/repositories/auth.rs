@@ -0,0 +1,126 @@
+use super::AuthRepo;
+use crate::models::auth::RefreshToken;
+use crate::models::error::Error;
+use crate::models::user::UserStatus;
+use crate::utils::database::get_config_db;
+use async_trait::async_trait;
+use shaku::Provider;
+use sqlx::Executor;
+
+#[derive(Provider)]
+#[shaku(interface = AuthRepo)]
+pub struct AuthRepoImpl {}
+
+#[async_trait]
+impl AuthRepo for AuthRepoImpl {
+    async fn add_refresh_token(&self, token: RefreshToken) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "INSERT INTO df_c_refresh_token (id, user_id, family_id, token_hash, project_code, expires_at) VALUES (?, ?, ?, ?, ?, ?)";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&token.id)
+                    .bind(&token.user_id)
+                    .bind(&token.family_id)
+                    .bind(&token.token_hash)
+                    .bind(&token.project_code)
+                    .bind(token.expires_at),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_refresh_token(&self, token_hash: String) -> Result<Option<RefreshToken>, Error> {
+        let pool = get_config_db().await?;
+        let sql = "SELECT * FROM df_c_refresh_token WHERE token_hash = ?";
+        let result = sqlx::query_as::<_, RefreshToken>(sql)
+            .bind(&token_hash)
+            .fetch_optional(&pool)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn revoke_refresh_token(&self, id: String, replaced_by: Option<String>) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        // 并发刷新时保留第一次作废的时间
+        let sql = "UPDATE df_c_refresh_token SET revoked_at = COALESCE(revoked_at, NOW()), replaced_by = COALESCE(?, replaced_by) WHERE id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&replaced_by).bind(&id)).await?;
+
+        Ok(())
+    }
+
+    async fn replace_refresh_token(&self, id: String, replaced_by: String) -> Result<bool, Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "UPDATE df_c_refresh_token SET revoked_at = NOW(), replaced_by = ? WHERE id = ? AND revoked_at IS NULL";
+        let result = pool.execute(sqlx::query(sql).bind(&replaced_by).bind(&id)).await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn mark_token_reused(&self, id: String) -> Result<bool, Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "UPDATE df_c_refresh_token SET reused_at = NOW() WHERE id = ? AND reused_at IS NULL";
+        let result = pool.execute(sqlx::query(sql).bind(&id)).await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn revoke_token_family(&self, family_id: String) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "UPDATE df_c_refresh_token SET revoked_at = NOW() WHERE family_id = ? AND revoked_at IS NULL";
+        let _ = pool.execute(sqlx::query(sql).bind(&family_id)).await?;
+
+        Ok(())
+    }
+
+    async fn revoke_user_token(&self, user_id: String) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "UPDATE df_c_refresh_token SET revoked_at = NOW() WHERE user_id = ? AND revoked_at IS NULL";
+        let _ = pool.execute(sqlx::query(sql).bind(&user_id)).await?;
+
+        Ok(())
+    }
+
+    async fn add_revoked_token(&self, jti: String, user_id: String, expires_at: chrono::DateTime<chrono::Utc>) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "INSERT IGNORE INTO df_c_revoked_token (jti, user_id, expires_at) VALUES (?, ?, ?)";
+        let _ = pool.execute(sqlx::query(sql).bind(&jti).bind(&user_id).bind(expires_at)).await?;
+
+        Ok(())
+    }
+
+    async fn is_revoked(&self, jti: String, user_id: String) -> Result<bool, Error> {
+        let pool = get_config_db().await?;
+        // 禁用或删除用户时不记录其访问 token，按用户状态判断
+        let sql = "SELECT (SELECT COUNT(*) FROM df_c_revoked_token WHERE jti = ?), \
+            (SELECT COUNT(*) FROM df_c_user WHERE id = ? AND status = ?)";
+        let (revoked, active) = sqlx::query_as::<_, (i64, i64)>(sql)
+            .bind(&jti)
+            .bind(&user_id)
+            .bind(UserStatus::Active)
+            .fetch_one(&pool)
+            .await?;
+
+        Ok(revoked > 0 || active == 0)
+    }
+
+    async fn purge_expired(&self, now: chrono::DateTime<chrono::Utc>) -> Result<u64, Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "DELETE FROM df_c_refresh_token WHERE expires_at < ?";
+        let refresh = pool.execute(sqlx::query(sql).bind(now)).await?.rows_affected();
+        let sql = "DELETE FROM df_c_revoked_token WHERE expires_at < ?";
+        let revoked = pool.execute(sqlx::query(sql).bind(now)).await?.rows_affected();
+
+        Ok(refresh + revoked)
+    }
+}
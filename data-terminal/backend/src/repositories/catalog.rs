@@ -0,0 +1,282 @@
+use super::CatalogRepo;
+use crate::models::catalog::{CatalogColumn, CatalogDrift, CatalogSearchHit, CatalogSearchQuery, CatalogSnapshot, CatalogSourceType, CatalogTable};
+use crate::models::error::Error;
+use crate::utils::database::get_project_db;
+use async_trait::async_trait;
+use shaku::Provider;
+use sqlx::{Executor, MySql, QueryBuilder};
+use crate::models::web::PageQuery;
+
+// 批量写入时每条语句的行数，避免超过 max_allowed_packet
+const INSERT_BATCH_ROWS: usize = 500;
+
+#[derive(Provider)]
+#[shaku(interface = CatalogRepo)]
+pub struct CatalogRepoImpl {}
+
+#[async_trait]
+impl CatalogRepo for CatalogRepoImpl {
+    async fn latest_snapshot(&self, project_code: String, source_type: CatalogSourceType, source_id: String) -> Result<Option<CatalogSnapshot>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_catalog_snapshot WHERE source_type = ? AND source_id = ? ORDER BY version DESC LIMIT 1";
+        let result = sqlx::query_as::<_, CatalogSnapshot>(sql)
+            .bind(&source_type)
+            .bind(&source_id)
+            .fetch_optional(&pool)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn get_snapshot(&self, project_code: String, id: String) -> Result<CatalogSnapshot, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_catalog_snapshot WHERE id = ?";
+        let result = sqlx::query_as::<_, CatalogSnapshot>(sql)
+            .bind(&id)
+            .fetch_one(&pool)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_snapshot(&self, project_code: String, source_type: CatalogSourceType, source_id: String, params: PageQuery) -> Result<Vec<CatalogSnapshot>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params.page_size.unwrap_or(10);
+        let offset = (page - 1) * page_size;
+
+        let sql = "SELECT * FROM df_c_catalog_snapshot WHERE source_type = ? AND source_id = ? ORDER BY version DESC LIMIT ? OFFSET ?";
+        let rows = sqlx::query_as::<_, CatalogSnapshot>(sql)
+            .bind(&source_type)
+            .bind(&source_id)
+            .bind(page_size as i64)
+            .bind(offset as i64)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn list_latest_snapshot(&self, project_code: String) -> Result<Vec<CatalogSnapshot>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_catalog_snapshot WHERE is_latest = 1 ORDER BY source_type, source_name";
+        let rows = sqlx::query_as::<_, CatalogSnapshot>(sql).fetch_all(&pool).await?;
+
+        Ok(rows)
+    }
+
+    async fn list_snapshot_table(&self, project_code: String, snapshot_id: String) -> Result<Vec<CatalogTable>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_catalog_table WHERE snapshot_id = ? ORDER BY schema_name, table_name";
+        let rows = sqlx::query_as::<_, CatalogTable>(sql)
+            .bind(&snapshot_id)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn list_snapshot_column(&self, project_code: String, snapshot_id: String) -> Result<Vec<CatalogColumn>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_catalog_column WHERE snapshot_id = ? ORDER BY schema_name, table_name, ordinal";
+        let rows = sqlx::query_as::<_, CatalogColumn>(sql)
+            .bind(&snapshot_id)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn add_snapshot(
+        &self,
+        project_code: String,
+        snapshot: CatalogSnapshot,
+        tables: Vec<CatalogTable>,
+        columns: Vec<CatalogColumn>,
+        drifts: Vec<CatalogDrift>,
+    ) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        let mut tx = pool.begin().await?;
+
+        let sql = "UPDATE df_c_catalog_snapshot SET is_latest = 0 WHERE source_type = ? AND source_id = ? AND is_latest = 1";
+        sqlx::query(sql).bind(&snapshot.source_type).bind(&snapshot.source_id).execute(&mut *tx).await?;
+
+        let sql = "INSERT INTO df_c_catalog_snapshot (id, source_type, source_id, source_name, version, is_latest, table_count, column_count, drift_count, created_at, checked_at) \
+            VALUES (?, ?, ?, ?, ?, 1, ?, ?, ?, ?, ?)";
+        sqlx::query(sql)
+            .bind(&snapshot.id)
+            .bind(&snapshot.source_type)
+            .bind(&snapshot.source_id)
+            .bind(&snapshot.source_name)
+            .bind(snapshot.version)
+            .bind(snapshot.table_count)
+            .bind(snapshot.column_count)
+            .bind(snapshot.drift_count)
+            .bind(&snapshot.created_at)
+            .bind(&snapshot.checked_at)
+            .execute(&mut *tx)
+            .await?;
+
+        for chunk in tables.chunks(INSERT_BATCH_ROWS) {
+            let mut builder: QueryBuilder<MySql> =
+                QueryBuilder::new("INSERT INTO df_c_catalog_table (id, snapshot_id, schema_name, table_name, table_type, comment, approx_rows) ");
+            builder.push_values(chunk, |mut row, table| {
+                row.push_bind(&table.id)
+                    .push_bind(&table.snapshot_id)
+                    .push_bind(&table.schema_name)
+                    .push_bind(&table.table_name)
+                    .push_bind(&table.table_type)
+                    .push_bind(&table.comment)
+                    .push_bind(table.approx_rows);
+            });
+            builder.build().execute(&mut *tx).await?;
+        }
+
+        for chunk in columns.chunks(INSERT_BATCH_ROWS) {
+            let mut builder: QueryBuilder<MySql> = QueryBuilder::new(
+                "INSERT INTO df_c_catalog_column (id, snapshot_id, schema_name, table_name, column_name, ordinal, column_type, data_type, nullable, default_value, comment, primary_key) ",
+            );
+            builder.push_values(chunk, |mut row, column| {
+                row.push_bind(&column.id)
+                    .push_bind(&column.snapshot_id)
+                    .push_bind(&column.schema_name)
+                    .push_bind(&column.table_name)
+                    .push_bind(&column.column_name)
+                    .push_bind(column.ordinal)
+                    .push_bind(&column.column_type)
+                    .push_bind(&column.data_type)
+                    .push_bind(column.nullable)
+                    .push_bind(&column.default_value)
+                    .push_bind(&column.comment)
+                    .push_bind(column.primary_key);
+            });
+            builder.build().execute(&mut *tx).await?;
+        }
+
+        for chunk in drifts.chunks(INSERT_BATCH_ROWS) {
+            let mut builder: QueryBuilder<MySql> = QueryBuilder::new(
+                "INSERT INTO df_c_catalog_drift (id, snapshot_id, source_type, source_id, from_version, to_version, schema_name, table_name, column_name, drift_type, before_value, after_value, detected_at) ",
+            );
+            builder.push_values(chunk, |mut row, drift| {
+                row.push_bind(&drift.id)
+                    .push_bind(&drift.snapshot_id)
+                    .push_bind(&drift.source_type)
+                    .push_bind(&drift.source_id)
+                    .push_bind(drift.from_version)
+                    .push_bind(drift.to_version)
+                    .push_bind(&drift.schema_name)
+                    .push_bind(&drift.table_name)
+                    .push_bind(&drift.column_name)
+                    .push_bind(&drift.drift_type)
+                    .push_bind(&drift.before_value)
+                    .push_bind(&drift.after_value)
+                    .push_bind(&drift.detected_at);
+            });
+            builder.build().execute(&mut *tx).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn touch_snapshot(&self, project_code: String, id: String, source_name: String, checked_at: chrono::DateTime<chrono::Utc>) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        // 来源被移除后又重新出现时恢复为最新版本
+        let sql = "UPDATE df_c_catalog_snapshot SET checked_at = ?, source_name = ?, is_latest = 1 WHERE id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&checked_at).bind(&source_name).bind(&id)).await?;
+
+        Ok(())
+    }
+
+    async fn retire_snapshot(&self, project_code: String, keep_ids: Vec<String>) -> Result<u64, Error> {
+        let pool = get_project_db(project_code).await?;
+        let mut builder: QueryBuilder<MySql> = QueryBuilder::new("UPDATE df_c_catalog_snapshot SET is_latest = 0 WHERE is_latest = 1");
+        if !keep_ids.is_empty() {
+            builder.push(" AND id NOT IN (");
+            let mut separated = builder.separated(", ");
+            for id in keep_ids {
+                separated.push_bind(id);
+            }
+            separated.push_unseparated(")");
+        }
+        let result = pool.execute(builder.build()).await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn list_drift(&self, project_code: String, source_type: CatalogSourceType, source_id: String, params: PageQuery) -> Result<Vec<CatalogDrift>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params.page_size.unwrap_or(10);
+        let offset = (page - 1) * page_size;
+
+        let sql = "SELECT * FROM df_c_catalog_drift WHERE source_type = ? AND source_id = ? ORDER BY to_version DESC, schema_name, table_name, column_name LIMIT ? OFFSET ?";
+        let rows = sqlx::query_as::<_, CatalogDrift>(sql)
+            .bind(&source_type)
+            .bind(&source_id)
+            .bind(page_size as i64)
+            .bind(offset as i64)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn search(&self, project_code: String, query: CatalogSearchQuery) -> Result<Vec<CatalogSearchHit>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let page = query.page.unwrap_or(1).max(1);
+        let page_size = query.page_size.unwrap_or(20);
+        let offset = (page - 1) * page_size;
+        let keyword = query.keyword.unwrap_or_default();
+        let pattern = format!("%{}%", keyword);
+
+        // 只在各来源的最新版本中搜索
+        let push_source_filter = |builder: &mut QueryBuilder<MySql>| {
+            if let Some(source_type) = query.source_type {
+                builder.push(" AND s.source_type = ").push_bind(source_type);
+            }
+            if let Some(source_id) = query.source_id.clone().filter(|s| !s.is_empty()) {
+                builder.push(" AND s.source_id = ").push_bind(source_id);
+            }
+        };
+
+        let mut builder: QueryBuilder<MySql> = QueryBuilder::new(
+            "SELECT s.source_type, s.source_id, s.source_name, s.version, t.schema_name, t.table_name, t.table_type, \
+            '' AS column_name, '' AS column_type, t.comment \
+            FROM df_c_catalog_table t JOIN df_c_catalog_snapshot s ON s.id = t.snapshot_id WHERE s.is_latest = 1",
+        );
+        push_source_filter(&mut builder);
+        if !keyword.is_empty() {
+            builder
+                .push(" AND (t.table_name LIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR t.comment LIKE ")
+                .push_bind(pattern.clone())
+                .push(")");
+
+            // 有关键字时同时搜索列
+            builder.push(
+                " UNION ALL SELECT s.source_type, s.source_id, s.source_name, s.version, c.schema_name, c.table_name, t.table_type, \
+                c.column_name, c.column_type, c.comment \
+                FROM df_c_catalog_column c JOIN df_c_catalog_snapshot s ON s.id = c.snapshot_id \
+                JOIN df_c_catalog_table t ON t.snapshot_id = c.snapshot_id AND t.schema_name = c.schema_name AND t.table_name = c.table_name \
+                WHERE s.is_latest = 1",
+            );
+            push_source_filter(&mut builder);
+            builder
+                .push(" AND (c.column_name LIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR c.comment LIKE ")
+                .push_bind(pattern)
+                .push(")");
+        }
+        builder
+            .push(" ORDER BY source_name, schema_name, table_name, column_name LIMIT ")
+            .push_bind(page_size as i64)
+            .push(" OFFSET ")
+            .push_bind(offset as i64);
+
+        let rows = builder.build_query_as::<CatalogSearchHit>().fetch_all(&pool).await?;
+        Ok(rows)
+    }
+}
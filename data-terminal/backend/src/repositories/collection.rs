@@ -0,0 +1,205 @@
+use super::CollectionRepo;
+use crate::models::collection::{CollectionTask, CollectionWatermark, CollectionCheckpoint};
+use crate::models::{Validator, error::Error};
+use crate::utils::database::get_project_db;
+use async_trait::async_trait;
+use shaku::Provider;
+use sqlx::Executor;
+use crate::models::web::PageQuery;
+
+#[derive(Provider)]
+#[shaku(interface = CollectionRepo)]
+pub struct CollectionRepoImpl {}
+
+#[async_trait]
+impl CollectionRepo for CollectionRepoImpl {
+    async fn add_collection(&self, project_code: String, task: CollectionTask) -> Result<String, Error> {
+        task.validate()?;
+
+        let pool = get_project_db(project_code).await?;
+        let sql = "INSERT INTO df_c_collection (id, name, description, category, collect_type, datasource_id, resource_id, rule, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&task.id)
+                    .bind(&task.name)
+                    .bind(&task.description)
+                    .bind(&task.category)
+                    .bind(&task.collect_type)
+                    .bind(&task.datasource_id)
+                    .bind(&task.resource_id)
+                    .bind(sqlx::types::Json(&task.rule))
+                    .bind(&task.created_at)
+                    .bind(&task.updated_at)
+            )
+            .await?;
+
+        Ok(task.id)
+    }
+
+    async fn edit_collection(&self, project_code: String, task: CollectionTask) -> Result<(), Error> {
+        task.validate()?;
+
+        let pool = get_project_db(project_code).await?;
+        let sql = "UPDATE df_c_collection SET name = ?, description = ?, category = ?, collect_type = ?, datasource_id = ?, resource_id = ?, rule = ?, updated_at = ? WHERE id = ?";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&task.name)
+                    .bind(&task.description)
+                    .bind(&task.category)
+                    .bind(&task.collect_type)
+                    .bind(&task.datasource_id)
+                    .bind(&task.resource_id)
+                    .bind(sqlx::types::Json(&task.rule))
+                    .bind(&task.updated_at)
+                    .bind(&task.id),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn del_collection(&self, project_code: String, id: String) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+
+        let sql = "DELETE FROM df_c_collection WHERE id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&id)).await?;
+        let sql = "DELETE FROM df_c_collection_watermark WHERE task_id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&id)).await?;
+        let sql = "DELETE FROM df_c_collection_checkpoint WHERE task_id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&id)).await?;
+        let sql = "DELETE FROM df_c_task_schedule WHERE task_id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&id)).await?;
+
+        Ok(())
+    }
+
+    async fn get_collection(&self, project_code: String, id: String) -> Result<CollectionTask, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_collection WHERE id = ?";
+        let result = sqlx::query_as::<_, CollectionTask>(sql)
+            .bind(&id)
+            .fetch_one(&pool)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_collection(&self, project_code: String, params: PageQuery) -> Result<Vec<CollectionTask>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let page = params.page.unwrap_or(1);
+        let page_size = params.page_size.unwrap_or(10);
+        let offset = (page - 1) * page_size;
+        let keyword = params.keyword.unwrap_or_default();
+
+        let rows = if keyword.is_empty() {
+            let sql = "SELECT * FROM df_c_collection ORDER BY created_at DESC LIMIT ? OFFSET ?";
+            sqlx::query_as::<_, CollectionTask>(sql)
+                .bind(page_size as i64)
+                .bind(offset as i64)
+                .fetch_all(&pool)
+                .await?
+        } else {
+            let sql = "SELECT * FROM df_c_collection WHERE name LIKE ? ORDER BY created_at DESC LIMIT ? OFFSET ?";
+            sqlx::query_as::<_, CollectionTask>(sql)
+                .bind(format!("%{}%", keyword))
+                .bind(page_size as i64)
+                .bind(offset as i64)
+                .fetch_all(&pool)
+                .await?
+        };
+
+        Ok(rows)
+    }
+
+    async fn list_all_collection(&self, project_code: String) -> Result<Vec<CollectionTask>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_collection";
+        let rows = sqlx::query_as::<_, CollectionTask>(sql)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn list_watermark(&self, project_code: String, task_id: String) -> Result<Vec<CollectionWatermark>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_collection_watermark WHERE task_id = ? ORDER BY source_table";
+        let rows = sqlx::query_as::<_, CollectionWatermark>(sql)
+            .bind(&task_id)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn save_watermark(&self, project_code: String, watermark: CollectionWatermark) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "INSERT INTO df_c_collection_watermark (task_id, source_table, watermark_column, watermark, updated_at) VALUES (?, ?, ?, ?, ?) \
+            ON DUPLICATE KEY UPDATE watermark_column = VALUES(watermark_column), watermark = VALUES(watermark), updated_at = VALUES(updated_at)";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&watermark.task_id)
+                    .bind(&watermark.source_table)
+                    .bind(&watermark.watermark_column)
+                    .bind(sqlx::types::Json(&watermark.watermark))
+                    .bind(&watermark.updated_at),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn reset_watermark(&self, project_code: String, task_id: String, source_table: Option<String>) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        match source_table {
+            Some(source_table) => {
+                let sql = "DELETE FROM df_c_collection_watermark WHERE task_id = ? AND source_table = ?";
+                let _ = pool.execute(sqlx::query(sql).bind(&task_id).bind(&source_table)).await?;
+            }
+            None => {
+                let sql = "DELETE FROM df_c_collection_watermark WHERE task_id = ?";
+                let _ = pool.execute(sqlx::query(sql).bind(&task_id)).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_checkpoint(&self, project_code: String, task_id: String) -> Result<Option<CollectionCheckpoint>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_collection_checkpoint WHERE task_id = ?";
+        let result = sqlx::query_as::<_, CollectionCheckpoint>(sql)
+            .bind(&task_id)
+            .fetch_optional(&pool)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn save_checkpoint(&self, project_code: String, checkpoint: CollectionCheckpoint) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "INSERT INTO df_c_collection_checkpoint (task_id, checkpoint, updated_at) VALUES (?, ?, ?) \
+            ON DUPLICATE KEY UPDATE checkpoint = VALUES(checkpoint), updated_at = VALUES(updated_at)";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&checkpoint.task_id)
+                    .bind(sqlx::types::Json(&checkpoint.checkpoint))
+                    .bind(&checkpoint.updated_at),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn del_checkpoint(&self, project_code: String, task_id: String) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "DELETE FROM df_c_collection_checkpoint WHERE task_id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&task_id)).await?;
+
+        Ok(())
+    }
+}
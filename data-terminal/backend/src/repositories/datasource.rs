@@ -1,5 +1,5 @@
 use super::DataSourceRepo;
-use crate::models::datasource::DataSource;
+use crate::models::datasource::{DataSource, ConnectionStatus};
 use crate::models::{Validator, error::Error};
 use crate::utils::database::get_project_db;
 use async_trait::async_trait;
@@ -150,4 +150,32 @@ impl DataSourceRepo for DataSourceRepoImpl {
 
         Ok(rows)
     }
+
+    async fn list_all_datasource(&self, project_code: String) -> Result<Vec<DataSource>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_datasource";
+        let rows = sqlx::query_as::<_, DataSource>(sql)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn update_connection_status(&self, project_code: String, id: String, status: ConnectionStatus, last_error: String, checked_at: chrono::DateTime<chrono::Utc>) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+
+        // 健康检查不属于用户修改，保持 updated_at 不变
+        let sql = "UPDATE df_c_datasource SET connection_status = ?, last_error = ?, last_checked_at = ?, updated_at = updated_at WHERE id = ?";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&status)
+                    .bind(&last_error)
+                    .bind(&checked_at)
+                    .bind(&id),
+            )
+            .await?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,84 @@
+use super::LineageRepo;
+use crate::models::error::Error;
+use crate::models::lineage::LineageEdge;
+use crate::models::task_run::TaskType;
+use crate::utils::database::get_project_db;
+use async_trait::async_trait;
+use shaku::Provider;
+use sqlx::{Executor, MySql, QueryBuilder};
+
+// 批量写入时每条语句的行数，避免超过 max_allowed_packet
+const INSERT_BATCH_ROWS: usize = 500;
+
+#[derive(Provider)]
+#[shaku(interface = LineageRepo)]
+pub struct LineageRepoImpl {}
+
+#[async_trait]
+impl LineageRepo for LineageRepoImpl {
+    async fn replace_task_edge(&self, project_code: String, task_type: TaskType, task_id: String, edges: Vec<LineageEdge>) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        let mut tx = pool.begin().await?;
+
+        let sql = "DELETE FROM df_c_lineage_edge WHERE task_type = ? AND task_id = ?";
+        sqlx::query(sql).bind(&task_type).bind(&task_id).execute(&mut *tx).await?;
+
+        for chunk in edges.chunks(INSERT_BATCH_ROWS) {
+            let mut builder: QueryBuilder<MySql> = QueryBuilder::new(
+                "INSERT INTO df_c_lineage_edge (id, task_type, task_id, task_name, source_type, source_id, source_schema, source_table, source_column, \
+                target_type, target_id, target_schema, target_table, target_column, expression, created_at) ",
+            );
+            builder.push_values(chunk, |mut row, edge| {
+                row.push_bind(&edge.id)
+                    .push_bind(&edge.task_type)
+                    .push_bind(&edge.task_id)
+                    .push_bind(&edge.task_name)
+                    .push_bind(&edge.source_type)
+                    .push_bind(&edge.source_id)
+                    .push_bind(&edge.source_schema)
+                    .push_bind(&edge.source_table)
+                    .push_bind(&edge.source_column)
+                    .push_bind(&edge.target_type)
+                    .push_bind(&edge.target_id)
+                    .push_bind(&edge.target_schema)
+                    .push_bind(&edge.target_table)
+                    .push_bind(&edge.target_column)
+                    .push_bind(&edge.expression)
+                    .push_bind(&edge.created_at);
+            });
+            builder.build().execute(&mut *tx).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn del_task_edge(&self, project_code: String, task_type: TaskType, task_id: String) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "DELETE FROM df_c_lineage_edge WHERE task_type = ? AND task_id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&task_type).bind(&task_id)).await?;
+
+        Ok(())
+    }
+
+    async fn list_task_edge(&self, project_code: String, task_type: TaskType, task_id: String) -> Result<Vec<LineageEdge>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_lineage_edge WHERE task_type = ? AND task_id = ? \
+            ORDER BY target_schema, target_table, target_column, source_schema, source_table, source_column";
+        let rows = sqlx::query_as::<_, LineageEdge>(sql)
+            .bind(&task_type)
+            .bind(&task_id)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn list_all_edge(&self, project_code: String) -> Result<Vec<LineageEdge>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_lineage_edge";
+        let rows = sqlx::query_as::<_, LineageEdge>(sql).fetch_all(&pool).await?;
+
+        Ok(rows)
+    }
+}
@@ -1,14 +1,34 @@
 pub mod project;
 pub mod resource;
 pub mod datasource;
+pub mod collection;
+pub mod task_run;
+pub mod schedule;
+pub mod pipeline;
+pub mod transform;
+pub mod query;
+pub mod catalog;
+pub mod lineage;
+pub mod user;
+pub mod auth;
 
 use async_trait::async_trait;
 use shaku::Interface;
-use crate::models::project::Project;
+use crate::models::project::{Project, CreateStatus, DeleteMode, ProjectArchive, ProjectMember, ProjectRole};
 use crate::models::error::Error;
 use crate::models::web::PageQuery;
 use crate::models::resource::Resource;
-use crate::models::datasource::DataSource;
+use crate::models::datasource::{DataSource, ConnectionStatus};
+use crate::models::collection::{CollectionTask, CollectionWatermark, CollectionCheckpoint};
+use crate::models::task_run::{TaskRun, TaskRunLog, TaskRunQuery, TaskType};
+use crate::models::schedule::TaskSchedule;
+use crate::models::pipeline::{Pipeline, PipelineNodeRun};
+use crate::models::transform::TransformTask;
+use crate::models::query::QueryHistory;
+use crate::models::catalog::{CatalogColumn, CatalogDrift, CatalogSearchHit, CatalogSearchQuery, CatalogSnapshot, CatalogSourceType, CatalogTable};
+use crate::models::lineage::LineageEdge;
+use crate::models::user::{User, UserOption};
+use crate::models::auth::RefreshToken;
 
 #[async_trait]
 pub trait ProjectRepo: Interface {
@@ -17,6 +37,19 @@ pub trait ProjectRepo: Interface {
     async fn del_project(&self, code: String) -> Result<(), Error>;
     async fn get_project(&self, code: String) -> Result<Project, Error>;
     async fn list_project(&self, params: PageQuery) -> Result<Vec<Project>, Error>;
+    async fn list_all_project(&self) -> Result<Vec<Project>, Error>;
+    async fn update_create_status(&self, code: String, status: CreateStatus, msg: String) -> Result<(), Error>;
+    async fn mark_deleting(&self, code: String, mode: DeleteMode, archive_db: String) -> Result<(), Error>;
+    async fn add_archive(&self, archive: ProjectArchive) -> Result<(), Error>;
+    async fn del_archive(&self, archive_db: String) -> Result<(), Error>;
+    async fn list_expired_archive(&self, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<ProjectArchive>, Error>;
+    async fn list_member_project(&self, user_id: String, params: PageQuery) -> Result<Vec<Project>, Error>;
+    async fn list_member(&self, code: String) -> Result<Vec<ProjectMember>, Error>;
+    async fn get_member_role(&self, code: String, user_id: String) -> Result<Option<ProjectRole>, Error>;
+    async fn save_member(&self, code: String, user_id: String, role: ProjectRole) -> Result<(), Error>;
+    async fn del_member(&self, code: String, user_id: String) -> Result<(), Error>;
+    async fn del_all_member(&self, code: String) -> Result<(), Error>;
+    async fn count_owner(&self, code: String) -> Result<i64, Error>;
 }
 
 #[async_trait]
@@ -36,4 +69,136 @@ pub trait DataSourceRepo: Interface {
     async fn get_datasource(&self, project_code: String, id: String) -> Result<DataSource, Error>;
     async fn list_datasource(&self, project_code: String, params: PageQuery) -> Result<Vec<DataSource>, Error>;
     async fn list_datasource_by_project(&self, project_code: String, params: PageQuery) -> Result<Vec<DataSource>, Error>;
-}
\ No newline at end of file
+    async fn list_all_datasource(&self, project_code: String) -> Result<Vec<DataSource>, Error>;
+    async fn update_connection_status(&self, project_code: String, id: String, status: ConnectionStatus, last_error: String, checked_at: chrono::DateTime<chrono::Utc>) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait CollectionRepo: Interface {
+    async fn add_collection(&self, project_code: String, task: CollectionTask) -> Result<String, Error>;
+    async fn edit_collection(&self, project_code: String, task: CollectionTask) -> Result<(), Error>;
+    async fn del_collection(&self, project_code: String, id: String) -> Result<(), Error>;
+    async fn get_collection(&self, project_code: String, id: String) -> Result<CollectionTask, Error>;
+    async fn list_collection(&self, project_code: String, params: PageQuery) -> Result<Vec<CollectionTask>, Error>;
+    async fn list_all_collection(&self, project_code: String) -> Result<Vec<CollectionTask>, Error>;
+    async fn list_watermark(&self, project_code: String, task_id: String) -> Result<Vec<CollectionWatermark>, Error>;
+    async fn save_watermark(&self, project_code: String, watermark: CollectionWatermark) -> Result<(), Error>;
+    async fn reset_watermark(&self, project_code: String, task_id: String, source_table: Option<String>) -> Result<(), Error>;
+    async fn get_checkpoint(&self, project_code: String, task_id: String) -> Result<Option<CollectionCheckpoint>, Error>;
+    async fn save_checkpoint(&self, project_code: String, checkpoint: CollectionCheckpoint) -> Result<(), Error>;
+    async fn del_checkpoint(&self, project_code: String, task_id: String) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait TaskRunRepo: Interface {
+    async fn add_run(&self, project_code: String, run: TaskRun) -> Result<String, Error>;
+    async fn finish_run(&self, project_code: String, run: TaskRun) -> Result<(), Error>;
+    async fn get_run(&self, project_code: String, id: String) -> Result<TaskRun, Error>;
+    async fn list_run_by_task(&self, project_code: String, task_id: String, limit: u32) -> Result<Vec<TaskRun>, Error>;
+    async fn list_run(&self, project_code: String, query: TaskRunQuery) -> Result<Vec<TaskRun>, Error>;
+    async fn add_run_log(&self, project_code: String, logs: Vec<TaskRunLog>) -> Result<(), Error>;
+    async fn list_run_log(&self, project_code: String, run_id: String, after: i64, limit: u32) -> Result<Vec<TaskRunLog>, Error>;
+}
+
+#[async_trait]
+pub trait ScheduleRepo: Interface {
+    async fn save_schedule(&self, project_code: String, schedule: TaskSchedule) -> Result<(), Error>;
+    async fn del_schedule(&self, project_code: String, task_id: String) -> Result<(), Error>;
+    async fn get_schedule(&self, project_code: String, task_id: String) -> Result<Option<TaskSchedule>, Error>;
+    async fn list_schedule(&self, project_code: String) -> Result<Vec<TaskSchedule>, Error>;
+    async fn list_due_schedule(&self, project_code: String, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<TaskSchedule>, Error>;
+    async fn update_fire_time(
+        &self,
+        project_code: String,
+        task_id: String,
+        next_fire_at: Option<chrono::DateTime<chrono::Utc>>,
+        last_fire_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait PipelineRepo: Interface {
+    async fn add_pipeline(&self, project_code: String, pipeline: Pipeline) -> Result<String, Error>;
+    async fn edit_pipeline(&self, project_code: String, pipeline: Pipeline) -> Result<(), Error>;
+    async fn del_pipeline(&self, project_code: String, id: String) -> Result<(), Error>;
+    async fn get_pipeline(&self, project_code: String, id: String) -> Result<Pipeline, Error>;
+    async fn list_pipeline(&self, project_code: String, params: PageQuery) -> Result<Vec<Pipeline>, Error>;
+    async fn list_all_pipeline(&self, project_code: String) -> Result<Vec<Pipeline>, Error>;
+    async fn save_node_run(&self, project_code: String, node_run: PipelineNodeRun) -> Result<(), Error>;
+    async fn list_node_run(&self, project_code: String, run_id: String) -> Result<Vec<PipelineNodeRun>, Error>;
+}
+
+#[async_trait]
+pub trait TransformRepo: Interface {
+    async fn add_transform(&self, project_code: String, task: TransformTask) -> Result<String, Error>;
+    async fn edit_transform(&self, project_code: String, task: TransformTask) -> Result<(), Error>;
+    async fn del_transform(&self, project_code: String, id: String) -> Result<(), Error>;
+    async fn get_transform(&self, project_code: String, id: String) -> Result<TransformTask, Error>;
+    async fn list_transform(&self, project_code: String, params: PageQuery) -> Result<Vec<TransformTask>, Error>;
+    async fn list_all_transform(&self, project_code: String) -> Result<Vec<TransformTask>, Error>;
+}
+
+#[async_trait]
+pub trait QueryHistoryRepo: Interface {
+    async fn add_history(&self, project_code: String, history: QueryHistory) -> Result<(), Error>;
+    async fn list_history(&self, project_code: String, user_name: String, params: PageQuery) -> Result<Vec<QueryHistory>, Error>;
+    async fn clear_history(&self, project_code: String, user_name: String) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait CatalogRepo: Interface {
+    async fn latest_snapshot(&self, project_code: String, source_type: CatalogSourceType, source_id: String) -> Result<Option<CatalogSnapshot>, Error>;
+    async fn get_snapshot(&self, project_code: String, id: String) -> Result<CatalogSnapshot, Error>;
+    async fn list_snapshot(&self, project_code: String, source_type: CatalogSourceType, source_id: String, params: PageQuery) -> Result<Vec<CatalogSnapshot>, Error>;
+    async fn list_latest_snapshot(&self, project_code: String) -> Result<Vec<CatalogSnapshot>, Error>;
+    async fn list_snapshot_table(&self, project_code: String, snapshot_id: String) -> Result<Vec<CatalogTable>, Error>;
+    async fn list_snapshot_column(&self, project_code: String, snapshot_id: String) -> Result<Vec<CatalogColumn>, Error>;
+    async fn add_snapshot(&self, project_code: String, snapshot: CatalogSnapshot, tables: Vec<CatalogTable>, columns: Vec<CatalogColumn>, drifts: Vec<CatalogDrift>) -> Result<(), Error>;
+    async fn touch_snapshot(&self, project_code: String, id: String, source_name: String, checked_at: chrono::DateTime<chrono::Utc>) -> Result<(), Error>;
+    async fn retire_snapshot(&self, project_code: String, keep_ids: Vec<String>) -> Result<u64, Error>;
+    async fn list_drift(&self, project_code: String, source_type: CatalogSourceType, source_id: String, params: PageQuery) -> Result<Vec<CatalogDrift>, Error>;
+    async fn search(&self, project_code: String, query: CatalogSearchQuery) -> Result<Vec<CatalogSearchHit>, Error>;
+}
+
+#[async_trait]
+pub trait LineageRepo: Interface {
+    async fn replace_task_edge(&self, project_code: String, task_type: TaskType, task_id: String, edges: Vec<LineageEdge>) -> Result<(), Error>;
+    async fn del_task_edge(&self, project_code: String, task_type: TaskType, task_id: String) -> Result<(), Error>;
+    async fn list_task_edge(&self, project_code: String, task_type: TaskType, task_id: String) -> Result<Vec<LineageEdge>, Error>;
+    async fn list_all_edge(&self, project_code: String) -> Result<Vec<LineageEdge>, Error>;
+}
+
+#[async_trait]
+pub trait UserRepo: Interface {
+    async fn add_user(&self, user: User) -> Result<String, Error>;
+    async fn edit_user(&self, user: User) -> Result<(), Error>;
+    async fn del_user(&self, id: String) -> Result<(), Error>;
+    async fn get_user(&self, id: String) -> Result<User, Error>;
+    async fn find_user_by_username(&self, username: String) -> Result<Option<User>, Error>;
+    async fn list_user(&self, params: PageQuery) -> Result<Vec<User>, Error>;
+    async fn list_user_option(&self) -> Result<Vec<UserOption>, Error>;
+    async fn count_user(&self) -> Result<i64, Error>;
+    async fn count_active_admin(&self) -> Result<i64, Error>;
+    async fn update_password(&self, id: String, password_hash: String) -> Result<(), Error>;
+    // 原子累加失败次数，达到上限（大于 0）时锁定到 locked_until 并清零计数
+    async fn record_login_failure(&self, id: String, max_failed_attempts: i32, locked_until: chrono::DateTime<chrono::Utc>) -> Result<(), Error>;
+    async fn record_login_success(&self, id: String, login_at: chrono::DateTime<chrono::Utc>) -> Result<(), Error>;
+    async fn unlock_user(&self, id: String) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait AuthRepo: Interface {
+    async fn add_refresh_token(&self, token: RefreshToken) -> Result<(), Error>;
+    async fn find_refresh_token(&self, token_hash: String) -> Result<Option<RefreshToken>, Error>;
+    async fn revoke_refresh_token(&self, id: String, replaced_by: Option<String>) -> Result<(), Error>;
+    // 仅当 token 未作废时作废并记录新 token，返回是否由本次调用完成轮换
+    async fn replace_refresh_token(&self, id: String, replaced_by: String) -> Result<bool, Error>;
+    // 记录作废后的首次再使用，已记录过时返回 false
+    async fn mark_token_reused(&self, id: String) -> Result<bool, Error>;
+    async fn revoke_token_family(&self, family_id: String) -> Result<(), Error>;
+    async fn revoke_user_token(&self, user_id: String) -> Result<(), Error>;
+    async fn add_revoked_token(&self, jti: String, user_id: String, expires_at: chrono::DateTime<chrono::Utc>) -> Result<(), Error>;
+    // token 已注销，或所属用户已被禁用、删除
+    async fn is_revoked(&self, jti: String, user_id: String) -> Result<bool, Error>;
+    async fn purge_expired(&self, now: chrono::DateTime<chrono::Utc>) -> Result<u64, Error>;
+}
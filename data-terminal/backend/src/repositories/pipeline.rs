@@ -0,0 +1,152 @@
+use super::PipelineRepo;
+use crate::models::pipeline::{Pipeline, PipelineNodeRun};
+use crate::models::{Validator, error::Error};
+use crate::utils::database::get_project_db;
+use async_trait::async_trait;
+use shaku::Provider;
+use sqlx::Executor;
+use crate::models::web::PageQuery;
+
+#[derive(Provider)]
+#[shaku(interface = PipelineRepo)]
+pub struct PipelineRepoImpl {}
+
+#[async_trait]
+impl PipelineRepo for PipelineRepoImpl {
+    async fn add_pipeline(&self, project_code: String, pipeline: Pipeline) -> Result<String, Error> {
+        pipeline.validate()?;
+
+        let pool = get_project_db(project_code).await?;
+        let sql = "INSERT INTO df_c_pipeline (id, name, description, nodes, edges, max_parallel, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&pipeline.id)
+                    .bind(&pipeline.name)
+                    .bind(&pipeline.description)
+                    .bind(sqlx::types::Json(&pipeline.nodes))
+                    .bind(sqlx::types::Json(&pipeline.edges))
+                    .bind(pipeline.max_parallel)
+                    .bind(&pipeline.created_at)
+                    .bind(&pipeline.updated_at)
+            )
+            .await?;
+
+        Ok(pipeline.id)
+    }
+
+    async fn edit_pipeline(&self, project_code: String, pipeline: Pipeline) -> Result<(), Error> {
+        pipeline.validate()?;
+
+        let pool = get_project_db(project_code).await?;
+        let sql = "UPDATE df_c_pipeline SET name = ?, description = ?, nodes = ?, edges = ?, max_parallel = ?, updated_at = ? WHERE id = ?";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&pipeline.name)
+                    .bind(&pipeline.description)
+                    .bind(sqlx::types::Json(&pipeline.nodes))
+                    .bind(sqlx::types::Json(&pipeline.edges))
+                    .bind(pipeline.max_parallel)
+                    .bind(&pipeline.updated_at)
+                    .bind(&pipeline.id),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn del_pipeline(&self, project_code: String, id: String) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+
+        let sql = "DELETE FROM df_c_pipeline WHERE id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&id)).await?;
+        let sql = "DELETE FROM df_c_task_schedule WHERE task_id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&id)).await?;
+
+        Ok(())
+    }
+
+    async fn get_pipeline(&self, project_code: String, id: String) -> Result<Pipeline, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_pipeline WHERE id = ?";
+        let result = sqlx::query_as::<_, Pipeline>(sql)
+            .bind(&id)
+            .fetch_one(&pool)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_pipeline(&self, project_code: String, params: PageQuery) -> Result<Vec<Pipeline>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let page = params.page.unwrap_or(1);
+        let page_size = params.page_size.unwrap_or(10);
+        let offset = (page - 1) * page_size;
+        let keyword = params.keyword.unwrap_or_default();
+
+        let rows = if keyword.is_empty() {
+            let sql = "SELECT * FROM df_c_pipeline ORDER BY created_at DESC LIMIT ? OFFSET ?";
+            sqlx::query_as::<_, Pipeline>(sql)
+                .bind(page_size as i64)
+                .bind(offset as i64)
+                .fetch_all(&pool)
+                .await?
+        } else {
+            let sql = "SELECT * FROM df_c_pipeline WHERE name LIKE ? ORDER BY created_at DESC LIMIT ? OFFSET ?";
+            sqlx::query_as::<_, Pipeline>(sql)
+                .bind(format!("%{}%", keyword))
+                .bind(page_size as i64)
+                .bind(offset as i64)
+                .fetch_all(&pool)
+                .await?
+        };
+
+        Ok(rows)
+    }
+
+    async fn list_all_pipeline(&self, project_code: String) -> Result<Vec<Pipeline>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_pipeline";
+        let rows = sqlx::query_as::<_, Pipeline>(sql)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn save_node_run(&self, project_code: String, node_run: PipelineNodeRun) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "INSERT INTO df_c_pipeline_node_run (run_id, node_id, task_type, task_id, status, attempts, task_run_id, error_message, started_at, finished_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+            ON DUPLICATE KEY UPDATE status = VALUES(status), attempts = VALUES(attempts), task_run_id = VALUES(task_run_id), error_message = VALUES(error_message), \
+            started_at = VALUES(started_at), finished_at = VALUES(finished_at)";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&node_run.run_id)
+                    .bind(&node_run.node_id)
+                    .bind(&node_run.task_type)
+                    .bind(&node_run.task_id)
+                    .bind(&node_run.status)
+                    .bind(node_run.attempts)
+                    .bind(&node_run.task_run_id)
+                    .bind(&node_run.error_message)
+                    .bind(&node_run.started_at)
+                    .bind(&node_run.finished_at),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_node_run(&self, project_code: String, run_id: String) -> Result<Vec<PipelineNodeRun>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_pipeline_node_run WHERE run_id = ? ORDER BY started_at IS NULL, started_at, node_id";
+        let rows = sqlx::query_as::<_, PipelineNodeRun>(sql)
+            .bind(&run_id)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+}
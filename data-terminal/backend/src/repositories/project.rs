@@ -1,5 +1,5 @@
 use super::ProjectRepo;
-use crate::models::project::Project;
+use crate::models::project::{Project, CreateStatus, DeleteMode, ProjectArchive, ProjectMember, ProjectRole};
 use crate::models::{Validator, error::Error};
 use crate::utils::database::get_config_db;
 use async_trait::async_trait;
@@ -100,5 +100,166 @@ impl ProjectRepo for ProjectRepoImpl {
 
         Ok(rows)
     }
-}
 
+    async fn list_all_project(&self) -> Result<Vec<Project>, Error> {
+        let pool = get_config_db().await?;
+        let sql = "SELECT * FROM df_c_project ORDER BY created_at";
+        let rows = sqlx::query_as::<_, Project>(sql)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn update_create_status(&self, code: String, status: CreateStatus, msg: String) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "UPDATE df_c_project SET create_status = ?, create_msg = ? WHERE code = ?";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&status)
+                    .bind(&msg)
+                    .bind(&code),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn mark_deleting(&self, code: String, mode: DeleteMode, archive_db: String) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "UPDATE df_c_project SET create_status = ?, create_msg = '', delete_mode = ?, archive_db = ? WHERE code = ?";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(CreateStatus::Deleting)
+                    .bind(mode)
+                    .bind(&archive_db)
+                    .bind(&code),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn add_archive(&self, archive: ProjectArchive) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "INSERT INTO df_c_project_archive (archive_db, code, archived_at, expire_at) VALUES (?, ?, ?, ?)";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&archive.archive_db)
+                    .bind(&archive.code)
+                    .bind(&archive.archived_at)
+                    .bind(&archive.expire_at),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn del_archive(&self, archive_db: String) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "DELETE FROM df_c_project_archive WHERE archive_db = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&archive_db)).await?;
+
+        Ok(())
+    }
+
+    async fn list_expired_archive(&self, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<ProjectArchive>, Error> {
+        let pool = get_config_db().await?;
+        let sql = "SELECT * FROM df_c_project_archive WHERE expire_at <= ?";
+        let rows = sqlx::query_as::<_, ProjectArchive>(sql)
+            .bind(&now)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn list_member_project(&self, user_id: String, params: PageQuery) -> Result<Vec<Project>, Error> {
+        let pool = get_config_db().await?;
+        let keyword = params.keyword.unwrap_or_default();
+        let page = params.page.unwrap_or(1);
+        let page_size = params.page_size.unwrap_or(10);
+        let offset = (page - 1) * page_size;
+        let sql = "SELECT p.*, m.role FROM df_c_project p JOIN df_c_project_member m ON m.project_code = p.code \
+            WHERE m.user_id = ? AND p.name LIKE ? LIMIT ? OFFSET ?";
+        let rows = sqlx::query_as::<_, Project>(sql)
+            .bind(&user_id)
+            .bind(format!("%{}%", keyword))
+            .bind(page_size as i64)
+            .bind(offset as i64)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn list_member(&self, code: String) -> Result<Vec<ProjectMember>, Error> {
+        let pool = get_config_db().await?;
+        let sql = "SELECT m.project_code, m.user_id, u.username, u.display_name, m.role, m.created_at \
+            FROM df_c_project_member m JOIN df_c_user u ON u.id = m.user_id \
+            WHERE m.project_code = ? ORDER BY m.created_at";
+        let rows = sqlx::query_as::<_, ProjectMember>(sql)
+            .bind(&code)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn get_member_role(&self, code: String, user_id: String) -> Result<Option<ProjectRole>, Error> {
+        let pool = get_config_db().await?;
+        let sql = "SELECT role FROM df_c_project_member WHERE project_code = ? AND user_id = ?";
+        let role = sqlx::query_scalar::<_, ProjectRole>(sql)
+            .bind(&code)
+            .bind(&user_id)
+            .fetch_optional(&pool)
+            .await?;
+
+        Ok(role)
+    }
+
+    async fn save_member(&self, code: String, user_id: String, role: ProjectRole) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "INSERT INTO df_c_project_member (project_code, user_id, role) VALUES (?, ?, ?) \
+            ON DUPLICATE KEY UPDATE role = VALUES(role)";
+        let _ = pool
+            .execute(sqlx::query(sql).bind(&code).bind(&user_id).bind(&role))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn del_member(&self, code: String, user_id: String) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "DELETE FROM df_c_project_member WHERE project_code = ? AND user_id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&code).bind(&user_id)).await?;
+
+        Ok(())
+    }
+
+    async fn del_all_member(&self, code: String) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "DELETE FROM df_c_project_member WHERE project_code = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&code)).await?;
+
+        Ok(())
+    }
+
+    async fn count_owner(&self, code: String) -> Result<i64, Error> {
+        let pool = get_config_db().await?;
+        let sql = "SELECT COUNT(*) FROM df_c_project_member WHERE project_code = ? AND role = 'owner'";
+        let count = sqlx::query_scalar::<_, i64>(sql).bind(&code).fetch_one(&pool).await?;
+
+        Ok(count)
+    }
+}
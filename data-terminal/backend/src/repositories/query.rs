@@ -0,0 +1,76 @@
+use super::QueryHistoryRepo;
+use crate::models::query::QueryHistory;
+use crate::models::error::Error;
+use crate::utils::database::get_project_db;
+use async_trait::async_trait;
+use shaku::Provider;
+use sqlx::Executor;
+use crate::models::web::PageQuery;
+
+#[derive(Provider)]
+#[shaku(interface = QueryHistoryRepo)]
+pub struct QueryHistoryRepoImpl {}
+
+#[async_trait]
+impl QueryHistoryRepo for QueryHistoryRepoImpl {
+    async fn add_history(&self, project_code: String, history: QueryHistory) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "INSERT INTO df_c_query_history (id, user_name, target_type, target_id, sql_text, status, row_count, truncated, duration_ms, error_message, executed_at) \
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&history.id)
+                    .bind(&history.user_name)
+                    .bind(&history.target_type)
+                    .bind(&history.target_id)
+                    .bind(&history.sql_text)
+                    .bind(&history.status)
+                    .bind(history.row_count)
+                    .bind(history.truncated)
+                    .bind(history.duration_ms)
+                    .bind(&history.error_message)
+                    .bind(&history.executed_at),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_history(&self, project_code: String, user_name: String, params: PageQuery) -> Result<Vec<QueryHistory>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let page = params.page.unwrap_or(1);
+        let page_size = params.page_size.unwrap_or(10);
+        let offset = (page - 1) * page_size;
+        let keyword = params.keyword.unwrap_or_default();
+
+        let rows = if keyword.is_empty() {
+            let sql = "SELECT * FROM df_c_query_history WHERE user_name = ? ORDER BY executed_at DESC LIMIT ? OFFSET ?";
+            sqlx::query_as::<_, QueryHistory>(sql)
+                .bind(&user_name)
+                .bind(page_size as i64)
+                .bind(offset as i64)
+                .fetch_all(&pool)
+                .await?
+        } else {
+            let sql = "SELECT * FROM df_c_query_history WHERE user_name = ? AND sql_text LIKE ? ORDER BY executed_at DESC LIMIT ? OFFSET ?";
+            sqlx::query_as::<_, QueryHistory>(sql)
+                .bind(&user_name)
+                .bind(format!("%{}%", keyword))
+                .bind(page_size as i64)
+                .bind(offset as i64)
+                .fetch_all(&pool)
+                .await?
+        };
+
+        Ok(rows)
+    }
+
+    async fn clear_history(&self, project_code: String, user_name: String) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "DELETE FROM df_c_query_history WHERE user_name = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&user_name)).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use super::ScheduleRepo;
+use crate::models::schedule::TaskSchedule;
+use crate::models::{Validator, error::Error};
+use crate::utils::database::get_project_db;
+use async_trait::async_trait;
+use shaku::Provider;
+use sqlx::Executor;
+
+#[derive(Provider)]
+#[shaku(interface = ScheduleRepo)]
+pub struct ScheduleRepoImpl {}
+
+#[async_trait]
+impl ScheduleRepo for ScheduleRepoImpl {
+    async fn save_schedule(&self, project_code: String, schedule: TaskSchedule) -> Result<(), Error> {
+        schedule.validate()?;
+
+        let pool = get_project_db(project_code).await?;
+        let sql = "INSERT INTO df_c_task_schedule (task_id, task_type, cron, timezone, misfire_policy, enabled, next_fire_at, last_fire_at, created_at, updated_at) \
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+            ON DUPLICATE KEY UPDATE task_type = VALUES(task_type), cron = VALUES(cron), timezone = VALUES(timezone), misfire_policy = VALUES(misfire_policy), \
+            enabled = VALUES(enabled), next_fire_at = VALUES(next_fire_at), updated_at = VALUES(updated_at)";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&schedule.task_id)
+                    .bind(&schedule.task_type)
+                    .bind(&schedule.cron)
+                    .bind(&schedule.timezone)
+                    .bind(&schedule.misfire_policy)
+                    .bind(schedule.enabled)
+                    .bind(&schedule.next_fire_at)
+                    .bind(&schedule.last_fire_at)
+                    .bind(&schedule.created_at)
+                    .bind(&schedule.updated_at),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn del_schedule(&self, project_code: String, task_id: String) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "DELETE FROM df_c_task_schedule WHERE task_id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&task_id)).await?;
+
+        Ok(())
+    }
+
+    async fn get_schedule(&self, project_code: String, task_id: String) -> Result<Option<TaskSchedule>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_task_schedule WHERE task_id = ?";
+        let result = sqlx::query_as::<_, TaskSchedule>(sql)
+            .bind(&task_id)
+            .fetch_optional(&pool)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_schedule(&self, project_code: String) -> Result<Vec<TaskSchedule>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_task_schedule ORDER BY created_at";
+        let rows = sqlx::query_as::<_, TaskSchedule>(sql).fetch_all(&pool).await?;
+
+        Ok(rows)
+    }
+
+    async fn list_due_schedule(&self, project_code: String, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<TaskSchedule>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_task_schedule WHERE enabled = 1 AND next_fire_at IS NOT NULL AND next_fire_at <= ? ORDER BY next_fire_at";
+        let rows = sqlx::query_as::<_, TaskSchedule>(sql)
+            .bind(&now)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn update_fire_time(
+        &self,
+        project_code: String,
+        task_id: String,
+        next_fire_at: Option<chrono::DateTime<chrono::Utc>>,
+        last_fire_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        // 触发时间的推进不算作计划的修改，保持 updated_at 不变
+        let sql = "UPDATE df_c_task_schedule SET next_fire_at = ?, last_fire_at = COALESCE(?, last_fire_at), updated_at = updated_at WHERE task_id = ?";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&next_fire_at)
+                    .bind(&last_fire_at)
+                    .bind(&task_id),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use super::TaskRunRepo;
+use crate::models::task_run::{TaskRun, TaskRunLog, TaskRunQuery};
+use crate::models::error::Error;
+use crate::utils::database::get_project_db;
+use async_trait::async_trait;
+use shaku::Provider;
+use sqlx::{Executor, MySql, QueryBuilder};
+
+#[derive(Provider)]
+#[shaku(interface = TaskRunRepo)]
+pub struct TaskRunRepoImpl {}
+
+#[async_trait]
+impl TaskRunRepo for TaskRunRepoImpl {
+    async fn add_run(&self, project_code: String, run: TaskRun) -> Result<String, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "INSERT INTO df_c_task_run (id, task_id, task_type, status, started_at) VALUES (?, ?, ?, ?, ?)";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&run.id)
+                    .bind(&run.task_id)
+                    .bind(&run.task_type)
+                    .bind(&run.status)
+                    .bind(&run.started_at)
+            )
+            .await?;
+
+        Ok(run.id)
+    }
+
+    async fn finish_run(&self, project_code: String, run: TaskRun) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "UPDATE df_c_task_run SET status = ?, rows_read = ?, rows_written = ?, bytes = ?, duration_ms = ?, error_message = ?, error_chain = ?, detail = ?, finished_at = ? WHERE id = ?";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&run.status)
+                    .bind(run.rows_read)
+                    .bind(run.rows_written)
+                    .bind(run.bytes)
+                    .bind(run.duration_ms)
+                    .bind(&run.error_message)
+                    .bind(&run.error_chain)
+                    .bind(&run.detail)
+                    .bind(&run.finished_at)
+                    .bind(&run.id),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_run(&self, project_code: String, id: String) -> Result<TaskRun, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_task_run WHERE id = ?";
+        let result = sqlx::query_as::<_, TaskRun>(sql)
+            .bind(&id)
+            .fetch_one(&pool)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_run_by_task(&self, project_code: String, task_id: String, limit: u32) -> Result<Vec<TaskRun>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_task_run WHERE task_id = ? ORDER BY started_at DESC LIMIT ?";
+        let rows = sqlx::query_as::<_, TaskRun>(sql)
+            .bind(&task_id)
+            .bind(limit as i64)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn list_run(&self, project_code: String, query: TaskRunQuery) -> Result<Vec<TaskRun>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let page = query.page.unwrap_or(1).max(1);
+        let page_size = query.page_size.unwrap_or(20);
+        let offset = (page - 1) * page_size;
+
+        let mut builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT * FROM df_c_task_run WHERE 1 = 1");
+        if let Some(task_id) = query.task_id.filter(|t| !t.is_empty()) {
+            builder.push(" AND task_id = ").push_bind(task_id);
+        }
+        if let Some(task_type) = query.task_type {
+            builder.push(" AND task_type = ").push_bind(task_type);
+        }
+        if let Some(status) = query.status {
+            builder.push(" AND status = ").push_bind(status);
+        }
+        if let Some(from) = query.started_from.filter(|t| !t.is_empty()) {
+            builder.push(" AND started_at >= ").push_bind(from);
+        }
+        if let Some(to) = query.started_to.filter(|t| !t.is_empty()) {
+            builder.push(" AND started_at <= ").push_bind(to);
+        }
+        builder
+            .push(" ORDER BY started_at DESC LIMIT ")
+            .push_bind(page_size as i64)
+            .push(" OFFSET ")
+            .push_bind(offset as i64);
+
+        let rows = builder.build_query_as::<TaskRun>().fetch_all(&pool).await?;
+        Ok(rows)
+    }
+
+    async fn add_run_log(&self, project_code: String, logs: Vec<TaskRunLog>) -> Result<(), Error> {
+        if logs.is_empty() {
+            return Ok(());
+        }
+        let pool = get_project_db(project_code).await?;
+        let mut builder: QueryBuilder<MySql> = QueryBuilder::new("INSERT IGNORE INTO df_c_task_run_log (run_id, seq, level, message, logged_at) ");
+        builder.push_values(logs, |mut row, log| {
+            row.push_bind(log.run_id)
+                .push_bind(log.seq)
+                .push_bind(log.level)
+                .push_bind(log.message)
+                .push_bind(log.logged_at);
+        });
+        pool.execute(builder.build()).await?;
+
+        Ok(())
+    }
+
+    async fn list_run_log(&self, project_code: String, run_id: String, after: i64, limit: u32) -> Result<Vec<TaskRunLog>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_task_run_log WHERE run_id = ? AND seq > ? ORDER BY seq LIMIT ?";
+        let rows = sqlx::query_as::<_, TaskRunLog>(sql)
+            .bind(&run_id)
+            .bind(after)
+            .bind(limit as i64)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+}
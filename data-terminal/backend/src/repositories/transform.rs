@@ -0,0 +1,132 @@
+use super::TransformRepo;
+use crate::models::transform::TransformTask;
+use crate::models::{Validator, error::Error};
+use crate::utils::database::get_project_db;
+use async_trait::async_trait;
+use shaku::Provider;
+use sqlx::Executor;
+use crate::models::web::PageQuery;
+
+#[derive(Provider)]
+#[shaku(interface = TransformRepo)]
+pub struct TransformRepoImpl {}
+
+#[async_trait]
+impl TransformRepo for TransformRepoImpl {
+    async fn add_transform(&self, project_code: String, task: TransformTask) -> Result<String, Error> {
+        task.validate()?;
+
+        let pool = get_project_db(project_code).await?;
+        let sql = "INSERT INTO df_c_transform (id, name, description, resource_id, target_database, output_table, materialization, merge_keys, sql_text, params, referenced_tables, doris_replication_num, created_at, updated_at) \
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&task.id)
+                    .bind(&task.name)
+                    .bind(&task.description)
+                    .bind(&task.resource_id)
+                    .bind(&task.target_database)
+                    .bind(&task.output_table)
+                    .bind(&task.materialization)
+                    .bind(sqlx::types::Json(&task.merge_keys))
+                    .bind(&task.sql_text)
+                    .bind(sqlx::types::Json(&task.params))
+                    .bind(sqlx::types::Json(&task.referenced_tables))
+                    .bind(task.doris_replication_num)
+                    .bind(&task.created_at)
+                    .bind(&task.updated_at)
+            )
+            .await?;
+
+        Ok(task.id)
+    }
+
+    async fn edit_transform(&self, project_code: String, task: TransformTask) -> Result<(), Error> {
+        task.validate()?;
+
+        let pool = get_project_db(project_code).await?;
+        let sql = "UPDATE df_c_transform SET name = ?, description = ?, resource_id = ?, target_database = ?, output_table = ?, materialization = ?, merge_keys = ?, \
+            sql_text = ?, params = ?, referenced_tables = ?, doris_replication_num = ?, updated_at = ? WHERE id = ?";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&task.name)
+                    .bind(&task.description)
+                    .bind(&task.resource_id)
+                    .bind(&task.target_database)
+                    .bind(&task.output_table)
+                    .bind(&task.materialization)
+                    .bind(sqlx::types::Json(&task.merge_keys))
+                    .bind(&task.sql_text)
+                    .bind(sqlx::types::Json(&task.params))
+                    .bind(sqlx::types::Json(&task.referenced_tables))
+                    .bind(task.doris_replication_num)
+                    .bind(&task.updated_at)
+                    .bind(&task.id),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn del_transform(&self, project_code: String, id: String) -> Result<(), Error> {
+        let pool = get_project_db(project_code).await?;
+
+        let sql = "DELETE FROM df_c_transform WHERE id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&id)).await?;
+        let sql = "DELETE FROM df_c_task_schedule WHERE task_id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&id)).await?;
+
+        Ok(())
+    }
+
+    async fn get_transform(&self, project_code: String, id: String) -> Result<TransformTask, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_transform WHERE id = ?";
+        let result = sqlx::query_as::<_, TransformTask>(sql)
+            .bind(&id)
+            .fetch_one(&pool)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_transform(&self, project_code: String, params: PageQuery) -> Result<Vec<TransformTask>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let page = params.page.unwrap_or(1);
+        let page_size = params.page_size.unwrap_or(10);
+        let offset = (page - 1) * page_size;
+        let keyword = params.keyword.unwrap_or_default();
+
+        let rows = if keyword.is_empty() {
+            let sql = "SELECT * FROM df_c_transform ORDER BY created_at DESC LIMIT ? OFFSET ?";
+            sqlx::query_as::<_, TransformTask>(sql)
+                .bind(page_size as i64)
+                .bind(offset as i64)
+                .fetch_all(&pool)
+                .await?
+        } else {
+            let sql = "SELECT * FROM df_c_transform WHERE name LIKE ? OR output_table LIKE ? ORDER BY created_at DESC LIMIT ? OFFSET ?";
+            sqlx::query_as::<_, TransformTask>(sql)
+                .bind(format!("%{}%", keyword))
+                .bind(format!("%{}%", keyword))
+                .bind(page_size as i64)
+                .bind(offset as i64)
+                .fetch_all(&pool)
+                .await?
+        };
+
+        Ok(rows)
+    }
+
+    async fn list_all_transform(&self, project_code: String) -> Result<Vec<TransformTask>, Error> {
+        let pool = get_project_db(project_code).await?;
+        let sql = "SELECT * FROM df_c_transform";
+        let rows = sqlx::query_as::<_, TransformTask>(sql)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+}
@@ -0,0 +1,175 @@
+use super::UserRepo;
+use crate::models::error::Error;
+use crate::models::user::{User, UserOption};
+use crate::models::web::PageQuery;
+use crate::utils::database::get_config_db;
+use async_trait::async_trait;
+use shaku::Provider;
+use sqlx::Executor;
+
+#[derive(Provider)]
+#[shaku(interface = UserRepo)]
+pub struct UserRepoImpl {}
+
+#[async_trait]
+impl UserRepo for UserRepoImpl {
+    async fn add_user(&self, user: User) -> Result<String, Error> {
+        let pool = get_config_db().await?;
+        let id = if user.id.is_empty() { uuid::Uuid::new_v4().to_string() } else { user.id.clone() };
+
+        let sql = "INSERT INTO df_c_user (id, username, display_name, password_hash, is_admin, status) VALUES (?, ?, ?, ?, ?, ?)";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&id)
+                    .bind(&user.username)
+                    .bind(&user.display_name)
+                    .bind(&user.password_hash)
+                    .bind(user.is_admin)
+                    .bind(&user.status),
+            )
+            .await?;
+
+        Ok(id)
+    }
+
+    async fn edit_user(&self, user: User) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "UPDATE df_c_user SET display_name = ?, is_admin = ?, status = ? WHERE id = ?";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(&user.display_name)
+                    .bind(user.is_admin)
+                    .bind(&user.status)
+                    .bind(&user.id),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn del_user(&self, id: String) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "DELETE FROM df_c_user WHERE id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&id)).await?;
+
+        Ok(())
+    }
+
+    async fn get_user(&self, id: String) -> Result<User, Error> {
+        let pool = get_config_db().await?;
+        let sql = "SELECT * FROM df_c_user WHERE id = ?";
+        let result = sqlx::query_as::<_, User>(sql)
+            .bind(&id)
+            .fetch_one(&pool)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_user_by_username(&self, username: String) -> Result<Option<User>, Error> {
+        let pool = get_config_db().await?;
+        let sql = "SELECT * FROM df_c_user WHERE username = ?";
+        let result = sqlx::query_as::<_, User>(sql)
+            .bind(&username)
+            .fetch_optional(&pool)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_user(&self, params: PageQuery) -> Result<Vec<User>, Error> {
+        let pool = get_config_db().await?;
+        let keyword = params.keyword.unwrap_or_default();
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params.page_size.unwrap_or(10);
+        let offset = (page - 1) * page_size;
+        let sql = "SELECT * FROM df_c_user WHERE username LIKE ? OR display_name LIKE ? ORDER BY created_at LIMIT ? OFFSET ?";
+        let rows = sqlx::query_as::<_, User>(sql)
+            .bind(format!("%{}%", keyword))
+            .bind(format!("%{}%", keyword))
+            .bind(page_size as i64)
+            .bind(offset as i64)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn list_user_option(&self) -> Result<Vec<UserOption>, Error> {
+        let pool = get_config_db().await?;
+        let sql = "SELECT id, username, display_name FROM df_c_user WHERE status = 'active' ORDER BY username";
+        let rows = sqlx::query_as::<_, UserOption>(sql).fetch_all(&pool).await?;
+
+        Ok(rows)
+    }
+
+    async fn count_user(&self) -> Result<i64, Error> {
+        let pool = get_config_db().await?;
+        let sql = "SELECT COUNT(*) FROM df_c_user";
+        let count = sqlx::query_scalar::<_, i64>(sql).fetch_one(&pool).await?;
+
+        Ok(count)
+    }
+
+    async fn count_active_admin(&self) -> Result<i64, Error> {
+        let pool = get_config_db().await?;
+        let sql = "SELECT COUNT(*) FROM df_c_user WHERE is_admin = 1 AND status = 'active'";
+        let count = sqlx::query_scalar::<_, i64>(sql).fetch_one(&pool).await?;
+
+        Ok(count)
+    }
+
+    async fn update_password(&self, id: String, password_hash: String) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "UPDATE df_c_user SET password_hash = ? WHERE id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&password_hash).bind(&id)).await?;
+
+        Ok(())
+    }
+
+    async fn record_login_failure(&self, id: String, max_failed_attempts: i32, locked_until: chrono::DateTime<chrono::Utc>) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        // MySQL 按顺序赋值，locked_until 需在 failed_attempts 清零前计算
+        let sql = "UPDATE df_c_user SET \
+            locked_until = IF(? > 0 AND failed_attempts + 1 >= ?, ?, locked_until), \
+            failed_attempts = IF(? > 0 AND failed_attempts + 1 >= ?, 0, failed_attempts + 1) \
+            WHERE id = ?";
+        let _ = pool
+            .execute(
+                sqlx::query(sql)
+                    .bind(max_failed_attempts)
+                    .bind(max_failed_attempts)
+                    .bind(locked_until)
+                    .bind(max_failed_attempts)
+                    .bind(max_failed_attempts)
+                    .bind(&id),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn record_login_success(&self, id: String, login_at: chrono::DateTime<chrono::Utc>) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "UPDATE df_c_user SET failed_attempts = 0, locked_until = NULL, last_login_at = ? WHERE id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(login_at).bind(&id)).await?;
+
+        Ok(())
+    }
+
+    async fn unlock_user(&self, id: String) -> Result<(), Error> {
+        let pool = get_config_db().await?;
+
+        let sql = "UPDATE df_c_user SET failed_attempts = 0, locked_until = NULL WHERE id = ?";
+        let _ = pool.execute(sqlx::query(sql).bind(&id)).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use axum::{extract::{Path, Query}, http::StatusCode, routing::{get, post}, Json, Router, debug_handler};
+use super::permission::{Authorized, Developer, Viewer};
+use crate::autofac;
+use crate::models::catalog::{
+    CatalogDrift, CatalogSearchHit, CatalogSearchQuery, CatalogSnapshot, CatalogSnapshotDetail, CatalogSnapshotRequest, CatalogSourceQuery, SnapshotResult,
+};
+use crate::models::web::Response;
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/snapshot", post(snapshot_source))
+        .route("/snapshot/all", post(snapshot_project))
+        .route("/snapshot/{id}", get(detail_snapshot))
+        .route("/sources", get(list_latest_snapshot))
+        .route("/snapshots", get(list_snapshot))
+        .route("/drift", get(list_drift))
+        .route("/search", get(search_catalog))
+}
+
+#[debug_handler]
+async fn snapshot_source(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Json(request): Json<CatalogSnapshotRequest>,
+) -> (StatusCode, Json<Response<SnapshotResult>>) {
+    let result = autofac::get_global_app_state_ref()
+        .get_catalog_service()
+        .snapshot_source(claims.project, request.source_type, request.source_id)
+        .await;
+    match result {
+        Ok(result) => (StatusCode::OK, Json(Response::success(result))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn snapshot_project(
+    Authorized { claims, .. }: Authorized<Developer>,
+) -> (StatusCode, Json<Response<usize>>) {
+    let result = autofac::get_global_app_state_ref().get_catalog_service().snapshot_project(claims.project).await;
+    match result {
+        Ok(count) => (StatusCode::OK, Json(Response::success(count))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn detail_snapshot(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<CatalogSnapshotDetail>>) {
+    let result = autofac::get_global_app_state_ref().get_catalog_service().get_snapshot(claims.project, id).await;
+    match result {
+        Ok(detail) => (StatusCode::OK, Json(Response::success(detail))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_latest_snapshot(
+    Authorized { claims, .. }: Authorized<Viewer>,
+) -> (StatusCode, Json<Response<Vec<CatalogSnapshot>>>) {
+    let result = autofac::get_global_app_state_ref().get_catalog_service().list_latest_snapshot(claims.project).await;
+    match result {
+        Ok(snapshots) => (StatusCode::OK, Json(Response::success(snapshots))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_snapshot(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Query(params): Query<CatalogSourceQuery>,
+) -> (StatusCode, Json<Response<Vec<CatalogSnapshot>>>) {
+    let result = autofac::get_global_app_state_ref()
+        .get_catalog_service()
+        .list_snapshot(claims.project, params.source_type, params.source_id.clone(), (&params).into())
+        .await;
+    match result {
+        Ok(snapshots) => (StatusCode::OK, Json(Response::success(snapshots))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_drift(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Query(params): Query<CatalogSourceQuery>,
+) -> (StatusCode, Json<Response<Vec<CatalogDrift>>>) {
+    let result = autofac::get_global_app_state_ref()
+        .get_catalog_service()
+        .list_drift(claims.project, params.source_type, params.source_id.clone(), (&params).into())
+        .await;
+    match result {
+        Ok(drifts) => (StatusCode::OK, Json(Response::success(drifts))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn search_catalog(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Query(params): Query<CatalogSearchQuery>,
+) -> (StatusCode, Json<Response<Vec<CatalogSearchHit>>>) {
+    let result = autofac::get_global_app_state_ref().get_catalog_service().search_catalog(claims.project, params).await;
+    match result {
+        Ok(hits) => (StatusCode::OK, Json(Response::success(hits))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
@@ -0,0 +1,169 @@
+use axum::{extract::{Path, Query}, http::StatusCode, routing::{delete, get, post}, Json, Router, debug_handler};
+use super::permission::{Authorized, Developer, Viewer};
+use crate::{autofac, models::collection::{CollectionTaskReadOnly, CollectionTaskCreateUpdate, CollectionWatermarkReadOnly, WatermarkResetQuery, CollectionCheckpointReadOnly}};
+use crate::models::task_run::TaskRunReadOnly;
+use crate::models::web::{Response, PageQuery};
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/add", post(add_collection))
+        .route("/update", post(update_collection))
+        .route("/list", get(list_collection))
+        .route("/{id}", get(detail_collection))
+        .route("/{id}", delete(delete_collection))
+        .route("/{id}/run", post(run_collection))
+        .route("/{id}/runs", get(list_collection_run))
+        .route("/{id}/watermark", get(list_collection_watermark))
+        .route("/{id}/watermark/reset", post(reset_collection_watermark))
+        .route("/{id}/stop", post(stop_collection))
+        .route("/{id}/checkpoint", get(get_collection_checkpoint))
+        .route("/{id}/checkpoint/reset", post(reset_collection_checkpoint))
+}
+
+#[debug_handler]
+async fn add_collection(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Json(task): Json<CollectionTaskCreateUpdate>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_collection_service().add_collection(claims.project, task).await;
+    match result {
+        Ok(id) => (StatusCode::OK, Json(Response::success(id))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_collection(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Query(params): Query<PageQuery>,
+) -> (StatusCode, Json<Response<Vec<CollectionTaskReadOnly>>>) {
+    let result = autofac::get_global_app_state_ref().get_collection_service().list_collection(claims.project, params).await;
+    match result {
+        Ok(tasks) => (StatusCode::OK, Json(Response::success(tasks))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn delete_collection(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_collection_service().del_collection(claims.project, id).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn update_collection(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Json(task): Json<CollectionTaskCreateUpdate>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_collection_service().edit_collection(claims.project, task).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn detail_collection(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<CollectionTaskReadOnly>>) {
+    let result = autofac::get_global_app_state_ref().get_collection_service().get_collection(claims.project, id).await;
+    match result {
+        Ok(task) => (StatusCode::OK, Json(Response::success(task))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn run_collection(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_collection_service().run_collection(claims.project, id).await;
+    match result {
+        Ok(run_id) => (StatusCode::OK, Json(Response::success(run_id))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_collection_run(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<Vec<TaskRunReadOnly>>>) {
+    let result = autofac::get_global_app_state_ref().get_collection_service().list_collection_run(claims.project, id).await;
+    match result {
+        Ok(runs) => (StatusCode::OK, Json(Response::success(runs))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_collection_watermark(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<Vec<CollectionWatermarkReadOnly>>>) {
+    let result = autofac::get_global_app_state_ref().get_collection_service().list_collection_watermark(claims.project, id).await;
+    match result {
+        Ok(watermarks) => (StatusCode::OK, Json(Response::success(watermarks))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn reset_collection_watermark(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+    Query(params): Query<WatermarkResetQuery>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref()
+        .get_collection_service()
+        .reset_watermark(claims.project, id, params.source_table)
+        .await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn stop_collection(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_collection_service().stop_collection(claims.project, id).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn get_collection_checkpoint(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<Option<CollectionCheckpointReadOnly>>>) {
+    let result = autofac::get_global_app_state_ref().get_collection_service().get_collection_checkpoint(claims.project, id).await;
+    match result {
+        Ok(checkpoint) => (StatusCode::OK, Json(Response::success(checkpoint))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn reset_collection_checkpoint(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_collection_service().reset_collection_checkpoint(claims.project, id).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
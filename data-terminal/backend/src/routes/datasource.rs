@@ -1,14 +1,20 @@
 
 use axum::{extract::{Path, Query}, http::StatusCode, routing::{delete, get, post}, Json, Router, debug_handler};
-use super::jwt::Claims;
-use crate::{autofac, models::datasource::{DataSourceReadOnly, DataSourceCreateUpdate}};
+use super::permission::{Authorized, Developer, Owner, Viewer};
+use crate::{autofac, models::datasource::{DataSourceReadOnly, DataSourceCreateUpdate, PingDiagnostic}};
+use crate::models::mask_secrets;
+use crate::models::project::ProjectRole;
 use crate::models::web::{Response, PageQuery};
+use crate::models::metadata::{CatalogQuery, DataSourceCatalog, TableDetail, TableDetailQuery};
 
 pub fn routes() -> Router {
     Router::new()
         .route("/add", post(add_datasource))
         .route("/update", post(update_datasource))
         .route("/ping", post(ping_datasource))
+        .route("/recheck/{id}", post(recheck_datasource))
+        .route("/{id}/catalog", get(datasource_catalog))
+        .route("/{id}/table", get(datasource_table))
         .route("/list", get(list_datasource))
         .route("/{id}", get(detail_datasource))
         .route("/{id}", delete(delete_datasource))
@@ -16,7 +22,7 @@ pub fn routes() -> Router {
 
 #[debug_handler]
 async fn add_datasource(
-    claims: Claims,
+    Authorized { claims, .. }: Authorized<Developer>,
     Json(datasource): Json<DataSourceCreateUpdate>,
 ) -> (StatusCode, Json<Response<String>>) {
     let result = autofac::get_global_app_state_ref().get_datasource_service().add_datasource(claims.project, datasource).await;
@@ -28,19 +34,25 @@ async fn add_datasource(
 
 #[debug_handler]
 async fn list_datasource(
-    claims: Claims,
+    Authorized { claims, role, .. }: Authorized<Viewer>,
     Query(params): Query<PageQuery>,
 ) -> (StatusCode, Json<Response<Vec<DataSourceReadOnly>>>) {
     let result = autofac::get_global_app_state_ref().get_datasource_service().list_datasource(claims.project, params).await;
     match result {
-        Ok(datasources) => (StatusCode::OK, Json(Response::success(datasources))),
+        Ok(mut datasources) => {
+            // 只读成员看不到连接密码
+            if role < ProjectRole::Developer {
+                datasources.iter_mut().for_each(|d| mask_secrets(&mut d.connection_config));
+            }
+            (StatusCode::OK, Json(Response::success(datasources)))
+        }
         Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
     }
 }
 
 #[debug_handler]
 async fn delete_datasource(
-    claims: Claims,
+    Authorized { claims, .. }: Authorized<Owner>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<Response<String>>) {
     let result = autofac::get_global_app_state_ref().get_datasource_service().del_datasource(claims.project, id).await;
@@ -52,7 +64,7 @@ async fn delete_datasource(
 
 #[debug_handler]
 async fn update_datasource(
-    claims: Claims,
+    Authorized { claims, .. }: Authorized<Developer>,
     Json(datasource): Json<DataSourceCreateUpdate>,
 ) -> (StatusCode, Json<Response<String>>) {
     let result = autofac::get_global_app_state_ref().get_datasource_service().edit_datasource(claims.project, datasource).await;
@@ -64,26 +76,74 @@ async fn update_datasource(
 
 #[debug_handler]
 async fn ping_datasource(
-    claims: Claims,
+    Authorized { claims, .. }: Authorized<Developer>,
     Json(datasource): Json<DataSourceCreateUpdate>,
-) -> (StatusCode, Json<Response<String>>) {
+) -> (StatusCode, Json<Response<PingDiagnostic>>) {
     let result = autofac::get_global_app_state_ref().get_datasource_service().ping_datasource(claims.project, datasource).await;
     match result {
-        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Ok(diagnostic) if diagnostic.success => (StatusCode::OK, Json(Response::success(diagnostic))),
+        // 探测失败时仍返回诊断详情，便于前端展示具体原因
+        Ok(diagnostic) => (StatusCode::OK, Json(Response {
+            result: false,
+            msg: diagnostic.message.clone(),
+            data: diagnostic,
+        })),
         Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
     }
 }
 
+#[debug_handler]
+async fn recheck_datasource(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<DataSourceReadOnly>>) {
+    let result = autofac::get_global_app_state_ref().get_datasource_service().recheck_datasource(claims.project, id).await;
+    match result {
+        Ok(datasource) => (StatusCode::OK, Json(Response::success(datasource))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
 
 #[debug_handler]
 async fn detail_datasource(
-    claims: Claims,
+    Authorized { claims, role, .. }: Authorized<Viewer>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<Response<DataSourceReadOnly>>) {
     let result = autofac::get_global_app_state_ref().get_datasource_service().get_datasource(claims.project, id).await;
     match result {
-        Ok(datasource) => (StatusCode::OK, Json(Response::success(datasource))),
+        Ok(mut datasource) => {
+            if role < ProjectRole::Developer {
+                mask_secrets(&mut datasource.connection_config);
+            }
+            (StatusCode::OK, Json(Response::success(datasource)))
+        }
         Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
     }
 }
 
+
+#[debug_handler]
+async fn datasource_catalog(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+    Query(params): Query<CatalogQuery>,
+) -> (StatusCode, Json<Response<DataSourceCatalog>>) {
+    let result = autofac::get_global_app_state_ref().get_datasource_service().get_catalog(claims.project, id, params.refresh).await;
+    match result {
+        Ok(catalog) => (StatusCode::OK, Json(Response::success(catalog))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn datasource_table(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+    Query(params): Query<TableDetailQuery>,
+) -> (StatusCode, Json<Response<TableDetail>>) {
+    let result = autofac::get_global_app_state_ref().get_datasource_service().get_table_detail(claims.project, id, params).await;
+    match result {
+        Ok(detail) => (StatusCode::OK, Json(Response::success(detail))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
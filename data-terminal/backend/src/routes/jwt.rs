@@ -2,17 +2,24 @@ use axum::{
     extract::{FromRequestParts}, http::{request::Parts, StatusCode}, response::IntoResponse, Json
 };
 use axum_extra::{
-    extract::cookie::CookieJar,
+    extract::cookie::{Cookie, CookieJar},
 };
-use jsonwebtoken::{decode, DecodingKey, EncodingKey,  Validation};
+use chrono::{Utc, Duration as ChronoDuration};
+use cookie::time::Duration;
+use jsonwebtoken::{decode, encode, errors::ErrorKind, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
 use std::sync::LazyLock;
 use std::fmt::Display;
+use crate::autofac;
 use crate::utils::config::Setting;
 use crate::models::web::Response;
 
 
 
+pub const TOKEN_COOKIE: &str = "token";
+pub const REFRESH_COOKIE: &str = "refresh_token";
+pub const PROJECT_COOKIE: &str = "project_code";
+
 static KEYS: LazyLock<Keys> = LazyLock::new(|| {
     let secret = Setting::get().jwt.secret.clone();
     Keys::new(secret.as_bytes())
@@ -26,14 +33,20 @@ pub enum AuthError {
     MissingCredentials,
     TokenCreation,
     InvalidToken,
+    TokenExpired,
+    Forbidden,
+    ProjectRequired,
 }
 impl IntoResponse for AuthError {
     fn into_response(self) -> axum::response::Response {
         let (status, error_message) = match self {
             AuthError::WrongCredentials => (StatusCode::UNAUTHORIZED, "Wrong credentials"),
-            AuthError::MissingCredentials => (StatusCode::BAD_REQUEST, "Missing credentials"), 
+            AuthError::MissingCredentials => (StatusCode::UNAUTHORIZED, "Missing credentials"),
             AuthError::TokenCreation => (StatusCode::INTERNAL_SERVER_ERROR, "Token creation error"),
             AuthError::InvalidToken => (StatusCode::BAD_REQUEST, "Invalid token"),
+            AuthError::TokenExpired => (StatusCode::UNAUTHORIZED, "Token expired"),
+            AuthError::Forbidden => (StatusCode::FORBIDDEN, "Permission denied"),
+            AuthError::ProjectRequired => (StatusCode::BAD_REQUEST, "No project selected"),
         };
         let body = Json(Response::<()>::error(error_message.to_string()));
         (status, body).into_response()
@@ -57,15 +70,79 @@ impl Keys {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
+    // 用户ID
     pub sub: String,
     pub company: String,
     pub project: String,
     pub exp: usize,
+    // token ID，注销时加入吊销列表
+    #[serde(default)]
+    pub jti: String,
+}
+
+impl Claims {
+    // 签发短期访问 token，project 为当前选择的项目，未选择时为空
+    pub fn issue(sub: String, project: String) -> Result<String, AuthError> {
+        let claims = Claims {
+            sub,
+            company: "".to_string(),
+            project,
+            exp: (Utc::now() + ChronoDuration::seconds(Setting::get().jwt.expires)).timestamp() as usize,
+            jti: uuid::Uuid::new_v4().to_string(),
+        };
+        encode(&Header::default(), &claims, &KEYS.encoding).map_err(|_| AuthError::TokenCreation)
+    }
+
+    // 注销时解析 token，不校验是否过期
+    pub fn decode_unchecked(token: &str) -> Option<Claims> {
+        let mut validation = Validation::default();
+        validation.validate_exp = false;
+        decode::<Claims>(token, &KEYS.decoding, &validation).ok().map(|data| data.claims)
+    }
+}
+
+// 签发访问 token 和刷新 token 并写入 cookie，previous 为切换项目前的刷新 token
+pub async fn issue_session(jar: CookieJar, user_id: String, project: String, previous: Option<String>) -> Result<CookieJar, AuthError> {
+    let access_token = Claims::issue(user_id.clone(), project.clone())?;
+    let refresh_token = autofac::get_global_app_state_ref()
+        .get_auth_service()
+        .create_refresh_token(user_id, project.clone(), previous)
+        .await
+        .map_err(|e| {
+            tracing::error!("保存刷新 token 失败: {}", e);
+            AuthError::TokenCreation
+        })?;
+    Ok(add_auth_cookies(jar, access_token, refresh_token, project))
+}
+
+// project_code 仅供前端展示，后端以 token 中的项目为准
+pub fn add_auth_cookies(jar: CookieJar, access_token: String, refresh_token: String, project: String) -> CookieJar {
+    let setting = Setting::get();
+    let cookie = &setting.cookie;
+    let jar = jar.add(auth_cookie(TOKEN_COOKIE, access_token, setting.jwt.expires, cookie.http_only));
+    let jar = jar.add(auth_cookie(REFRESH_COOKIE, refresh_token, setting.jwt.refresh_expires, cookie.http_only));
+    jar.add(auth_cookie(PROJECT_COOKIE, project, setting.jwt.refresh_expires, false))
+}
+
+pub fn clear_auth_cookies(jar: CookieJar) -> CookieJar {
+    [TOKEN_COOKIE, REFRESH_COOKIE, PROJECT_COOKIE]
+        .into_iter()
+        .fold(jar, |jar, name| jar.remove(Cookie::build(name).path("/")))
+}
+
+fn auth_cookie(name: &'static str, value: String, max_age_secs: i64, http_only: bool) -> Cookie<'static> {
+    Cookie::build((name, value))
+        .http_only(http_only)
+        .path("/")
+        .max_age(Duration::seconds(max_age_secs))
+        .same_site(cookie::SameSite::Strict)  // 使用Strict，确保同站点
+        .secure(Setting::get().cookie.secure)  // 不指定domain，让浏览器自动处理
+        .build()
 }
 
 impl Display for Claims {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Email: {}\nCompany: {}\nProject: {}", self.sub, self.company, self.project)
+        write!(f, "User: {}\nCompany: {}\nProject: {}", self.sub, self.company, self.project)
     }
 }
 
@@ -79,14 +156,33 @@ where
 
         let jar = CookieJar::from_headers(&parts.headers);
         let token = jar
-            .get("token")
+            .get(TOKEN_COOKIE)
             .map(|cookie| cookie.value().to_string())
             .ok_or(AuthError::MissingCredentials)?;
 
-        // 校验 token
+        // 校验 token，过期时返回 401 由前端使用刷新 token 换取新 token
         let token_data = decode::<Claims>(&token, &KEYS.decoding, &Validation::default())
-            .map_err(|_| AuthError::InvalidToken)?;
+            .map_err(|e| match e.kind() {
+                ErrorKind::ExpiredSignature => AuthError::TokenExpired,
+                _ => AuthError::InvalidToken,
+            })?;
+        let claims = token_data.claims;
+        if claims.jti.is_empty() {
+            return Err(AuthError::InvalidToken);
+        }
 
-        Ok(token_data.claims)
+        // 已注销的 token 以及已禁用、删除用户的 token 不再可用
+        let result = autofac::get_global_app_state_ref()
+            .get_auth_service()
+            .is_revoked(claims.jti.clone(), claims.sub.clone())
+            .await;
+        match result {
+            Ok(false) => Ok(claims),
+            Ok(true) => Err(AuthError::TokenExpired),
+            Err(e) => {
+                tracing::error!("查询 token 吊销状态失败: {}", e);
+                Err(AuthError::InvalidToken)
+            }
+        }
     }
 }
\ No newline at end of file
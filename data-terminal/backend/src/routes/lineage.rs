@@ -0,0 +1,69 @@
+use axum::{extract::Query, http::StatusCode, routing::{get, post}, Json, Router, debug_handler};
+use super::permission::{Authorized, Developer, Viewer};
+use crate::autofac;
+use crate::models::lineage::{LineageDirection, LineageEdge, LineageGraph, LineageQuery, LineageTaskQuery};
+use crate::models::web::Response;
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/upstream", get(upstream_lineage))
+        .route("/downstream", get(downstream_lineage))
+        .route("/task", get(list_task_lineage))
+        .route("/rebuild", post(rebuild_lineage))
+}
+
+#[debug_handler]
+async fn upstream_lineage(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Query(params): Query<LineageQuery>,
+) -> (StatusCode, Json<Response<LineageGraph>>) {
+    let result = autofac::get_global_app_state_ref()
+        .get_lineage_service()
+        .trace_lineage(claims.project, params, LineageDirection::Upstream)
+        .await;
+    match result {
+        Ok(graph) => (StatusCode::OK, Json(Response::success(graph))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn downstream_lineage(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Query(params): Query<LineageQuery>,
+) -> (StatusCode, Json<Response<LineageGraph>>) {
+    let result = autofac::get_global_app_state_ref()
+        .get_lineage_service()
+        .trace_lineage(claims.project, params, LineageDirection::Downstream)
+        .await;
+    match result {
+        Ok(graph) => (StatusCode::OK, Json(Response::success(graph))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_task_lineage(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Query(params): Query<LineageTaskQuery>,
+) -> (StatusCode, Json<Response<Vec<LineageEdge>>>) {
+    let result = autofac::get_global_app_state_ref()
+        .get_lineage_service()
+        .list_task_lineage(claims.project, params.task_type, params.task_id)
+        .await;
+    match result {
+        Ok(edges) => (StatusCode::OK, Json(Response::success(edges))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn rebuild_lineage(
+    Authorized { claims, .. }: Authorized<Developer>,
+) -> (StatusCode, Json<Response<usize>>) {
+    let result = autofac::get_global_app_state_ref().get_lineage_service().rebuild_project(claims.project).await;
+    match result {
+        Ok(count) => (StatusCode::OK, Json(Response::success(count))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
@@ -4,21 +4,17 @@ use axum::{
     routing::{post},
     Json, Router,
 };
-use axum_extra::{
-    extract::{cookie::{Cookie, CookieJar}},
-};
-use cookie::time::Duration;
-use jsonwebtoken::{encode, EncodingKey, Header};
-use chrono::{Utc, Duration as ChronoDuration};
-use crate::utils::config::Setting;
-use crate::models::web;
-use super::jwt::Claims;
+use axum_extra::extract::cookie::CookieJar;
+use crate::autofac;
+use crate::models::{web, Error};
+use super::jwt::{add_auth_cookies, clear_auth_cookies, issue_session, Claims, REFRESH_COOKIE, TOKEN_COOKIE};
 
 
 
 pub fn routes() -> Router {
     Router::new()
         .route("/", post(login))
+        .route("/refresh", post(refresh))
 }
 
 
@@ -26,74 +22,44 @@ async fn login(
     jar: CookieJar,
     Json(payload): Json<web::LoginRequest>,
 ) -> impl IntoResponse {
-    let setting = Setting::get();
-    let admin = &setting.admin;
-
-    // 校验用户名
-    if payload.username != admin.username {
-        return (
-            StatusCode::UNAUTHORIZED,
-            jar,
-            Json(web::Response::<String> {
-                result: false,
-                msg: "username is incorrect".to_string(),
-                data: "".to_string(),
-            }),
-        );
-    }
-
-    if admin.password != payload.password {
-        return (
-            StatusCode::UNAUTHORIZED,
-            jar,
-            Json(web::Response::<String> {
-                result: false,
-                msg: "password is incorrect".to_string(),
-                data: "".to_string(),
-            }),
-        );
-    }
-
-    // 生成 JWT
-    let exp = (Utc::now() + ChronoDuration::hours(24)).timestamp() as usize;
-    let claims = Claims {
-        sub: admin.username.clone(),
-        company: "".to_string(),
-        project: "".to_string(),
-        exp,
+    let result = autofac::get_global_app_state_ref()
+        .get_user_service()
+        .authenticate(payload.username, payload.password)
+        .await;
+    let user = match result {
+        Ok(user) => user,
+        Err(e) => {
+            let status = match e {
+                Error::InvalidCredentials | Error::AccountDisabled | Error::AccountLocked(_) => StatusCode::UNAUTHORIZED,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            return (
+                status,
+                jar,
+                Json(web::Response::<String> {
+                    result: false,
+                    msg: e.to_string(),
+                    data: "".to_string(),
+                }),
+            );
+        }
     };
-    let secret = Setting::get().jwt.secret.clone();
-    let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes()))
-        .map_err(|_| {
-            (
+
+    // 登录后尚未选择项目，需调用切换项目接口重新签发 token
+    let jar = match issue_session(jar.clone(), user.id.clone(), "".to_string(), None).await {
+        Ok(jar) => jar,
+        Err(_) => {
+            return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                jar.clone(),
+                jar,
                 Json(web::Response::<String> {
                     result: false,
                     msg: "creating token occur error".to_string(),
                     data: "".to_string(),
                 }),
-            )
-        })
-        .unwrap();
-
-    // 设置 token cookie - 使用Strict SameSite策略
-    let cookie = Cookie::build(("token", token.clone()))
-        .http_only(false)  // 允许JavaScript访问
-        .path("/")
-        .max_age(Duration::days(1))
-        .same_site(cookie::SameSite::Strict)  // 使用Strict，确保同站点
-        .secure(false);  // 不指定domain，让浏览器自动处理
-    let jar = jar.add(cookie);
-
-    // 设置project_code cookie - 使用Strict SameSite策略
-    let cookie = Cookie::build(("project_code", "None"))
-        .http_only(false)  // 允许JavaScript访问
-        .path("/")
-        .max_age(Duration::days(1))
-        .same_site(cookie::SameSite::Strict)  // 使用Strict，确保同站点
-        .secure(false);  // 不指定domain，让浏览器自动处理
-    let jar = jar.add(cookie);
+            );
+        }
+    };
 
     (
         StatusCode::OK,
@@ -106,3 +72,70 @@ async fn login(
     )
 }
 
+// 使用刷新 token 换取新的访问 token，刷新 token 同时轮换
+pub async fn refresh(
+    jar: CookieJar,
+) -> impl IntoResponse {
+    let Some(token) = jar.get(REFRESH_COOKIE).map(|cookie| cookie.value().to_string()) else {
+        return (
+            StatusCode::UNAUTHORIZED,
+            jar,
+            Json(web::Response::<String>::error(Error::InvalidSession.to_string())),
+        );
+    };
+    let result = autofac::get_global_app_state_ref()
+        .get_auth_service()
+        .rotate_refresh_token(token)
+        .await;
+    let issued = match result {
+        Ok(issued) => issued,
+        // 并发刷新已换发新 token，保留浏览器中的 cookie
+        Err(Error::RefreshConflict) => {
+            return (StatusCode::CONFLICT, jar, Json(web::Response::<String>::error(Error::RefreshConflict.to_string())));
+        }
+        Err(e) => {
+            let status = match e {
+                Error::InvalidSession | Error::AccountDisabled => StatusCode::UNAUTHORIZED,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            return (status, clear_auth_cookies(jar), Json(web::Response::<String>::error(e.to_string())));
+        }
+    };
+
+    match Claims::issue(issued.user_id, issued.project_code.clone()) {
+        Ok(access_token) => (
+            StatusCode::OK,
+            add_auth_cookies(jar, access_token, issued.token, issued.project_code),
+            Json(web::Response::success("".to_string())),
+        ),
+        Err(_) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            jar,
+            Json(web::Response::<String>::error("creating token occur error".to_string())),
+        ),
+    }
+}
+
+// 注销当前会话：访问 token 加入吊销列表，刷新 token 所在的整族作废
+pub async fn logout(
+    jar: CookieJar,
+) -> impl IntoResponse {
+    let service = autofac::get_global_app_state_ref().get_auth_service();
+    if let Some(claims) = jar.get(TOKEN_COOKIE).and_then(|cookie| Claims::decode_unchecked(cookie.value())) {
+        let expires_at = chrono::DateTime::from_timestamp(claims.exp as i64, 0).unwrap_or_default();
+        if let Err(e) = service.revoke_access_token(claims.jti, claims.sub, expires_at).await {
+            tracing::error!("注销访问 token 失败: {}", e);
+        }
+    }
+    if let Some(token) = jar.get(REFRESH_COOKIE).map(|cookie| cookie.value().to_string()) {
+        if let Err(e) = service.revoke_refresh_token(token).await {
+            tracing::error!("注销刷新 token 失败: {}", e);
+        }
+    }
+
+    (
+        StatusCode::OK,
+        clear_auth_cookies(jar),
+        Json(web::Response::success("".to_string())),
+    )
+}
@@ -1,11 +1,21 @@
 mod jwt;
+mod permission;
 mod login;
 mod project;
 mod resource;
 mod datasource;
+mod collection;
+mod schedule;
+mod task_run;
+mod pipeline;
+mod transform;
+mod query;
+mod catalog;
+mod lineage;
+mod user;
 
 use axum::{
-    Router
+    routing::post, Router
 };
 use tower_http::cors::{Any, CorsLayer};
 use axum::http::{HeaderName, Method}; 
@@ -50,13 +60,23 @@ fn api_routes_v1() -> Router {
 
     // UnAuth
     let public_routes = Router::new()
-        .nest("/login", login::routes());
+        .nest("/login", login::routes())
+        .route("/logout", post(login::logout));
 
     // JwtAuth
     let protected_routes = Router::new()
         .nest("/project", project::routes())
         .nest("/resource", resource::routes())
-        .nest("/datasource", datasource::routes());
+        .nest("/datasource", datasource::routes())
+        .nest("/collection", collection::routes())
+        .nest("/schedule", schedule::routes())
+        .nest("/run", task_run::routes())
+        .nest("/pipeline", pipeline::routes())
+        .nest("/transform", transform::routes())
+        .nest("/query", query::routes())
+        .nest("/catalog", catalog::routes())
+        .nest("/lineage", lineage::routes())
+        .nest("/user", user::routes());
 
     // 合并两组路由
     public_routes.merge(protected_routes)
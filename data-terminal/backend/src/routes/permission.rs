@@ -0,0 +1,121 @@
+use axum::{extract::FromRequestParts, http::request::Parts};
+use std::marker::PhantomData;
+use super::jwt::{AuthError, Claims};
+use crate::autofac;
+use crate::models::project::ProjectRole;
+use crate::models::user::UserStatus;
+
+// 接口要求的最低项目角色
+pub trait Requirement: Send + Sync {
+    const ROLE: ProjectRole;
+}
+
+pub struct Viewer;
+pub struct Developer;
+pub struct Owner;
+
+impl Requirement for Viewer {
+    const ROLE: ProjectRole = ProjectRole::Viewer;
+}
+
+impl Requirement for Developer {
+    const ROLE: ProjectRole = ProjectRole::Developer;
+}
+
+impl Requirement for Owner {
+    const ROLE: ProjectRole = ProjectRole::Owner;
+}
+
+/// 校验当前用户在 token 所属项目中的角色，不满足要求时返回 403
+pub struct Authorized<R: Requirement> {
+    pub claims: Claims,
+    pub role: ProjectRole,
+    requirement: PhantomData<R>,
+}
+
+impl<S, R> FromRequestParts<S> for Authorized<R>
+where
+    S: Send + Sync,
+    R: Requirement,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let claims = Claims::from_request_parts(parts, state).await?;
+        // 项目内的接口必须先选择项目
+        if claims.project.is_empty() {
+            return Err(AuthError::ProjectRequired);
+        }
+        let project = claims.project.clone();
+        let role = authorize(&claims, &project, R::ROLE).await?;
+        Ok(Self { claims, role, requirement: PhantomData })
+    }
+}
+
+/// 校验当前用户在指定项目中的角色，用于项目编码来自路径的接口
+pub async fn authorize(claims: &Claims, project_code: &str, required: ProjectRole) -> Result<ProjectRole, AuthError> {
+    let result = autofac::get_global_app_state_ref()
+        .get_project_service()
+        .get_role(claims.sub.clone(), project_code.to_string())
+        .await;
+    match result {
+        Ok(Some(role)) if role >= required => Ok(role),
+        Ok(_) => Err(AuthError::Forbidden),
+        Err(e) => {
+            tracing::error!("查询用户 {} 在项目 {} 的角色失败: {}", claims.sub, project_code, e);
+            Err(AuthError::Forbidden)
+        }
+    }
+}
+
+/// 仅系统管理员可以访问，用于全局资源等不属于某个项目的配置
+pub struct AdminClaims(pub Claims);
+
+impl<S> FromRequestParts<S> for AdminClaims
+where
+    S: Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let claims = Claims::from_request_parts(parts, state).await?;
+        let result = autofac::get_global_app_state_ref()
+            .get_user_service()
+            .get_current_user(claims.sub.clone())
+            .await;
+        match result {
+            Ok(user) if user.is_admin && user.status == UserStatus::Active => Ok(Self(claims)),
+            _ => Err(AuthError::Forbidden),
+        }
+    }
+}
+
+/// 全局资源的读取权限：系统管理员，或已选择项目的项目成员；非管理员看到的连接配置隐藏密码
+pub struct ResourceReader {
+    pub claims: Claims,
+    pub is_admin: bool,
+}
+
+impl<S> FromRequestParts<S> for ResourceReader
+where
+    S: Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let claims = Claims::from_request_parts(parts, state).await?;
+        let result = autofac::get_global_app_state_ref()
+            .get_user_service()
+            .get_current_user(claims.sub.clone())
+            .await;
+        if matches!(result, Ok(user) if user.is_admin && user.status == UserStatus::Active) {
+            return Ok(Self { claims, is_admin: true });
+        }
+        if claims.project.is_empty() {
+            return Err(AuthError::ProjectRequired);
+        }
+        let project = claims.project.clone();
+        authorize(&claims, &project, ProjectRole::Viewer).await?;
+        Ok(Self { claims, is_admin: false })
+    }
+}
@@ -0,0 +1,139 @@
+use axum::{extract::{Path, Query}, http::StatusCode, routing::{delete, get, post}, Json, Router, debug_handler};
+use super::permission::{Authorized, Developer, Viewer};
+use crate::{autofac, models::pipeline::{PipelineReadOnly, PipelineCreateUpdate, PipelineRunDetail}};
+use crate::models::task_run::TaskRunReadOnly;
+use crate::models::web::{Response, PageQuery};
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/add", post(add_pipeline))
+        .route("/update", post(update_pipeline))
+        .route("/validate", post(validate_pipeline))
+        .route("/list", get(list_pipeline))
+        .route("/run/{run_id}", get(detail_pipeline_run))
+        .route("/{id}", get(detail_pipeline))
+        .route("/{id}", delete(delete_pipeline))
+        .route("/{id}/run", post(run_pipeline))
+        .route("/{id}/runs", get(list_pipeline_run))
+        .route("/{id}/stop", post(stop_pipeline))
+}
+
+#[debug_handler]
+async fn add_pipeline(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Json(pipeline): Json<PipelineCreateUpdate>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_pipeline_service().add_pipeline(claims.project, pipeline).await;
+    match result {
+        Ok(id) => (StatusCode::OK, Json(Response::success(id))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn update_pipeline(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Json(pipeline): Json<PipelineCreateUpdate>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_pipeline_service().edit_pipeline(claims.project, pipeline).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn validate_pipeline(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Json(pipeline): Json<PipelineCreateUpdate>,
+) -> (StatusCode, Json<Response<Vec<String>>>) {
+    let result = autofac::get_global_app_state_ref().get_pipeline_service().validate_pipeline(claims.project, pipeline).await;
+    match result {
+        Ok(order) => (StatusCode::OK, Json(Response::success(order))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_pipeline(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Query(params): Query<PageQuery>,
+) -> (StatusCode, Json<Response<Vec<PipelineReadOnly>>>) {
+    let result = autofac::get_global_app_state_ref().get_pipeline_service().list_pipeline(claims.project, params).await;
+    match result {
+        Ok(pipelines) => (StatusCode::OK, Json(Response::success(pipelines))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn detail_pipeline(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<PipelineReadOnly>>) {
+    let result = autofac::get_global_app_state_ref().get_pipeline_service().get_pipeline(claims.project, id).await;
+    match result {
+        Ok(pipeline) => (StatusCode::OK, Json(Response::success(pipeline))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn delete_pipeline(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_pipeline_service().del_pipeline(claims.project, id).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn run_pipeline(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_pipeline_service().run_pipeline(claims.project, id).await;
+    match result {
+        Ok(run_id) => (StatusCode::OK, Json(Response::success(run_id))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn stop_pipeline(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_pipeline_service().stop_pipeline(claims.project, id).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_pipeline_run(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<Vec<TaskRunReadOnly>>>) {
+    let result = autofac::get_global_app_state_ref().get_pipeline_service().list_pipeline_run(claims.project, id).await;
+    match result {
+        Ok(runs) => (StatusCode::OK, Json(Response::success(runs))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn detail_pipeline_run(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(run_id): Path<String>,
+) -> (StatusCode, Json<Response<PipelineRunDetail>>) {
+    let result = autofac::get_global_app_state_ref().get_pipeline_service().get_pipeline_run(claims.project, run_id).await;
+    match result {
+        Ok(detail) => (StatusCode::OK, Json(Response::success(detail))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
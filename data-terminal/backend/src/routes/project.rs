@@ -1,6 +1,8 @@
 use axum::{extract::{Path, Query}, http::StatusCode, routing::{delete, get, post}, Json, Router, debug_handler};
-use super::jwt::Claims;
-use crate::{autofac, models::project::Project};
+use axum_extra::extract::cookie::CookieJar;
+use super::jwt::{issue_session, AuthError, Claims, REFRESH_COOKIE};
+use super::permission::authorize;
+use crate::{autofac, models::project::{Project, ProjectDeleteQuery, ProjectMember, ProjectMemberForm, ProjectRole}};
 use crate::models::web::{Response, PageQuery};
 
 
@@ -9,8 +11,13 @@ pub fn routes() -> Router {
         .route("/add", post(add_project))
         .route("/update", post(update_project))
         .route("/list", get(list_project))
+        .route("/{code}/provision", post(retry_provision))
+        .route("/{code}/switch", post(switch_project))
         .route("/{code}", get(detail_project))
         .route("/{code}", delete(delete_project))
+        .route("/{code}/member", get(list_member))
+        .route("/{code}/member", post(save_member))
+        .route("/{code}/member/{user_id}", delete(delete_member))
 }
 
 
@@ -19,7 +26,7 @@ async fn add_project(
     claims: Claims,
     Json(project): Json<Project>,
 ) -> (StatusCode, Json<Response<String>>) {
-    let result = autofac::get_global_app_state_ref().get_project_service().add_project(project).await;
+    let result = autofac::get_global_app_state_ref().get_project_service().add_project(claims.sub, project).await;
     match result {
         Ok(code) => (StatusCode::OK, Json(Response::success(code))),
         Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
@@ -31,7 +38,7 @@ async fn list_project(
     claims: Claims,
     Query(params): Query<PageQuery>,
 ) -> (StatusCode, Json<Response<Vec<Project>>>) {
-    let result = autofac::get_global_app_state_ref().get_project_service().list_project(params).await;
+    let result = autofac::get_global_app_state_ref().get_project_service().list_project(claims.sub, params).await;
     match result {
         Ok(projects) => (StatusCode::OK, Json(Response::success(projects))),
         Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
@@ -42,11 +49,13 @@ async fn list_project(
 async fn delete_project(
     claims: Claims,
     Path(code): Path<String>,
-) -> (StatusCode, Json<Response<String>>) {
-  let result = autofac::get_global_app_state_ref().get_project_service().del_project(code).await;
+    Query(params): Query<ProjectDeleteQuery>,
+) -> Result<(StatusCode, Json<Response<String>>), AuthError> {
+  authorize(&claims, &code, ProjectRole::Owner).await?;
+  let result = autofac::get_global_app_state_ref().get_project_service().del_project(code, params).await;
   match result {
-    Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
-    Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    Ok(_) => Ok((StatusCode::OK, Json(Response::success("".to_string())))),
+    Err(e) => Ok((StatusCode::OK, Json(Response::error(e.to_string())))),
   }
 }
 
@@ -54,11 +63,25 @@ async fn delete_project(
 async fn update_project(
     claims: Claims,
     Json(project): Json<Project>,
-) -> (StatusCode, Json<Response<String>>) {
+) -> Result<(StatusCode, Json<Response<String>>), AuthError> {
+  authorize(&claims, &project.code, ProjectRole::Owner).await?;
   let result = autofac::get_global_app_state_ref().get_project_service().edit_project(project).await;
   match result {
-    Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
-    Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    Ok(_) => Ok((StatusCode::OK, Json(Response::success("".to_string())))),
+    Err(e) => Ok((StatusCode::OK, Json(Response::error(e.to_string())))),
+  }
+}
+
+#[debug_handler]
+async fn retry_provision(
+    claims: Claims,
+    Path(code): Path<String>,
+) -> Result<(StatusCode, Json<Response<String>>), AuthError> {
+  authorize(&claims, &code, ProjectRole::Owner).await?;
+  let result = autofac::get_global_app_state_ref().get_project_service().retry_provision(code).await;
+  match result {
+    Ok(_) => Ok((StatusCode::OK, Json(Response::success("".to_string())))),
+    Err(e) => Ok((StatusCode::OK, Json(Response::error(e.to_string())))),
   }
 }
 
@@ -66,13 +89,73 @@ async fn update_project(
 async fn detail_project(
     claims: Claims,
     Path(code): Path<String>,
-) -> (StatusCode, Json<Response<Project>>) {
+) -> Result<(StatusCode, Json<Response<Project>>), AuthError> {
+  let role = authorize(&claims, &code, ProjectRole::Viewer).await?;
   let result = autofac::get_global_app_state_ref().get_project_service().get_project(code).await;
   match result {
-    Ok(project) => (StatusCode::OK, Json(Response::success(project))),
-    Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    Ok(project) => Ok((StatusCode::OK, Json(Response::success(Project { role: Some(role), ..project })))),
+    Err(e) => Ok((StatusCode::OK, Json(Response::error(e.to_string())))),
   }
 }
 
+// 切换当前项目，校验成员身份后重新签发包含项目编码的 token
+#[debug_handler]
+async fn switch_project(
+    claims: Claims,
+    jar: CookieJar,
+    Path(code): Path<String>,
+) -> Result<(StatusCode, CookieJar, Json<Response<Project>>), AuthError> {
+    let role = authorize(&claims, &code, ProjectRole::Viewer).await?;
+    let result = autofac::get_global_app_state_ref().get_project_service().switch_project(code.clone()).await;
+    match result {
+        Ok(project) => {
+            let previous = jar.get(REFRESH_COOKIE).map(|cookie| cookie.value().to_string());
+            let jar = issue_session(jar, claims.sub, code, previous).await?;
+            Ok((StatusCode::OK, jar, Json(Response::success(Project { role: Some(role), ..project }))))
+        }
+        Err(e) => Ok((StatusCode::OK, jar, Json(Response::error(e.to_string())))),
+    }
+}
+
+#[debug_handler]
+async fn list_member(
+    claims: Claims,
+    Path(code): Path<String>,
+) -> Result<(StatusCode, Json<Response<Vec<ProjectMember>>>), AuthError> {
+    authorize(&claims, &code, ProjectRole::Viewer).await?;
+    let result = autofac::get_global_app_state_ref().get_project_service().list_member(code).await;
+    match result {
+        Ok(members) => Ok((StatusCode::OK, Json(Response::success(members)))),
+        Err(e) => Ok((StatusCode::OK, Json(Response::error(e.to_string())))),
+    }
+}
+
+#[debug_handler]
+async fn save_member(
+    claims: Claims,
+    Path(code): Path<String>,
+    Json(form): Json<ProjectMemberForm>,
+) -> Result<(StatusCode, Json<Response<String>>), AuthError> {
+    authorize(&claims, &code, ProjectRole::Owner).await?;
+    let result = autofac::get_global_app_state_ref().get_project_service().save_member(code, form).await;
+    match result {
+        Ok(_) => Ok((StatusCode::OK, Json(Response::success("".to_string())))),
+        Err(e) => Ok((StatusCode::OK, Json(Response::error(e.to_string())))),
+    }
+}
+
+#[debug_handler]
+async fn delete_member(
+    claims: Claims,
+    Path((code, user_id)): Path<(String, String)>,
+) -> Result<(StatusCode, Json<Response<String>>), AuthError> {
+    authorize(&claims, &code, ProjectRole::Owner).await?;
+    let result = autofac::get_global_app_state_ref().get_project_service().del_member(code, user_id).await;
+    match result {
+        Ok(_) => Ok((StatusCode::OK, Json(Response::success("".to_string())))),
+        Err(e) => Ok((StatusCode::OK, Json(Response::error(e.to_string())))),
+    }
+}
+
 
 
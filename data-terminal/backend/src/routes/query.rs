@@ -0,0 +1,71 @@
+use axum::{extract::{Path, Query}, http::StatusCode, routing::{delete, get, post}, Json, Router, debug_handler};
+use super::permission::{Authorized, Viewer};
+use crate::{autofac, models::query::{QueryHistory, QueryRequest, QueryResult}};
+use crate::models::web::{Response, PageQuery};
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/reserve", post(reserve_query))
+        .route("/execute", post(execute_query))
+        .route("/{id}/cancel", post(cancel_query))
+        .route("/history", get(list_query_history))
+        .route("/history", delete(clear_query_history))
+}
+
+#[debug_handler]
+async fn execute_query(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Json(request): Json<QueryRequest>,
+) -> (StatusCode, Json<Response<QueryResult>>) {
+    let result = autofac::get_global_app_state_ref().get_query_service().execute_query(claims.project, claims.sub, request).await;
+    match result {
+        Ok(result) => (StatusCode::OK, Json(Response::success(result))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn reserve_query(
+    Authorized { claims, .. }: Authorized<Viewer>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_query_service().reserve_query(claims.project, claims.sub).await;
+    match result {
+        Ok(query_id) => (StatusCode::OK, Json(Response::success(query_id))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn cancel_query(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_query_service().cancel_query(claims.project, claims.sub, id).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_query_history(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Query(params): Query<PageQuery>,
+) -> (StatusCode, Json<Response<Vec<QueryHistory>>>) {
+    let result = autofac::get_global_app_state_ref().get_query_service().list_query_history(claims.project, claims.sub, params).await;
+    match result {
+        Ok(history) => (StatusCode::OK, Json(Response::success(history))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn clear_query_history(
+    Authorized { claims, .. }: Authorized<Viewer>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_query_service().clear_query_history(claims.project, claims.sub).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
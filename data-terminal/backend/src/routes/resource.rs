@@ -1,6 +1,7 @@
 use axum::{extract::{Path, Query}, http::StatusCode, routing::{delete, get, post}, Json, Router, debug_handler};
-use super::jwt::Claims;
+use super::permission::{AdminClaims, ResourceReader};
 use crate::{autofac, models::resource::{Resource, ResourceReadOnly, ResourceCreateUpdate}};
+use crate::models::mask_secrets;
 use crate::models::web::{Response, PageQuery};
 
 pub fn routes() -> Router {
@@ -14,7 +15,7 @@ pub fn routes() -> Router {
 
 #[debug_handler]
 async fn add_resource(
-    claims: Claims,
+    AdminClaims(claims): AdminClaims,
     Json(resource): Json<ResourceCreateUpdate>,
 ) -> (StatusCode, Json<Response<String>>) {
     let result = autofac::get_global_app_state_ref().get_resource_service().add_resource(resource).await;
@@ -26,19 +27,24 @@ async fn add_resource(
 
 #[debug_handler]
 async fn list_resource(
-    claims: Claims,
+    reader: ResourceReader,
     Query(params): Query<PageQuery>,
 ) -> (StatusCode, Json<Response<Vec<ResourceReadOnly>>>) {
     let result = autofac::get_global_app_state_ref().get_resource_service().list_resource(params).await;
     match result {
-        Ok(resources) => (StatusCode::OK, Json(Response::success(resources))),
+        Ok(mut resources) => {
+            if !reader.is_admin {
+                resources.iter_mut().for_each(|r| mask_secrets(&mut r.config));
+            }
+            (StatusCode::OK, Json(Response::success(resources)))
+        }
         Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
     }
 }
 
 #[debug_handler]
 async fn delete_resource(
-    claims: Claims,
+    AdminClaims(claims): AdminClaims,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<Response<String>>) {
     let result = autofac::get_global_app_state_ref().get_resource_service().del_resource(id).await;
@@ -50,7 +56,7 @@ async fn delete_resource(
 
 #[debug_handler]
 async fn update_resource(
-    claims: Claims,
+    AdminClaims(claims): AdminClaims,
     Json(resource): Json<ResourceCreateUpdate>,
 ) -> (StatusCode, Json<Response<String>>) {
     let result = autofac::get_global_app_state_ref().get_resource_service().edit_resource(resource).await;
@@ -62,12 +68,17 @@ async fn update_resource(
 
 #[debug_handler]
 async fn detail_resource(
-    claims: Claims,
+    reader: ResourceReader,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<Response<ResourceReadOnly>>) {
     let result = autofac::get_global_app_state_ref().get_resource_service().get_resource(id).await;
     match result {
-        Ok(resource) => (StatusCode::OK, Json(Response::success(resource))),
+        Ok(mut resource) => {
+            if !reader.is_admin {
+                mask_secrets(&mut resource.config);
+            }
+            (StatusCode::OK, Json(Response::success(resource)))
+        }
         Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
     }
 } 
\ No newline at end of file
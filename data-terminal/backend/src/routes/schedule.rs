@@ -0,0 +1,75 @@
+use axum::{extract::{Path, Query}, http::StatusCode, routing::{delete, get, post}, Json, Router, debug_handler};
+use super::jwt::Claims;
+use super::permission::{Authorized, Developer, Viewer};
+use crate::autofac;
+use crate::models::schedule::{TaskScheduleReadOnly, TaskScheduleCreateUpdate, SchedulePreviewQuery};
+use crate::models::web::Response;
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/list", get(list_schedule))
+        .route("/preview", get(preview_schedule))
+        .route("/{task_id}", get(detail_schedule))
+        .route("/{task_id}", post(save_schedule))
+        .route("/{task_id}", delete(delete_schedule))
+}
+
+#[debug_handler]
+async fn list_schedule(
+    Authorized { claims, .. }: Authorized<Viewer>,
+) -> (StatusCode, Json<Response<Vec<TaskScheduleReadOnly>>>) {
+    let result = autofac::get_global_app_state_ref().get_schedule_service().list_schedule(claims.project).await;
+    match result {
+        Ok(schedules) => (StatusCode::OK, Json(Response::success(schedules))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn preview_schedule(
+    _claims: Claims,
+    Query(query): Query<SchedulePreviewQuery>,
+) -> (StatusCode, Json<Response<Vec<String>>>) {
+    let result = autofac::get_global_app_state_ref().get_schedule_service().preview_schedule(query).await;
+    match result {
+        Ok(times) => (StatusCode::OK, Json(Response::success(times))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn detail_schedule(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(task_id): Path<String>,
+) -> (StatusCode, Json<Response<Option<TaskScheduleReadOnly>>>) {
+    let result = autofac::get_global_app_state_ref().get_schedule_service().get_schedule(claims.project, task_id).await;
+    match result {
+        Ok(schedule) => (StatusCode::OK, Json(Response::success(schedule))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn save_schedule(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(task_id): Path<String>,
+    Json(schedule): Json<TaskScheduleCreateUpdate>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_schedule_service().save_schedule(claims.project, task_id, schedule).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn delete_schedule(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(task_id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_schedule_service().del_schedule(claims.project, task_id).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
@@ -0,0 +1,166 @@
+use axum::{extract::{Path, Query}, http::StatusCode, routing::{get, post}, Json, Router, debug_handler};
+use axum::extract::ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade};
+use axum::response::IntoResponse;
+use tokio::sync::broadcast::error::RecvError;
+use super::permission::{Authorized, Developer, Viewer};
+use crate::autofac;
+use crate::models::task_run::{RunEvent, TaskRunReadOnly, TaskRunQuery, TaskRunLogQuery, TaskRunLogPage, TaskRunLogReadOnly};
+use crate::models::web::Response;
+use crate::services::run_log;
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/list", get(list_run))
+        .route("/{id}", get(detail_run))
+        .route("/{id}/log", get(run_log))
+        .route("/{id}/ws", get(run_log_ws))
+        .route("/{id}/cancel", post(cancel_run))
+}
+
+#[debug_handler]
+async fn list_run(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Query(query): Query<TaskRunQuery>,
+) -> (StatusCode, Json<Response<Vec<TaskRunReadOnly>>>) {
+    let result = autofac::get_global_app_state_ref().get_task_run_service().list_run(claims.project, query).await;
+    match result {
+        Ok(runs) => (StatusCode::OK, Json(Response::success(runs))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn detail_run(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<TaskRunReadOnly>>) {
+    let result = autofac::get_global_app_state_ref().get_task_run_service().get_run(claims.project, id).await;
+    match result {
+        Ok(run) => (StatusCode::OK, Json(Response::success(run))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn run_log(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+    Query(query): Query<TaskRunLogQuery>,
+) -> (StatusCode, Json<Response<TaskRunLogPage>>) {
+    let result = autofac::get_global_app_state_ref().get_task_run_service().get_run_log(claims.project, id, query).await;
+    match result {
+        Ok(page) => (StatusCode::OK, Json(Response::success(page))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn cancel_run(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_task_run_service().cancel_run(claims.project, id).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+// 实时推送运行日志与状态：先回放 after 之后的日志和当前状态，运行中则继续推送直到结束
+#[debug_handler]
+async fn run_log_ws(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+    Query(query): Query<TaskRunLogQuery>,
+    ws: WebSocketUpgrade,
+) -> axum::response::Response {
+    let result = autofac::get_global_app_state_ref().get_task_run_service().get_run(claims.project.clone(), id).await;
+    match result {
+        Ok(run) => ws.on_upgrade(move |socket| stream_run_log(socket, claims.project, run, query.after.unwrap_or(0))),
+        Err(e) => (StatusCode::OK, Json(Response::<()>::error(e.to_string()))).into_response(),
+    }
+}
+
+async fn stream_run_log(mut socket: WebSocket, project_code: String, run: TaskRunReadOnly, after: i64) {
+    let service = autofac::get_global_app_state_ref().get_task_run_service();
+    // 先订阅再读库，订阅之后产生的日志都会通过事件送达
+    let live = run_log::subscribe(&project_code, &run.id).await;
+
+    let mut last = after;
+    loop {
+        let query = TaskRunLogQuery { after: Some(last), limit: None };
+        let page = match service.get_run_log(project_code.clone(), run.id.clone(), query).await {
+            Ok(page) => page,
+            Err(e) => {
+                close(socket, close_code::ERROR, &e.to_string()).await;
+                return;
+            }
+        };
+        if page.lines.is_empty() {
+            break;
+        }
+        for line in page.lines {
+            last = line.seq;
+            if !send(&mut socket, &RunEvent::Log(line)).await {
+                return;
+            }
+        }
+    }
+
+    let Some((mut events, pending)) = live else {
+        // 运行已结束，推送最终状态后关闭
+        let run = service.get_run(project_code, run.id).await.unwrap_or(run);
+        if send(&mut socket, &RunEvent::Status(run)).await {
+            close(socket, close_code::NORMAL, "").await;
+        }
+        return;
+    };
+    for log in pending.into_iter().filter(|l| l.seq > last) {
+        last = log.seq;
+        if !send(&mut socket, &RunEvent::Log(TaskRunLogReadOnly::from(log))).await {
+            return;
+        }
+    }
+    if !send(&mut socket, &RunEvent::Status(run)).await {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(RunEvent::Log(line)) if line.seq <= last => {}
+                Ok(event) => {
+                    if let RunEvent::Log(line) = &event {
+                        last = line.seq;
+                    }
+                    if !send(&mut socket, &event).await {
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(_)) => {
+                    // 客户端消费过慢，关闭连接，由客户端带上 after 重新订阅
+                    close(socket, close_code::AGAIN, &format!("lagged behind after {}", last)).await;
+                    return;
+                }
+                Err(RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    close(socket, close_code::NORMAL, "").await;
+}
+
+async fn send(socket: &mut WebSocket, event: &RunEvent) -> bool {
+    match serde_json::to_string(event) {
+        Ok(text) => socket.send(Message::Text(text.into())).await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+async fn close(mut socket: WebSocket, code: u16, reason: &str) {
+    let frame = CloseFrame { code, reason: reason.to_string().into() };
+    let _ = socket.send(Message::Close(Some(frame))).await;
+}
@@ -0,0 +1,127 @@
+use axum::{extract::{Path, Query}, http::StatusCode, routing::{delete, get, post}, Json, Router, debug_handler};
+use super::permission::{Authorized, Developer, Viewer};
+use crate::{autofac, models::transform::{TransformTaskReadOnly, TransformTaskCreateUpdate, TransformCheck, TransformRunQuery}};
+use crate::models::task_run::TaskRunReadOnly;
+use crate::models::web::{Response, PageQuery};
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/add", post(add_transform))
+        .route("/update", post(update_transform))
+        .route("/check", post(check_transform))
+        .route("/list", get(list_transform))
+        .route("/{id}", get(detail_transform))
+        .route("/{id}", delete(delete_transform))
+        .route("/{id}/run", post(run_transform))
+        .route("/{id}/runs", get(list_transform_run))
+        .route("/{id}/stop", post(stop_transform))
+}
+
+#[debug_handler]
+async fn add_transform(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Json(task): Json<TransformTaskCreateUpdate>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_transform_service().add_transform(claims.project, task).await;
+    match result {
+        Ok(id) => (StatusCode::OK, Json(Response::success(id))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn update_transform(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Json(task): Json<TransformTaskCreateUpdate>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_transform_service().edit_transform(claims.project, task).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn check_transform(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Json(task): Json<TransformTaskCreateUpdate>,
+) -> (StatusCode, Json<Response<TransformCheck>>) {
+    let result = autofac::get_global_app_state_ref().get_transform_service().check_transform(claims.project, task).await;
+    match result {
+        Ok(check) => (StatusCode::OK, Json(Response::success(check))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_transform(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Query(params): Query<PageQuery>,
+) -> (StatusCode, Json<Response<Vec<TransformTaskReadOnly>>>) {
+    let result = autofac::get_global_app_state_ref().get_transform_service().list_transform(claims.project, params).await;
+    match result {
+        Ok(tasks) => (StatusCode::OK, Json(Response::success(tasks))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn detail_transform(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<TransformTaskReadOnly>>) {
+    let result = autofac::get_global_app_state_ref().get_transform_service().get_transform(claims.project, id).await;
+    match result {
+        Ok(task) => (StatusCode::OK, Json(Response::success(task))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn delete_transform(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_transform_service().del_transform(claims.project, id).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn run_transform(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+    Query(params): Query<TransformRunQuery>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_transform_service().run_transform(claims.project, id, params.biz_date).await;
+    match result {
+        Ok(run_id) => (StatusCode::OK, Json(Response::success(run_id))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn stop_transform(
+    Authorized { claims, .. }: Authorized<Developer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_transform_service().stop_transform(claims.project, id).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_transform_run(
+    Authorized { claims, .. }: Authorized<Viewer>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<Vec<TaskRunReadOnly>>>) {
+    let result = autofac::get_global_app_state_ref().get_transform_service().list_transform_run(claims.project, id).await;
+    match result {
+        Ok(runs) => (StatusCode::OK, Json(Response::success(runs))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
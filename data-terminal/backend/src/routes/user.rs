@@ -0,0 +1,113 @@
+use axum::{extract::{Path, Query}, http::StatusCode, routing::{delete, get, post}, Json, Router, debug_handler};
+use super::jwt::Claims;
+use crate::autofac;
+use crate::models::user::{PasswordChangeRequest, User, UserForm, UserOption};
+use crate::models::web::{Response, PageQuery};
+
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/me", get(current_user))
+        .route("/password", post(change_password))
+        .route("/add", post(add_user))
+        .route("/update", post(update_user))
+        .route("/list", get(list_user))
+        .route("/options", get(list_user_option))
+        .route("/{id}/unlock", post(unlock_user))
+        .route("/{id}", delete(delete_user))
+}
+
+
+#[debug_handler]
+async fn current_user(
+    claims: Claims,
+) -> (StatusCode, Json<Response<User>>) {
+    let result = autofac::get_global_app_state_ref().get_user_service().get_current_user(claims.sub).await;
+    match result {
+        Ok(user) => (StatusCode::OK, Json(Response::success(user))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn change_password(
+    claims: Claims,
+    Json(request): Json<PasswordChangeRequest>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_user_service().change_password(claims.sub, request).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn add_user(
+    claims: Claims,
+    Json(form): Json<UserForm>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_user_service().add_user(claims.sub, form).await;
+    match result {
+        Ok(id) => (StatusCode::OK, Json(Response::success(id))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn update_user(
+    claims: Claims,
+    Json(form): Json<UserForm>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_user_service().edit_user(claims.sub, form).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_user(
+    claims: Claims,
+    Query(params): Query<PageQuery>,
+) -> (StatusCode, Json<Response<Vec<User>>>) {
+    let result = autofac::get_global_app_state_ref().get_user_service().list_user(claims.sub, params).await;
+    match result {
+        Ok(users) => (StatusCode::OK, Json(Response::success(users))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn list_user_option(
+    _claims: Claims,
+) -> (StatusCode, Json<Response<Vec<UserOption>>>) {
+    let result = autofac::get_global_app_state_ref().get_user_service().list_user_option().await;
+    match result {
+        Ok(users) => (StatusCode::OK, Json(Response::success(users))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn unlock_user(
+    claims: Claims,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_user_service().unlock_user(claims.sub, id).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
+
+#[debug_handler]
+async fn delete_user(
+    claims: Claims,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Response<String>>) {
+    let result = autofac::get_global_app_state_ref().get_user_service().del_user(claims.sub, id).await;
+    match result {
+        Ok(_) => (StatusCode::OK, Json(Response::success("".to_string()))),
+        Err(e) => (StatusCode::OK, Json(Response::error(e.to_string()))),
+    }
+}
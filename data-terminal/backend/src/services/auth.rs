@@ -0,0 +1,174 @@
+use crate::repositories::{AuthRepo, UserRepo};
+use crate::models::Error;
+use crate::models::auth::{IssuedRefreshToken, RefreshToken};
+use crate::models::user::UserStatus;
+use crate::utils::config::Setting;
+use crate::autofac;
+use sha2::{Digest, Sha256};
+use shaku::Provider;
+use async_trait::async_trait;
+use super::AuthService;
+
+// 刚被轮换的刷新 token 在该时间内的首次再使用视为并发刷新，其余再使用视为重放
+const REUSE_GRACE_SECS: i64 = 30;
+
+
+#[derive(Provider)]
+#[shaku(interface = AuthService)]
+pub struct AuthServiceImpl {
+    #[shaku(provide)]
+    repo: Box<dyn AuthRepo>,
+    #[shaku(provide)]
+    user_repo: Box<dyn UserRepo>,
+}
+
+impl AuthServiceImpl {
+    // 生成新的刷新 token 并保存摘要，返回 token 原文
+    async fn save_refresh_token(&self, id: String, user_id: String, family_id: String, project_code: String) -> Result<String, Error> {
+        let token = generate_token();
+        let record = RefreshToken {
+            id,
+            user_id,
+            family_id,
+            token_hash: hash_token(&token),
+            project_code,
+            expires_at: chrono::Utc::now() + chrono::Duration::seconds(Setting::get().jwt.refresh_expires),
+            ..Default::default()
+        };
+        self.repo.add_refresh_token(record).await?;
+        Ok(token)
+    }
+
+    // 已作废的 token 被再次使用：宽限期内的首次再使用视为并发刷新，只拒绝不签发新 token；
+    // 其余情况可能是 token 已泄露，作废同一族的所有 token
+    async fn reject_reuse(&self, record: &RefreshToken) -> Error {
+        let concurrent = record.replaced_by.is_some()
+            && record
+                .revoked_at
+                .is_some_and(|t| chrono::Utc::now() - t <= chrono::Duration::seconds(REUSE_GRACE_SECS));
+        if concurrent {
+            match self.repo.mark_token_reused(record.id.clone()).await {
+                Ok(true) => return Error::RefreshConflict,
+                Ok(false) => {}
+                Err(e) => return e,
+            }
+        }
+        if let Err(e) = self.repo.revoke_token_family(record.family_id.clone()).await {
+            return e;
+        }
+        tracing::warn!("用户 {} 重复使用已作废的刷新 token，已作废该登录会话", record.user_id);
+        Error::InvalidSession
+    }
+
+    // 未作废且未过期的刷新 token
+    async fn find_active_token(&self, token: &str) -> Result<Option<RefreshToken>, Error> {
+        let record = self.repo.find_refresh_token(hash_token(token)).await?;
+        Ok(record.filter(|r| r.revoked_at.is_none() && r.expires_at > chrono::Utc::now()))
+    }
+}
+
+
+#[async_trait]
+impl AuthService for AuthServiceImpl {
+    async fn create_refresh_token(&self, user_id: String, project_code: String, previous: Option<String>) -> Result<String, Error> {
+        // 切换项目时沿用原来的 token 族，登录时开始新的一族
+        let previous = match previous {
+            Some(token) => self.find_active_token(&token).await?.filter(|r| r.user_id == user_id),
+            None => None,
+        };
+        let family_id = previous.as_ref().map(|r| r.family_id.clone()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let id = uuid::Uuid::new_v4().to_string();
+        let token = self.save_refresh_token(id.clone(), user_id, family_id, project_code).await?;
+        if let Some(previous) = previous {
+            self.repo.revoke_refresh_token(previous.id, Some(id)).await?;
+        }
+        Ok(token)
+    }
+
+    async fn rotate_refresh_token(&self, token: String) -> Result<IssuedRefreshToken, Error> {
+        let Some(record) = self.repo.find_refresh_token(hash_token(&token)).await? else {
+            return Err(Error::InvalidSession);
+        };
+        let now = chrono::Utc::now();
+        if record.expires_at <= now {
+            return Err(Error::InvalidSession);
+        }
+        if record.revoked_at.is_some() {
+            return Err(self.reject_reuse(&record).await);
+        }
+
+        let user = match self.user_repo.get_user(record.user_id.clone()).await {
+            Ok(user) => user,
+            Err(Error::NotFound) => return Err(Error::InvalidSession),
+            Err(e) => return Err(e),
+        };
+        if user.status != UserStatus::Active {
+            self.repo.revoke_token_family(record.family_id.clone()).await?;
+            return Err(Error::AccountDisabled);
+        }
+
+        // 先原子地作废旧 token，并发请求中只有一个能完成轮换，避免同一族分叉
+        let id = uuid::Uuid::new_v4().to_string();
+        if !self.repo.replace_refresh_token(record.id.clone(), id.clone()).await? {
+            let record = self.repo.find_refresh_token(record.token_hash.clone()).await?.ok_or(Error::InvalidSession)?;
+            return Err(self.reject_reuse(&record).await);
+        }
+        let token = self.save_refresh_token(id, record.user_id.clone(), record.family_id.clone(), record.project_code.clone()).await?;
+        Ok(IssuedRefreshToken {
+            token,
+            user_id: record.user_id,
+            project_code: record.project_code,
+        })
+    }
+
+    async fn revoke_refresh_token(&self, token: String) -> Result<(), Error> {
+        if let Some(record) = self.repo.find_refresh_token(hash_token(&token)).await? {
+            self.repo.revoke_token_family(record.family_id).await?;
+        }
+        Ok(())
+    }
+
+    async fn revoke_access_token(&self, jti: String, user_id: String, expires_at: chrono::DateTime<chrono::Utc>) -> Result<(), Error> {
+        if jti.is_empty() || expires_at <= chrono::Utc::now() {
+            return Ok(());
+        }
+        self.repo.add_revoked_token(jti, user_id, expires_at).await
+    }
+
+    async fn revoke_user_session(&self, user_id: String) -> Result<(), Error> {
+        self.repo.revoke_user_token(user_id).await
+    }
+
+    async fn is_revoked(&self, jti: String, user_id: String) -> Result<bool, Error> {
+        self.repo.is_revoked(jti, user_id).await
+    }
+
+    async fn purge_expired(&self) -> Result<u64, Error> {
+        self.repo.purge_expired(chrono::Utc::now()).await
+    }
+}
+
+// 刷新 token 原文只返回给浏览器，服务端保存摘要
+fn generate_token() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+// 定期清理过期的刷新 token 和注销记录
+pub fn spawn_token_purge() {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(3600));
+        loop {
+            ticker.tick().await;
+            let result = autofac::get_global_app_state_ref().get_auth_service().purge_expired().await;
+            match result {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("purged {} expired auth tokens", count),
+                Err(e) => tracing::warn!("failed to purge auth tokens: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,290 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::repositories::{CatalogRepo, CollectionRepo, DataSourceRepo, ResourceRepo, TransformRepo};
+use crate::models::Error;
+use crate::models::web::PageQuery;
+use crate::models::catalog::{
+    CatalogColumn, CatalogDrift, CatalogSearchHit, CatalogSearchQuery, CatalogSnapshot, CatalogSnapshotDetail, CatalogSourceType, CatalogTable,
+    CatalogTableDetail, DriftType, SnapshotResult,
+};
+use crate::models::datasource::DataSourceType;
+use crate::models::metadata::TableDetail;
+use crate::models::resource::ResourceType;
+use shaku::Provider;
+use async_trait::async_trait;
+use super::CatalogService;
+use super::metadata;
+use uuid::Uuid;
+
+#[derive(Provider)]
+#[shaku(interface = CatalogService)]
+pub struct CatalogServiceImpl {
+    #[shaku(provide)]
+    repo: Box<dyn CatalogRepo>,
+    #[shaku(provide)]
+    datasource_repo: Box<dyn DataSourceRepo>,
+    #[shaku(provide)]
+    resource_repo: Box<dyn ResourceRepo>,
+    #[shaku(provide)]
+    collection_repo: Box<dyn CollectionRepo>,
+    #[shaku(provide)]
+    transform_repo: Box<dyn TransformRepo>,
+}
+
+#[async_trait]
+impl CatalogService for CatalogServiceImpl {
+    async fn snapshot_source(&self, project_code: String, source_type: CatalogSourceType, source_id: String) -> Result<SnapshotResult, Error> {
+        let (source_name, tables) = match source_type {
+            CatalogSourceType::Datasource => {
+                let datasource = match self.datasource_repo.get_datasource(project_code.clone(), source_id.clone()).await {
+                    Ok(datasource) => datasource,
+                    Err(Error::NotFound) => return Err(Error::InvalidValue(format!("datasource {} does not exist", source_id))),
+                    Err(e) => return Err(e),
+                };
+                (datasource.name.clone(), metadata::introspect_datasource(&datasource).await?)
+            }
+            CatalogSourceType::Resource => {
+                let resource = match self.resource_repo.get_resource(source_id.clone()).await {
+                    Ok(resource) => resource,
+                    Err(Error::NotFound) => return Err(Error::InvalidValue(format!("resource {} does not exist", source_id))),
+                    Err(e) => return Err(e),
+                };
+                (resource.name.clone(), metadata::introspect_resource(&resource).await?)
+            }
+        };
+        self.save_snapshot(project_code, source_type, source_id, source_name, tables).await
+    }
+
+    async fn snapshot_project(&self, project_code: String) -> Result<usize, Error> {
+        let sources = self.project_sources(&project_code).await?;
+        let mut keep_ids = Vec::new();
+        for (source_type, source_id) in sources.iter() {
+            match self.snapshot_source(project_code.clone(), *source_type, source_id.clone()).await {
+                Ok(result) => keep_ids.push(result.snapshot.id),
+                Err(e) => {
+                    // 暂时无法连接的来源保留上一次的快照
+                    tracing::warn!("catalog snapshot of {} {} in project {} failed: {}", source_type, source_id, project_code, e);
+                    if let Some(latest) = self.repo.latest_snapshot(project_code.clone(), *source_type, source_id.clone()).await? {
+                        keep_ids.push(latest.id);
+                    }
+                }
+            }
+        }
+        // 已删除或不再被引用的来源不再出现在搜索结果中，历史版本保留
+        self.repo.retire_snapshot(project_code, keep_ids).await?;
+        Ok(sources.len())
+    }
+
+    async fn list_latest_snapshot(&self, project_code: String) -> Result<Vec<CatalogSnapshot>, Error> {
+        self.repo.list_latest_snapshot(project_code).await
+    }
+
+    async fn list_snapshot(&self, project_code: String, source_type: CatalogSourceType, source_id: String, params: PageQuery) -> Result<Vec<CatalogSnapshot>, Error> {
+        self.repo.list_snapshot(project_code, source_type, source_id, params).await
+    }
+
+    async fn get_snapshot(&self, project_code: String, id: String) -> Result<CatalogSnapshotDetail, Error> {
+        let snapshot = self.repo.get_snapshot(project_code.clone(), id.clone()).await?;
+        let tables = self.repo.list_snapshot_table(project_code.clone(), id.clone()).await?;
+        let mut columns = group_columns(self.repo.list_snapshot_column(project_code, id).await?);
+        let tables = tables
+            .into_iter()
+            .map(|table| {
+                let columns = columns.remove(&(table.schema_name.clone(), table.table_name.clone())).unwrap_or_default();
+                CatalogTableDetail { table, columns }
+            })
+            .collect();
+        Ok(CatalogSnapshotDetail { snapshot, tables })
+    }
+
+    async fn list_drift(&self, project_code: String, source_type: CatalogSourceType, source_id: String, params: PageQuery) -> Result<Vec<CatalogDrift>, Error> {
+        self.repo.list_drift(project_code, source_type, source_id, params).await
+    }
+
+    async fn search_catalog(&self, project_code: String, query: CatalogSearchQuery) -> Result<Vec<CatalogSearchHit>, Error> {
+        self.repo.search(project_code, query).await
+    }
+}
+
+impl CatalogServiceImpl {
+    // 项目的元数据来源：MySQL、PostgreSQL 数据源，以及采集任务与转换任务写入的数据库资源
+    async fn project_sources(&self, project_code: &str) -> Result<Vec<(CatalogSourceType, String)>, Error> {
+        let mut sources = Vec::new();
+        for datasource in self.datasource_repo.list_all_datasource(project_code.to_string()).await? {
+            if matches!(datasource.datasource_type, DataSourceType::Mysql | DataSourceType::Postgres) {
+                sources.push((CatalogSourceType::Datasource, datasource.id));
+            }
+        }
+
+        let mut resource_ids = BTreeSet::new();
+        for task in self.collection_repo.list_all_collection(project_code.to_string()).await? {
+            resource_ids.insert(task.resource_id);
+        }
+        for task in self.transform_repo.list_all_transform(project_code.to_string()).await? {
+            resource_ids.insert(task.resource_id);
+        }
+        for resource_id in resource_ids.into_iter().filter(|id| !id.is_empty()) {
+            match self.resource_repo.get_resource(resource_id.clone()).await {
+                Ok(resource) if matches!(resource.resource_type, ResourceType::Mysql | ResourceType::Postgres | ResourceType::Doris) => {
+                    sources.push((CatalogSourceType::Resource, resource.id));
+                }
+                Ok(_) | Err(Error::NotFound) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(sources)
+    }
+
+    // 与最新版本比较，结构有变化时写入新版本与变更记录，否则只更新检查时间
+    async fn save_snapshot(
+        &self,
+        project_code: String,
+        source_type: CatalogSourceType,
+        source_id: String,
+        source_name: String,
+        introspected: Vec<TableDetail>,
+    ) -> Result<SnapshotResult, Error> {
+        let now = chrono::Utc::now();
+        let snapshot_id = Uuid::new_v4().to_string();
+        let (tables, columns) = to_catalog_rows(&snapshot_id, introspected);
+
+        let latest = self.repo.latest_snapshot(project_code.clone(), source_type, source_id.clone()).await?;
+        let (version, drifts) = match &latest {
+            Some(latest) => {
+                let previous_tables = self.repo.list_snapshot_table(project_code.clone(), latest.id.clone()).await?;
+                let previous_columns = self.repo.list_snapshot_column(project_code.clone(), latest.id.clone()).await?;
+                (latest.version + 1, diff(&previous_tables, previous_columns, &tables, &columns))
+            }
+            None => (1, Vec::new()),
+        };
+
+        if let Some(mut latest) = latest.filter(|_| drifts.is_empty()) {
+            self.repo.touch_snapshot(project_code, latest.id.clone(), source_name.clone(), now).await?;
+            latest.checked_at = now;
+            latest.source_name = source_name;
+            latest.is_latest = true;
+            return Ok(SnapshotResult { snapshot: latest, changed: false, drifts });
+        }
+
+        let drifts: Vec<CatalogDrift> = drifts
+            .into_iter()
+            .map(|drift| CatalogDrift {
+                id: Uuid::new_v4().to_string(),
+                snapshot_id: snapshot_id.clone(),
+                source_type,
+                source_id: source_id.clone(),
+                from_version: version - 1,
+                to_version: version,
+                detected_at: now,
+                ..drift
+            })
+            .collect();
+        let snapshot = CatalogSnapshot {
+            id: snapshot_id,
+            source_type,
+            source_id,
+            source_name,
+            version,
+            is_latest: true,
+            table_count: tables.len() as i32,
+            column_count: columns.len() as i32,
+            drift_count: drifts.len() as i32,
+            created_at: now,
+            checked_at: now,
+        };
+        self.repo.add_snapshot(project_code, snapshot.clone(), tables, columns, drifts.clone()).await?;
+        Ok(SnapshotResult { snapshot, changed: true, drifts })
+    }
+}
+
+fn to_catalog_rows(snapshot_id: &str, introspected: Vec<TableDetail>) -> (Vec<CatalogTable>, Vec<CatalogColumn>) {
+    let mut tables = Vec::with_capacity(introspected.len());
+    let mut columns = Vec::new();
+    for table in introspected {
+        for (i, column) in table.columns.into_iter().enumerate() {
+            columns.push(CatalogColumn {
+                id: Uuid::new_v4().to_string(),
+                snapshot_id: snapshot_id.to_string(),
+                schema_name: table.schema.clone(),
+                table_name: table.name.clone(),
+                primary_key: table.primary_key.contains(&column.name),
+                column_name: column.name,
+                ordinal: i as i32 + 1,
+                column_type: column.column_type,
+                data_type: column.data_type,
+                nullable: column.nullable,
+                default_value: column.default_value,
+                comment: column.comment,
+            });
+        }
+        tables.push(CatalogTable {
+            id: Uuid::new_v4().to_string(),
+            snapshot_id: snapshot_id.to_string(),
+            schema_name: table.schema,
+            table_name: table.name,
+            table_type: table.table_type,
+            comment: table.comment,
+            approx_rows: table.approx_rows,
+        });
+    }
+    (tables, columns)
+}
+
+fn group_columns(columns: Vec<CatalogColumn>) -> BTreeMap<(String, String), Vec<CatalogColumn>> {
+    let mut grouped: BTreeMap<(String, String), Vec<CatalogColumn>> = BTreeMap::new();
+    for column in columns {
+        grouped.entry((column.schema_name.clone(), column.table_name.clone())).or_default().push(column);
+    }
+    grouped
+}
+
+// 比较两个版本的表与列，只填充变更内容，ID 与版本号由调用方补充
+fn diff(previous_tables: &[CatalogTable], previous_columns: Vec<CatalogColumn>, tables: &[CatalogTable], columns: &[CatalogColumn]) -> Vec<CatalogDrift> {
+    let drift = |schema: &str, table: &str, column: &str, drift_type: DriftType, before: Option<String>, after: Option<String>| CatalogDrift {
+        schema_name: schema.to_string(),
+        table_name: table.to_string(),
+        column_name: column.to_string(),
+        drift_type,
+        before_value: before,
+        after_value: after,
+        ..Default::default()
+    };
+
+    let previous_names: BTreeSet<(&str, &str)> = previous_tables.iter().map(|t| (t.schema_name.as_str(), t.table_name.as_str())).collect();
+    let current_names: BTreeSet<(&str, &str)> = tables.iter().map(|t| (t.schema_name.as_str(), t.table_name.as_str())).collect();
+    let previous_columns = group_columns(previous_columns);
+    let current_columns = group_columns(columns.to_vec());
+
+    let mut drifts = Vec::new();
+    for table in previous_tables.iter().filter(|t| !current_names.contains(&(t.schema_name.as_str(), t.table_name.as_str()))) {
+        drifts.push(drift(&table.schema_name, &table.table_name, "", DriftType::TableRemoved, Some(table.table_type.clone()), None));
+    }
+    for table in tables {
+        let key = (table.schema_name.clone(), table.table_name.clone());
+        if !previous_names.contains(&(table.schema_name.as_str(), table.table_name.as_str())) {
+            drifts.push(drift(&table.schema_name, &table.table_name, "", DriftType::TableAdded, None, Some(table.table_type.clone())));
+            continue;
+        }
+
+        let before = previous_columns.get(&key).map(Vec::as_slice).unwrap_or_default();
+        let after = current_columns.get(&key).map(Vec::as_slice).unwrap_or_default();
+        for old in before.iter() {
+            match after.iter().find(|c| c.column_name == old.column_name) {
+                None => drifts.push(drift(&table.schema_name, &table.table_name, &old.column_name, DriftType::ColumnRemoved, Some(old.definition()), None)),
+                Some(new) if new.definition() != old.definition() => drifts.push(drift(
+                    &table.schema_name,
+                    &table.table_name,
+                    &old.column_name,
+                    DriftType::ColumnChanged,
+                    Some(old.definition()),
+                    Some(new.definition()),
+                )),
+                Some(_) => {}
+            }
+        }
+        for new in after.iter().filter(|c| !before.iter().any(|old| old.column_name == c.column_name)) {
+            drifts.push(drift(&table.schema_name, &table.table_name, &new.column_name, DriftType::ColumnAdded, None, Some(new.definition())));
+        }
+    }
+    drifts
+}
@@ -0,0 +1,50 @@
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+use crate::autofac;
+use crate::models::project::CreateStatus;
+use crate::utils::config::Setting;
+
+/// 启动元数据目录的后台快照，按配置的间隔读取所有项目下数据源与资源的表结构
+pub fn spawn_catalog_sync() {
+    let setting = &Setting::get().catalog;
+    if !setting.enabled {
+        tracing::info!("catalog snapshot is disabled");
+        return;
+    }
+
+    let interval = Duration::from_secs(setting.interval_secs.max(60));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        // 单轮快照耗时超过间隔时顺延，避免连续触发
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            run_catalog_sync().await;
+        }
+    });
+}
+
+async fn run_catalog_sync() {
+    let projects = match autofac::get_global_app_state_ref().get_project_service().list_all_project().await {
+        Ok(projects) => projects,
+        Err(e) => {
+            tracing::warn!("catalog snapshot failed to list projects: {}", e);
+            return;
+        }
+    };
+
+    for project in projects.into_iter().filter(|p| p.create_status == CreateStatus::Success) {
+        let result = autofac::get_global_app_state_ref().get_catalog_service().snapshot_project(project.code.clone()).await;
+        match result {
+            Ok(count) => tracing::debug!("catalog snapshot of project {} finished, {} sources checked", project.code, count),
+            Err(e) => tracing::warn!("catalog snapshot of project {} failed: {}", project.code, e),
+        }
+
+        // 快照更新后重新展开血缘中未指定的列
+        let result = autofac::get_global_app_state_ref().get_lineage_service().rebuild_project(project.code.clone()).await;
+        if let Err(e) = result {
+            tracing::warn!("lineage rebuild of project {} failed: {}", project.code, e);
+        }
+    }
+}
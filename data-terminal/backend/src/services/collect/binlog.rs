@@ -0,0 +1,710 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use mysql_async::binlog::events::{EventData, RowsEventData};
+use mysql_async::binlog::row::BinlogRow;
+use mysql_async::binlog::value::BinlogValue;
+use mysql_async::prelude::Queryable;
+use mysql_async::{BinlogStream, BinlogStreamRequest, Conn, OptsBuilder, Sid};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::mysql::MySqlConnection;
+use tokio::sync::watch;
+
+use crate::autofac;
+use crate::models::Error;
+use crate::models::collection::{CollectionCheckpoint, StreamCollectRule};
+use crate::models::datasource::{DataSource, MysqlConnectionConfig};
+use crate::models::resource::Resource;
+use super::change::{ChangeEvent, ChangeOp, ChangeSink, StreamTable};
+use super::mysql::{self, Cell, ColumnMeta, TableMeta};
+use super::{CollectStats, TableStats};
+
+// binlog 消费位点，GTID 模式下以 gtid_set 为准
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BinlogOffset {
+    pub file: String,
+    pub position: u64,
+    #[serde(default)]
+    pub gtid_set: String,
+}
+
+/// 以从库身份读取 MySQL 行格式 binlog，将配置表的变更写出到目标，直到收到取消信号
+pub async fn run(
+    project_code: &str,
+    task_id: &str,
+    source: &DataSource,
+    target: &Resource,
+    rule: &StreamCollectRule,
+    cancel: watch::Receiver<bool>,
+    stats: &mut CollectStats,
+) -> Result<(), Error> {
+    let cfg = serde_json::from_value::<MysqlConnectionConfig>(source.connection_config.clone())
+        .map_err(|e| Error::InvalidValue(format!("invalid mysql connection config: {}", e)))?;
+
+    let mut meta_conn = mysql::connect_datasource(&source.connection_config).await?;
+    let mut reader = match BinlogReader::load(project_code, task_id, &cfg, rule, &mut meta_conn).await {
+        Ok(reader) => reader,
+        Err(e) => {
+            mysql::close(meta_conn).await;
+            return Err(e);
+        }
+    };
+    stats.tables = reader
+        .tables
+        .iter()
+        .map(|t| TableStats {
+            source_table: t.rule.source_table.clone(),
+            target_table: t.rule.target_table().to_string(),
+            ..Default::default()
+        })
+        .collect();
+
+    let result = match ChangeSink::open(target, rule, &reader.tables).await {
+        Ok(mut sink) => {
+            let started = std::time::Instant::now();
+            let result = reader.consume(&cfg, &mut sink, &mut meta_conn, cancel, stats).await;
+            let elapsed = started.elapsed().as_millis() as u64;
+            stats.tables.iter_mut().for_each(|t| t.duration_ms = elapsed);
+            sink.close().await;
+            result
+        }
+        Err(e) => Err(e),
+    };
+    mysql::close(meta_conn).await;
+    result
+}
+
+struct BinlogReader<'a> {
+    project_code: &'a str,
+    task_id: &'a str,
+    rule: &'a StreamCollectRule,
+    database: String,
+    tables: Vec<StreamTable>,
+    // 源表的全部列与被采集列在其中的下标，与 tables 一一对应
+    sources: Vec<(TableMeta, Vec<usize>)>,
+    // 发生 DDL 后需要重新读取表结构
+    stale: Vec<bool>,
+    offset: BinlogOffset,
+    gtids: GtidSet,
+    // 当前事务的 GTID
+    gtid: Option<(String, u64)>,
+    // 未提交事务中的变更
+    pending: Vec<ChangeEvent>,
+    // 已提交、待写出的变更
+    ready: Vec<ChangeEvent>,
+    // 位点推进后尚未保存
+    dirty: bool,
+}
+
+impl<'a> BinlogReader<'a> {
+    async fn load(
+        project_code: &'a str,
+        task_id: &'a str,
+        cfg: &MysqlConnectionConfig,
+        rule: &'a StreamCollectRule,
+        meta_conn: &mut MySqlConnection,
+    ) -> Result<Self, Error> {
+        let mut tables = Vec::with_capacity(rule.tables.len());
+        let mut sources = Vec::with_capacity(rule.tables.len());
+        for table in &rule.tables {
+            let (full, selected) = load_table_meta(meta_conn, &table.source_table, &table.columns).await?;
+            tables.push(StreamTable {
+                database: cfg.database.clone(),
+                rule: table.clone(),
+                meta: full.clone().select_columns(&table.columns)?,
+            });
+            sources.push((full, selected));
+        }
+        let stale = vec![false; tables.len()];
+
+        Ok(Self {
+            project_code,
+            task_id,
+            rule,
+            database: cfg.database.clone(),
+            tables,
+            sources,
+            stale,
+            offset: BinlogOffset::default(),
+            gtids: GtidSet::default(),
+            gtid: None,
+            pending: Vec::new(),
+            ready: Vec::new(),
+            dirty: false,
+        })
+    }
+
+    async fn consume(
+        &mut self,
+        cfg: &MysqlConnectionConfig,
+        sink: &mut ChangeSink,
+        meta_conn: &mut MySqlConnection,
+        mut cancel: watch::Receiver<bool>,
+        stats: &mut CollectStats,
+    ) -> Result<(), Error> {
+        let opts = OptsBuilder::default()
+            .ip_or_hostname(cfg.host.clone())
+            .tcp_port(cfg.port)
+            .user(Some(cfg.username.clone()))
+            .pass(Some(cfg.password.clone()));
+        let mut conn = Conn::new(opts).await.map_err(binlog_error)?;
+        self.check_server(&mut conn).await?;
+        self.start_offset(&mut conn).await?;
+
+        let sids = if self.rule.use_gtid { self.gtids.sids()? } else { Vec::new() };
+        let mut request = BinlogStreamRequest::new(self.server_id());
+        if self.rule.use_gtid {
+            request = request.with_gtid().with_gtid_set(sids);
+        } else {
+            request = request.with_filename(self.offset.file.as_bytes()).with_pos(self.offset.position);
+        }
+        tracing::info!(
+            "collection task {} start reading binlog from {}:{} gtid [{}]",
+            self.task_id, self.offset.file, self.offset.position, self.offset.gtid_set
+        );
+        let mut stream = conn.get_binlog_stream(request).await.map_err(binlog_error)?;
+
+        let mut ticker = tokio::time::interval(Duration::from_millis(self.rule.flush_interval_ms));
+        ticker.tick().await;
+        while !*cancel.borrow() {
+            tokio::select! {
+                changed = cancel.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                }
+                _ = ticker.tick() => self.flush(sink, stats).await?,
+                event = stream.next() => {
+                    let event = match event {
+                        Some(event) => event.map_err(binlog_error)?,
+                        None => return Err(Error::InternalError("binlog stream closed by server".to_string())),
+                    };
+                    self.handle(&stream, &event, meta_conn).await?;
+                    if self.ready.len() >= self.rule.batch_size {
+                        self.flush(sink, stats).await?;
+                    }
+                }
+            }
+        }
+
+        // 停止前写出已提交的变更，未提交事务下次从位点重新读取
+        self.flush(sink, stats).await?;
+        let _ = stream.close().await;
+        Ok(())
+    }
+
+    // CDC 依赖行格式且包含完整行镜像的 binlog
+    async fn check_server(&self, conn: &mut Conn) -> Result<(), Error> {
+        let settings: Option<(String, String)> = conn
+            .query_first("SELECT @@GLOBAL.binlog_format, @@GLOBAL.binlog_row_image")
+            .await
+            .map_err(binlog_error)?;
+        let (format, row_image) = settings.unwrap_or_default();
+        if !format.eq_ignore_ascii_case("ROW") || !row_image.eq_ignore_ascii_case("FULL") {
+            return Err(Error::InvalidValue(format!(
+                "binlog_format must be ROW and binlog_row_image must be FULL, got {} and {}",
+                format, row_image
+            )));
+        }
+        if self.rule.use_gtid {
+            let mode: Option<String> = conn.query_first("SELECT @@GLOBAL.gtid_mode").await.map_err(binlog_error)?;
+            if !mode.unwrap_or_default().to_uppercase().starts_with("ON") {
+                return Err(Error::InvalidValue("gtid_mode is not enabled on the datasource".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    // 优先使用保存的位点，首次运行从源库当前位置开始
+    async fn start_offset(&mut self, conn: &mut Conn) -> Result<(), Error> {
+        let saved = autofac::get_global_app_state_ref()
+            .get_collection_service()
+            .get_checkpoint(self.project_code.to_string(), self.task_id.to_string())
+            .await?;
+        if let Some(saved) = saved {
+            self.offset = serde_json::from_value::<BinlogOffset>(saved.checkpoint)
+                .map_err(|e| Error::InvalidValue(format!("invalid binlog checkpoint: {}", e)))?;
+            self.gtids = GtidSet::parse(&self.offset.gtid_set)?;
+            if self.rule.use_gtid && self.gtids.is_empty() {
+                return Err(Error::InvalidValue("checkpoint has no gtid set, reset the checkpoint first".to_string()));
+            }
+            return Ok(());
+        }
+
+        // MySQL 8.4 起 SHOW MASTER STATUS 更名为 SHOW BINARY LOG STATUS
+        let row: Option<mysql_async::Row> = match conn.query_first("SHOW BINARY LOG STATUS").await {
+            Ok(row) => row,
+            Err(_) => conn.query_first("SHOW MASTER STATUS").await.map_err(binlog_error)?,
+        };
+        let row = row.ok_or_else(|| Error::InvalidValue("binary logging is not enabled on the datasource".to_string()))?;
+        self.offset = BinlogOffset {
+            file: row.get::<String, _>("File").unwrap_or_default(),
+            position: row.get::<u64, _>("Position").unwrap_or(4),
+            gtid_set: row
+                .get::<Option<String>, _>("Executed_Gtid_Set")
+                .flatten()
+                .unwrap_or_default()
+                .replace('\n', ""),
+        };
+        self.gtids = GtidSet::parse(&self.offset.gtid_set)?;
+        self.save_offset().await
+    }
+
+    // 未配置时由任务ID生成，避免与真实从库冲突
+    fn server_id(&self) -> u32 {
+        if let Some(id) = self.rule.server_id {
+            return id;
+        }
+        let digest = Sha256::digest(self.task_id.as_bytes());
+        let value = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+        1_000_000 + value % 1_000_000_000
+    }
+
+    async fn handle(&mut self, stream: &BinlogStream, event: &mysql_async::binlog::events::Event, meta_conn: &mut MySqlConnection) -> Result<(), Error> {
+        let header = event.header();
+        let data = event
+            .read_data()
+            .map_err(|e| Error::InternalError(format!("failed to decode binlog event: {}", e)))?;
+        match data {
+            Some(EventData::RotateEvent(e)) => {
+                self.offset.file = e.name().to_string();
+                self.offset.position = e.position();
+            }
+            Some(EventData::GtidEvent(e)) => {
+                self.gtid = Some((uuid::Uuid::from_bytes(e.sid()).to_string(), e.gno()));
+            }
+            Some(EventData::XidEvent(_)) => self.commit(header.log_pos()),
+            Some(EventData::QueryEvent(e)) => {
+                let query = e.query();
+                if query.eq_ignore_ascii_case("BEGIN") {
+                    return Ok(());
+                }
+                // 非事务表的 COMMIT 与 DDL 都是事务边界
+                if !query.eq_ignore_ascii_case("COMMIT") {
+                    self.stale.iter_mut().for_each(|s| *s = true);
+                }
+                self.commit(header.log_pos());
+            }
+            Some(EventData::RowsEvent(rows)) => {
+                self.decode_rows(stream, &rows, header.timestamp(), header.log_pos(), meta_conn).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn commit(&mut self, log_pos: u32) {
+        self.offset.position = log_pos as u64;
+        if let Some((uuid, gno)) = self.gtid.take() {
+            self.gtids.add(uuid, gno);
+            self.offset.gtid_set = self.gtids.to_string();
+        }
+        self.ready.append(&mut self.pending);
+        self.dirty = true;
+    }
+
+    async fn decode_rows(
+        &mut self,
+        stream: &BinlogStream,
+        rows: &RowsEventData<'_>,
+        timestamp: u32,
+        log_pos: u32,
+        meta_conn: &mut MySqlConnection,
+    ) -> Result<(), Error> {
+        let tme = stream
+            .get_tme(rows.table_id())
+            .ok_or_else(|| Error::InternalError(format!("missing table map for table id {}", rows.table_id())))?;
+        if tme.database_name() != self.database {
+            return Ok(());
+        }
+        let table_name = tme.table_name();
+        let Some(index) = self.tables.iter().position(|t| t.rule.source_table == table_name) else {
+            return Ok(());
+        };
+
+        let columns_count = tme.columns_count() as usize;
+        if self.stale[index] || columns_count != self.sources[index].0.columns.len() {
+            let table = &self.tables[index].rule;
+            self.sources[index] = load_table_meta(meta_conn, &table.source_table, &table.columns).await?;
+            self.stale[index] = false;
+            if columns_count != self.sources[index].0.columns.len() {
+                return Err(Error::InvalidValue(format!(
+                    "table {} has {} columns in binlog but {} in database",
+                    table.source_table,
+                    columns_count,
+                    self.sources[index].0.columns.len()
+                )));
+            }
+        }
+
+        let op = match rows {
+            RowsEventData::WriteRowsEventV1(_) | RowsEventData::WriteRowsEvent(_) => ChangeOp::Insert,
+            RowsEventData::DeleteRowsEventV1(_) | RowsEventData::DeleteRowsEvent(_) => ChangeOp::Delete,
+            _ => ChangeOp::Update,
+        };
+        let position = serde_json::json!({ "file": self.offset.file, "position": log_pos });
+        for row in rows.rows(tme) {
+            let (before, after) = row.map_err(|e| Error::InternalError(format!("failed to decode binlog row: {}", e)))?;
+            let before = before.map(|r| self.decode_row(index, &r)).transpose()?;
+            let after = after.map(|r| self.decode_row(index, &r)).transpose()?;
+            self.pending.push(ChangeEvent {
+                op,
+                table: index,
+                before,
+                after,
+                ts_ms: timestamp as i64 * 1000,
+                position: position.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn decode_row(&self, index: usize, row: &BinlogRow) -> Result<Vec<Cell>, Error> {
+        let (full, selected) = &self.sources[index];
+        selected
+            .iter()
+            .map(|&i| binlog_cell(row.as_ref(i), &full.columns[i]))
+            .collect()
+    }
+
+    // 先写出变更再保存位点，保证至少一次投递
+    async fn flush(&mut self, sink: &mut ChangeSink, stats: &mut CollectStats) -> Result<(), Error> {
+        if !self.ready.is_empty() {
+            sink.apply(&self.tables, &self.ready).await?;
+            for event in &self.ready {
+                let table = &mut stats.tables[event.table];
+                table.rows_read += 1;
+                table.rows_written += 1;
+                table.bytes += event.after.as_deref().or(event.before.as_deref()).map(mysql::row_size).unwrap_or(0);
+            }
+            self.ready.clear();
+        }
+        if self.dirty {
+            self.save_offset().await?;
+            self.dirty = false;
+        }
+        Ok(())
+    }
+
+    async fn save_offset(&self) -> Result<(), Error> {
+        let checkpoint = CollectionCheckpoint {
+            task_id: self.task_id.to_string(),
+            checkpoint: serde_json::to_value(&self.offset).unwrap_or_default(),
+            updated_at: chrono::Utc::now(),
+        };
+        autofac::get_global_app_state_ref()
+            .get_collection_service()
+            .save_checkpoint(self.project_code.to_string(), checkpoint)
+            .await
+    }
+}
+
+// 读取源表全部列，并返回规则中的列在其中的下标
+async fn load_table_meta(conn: &mut MySqlConnection, table: &str, columns: &[String]) -> Result<(TableMeta, Vec<usize>), Error> {
+    let full = mysql::table_meta(conn, table).await?;
+    let selected = full.clone().select_columns(columns)?;
+    let indexes = selected
+        .columns
+        .iter()
+        .filter_map(|c| full.columns.iter().position(|f| f.name == c.name))
+        .collect();
+    Ok((full, indexes))
+}
+
+fn binlog_error(e: mysql_async::Error) -> Error {
+    Error::InternalError(format!("binlog error: {}", e))
+}
+
+// binlog 中的值按源表列类型转换为单元格
+fn binlog_cell(value: Option<&BinlogValue<'_>>, column: &ColumnMeta) -> Result<Cell, Error> {
+    match value {
+        None => Ok(Cell::Null),
+        Some(BinlogValue::Value(value)) => Ok(value_cell(value, column)),
+        Some(BinlogValue::Jsonb(value)) => serde_json::Value::try_from(value.clone())
+            .map(Cell::Json)
+            .map_err(|e| Error::InternalError(format!("failed to decode json column {}: {}", column.name, e))),
+        Some(BinlogValue::JsonDiff(_)) => Err(Error::InvalidValue(
+            "partial json updates are not supported, set binlog_row_value_options to empty".to_string(),
+        )),
+    }
+}
+
+fn value_cell(value: &mysql_async::Value, column: &ColumnMeta) -> Cell {
+    use mysql_async::Value;
+    let data_type = column.data_type.as_str();
+    match value {
+        Value::NULL => Cell::Null,
+        Value::Int(v) => match data_type {
+            "enum" => Cell::Text(enum_label(&column.column_type, *v)),
+            "timestamp" => chrono::DateTime::from_timestamp(*v, 0).map(Cell::Timestamp).unwrap_or(Cell::Null),
+            _ if column.column_type.contains("unsigned") => Cell::UInt(unsigned_value(*v, data_type)),
+            _ => Cell::Int(*v),
+        },
+        Value::UInt(v) => Cell::UInt(*v),
+        Value::Float(v) => Cell::Float(*v),
+        Value::Double(v) => Cell::Double(*v),
+        Value::Date(year, month, day, hour, minute, second, micros) => {
+            // 零值日期无法表示，按 NULL 处理
+            let Some(date) = chrono::NaiveDate::from_ymd_opt(*year as i32, *month as u32, *day as u32) else {
+                return Cell::Null;
+            };
+            if data_type == "date" {
+                return Cell::Date(date);
+            }
+            date.and_hms_micro_opt(*hour as u32, *minute as u32, *second as u32, *micros)
+                .map(Cell::DateTime)
+                .unwrap_or(Cell::Null)
+        }
+        Value::Time(negative, days, hours, minutes, seconds, micros) => {
+            if !negative && *days == 0 {
+                if let Some(time) = chrono::NaiveTime::from_hms_micro_opt(*hours as u32, *minutes as u32, *seconds as u32, *micros) {
+                    return Cell::Time(time);
+                }
+            }
+            let hours = *days * 24 + *hours as u32;
+            Cell::Text(format!(
+                "{}{:02}:{:02}:{:02}.{:06}",
+                if *negative { "-" } else { "" },
+                hours,
+                minutes,
+                seconds,
+                micros
+            ))
+        }
+        Value::Bytes(bytes) => bytes_cell(bytes, column),
+    }
+}
+
+fn bytes_cell(bytes: &[u8], column: &ColumnMeta) -> Cell {
+    let text = || String::from_utf8_lossy(bytes).to_string();
+    match column.data_type.as_str() {
+        "decimal" | "numeric" => sqlx::types::Decimal::from_str(&text()).map(Cell::Decimal).unwrap_or_else(|_| Cell::Text(text())),
+        // TIMESTAMP2 以 "秒[.微秒]" 的形式给出
+        "timestamp" => {
+            let value = text();
+            let (seconds, micros) = value.split_once('.').unwrap_or((&value, "0"));
+            let seconds = seconds.parse::<i64>().unwrap_or_default();
+            let micros = format!("{:0<6}", micros).parse::<u32>().unwrap_or_default();
+            chrono::DateTime::from_timestamp(seconds, micros * 1000).map(Cell::Timestamp).unwrap_or(Cell::Null)
+        }
+        "year" => text().parse::<u64>().map(Cell::UInt).unwrap_or(Cell::Null),
+        "bit" => Cell::UInt(bytes.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64)),
+        "set" => {
+            let mask = bytes.iter().rev().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+            Cell::Text(set_labels(&column.column_type, mask))
+        }
+        "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob" | "geometry" => Cell::Bytes(bytes.to_vec()),
+        _ => Cell::Text(text()),
+    }
+}
+
+// 表映射事件缺少符号信息时无符号整数以有符号形式给出，按列宽还原
+fn unsigned_value(value: i64, data_type: &str) -> u64 {
+    match data_type {
+        "tinyint" => value as u8 as u64,
+        "smallint" => value as u16 as u64,
+        "mediumint" => (value as u64) & 0xFF_FFFF,
+        "int" | "integer" => value as u32 as u64,
+        _ => value as u64,
+    }
+}
+
+// 解析 enum('a','b') / set('a','b') 中的取值列表
+fn type_values(column_type: &str) -> Vec<String> {
+    let Some(start) = column_type.find('(') else {
+        return Vec::new();
+    };
+    let body = column_type[start + 1..].trim_end_matches(')');
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' if quoted && chars.peek() == Some(&'\'') => {
+                current.push('\'');
+                chars.next();
+            }
+            '\'' => quoted = !quoted,
+            ',' if !quoted => values.push(std::mem::take(&mut current)),
+            c if quoted => current.push(c),
+            _ => {}
+        }
+    }
+    values.push(current);
+    values
+}
+
+// enum 在 binlog 中为从 1 开始的序号
+fn enum_label(column_type: &str, index: i64) -> String {
+    if index <= 0 {
+        return String::new();
+    }
+    type_values(column_type).get(index as usize - 1).cloned().unwrap_or_default()
+}
+
+// set 在 binlog 中为位图
+fn set_labels(column_type: &str, mask: u64) -> String {
+    type_values(column_type)
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| *i < 64 && mask & (1 << i) != 0)
+        .map(|(_, v)| v)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// GTID 集合：server uuid -> 已执行的闭区间
+#[derive(Debug, Clone, Default)]
+struct GtidSet(BTreeMap<String, Vec<(u64, u64)>>);
+
+impl GtidSet {
+    fn parse(value: &str) -> Result<Self, Error> {
+        let mut set = Self::default();
+        for part in value.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+            let mut items = part.split(':');
+            let uuid = items.next().unwrap_or_default().to_lowercase();
+            for interval in items {
+                let (start, end) = interval.split_once('-').unwrap_or((interval, interval));
+                let start = start.parse::<u64>().map_err(|_| Error::InvalidValue(format!("invalid gtid set {}", value)))?;
+                let end = end.parse::<u64>().map_err(|_| Error::InvalidValue(format!("invalid gtid set {}", value)))?;
+                if start == 0 || start > end {
+                    return Err(Error::InvalidValue(format!("invalid gtid set {}", value)));
+                }
+                set.0.entry(uuid.clone()).or_default().push((start, end));
+            }
+        }
+        set.0.values_mut().for_each(merge_intervals);
+        Ok(set)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn add(&mut self, uuid: String, gno: u64) {
+        let intervals = self.0.entry(uuid).or_default();
+        intervals.push((gno, gno));
+        merge_intervals(intervals);
+    }
+
+    fn sids(&self) -> Result<Vec<Sid<'static>>, Error> {
+        self.0
+            .iter()
+            .map(|(uuid, intervals)| {
+                let sid = format!("{}:{}", uuid, format_intervals(intervals));
+                Sid::from_str(&sid).map_err(|e| Error::InvalidValue(format!("invalid gtid {}: {}", sid, e)))
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for GtidSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self
+            .0
+            .iter()
+            .map(|(uuid, intervals)| format!("{}:{}", uuid, format_intervals(intervals)))
+            .collect();
+        write!(f, "{}", parts.join(","))
+    }
+}
+
+fn merge_intervals(intervals: &mut Vec<(u64, u64)>) {
+    intervals.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(intervals.len());
+    for &(start, end) in intervals.iter() {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    *intervals = merged;
+}
+
+fn format_intervals(intervals: &[(u64, u64)]) -> String {
+    intervals
+        .iter()
+        .map(|(start, end)| if start == end { start.to_string() } else { format!("{}-{}", start, end) })
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID_A: &str = "3e11fa47-71ca-11e1-9e33-c80aa9429562";
+    const UUID_B: &str = "4f2a6b10-1f1b-11ee-8c5d-0242ac120002";
+
+    #[test]
+    fn gtid_set_parse_merges_and_normalizes() {
+        let raw = format!("{}:1-5:7-9:6,\n {}:3:1-2, {}:10", UUID_A.to_uppercase(), UUID_B, UUID_A);
+        let set = GtidSet::parse(&raw).unwrap();
+        assert_eq!(set.to_string(), format!("{}:1-10,{}:1-3", UUID_A, UUID_B));
+        assert_eq!(set.sids().unwrap().len(), 2);
+
+        let empty = GtidSet::parse("").unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.to_string(), "");
+    }
+
+    #[test]
+    fn gtid_set_rejects_invalid_intervals() {
+        assert!(GtidSet::parse(&format!("{}:a-b", UUID_A)).is_err());
+        assert!(GtidSet::parse(&format!("{}:5-3", UUID_A)).is_err());
+        assert!(GtidSet::parse(&format!("{}:0-3", UUID_A)).is_err());
+        assert!(GtidSet::parse(&format!("{}:1-", UUID_A)).is_err());
+    }
+
+    #[test]
+    fn gtid_set_add_extends_and_fills_gaps() {
+        let mut set = GtidSet::parse(&format!("{}:1-3:7", UUID_A)).unwrap();
+        set.add(UUID_A.to_string(), 4);
+        assert_eq!(set.to_string(), format!("{}:1-4:7", UUID_A));
+        // 重复的事务不改变集合
+        set.add(UUID_A.to_string(), 2);
+        assert_eq!(set.to_string(), format!("{}:1-4:7", UUID_A));
+        set.add(UUID_A.to_string(), 9);
+        assert_eq!(set.to_string(), format!("{}:1-4:7:9", UUID_A));
+        set.add(UUID_A.to_string(), 6);
+        set.add(UUID_A.to_string(), 5);
+        set.add(UUID_A.to_string(), 8);
+        assert_eq!(set.to_string(), format!("{}:1-9", UUID_A));
+
+        set.add(UUID_B.to_string(), 1);
+        assert_eq!(set.to_string(), format!("{}:1-9,{}:1", UUID_A, UUID_B));
+    }
+
+    #[test]
+    fn merge_intervals_joins_overlapping_and_adjacent() {
+        let mut intervals = vec![(8, 8), (2, 6), (1, 3), (7, 7), (20, 25), (11, 12)];
+        merge_intervals(&mut intervals);
+        assert_eq!(intervals, vec![(1, 8), (11, 12), (20, 25)]);
+        assert_eq!(format_intervals(&intervals), "1-8:11-12:20-25");
+    }
+
+    #[test]
+    fn enum_and_set_values_follow_column_definition() {
+        let column_type = "enum('a','it''s','c,d')";
+        assert_eq!(type_values(column_type), vec!["a", "it's", "c,d"]);
+        assert_eq!(enum_label(column_type, 2), "it's");
+        assert_eq!(enum_label(column_type, 0), "");
+        assert_eq!(enum_label(column_type, 9), "");
+        assert_eq!(set_labels("set('x','y','z')", 0b101), "x,z");
+        assert_eq!(set_labels("set('x','y','z')", 0), "");
+    }
+
+    #[test]
+    fn unsigned_values_are_restored_by_column_width() {
+        assert_eq!(unsigned_value(-1, "tinyint"), 255);
+        assert_eq!(unsigned_value(-1, "smallint"), 65_535);
+        assert_eq!(unsigned_value(-1, "mediumint"), 16_777_215);
+        assert_eq!(unsigned_value(-1, "int"), 4_294_967_295);
+        assert_eq!(unsigned_value(-1, "bigint"), u64::MAX);
+        assert_eq!(unsigned_value(42, "int"), 42);
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rdkafka::ClientConfig;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use serde::Serialize;
+use sqlx::mysql::MySqlConnection;
+
+use crate::models::Error;
+use crate::models::collection::{StreamCollectRule, TableRule};
+use crate::models::resource::{KafkaResourceConfig, Resource, ResourceType};
+use super::mysql::{self, Cell, TableMeta, TargetDialect};
+use super::resolve_target;
+
+// 变更类型
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+// 实时采集的源表：规则与按规则筛选后的列信息
+#[derive(Debug, Clone)]
+pub struct StreamTable {
+    pub database: String,
+    pub rule: TableRule,
+    pub meta: TableMeta,
+}
+
+impl StreamTable {
+    // 行中的主键值，顺序与 meta.primary_key 一致
+    pub fn key(&self, row: &[Cell]) -> Vec<Cell> {
+        self.meta
+            .primary_key
+            .iter()
+            .filter_map(|k| self.meta.columns.iter().position(|c| &c.name == k))
+            .map(|i| row[i].clone())
+            .collect()
+    }
+
+    fn row_json(&self, row: &[Cell]) -> serde_json::Map<String, serde_json::Value> {
+        self.meta
+            .columns
+            .iter()
+            .zip(row.iter())
+            .map(|(c, v)| (c.name.clone(), mysql::cell_json(v)))
+            .collect()
+    }
+}
+
+// 日志采集统一的变更事件，行数据按 StreamTable.meta 的列顺序排列
+#[derive(Debug, Clone)]
+pub struct ChangeEvent {
+    pub op: ChangeOp,
+    // 在 StreamTable 列表中的下标
+    pub table: usize,
+    pub before: Option<Vec<Cell>>,
+    pub after: Option<Vec<Cell>>,
+    // 源库提交时间
+    pub ts_ms: i64,
+    // 事件在源库日志中的位置
+    pub position: serde_json::Value,
+}
+
+impl ChangeEvent {
+    /// 输出到消息队列的 JSON 结构
+    pub fn to_json(&self, table: &StreamTable) -> serde_json::Value {
+        serde_json::json!({
+            "op": self.op,
+            "source": {
+                "database": table.database,
+                "table": table.rule.source_table,
+                "position": self.position,
+            },
+            "ts_ms": self.ts_ms,
+            "before": self.before.as_ref().map(|row| table.row_json(row)),
+            "after": self.after.as_ref().map(|row| table.row_json(row)),
+        })
+    }
+}
+
+// 变更写出目标
+pub enum ChangeSink {
+    Kafka(KafkaSink),
+    Database(DatabaseSink),
+}
+
+impl ChangeSink {
+    /// 按目标资源类型创建写出目标，目标为数据库时会预先建表
+    pub async fn open(resource: &Resource, rule: &StreamCollectRule, tables: &[StreamTable]) -> Result<Self, Error> {
+        match resource.resource_type {
+            ResourceType::Kafka => Ok(Self::Kafka(KafkaSink::open(resource, rule)?)),
+            _ => Ok(Self::Database(DatabaseSink::open(resource, rule, tables).await?)),
+        }
+    }
+
+    /// 按顺序写出一批变更，返回写出的行数
+    pub async fn apply(&mut self, tables: &[StreamTable], events: &[ChangeEvent]) -> Result<u64, Error> {
+        match self {
+            Self::Kafka(sink) => sink.apply(tables, events).await,
+            Self::Database(sink) => sink.apply(tables, events).await,
+        }
+    }
+
+    pub async fn close(self) {
+        match self {
+            Self::Kafka(sink) => sink.close(),
+            Self::Database(sink) => mysql::close(sink.conn).await,
+        }
+    }
+}
+
+pub struct KafkaSink {
+    producer: FutureProducer,
+    topic: String,
+}
+
+impl KafkaSink {
+    fn open(resource: &Resource, rule: &StreamCollectRule) -> Result<Self, Error> {
+        let cfg = serde_json::from_value::<KafkaResourceConfig>(resource.config.clone())
+            .map_err(|e| Error::InvalidValue(format!("invalid kafka config: {}", e)))?;
+        let servers = cfg.bootstrap_servers();
+        if servers.is_empty() {
+            return Err(Error::EmptyValue("resource.config.host".to_string()));
+        }
+
+        let mut config = ClientConfig::new();
+        // 开启幂等写入，保证同一分区内的顺序
+        config
+            .set("bootstrap.servers", &servers)
+            .set("enable.idempotence", "true")
+            .set("message.timeout.ms", "30000");
+        let protocol = match (cfg.ssl_enabled, cfg.sasl_enabled) {
+            (false, false) => "PLAINTEXT",
+            (true, false) => "SSL",
+            (false, true) => "SASL_PLAINTEXT",
+            (true, true) => "SASL_SSL",
+        };
+        config.set("security.protocol", protocol);
+        if cfg.sasl_enabled {
+            config
+                .set("sasl.mechanisms", cfg.sasl_mechanism.as_deref().unwrap_or("PLAIN"))
+                .set("sasl.username", cfg.username.as_deref().unwrap_or_default())
+                .set("sasl.password", cfg.password.as_deref().unwrap_or_default());
+        }
+        let producer = config
+            .create::<FutureProducer>()
+            .map_err(|e| Error::InternalError(format!("failed to create kafka producer: {}", e)))?;
+
+        Ok(Self { producer, topic: rule.topic.clone() })
+    }
+
+    // 以主键作为消息 key，同一行的变更落在同一分区
+    async fn apply(&mut self, tables: &[StreamTable], events: &[ChangeEvent]) -> Result<u64, Error> {
+        let messages: Vec<(String, String, String)> = events
+            .iter()
+            .map(|event| {
+                let table = &tables[event.table];
+                let topic = if self.topic.is_empty() {
+                    format!("{}.{}", table.database, table.rule.source_table)
+                } else {
+                    self.topic.clone()
+                };
+                let row = event.after.as_ref().or(event.before.as_ref());
+                let key = row.map(|row| table.key(row)).unwrap_or_default();
+                let key = serde_json::Value::Array(key.iter().map(mysql::cell_json).collect()).to_string();
+                (topic, key, event.to_json(table).to_string())
+            })
+            .collect();
+
+        let sends = messages.iter().map(|(topic, key, payload)| {
+            self.producer
+                .send(FutureRecord::to(topic).key(key).payload(payload), Duration::from_secs(30))
+        });
+        for result in futures_util::future::join_all(sends).await {
+            result.map_err(|(e, _)| Error::InternalError(format!("failed to send change event to kafka: {}", e)))?;
+        }
+        Ok(messages.len() as u64)
+    }
+
+    fn close(self) {
+        use rdkafka::producer::Producer;
+        let _ = self.producer.flush(Duration::from_secs(10));
+    }
+}
+
+pub struct DatabaseSink {
+    conn: MySqlConnection,
+    dialect: TargetDialect,
+    database: String,
+    batch_size: usize,
+}
+
+// 写入目标库的动作，主键变化的更新拆为删除旧行与写入新行
+enum Action {
+    Upsert(Vec<Cell>),
+    Delete(Vec<Cell>),
+}
+
+impl DatabaseSink {
+    async fn open(resource: &Resource, rule: &StreamCollectRule, tables: &[StreamTable]) -> Result<Self, Error> {
+        let (config, dialect) = resolve_target(resource)?;
+        let database = if rule.target_database.is_empty() {
+            config.default_database().ok_or_else(|| Error::EmptyValue("rule.target_database".to_string()))?
+        } else {
+            rule.target_database.clone()
+        };
+        if let Some(table) = tables.iter().find(|t| t.meta.primary_key.is_empty()) {
+            return Err(Error::InvalidValue(format!("table {} has no primary key", table.rule.source_table)));
+        }
+
+        let mut conn = mysql::connect_resource(&config, dialect).await?;
+        for table in tables {
+            let created = mysql::ensure_target_table(
+                &mut conn,
+                dialect,
+                &database,
+                table.rule.target_table(),
+                &table.meta,
+                rule.doris_replication_num,
+            )
+            .await;
+            if let Err(e) = created {
+                mysql::close(conn).await;
+                return Err(e);
+            }
+        }
+
+        Ok(Self { conn, dialect, database, batch_size: rule.batch_size })
+    }
+
+    // 连续的同表同类动作合并为一批执行，批内同一主键只保留最后一次写入
+    async fn apply(&mut self, tables: &[StreamTable], events: &[ChangeEvent]) -> Result<u64, Error> {
+        let mut actions: Vec<(usize, Action)> = Vec::with_capacity(events.len());
+        for event in events {
+            let table = &tables[event.table];
+            match (event.op, &event.before, &event.after) {
+                (ChangeOp::Delete, Some(before), _) => actions.push((event.table, Action::Delete(table.key(before)))),
+                (ChangeOp::Update, Some(before), Some(after)) if table.key(before) != table.key(after) => {
+                    actions.push((event.table, Action::Delete(table.key(before))));
+                    actions.push((event.table, Action::Upsert(after.clone())));
+                }
+                (_, _, Some(after)) => actions.push((event.table, Action::Upsert(after.clone()))),
+                _ => {}
+            }
+        }
+
+        let mut written = 0;
+        let mut start = 0;
+        while start < actions.len() {
+            let table_index = actions[start].0;
+            let is_delete = matches!(actions[start].1, Action::Delete(_));
+            let mut end = start;
+            while end < actions.len()
+                && actions[end].0 == table_index
+                && matches!(actions[end].1, Action::Delete(_)) == is_delete
+            {
+                end += 1;
+            }
+
+            let table = &tables[table_index];
+            let mut rows: Vec<Vec<Cell>> = Vec::new();
+            let mut positions: HashMap<String, usize> = HashMap::new();
+            for (_, action) in &actions[start..end] {
+                let row = match action {
+                    Action::Upsert(row) | Action::Delete(row) => row,
+                };
+                let key = if is_delete { format!("{:?}", row) } else { format!("{:?}", table.key(row)) };
+                match positions.get(&key) {
+                    Some(i) => rows[*i] = row.clone(),
+                    None => {
+                        positions.insert(key, rows.len());
+                        rows.push(row.clone());
+                    }
+                }
+            }
+            written += self.write(table, rows, is_delete).await?;
+            start = end;
+        }
+        Ok(written)
+    }
+
+    async fn write(&mut self, table: &StreamTable, rows: Vec<Vec<Cell>>, is_delete: bool) -> Result<u64, Error> {
+        let target_table = table.rule.target_table();
+        let mut written = 0;
+        if is_delete {
+            let batch_rows = mysql::max_batch_rows(self.batch_size, table.meta.primary_key.len());
+            for chunk in rows.chunks(batch_rows) {
+                written += mysql::delete_rows(
+                    &mut self.conn,
+                    self.dialect,
+                    &self.database,
+                    target_table,
+                    &table.meta.primary_key,
+                    chunk.to_vec(),
+                )
+                .await?;
+            }
+        } else {
+            let columns: Vec<String> = table.meta.columns.iter().map(|c| c.name.clone()).collect();
+            let batch_rows = mysql::max_batch_rows(self.batch_size, columns.len());
+            for chunk in rows.chunks(batch_rows) {
+                written += mysql::write_batch(&mut self.conn, self.dialect, &self.database, target_table, &columns, chunk.to_vec(), true)
+                    .await?;
+            }
+        }
+        Ok(written)
+    }
+}
@@ -0,0 +1,176 @@
+use std::time::Instant;
+
+use sqlx::mysql::MySqlConnection;
+use tokio::sync::watch;
+
+use crate::models::Error;
+use crate::models::collection::{DatabaseCollectRule, TableRule};
+use crate::models::datasource::DataSource;
+use crate::models::resource::Resource;
+use super::mysql::{self, Cell, TargetDialect, quote_ident};
+use super::{CollectStats, TableStats, error_detail, resolve_target, target_database};
+
+/// 全量采集：逐表分批读取源表并写入目标表
+pub async fn run(
+    source: &DataSource,
+    target: &Resource,
+    rule: &DatabaseCollectRule,
+    cancel: &watch::Receiver<bool>,
+    stats: &mut CollectStats,
+) -> Result<(), Error> {
+    let (target_config, dialect) = resolve_target(target)?;
+    let database = target_database(rule, &target_config)?;
+
+    let mut src = mysql::connect_datasource(&source.connection_config).await?;
+    let mut dst = match mysql::connect_resource(&target_config, dialect).await {
+        Ok(conn) => conn,
+        Err(e) => {
+            mysql::close(src).await;
+            return Err(e);
+        }
+    };
+
+    let mut result = Ok(());
+    for table in &rule.tables {
+        // 取消时已完成的表保持不变，剩余的表不再采集
+        if *cancel.borrow() {
+            tracing::info!("collection cancelled before table {}", table.source_table);
+            break;
+        }
+        let mut table_stats = TableStats {
+            source_table: table.source_table.clone(),
+            target_table: table.target_table().to_string(),
+            ..Default::default()
+        };
+        let ctx = CopyContext { dialect, database: &database, rule, cancel };
+        let copied = copy_table(&mut src, &mut dst, &ctx, table, &mut table_stats).await;
+        stats.tables.push(table_stats);
+        if let Err(e) = copied {
+            result = Err(Error::InternalError(format!("table {}: {}", table.source_table, error_detail(&e))));
+            break;
+        }
+    }
+
+    mysql::close(src).await;
+    mysql::close(dst).await;
+    result
+}
+
+struct CopyContext<'a> {
+    dialect: TargetDialect,
+    database: &'a str,
+    rule: &'a DatabaseCollectRule,
+    cancel: &'a watch::Receiver<bool>,
+}
+
+async fn copy_table(
+    src: &mut MySqlConnection,
+    dst: &mut MySqlConnection,
+    ctx: &CopyContext<'_>,
+    table: &TableRule,
+    stats: &mut TableStats,
+) -> Result<(), Error> {
+    let CopyContext { dialect, database, rule, cancel } = *ctx;
+    let started = Instant::now();
+    let meta = mysql::table_meta(src, &table.source_table).await?.select_columns(&table.columns)?;
+    let target_table = table.target_table();
+
+    // 不清空目标表时按主键覆盖写入，没有主键的表每次采集都会重复写入全部行
+    if !rule.truncate_target {
+        if meta.primary_key.is_empty() {
+            return Err(Error::InvalidValue(format!(
+                "table {} has no primary key, enable truncate_target to reload it",
+                meta.name
+            )));
+        }
+        if meta.primary_key.iter().any(|k| !meta.columns.iter().any(|c| c.name == *k)) {
+            return Err(Error::InvalidValue("rule.tables.columns must include all primary key columns".to_string()));
+        }
+    }
+
+    mysql::ensure_target_table(dst, dialect, database, target_table, &meta, rule.doris_replication_num).await?;
+    if rule.truncate_target {
+        mysql::truncate_table(dst, database, target_table).await?;
+    }
+
+    // 不清空目标表时重复采集会遇到主键冲突，改为按主键覆盖写入
+    let upsert = !rule.truncate_target;
+    let columns: Vec<String> = meta.columns.iter().map(|c| c.name.clone()).collect();
+    let batch_rows = mysql::max_batch_rows(rule.batch_size, columns.len());
+    let select_list = columns.iter().map(|c| quote_ident(c)).collect::<Vec<_>>().join(", ");
+    let from = quote_ident(&meta.name);
+
+    // 单列主键时按主键游标翻页，否则在快照内按偏移量翻页
+    let key_index = match meta.primary_key.as_slice() {
+        [key] => columns.iter().position(|c| c == key),
+        _ => None,
+    };
+    let order_by = meta.primary_key.iter().map(|k| quote_ident(k)).collect::<Vec<_>>().join(", ");
+    let first_sql;
+    let next_sql;
+    if key_index.is_some() {
+        first_sql = format!("SELECT {} FROM {} ORDER BY {} LIMIT ?", select_list, from, order_by);
+        next_sql = format!("SELECT {} FROM {} WHERE {} > ? ORDER BY {} LIMIT ?", select_list, from, order_by, order_by);
+    } else if order_by.is_empty() {
+        first_sql = format!("SELECT {} FROM {} LIMIT ? OFFSET ?", select_list, from);
+        next_sql = first_sql.clone();
+    } else {
+        first_sql = format!("SELECT {} FROM {} ORDER BY {} LIMIT ? OFFSET ?", select_list, from, order_by);
+        next_sql = first_sql.clone();
+    }
+
+    mysql::begin_snapshot(src).await?;
+    let mut cursor: Option<Cell> = None;
+    let copied: Result<(), Error> = async {
+        loop {
+            let rows = match (key_index, &cursor) {
+                (Some(_), Some(last)) => {
+                    mysql::bind_cell(sqlx::query(&next_sql), last.clone())
+                        .bind(batch_rows as i64)
+                        .fetch_all(&mut *src)
+                        .await?
+                }
+                (Some(_), None) => sqlx::query(&first_sql).bind(batch_rows as i64).fetch_all(&mut *src).await?,
+                (None, _) => {
+                    sqlx::query(&first_sql)
+                        .bind(batch_rows as i64)
+                        .bind(stats.rows_read as i64)
+                        .fetch_all(&mut *src)
+                        .await?
+                }
+            };
+            let fetched = rows.len();
+            if fetched == 0 {
+                break;
+            }
+
+            let cells = rows.iter().map(mysql::decode_row).collect::<Result<Vec<_>, _>>()?;
+            if let Some(index) = key_index {
+                cursor = cells.last().map(|row| row[index].clone());
+            }
+            stats.rows_read += fetched as u64;
+            stats.bytes += cells.iter().map(|row| mysql::row_size(row)).sum::<u64>();
+            stats.rows_written += mysql::write_batch(dst, dialect, database, target_table, &columns, cells, upsert).await?;
+
+            if fetched < batch_rows {
+                break;
+            }
+            if *cancel.borrow() {
+                tracing::info!("full load {} cancelled after {} rows", table.source_table, stats.rows_written);
+                break;
+            }
+        }
+        Ok(())
+    }
+    .await;
+    let ended = mysql::end_snapshot(src).await;
+    stats.duration_ms = started.elapsed().as_millis() as u64;
+    copied?;
+    ended?;
+
+    tracing::info!(
+        "full load {} -> {}.{} finished, {} rows in {} ms",
+        table.source_table, database, target_table, stats.rows_written, stats.duration_ms
+    );
+    Ok(())
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use sqlx::mysql::MySqlConnection;
+use tokio::sync::watch;
+
+use crate::autofac;
+use crate::models::Error;
+use crate::models::collection::{CollectionWatermark, DatabaseCollectRule, TableRule, WatermarkValue};
+use crate::models::datasource::DataSource;
+use crate::models::resource::Resource;
+use super::mysql::{self, Cell, TargetDialect, quote_ident};
+use super::{CollectStats, TableStats, error_detail, resolve_target, target_database};
+
+/// 增量采集：按水位列拉取上次水位之后的数据并 upsert 到目标表
+pub async fn run(
+    project_code: &str,
+    task_id: &str,
+    source: &DataSource,
+    target: &Resource,
+    rule: &DatabaseCollectRule,
+    cancel: &watch::Receiver<bool>,
+    stats: &mut CollectStats,
+) -> Result<(), Error> {
+    let (target_config, dialect) = resolve_target(target)?;
+    let database = target_database(rule, &target_config)?;
+
+    let saved = autofac::get_global_app_state_ref()
+        .get_collection_service()
+        .list_watermark(project_code.to_string(), task_id.to_string())
+        .await?;
+    let mut watermarks: HashMap<String, CollectionWatermark> =
+        saved.into_iter().map(|w| (w.source_table.clone(), w)).collect();
+
+    let mut src = mysql::connect_datasource(&source.connection_config).await?;
+    let mut dst = match mysql::connect_resource(&target_config, dialect).await {
+        Ok(conn) => conn,
+        Err(e) => {
+            mysql::close(src).await;
+            return Err(e);
+        }
+    };
+
+    let mut result = Ok(());
+    for table in &rule.tables {
+        // 取消时已完成的表保持不变，剩余的表不再采集
+        if *cancel.borrow() {
+            tracing::info!("collection cancelled before table {}", table.source_table);
+            break;
+        }
+        let mut table_stats = TableStats {
+            source_table: table.source_table.clone(),
+            target_table: table.target_table().to_string(),
+            ..Default::default()
+        };
+        // 水位列变更后旧水位失效，从头采集
+        let last = watermarks
+            .remove(&table.source_table)
+            .filter(|w| w.watermark_column == table.watermark_column)
+            .map(|w| w.watermark);
+        let ctx = SyncContext { project_code, task_id, dialect, database: &database, rule, cancel };
+        let copied = sync_table(&mut src, &mut dst, &ctx, table, last, &mut table_stats).await;
+        stats.tables.push(table_stats);
+        if let Err(e) = copied {
+            result = Err(Error::InternalError(format!("table {}: {}", table.source_table, error_detail(&e))));
+            break;
+        }
+    }
+
+    mysql::close(src).await;
+    mysql::close(dst).await;
+    result
+}
+
+struct SyncContext<'a> {
+    project_code: &'a str,
+    task_id: &'a str,
+    dialect: TargetDialect,
+    database: &'a str,
+    rule: &'a DatabaseCollectRule,
+    cancel: &'a watch::Receiver<bool>,
+}
+
+async fn sync_table(
+    src: &mut MySqlConnection,
+    dst: &mut MySqlConnection,
+    ctx: &SyncContext<'_>,
+    table: &TableRule,
+    last: Option<WatermarkValue>,
+    stats: &mut TableStats,
+) -> Result<(), Error> {
+    let started = Instant::now();
+    let meta = mysql::table_meta(src, &table.source_table).await?.select_columns(&table.columns)?;
+    let target_table = table.target_table();
+    let columns: Vec<String> = meta.columns.iter().map(|c| c.name.clone()).collect();
+
+    // upsert 依赖主键，翻页游标为 (水位列, 主键列)
+    if meta.primary_key.is_empty() {
+        return Err(Error::InvalidValue(format!("table {} has no primary key", meta.name)));
+    }
+    if meta.primary_key.iter().any(|k| !columns.contains(k)) {
+        return Err(Error::InvalidValue("rule.tables.columns must include all primary key columns".to_string()));
+    }
+    let mut cursor_columns = vec![table.watermark_column.clone()];
+    cursor_columns.extend(meta.primary_key.iter().filter(|k| **k != table.watermark_column).cloned());
+    let cursor_index = cursor_columns
+        .iter()
+        .map(|c| columns.iter().position(|name| name == c))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| Error::InvalidValue(format!("watermark column {} is not collected", table.watermark_column)))?;
+
+    mysql::ensure_target_table(dst, ctx.dialect, ctx.database, target_table, &meta, ctx.rule.doris_replication_num).await?;
+
+    let batch_rows = mysql::max_batch_rows(ctx.rule.batch_size, columns.len());
+    let select_list = columns.iter().map(|c| quote_ident(c)).collect::<Vec<_>>().join(", ");
+    let from = quote_ident(&meta.name);
+    let watermark = quote_ident(&table.watermark_column);
+    let order_by = cursor_columns.iter().map(|c| quote_ident(c)).collect::<Vec<_>>().join(", ");
+    let placeholders = vec!["?"; cursor_columns.len()].join(", ");
+
+    // 水位值为 NULL 的行不参与增量采集；起始条件取等号，重复读取的行由 upsert 去重
+    let first_sql = match last {
+        Some(_) => format!(
+            "SELECT {} FROM {} WHERE {} >= ? ORDER BY {} LIMIT ?",
+            select_list, from, watermark, order_by
+        ),
+        None => format!(
+            "SELECT {} FROM {} WHERE {} IS NOT NULL ORDER BY {} LIMIT ?",
+            select_list, from, watermark, order_by
+        ),
+    };
+    let next_sql = format!(
+        "SELECT {} FROM {} WHERE {} IS NOT NULL AND ({}) > ({}) ORDER BY {} LIMIT ?",
+        select_list, from, watermark, order_by, placeholders, order_by
+    );
+
+    mysql::begin_snapshot(src).await?;
+    let mut cursor: Option<Vec<Cell>> = None;
+    let synced: Result<(), Error> = async {
+        loop {
+            let rows = match (&cursor, &last) {
+                (Some(values), _) => {
+                    let query = values.iter().cloned().fold(sqlx::query(&next_sql), mysql::bind_cell);
+                    query.bind(batch_rows as i64).fetch_all(&mut *src).await?
+                }
+                (None, Some(value)) => {
+                    mysql::bind_cell(sqlx::query(&first_sql), to_cell(value))
+                        .bind(batch_rows as i64)
+                        .fetch_all(&mut *src)
+                        .await?
+                }
+                (None, None) => sqlx::query(&first_sql).bind(batch_rows as i64).fetch_all(&mut *src).await?,
+            };
+            let fetched = rows.len();
+            if fetched == 0 {
+                break;
+            }
+
+            let cells = rows.iter().map(mysql::decode_row).collect::<Result<Vec<_>, _>>()?;
+            let next: Vec<Cell> = match cells.last() {
+                Some(row) => cursor_index.iter().map(|i| row[*i].clone()).collect(),
+                None => break,
+            };
+            stats.rows_read += fetched as u64;
+            stats.bytes += cells.iter().map(|row| mysql::row_size(row)).sum::<u64>();
+            stats.rows_written += mysql::write_batch(dst, ctx.dialect, ctx.database, target_table, &columns, cells, true).await?;
+
+            // 每批写入成功后推进水位，失败重跑时从已完成的位置继续
+            save_watermark(ctx, table, &next[0]).await?;
+            cursor = Some(next);
+
+            if fetched < batch_rows {
+                break;
+            }
+            // 水位已随批次推进，取消后下次运行从这里继续
+            if *ctx.cancel.borrow() {
+                tracing::info!("incremental load {} cancelled after {} rows", table.source_table, stats.rows_written);
+                break;
+            }
+        }
+        Ok(())
+    }
+    .await;
+    let ended = mysql::end_snapshot(src).await;
+    stats.duration_ms = started.elapsed().as_millis() as u64;
+    synced?;
+    ended?;
+
+    tracing::info!(
+        "incremental load {} -> {}.{} finished, {} rows in {} ms",
+        table.source_table, ctx.database, target_table, stats.rows_written, stats.duration_ms
+    );
+    Ok(())
+}
+
+async fn save_watermark(ctx: &SyncContext<'_>, table: &TableRule, value: &Cell) -> Result<(), Error> {
+    let watermark = CollectionWatermark {
+        task_id: ctx.task_id.to_string(),
+        source_table: table.source_table.clone(),
+        watermark_column: table.watermark_column.clone(),
+        watermark: to_watermark(value)?,
+        updated_at: chrono::Utc::now(),
+    };
+    autofac::get_global_app_state_ref()
+        .get_collection_service()
+        .save_watermark(ctx.project_code.to_string(), watermark)
+        .await
+}
+
+// 水位列只支持整数、字符串、日期时间类型
+fn to_watermark(cell: &Cell) -> Result<WatermarkValue, Error> {
+    match cell {
+        Cell::Int(v) => Ok(WatermarkValue::Int(*v)),
+        Cell::UInt(v) => Ok(WatermarkValue::UInt(*v)),
+        Cell::Decimal(v) => Ok(WatermarkValue::Text(v.to_string())),
+        Cell::Text(v) => Ok(WatermarkValue::Text(v.clone())),
+        Cell::Date(v) => Ok(WatermarkValue::Date(*v)),
+        Cell::DateTime(v) => Ok(WatermarkValue::DateTime(*v)),
+        Cell::Timestamp(v) => Ok(WatermarkValue::Timestamp(*v)),
+        other => Err(Error::InvalidValue(format!("unsupported watermark value {:?}", other))),
+    }
+}
+
+fn to_cell(value: &WatermarkValue) -> Cell {
+    match value {
+        WatermarkValue::Int(v) => Cell::Int(*v),
+        WatermarkValue::UInt(v) => Cell::UInt(*v),
+        WatermarkValue::Text(v) => Cell::Text(v.clone()),
+        WatermarkValue::Date(v) => Cell::Date(*v),
+        WatermarkValue::DateTime(v) => Cell::DateTime(*v),
+        WatermarkValue::Timestamp(v) => Cell::Timestamp(*v),
+    }
+}
@@ -0,0 +1,225 @@
+pub mod mysql;
+pub mod full;
+pub mod incremental;
+pub mod change;
+pub mod binlog;
+pub mod postgres;
+pub mod pgoutput;
+
+use std::time::Instant;
+
+use serde::Serialize;
+use tokio::sync::watch;
+use tracing::Instrument;
+
+use crate::autofac;
+use crate::models::Error;
+use crate::models::task_run::{TaskRun, RunStatus};
+use crate::services::run_log;
+use crate::services::task_registry::RunningTaskGuard;
+use crate::models::collection::{CollectionTask, CollectionCategory, CollectType, DatabaseCollectRule, StreamCollectRule};
+use crate::models::datasource::{DataSource, DataSourceType};
+use crate::models::resource::{Resource, ResourceType, DatabaseResourceConfig};
+use crate::models::project::CreateStatus;
+use mysql::TargetDialect;
+
+// 单张表的采集统计
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct TableStats {
+    pub source_table: String,
+    pub target_table: String,
+    pub rows_read: u64,
+    pub rows_written: u64,
+    pub bytes: u64,
+    pub duration_ms: u64,
+}
+
+// 一次采集运行的统计，失败时保留已完成部分
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CollectStats {
+    pub tables: Vec<TableStats>,
+}
+
+impl CollectStats {
+    pub fn rows_read(&self) -> u64 {
+        self.tables.iter().map(|t| t.rows_read).sum()
+    }
+
+    pub fn rows_written(&self) -> u64 {
+        self.tables.iter().map(|t| t.rows_written).sum()
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.tables.iter().map(|t| t.bytes).sum()
+    }
+}
+
+/// 解析数据库类资源的连接配置与写入方言
+pub fn resolve_target(resource: &Resource) -> Result<(DatabaseResourceConfig, TargetDialect), Error> {
+    let dialect = match resource.resource_type {
+        ResourceType::Mysql => TargetDialect::Mysql,
+        ResourceType::Doris => TargetDialect::Doris,
+        other => return Err(Error::InvalidValue(format!("resource type {} is not supported as collection target", other))),
+    };
+    let config = serde_json::from_value::<DatabaseResourceConfig>(resource.config.clone())
+        .map_err(|e| Error::InvalidValue(format!("invalid resource config: {}", e)))?;
+    Ok((config, dialect))
+}
+
+/// 目标库名：规则优先，否则取资源配置中的第一个库
+pub fn target_database(rule: &DatabaseCollectRule, config: &DatabaseResourceConfig) -> Result<String, Error> {
+    if !rule.target_database.is_empty() {
+        return Ok(rule.target_database.clone());
+    }
+    config
+        .default_database()
+        .ok_or_else(|| Error::EmptyValue("rule.target_database".to_string()))
+}
+
+/// 检查任务、数据源与资源的组合是否有可用的执行器
+pub fn check_supported(task: &CollectionTask, datasource: &DataSource, resource: &Resource) -> Result<(), Error> {
+    let supported = match (task.category, task.collect_type, &datasource.datasource_type) {
+        (CollectionCategory::Database, _, DataSourceType::Mysql) => true,
+        // PostgreSQL 只支持基于逻辑复制的实时采集
+        (CollectionCategory::Database, CollectType::Stream, DataSourceType::Postgres) => true,
+        _ => false,
+    };
+    if !supported {
+        return Err(Error::InvalidValue(format!(
+            "{} collection from {} datasource is not supported yet",
+            task.collect_type, datasource.datasource_type
+        )));
+    }
+    match task.collect_type {
+        CollectType::Stream => {
+            // 实时采集可写入 Kafka 或数据库类资源
+            if resource.resource_type != ResourceType::Kafka {
+                resolve_target(resource)?;
+            }
+            StreamCollectRule::parse(&task.rule)?;
+        }
+        CollectType::Full | CollectType::Incremental => {
+            resolve_target(resource)?;
+            let rule = DatabaseCollectRule::parse(&task.rule)?;
+            if task.collect_type == CollectType::Incremental && rule.tables.iter().any(|t| t.watermark_column.is_empty()) {
+                return Err(Error::EmptyValue("rule.tables.watermark_column".to_string()));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 按任务类型执行一次采集；实时采集持续运行直到收到取消信号
+pub async fn execute(
+    project_code: &str,
+    task: &CollectionTask,
+    datasource: &DataSource,
+    resource: &Resource,
+    cancel: watch::Receiver<bool>,
+    stats: &mut CollectStats,
+) -> Result<(), Error> {
+    match task.collect_type {
+        CollectType::Full => {
+            let rule = DatabaseCollectRule::parse(&task.rule)?;
+            full::run(datasource, resource, &rule, &cancel, stats).await
+        }
+        CollectType::Incremental => {
+            let rule = DatabaseCollectRule::parse(&task.rule)?;
+            incremental::run(project_code, &task.id, datasource, resource, &rule, &cancel, stats).await
+        }
+        CollectType::Stream => {
+            let rule = StreamCollectRule::parse(&task.rule)?;
+            match &datasource.datasource_type {
+                DataSourceType::Postgres => pgoutput::run(project_code, &task.id, datasource, resource, &rule, cancel, stats).await,
+                _ => binlog::run(project_code, &task.id, datasource, resource, &rule, cancel, stats).await,
+            }
+        }
+    }
+}
+
+/// 释放实时采集任务在源库上占用的复制槽等资源，任务删除或位点重置时调用
+pub async fn release_stream_source(task: &CollectionTask, datasource: &DataSource) -> Result<(), Error> {
+    if task.collect_type != CollectType::Stream || datasource.datasource_type != DataSourceType::Postgres {
+        return Ok(());
+    }
+    let rule = StreamCollectRule::parse(&task.rule)?;
+    pgoutput::drop_replication(datasource, &task.id, &rule).await
+}
+
+/// 错误描述，数据库错误保留驱动返回的详细信息
+pub fn error_detail(e: &Error) -> String {
+    match e {
+        Error::DbError(inner) => format!("database error: {}", inner),
+        other => other.to_string(),
+    }
+}
+
+/// 在后台执行一次采集并记录运行结果与运行日志，guard 在运行结束后释放
+pub fn spawn_run(project_code: String, task: CollectionTask, datasource: DataSource, resource: Resource, mut run: TaskRun, guard: RunningTaskGuard) {
+    let span = run_log::start(&project_code, &run.id);
+    tokio::spawn(
+        async move {
+            let started = Instant::now();
+            tracing::info!(
+                "collection task {} ({}) started, {} collection from {} to {}",
+                task.name, task.id, task.collect_type, datasource.name, resource.name
+            );
+            let mut stats = CollectStats::default();
+            let result = execute(&project_code, &task, &datasource, &resource, guard.cancel_signal(), &mut stats).await;
+
+            run.rows_read = stats.rows_read() as i64;
+            run.rows_written = stats.rows_written() as i64;
+            run.bytes = stats.bytes() as i64;
+            run.duration_ms = started.elapsed().as_millis() as i64;
+            run.detail = serde_json::to_value(&stats).ok().map(sqlx::types::Json);
+            run.finished_at = Some(chrono::Utc::now());
+            match &result {
+                Ok(_) if guard.is_cancelled() => run.status = RunStatus::Cancelled,
+                Ok(_) => run.status = RunStatus::Success,
+                Err(e) => {
+                    run.status = RunStatus::Failed;
+                    run.error_message = Some(error_detail(e));
+                    run.error_chain = Some(sqlx::types::Json(e.chain()));
+                }
+            }
+
+            let run_id = run.id.clone();
+            match &result {
+                Ok(_) => tracing::info!(
+                    "collection task {} run {} finished as {}, {} rows written in {} ms",
+                    task.id, run_id, run.status, run.rows_written, run.duration_ms
+                ),
+                Err(e) => tracing::warn!("collection task {} run {} failed: {}", task.id, run_id, error_detail(e)),
+            }
+
+            // 日志先落库再保存结束状态，读到结束状态时日志已完整
+            run_log::finish(&run).await;
+            let saved = autofac::get_global_app_state_ref().get_collection_service().finish_collection_run(project_code, run).await;
+            drop(guard);
+
+            if let Err(e) = saved {
+                tracing::error!("failed to save collection run {}: {}", run_id, error_detail(&e));
+            }
+        }
+        .instrument(span),
+    );
+}
+
+/// 启动时恢复重启前仍在运行的实时采集任务
+pub async fn resume_streams() {
+    let projects = match autofac::get_global_app_state_ref().get_project_service().list_all_project().await {
+        Ok(projects) => projects,
+        Err(e) => {
+            tracing::warn!("failed to list projects for resuming stream collection: {}", e);
+            return;
+        }
+    };
+
+    for project in projects.into_iter().filter(|p| matches!(p.create_status, CreateStatus::Success)) {
+        match autofac::get_global_app_state_ref().get_collection_service().resume_stream_collection(project.code.clone()).await {
+            Ok(resumed) if resumed > 0 => tracing::info!("project {} resumed {} stream collection tasks", project.code, resumed),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("failed to resume stream collection of project {}: {}", project.code, error_detail(&e)),
+        }
+    }
+}
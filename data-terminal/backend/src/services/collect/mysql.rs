@@ -0,0 +1,550 @@
+use sqlx::mysql::{MySqlArguments, MySqlConnectOptions, MySqlConnection, MySqlRow};
+use sqlx::query::Query;
+use sqlx::{ConnectOptions, Column, Connection, MySql, QueryBuilder, Row, TypeInfo};
+
+use crate::models::Error;
+use crate::models::datasource::MysqlConnectionConfig;
+use crate::models::resource::DatabaseResourceConfig;
+
+// 单条语句最多 65535 个占位符
+const MAX_PLACEHOLDERS: usize = 65535;
+
+// 写入目标库的方言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetDialect {
+    Mysql,
+    // Doris 兼容 MySQL 协议，但建表语法不同且默认不支持服务端预处理语句
+    Doris,
+}
+
+#[derive(Debug, Clone)]
+pub struct ColumnMeta {
+    pub name: String,
+    // 完整类型，例如 varchar(64)、int unsigned
+    pub column_type: String,
+    // 基础类型，例如 varchar、int
+    pub data_type: String,
+    pub nullable: bool,
+    pub comment: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TableMeta {
+    pub name: String,
+    pub columns: Vec<ColumnMeta>,
+    pub primary_key: Vec<String>,
+    pub comment: String,
+}
+
+impl TableMeta {
+    // 只保留规则中指定的列，未指定时保留全部
+    pub fn select_columns(mut self, columns: &[String]) -> Result<Self, Error> {
+        if columns.is_empty() {
+            return Ok(self);
+        }
+        for name in columns {
+            if !self.columns.iter().any(|c| &c.name == name) {
+                return Err(Error::InvalidValue(format!("column {} does not exist in table {}", name, self.name)));
+            }
+        }
+        self.columns.retain(|c| columns.contains(&c.name));
+        // 主键列未被选中时不能再作为目标表主键
+        if !self.primary_key.iter().all(|k| columns.contains(k)) {
+            self.primary_key.clear();
+        }
+        Ok(self)
+    }
+}
+
+// 从源表读取的单元格，按列类型解码，写入时原样绑定
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Null,
+    Int(i64),
+    UInt(u64),
+    Float(f32),
+    Double(f64),
+    Decimal(sqlx::types::Decimal),
+    Text(String),
+    Bytes(Vec<u8>),
+    Date(chrono::NaiveDate),
+    Time(chrono::NaiveTime),
+    DateTime(chrono::NaiveDateTime),
+    Timestamp(chrono::DateTime<chrono::Utc>),
+    Json(serde_json::Value),
+}
+
+impl Cell {
+    // 单元格数据的近似字节数，用于统计传输量
+    pub fn size(&self) -> u64 {
+        match self {
+            Cell::Null => 0,
+            Cell::Int(_) | Cell::UInt(_) | Cell::Double(_) | Cell::DateTime(_) | Cell::Timestamp(_) => 8,
+            Cell::Float(_) | Cell::Date(_) => 4,
+            Cell::Time(_) => 6,
+            Cell::Decimal(_) => 16,
+            Cell::Text(v) => v.len() as u64,
+            Cell::Bytes(v) => v.len() as u64,
+            Cell::Json(v) => v.to_string().len() as u64,
+        }
+    }
+}
+
+/// 一行数据的近似字节数
+pub fn row_size(row: &[Cell]) -> u64 {
+    row.iter().map(Cell::size).sum()
+}
+
+pub fn quote_ident(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+pub fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''"))
+}
+
+pub async fn connect_datasource(config: &serde_json::Value) -> Result<MySqlConnection, Error> {
+    let cfg = serde_json::from_value::<MysqlConnectionConfig>(config.clone())
+        .map_err(|e| Error::InvalidValue(format!("invalid mysql connection config: {}", e)))?;
+    let options = MySqlConnectOptions::new()
+        .host(&cfg.host)
+        .port(cfg.port)
+        .username(&cfg.username)
+        .password(&cfg.password)
+        .database(&cfg.database)
+        .charset("utf8mb4");
+    Ok(options.connect().await?)
+}
+
+pub async fn connect_resource(config: &DatabaseResourceConfig, dialect: TargetDialect) -> Result<MySqlConnection, Error> {
+    let mut options = MySqlConnectOptions::new()
+        .host(&config.host)
+        .port(config.port)
+        .username(&config.username)
+        .password(&config.password)
+        .charset("utf8mb4");
+    if dialect == TargetDialect::Doris {
+        // Doris 不支持 sqlx 连接后设置的部分会话变量
+        options = options
+            .pipes_as_concat(false)
+            .no_engine_substitution(false)
+            .timezone(None)
+            .set_names(false);
+    }
+    Ok(options.connect().await?)
+}
+
+/// 读取当前库中表的列与主键信息
+pub async fn table_meta(conn: &mut MySqlConnection, table: &str) -> Result<TableMeta, Error> {
+    let sql = "SELECT CAST(COLUMN_NAME AS CHAR), CAST(COLUMN_TYPE AS CHAR), CAST(DATA_TYPE AS CHAR), CAST(IS_NULLABLE AS CHAR), CAST(COLUMN_COMMENT AS CHAR) \
+        FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION";
+    let rows: Vec<(String, String, String, String, String)> = sqlx::query_as(sql).bind(table).fetch_all(&mut *conn).await?;
+    if rows.is_empty() {
+        return Err(Error::InvalidValue(format!("source table {} does not exist", table)));
+    }
+    let columns = rows
+        .into_iter()
+        .map(|(name, column_type, data_type, nullable, comment)| ColumnMeta {
+            name,
+            column_type: column_type.to_lowercase(),
+            data_type: data_type.to_lowercase(),
+            nullable: nullable.eq_ignore_ascii_case("YES"),
+            comment,
+        })
+        .collect();
+
+    let sql = "SELECT CAST(COLUMN_NAME AS CHAR) FROM information_schema.KEY_COLUMN_USAGE \
+        WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? AND CONSTRAINT_NAME = 'PRIMARY' ORDER BY ORDINAL_POSITION";
+    let primary_key: Vec<String> = sqlx::query_scalar(sql).bind(table).fetch_all(&mut *conn).await?;
+
+    let sql = "SELECT CAST(TABLE_COMMENT AS CHAR) FROM information_schema.TABLES WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ?";
+    let comment: Option<String> = sqlx::query_scalar(sql).bind(table).fetch_optional(&mut *conn).await?;
+
+    Ok(TableMeta {
+        name: table.to_string(),
+        columns,
+        primary_key,
+        comment: comment.unwrap_or_default(),
+    })
+}
+
+/// 在读连接上开启一致性快照，保证分批读取看到同一版本的数据
+pub async fn begin_snapshot(conn: &mut MySqlConnection) -> Result<(), Error> {
+    sqlx::raw_sql("SET SESSION TRANSACTION ISOLATION LEVEL REPEATABLE READ").execute(&mut *conn).await?;
+    sqlx::raw_sql("START TRANSACTION WITH CONSISTENT SNAPSHOT, READ ONLY").execute(&mut *conn).await?;
+    Ok(())
+}
+
+pub async fn end_snapshot(conn: &mut MySqlConnection) -> Result<(), Error> {
+    sqlx::raw_sql("COMMIT").execute(&mut *conn).await?;
+    Ok(())
+}
+
+// MySQL 列类型映射为 Doris 列类型
+fn doris_type(column: &ColumnMeta, is_key: bool) -> String {
+    let unsigned = column.column_type.contains("unsigned");
+    let mapped = match column.data_type.as_str() {
+        "tinyint" if unsigned => "SMALLINT".to_string(),
+        "tinyint" => "TINYINT".to_string(),
+        "smallint" if unsigned => "INT".to_string(),
+        "smallint" => "SMALLINT".to_string(),
+        "mediumint" => "INT".to_string(),
+        "int" | "integer" if unsigned => "BIGINT".to_string(),
+        "int" | "integer" => "INT".to_string(),
+        "bigint" if unsigned => "LARGEINT".to_string(),
+        "bigint" => "BIGINT".to_string(),
+        "float" => "FLOAT".to_string(),
+        "double" | "real" => "DOUBLE".to_string(),
+        "decimal" | "numeric" => {
+            // Doris DECIMAL 精度上限为 38
+            let (precision, scale) = parse_precision(&column.column_type).unwrap_or((10, 0));
+            let precision = precision.min(38);
+            format!("DECIMAL({}, {})", precision, scale.min(precision))
+        }
+        // Doris 的 VARCHAR/CHAR 长度按字节计算，utf8mb4 下按 4 倍放大
+        "char" => {
+            let len = parse_precision(&column.column_type).map(|(l, _)| l).unwrap_or(1) * 4;
+            if len <= 255 { format!("CHAR({})", len) } else { format!("VARCHAR({})", len) }
+        }
+        "varchar" => {
+            let len = parse_precision(&column.column_type).map(|(l, _)| l).unwrap_or(255) * 4;
+            format!("VARCHAR({})", len.min(65533))
+        }
+        "enum" | "set" => "VARCHAR(1024)".to_string(),
+        "date" => "DATE".to_string(),
+        "datetime" | "timestamp" => "DATETIME".to_string(),
+        "time" => "VARCHAR(32)".to_string(),
+        "year" => "SMALLINT".to_string(),
+        "json" => "JSON".to_string(),
+        "bit" => "BIGINT".to_string(),
+        _ => "STRING".to_string(),
+    };
+
+    // Doris 的 key 列不能使用 STRING/JSON/浮点类型
+    if is_key && matches!(mapped.as_str(), "STRING" | "JSON" | "FLOAT" | "DOUBLE") {
+        return "VARCHAR(1024)".to_string();
+    }
+    mapped
+}
+
+// 解析类型中括号内的长度/精度，例如 decimal(10,2) -> (10, 2)
+pub fn parse_precision(column_type: &str) -> Option<(u32, u32)> {
+    let start = column_type.find('(')?;
+    let end = column_type[start..].find(')')? + start;
+    let mut parts = column_type[start + 1..end].split(',').map(|s| s.trim().parse::<u32>());
+    let first = parts.next()?.ok()?;
+    let second = parts.next().and_then(|p| p.ok()).unwrap_or(0);
+    Some((first, second))
+}
+
+/// 生成目标表建表语句
+pub fn create_table_sql(dialect: TargetDialect, database: &str, table: &str, meta: &TableMeta, replication_num: u32) -> String {
+    let full_name = format!("{}.{}", quote_ident(database), quote_ident(table));
+    match dialect {
+        TargetDialect::Mysql => {
+            let mut defs: Vec<String> = meta
+                .columns
+                .iter()
+                .map(|c| {
+                    format!(
+                        "{} {}{} COMMENT {}",
+                        quote_ident(&c.name),
+                        c.column_type,
+                        if c.nullable { " NULL" } else { " NOT NULL" },
+                        quote_literal(&c.comment)
+                    )
+                })
+                .collect();
+            if !meta.primary_key.is_empty() {
+                let keys: Vec<String> = meta.primary_key.iter().map(|k| quote_ident(k)).collect();
+                defs.push(format!("PRIMARY KEY ({})", keys.join(", ")));
+            }
+            format!(
+                "CREATE TABLE IF NOT EXISTS {} (\n  {}\n) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT={}",
+                full_name,
+                defs.join(",\n  "),
+                quote_literal(&meta.comment)
+            )
+        }
+        TargetDialect::Doris => {
+            // Doris 要求 key 列排在最前面；无主键时使用第一列作为 duplicate key
+            let keys: Vec<String> = if meta.primary_key.is_empty() {
+                meta.columns.iter().take(1).map(|c| c.name.clone()).collect()
+            } else {
+                meta.primary_key.clone()
+            };
+            let mut ordered: Vec<&ColumnMeta> = keys
+                .iter()
+                .filter_map(|k| meta.columns.iter().find(|c| &c.name == k))
+                .collect();
+            ordered.extend(meta.columns.iter().filter(|c| !keys.contains(&c.name)));
+
+            let defs: Vec<String> = ordered
+                .iter()
+                .map(|c| {
+                    let is_key = keys.contains(&c.name);
+                    format!(
+                        "{} {}{} COMMENT {}",
+                        quote_ident(&c.name),
+                        doris_type(c, is_key),
+                        if c.nullable && !is_key { " NULL" } else { " NOT NULL" },
+                        quote_literal(&c.comment)
+                    )
+                })
+                .collect();
+            let key_list: Vec<String> = keys.iter().map(|k| quote_ident(k)).collect();
+            let key_model = if meta.primary_key.is_empty() { "DUPLICATE KEY" } else { "UNIQUE KEY" };
+            format!(
+                "CREATE TABLE IF NOT EXISTS {} (\n  {}\n) {}({}) COMMENT {} DISTRIBUTED BY HASH({}) BUCKETS AUTO PROPERTIES (\"replication_num\" = \"{}\")",
+                full_name,
+                defs.join(",\n  "),
+                key_model,
+                key_list.join(", "),
+                quote_literal(&meta.comment),
+                key_list.join(", "),
+                replication_num
+            )
+        }
+    }
+}
+
+/// 确保目标库与目标表存在
+pub async fn ensure_target_table(
+    conn: &mut MySqlConnection,
+    dialect: TargetDialect,
+    database: &str,
+    table: &str,
+    meta: &TableMeta,
+    replication_num: u32,
+) -> Result<(), Error> {
+    let sql = format!("CREATE DATABASE IF NOT EXISTS {}", quote_ident(database));
+    sqlx::raw_sql(&sql).execute(&mut *conn).await?;
+    let sql = create_table_sql(dialect, database, table, meta, replication_num);
+    sqlx::raw_sql(&sql).execute(&mut *conn).await?;
+    Ok(())
+}
+
+pub async fn truncate_table(conn: &mut MySqlConnection, database: &str, table: &str) -> Result<(), Error> {
+    let sql = format!("TRUNCATE TABLE {}.{}", quote_ident(database), quote_ident(table));
+    sqlx::raw_sql(&sql).execute(&mut *conn).await?;
+    Ok(())
+}
+
+// 按列类型解码单元格；类型名来自 sqlx 的 MySqlTypeInfo
+fn decode_cell(row: &MySqlRow, index: usize) -> Result<Cell, sqlx::Error> {
+    let type_name = row.column(index).type_info().name().to_string();
+    let cell = match type_name.as_str() {
+        "BOOLEAN" | "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "BIGINT" => {
+            row.try_get_unchecked::<Option<i64>, _>(index)?.map(Cell::Int)
+        }
+        "TINYINT UNSIGNED" | "SMALLINT UNSIGNED" | "MEDIUMINT UNSIGNED" | "INT UNSIGNED" | "BIGINT UNSIGNED" | "YEAR" | "BIT" => {
+            row.try_get_unchecked::<Option<u64>, _>(index)?.map(Cell::UInt)
+        }
+        "FLOAT" => row.try_get_unchecked::<Option<f32>, _>(index)?.map(Cell::Float),
+        "DOUBLE" => row.try_get_unchecked::<Option<f64>, _>(index)?.map(Cell::Double),
+        "DECIMAL" => row.try_get_unchecked::<Option<sqlx::types::Decimal>, _>(index)?.map(Cell::Decimal),
+        "DATE" => row.try_get_unchecked::<Option<chrono::NaiveDate>, _>(index)?.map(Cell::Date),
+        "TIME" => row.try_get_unchecked::<Option<chrono::NaiveTime>, _>(index)?.map(Cell::Time),
+        "DATETIME" => row.try_get_unchecked::<Option<chrono::NaiveDateTime>, _>(index)?.map(Cell::DateTime),
+        "TIMESTAMP" => row.try_get_unchecked::<Option<chrono::DateTime<chrono::Utc>>, _>(index)?.map(Cell::Timestamp),
+        "JSON" => row
+            .try_get_unchecked::<Option<sqlx::types::Json<serde_json::Value>>, _>(index)?
+            .map(|v| Cell::Json(v.0)),
+        "BINARY" | "VARBINARY" | "TINYBLOB" | "BLOB" | "MEDIUMBLOB" | "LONGBLOB" | "GEOMETRY" => {
+            row.try_get_unchecked::<Option<Vec<u8>>, _>(index)?.map(Cell::Bytes)
+        }
+        _ => row.try_get_unchecked::<Option<String>, _>(index)?.map(Cell::Text),
+    };
+    Ok(cell.unwrap_or(Cell::Null))
+}
+
+pub fn decode_row(row: &MySqlRow) -> Result<Vec<Cell>, Error> {
+    (0..row.columns().len())
+        .map(|i| decode_cell(row, i).map_err(Error::from))
+        .collect()
+}
+
+pub fn bind_cell<'q>(query: Query<'q, MySql, MySqlArguments>, cell: Cell) -> Query<'q, MySql, MySqlArguments> {
+    match cell {
+        Cell::Null => query.bind(None::<String>),
+        Cell::Int(v) => query.bind(v),
+        Cell::UInt(v) => query.bind(v),
+        Cell::Float(v) => query.bind(v),
+        Cell::Double(v) => query.bind(v),
+        Cell::Decimal(v) => query.bind(v),
+        Cell::Text(v) => query.bind(v),
+        Cell::Bytes(v) => query.bind(v),
+        Cell::Date(v) => query.bind(v),
+        Cell::Time(v) => query.bind(v),
+        Cell::DateTime(v) => query.bind(v),
+        Cell::Timestamp(v) => query.bind(v),
+        Cell::Json(v) => query.bind(sqlx::types::Json(v)),
+    }
+}
+
+fn push_bind_cell(separated: &mut sqlx::query_builder::Separated<'_, '_, MySql, &'static str>, cell: Cell) {
+    match cell {
+        Cell::Null => separated.push_bind(None::<String>),
+        Cell::Int(v) => separated.push_bind(v),
+        Cell::UInt(v) => separated.push_bind(v),
+        Cell::Float(v) => separated.push_bind(v),
+        Cell::Double(v) => separated.push_bind(v),
+        Cell::Decimal(v) => separated.push_bind(v),
+        Cell::Text(v) => separated.push_bind(v),
+        Cell::Bytes(v) => separated.push_bind(v),
+        Cell::Date(v) => separated.push_bind(v),
+        Cell::Time(v) => separated.push_bind(v),
+        Cell::DateTime(v) => separated.push_bind(v),
+        Cell::Timestamp(v) => separated.push_bind(v),
+        Cell::Json(v) => separated.push_bind(sqlx::types::Json(v)),
+    };
+}
+
+// 以字面量形式输出单元格，用于不支持预处理语句的 Doris
+fn cell_literal(cell: &Cell) -> String {
+    match cell {
+        Cell::Null => "NULL".to_string(),
+        Cell::Int(v) => v.to_string(),
+        Cell::UInt(v) => v.to_string(),
+        Cell::Float(v) => v.to_string(),
+        Cell::Double(v) => v.to_string(),
+        Cell::Decimal(v) => v.to_string(),
+        Cell::Text(v) => quote_literal(v),
+        // 二进制数据按原始字节写入，避免按 UTF-8 转换时被替换
+        Cell::Bytes(v) => format!("unhex('{}')", v.iter().map(|b| format!("{:02x}", b)).collect::<String>()),
+        Cell::Date(v) => quote_literal(&v.format("%Y-%m-%d").to_string()),
+        Cell::Time(v) => quote_literal(&v.format("%H:%M:%S%.f").to_string()),
+        Cell::DateTime(v) => quote_literal(&v.format("%Y-%m-%d %H:%M:%S%.f").to_string()),
+        Cell::Timestamp(v) => quote_literal(&v.format("%Y-%m-%d %H:%M:%S%.f").to_string()),
+        Cell::Json(v) => quote_literal(&v.to_string()),
+    }
+}
+
+/// 单批可写入的最大行数，受占位符数量限制
+pub fn max_batch_rows(batch_size: usize, column_count: usize) -> usize {
+    batch_size.min(MAX_PLACEHOLDERS / column_count.max(1)).max(1)
+}
+
+/// 批量写入目标表；upsert 为 true 时主键冲突的行会被更新
+pub async fn write_batch(
+    conn: &mut MySqlConnection,
+    dialect: TargetDialect,
+    database: &str,
+    table: &str,
+    columns: &[String],
+    rows: Vec<Vec<Cell>>,
+    upsert: bool,
+) -> Result<u64, Error> {
+    if rows.is_empty() {
+        return Ok(0);
+    }
+    let count = rows.len() as u64;
+    let column_list: Vec<String> = columns.iter().map(|c| quote_ident(c)).collect();
+    let head = format!(
+        "INSERT INTO {}.{} ({}) ",
+        quote_ident(database),
+        quote_ident(table),
+        column_list.join(", ")
+    );
+
+    match dialect {
+        TargetDialect::Mysql => {
+            let mut builder: QueryBuilder<MySql> = QueryBuilder::new(head);
+            builder.push_values(rows, |mut separated, row| {
+                for cell in row {
+                    push_bind_cell(&mut separated, cell);
+                }
+            });
+            if upsert {
+                let updates: Vec<String> = column_list.iter().map(|c| format!("{} = VALUES({})", c, c)).collect();
+                builder.push(" ON DUPLICATE KEY UPDATE ");
+                builder.push(updates.join(", "));
+            }
+            builder.build().execute(&mut *conn).await?;
+        }
+        TargetDialect::Doris => {
+            // Doris unique key 模型写入即覆盖，无需额外处理 upsert
+            let values: Vec<String> = rows
+                .iter()
+                .map(|row| format!("({})", row.iter().map(cell_literal).collect::<Vec<_>>().join(", ")))
+                .collect();
+            let sql = format!("{}VALUES {}", head, values.join(", "));
+            sqlx::raw_sql(&sql).execute(&mut *conn).await?;
+        }
+    }
+
+    Ok(count)
+}
+
+/// 按主键批量删除目标表中的行，返回删除的行数
+pub async fn delete_rows(
+    conn: &mut MySqlConnection,
+    dialect: TargetDialect,
+    database: &str,
+    table: &str,
+    key_columns: &[String],
+    keys: Vec<Vec<Cell>>,
+) -> Result<u64, Error> {
+    if keys.is_empty() {
+        return Ok(0);
+    }
+    let full_name = format!("{}.{}", quote_ident(database), quote_ident(table));
+
+    match dialect {
+        TargetDialect::Mysql => {
+            let key_list: Vec<String> = key_columns.iter().map(|c| quote_ident(c)).collect();
+            let mut builder: QueryBuilder<MySql> =
+                QueryBuilder::new(format!("DELETE FROM {} WHERE ({}) IN ", full_name, key_list.join(", ")));
+            builder.push_tuples(keys, |mut separated, key| {
+                for cell in key {
+                    push_bind_cell(&mut separated, cell);
+                }
+            });
+            Ok(builder.build().execute(&mut *conn).await?.rows_affected())
+        }
+        TargetDialect::Doris => {
+            // Doris 的 DELETE 不支持多列 IN，逐行删除
+            let mut deleted = 0;
+            for key in keys {
+                let conditions: Vec<String> = key_columns
+                    .iter()
+                    .zip(key.iter())
+                    .map(|(c, v)| match v {
+                        Cell::Null => format!("{} IS NULL", quote_ident(c)),
+                        v => format!("{} = {}", quote_ident(c), cell_literal(v)),
+                    })
+                    .collect();
+                let sql = format!("DELETE FROM {} WHERE {}", full_name, conditions.join(" AND "));
+                sqlx::raw_sql(&sql).execute(&mut *conn).await?;
+                deleted += 1;
+            }
+            Ok(deleted)
+        }
+    }
+}
+
+/// 单元格转为 JSON，用于输出到消息队列
+pub fn cell_json(cell: &Cell) -> serde_json::Value {
+    use serde_json::Value;
+    match cell {
+        Cell::Null => Value::Null,
+        Cell::Int(v) => Value::from(*v),
+        Cell::UInt(v) => Value::from(*v),
+        Cell::Float(v) => Value::from(*v),
+        Cell::Double(v) => Value::from(*v),
+        // 保留精度，以字符串输出
+        Cell::Decimal(v) => Value::String(v.to_string()),
+        Cell::Text(v) => Value::String(v.clone()),
+        Cell::Bytes(v) => Value::String(String::from_utf8_lossy(v).to_string()),
+        Cell::Date(v) => Value::String(v.format("%Y-%m-%d").to_string()),
+        Cell::Time(v) => Value::String(v.format("%H:%M:%S%.f").to_string()),
+        Cell::DateTime(v) => Value::String(v.format("%Y-%m-%d %H:%M:%S%.f").to_string()),
+        Cell::Timestamp(v) => Value::String(v.to_rfc3339()),
+        Cell::Json(v) => v.clone(),
+    }
+}
+
+/// 关闭连接，忽略关闭失败
+pub async fn close(conn: MySqlConnection) {
+    let _ = conn.close().await;
+}
@@ -0,0 +1,849 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgConnection;
+use tokio::sync::watch;
+
+use crate::autofac;
+use crate::models::Error;
+use crate::models::collection::{CollectionCheckpoint, StreamCollectRule};
+use crate::models::datasource::{DataSource, PostgresConnectionConfig};
+use crate::models::resource::Resource;
+use super::change::{ChangeEvent, ChangeOp, ChangeSink, StreamTable};
+use super::mysql::{self, Cell};
+use super::postgres::{self, quote_ident};
+use super::{CollectStats, TableStats};
+
+// PostgreSQL 时间戳以 2000-01-01 为起点
+const PG_EPOCH_MS: i64 = 946_684_800_000;
+
+// 逻辑复制消费位点，lsn 为最后一个已写出事务的结束位置
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PgOffset {
+    pub slot: String,
+    pub lsn: String,
+}
+
+/// 任务使用的复制槽与发布名，规则未指定时按任务ID生成
+pub fn slot_name(task_id: &str, rule: &StreamCollectRule) -> String {
+    if !rule.slot_name.is_empty() {
+        return rule.slot_name.clone();
+    }
+    format!("df_{}", task_id.replace('-', "").to_lowercase())
+}
+
+/// 删除任务的复制槽与发布，复制槽不再被消费时会持续占用源库 WAL
+pub async fn drop_replication(source: &DataSource, task_id: &str, rule: &StreamCollectRule) -> Result<(), Error> {
+    let cfg = postgres::parse_config(&source.connection_config)?;
+    let slot = slot_name(task_id, rule);
+    let mut conn = postgres::connect(&cfg).await?;
+    let dropped = async {
+        sqlx::query("SELECT pg_drop_replication_slot(slot_name) FROM pg_replication_slots WHERE slot_name = $1::name")
+            .bind(&slot)
+            .execute(&mut conn)
+            .await?;
+        sqlx::raw_sql(&format!("DROP PUBLICATION IF EXISTS {}", quote_ident(&slot))).execute(&mut conn).await?;
+        Ok::<(), Error>(())
+    }
+    .await;
+    postgres::close(conn).await;
+    dropped?;
+    tracing::info!("replication slot and publication {} of collection task {} dropped", slot, task_id);
+    Ok(())
+}
+
+/// 通过 pgoutput 逻辑复制槽读取配置表的变更并写出到目标，直到收到取消信号
+pub async fn run(
+    project_code: &str,
+    task_id: &str,
+    source: &DataSource,
+    target: &Resource,
+    rule: &StreamCollectRule,
+    cancel: watch::Receiver<bool>,
+    stats: &mut CollectStats,
+) -> Result<(), Error> {
+    let cfg = postgres::parse_config(&source.connection_config)?;
+    let mut conn = postgres::connect(&cfg).await?;
+    let result = async {
+        let mut reader = PgOutputReader::load(project_code, task_id, &cfg, rule, &mut conn).await?;
+        stats.tables = reader
+            .tables
+            .iter()
+            .map(|t| TableStats {
+                source_table: t.rule.source_table.clone(),
+                target_table: t.rule.target_table().to_string(),
+                ..Default::default()
+            })
+            .collect();
+
+        let mut sink = ChangeSink::open(target, rule, &reader.tables).await?;
+        let started = Instant::now();
+        let result = reader.consume(&mut conn, &mut sink, cancel, stats).await;
+        let elapsed = started.elapsed().as_millis() as u64;
+        stats.tables.iter_mut().for_each(|t| t.duration_ms = elapsed);
+        sink.close().await;
+        result
+    }
+    .await;
+    postgres::close(conn).await;
+    result
+}
+
+// 元组中的列值
+enum TupleValue {
+    Null,
+    // 未修改的 TOAST 值，消息中不携带
+    Unchanged,
+    Text(String),
+}
+
+struct PgOutputReader<'a> {
+    project_code: &'a str,
+    task_id: &'a str,
+    rule: &'a StreamCollectRule,
+    schema: String,
+    slot: String,
+    tables: Vec<StreamTable>,
+    // 被采集列在源库中的类型名，与 tables 一一对应
+    pg_types: Vec<Vec<String>>,
+    // relation id -> (tables 下标, 被采集列在元组中的下标)
+    relations: HashMap<u32, (usize, Vec<usize>)>,
+    // 已写出并保存的位点
+    confirmed: u64,
+    // 最后一个已提交事务的结束位置
+    lsn: u64,
+    // 当前事务的提交时间
+    commit_ts_ms: i64,
+    pending: Vec<ChangeEvent>,
+    ready: Vec<ChangeEvent>,
+}
+
+impl<'a> PgOutputReader<'a> {
+    async fn load(
+        project_code: &'a str,
+        task_id: &'a str,
+        cfg: &PostgresConnectionConfig,
+        rule: &'a StreamCollectRule,
+        conn: &mut PgConnection,
+    ) -> Result<Self, Error> {
+        let schema = postgres::schema(cfg).to_string();
+        let mut tables = Vec::with_capacity(rule.tables.len());
+        let mut pg_types = Vec::with_capacity(rule.tables.len());
+        for table in &rule.tables {
+            let (full, types) = postgres::table_meta(conn, &schema, &table.source_table).await?;
+            let meta = full.clone().select_columns(&table.columns)?;
+            pg_types.push(
+                meta.columns
+                    .iter()
+                    .filter_map(|c| full.columns.iter().position(|f| f.name == c.name))
+                    .map(|i| types[i].clone())
+                    .collect(),
+            );
+            tables.push(StreamTable {
+                database: schema.clone(),
+                rule: table.clone(),
+                meta,
+            });
+        }
+
+        Ok(Self {
+            project_code,
+            task_id,
+            rule,
+            schema,
+            slot: slot_name(task_id, rule),
+            tables,
+            pg_types,
+            relations: HashMap::new(),
+            confirmed: 0,
+            lsn: 0,
+            commit_ts_ms: 0,
+            pending: Vec::new(),
+            ready: Vec::new(),
+        })
+    }
+
+    async fn consume(
+        &mut self,
+        conn: &mut PgConnection,
+        sink: &mut ChangeSink,
+        mut cancel: watch::Receiver<bool>,
+        stats: &mut CollectStats,
+    ) -> Result<(), Error> {
+        let wal_level: String = sqlx::query_scalar("SHOW wal_level").fetch_one(&mut *conn).await?;
+        if wal_level != "logical" {
+            return Err(Error::InvalidValue(format!("wal_level must be logical, got {}", wal_level)));
+        }
+        self.ensure_publication(conn).await?;
+        self.start_offset(conn).await?;
+        tracing::info!("collection task {} start reading slot {} from {}", self.task_id, self.slot, format_lsn(self.confirmed));
+
+        let peek_sql = "SELECT lsn::text, data FROM pg_logical_slot_peek_binary_changes($1::name, NULL, $2, \
+            'proto_version', '1', 'publication_names', $3)";
+        while !*cancel.borrow() {
+            // 按完整事务返回，数量可能略多于 batch_size
+            let rows: Vec<(String, Vec<u8>)> = sqlx::query_as(peek_sql)
+                .bind(&self.slot)
+                .bind(self.rule.batch_size.min(i32::MAX as usize) as i32)
+                .bind(&self.slot)
+                .fetch_all(&mut *conn)
+                .await?;
+            if rows.is_empty() {
+                tokio::select! {
+                    changed = cancel.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                    _ = tokio::time::sleep(Duration::from_millis(self.rule.flush_interval_ms)) => {}
+                }
+                continue;
+            }
+            for (lsn, data) in &rows {
+                self.handle(lsn, data)?;
+            }
+            // 批末尾未提交的事务下次从位点重新读取
+            self.pending.clear();
+            self.flush(conn, sink, stats).await?;
+        }
+        Ok(())
+    }
+
+    // 发布与任务配置的表保持一致
+    async fn ensure_publication(&self, conn: &mut PgConnection) -> Result<(), Error> {
+        let table_list = self
+            .tables
+            .iter()
+            .map(|t| format!("{}.{}", quote_ident(&self.schema), quote_ident(&t.rule.source_table)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_publication WHERE pubname = $1::name)")
+            .bind(&self.slot)
+            .fetch_one(&mut *conn)
+            .await?;
+        let sql = if exists {
+            format!("ALTER PUBLICATION {} SET TABLE {}", quote_ident(&self.slot), table_list)
+        } else {
+            format!("CREATE PUBLICATION {} FOR TABLE {}", quote_ident(&self.slot), table_list)
+        };
+        sqlx::raw_sql(&sql).execute(&mut *conn).await?;
+        Ok(())
+    }
+
+    // 复制槽不存在时创建，从当前位置开始；保存的位点领先复制槽时推进复制槽
+    async fn start_offset(&mut self, conn: &mut PgConnection) -> Result<(), Error> {
+        let slot: Option<(Option<String>, Option<String>, Option<String>)> = sqlx::query_as(
+            "SELECT plugin::text, database::text, confirmed_flush_lsn::text FROM pg_replication_slots WHERE slot_name = $1::name",
+        )
+        .bind(&self.slot)
+        .fetch_optional(&mut *conn)
+        .await?;
+        let saved = autofac::get_global_app_state_ref()
+            .get_collection_service()
+            .get_checkpoint(self.project_code.to_string(), self.task_id.to_string())
+            .await?
+            .map(|c| {
+                serde_json::from_value::<PgOffset>(c.checkpoint)
+                    .map_err(|e| Error::InvalidValue(format!("invalid replication checkpoint: {}", e)))
+            })
+            .transpose()?
+            .filter(|offset| offset.slot == self.slot);
+
+        let slot_lsn = match slot {
+            Some((plugin, database, lsn)) => {
+                if plugin.as_deref() != Some("pgoutput") {
+                    return Err(Error::InvalidValue(format!("replication slot {} is not a pgoutput logical slot", self.slot)));
+                }
+                let current: String = sqlx::query_scalar("SELECT current_database()::text").fetch_one(&mut *conn).await?;
+                if database.as_deref() != Some(current.as_str()) {
+                    return Err(Error::InvalidValue(format!("replication slot {} belongs to another database", self.slot)));
+                }
+                parse_lsn(lsn.as_deref().unwrap_or_default())?
+            }
+            None => {
+                let lsn: String = sqlx::query_scalar("SELECT lsn::text FROM pg_create_logical_replication_slot($1::name, 'pgoutput')")
+                    .bind(&self.slot)
+                    .fetch_one(&mut *conn)
+                    .await?;
+                if let Some(saved) = &saved {
+                    tracing::warn!(
+                        "replication slot {} of collection task {} was missing and has been recreated, changes after {} before {} are lost",
+                        self.slot, self.task_id, saved.lsn, lsn
+                    );
+                }
+                let lsn = parse_lsn(&lsn)?;
+                self.confirmed = lsn;
+                self.lsn = lsn;
+                return self.save_offset().await;
+            }
+        };
+
+        match saved {
+            Some(saved) => {
+                let saved_lsn = parse_lsn(&saved.lsn)?;
+                if saved_lsn > slot_lsn {
+                    self.advance_slot(conn, saved_lsn).await?;
+                }
+                self.confirmed = saved_lsn.max(slot_lsn);
+                self.lsn = self.confirmed;
+                Ok(())
+            }
+            None => {
+                self.confirmed = slot_lsn;
+                self.lsn = slot_lsn;
+                self.save_offset().await
+            }
+        }
+    }
+
+    fn handle(&mut self, lsn: &str, data: &[u8]) -> Result<(), Error> {
+        let mut msg = Message { data, pos: 0 };
+        match msg.u8()? {
+            b'B' => {
+                msg.i64()?;
+                self.commit_ts_ms = msg.i64()? / 1000 + PG_EPOCH_MS;
+                self.pending.clear();
+            }
+            b'C' => {
+                msg.u8()?;
+                msg.i64()?;
+                let end_lsn = msg.i64()? as u64;
+                // 重启后复制槽可能落后于保存的位点，已写出的事务跳过
+                if end_lsn <= self.confirmed {
+                    self.pending.clear();
+                } else {
+                    self.ready.append(&mut self.pending);
+                }
+                self.lsn = self.lsn.max(end_lsn);
+            }
+            b'R' => self.relation(&mut msg)?,
+            b'I' => {
+                let relid = msg.u32()?;
+                msg.expect(b'N')?;
+                let new = msg.tuple()?;
+                self.push(relid, ChangeOp::Insert, None, Some(new), lsn)?;
+            }
+            b'U' => {
+                let relid = msg.u32()?;
+                let old = match msg.u8()? {
+                    b'K' | b'O' => {
+                        let old = msg.tuple()?;
+                        msg.expect(b'N')?;
+                        Some(old)
+                    }
+                    b'N' => None,
+                    other => return Err(protocol_error(&format!("unexpected tuple type {}", other as char))),
+                };
+                let new = msg.tuple()?;
+                self.push(relid, ChangeOp::Update, old, Some(new), lsn)?;
+            }
+            b'D' => {
+                let relid = msg.u32()?;
+                msg.u8()?;
+                let old = msg.tuple()?;
+                self.push(relid, ChangeOp::Delete, Some(old), None, lsn)?;
+            }
+            b'T' => tracing::warn!("collection task {} ignored truncate at {}", self.task_id, lsn),
+            // Type、Origin、Message 与采集无关
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // 表结构变更后会重新发送 Relation，按列名重新定位被采集的列
+    fn relation(&mut self, msg: &mut Message) -> Result<(), Error> {
+        let relid = msg.u32()?;
+        let namespace = msg.cstr()?;
+        let name = msg.cstr()?;
+        msg.u8()?;
+        let count = msg.i16()?;
+        let mut columns = Vec::with_capacity(count.max(0) as usize);
+        for _ in 0..count {
+            msg.u8()?;
+            columns.push(msg.cstr()?);
+            msg.i32()?;
+            msg.i32()?;
+        }
+
+        if namespace != self.schema {
+            return Ok(());
+        }
+        let Some(index) = self.tables.iter().position(|t| t.rule.source_table == name) else {
+            return Ok(());
+        };
+        let positions = self.tables[index]
+            .meta
+            .columns
+            .iter()
+            .map(|c| {
+                columns.iter().position(|column| *column == c.name).ok_or_else(|| {
+                    Error::InvalidValue(format!("column {} no longer exists in table {}", c.name, self.tables[index].rule.source_table))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.relations.insert(relid, (index, positions));
+        Ok(())
+    }
+
+    fn push(
+        &mut self,
+        relid: u32,
+        op: ChangeOp,
+        old: Option<Vec<TupleValue>>,
+        new: Option<Vec<TupleValue>>,
+        lsn: &str,
+    ) -> Result<(), Error> {
+        let Some((index, positions)) = self.relations.get(&relid) else {
+            return Ok(());
+        };
+        let index = *index;
+        let types = &self.pg_types[index];
+        let table = &self.tables[index].rule.source_table;
+        let before = old.as_ref().map(|tuple| decode_tuple(tuple, positions, types, None, table)).transpose()?;
+        let after = new
+            .as_ref()
+            .map(|tuple| decode_tuple(tuple, positions, types, before.as_deref(), table))
+            .transpose()?;
+        self.pending.push(ChangeEvent {
+            op,
+            table: index,
+            before,
+            after,
+            ts_ms: self.commit_ts_ms,
+            position: serde_json::json!({ "lsn": lsn }),
+        });
+        Ok(())
+    }
+
+    // 先写出变更、保存位点，再推进复制槽，保证至少一次投递
+    async fn flush(&mut self, conn: &mut PgConnection, sink: &mut ChangeSink, stats: &mut CollectStats) -> Result<(), Error> {
+        if !self.ready.is_empty() {
+            sink.apply(&self.tables, &self.ready).await?;
+            for event in &self.ready {
+                let table = &mut stats.tables[event.table];
+                table.rows_read += 1;
+                table.rows_written += 1;
+                table.bytes += event.after.as_deref().or(event.before.as_deref()).map(mysql::row_size).unwrap_or(0);
+            }
+            self.ready.clear();
+        }
+        if self.lsn > self.confirmed {
+            self.save_offset().await?;
+            self.advance_slot(conn, self.lsn).await?;
+            self.confirmed = self.lsn;
+        }
+        Ok(())
+    }
+
+    async fn advance_slot(&self, conn: &mut PgConnection, lsn: u64) -> Result<(), Error> {
+        sqlx::query("SELECT pg_replication_slot_advance($1::name, $2::pg_lsn)")
+            .bind(&self.slot)
+            .bind(format_lsn(lsn))
+            .execute(&mut *conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn save_offset(&self) -> Result<(), Error> {
+        let offset = PgOffset { slot: self.slot.clone(), lsn: format_lsn(self.lsn) };
+        let checkpoint = CollectionCheckpoint {
+            task_id: self.task_id.to_string(),
+            checkpoint: serde_json::to_value(&offset).unwrap_or_default(),
+            updated_at: chrono::Utc::now(),
+        };
+        autofac::get_global_app_state_ref()
+            .get_collection_service()
+            .save_checkpoint(self.project_code.to_string(), checkpoint)
+            .await
+    }
+}
+
+// 按被采集列取出元组中的值；未修改的 TOAST 值只能取自完整的旧行
+fn decode_tuple(
+    tuple: &[TupleValue],
+    positions: &[usize],
+    types: &[String],
+    old: Option<&[Cell]>,
+    table: &str,
+) -> Result<Vec<Cell>, Error> {
+    positions
+        .iter()
+        .enumerate()
+        .map(|(i, &pos)| match tuple.get(pos) {
+            Some(TupleValue::Text(text)) => postgres::text_cell(text, &types[i]),
+            Some(TupleValue::Unchanged) => match old.and_then(|row| row.get(i)) {
+                Some(cell) if *cell != Cell::Null => Ok(cell.clone()),
+                _ => Err(Error::InvalidValue(format!(
+                    "unchanged toast value in table {}, set REPLICA IDENTITY FULL on the table",
+                    table
+                ))),
+            },
+            Some(TupleValue::Null) | None => Ok(Cell::Null),
+        })
+        .collect()
+}
+
+// pgoutput 协议消息，整数均为大端序
+struct Message<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Message<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], Error> {
+        let end = self.pos + len;
+        let bytes = self.data.get(self.pos..end).ok_or_else(|| protocol_error("message is truncated"))?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i16(&mut self) -> Result<i16, Error> {
+        Ok(i16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn i32(&mut self) -> Result<i32, Error> {
+        Ok(i32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn i64(&mut self) -> Result<i64, Error> {
+        Ok(i64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn expect(&mut self, tag: u8) -> Result<(), Error> {
+        match self.u8()? {
+            t if t == tag => Ok(()),
+            other => Err(protocol_error(&format!("expected {} but got {}", tag as char, other as char))),
+        }
+    }
+
+    // 以 \0 结尾的字符串
+    fn cstr(&mut self) -> Result<String, Error> {
+        let rest = &self.data[self.pos.min(self.data.len())..];
+        let len = rest.iter().position(|b| *b == 0).ok_or_else(|| protocol_error("unterminated string"))?;
+        let text = String::from_utf8_lossy(&rest[..len]).to_string();
+        self.pos += len + 1;
+        Ok(text)
+    }
+
+    fn tuple(&mut self) -> Result<Vec<TupleValue>, Error> {
+        let count = self.i16()?;
+        let mut values = Vec::with_capacity(count.max(0) as usize);
+        for _ in 0..count {
+            let value = match self.u8()? {
+                b'n' => TupleValue::Null,
+                b'u' => TupleValue::Unchanged,
+                b't' => {
+                    let len = self.i32()?.max(0) as usize;
+                    TupleValue::Text(String::from_utf8_lossy(self.take(len)?).to_string())
+                }
+                other => return Err(protocol_error(&format!("unsupported tuple value type {}", other as char))),
+            };
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+fn protocol_error(message: &str) -> Error {
+    Error::InternalError(format!("pgoutput protocol error: {}", message))
+}
+
+// LSN 文本格式为 "高32位/低32位" 的十六进制
+fn parse_lsn(text: &str) -> Result<u64, Error> {
+    let (high, low) = text
+        .split_once('/')
+        .ok_or_else(|| Error::InvalidValue(format!("invalid lsn {}", text)))?;
+    let parse = |part: &str| u64::from_str_radix(part, 16).map_err(|_| Error::InvalidValue(format!("invalid lsn {}", text)));
+    Ok((parse(high)? << 32) | parse(low)?)
+}
+
+fn format_lsn(lsn: u64) -> String {
+    format!("{:X}/{:X}", lsn >> 32, lsn & 0xFFFF_FFFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::mysql::{ColumnMeta, TableMeta};
+
+    const RELID: u32 = 16384;
+
+    fn rule() -> StreamCollectRule {
+        StreamCollectRule::parse(&serde_json::json!({"tables": [{"source_table": "orders"}]})).unwrap()
+    }
+
+    fn reader(rule: &StreamCollectRule) -> PgOutputReader<'_> {
+        let column = |name: &str, data_type: &str| ColumnMeta {
+            name: name.to_string(),
+            column_type: data_type.to_string(),
+            data_type: data_type.to_string(),
+            nullable: true,
+            comment: String::new(),
+        };
+        PgOutputReader {
+            project_code: "p1",
+            task_id: "t1",
+            rule,
+            schema: "public".to_string(),
+            slot: "df_t1".to_string(),
+            tables: vec![StreamTable {
+                database: "public".to_string(),
+                rule: rule.tables[0].clone(),
+                meta: TableMeta {
+                    name: "orders".to_string(),
+                    columns: vec![column("id", "int8"), column("note", "text")],
+                    primary_key: vec!["id".to_string()],
+                    comment: String::new(),
+                },
+            }],
+            pg_types: vec![vec!["int8".to_string(), "text".to_string()]],
+            relations: HashMap::new(),
+            confirmed: 0,
+            lsn: 0,
+            commit_ts_ms: 0,
+            pending: Vec::new(),
+            ready: Vec::new(),
+        }
+    }
+
+    fn cstr(buf: &mut Vec<u8>, text: &str) {
+        buf.extend_from_slice(text.as_bytes());
+        buf.push(0);
+    }
+
+    fn tuple(buf: &mut Vec<u8>, values: &[TupleValue]) {
+        buf.extend_from_slice(&(values.len() as i16).to_be_bytes());
+        for value in values {
+            match value {
+                TupleValue::Null => buf.push(b'n'),
+                TupleValue::Unchanged => buf.push(b'u'),
+                TupleValue::Text(text) => {
+                    buf.push(b't');
+                    buf.extend_from_slice(&(text.len() as i32).to_be_bytes());
+                    buf.extend_from_slice(text.as_bytes());
+                }
+            }
+        }
+    }
+
+    fn text(value: &str) -> TupleValue {
+        TupleValue::Text(value.to_string())
+    }
+
+    // 源表比被采集的列多一列 extra，用于校验列的定位
+    fn relation(namespace: &str, name: &str, columns: &[&str]) -> Vec<u8> {
+        let mut buf = vec![b'R'];
+        buf.extend_from_slice(&RELID.to_be_bytes());
+        cstr(&mut buf, namespace);
+        cstr(&mut buf, name);
+        buf.push(b'd');
+        buf.extend_from_slice(&(columns.len() as i16).to_be_bytes());
+        for column in columns {
+            buf.push(0);
+            cstr(&mut buf, column);
+            buf.extend_from_slice(&25i32.to_be_bytes());
+            buf.extend_from_slice(&(-1i32).to_be_bytes());
+        }
+        buf
+    }
+
+    fn begin(final_lsn: u64, ts_us: i64) -> Vec<u8> {
+        let mut buf = vec![b'B'];
+        buf.extend_from_slice(&(final_lsn as i64).to_be_bytes());
+        buf.extend_from_slice(&ts_us.to_be_bytes());
+        buf.extend_from_slice(&1u32.to_be_bytes());
+        buf
+    }
+
+    fn commit(commit_lsn: u64, end_lsn: u64) -> Vec<u8> {
+        let mut buf = vec![b'C', 0];
+        buf.extend_from_slice(&(commit_lsn as i64).to_be_bytes());
+        buf.extend_from_slice(&(end_lsn as i64).to_be_bytes());
+        buf.extend_from_slice(&0i64.to_be_bytes());
+        buf
+    }
+
+    fn insert(new: &[TupleValue]) -> Vec<u8> {
+        let mut buf = vec![b'I'];
+        buf.extend_from_slice(&RELID.to_be_bytes());
+        buf.push(b'N');
+        tuple(&mut buf, new);
+        buf
+    }
+
+    fn update(old: Option<(u8, Vec<TupleValue>)>, new: &[TupleValue]) -> Vec<u8> {
+        let mut buf = vec![b'U'];
+        buf.extend_from_slice(&RELID.to_be_bytes());
+        if let Some((tag, old)) = old {
+            buf.push(tag);
+            tuple(&mut buf, &old);
+        }
+        buf.push(b'N');
+        tuple(&mut buf, new);
+        buf
+    }
+
+    fn delete(tag: u8, old: &[TupleValue]) -> Vec<u8> {
+        let mut buf = vec![b'D'];
+        buf.extend_from_slice(&RELID.to_be_bytes());
+        buf.push(tag);
+        tuple(&mut buf, old);
+        buf
+    }
+
+    #[test]
+    fn changes_become_ready_on_commit() {
+        let rule = rule();
+        let mut reader = reader(&rule);
+        reader.handle("0/100", &relation("public", "orders", &["id", "extra", "note"])).unwrap();
+        reader.handle("0/100", &begin(0x1F0, 1_000_000)).unwrap();
+        reader.handle("0/180", &insert(&[text("1"), text("x"), text("first")])).unwrap();
+        assert_eq!(reader.pending.len(), 1);
+        assert!(reader.ready.is_empty());
+        assert_eq!(reader.lsn, 0);
+
+        reader.handle("0/1F0", &commit(0x1F0, 0x200)).unwrap();
+        assert!(reader.pending.is_empty());
+        assert_eq!(reader.lsn, 0x200);
+        assert_eq!(reader.ready.len(), 1);
+
+        let event = &reader.ready[0];
+        assert_eq!(event.op, ChangeOp::Insert);
+        assert_eq!(event.table, 0);
+        assert!(event.before.is_none());
+        assert_eq!(event.after, Some(vec![Cell::Int(1), Cell::Text("first".to_string())]));
+        assert_eq!(event.ts_ms, PG_EPOCH_MS + 1000);
+        assert_eq!(event.position, serde_json::json!({ "lsn": "0/180" }));
+    }
+
+    #[test]
+    fn update_and_delete_carry_old_rows() {
+        let rule = rule();
+        let mut reader = reader(&rule);
+        reader.handle("0/100", &relation("public", "orders", &["id", "extra", "note"])).unwrap();
+        reader.handle("0/100", &begin(0x300, 0)).unwrap();
+        reader.handle("0/110", &update(None, &[text("1"), TupleValue::Null, text("second")])).unwrap();
+        let key = vec![text("1"), TupleValue::Null, TupleValue::Null];
+        reader.handle("0/120", &update(Some((b'K', key)), &[text("2"), TupleValue::Null, text("moved")])).unwrap();
+        reader.handle("0/130", &delete(b'O', &[text("2"), text("x"), text("moved")])).unwrap();
+        reader.handle("0/300", &commit(0x300, 0x310)).unwrap();
+
+        let ops: Vec<ChangeOp> = reader.ready.iter().map(|e| e.op).collect();
+        assert_eq!(ops, vec![ChangeOp::Update, ChangeOp::Update, ChangeOp::Delete]);
+        assert!(reader.ready[0].before.is_none());
+        assert_eq!(reader.ready[1].before, Some(vec![Cell::Int(1), Cell::Null]));
+        assert_eq!(reader.ready[1].after, Some(vec![Cell::Int(2), Cell::Text("moved".to_string())]));
+        assert_eq!(reader.ready[2].before, Some(vec![Cell::Int(2), Cell::Text("moved".to_string())]));
+        assert!(reader.ready[2].after.is_none());
+        assert_eq!(reader.lsn, 0x310);
+    }
+
+    #[test]
+    fn unchanged_toast_is_taken_from_full_old_row() {
+        let rule = rule();
+        let mut reader = reader(&rule);
+        reader.handle("0/100", &relation("public", "orders", &["id", "extra", "note"])).unwrap();
+        reader.handle("0/100", &begin(0x200, 0)).unwrap();
+        let old = vec![text("1"), text("x"), text("large")];
+        reader.handle("0/110", &update(Some((b'O', old)), &[text("1"), text("y"), TupleValue::Unchanged])).unwrap();
+        assert_eq!(reader.pending[0].after, Some(vec![Cell::Int(1), Cell::Text("large".to_string())]));
+
+        // 没有完整旧行时无法还原未修改的 TOAST 值
+        let err = reader.handle("0/120", &update(None, &[text("1"), text("y"), TupleValue::Unchanged]));
+        assert!(err.unwrap_err().to_string().contains("REPLICA IDENTITY FULL"));
+    }
+
+    #[test]
+    fn transactions_before_confirmed_lsn_are_skipped() {
+        let rule = rule();
+        let mut reader = reader(&rule);
+        reader.confirmed = 0x300;
+        reader.lsn = 0x300;
+        reader.handle("0/100", &relation("public", "orders", &["id", "extra", "note"])).unwrap();
+        reader.handle("0/100", &begin(0x200, 0)).unwrap();
+        reader.handle("0/110", &insert(&[text("1"), text("x"), text("replayed")])).unwrap();
+        reader.handle("0/200", &commit(0x200, 0x210)).unwrap();
+        assert!(reader.pending.is_empty());
+        assert!(reader.ready.is_empty());
+        assert_eq!(reader.lsn, 0x300);
+
+        reader.handle("0/400", &begin(0x400, 0)).unwrap();
+        reader.handle("0/410", &insert(&[text("2"), text("x"), text("new")])).unwrap();
+        reader.handle("0/400", &commit(0x400, 0x410)).unwrap();
+        assert_eq!(reader.ready.len(), 1);
+        assert_eq!(reader.lsn, 0x410);
+    }
+
+    #[test]
+    fn begin_discards_unfinished_transaction() {
+        let rule = rule();
+        let mut reader = reader(&rule);
+        reader.handle("0/100", &relation("public", "orders", &["id", "extra", "note"])).unwrap();
+        reader.handle("0/100", &begin(0x200, 0)).unwrap();
+        reader.handle("0/110", &insert(&[text("1"), text("x"), text("lost")])).unwrap();
+        reader.handle("0/200", &begin(0x300, 0)).unwrap();
+        assert!(reader.pending.is_empty());
+    }
+
+    #[test]
+    fn untracked_relations_are_ignored() {
+        let rule = rule();
+        let mut reader = reader(&rule);
+        reader.handle("0/100", &relation("other", "orders", &["id", "note"])).unwrap();
+        reader.handle("0/100", &relation("public", "customers", &["id", "note"])).unwrap();
+        assert!(reader.relations.is_empty());
+        reader.handle("0/100", &begin(0x200, 0)).unwrap();
+        reader.handle("0/110", &insert(&[text("1"), text("a")])).unwrap();
+        reader.handle("0/200", &commit(0x200, 0x210)).unwrap();
+        assert!(reader.ready.is_empty());
+        assert_eq!(reader.lsn, 0x210);
+    }
+
+    #[test]
+    fn relation_without_captured_column_fails() {
+        let rule = rule();
+        let mut reader = reader(&rule);
+        let err = reader.handle("0/100", &relation("public", "orders", &["id", "extra"])).unwrap_err();
+        assert!(err.to_string().contains("column note no longer exists"));
+    }
+
+    #[test]
+    fn malformed_messages_are_rejected() {
+        let rule = rule();
+        let mut reader = reader(&rule);
+        reader.handle("0/100", &relation("public", "orders", &["id", "extra", "note"])).unwrap();
+
+        let mut truncated = insert(&[text("1"), text("x"), text("note")]);
+        truncated.truncate(truncated.len() - 2);
+        assert!(reader.handle("0/110", &truncated).is_err());
+
+        let mut unknown = vec![b'I'];
+        unknown.extend_from_slice(&RELID.to_be_bytes());
+        unknown.extend_from_slice(&[b'N', 0, 1, b'b']);
+        assert!(reader.handle("0/110", &unknown).is_err());
+
+        let mut missing_new = vec![b'U'];
+        missing_new.extend_from_slice(&RELID.to_be_bytes());
+        missing_new.push(b'X');
+        assert!(reader.handle("0/110", &missing_new).is_err());
+        assert!(reader.pending.is_empty());
+    }
+
+    #[test]
+    fn lsn_text_round_trip() {
+        assert_eq!(parse_lsn("16/B374D848").unwrap(), 0x16_B374_D848);
+        assert_eq!(format_lsn(0x16_B374_D848), "16/B374D848");
+        assert_eq!(format_lsn(parse_lsn("0/0").unwrap()), "0/0");
+        assert!(parse_lsn("B374D848").is_err());
+        assert!(parse_lsn("G/1").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use std::str::FromStr;
+
+use sqlx::postgres::{PgConnectOptions, PgConnection, PgRow};
+use sqlx::{Column, ConnectOptions, Connection, Row, TypeInfo};
+
+use crate::models::Error;
+use crate::models::datasource::PostgresConnectionConfig;
+use super::mysql::{Cell, ColumnMeta, TableMeta, parse_precision};
+
+pub fn parse_config(config: &serde_json::Value) -> Result<PostgresConnectionConfig, Error> {
+    serde_json::from_value::<PostgresConnectionConfig>(config.clone())
+        .map_err(|e| Error::InvalidValue(format!("invalid postgres connection config: {}", e)))
+}
+
+/// 数据源配置的 schema，未配置时为 public
+pub fn schema(cfg: &PostgresConnectionConfig) -> &str {
+    if cfg.catalog.is_empty() { "public" } else { &cfg.catalog }
+}
+
+pub fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub async fn connect(cfg: &PostgresConnectionConfig) -> Result<PgConnection, Error> {
+    let options = PgConnectOptions::new()
+        .host(&cfg.host)
+        .port(cfg.port)
+        .username(&cfg.username)
+        .password(&cfg.password)
+        .database(&cfg.database);
+    let mut conn = options.connect().await?;
+    // 固定文本输出格式，保证变更数据的解析结果稳定
+    sqlx::raw_sql("SET TIME ZONE 'UTC'; SET DateStyle = 'ISO, YMD'; SET bytea_output = 'hex'")
+        .execute(&mut conn)
+        .await?;
+    Ok(conn)
+}
+
+/// 读取表的列与主键信息，列类型映射为目标库（MySQL 语法）的类型；
+/// 同时返回每列在源库中的类型名，用于解析文本格式的值
+pub async fn table_meta(conn: &mut PgConnection, schema: &str, table: &str) -> Result<(TableMeta, Vec<String>), Error> {
+    let qualified = format!("{}.{}", quote_ident(schema), quote_ident(table));
+    let sql = "SELECT a.attname::text, format_type(a.atttypid, a.atttypmod), t.typname::text, NOT a.attnotnull, \
+        COALESCE(col_description(a.attrelid, a.attnum), '') \
+        FROM pg_attribute a JOIN pg_type t ON t.oid = a.atttypid \
+        WHERE a.attrelid = to_regclass($1) AND a.attnum > 0 AND NOT a.attisdropped ORDER BY a.attnum";
+    let rows: Vec<(String, String, String, bool, String)> = sqlx::query_as(sql).bind(&qualified).fetch_all(&mut *conn).await?;
+    if rows.is_empty() {
+        return Err(Error::InvalidValue(format!("source table {}.{} does not exist", schema, table)));
+    }
+
+    let sql = "SELECT a.attname::text FROM pg_index i \
+        JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = ANY(i.indkey) \
+        WHERE i.indrelid = to_regclass($1) AND i.indisprimary ORDER BY array_position(i.indkey::int2[], a.attnum)";
+    let primary_key: Vec<String> = sqlx::query_scalar(sql).bind(&qualified).fetch_all(&mut *conn).await?;
+
+    let sql = "SELECT COALESCE(obj_description(to_regclass($1), 'pg_class'), '')";
+    let comment: String = sqlx::query_scalar(sql).bind(&qualified).fetch_one(&mut *conn).await?;
+
+    let mut pg_types = Vec::with_capacity(rows.len());
+    let columns = rows
+        .into_iter()
+        .map(|(name, full_type, type_name, nullable, comment)| {
+            let (column_type, data_type) = mysql_type(&full_type, &type_name, primary_key.contains(&name));
+            pg_types.push(type_name);
+            ColumnMeta { name, column_type, data_type, nullable, comment }
+        })
+        .collect();
+
+    Ok((
+        TableMeta {
+            name: table.to_string(),
+            columns,
+            primary_key,
+            comment,
+        },
+        pg_types,
+    ))
+}
+
+// PostgreSQL 列类型映射为 MySQL 列类型，返回 (完整类型, 基础类型)；主键列不能使用 text/blob
+fn mysql_type(full_type: &str, type_name: &str, is_key: bool) -> (String, String) {
+    let mapped = |column_type: String, data_type: &str| (column_type, data_type.to_string());
+    match type_name {
+        "int2" => mapped("smallint".to_string(), "smallint"),
+        "int4" => mapped("int".to_string(), "int"),
+        "int8" => mapped("bigint".to_string(), "bigint"),
+        "float4" => mapped("float".to_string(), "float"),
+        "float8" => mapped("double".to_string(), "double"),
+        "numeric" => {
+            // MySQL DECIMAL 精度上限为 65、小数位上限为 30；未声明精度时取常用的 (38, 10)
+            let (precision, scale) = parse_precision(full_type).unwrap_or((38, 10));
+            let precision = precision.min(65);
+            mapped(format!("decimal({},{})", precision, scale.min(30).min(precision)), "decimal")
+        }
+        "bool" => mapped("tinyint(1)".to_string(), "tinyint"),
+        "varchar" => match parse_precision(full_type) {
+            Some((len, _)) => mapped(format!("varchar({})", len), "varchar"),
+            None if is_key => mapped("varchar(768)".to_string(), "varchar"),
+            None => mapped("longtext".to_string(), "longtext"),
+        },
+        "bpchar" => {
+            let len = parse_precision(full_type).map(|(l, _)| l).unwrap_or(1);
+            if len <= 255 {
+                mapped(format!("char({})", len), "char")
+            } else {
+                mapped(format!("varchar({})", len), "varchar")
+            }
+        }
+        "date" => mapped("date".to_string(), "date"),
+        // 带时区的时间统一转换为 UTC 存储
+        "timestamp" | "timestamptz" => mapped("datetime(6)".to_string(), "datetime"),
+        "time" => mapped("time(6)".to_string(), "time"),
+        "json" | "jsonb" => mapped("json".to_string(), "json"),
+        "uuid" => mapped("char(36)".to_string(), "char"),
+        "bytea" if is_key => mapped("varbinary(768)".to_string(), "varbinary"),
+        "bytea" => mapped("longblob".to_string(), "longblob"),
+        _ if is_key => mapped("varchar(768)".to_string(), "varchar"),
+        _ => mapped("longtext".to_string(), "longtext"),
+    }
+}
+
+/// 文本格式的列值按源库类型转换为单元格，无法在目标库表示的值（如 infinity）按 NULL 处理
+pub fn text_cell(text: &str, type_name: &str) -> Result<Cell, Error> {
+    let invalid = |e: &dyn std::fmt::Display| Error::InvalidValue(format!("invalid {} value {}: {}", type_name, text, e));
+    let cell = match type_name {
+        "int2" | "int4" | "int8" => Cell::Int(text.parse::<i64>().map_err(|e| invalid(&e))?),
+        "float4" => Cell::Float(text.parse::<f32>().map_err(|e| invalid(&e))?),
+        "float8" => Cell::Double(text.parse::<f64>().map_err(|e| invalid(&e))?),
+        "numeric" if text == "NaN" => Cell::Null,
+        // 超出 Decimal 精度的值以字符串写入，由目标库转换
+        "numeric" => sqlx::types::Decimal::from_str(text)
+            .map(Cell::Decimal)
+            .unwrap_or_else(|_| Cell::Text(text.to_string())),
+        "bool" => Cell::Int(if text == "t" { 1 } else { 0 }),
+        "date" => chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d").map(Cell::Date).unwrap_or(Cell::Null),
+        "timestamp" => chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f")
+            .map(Cell::DateTime)
+            .unwrap_or(Cell::Null),
+        "timestamptz" => chrono::DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f%#z")
+            .map(|v| Cell::DateTime(v.naive_utc()))
+            .unwrap_or(Cell::Null),
+        "time" => chrono::NaiveTime::parse_from_str(text, "%H:%M:%S%.f").map(Cell::Time).unwrap_or(Cell::Null),
+        "json" | "jsonb" => serde_json::from_str(text).map(Cell::Json).map_err(|e| invalid(&e))?,
+        "bytea" => Cell::Bytes(decode_bytea(text).ok_or_else(|| invalid(&"malformed hex"))?),
+        _ => Cell::Text(text.to_string()),
+    };
+    Ok(cell)
+}
+
+/// 解码 simple query 协议返回的行，各列均为文本格式，按列类型转换为单元格
+pub fn decode_text_row(row: &PgRow) -> Result<Vec<Cell>, Error> {
+    row.columns()
+        .iter()
+        .map(|column| match row.try_get_unchecked::<Option<String>, _>(column.ordinal())? {
+            Some(text) => text_cell(&text, &column.type_info().name().to_lowercase()),
+            None => Ok(Cell::Null),
+        })
+        .collect()
+}
+
+// bytea 的 hex 输出格式：\x 前缀加十六进制
+fn decode_bytea(text: &str) -> Option<Vec<u8>> {
+    let hex = text.strip_prefix("\\x")?;
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// 关闭连接，忽略关闭失败
+pub async fn close(conn: PgConnection) {
+    let _ = conn.close().await;
+}
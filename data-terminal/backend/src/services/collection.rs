@@ -0,0 +1,239 @@
+use crate::repositories::{CollectionRepo, DataSourceRepo, PipelineRepo, ResourceRepo, TaskRunRepo};
+use crate::models::Error;
+use crate::models::web::PageQuery;
+use crate::models::collection::{CollectionTask, CollectionTaskReadOnly, CollectionTaskCreateUpdate, CollectionWatermark, CollectionWatermarkReadOnly, CollectionCheckpoint, CollectionCheckpointReadOnly, CollectType};
+use crate::models::task_run::{TaskRun, TaskRunReadOnly, TaskType, RunStatus};
+use shaku::Provider;
+use async_trait::async_trait;
+use super::CollectionService;
+use super::{collect, provision, task_registry};
+use crate::autofac;
+use uuid::Uuid;
+
+#[derive(Provider)]
+#[shaku(interface = CollectionService)]
+pub struct CollectionServiceImpl {
+    #[shaku(provide)]
+    repo: Box<dyn CollectionRepo>,
+    #[shaku(provide)]
+    datasource_repo: Box<dyn DataSourceRepo>,
+    #[shaku(provide)]
+    resource_repo: Box<dyn ResourceRepo>,
+    #[shaku(provide)]
+    run_repo: Box<dyn TaskRunRepo>,
+    #[shaku(provide)]
+    pipeline_repo: Box<dyn PipelineRepo>,
+}
+
+#[async_trait]
+impl CollectionService for CollectionServiceImpl {
+    async fn add_collection(&self, project_code: String, task: CollectionTaskCreateUpdate) -> Result<String, Error> {
+        let mut task = CollectionTask::from(task);
+        task.id = Uuid::new_v4().to_string();
+        self.check_reference(project_code.clone(), &task).await?;
+        let id = self.repo.add_collection(project_code.clone(), task.clone()).await?;
+        record_lineage(project_code, task).await;
+        Ok(id)
+    }
+
+    async fn edit_collection(&self, project_code: String, task: CollectionTaskCreateUpdate) -> Result<(), Error> {
+        let existing = self.repo.get_collection(project_code.clone(), task.id.clone()).await?;
+        let mut updated_task = CollectionTask::from(task);
+        updated_task.created_at = existing.created_at;
+        self.check_reference(project_code.clone(), &updated_task).await?;
+        self.repo.edit_collection(project_code.clone(), updated_task.clone()).await?;
+        record_lineage(project_code, updated_task).await;
+        Ok(())
+    }
+
+    async fn del_collection(&self, project_code: String, id: String) -> Result<(), Error> {
+        let task = self.repo.get_collection(project_code.clone(), id).await?;
+        let pipelines = self.pipeline_repo.list_all_pipeline(project_code.clone()).await?;
+        if let Some(pipeline) = pipelines
+            .iter()
+            .find(|p| p.nodes.iter().any(|n| n.task_type == TaskType::Collection && n.task_id == task.id))
+        {
+            return Err(Error::InvalidValue(format!("collection task {} is used by pipeline {}", task.id, pipeline.name)));
+        }
+        let _guard = task_registry::try_register(&project_code, &task.id)
+            .ok_or_else(|| Error::InvalidValue(format!("collection task {} is running, stop it first", task.id)))?;
+        self.release_stream_source(project_code.clone(), &task).await?;
+        self.repo.del_collection(project_code.clone(), task.id.clone()).await?;
+        let result = autofac::get_global_app_state_ref()
+            .get_lineage_service()
+            .remove_task_lineage(project_code.clone(), TaskType::Collection, task.id.clone())
+            .await;
+        if let Err(e) = result {
+            tracing::warn!("failed to remove lineage of collection task {} in project {}: {}", task.id, project_code, e);
+        }
+        Ok(())
+    }
+
+    async fn get_collection(&self, project_code: String, id: String) -> Result<CollectionTaskReadOnly, Error> {
+        let task = self.repo.get_collection(project_code, id).await?;
+        Ok(CollectionTaskReadOnly::from(task))
+    }
+
+    async fn list_collection(&self, project_code: String, params: PageQuery) -> Result<Vec<CollectionTaskReadOnly>, Error> {
+        let tasks = self.repo.list_collection(project_code, params).await?;
+        Ok(tasks.into_iter().map(CollectionTaskReadOnly::from).collect())
+    }
+
+    async fn run_collection(&self, project_code: String, id: String) -> Result<String, Error> {
+        provision::check_project_active(&project_code).await?;
+        let task = self.repo.get_collection(project_code.clone(), id).await?;
+        let datasource = self.datasource_repo.get_datasource(project_code.clone(), task.datasource_id.clone()).await?;
+        let resource = self.resource_repo.get_resource(task.resource_id.clone()).await?;
+        collect::check_supported(&task, &datasource, &resource)?;
+
+        // 同一任务不允许并发运行
+        let guard = task_registry::try_register(&project_code, &task.id)
+            .ok_or_else(|| Error::InvalidValue(format!("collection task {} is already running", task.id)))?;
+        let run = TaskRun::start(task.id.clone(), TaskType::Collection);
+        let run_id = self.run_repo.add_run(project_code.clone(), run.clone()).await?;
+
+        collect::spawn_run(project_code, task, datasource, resource, run, guard);
+        Ok(run_id)
+    }
+
+    async fn finish_collection_run(&self, project_code: String, run: TaskRun) -> Result<(), Error> {
+        self.run_repo.finish_run(project_code, run).await
+    }
+
+    async fn list_collection_run(&self, project_code: String, id: String) -> Result<Vec<TaskRunReadOnly>, Error> {
+        let runs = self.run_repo.list_run_by_task(project_code, id, 50).await?;
+        Ok(runs.into_iter().map(TaskRunReadOnly::from).collect())
+    }
+
+    async fn list_watermark(&self, project_code: String, id: String) -> Result<Vec<CollectionWatermark>, Error> {
+        self.repo.list_watermark(project_code, id).await
+    }
+
+    async fn list_collection_watermark(&self, project_code: String, id: String) -> Result<Vec<CollectionWatermarkReadOnly>, Error> {
+        let task = self.repo.get_collection(project_code.clone(), id).await?;
+        let watermarks = self.repo.list_watermark(project_code, task.id).await?;
+        Ok(watermarks.into_iter().map(CollectionWatermarkReadOnly::from).collect())
+    }
+
+    async fn save_watermark(&self, project_code: String, watermark: CollectionWatermark) -> Result<(), Error> {
+        self.repo.save_watermark(project_code, watermark).await
+    }
+
+    async fn reset_watermark(&self, project_code: String, id: String, source_table: Option<String>) -> Result<(), Error> {
+        let task = self.repo.get_collection(project_code.clone(), id).await?;
+        // 运行中重置会被正在推进的水位覆盖
+        let _guard = task_registry::try_register(&project_code, &task.id)
+            .ok_or_else(|| Error::InvalidValue(format!("collection task {} is running", task.id)))?;
+        self.repo.reset_watermark(project_code, task.id, source_table.filter(|t| !t.is_empty())).await
+    }
+
+    async fn stop_collection(&self, project_code: String, id: String) -> Result<(), Error> {
+        let task = self.repo.get_collection(project_code.clone(), id).await?;
+        if !task_registry::cancel(&project_code, &task.id) {
+            return Err(Error::InvalidValue(format!("collection task {} is not running", task.id)));
+        }
+        Ok(())
+    }
+
+    async fn get_checkpoint(&self, project_code: String, id: String) -> Result<Option<CollectionCheckpoint>, Error> {
+        self.repo.get_checkpoint(project_code, id).await
+    }
+
+    async fn get_collection_checkpoint(&self, project_code: String, id: String) -> Result<Option<CollectionCheckpointReadOnly>, Error> {
+        let task = self.repo.get_collection(project_code.clone(), id).await?;
+        let checkpoint = self.repo.get_checkpoint(project_code, task.id).await?;
+        Ok(checkpoint.map(CollectionCheckpointReadOnly::from))
+    }
+
+    async fn save_checkpoint(&self, project_code: String, checkpoint: CollectionCheckpoint) -> Result<(), Error> {
+        self.repo.save_checkpoint(project_code, checkpoint).await
+    }
+
+    async fn reset_collection_checkpoint(&self, project_code: String, id: String) -> Result<(), Error> {
+        let task = self.repo.get_collection(project_code.clone(), id).await?;
+        let _guard = task_registry::try_register(&project_code, &task.id)
+            .ok_or_else(|| Error::InvalidValue(format!("collection task {} is running", task.id)))?;
+        // 复制槽保留着旧位置之后的变更，重置时一并删除，下次运行从当前位置开始
+        self.release_stream_source(project_code.clone(), &task).await?;
+        self.repo.del_checkpoint(project_code, task.id).await
+    }
+
+    // 服务重启前仍在运行的实时采集任务，关闭旧的运行记录并从位点继续
+    async fn resume_stream_collection(&self, project_code: String) -> Result<usize, Error> {
+        provision::check_project_active(&project_code).await?;
+        let tasks = self.repo.list_all_collection(project_code.clone()).await?;
+        let mut resumed = 0;
+        for task in tasks.into_iter().filter(|t| t.collect_type == CollectType::Stream) {
+            let last = self.run_repo.list_run_by_task(project_code.clone(), task.id.clone(), 1).await?;
+            let Some(mut run) = last.into_iter().next().filter(|r| r.status == RunStatus::Running) else {
+                continue;
+            };
+            if task_registry::is_running(&project_code, &task.id) {
+                continue;
+            }
+            run.status = RunStatus::Failed;
+            run.error_message = Some("interrupted by server restart".to_string());
+            run.finished_at = Some(chrono::Utc::now());
+            run.duration_ms = (chrono::Utc::now() - run.started_at).num_milliseconds();
+            self.run_repo.finish_run(project_code.clone(), run).await?;
+
+            match self.run_collection(project_code.clone(), task.id.clone()).await {
+                Ok(_) => resumed += 1,
+                Err(e) => tracing::warn!("failed to resume collection task {}: {}", task.id, e),
+            }
+        }
+        Ok(resumed)
+    }
+}
+
+impl CollectionServiceImpl {
+    // 数据源已被删除时无法清理，只记录告警
+    async fn release_stream_source(&self, project_code: String, task: &CollectionTask) -> Result<(), Error> {
+        if task.collect_type != CollectType::Stream {
+            return Ok(());
+        }
+        let datasource = match self.datasource_repo.get_datasource(project_code, task.datasource_id.clone()).await {
+            Ok(datasource) => datasource,
+            Err(Error::NotFound) => {
+                tracing::warn!("datasource {} of collection task {} not found, skip releasing replication slot", task.datasource_id, task.id);
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+        collect::release_stream_source(task, &datasource)
+            .await
+            .map_err(|e| Error::InvalidValue(format!("failed to release replication slot: {}", collect::error_detail(&e))))
+    }
+
+    // 校验采集源（项目内数据源）与采集目标（全局资源）是否存在
+    async fn check_reference(&self, project_code: String, task: &CollectionTask) -> Result<(), Error> {
+        let datasource = match self.datasource_repo.get_datasource(project_code, task.datasource_id.clone()).await {
+            Ok(datasource) => datasource,
+            Err(Error::NotFound) => {
+                return Err(Error::InvalidValue(format!("datasource {} does not exist in project", task.datasource_id)));
+            }
+            Err(e) => return Err(e),
+        };
+        if !task.category.accepts(&datasource.category) {
+            return Err(Error::InvalidValue(format!(
+                "datasource category {} does not match collection category {}",
+                datasource.category, task.category
+            )));
+        }
+
+        match self.resource_repo.get_resource(task.resource_id.clone()).await {
+            Ok(_) => Ok(()),
+            Err(Error::NotFound) => Err(Error::InvalidValue(format!("resource {} does not exist", task.resource_id))),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// 血缘依赖数据源与元数据快照，解析失败只记录日志，不影响任务保存
+async fn record_lineage(project_code: String, task: CollectionTask) {
+    let task_id = task.id.clone();
+    let result = autofac::get_global_app_state_ref().get_lineage_service().record_collection(project_code.clone(), task).await;
+    if let Err(e) = result {
+        tracing::warn!("lineage of collection task {} in project {} not recorded: {}", task_id, project_code, e);
+    }
+}
@@ -0,0 +1,206 @@
+use std::future::Future;
+use std::time::Duration;
+
+use futures_util::TryStreamExt;
+use futures_util::stream::BoxStream;
+use sqlparser::dialect::{Dialect, MySqlDialect, PostgreSqlDialect};
+use sqlx::{Column, Connection, Database, Executor, Row, TypeInfo};
+use tokio::sync::watch;
+
+use crate::models::Error;
+use crate::models::datasource::PostgresConnectionConfig;
+use crate::models::query::QueryColumn;
+use crate::models::resource::DatabaseResourceConfig;
+use super::collect::error_detail;
+use super::collect::mysql::{self, Cell, TargetDialect, cell_json};
+use super::collect::postgres;
+use super::task_registry;
+
+// 查询控制台可以连接的库
+pub enum Endpoint {
+    MysqlDatasource(serde_json::Value),
+    MysqlResource(DatabaseResourceConfig),
+    Postgres(PostgresConnectionConfig),
+}
+
+impl Endpoint {
+    pub fn dialect(&self) -> Box<dyn Dialect> {
+        match self {
+            Endpoint::MysqlDatasource(_) | Endpoint::MysqlResource(_) => Box::new(MySqlDialect {}),
+            Endpoint::Postgres(_) => Box::new(PostgreSqlDialect {}),
+        }
+    }
+}
+
+// 查询限制
+pub struct Limits {
+    pub max_rows: usize,
+    pub timeout: Duration,
+}
+
+#[derive(Debug, Default)]
+pub struct QueryRows {
+    pub columns: Vec<QueryColumn>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+    pub truncated: bool,
+}
+
+/// 在只读会话中执行查询，最多读取 max_rows 行；收到取消信号时终止语句并返回 None
+pub async fn execute(endpoint: &Endpoint, sql: &str, limits: &Limits, cancel: &watch::Receiver<bool>) -> Result<Option<QueryRows>, Error> {
+    match endpoint {
+        Endpoint::MysqlDatasource(_) | Endpoint::MysqlResource(_) => query_mysql(endpoint, sql, limits, cancel).await,
+        Endpoint::Postgres(cfg) => query_postgres(cfg, sql, limits, cancel).await,
+    }
+}
+
+async fn connect_mysql(endpoint: &Endpoint) -> Result<sqlx::MySqlConnection, Error> {
+    match endpoint {
+        Endpoint::MysqlDatasource(config) => mysql::connect_datasource(config).await,
+        Endpoint::MysqlResource(config) => mysql::connect_resource(config, TargetDialect::Mysql).await,
+        Endpoint::Postgres(_) => Err(Error::InternalError("not a mysql endpoint".to_string())),
+    }
+}
+
+async fn query_mysql(endpoint: &Endpoint, sql: &str, limits: &Limits, cancel: &watch::Receiver<bool>) -> Result<Option<QueryRows>, Error> {
+    let mut conn = connect_mysql(endpoint).await?;
+    // 资源连接不指定库，默认使用资源配置中的第一个库
+    let database = match endpoint {
+        Endpoint::MysqlResource(config) => config.default_database(),
+        _ => None,
+    };
+    if let Some(database) = database {
+        sqlx::raw_sql(&format!("USE {}", mysql::quote_ident(&database))).execute(&mut conn).await?;
+    }
+    let row = sqlx::raw_sql("SELECT CONNECTION_ID()").fetch_one(&mut conn).await?;
+    let connection_id = row.try_get_unchecked::<u64, _>(0)?;
+    // 会话内的语句都以只读事务执行，并由服务端限制单条查询的执行时间
+    sqlx::raw_sql("SET SESSION TRANSACTION READ ONLY").execute(&mut conn).await?;
+    let set_timeout = format!("SET SESSION MAX_EXECUTION_TIME = {}", limits.timeout.as_millis());
+    sqlx::raw_sql(&set_timeout).execute(&mut conn).await?;
+
+    let kill = move || async move {
+        let result = async {
+            let mut conn = connect_mysql(endpoint).await?;
+            sqlx::raw_sql(&format!("KILL QUERY {}", connection_id)).execute(&mut conn).await?;
+            mysql::close(conn).await;
+            Ok::<_, Error>(())
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("failed to kill query on connection {}: {}", connection_id, error_detail(&e));
+        }
+    };
+    let mut result = guarded(
+        fetch_rows::<sqlx::MySql>(sqlx::raw_sql(sql).fetch(&mut conn), limits.max_rows, mysql::decode_row),
+        kill,
+        limits.timeout,
+        cancel,
+    )
+    .await;
+    // 结果为空时从语句描述中补全列信息
+    if matches!(&result, Ok(Some(rows)) if rows.columns.is_empty()) {
+        let describe = (&mut conn).describe(sql).await;
+        if let (Ok(describe), Ok(Some(rows))) = (describe, &mut result) {
+            rows.columns = describe.columns().iter().map(query_column::<sqlx::MySql>).collect();
+        }
+    }
+    finish(conn, result).await
+}
+
+async fn query_postgres(
+    cfg: &PostgresConnectionConfig,
+    sql: &str,
+    limits: &Limits,
+    cancel: &watch::Receiver<bool>,
+) -> Result<Option<QueryRows>, Error> {
+    let mut conn = postgres::connect(cfg).await?;
+    let row = sqlx::raw_sql("SELECT pg_backend_pid()").fetch_one(&mut conn).await?;
+    let pid = row.try_get_unchecked::<i32, _>(0)?;
+    sqlx::raw_sql("SET SESSION CHARACTERISTICS AS TRANSACTION READ ONLY").execute(&mut conn).await?;
+    let set_timeout = format!("SET statement_timeout = {}", limits.timeout.as_millis());
+    sqlx::raw_sql(&set_timeout).execute(&mut conn).await?;
+
+    let kill = move || async move {
+        let result = async {
+            let mut conn = postgres::connect(cfg).await?;
+            sqlx::raw_sql(&format!("SELECT pg_cancel_backend({})", pid)).execute(&mut conn).await?;
+            postgres::close(conn).await;
+            Ok::<_, Error>(())
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("failed to cancel query on backend {}: {}", pid, error_detail(&e));
+        }
+    };
+    let mut result = guarded(
+        fetch_rows::<sqlx::Postgres>(sqlx::raw_sql(sql).fetch(&mut conn), limits.max_rows, postgres::decode_text_row),
+        kill,
+        limits.timeout,
+        cancel,
+    )
+    .await;
+    // 结果为空时从语句描述中补全列信息
+    if matches!(&result, Ok(Some(rows)) if rows.columns.is_empty()) {
+        let describe = (&mut conn).describe(sql).await;
+        if let (Ok(describe), Ok(Some(rows))) = (describe, &mut result) {
+            rows.columns = describe.columns().iter().map(query_column::<sqlx::Postgres>).collect();
+        }
+    }
+    finish(conn, result).await
+}
+
+// 等待读取完成；超时或取消时先终止服务端语句
+async fn guarded<F, K, KF>(fetch: F, kill: K, timeout: Duration, cancel: &watch::Receiver<bool>) -> Result<Option<QueryRows>, Error>
+where
+    F: Future<Output = Result<QueryRows, Error>>,
+    K: FnOnce() -> KF,
+    KF: Future<Output = ()>,
+{
+    let mut cancel = cancel.clone();
+    tokio::select! {
+        result = fetch => result.map(Some),
+        _ = tokio::time::sleep(timeout) => {
+            kill().await;
+            Err(Error::InternalError(format!("query exceeded the timeout of {} seconds", timeout.as_secs())))
+        }
+        _ = task_registry::cancelled(&mut cancel) => {
+            kill().await;
+            Ok(None)
+        }
+    }
+}
+
+// 读取至多 max_rows 行，多读一行用于判断结果是否被截断
+async fn fetch_rows<DB: Database>(
+    mut stream: BoxStream<'_, Result<DB::Row, sqlx::Error>>,
+    max_rows: usize,
+    decode: fn(&DB::Row) -> Result<Vec<Cell>, Error>,
+) -> Result<QueryRows, Error> {
+    let mut result = QueryRows::default();
+    while let Some(row) = stream.try_next().await? {
+        if result.columns.is_empty() {
+            result.columns = row.columns().iter().map(query_column::<DB>).collect();
+        }
+        if result.rows.len() == max_rows {
+            result.truncated = true;
+            break;
+        }
+        result.rows.push(decode(&row)?.iter().map(cell_json).collect());
+    }
+    Ok(result)
+}
+
+fn query_column<DB: Database>(column: &DB::Column) -> QueryColumn {
+    QueryColumn {
+        name: column.name().to_string(),
+        type_name: column.type_info().name().to_string(),
+    }
+}
+
+// 读取未完成（截断、超时或取消）的连接直接丢弃，避免关闭时等待剩余结果
+async fn finish<C: Connection>(conn: C, result: Result<Option<QueryRows>, Error>) -> Result<Option<QueryRows>, Error> {
+    if matches!(&result, Ok(Some(rows)) if !rows.truncated) {
+        let _ = conn.close().await;
+    }
+    result
+}
@@ -0,0 +1,507 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tracing::Instrument;
+
+use crate::autofac;
+use crate::models::Error;
+use crate::models::pipeline::{NodeStatus, Pipeline, PipelineNode, PipelineNodeRun};
+use crate::models::task_run::{RunStatus, TaskRun, TaskRunReadOnly, TaskType};
+use super::run_log;
+use super::task_registry::{self, RunningTaskGuard};
+
+// 检查节点任务是否结束的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 在后台执行一次流水线运行，结束时保存各节点状态与运行记录
+pub fn spawn_run(project_code: String, pipeline: Pipeline, mut run: TaskRun, guard: RunningTaskGuard) {
+    let span = run_log::start(&project_code, &run.id);
+    tokio::spawn(
+        async move {
+            let started = Instant::now();
+            tracing::info!(
+                "pipeline {} ({}) started, {} nodes, max parallel {}",
+                pipeline.name, pipeline.id, pipeline.nodes.len(), pipeline.max_parallel
+            );
+            let (nodes, task_runs) = execute(&project_code, &pipeline, &run.id, guard.cancel_signal()).await;
+
+            let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+            for node in &nodes {
+                *counts.entry(node.status.to_string()).or_default() += 1;
+            }
+            let failed: Vec<&PipelineNodeRun> = nodes.iter().filter(|n| n.status == NodeStatus::Failed).collect();
+
+            run.rows_read = task_runs.iter().map(|r| r.rows_read).sum();
+            run.rows_written = task_runs.iter().map(|r| r.rows_written).sum();
+            run.bytes = task_runs.iter().map(|r| r.bytes).sum();
+            run.duration_ms = started.elapsed().as_millis() as i64;
+            run.detail = serde_json::to_value(&counts).ok().map(sqlx::types::Json);
+            run.finished_at = Some(chrono::Utc::now());
+            run.status = run_status(&nodes, guard.is_cancelled());
+            if !failed.is_empty() {
+                let ids: Vec<&str> = failed.iter().map(|n| n.node_id.as_str()).collect();
+                run.error_message = Some(format!("node {} failed", ids.join(", ")));
+                run.error_chain = Some(sqlx::types::Json(
+                    failed
+                        .iter()
+                        .map(|n| format!("{}: {}", n.node_id, n.error_message.as_deref().unwrap_or_default()))
+                        .collect(),
+                ));
+            }
+
+            let run_id = run.id.clone();
+            tracing::info!("pipeline {} run {} finished as {} in {} ms", pipeline.id, run_id, run.status, run.duration_ms);
+
+            run_log::finish(&run).await;
+            let saved = autofac::get_global_app_state_ref().get_pipeline_service().finish_pipeline_run(project_code, run).await;
+            drop(guard);
+
+            if let Err(e) = saved {
+                tracing::error!("failed to save pipeline run {}: {}", run_id, e);
+            }
+        }
+        .instrument(span),
+    );
+}
+
+// 流水线的运行结果：取消优先，其次是节点失败，其余未成功的节点只可能是被取消
+fn run_status(nodes: &[PipelineNodeRun], cancelled: bool) -> RunStatus {
+    if cancelled {
+        RunStatus::Cancelled
+    } else if nodes.iter().any(|n| n.status == NodeStatus::Failed) {
+        RunStatus::Failed
+    } else if nodes.iter().any(|n| n.status != NodeStatus::Success) {
+        RunStatus::Cancelled
+    } else {
+        RunStatus::Success
+    }
+}
+
+// 按 Scheduler 的调度结果启动节点并保存各节点状态，取消流水线时等待运行中的节点结束，未执行的节点标记为取消
+async fn execute(
+    project_code: &str,
+    pipeline: &Pipeline,
+    run_id: &str,
+    mut cancel: watch::Receiver<bool>,
+) -> (Vec<PipelineNodeRun>, Vec<TaskRunReadOnly>) {
+    let mut states: Vec<PipelineNodeRun> = pipeline.nodes.iter().map(|n| PipelineNodeRun::pending(run_id, n)).collect();
+    for state in &states {
+        save_node(project_code, state).await;
+    }
+
+    let mut scheduler = Scheduler::new(pipeline);
+    let mut running = JoinSet::new();
+    let mut running_ids = HashMap::new();
+    let mut task_runs = Vec::new();
+
+    loop {
+        while let Some(i) = scheduler.start_next() {
+            states[i].status = NodeStatus::Running;
+            states[i].started_at = Some(chrono::Utc::now());
+            save_node(project_code, &states[i]).await;
+
+            let project_code = project_code.to_string();
+            let node = pipeline.nodes[i].clone();
+            let state = states[i].clone();
+            let cancel = cancel.clone();
+            let handle = running.spawn(async move { run_node(&project_code, &node, state, cancel).await }.in_current_span());
+            running_ids.insert(handle.id(), i);
+        }
+        if running.is_empty() {
+            break;
+        }
+
+        tokio::select! {
+            joined = running.join_next_with_id() => {
+                let (i, task_run) = match joined {
+                    Some(Ok((id, (state, task_run)))) => {
+                        let i = running_ids.remove(&id).unwrap_or_default();
+                        states[i] = state;
+                        (i, task_run)
+                    }
+                    Some(Err(e)) => {
+                        let i = running_ids.remove(&e.id()).unwrap_or_default();
+                        states[i].status = NodeStatus::Failed;
+                        states[i].error_message = Some(format!("node execution aborted: {}", e));
+                        states[i].finished_at = Some(chrono::Utc::now());
+                        save_node(project_code, &states[i]).await;
+                        (i, None)
+                    }
+                    None => break,
+                };
+                task_runs.extend(task_run);
+
+                let skipped = scheduler.finish(i, states[i].status);
+                skip_nodes(project_code, pipeline, &mut states, i, &skipped).await;
+            }
+            _ = task_registry::cancelled(&mut cancel), if !scheduler.cancelling => {
+                // 运行中的节点各自转发取消信号，这里只停止启动新节点
+                tracing::info!("pipeline cancelled, waiting for {} running nodes to stop", running.len());
+                scheduler.cancel();
+            }
+        }
+    }
+
+    for i in scheduler.pending() {
+        let state = &mut states[i];
+        state.status = NodeStatus::Cancelled;
+        state.error_message = Some("pipeline cancelled".to_string());
+        state.finished_at = Some(chrono::Utc::now());
+        save_node(project_code, state).await;
+    }
+    (states, task_runs)
+}
+
+// 流水线的调度步骤，只根据节点结果决定接下来启动哪些节点，不执行任务也不保存状态：
+// 上游全部成功的节点进入就绪队列，最多同时运行 max_parallel 个；
+// 节点失败或取消后其下游全部跳过，取消流水线后不再启动新节点
+struct Scheduler {
+    downstream: Vec<Vec<usize>>,
+    // 每个节点尚未成功的上游数量
+    waiting: Vec<usize>,
+    ready: VecDeque<usize>,
+    status: Vec<NodeStatus>,
+    max_parallel: usize,
+    running: usize,
+    cancelling: bool,
+}
+
+impl Scheduler {
+    fn new(pipeline: &Pipeline) -> Self {
+        let waiting: Vec<usize> = pipeline.upstream().iter().map(|u| u.len()).collect();
+        let ready = (0..waiting.len()).filter(|&i| waiting[i] == 0).collect();
+        Self {
+            downstream: pipeline.downstream(),
+            status: vec![NodeStatus::Pending; waiting.len()],
+            waiting,
+            ready,
+            max_parallel: pipeline.max_parallel.max(1) as usize,
+            running: 0,
+            cancelling: false,
+        }
+    }
+
+    // 取出下一个可以启动的节点，达到并发上限、没有就绪节点或已取消时返回 None
+    fn start_next(&mut self) -> Option<usize> {
+        if self.cancelling || self.running >= self.max_parallel {
+            return None;
+        }
+        let i = self.ready.pop_front()?;
+        self.status[i] = NodeStatus::Running;
+        self.running += 1;
+        Some(i)
+    }
+
+    // 记录节点的结束状态：成功时释放下游节点，否则返回因此跳过的全部下游节点
+    fn finish(&mut self, i: usize, status: NodeStatus) -> Vec<usize> {
+        self.status[i] = status;
+        self.running = self.running.saturating_sub(1);
+        if status == NodeStatus::Success {
+            for &next in &self.downstream[i] {
+                self.waiting[next] -= 1;
+                if self.waiting[next] == 0 && self.status[next] == NodeStatus::Pending {
+                    self.ready.push_back(next);
+                }
+            }
+            return Vec::new();
+        }
+
+        let mut skipped = Vec::new();
+        let mut queue: VecDeque<usize> = self.downstream[i].iter().copied().collect();
+        while let Some(next) = queue.pop_front() {
+            if self.status[next] != NodeStatus::Pending {
+                continue;
+            }
+            self.status[next] = NodeStatus::Skipped;
+            skipped.push(next);
+            queue.extend(self.downstream[next].iter().copied());
+        }
+        skipped
+    }
+
+    fn cancel(&mut self) {
+        self.cancelling = true;
+    }
+
+    // 调度结束时仍未启动的节点
+    fn pending(&self) -> Vec<usize> {
+        (0..self.status.len()).filter(|&i| self.status[i] == NodeStatus::Pending).collect()
+    }
+}
+
+// 失败节点的下游节点不再执行
+async fn skip_nodes(project_code: &str, pipeline: &Pipeline, states: &mut [PipelineNodeRun], from: usize, skipped: &[usize]) {
+    let reason = format!("upstream node {} {}", pipeline.nodes[from].id, states[from].status);
+    for &i in skipped {
+        tracing::info!("node {} skipped: {}", pipeline.nodes[i].id, reason);
+        states[i].status = NodeStatus::Skipped;
+        states[i].error_message = Some(reason.clone());
+        states[i].finished_at = Some(chrono::Utc::now());
+        save_node(project_code, &states[i]).await;
+    }
+}
+
+// 执行单个节点，失败时按节点配置重试，返回节点最终状态和最后一次任务运行记录
+async fn run_node(
+    project_code: &str,
+    node: &PipelineNode,
+    mut state: PipelineNodeRun,
+    mut cancel: watch::Receiver<bool>,
+) -> (PipelineNodeRun, Option<TaskRunReadOnly>) {
+    let mut last_run = None;
+    loop {
+        state.attempts += 1;
+        tracing::info!("node {} ({} task {}) started, attempt {}", node.id, node.task_type, node.task_id, state.attempts);
+        let message = match run_task(project_code, node, &mut state, &mut cancel).await {
+            Ok(run) => {
+                let status = run.status;
+                let message = run.error_message.clone();
+                last_run = Some(run);
+                match status {
+                    RunStatus::Success => {
+                        state.status = NodeStatus::Success;
+                        state.error_message = None;
+                        break;
+                    }
+                    RunStatus::Cancelled => {
+                        state.status = NodeStatus::Cancelled;
+                        state.error_message = Some("task run cancelled".to_string());
+                        break;
+                    }
+                    _ if message.is_empty() => format!("task run finished as {}", status),
+                    _ => message,
+                }
+            }
+            Err(e) => e.to_string(),
+        };
+
+        state.error_message = Some(message.clone());
+        let cancelled = *cancel.borrow();
+        if let Some(status) = failed_attempt(node, state.attempts, cancelled) {
+            if status == NodeStatus::Failed {
+                tracing::warn!("node {} failed after {} attempts: {}", node.id, state.attempts, message);
+            }
+            state.status = status;
+            break;
+        }
+
+        tracing::warn!("node {} attempt {} failed: {}, retry in {} s", node.id, state.attempts, message, node.retry_interval_secs);
+        save_node(project_code, &state).await;
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(node.retry_interval_secs)) => {}
+            _ = task_registry::cancelled(&mut cancel) => {
+                state.status = NodeStatus::Cancelled;
+                break;
+            }
+        }
+    }
+
+    state.finished_at = Some(chrono::Utc::now());
+    save_node(project_code, &state).await;
+    (state, last_run)
+}
+
+// 一次尝试失败后的节点状态：流水线已取消或重试次数用尽时结束，返回 None 表示继续重试
+fn failed_attempt(node: &PipelineNode, attempts: i32, cancelled: bool) -> Option<NodeStatus> {
+    if cancelled {
+        Some(NodeStatus::Cancelled)
+    } else if attempts > node.retries as i32 {
+        Some(NodeStatus::Failed)
+    } else {
+        None
+    }
+}
+
+// 启动节点对应的任务并等待其结束；流水线取消时转发给任务
+async fn run_task(
+    project_code: &str,
+    node: &PipelineNode,
+    state: &mut PipelineNodeRun,
+    cancel: &mut watch::Receiver<bool>,
+) -> Result<TaskRunReadOnly, Error> {
+    let task_run_id = start_task(project_code, node).await?;
+    state.task_run_id = Some(task_run_id.clone());
+    save_node(project_code, state).await;
+
+    // 任务在保存运行结果后才注销，注销后读到的运行记录即为最终状态
+    let mut cancel_sent = false;
+    while task_registry::is_running(project_code, &node.task_id) {
+        tokio::select! {
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+            _ = task_registry::cancelled(cancel), if !cancel_sent => {
+                task_registry::cancel(project_code, &node.task_id);
+                cancel_sent = true;
+            }
+        }
+    }
+
+    autofac::get_global_app_state_ref()
+        .get_task_run_service()
+        .get_run(project_code.to_string(), task_run_id)
+        .await
+}
+
+async fn start_task(project_code: &str, node: &PipelineNode) -> Result<String, Error> {
+    match node.task_type {
+        TaskType::Collection => {
+            autofac::get_global_app_state_ref()
+                .get_collection_service()
+                .run_collection(project_code.to_string(), node.task_id.clone())
+                .await
+        }
+        TaskType::Transform => {
+            autofac::get_global_app_state_ref()
+                .get_transform_service()
+                .run_transform(project_code.to_string(), node.task_id.clone(), None)
+                .await
+        }
+        other => Err(Error::InvalidValue(format!("{} task cannot run in pipeline yet", other))),
+    }
+}
+
+async fn save_node(project_code: &str, state: &PipelineNodeRun) {
+    let saved = autofac::get_global_app_state_ref()
+        .get_pipeline_service()
+        .save_node_run(project_code.to_string(), state.clone())
+        .await;
+    if let Err(e) = saved {
+        tracing::warn!("failed to save state of node {} in run {}: {}", state.node_id, state.run_id, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::pipeline::PipelineEdge;
+
+    fn pipeline(nodes: &[&str], edges: &[(&str, &str)], max_parallel: i32) -> Pipeline {
+        Pipeline {
+            id: "p1".to_string(),
+            name: "pipeline".to_string(),
+            nodes: nodes
+                .iter()
+                .map(|id| PipelineNode { id: id.to_string(), task_id: format!("task_{}", id), ..Default::default() })
+                .collect(),
+            edges: edges.iter().map(|(from, to)| PipelineEdge { from: from.to_string(), to: to.to_string() }).collect(),
+            max_parallel,
+            ..Default::default()
+        }
+    }
+
+    // 取出当前所有可以启动的节点
+    fn start_all(scheduler: &mut Scheduler) -> Vec<usize> {
+        std::iter::from_fn(|| scheduler.start_next()).collect()
+    }
+
+    fn sorted(mut nodes: Vec<usize>) -> Vec<usize> {
+        nodes.sort();
+        nodes
+    }
+
+    #[test]
+    fn ready_nodes_start_up_to_max_parallel() {
+        let p = pipeline(&["a", "b", "c", "d"], &[], 2);
+        let mut scheduler = Scheduler::new(&p);
+        assert_eq!(start_all(&mut scheduler), vec![0, 1]);
+
+        assert!(scheduler.finish(1, NodeStatus::Success).is_empty());
+        assert_eq!(start_all(&mut scheduler), vec![2]);
+        scheduler.finish(0, NodeStatus::Success);
+        scheduler.finish(2, NodeStatus::Success);
+        assert_eq!(start_all(&mut scheduler), vec![3]);
+        scheduler.finish(3, NodeStatus::Success);
+        assert!(scheduler.start_next().is_none());
+        assert!(scheduler.pending().is_empty());
+    }
+
+    #[test]
+    fn node_starts_after_all_upstream_succeed() {
+        let p = pipeline(&["a", "b", "c", "d"], &[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d")], 4);
+        let mut scheduler = Scheduler::new(&p);
+        assert_eq!(start_all(&mut scheduler), vec![0]);
+
+        scheduler.finish(0, NodeStatus::Success);
+        assert_eq!(start_all(&mut scheduler), vec![1, 2]);
+        scheduler.finish(1, NodeStatus::Success);
+        assert!(scheduler.start_next().is_none(), "d still waits for c");
+        scheduler.finish(2, NodeStatus::Success);
+        assert_eq!(start_all(&mut scheduler), vec![3]);
+    }
+
+    #[test]
+    fn failure_skips_all_downstream() {
+        let p = pipeline(&["a", "b", "c", "d", "e"], &[("a", "b"), ("b", "c"), ("a", "d")], 4);
+        let mut scheduler = Scheduler::new(&p);
+        assert_eq!(start_all(&mut scheduler), vec![0, 4]);
+
+        assert_eq!(sorted(scheduler.finish(0, NodeStatus::Failed)), vec![1, 2, 3]);
+        assert!(scheduler.start_next().is_none());
+        // 与失败节点无关的节点照常结束
+        assert!(scheduler.finish(4, NodeStatus::Success).is_empty());
+        assert!(scheduler.pending().is_empty());
+    }
+
+    #[test]
+    fn skipped_node_does_not_start_when_other_upstream_succeeds() {
+        let p = pipeline(&["a", "b", "c", "d"], &[("a", "c"), ("b", "c"), ("c", "d")], 4);
+        let mut scheduler = Scheduler::new(&p);
+        assert_eq!(start_all(&mut scheduler), vec![0, 1]);
+
+        assert_eq!(scheduler.finish(0, NodeStatus::Failed), vec![2, 3]);
+        assert!(scheduler.finish(1, NodeStatus::Success).is_empty());
+        assert!(scheduler.start_next().is_none());
+        assert!(scheduler.pending().is_empty());
+    }
+
+    #[test]
+    fn cancelled_node_skips_downstream() {
+        let p = pipeline(&["a", "b"], &[("a", "b")], 4);
+        let mut scheduler = Scheduler::new(&p);
+        assert_eq!(start_all(&mut scheduler), vec![0]);
+        assert_eq!(scheduler.finish(0, NodeStatus::Cancelled), vec![1]);
+        assert!(scheduler.start_next().is_none());
+    }
+
+    #[test]
+    fn cancel_stops_starting_new_nodes() {
+        let p = pipeline(&["a", "b", "c"], &[("a", "b")], 1);
+        let mut scheduler = Scheduler::new(&p);
+        assert_eq!(start_all(&mut scheduler), vec![0]);
+
+        scheduler.cancel();
+        assert!(scheduler.finish(0, NodeStatus::Success).is_empty());
+        assert!(scheduler.start_next().is_none());
+        assert_eq!(scheduler.pending(), vec![1, 2]);
+    }
+
+    #[test]
+    fn node_retries_until_attempts_exceed_retries() {
+        let node = PipelineNode { id: "a".to_string(), retries: 2, ..Default::default() };
+        assert_eq!(failed_attempt(&node, 1, false), None);
+        assert_eq!(failed_attempt(&node, 2, false), None);
+        assert_eq!(failed_attempt(&node, 3, false), Some(NodeStatus::Failed));
+        // 取消后不再重试
+        assert_eq!(failed_attempt(&node, 1, true), Some(NodeStatus::Cancelled));
+
+        let once = PipelineNode { id: "b".to_string(), ..Default::default() };
+        assert_eq!(failed_attempt(&once, 1, false), Some(NodeStatus::Failed));
+    }
+
+    #[test]
+    fn run_status_follows_node_results() {
+        let nodes = |statuses: &[NodeStatus]| -> Vec<PipelineNodeRun> {
+            statuses.iter().map(|&status| PipelineNodeRun { status, ..Default::default() }).collect()
+        };
+        let all_success = nodes(&[NodeStatus::Success, NodeStatus::Success]);
+        let failed = nodes(&[NodeStatus::Success, NodeStatus::Failed, NodeStatus::Skipped]);
+        let cancelled = nodes(&[NodeStatus::Success, NodeStatus::Cancelled, NodeStatus::Skipped]);
+
+        assert_eq!(run_status(&all_success, false), RunStatus::Success);
+        assert_eq!(run_status(&failed, false), RunStatus::Failed);
+        assert_eq!(run_status(&cancelled, false), RunStatus::Cancelled);
+        // 流水线被取消时即使有节点失败也记为取消
+        assert_eq!(run_status(&failed, true), RunStatus::Cancelled);
+    }
+}
@@ -1,15 +1,21 @@
 use std::fmt::format;
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
 
 use crate::repositories::{DataSourceRepo};
 use crate::models::{Error};
 use crate::models::web::PageQuery;
-use crate::models::datasource::{DataSource, DataSourceReadOnly, DataSourceCreateUpdate, DataSourceType};
+use crate::models::datasource::{DataSource, DataSourceReadOnly, DataSourceCreateUpdate, ConnectionStatus, PingDiagnostic};
 use shaku::Provider;
 use async_trait::async_trait;
 use super::DataSourceService;
+use super::probe;
+use super::metadata;
+use crate::models::metadata::{DataSourceCatalog, TableDetail, TableDetailQuery};
+use crate::utils::config::Setting;
 use chrono;
 use uuid::Uuid;
-use sqlx::mysql::MySqlPoolOptions;
 
 #[derive(Provider)]
 #[shaku(interface = DataSourceService)]
@@ -41,24 +47,25 @@ impl DataSourceService for DataSourceServiceImpl {
         updated_datasource.connection_status = existing.connection_status;
         updated_datasource.created_at = existing.created_at;
         
-        let result = self.repo.edit_datasource(project_code, updated_datasource).await;
+        let id = updated_datasource.id.clone();
+        let result = self.repo.edit_datasource(project_code.clone(), updated_datasource).await;
+        // 连接配置可能已变更，清除库表缓存
+        metadata::evict(&project_code, &id);
         result.map_err(|e| Error::InternalError(format!("Failed to edit datasource: {:?}", e)))
     }
 
-    async fn ping_datasource(&self, project_code: String, datasource: DataSourceCreateUpdate) -> Result<(), Error> {
+    async fn ping_datasource(&self, project_code: String, datasource: DataSourceCreateUpdate) -> Result<PingDiagnostic, Error> {
         let datasource = DataSource::from(datasource);
-        match datasource.datasource_type {
-            DataSourceType::Mysql => Ok(()), // TODO: 添加 MySQL 数据源的 ping 功能
-            _ => {
-                return Err(Error::NotImplemented);
-            }
-        }
+        Ok(probe::probe_datasource(&datasource).await)
     }
 
     async fn del_datasource(&self, project_code: String, id: String) -> Result<(), Error> {
-        let result = self.repo.del_datasource(project_code, id).await;
+        let result = self.repo.del_datasource(project_code.clone(), id.clone()).await;
         match result {
-            Ok(_) => Ok(()),
+            Ok(_) => {
+                metadata::evict(&project_code, &id);
+                Ok(())
+            },
             Err(e) => Err(e),
         }
     }
@@ -81,4 +88,79 @@ impl DataSourceService for DataSourceServiceImpl {
             },
         }
     }
+
+    async fn recheck_datasource(&self, project_code: String, id: String) -> Result<DataSourceReadOnly, Error> {
+        let datasource = self.repo.get_datasource(project_code.clone(), id).await?;
+        let diagnostic = probe::probe_datasource(&datasource).await;
+        let datasource = self.save_health(project_code, datasource, &diagnostic).await?;
+        Ok(DataSourceReadOnly::from(datasource))
+    }
+
+    async fn check_project_datasource(&self, project_code: String) -> Result<usize, Error> {
+        let datasources = self.repo.list_all_datasource(project_code.clone()).await?;
+        let count = datasources.len();
+
+        // 限制并发探测的数量，结果按完成顺序落库
+        let semaphore = Arc::new(Semaphore::new(Setting::get().health_check.concurrency.max(1)));
+        let mut probes = tokio::task::JoinSet::new();
+        for datasource in datasources {
+            let semaphore = semaphore.clone();
+            probes.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let diagnostic = probe::check_datasource(&datasource).await;
+                (datasource, diagnostic)
+            });
+        }
+        while let Some(joined) = probes.join_next().await {
+            let (datasource, diagnostic) = match joined {
+                Ok(probed) => probed,
+                Err(e) => {
+                    tracing::warn!("health check probe of project {} panicked: {}", project_code, e);
+                    continue;
+                }
+            };
+            // 单个数据源保存失败不影响其他数据源
+            let id = datasource.id.clone();
+            if let Err(e) = self.save_health(project_code.clone(), datasource, &diagnostic).await {
+                tracing::warn!("failed to save health of datasource {} in project {}: {}", id, project_code, e);
+            }
+        }
+
+        Ok(count)
+    }
+
+    async fn get_catalog(&self, project_code: String, id: String, refresh: bool) -> Result<DataSourceCatalog, Error> {
+        let datasource = self.repo.get_datasource(project_code.clone(), id).await?;
+        metadata::catalog(&project_code, &datasource, refresh).await
+    }
+
+    async fn get_table_detail(&self, project_code: String, id: String, params: TableDetailQuery) -> Result<TableDetail, Error> {
+        if params.schema.is_empty() {
+            return Err(Error::EmptyValue("schema".to_string()));
+        }
+        if params.table.is_empty() {
+            return Err(Error::EmptyValue("table".to_string()));
+        }
+        let datasource = self.repo.get_datasource(project_code.clone(), id).await?;
+        metadata::table_detail(&project_code, &datasource, &params.schema, &params.table, params.refresh).await
+    }
+}
+
+impl DataSourceServiceImpl {
+    // 将探测结果写回数据源的连接状态
+    async fn save_health(&self, project_code: String, mut datasource: DataSource, diagnostic: &PingDiagnostic) -> Result<DataSource, Error> {
+        let checked_at = chrono::Utc::now();
+        let last_error = match diagnostic.error_kind {
+            Some(kind) => format!("[{}] {}", kind, diagnostic.message).chars().take(1024).collect(),
+            None => String::new(),
+        };
+        datasource.connection_status = ConnectionStatus::from(diagnostic);
+        datasource.last_checked_at = Some(checked_at);
+        datasource.last_error = last_error.clone();
+
+        self.repo
+            .update_connection_status(project_code, datasource.id.clone(), datasource.connection_status.clone(), last_error, checked_at)
+            .await?;
+        Ok(datasource)
+    }
 }
@@ -0,0 +1,45 @@
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+use crate::autofac;
+use crate::models::project::CreateStatus;
+use crate::utils::config::Setting;
+
+/// 启动数据源后台健康检查，按配置的间隔轮询所有项目下的数据源
+pub fn spawn_health_check() {
+    let setting = &Setting::get().health_check;
+    if !setting.enabled {
+        tracing::info!("datasource health check is disabled");
+        return;
+    }
+
+    let interval = Duration::from_secs(setting.interval_secs.max(10));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        // 单轮检查耗时超过间隔时顺延，避免连续触发
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            run_health_check().await;
+        }
+    });
+}
+
+async fn run_health_check() {
+    let projects = match autofac::get_global_app_state_ref().get_project_service().list_all_project().await {
+        Ok(projects) => projects,
+        Err(e) => {
+            tracing::warn!("health check failed to list projects: {}", e);
+            return;
+        }
+    };
+
+    // 只有初始化完成的项目才有可用的项目库
+    for project in projects.into_iter().filter(|p| p.create_status == CreateStatus::Success) {
+        let result = autofac::get_global_app_state_ref().get_datasource_service().check_project_datasource(project.code.clone()).await;
+        match result {
+            Ok(count) => tracing::debug!("health check of project {} finished, {} datasources probed", project.code, count),
+            Err(e) => tracing::warn!("health check of project {} failed: {}", project.code, e),
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::repositories::{CatalogRepo, CollectionRepo, DataSourceRepo, LineageRepo, ResourceRepo, TransformRepo};
+use crate::models::Error;
+use crate::models::catalog::CatalogSourceType;
+use crate::models::collection::{CollectType, CollectionCategory, CollectionTask, DatabaseCollectRule, StreamCollectRule};
+use crate::models::datasource::{DataSourceType, MysqlConnectionConfig};
+use crate::models::lineage::{LineageDirection, LineageEdge, LineageGraph, LineageNode, LineageQuery};
+use crate::models::resource::{DatabaseResourceConfig, ResourceType};
+use crate::models::task_run::TaskType;
+use crate::models::transform::TransformTask;
+use crate::utils::sql_parse;
+use shaku::Provider;
+use async_trait::async_trait;
+use super::LineageService;
+use super::collect::{self, postgres};
+use super::materialize;
+use uuid::Uuid;
+
+// 默认与最大追溯层数
+const DEFAULT_DEPTH: usize = 10;
+const MAX_DEPTH: usize = 50;
+
+// 来源 -> (小写 schema, 小写表名) -> 最新快照中的列名
+type CatalogColumns = HashMap<(CatalogSourceType, String), BTreeMap<(String, String), Vec<String>>>;
+
+#[derive(Provider)]
+#[shaku(interface = LineageService)]
+pub struct LineageServiceImpl {
+    #[shaku(provide)]
+    repo: Box<dyn LineageRepo>,
+    #[shaku(provide)]
+    catalog_repo: Box<dyn CatalogRepo>,
+    #[shaku(provide)]
+    datasource_repo: Box<dyn DataSourceRepo>,
+    #[shaku(provide)]
+    resource_repo: Box<dyn ResourceRepo>,
+    #[shaku(provide)]
+    collection_repo: Box<dyn CollectionRepo>,
+    #[shaku(provide)]
+    transform_repo: Box<dyn TransformRepo>,
+}
+
+#[async_trait]
+impl LineageService for LineageServiceImpl {
+    async fn record_collection(&self, project_code: String, task: CollectionTask) -> Result<usize, Error> {
+        let mut cache = CatalogColumns::new();
+        let edges = match self.collection_edges(&project_code, &task, &mut cache).await {
+            Ok(edges) => edges,
+            Err(e) => {
+                // 解析失败时清除旧的血缘，避免保留与任务不一致的记录
+                self.repo.del_task_edge(project_code, TaskType::Collection, task.id).await?;
+                return Err(e);
+            }
+        };
+        let count = edges.len();
+        self.repo.replace_task_edge(project_code, TaskType::Collection, task.id, edges).await?;
+        Ok(count)
+    }
+
+    async fn record_transform(&self, project_code: String, task: TransformTask) -> Result<usize, Error> {
+        let mut cache = CatalogColumns::new();
+        let edges = match self.transform_edges(&project_code, &task, &mut cache).await {
+            Ok(edges) => edges,
+            Err(e) => {
+                self.repo.del_task_edge(project_code, TaskType::Transform, task.id).await?;
+                return Err(e);
+            }
+        };
+        let count = edges.len();
+        self.repo.replace_task_edge(project_code, TaskType::Transform, task.id, edges).await?;
+        Ok(count)
+    }
+
+    async fn remove_task_lineage(&self, project_code: String, task_type: TaskType, task_id: String) -> Result<(), Error> {
+        self.repo.del_task_edge(project_code, task_type, task_id).await
+    }
+
+    // 重新解析项目下全部采集与加工任务，用于快照更新后展开未指定的列
+    async fn rebuild_project(&self, project_code: String) -> Result<usize, Error> {
+        let mut cache = CatalogColumns::new();
+        let mut count = 0;
+        for task in self.collection_repo.list_all_collection(project_code.clone()).await? {
+            match self.collection_edges(&project_code, &task, &mut cache).await {
+                Ok(edges) => {
+                    count += edges.len();
+                    self.repo.replace_task_edge(project_code.clone(), TaskType::Collection, task.id, edges).await?;
+                }
+                Err(e) => tracing::warn!("lineage of collection task {} in project {} skipped: {}", task.id, project_code, e),
+            }
+        }
+        for task in self.transform_repo.list_all_transform(project_code.clone()).await? {
+            match self.transform_edges(&project_code, &task, &mut cache).await {
+                Ok(edges) => {
+                    count += edges.len();
+                    self.repo.replace_task_edge(project_code.clone(), TaskType::Transform, task.id, edges).await?;
+                }
+                Err(e) => tracing::warn!("lineage of transform task {} in project {} skipped: {}", task.id, project_code, e),
+            }
+        }
+        Ok(count)
+    }
+
+    async fn list_task_lineage(&self, project_code: String, task_type: TaskType, task_id: String) -> Result<Vec<LineageEdge>, Error> {
+        self.repo.list_task_edge(project_code, task_type, task_id).await
+    }
+
+    async fn trace_lineage(&self, project_code: String, query: LineageQuery, direction: LineageDirection) -> Result<LineageGraph, Error> {
+        if query.source_id.is_empty() {
+            return Err(Error::EmptyValue("source_id".to_string()));
+        }
+        if query.table_name.is_empty() {
+            return Err(Error::EmptyValue("table_name".to_string()));
+        }
+        let depth = query.depth.unwrap_or(DEFAULT_DEPTH).clamp(1, MAX_DEPTH);
+        let mut root = LineageNode {
+            source_type: query.source_type,
+            source_id: query.source_id,
+            source_name: String::new(),
+            schema_name: query.schema_name,
+            table_name: query.table_name,
+            column_name: query.column_name.unwrap_or_default(),
+        };
+
+        // 逐层沿边追溯，到达表级节点后按整张表继续
+        let edges = self.repo.list_all_edge(project_code.clone()).await?;
+        let mut visited = HashSet::from([root.key()]);
+        let mut frontier = vec![root.clone()];
+        let mut seen_edges = HashSet::new();
+        let mut selected = Vec::new();
+        for _ in 0..depth {
+            let mut next = Vec::new();
+            for node in frontier.iter() {
+                for edge in edges.iter() {
+                    let (near, far) = match direction {
+                        LineageDirection::Upstream => (edge.target_node(), edge.source_node()),
+                        LineageDirection::Downstream => (edge.source_node(), edge.target_node()),
+                    };
+                    if !node.matches(&near) || !seen_edges.insert(edge.id.clone()) {
+                        continue;
+                    }
+                    selected.push(edge.clone());
+                    if visited.insert(far.key()) {
+                        next.push(far);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        let mut seen_nodes = HashSet::from([root.key()]);
+        let mut nodes = Vec::new();
+        for node in selected.iter().flat_map(|e| [e.source_node(), e.target_node()]) {
+            if seen_nodes.insert(node.key()) {
+                nodes.push(node);
+            }
+        }
+        let names = self.source_names(&project_code, nodes.iter().chain([&root])).await?;
+        let name_of = |node: &LineageNode| names.get(&(node.source_type, node.source_id.clone())).cloned().unwrap_or_default();
+        root.source_name = name_of(&root);
+        for node in nodes.iter_mut() {
+            node.source_name = name_of(node);
+        }
+        nodes.insert(0, root.clone());
+        Ok(LineageGraph { root, nodes, edges: selected })
+    }
+}
+
+// 产生血缘的任务
+struct TaskOrigin<'a> {
+    task_type: TaskType,
+    task_id: &'a str,
+    task_name: &'a str,
+}
+
+impl TaskOrigin<'_> {
+    fn edge(&self, source: LineageNode, target: LineageNode, expression: &str) -> LineageEdge {
+        LineageEdge {
+            id: Uuid::new_v4().to_string(),
+            task_type: self.task_type,
+            task_id: self.task_id.to_string(),
+            task_name: self.task_name.to_string(),
+            source_type: source.source_type,
+            source_id: source.source_id,
+            source_schema: source.schema_name,
+            source_table: source.table_name,
+            source_column: source.column_name,
+            target_type: target.source_type,
+            target_id: target.source_id,
+            target_schema: target.schema_name,
+            target_table: target.table_name,
+            target_column: target.column_name,
+            expression: expression.to_string(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+}
+
+impl LineageServiceImpl {
+    // 采集任务：源表的列按同名写入目标表，未指定列时用数据源的最新快照展开
+    async fn collection_edges(&self, project_code: &str, task: &CollectionTask, cache: &mut CatalogColumns) -> Result<Vec<LineageEdge>, Error> {
+        // 只有数据库类采集有表与列
+        if task.category != CollectionCategory::Database {
+            return Ok(Vec::new());
+        }
+        let datasource = self.datasource_repo.get_datasource(project_code.to_string(), task.datasource_id.clone()).await?;
+        let source_schema = match datasource.datasource_type {
+            DataSourceType::Mysql => {
+                serde_json::from_value::<MysqlConnectionConfig>(datasource.connection_config.clone())
+                    .map_err(|e| Error::InvalidValue(format!("invalid mysql connection config: {}", e)))?
+                    .database
+            }
+            DataSourceType::Postgres => postgres::schema(&postgres::parse_config(&datasource.connection_config)?).to_string(),
+            _ => return Ok(Vec::new()),
+        };
+        let resource = self.resource_repo.get_resource(task.resource_id.clone()).await?;
+        let (tables, target_database, topic) = match task.collect_type {
+            CollectType::Stream => {
+                let rule = StreamCollectRule::parse(&task.rule)?;
+                (rule.tables, rule.target_database, rule.topic)
+            }
+            CollectType::Full | CollectType::Incremental => {
+                let rule = DatabaseCollectRule::parse(&task.rule)?;
+                (rule.tables, rule.target_database, String::new())
+            }
+        };
+        // Kafka 目标没有库，以 topic 作为下游表
+        let is_kafka = resource.resource_type == ResourceType::Kafka;
+        let target_schema = if is_kafka || !target_database.is_empty() {
+            target_database
+        } else {
+            serde_json::from_value::<DatabaseResourceConfig>(resource.config.clone())
+                .map_err(|e| Error::InvalidValue(format!("invalid resource config: {}", e)))?
+                .default_database()
+                .ok_or_else(|| Error::EmptyValue("rule.target_database".to_string()))?
+        };
+
+        let origin = TaskOrigin { task_type: TaskType::Collection, task_id: &task.id, task_name: &task.name };
+        let mut edges = Vec::new();
+        for table in tables.iter() {
+            let target_table = match (is_kafka, topic.is_empty()) {
+                (true, true) => format!("{}.{}", source_schema, table.source_table),
+                (true, false) => topic.clone(),
+                (false, _) => table.target_table().to_string(),
+            };
+            let source = |column: &str| node(CatalogSourceType::Datasource, &datasource.id, &source_schema, &table.source_table, column);
+            let target = |column: &str| node(CatalogSourceType::Resource, &resource.id, &target_schema, &target_table, column);
+            let columns = if table.columns.is_empty() {
+                self.catalog_columns(project_code, cache, CatalogSourceType::Datasource, &datasource.id, &source_schema, &table.source_table).await?
+            } else {
+                Some(table.columns.clone())
+            };
+            match columns {
+                Some(columns) => {
+                    for column in columns.iter() {
+                        edges.push(origin.edge(source(column), target(column), ""));
+                    }
+                }
+                // 未指定列且没有快照时只记录表级血缘
+                None => edges.push(origin.edge(source(""), target(""), "")),
+            }
+        }
+        Ok(dedup(edges))
+    }
+
+    // 加工任务：解析 SELECT 的输出列与 FROM/JOIN 中的表，表都在任务的执行资源上
+    async fn transform_edges(&self, project_code: &str, task: &TransformTask, cache: &mut CatalogColumns) -> Result<Vec<LineageEdge>, Error> {
+        let resource = self.resource_repo.get_resource(task.resource_id.clone()).await?;
+        let (config, _) = collect::resolve_target(&resource)?;
+        let database = materialize::output_database(task, &config)?;
+        let check = materialize::analyze(task, sql_parse::default_biz_date())?;
+        let (_, query) = sql_parse::parse_select(&check.rendered_sql)?;
+
+        let origin = TaskOrigin { task_type: TaskType::Transform, task_id: &task.id, task_name: &task.name };
+        let source = |schema: &str, table: &str, column: &str| node(CatalogSourceType::Resource, &resource.id, schema, table, column);
+        let target = |column: &str| node(CatalogSourceType::Resource, &resource.id, &database, &task.output_table, column);
+        let mut edges = Vec::new();
+        for output in sql_parse::column_lineage(&query) {
+            for reference in output.sources.iter() {
+                let (schema, table) = split_table(&reference.table, &database);
+                let known = self.catalog_columns(project_code, cache, CatalogSourceType::Resource, &resource.id, &schema, &table).await?;
+                if reference.column == "*" {
+                    match known {
+                        Some(columns) => {
+                            for column in columns.iter() {
+                                edges.push(origin.edge(source(&schema, &table, column), target(column), ""));
+                            }
+                        }
+                        None => edges.push(origin.edge(source(&schema, &table, ""), target(""), "")),
+                    }
+                    continue;
+                }
+                // 有快照时丢弃表中不存在的列（例如引用了输出列别名），来源不确定的列必须由快照确认
+                let exists = known.map(|columns| columns.iter().any(|c| c.eq_ignore_ascii_case(&reference.column)));
+                if exists == Some(false) || (reference.ambiguous && exists.is_none()) {
+                    continue;
+                }
+                let column = if output.name == "*" { &reference.column } else { &output.name };
+                edges.push(origin.edge(source(&schema, &table, &reference.column), target(column), &output.expression));
+            }
+        }
+
+        // 只出现在过滤或关联条件中的表记录为表级血缘
+        for name in check.referenced_tables.iter() {
+            let (schema, table) = split_table(name, &database);
+            let linked = edges
+                .iter()
+                .any(|e| e.source_schema.eq_ignore_ascii_case(&schema) && e.source_table.eq_ignore_ascii_case(&table));
+            if !linked {
+                edges.push(origin.edge(source(&schema, &table, ""), target(""), ""));
+            }
+        }
+        Ok(dedup(edges))
+    }
+
+    // 来源最新快照中某张表的列，没有快照或快照中没有该表时为 None
+    async fn catalog_columns(
+        &self,
+        project_code: &str,
+        cache: &mut CatalogColumns,
+        source_type: CatalogSourceType,
+        source_id: &str,
+        schema: &str,
+        table: &str,
+    ) -> Result<Option<Vec<String>>, Error> {
+        let key = (source_type, source_id.to_string());
+        if !cache.contains_key(&key) {
+            let mut tables: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+            if let Some(snapshot) = self.catalog_repo.latest_snapshot(project_code.to_string(), source_type, source_id.to_string()).await? {
+                for column in self.catalog_repo.list_snapshot_column(project_code.to_string(), snapshot.id).await? {
+                    tables
+                        .entry((column.schema_name.to_lowercase(), column.table_name.to_lowercase()))
+                        .or_default()
+                        .push(column.column_name);
+                }
+            }
+            cache.insert(key.clone(), tables);
+        }
+        Ok(cache
+            .get(&key)
+            .and_then(|tables| tables.get(&(schema.to_lowercase(), table.to_lowercase())))
+            .cloned())
+    }
+
+    // 节点所属数据源与资源的名称，已删除的来源没有名称
+    async fn source_names<'a>(
+        &self,
+        project_code: &str,
+        nodes: impl Iterator<Item = &'a LineageNode>,
+    ) -> Result<HashMap<(CatalogSourceType, String), String>, Error> {
+        let mut names = HashMap::new();
+        let resource_ids: HashSet<String> = nodes
+            .filter(|n| n.source_type == CatalogSourceType::Resource)
+            .map(|n| n.source_id.clone())
+            .collect();
+        for datasource in self.datasource_repo.list_all_datasource(project_code.to_string()).await? {
+            names.insert((CatalogSourceType::Datasource, datasource.id), datasource.name);
+        }
+        for id in resource_ids {
+            match self.resource_repo.get_resource(id).await {
+                Ok(resource) => {
+                    names.insert((CatalogSourceType::Resource, resource.id), resource.name);
+                }
+                Err(Error::NotFound) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(names)
+    }
+}
+
+fn node(source_type: CatalogSourceType, source_id: &str, schema: &str, table: &str, column: &str) -> LineageNode {
+    LineageNode {
+        source_type,
+        source_id: source_id.to_string(),
+        source_name: String::new(),
+        schema_name: schema.to_string(),
+        table_name: table.to_string(),
+        column_name: column.to_string(),
+    }
+}
+
+// "库.表" 拆分为库与表，未带库名时使用默认库
+fn split_table(name: &str, default_schema: &str) -> (String, String) {
+    match name.rsplit_once('.') {
+        Some((schema, table)) => (schema.rsplit('.').next().unwrap_or(schema).to_string(), table.to_string()),
+        None => (default_schema.to_string(), name.to_string()),
+    }
+}
+
+// 同一对节点只保留第一条边
+fn dedup(edges: Vec<LineageEdge>) -> Vec<LineageEdge> {
+    let mut seen = HashSet::new();
+    edges
+        .into_iter()
+        .filter(|e| seen.insert((e.source_node().key(), e.target_node().key())))
+        .collect()
+}
@@ -0,0 +1,451 @@
+use std::time::Instant;
+
+use serde::Serialize;
+use sqlx::Row;
+use sqlx::mysql::MySqlConnection;
+use tokio::sync::watch;
+use tracing::Instrument;
+
+use crate::autofac;
+use crate::models::Error;
+use crate::models::resource::{DatabaseResourceConfig, Resource};
+use crate::models::task_run::{RunStatus, TaskRun};
+use crate::models::transform::{Materialization, TransformCheck, TransformTask};
+use crate::utils::sql_parse;
+use super::collect::{self, error_detail};
+use super::collect::mysql::{self, TargetDialect, quote_ident, quote_literal};
+use super::run_log;
+use super::task_registry::{self, RunningTaskGuard};
+
+// 一次加工运行的统计
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct TransformStats {
+    pub biz_date: String,
+    pub output_table: String,
+    pub materialization: Materialization,
+    pub rows_written: u64,
+    // 替换参数后实际执行的查询
+    pub sql: String,
+}
+
+// 物化的一步，count 为 true 时语句影响的行数计入写入行数
+struct Step {
+    sql: String,
+    count: bool,
+}
+
+// 输出表所在的资源与库
+struct Target<'a> {
+    config: &'a DatabaseResourceConfig,
+    dialect: TargetDialect,
+    database: String,
+}
+
+/// 按业务日期替换参数并解析 SQL
+pub fn analyze(task: &TransformTask, biz_date: chrono::NaiveDate) -> Result<TransformCheck, Error> {
+    let params = sql_parse::resolve_params(&task.params, biz_date)?;
+    let (rendered, used) = sql_parse::render(&task.sql_text, &params)?;
+    let (sql, query) = sql_parse::parse_select(&rendered)?;
+    Ok(TransformCheck {
+        referenced_tables: sql_parse::referenced_tables(&query),
+        params: used,
+        rendered_sql: sql,
+    })
+}
+
+/// 输出库名：任务配置优先，否则取资源配置中的第一个库
+pub fn output_database(task: &TransformTask, config: &DatabaseResourceConfig) -> Result<String, Error> {
+    if !task.target_database.is_empty() {
+        return Ok(task.target_database.clone());
+    }
+    config
+        .default_database()
+        .ok_or_else(|| Error::EmptyValue("target_database".to_string()))
+}
+
+/// 在资源上执行查询并按物化方式写入输出表
+pub async fn execute(
+    task: &TransformTask,
+    resource: &Resource,
+    sql: &str,
+    cancel: &watch::Receiver<bool>,
+    stats: &mut TransformStats,
+) -> Result<(), Error> {
+    let (config, dialect) = collect::resolve_target(resource)?;
+    let target = Target { config: &config, dialect, database: output_database(task, &config)? };
+    stats.output_table = format!("{}.{}", target.database, task.output_table);
+
+    let mut conn = mysql::connect_resource(&config, dialect).await?;
+    let result = materialize(&mut conn, &target, task, sql, cancel, stats).await;
+    mysql::close(conn).await;
+    result
+}
+
+async fn materialize(
+    conn: &mut MySqlConnection,
+    target: &Target<'_>,
+    task: &TransformTask,
+    sql: &str,
+    cancel: &watch::Receiver<bool>,
+    stats: &mut TransformStats,
+) -> Result<(), Error> {
+    // 取消时通过另一个连接终止正在执行的语句
+    let row = sqlx::raw_sql("SELECT CONNECTION_ID()").fetch_one(&mut *conn).await?;
+    let connection_id = row.try_get_unchecked::<u64, _>(0)?;
+
+    let create_database = format!("CREATE DATABASE IF NOT EXISTS {}", quote_ident(&target.database));
+    sqlx::raw_sql(&create_database).execute(&mut *conn).await?;
+    let exists = table_exists(conn, &target.database, &task.output_table).await?;
+    if exists && task.materialization == Materialization::Merge {
+        check_merge_keys(conn, target, task).await?;
+    }
+
+    for step in plan(target, task, sql_select(sql).as_str(), exists) {
+        if *cancel.borrow() {
+            tracing::info!("transform task {} cancelled before: {}", task.id, step.sql);
+            return Ok(());
+        }
+        tracing::info!("executing: {}", step.sql);
+        match run_step(conn, target, connection_id, &step.sql, cancel).await? {
+            Some(rows) if step.count => stats.rows_written += rows,
+            Some(_) => {}
+            None => {
+                tracing::info!("transform task {} cancelled, statement killed", task.id);
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+// 把查询包成派生表，避免 WITH、UNION 等写法与外层语句冲突
+fn sql_select(sql: &str) -> String {
+    format!("SELECT * FROM (\n{}\n) AS t", sql)
+}
+
+// 生成物化语句；输出表已存在时 append 与 merge 要求查询的列顺序与输出表一致
+fn plan(target: &Target<'_>, task: &TransformTask, select: &str, exists: bool) -> Vec<Step> {
+    let table = |name: &str| format!("{}.{}", quote_ident(&target.database), quote_ident(name));
+    let output = table(&task.output_table);
+    let properties = format!("PROPERTIES (\"replication_num\" = \"{}\")", task.doris_replication_num);
+    let create_as = |name: &str| match target.dialect {
+        TargetDialect::Mysql => format!("CREATE TABLE {} AS {}", table(name), select),
+        TargetDialect::Doris => format!("CREATE TABLE {} {} AS {}", table(name), properties, select),
+    };
+    let step = |sql: String, count: bool| Step { sql, count };
+
+    match task.materialization {
+        // 先写入临时表再替换，失败时输出表保持上一次的结果
+        Materialization::Replace => {
+            let id = task.id.replace('-', "");
+            let tmp = format!("_tmp_{}", id);
+            let mut steps = vec![
+                step(format!("DROP TABLE IF EXISTS {}", table(&tmp)), false),
+                step(create_as(&tmp), true),
+            ];
+            match (target.dialect, exists) {
+                (TargetDialect::Mysql, true) => {
+                    let old = format!("_old_{}", id);
+                    steps.push(step(format!("DROP TABLE IF EXISTS {}", table(&old)), false));
+                    steps.push(step(format!("RENAME TABLE {} TO {}, {} TO {}", output, table(&old), table(&tmp), output), false));
+                    steps.push(step(format!("DROP TABLE {}", table(&old)), false));
+                }
+                (TargetDialect::Doris, true) => {
+                    steps.push(step(
+                        format!("ALTER TABLE {} REPLACE WITH TABLE {} PROPERTIES (\"swap\" = \"false\")", output, quote_ident(&tmp)),
+                        false,
+                    ));
+                }
+                (TargetDialect::Mysql, false) => steps.push(step(format!("RENAME TABLE {} TO {}", table(&tmp), output), false)),
+                (TargetDialect::Doris, false) => {
+                    steps.push(step(format!("ALTER TABLE {} RENAME {}", table(&tmp), quote_ident(&task.output_table)), false));
+                }
+            }
+            steps
+        }
+        Materialization::Append if exists => vec![step(format!("INSERT INTO {} {}", output, select), true)],
+        Materialization::Append => vec![step(create_as(&task.output_table), true)],
+        Materialization::Merge if exists => {
+            // MySQL 依赖输出表上的主键或唯一键，Doris 依赖 Unique 模型
+            let verb = match target.dialect {
+                TargetDialect::Mysql => "REPLACE INTO",
+                TargetDialect::Doris => "INSERT INTO",
+            };
+            vec![step(format!("{} {} {}", verb, output, select), true)]
+        }
+        Materialization::Merge => {
+            let keys: Vec<String> = task.merge_keys.iter().map(|k| quote_ident(k)).collect();
+            let keys = keys.join(", ");
+            let sql = match target.dialect {
+                TargetDialect::Mysql => format!("CREATE TABLE {} (PRIMARY KEY ({})) AS {}", output, keys, select),
+                // Doris 要求键列位于查询结果的最前面
+                TargetDialect::Doris => format!(
+                    "CREATE TABLE {} UNIQUE KEY({}) DISTRIBUTED BY HASH({}) BUCKETS AUTO {} AS {}",
+                    output, keys, keys, properties, select
+                ),
+            };
+            vec![step(sql, true)]
+        }
+    }
+}
+
+async fn table_exists(conn: &mut MySqlConnection, database: &str, table: &str) -> Result<bool, Error> {
+    let sql = format!(
+        "SELECT COUNT(*) FROM information_schema.TABLES WHERE TABLE_SCHEMA = {} AND TABLE_NAME = {}",
+        quote_literal(database),
+        quote_literal(table)
+    );
+    let row = sqlx::raw_sql(&sql).fetch_one(&mut *conn).await?;
+    Ok(row.try_get_unchecked::<i64, _>(0)? > 0)
+}
+
+// 已存在的输出表可能由 replace 或 append 创建，没有按合并键去重的主键、唯一键或 Unique 模型时拒绝合并
+async fn check_merge_keys(conn: &mut MySqlConnection, target: &Target<'_>, task: &TransformTask) -> Result<(), Error> {
+    let keys = match target.dialect {
+        TargetDialect::Mysql => {
+            let sql = format!(
+                "SELECT INDEX_NAME, COLUMN_NAME FROM information_schema.STATISTICS \
+                 WHERE TABLE_SCHEMA = {} AND TABLE_NAME = {} AND NON_UNIQUE = 0 ORDER BY INDEX_NAME, SEQ_IN_INDEX",
+                quote_literal(&target.database),
+                quote_literal(&task.output_table)
+            );
+            let rows = sqlx::raw_sql(&sql).fetch_all(&mut *conn).await?;
+            let mut indexes: Vec<(String, Vec<String>)> = Vec::new();
+            for row in rows {
+                let index = row.try_get_unchecked::<String, _>(0)?;
+                let column = row.try_get_unchecked::<String, _>(1)?;
+                match indexes.last_mut() {
+                    Some((name, columns)) if *name == index => columns.push(column),
+                    _ => indexes.push((index, vec![column])),
+                }
+            }
+            indexes.into_iter().map(|(_, columns)| columns).collect()
+        }
+        TargetDialect::Doris => {
+            let sql = format!("SHOW CREATE TABLE {}.{}", quote_ident(&target.database), quote_ident(&task.output_table));
+            let row = sqlx::raw_sql(&sql).fetch_one(&mut *conn).await?;
+            doris_unique_key(&row.try_get_unchecked::<String, _>(1)?).into_iter().collect()
+        }
+    };
+    if keys.iter().any(|columns| same_columns(columns, &task.merge_keys)) {
+        return Ok(());
+    }
+    Err(Error::InvalidValue(format!(
+        "output table {}.{} has no primary key, unique key or Doris unique model on merge keys ({}), \
+         merging would append duplicate rows",
+        target.database,
+        task.output_table,
+        task.merge_keys.join(", ")
+    )))
+}
+
+// Doris 建表语句中 Unique 模型的键列
+fn doris_unique_key(create_sql: &str) -> Option<Vec<String>> {
+    let re = regex::Regex::new(r"(?i)\bUNIQUE\s+KEY\s*\(([^)]*)\)").unwrap();
+    let caps = re.captures(create_sql)?;
+    Some(caps[1].split(',').map(|c| c.trim().trim_matches('`').to_string()).collect())
+}
+
+// 列名不区分大小写与顺序
+fn same_columns(columns: &[String], keys: &[String]) -> bool {
+    let normalize = |names: &[String]| {
+        let mut names: Vec<String> = names.iter().map(|n| n.to_lowercase()).collect();
+        names.sort();
+        names.dedup();
+        names
+    };
+    normalize(columns) == normalize(keys)
+}
+
+// 执行一条语句，返回影响的行数；执行中收到取消信号时终止语句并返回 None
+async fn run_step(
+    conn: &mut MySqlConnection,
+    target: &Target<'_>,
+    connection_id: u64,
+    sql: &str,
+    cancel: &watch::Receiver<bool>,
+) -> Result<Option<u64>, Error> {
+    let mut cancel = cancel.clone();
+    let execute = sqlx::raw_sql(sql).execute(&mut *conn);
+    tokio::pin!(execute);
+    let mut killed = false;
+    loop {
+        tokio::select! {
+            result = &mut execute => {
+                return match result {
+                    Ok(result) => Ok(Some(result.rows_affected())),
+                    Err(_) if killed => Ok(None),
+                    Err(e) => Err(e.into()),
+                };
+            }
+            _ = task_registry::cancelled(&mut cancel), if !killed => {
+                kill_query(target, connection_id).await;
+                killed = true;
+            }
+        }
+    }
+}
+
+async fn kill_query(target: &Target<'_>, connection_id: u64) {
+    let result = async {
+        let mut conn = mysql::connect_resource(target.config, target.dialect).await?;
+        sqlx::raw_sql(&format!("KILL QUERY {}", connection_id)).execute(&mut conn).await?;
+        mysql::close(conn).await;
+        Ok::<_, Error>(())
+    }
+    .await;
+    if let Err(e) = result {
+        tracing::warn!("failed to kill query on connection {}: {}", connection_id, error_detail(&e));
+    }
+}
+
+/// 在后台执行一次加工并记录运行结果与运行日志，guard 在运行结束后释放
+pub fn spawn_run(
+    project_code: String,
+    task: TransformTask,
+    resource: Resource,
+    biz_date: chrono::NaiveDate,
+    check: TransformCheck,
+    mut run: TaskRun,
+    guard: RunningTaskGuard,
+) {
+    let span = run_log::start(&project_code, &run.id);
+    tokio::spawn(
+        async move {
+            let started = Instant::now();
+            tracing::info!(
+                "transform task {} ({}) started, biz_date {}, {} into {} on {}",
+                task.name, task.id, biz_date, task.materialization, task.output_table, resource.name
+            );
+            if !check.referenced_tables.is_empty() {
+                tracing::info!("referenced tables: {}", check.referenced_tables.join(", "));
+            }
+            let mut stats = TransformStats {
+                biz_date: biz_date.format("%Y-%m-%d").to_string(),
+                output_table: task.output_table.clone(),
+                materialization: task.materialization,
+                rows_written: 0,
+                sql: check.rendered_sql.clone(),
+            };
+            let result = execute(&task, &resource, &check.rendered_sql, &guard.cancel_signal(), &mut stats).await;
+
+            run.rows_written = stats.rows_written as i64;
+            run.duration_ms = started.elapsed().as_millis() as i64;
+            run.detail = serde_json::to_value(&stats).ok().map(sqlx::types::Json);
+            run.finished_at = Some(chrono::Utc::now());
+            match &result {
+                Ok(_) if guard.is_cancelled() => run.status = RunStatus::Cancelled,
+                Ok(_) => run.status = RunStatus::Success,
+                Err(e) => {
+                    run.status = RunStatus::Failed;
+                    run.error_message = Some(error_detail(e));
+                    run.error_chain = Some(sqlx::types::Json(e.chain()));
+                }
+            }
+
+            let run_id = run.id.clone();
+            match &result {
+                Ok(_) => tracing::info!(
+                    "transform task {} run {} finished as {}, {} rows written in {} ms",
+                    task.id, run_id, run.status, run.rows_written, run.duration_ms
+                ),
+                Err(e) => tracing::warn!("transform task {} run {} failed: {}", task.id, run_id, error_detail(e)),
+            }
+
+            run_log::finish(&run).await;
+            let saved = autofac::get_global_app_state_ref().get_transform_service().finish_transform_run(project_code, run).await;
+            drop(guard);
+
+            if let Err(e) = saved {
+                tracing::error!("failed to save transform run {}: {}", run_id, error_detail(&e));
+            }
+        }
+        .instrument(span),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::transform::{Materialization, TransformTask};
+
+    fn task(materialization: Materialization, sql: &str) -> TransformTask {
+        TransformTask {
+            id: "a-b".to_string(),
+            output_table: "out".to_string(),
+            materialization,
+            merge_keys: vec!["id".to_string()],
+            sql_text: sql.to_string(),
+            doris_replication_num: 1,
+            ..Default::default()
+        }
+    }
+
+    fn sqls(dialect: TargetDialect, task: &TransformTask, exists: bool) -> Vec<String> {
+        let config = DatabaseResourceConfig::default();
+        let target = Target { config: &config, dialect, database: "dw".to_string() };
+        plan(&target, task, &sql_select("SELECT 1 AS id"), exists).into_iter().map(|s| s.sql).collect()
+    }
+
+    #[test]
+    fn analyze_accepts_cte_and_union() {
+        let biz_date = chrono::NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let sql = "WITH d AS (SELECT * FROM ods.orders WHERE dt = '${biz_date}') SELECT id FROM d UNION ALL SELECT id FROM ods.refunds";
+        let check = analyze(&task(Materialization::Replace, sql), biz_date).unwrap();
+        assert_eq!(check.referenced_tables, vec!["ods.orders".to_string(), "ods.refunds".to_string()]);
+        assert_eq!(check.params, vec!["biz_date".to_string()]);
+        assert!(check.rendered_sql.contains("dt = '2024-03-01'"));
+    }
+
+    #[test]
+    fn analyze_rejects_ddl_and_dml() {
+        let biz_date = chrono::NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        for sql in ["DROP TABLE ods.orders", "DELETE FROM ods.orders", "INSERT INTO t SELECT 1", "SELECT 1; DROP TABLE t"] {
+            assert!(analyze(&task(Materialization::Replace, sql), biz_date).is_err(), "{} should be rejected", sql);
+        }
+    }
+
+    #[test]
+    fn replace_swaps_through_temporary_table() {
+        let t = task(Materialization::Replace, "");
+        let steps = sqls(TargetDialect::Mysql, &t, true);
+        assert_eq!(steps[0], "DROP TABLE IF EXISTS `dw`.`_tmp_ab`");
+        assert!(steps[1].starts_with("CREATE TABLE `dw`.`_tmp_ab` AS SELECT * FROM ("));
+        assert_eq!(steps[3], "RENAME TABLE `dw`.`out` TO `dw`.`_old_ab`, `dw`.`_tmp_ab` TO `dw`.`out`");
+        assert_eq!(steps.last().unwrap(), "DROP TABLE `dw`.`_old_ab`");
+
+        let steps = sqls(TargetDialect::Mysql, &t, false);
+        assert_eq!(steps.last().unwrap(), "RENAME TABLE `dw`.`_tmp_ab` TO `dw`.`out`");
+    }
+
+    #[test]
+    fn append_and_merge_write_into_existing_table() {
+        let steps = sqls(TargetDialect::Mysql, &task(Materialization::Append, ""), true);
+        assert_eq!(steps.len(), 1);
+        assert!(steps[0].starts_with("INSERT INTO `dw`.`out` SELECT"));
+
+        let steps = sqls(TargetDialect::Mysql, &task(Materialization::Merge, ""), true);
+        assert!(steps[0].starts_with("REPLACE INTO `dw`.`out` SELECT"));
+
+        let steps = sqls(TargetDialect::Mysql, &task(Materialization::Merge, ""), false);
+        assert!(steps[0].starts_with("CREATE TABLE `dw`.`out` (PRIMARY KEY (`id`)) AS SELECT"));
+    }
+
+    #[test]
+    fn merge_requires_key_on_exactly_merge_columns() {
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert!(same_columns(&names(&["ID"]), &names(&["id"])));
+        assert!(same_columns(&names(&["dt", "id"]), &names(&["id", "dt"])));
+        // 键列更少会误合并不同的行，更多则无法按合并键去重
+        assert!(!same_columns(&names(&["id"]), &names(&["id", "dt"])));
+        assert!(!same_columns(&names(&["id", "dt"]), &names(&["id"])));
+    }
+
+    #[test]
+    fn doris_unique_key_is_read_from_create_table() {
+        let unique = "CREATE TABLE `out` (\n  `id` bigint NULL,\n  `dt` date NULL\n) ENGINE=OLAP\nUNIQUE KEY(`id`, `dt`)\nDISTRIBUTED BY HASH(`id`) BUCKETS AUTO";
+        assert_eq!(doris_unique_key(unique), Some(vec!["id".to_string(), "dt".to_string()]));
+        let duplicate = "CREATE TABLE `out` (\n  `id` bigint NULL\n) ENGINE=OLAP\nDUPLICATE KEY(`id`)\nDISTRIBUTED BY HASH(`id`) BUCKETS AUTO";
+        assert_eq!(doris_unique_key(duplicate), None);
+    }
+}
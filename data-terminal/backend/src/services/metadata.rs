@@ -0,0 +1,482 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use sqlx::Row;
+use sqlx::mysql::MySqlConnection;
+use sqlx::postgres::PgConnection;
+
+use crate::models::Error;
+use crate::models::datasource::{DataSource, DataSourceType, MysqlConnectionConfig};
+use crate::models::datasource::PostgresConnectionConfig;
+use crate::models::metadata::{ColumnInfo, DataSourceCatalog, IndexInfo, SchemaMeta, TableDetail, TableSummary};
+use crate::models::resource::{DatabaseResourceConfig, Resource, ResourceType};
+use super::collect::{mysql, postgres};
+use super::collect::mysql::TargetDialect;
+
+// 缓存有效期，过期或手动刷新时重新读取
+const CACHE_TTL_MINUTES: i64 = 10;
+
+const MYSQL_SYSTEM_SCHEMAS: &str = "'information_schema', 'mysql', 'performance_schema', 'sys'";
+const PG_SYSTEM_SCHEMAS: &str = "'information_schema', 'pg_catalog', 'pg_toast'";
+
+// (项目编码, 数据源ID) -> 库表目录
+static CATALOGS: LazyLock<Mutex<HashMap<(String, String), DataSourceCatalog>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+// (项目编码, 数据源ID, schema, 表名) -> 表结构
+static TABLES: LazyLock<Mutex<HashMap<(String, String, String, String), TableDetail>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn is_fresh(refreshed_at: chrono::DateTime<chrono::Utc>) -> bool {
+    chrono::Utc::now() - refreshed_at < chrono::Duration::minutes(CACHE_TTL_MINUTES)
+}
+
+/// 移除数据源的全部缓存，数据源修改或删除时调用
+pub fn evict(project_code: &str, datasource_id: &str) {
+    CATALOGS.lock().unwrap().remove(&(project_code.to_string(), datasource_id.to_string()));
+    TABLES
+        .lock()
+        .unwrap()
+        .retain(|(project, id, _, _), _| project != project_code || id != datasource_id);
+}
+
+/// 数据源的库表目录，优先使用缓存；refresh 为 true 时重新读取并清空该数据源的表结构缓存
+pub async fn catalog(project_code: &str, datasource: &DataSource, refresh: bool) -> Result<DataSourceCatalog, Error> {
+    let key = (project_code.to_string(), datasource.id.clone());
+    let cached = if refresh {
+        evict(project_code, &datasource.id);
+        None
+    } else {
+        CATALOGS.lock().unwrap().get(&key).filter(|c| is_fresh(c.refreshed_at)).cloned()
+    };
+    if let Some(cached) = cached {
+        return Ok(cached);
+    }
+
+    let catalog = match datasource.datasource_type {
+        DataSourceType::Mysql => {
+            let cfg = mysql_config(&datasource.connection_config)?;
+            let mut conn = mysql::connect_datasource(&datasource.connection_config).await?;
+            let result = mysql_catalog(&mut conn).await;
+            mysql::close(conn).await;
+            DataSourceCatalog {
+                datasource_id: datasource.id.clone(),
+                database: cfg.database.clone(),
+                default_schema: cfg.database,
+                schemas: result?,
+                refreshed_at: chrono::Utc::now(),
+            }
+        }
+        DataSourceType::Postgres => {
+            let cfg = postgres::parse_config(&datasource.connection_config)?;
+            let mut conn = postgres::connect(&cfg).await?;
+            let result = pg_catalog(&mut conn).await;
+            postgres::close(conn).await;
+            DataSourceCatalog {
+                datasource_id: datasource.id.clone(),
+                database: cfg.database.clone(),
+                default_schema: postgres::schema(&cfg).to_string(),
+                schemas: result?,
+                refreshed_at: chrono::Utc::now(),
+            }
+        }
+        ref other => return Err(Error::InvalidValue(format!("{} datasource has no schema metadata", other))),
+    };
+
+    CATALOGS.lock().unwrap().insert(key, catalog.clone());
+    Ok(catalog)
+}
+
+/// 表的列、主键、索引与估算行数，优先使用缓存
+pub async fn table_detail(project_code: &str, datasource: &DataSource, schema: &str, table: &str, refresh: bool) -> Result<TableDetail, Error> {
+    let key = (project_code.to_string(), datasource.id.clone(), schema.to_string(), table.to_string());
+    let cached = if refresh {
+        None
+    } else {
+        TABLES.lock().unwrap().get(&key).filter(|t| is_fresh(t.refreshed_at)).cloned()
+    };
+    if let Some(cached) = cached {
+        return Ok(cached);
+    }
+
+    let detail = match datasource.datasource_type {
+        DataSourceType::Mysql => {
+            let mut conn = mysql::connect_datasource(&datasource.connection_config).await?;
+            let result = mysql_table(&mut conn, schema, table).await;
+            mysql::close(conn).await;
+            result?
+        }
+        DataSourceType::Postgres => {
+            let cfg = postgres::parse_config(&datasource.connection_config)?;
+            let mut conn = postgres::connect(&cfg).await?;
+            let result = pg_table(&mut conn, schema, table).await;
+            postgres::close(conn).await;
+            result?
+        }
+        ref other => return Err(Error::InvalidValue(format!("{} datasource has no schema metadata", other))),
+    };
+
+    TABLES.lock().unwrap().insert(key, detail.clone());
+    Ok(detail)
+}
+
+/// 读取数据源配置的库（MySQL）或 schema（PostgreSQL）下全部表的列与主键
+pub async fn introspect_datasource(datasource: &DataSource) -> Result<Vec<TableDetail>, Error> {
+    match datasource.datasource_type {
+        DataSourceType::Mysql => {
+            let cfg = mysql_config(&datasource.connection_config)?;
+            let mut conn = mysql::connect_datasource(&datasource.connection_config).await?;
+            let result = introspect_mysql(&mut conn, &[cfg.database]).await;
+            mysql::close(conn).await;
+            result
+        }
+        DataSourceType::Postgres => {
+            let cfg = postgres::parse_config(&datasource.connection_config)?;
+            let mut conn = postgres::connect(&cfg).await?;
+            let result = introspect_postgres(&mut conn, &[postgres::schema(&cfg).to_string()]).await;
+            postgres::close(conn).await;
+            result
+        }
+        ref other => Err(Error::InvalidValue(format!("{} datasource has no schema metadata", other))),
+    }
+}
+
+/// 读取资源配置的库下全部表的列与主键；PostgreSQL 资源读取第一个库的全部 schema
+pub async fn introspect_resource(resource: &Resource) -> Result<Vec<TableDetail>, Error> {
+    let config = serde_json::from_value::<DatabaseResourceConfig>(resource.config.clone())
+        .map_err(|e| Error::InvalidValue(format!("invalid resource config: {}", e)))?;
+    let databases: Vec<String> = config
+        .databases
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    match resource.resource_type {
+        ResourceType::Mysql | ResourceType::Doris => {
+            let dialect = if resource.resource_type == ResourceType::Doris { TargetDialect::Doris } else { TargetDialect::Mysql };
+            let mut conn = mysql::connect_resource(&config, dialect).await?;
+            let result = introspect_mysql(&mut conn, &databases).await;
+            mysql::close(conn).await;
+            result
+        }
+        ResourceType::Postgres => {
+            let cfg = PostgresConnectionConfig {
+                database: config.default_database().unwrap_or_else(|| "postgres".to_string()),
+                host: config.host,
+                port: config.port,
+                username: config.username,
+                password: config.password,
+                catalog: String::new(),
+            };
+            let mut conn = postgres::connect(&cfg).await?;
+            let result = introspect_postgres(&mut conn, &[]).await;
+            postgres::close(conn).await;
+            result
+        }
+        other => Err(Error::InvalidValue(format!("{} resource has no schema metadata", other))),
+    }
+}
+
+/// 读取库下全部表的列与主键，用于元数据快照，不包含索引；schemas 为空时读取全部非系统库。
+/// 只使用文本协议，Doris 资源同样适用
+pub async fn introspect_mysql(conn: &mut MySqlConnection, schemas: &[String]) -> Result<Vec<TableDetail>, Error> {
+    let filter = if schemas.is_empty() {
+        format!("TABLE_SCHEMA NOT IN ({})", MYSQL_SYSTEM_SCHEMAS)
+    } else {
+        format!("TABLE_SCHEMA IN ({})", schemas.iter().map(|s| mysql::quote_literal(s)).collect::<Vec<_>>().join(", "))
+    };
+
+    let sql = format!(
+        "SELECT CAST(TABLE_SCHEMA AS CHAR), CAST(TABLE_NAME AS CHAR), CAST(TABLE_TYPE AS CHAR), CAST(TABLE_ROWS AS SIGNED), CAST(COALESCE(TABLE_COMMENT, '') AS CHAR) \
+        FROM information_schema.TABLES WHERE {} ORDER BY TABLE_SCHEMA, TABLE_NAME",
+        filter
+    );
+    let mut tables = Vec::new();
+    for row in sqlx::raw_sql(&sql).fetch_all(&mut *conn).await? {
+        let table_type = table_type(&row.try_get::<String, _>(2)?);
+        let comment: String = row.try_get(4)?;
+        tables.push(TableDetail {
+            schema: row.try_get(0)?,
+            name: row.try_get(1)?,
+            approx_rows: if table_type == "view" { None } else { row.try_get(3)? },
+            comment: if table_type == "view" { String::new() } else { comment },
+            table_type,
+            refreshed_at: chrono::Utc::now(),
+            ..Default::default()
+        });
+    }
+
+    let sql = format!(
+        "SELECT CAST(TABLE_SCHEMA AS CHAR), CAST(TABLE_NAME AS CHAR), CAST(COLUMN_NAME AS CHAR), CAST(COLUMN_TYPE AS CHAR), CAST(DATA_TYPE AS CHAR), \
+        CAST(IS_NULLABLE AS CHAR), CAST(COLUMN_DEFAULT AS CHAR), CAST(COLUMN_COMMENT AS CHAR), CAST(COLUMN_KEY AS CHAR) \
+        FROM information_schema.COLUMNS WHERE {} ORDER BY TABLE_SCHEMA, TABLE_NAME, ORDINAL_POSITION",
+        filter
+    );
+    let mut columns: Vec<(String, String, ColumnInfo, bool)> = Vec::new();
+    for row in sqlx::raw_sql(&sql).fetch_all(&mut *conn).await? {
+        let key: Option<String> = row.try_get(8)?;
+        let column = ColumnInfo {
+            name: row.try_get(2)?,
+            column_type: row.try_get::<String, _>(3)?.to_lowercase(),
+            data_type: row.try_get::<String, _>(4)?.to_lowercase(),
+            nullable: row.try_get::<String, _>(5)?.eq_ignore_ascii_case("YES"),
+            default_value: row.try_get(6)?,
+            comment: row.try_get::<Option<String>, _>(7)?.unwrap_or_default(),
+        };
+        columns.push((row.try_get(0)?, row.try_get(1)?, column, key.as_deref() == Some("PRI")));
+    }
+    Ok(attach_columns(tables, columns))
+}
+
+/// 读取 schema 下全部表的列与主键，用于元数据快照，不包含索引；schemas 为空时读取全部非系统 schema
+pub async fn introspect_postgres(conn: &mut PgConnection, schemas: &[String]) -> Result<Vec<TableDetail>, Error> {
+    let filter = format!(
+        "((cardinality($1::text[]) = 0 AND n.nspname NOT IN ({}) AND n.nspname NOT LIKE 'pg_temp_%' AND n.nspname NOT LIKE 'pg_toast_temp_%') \
+        OR n.nspname = ANY($1::text[])) AND c.relkind IN ('r', 'p', 'v', 'm', 'f') AND NOT c.relispartition",
+        PG_SYSTEM_SCHEMAS
+    );
+
+    let sql = format!(
+        "SELECT n.nspname::text, c.relname::text, c.relkind::text, CASE WHEN c.reltuples < 0 THEN NULL ELSE c.reltuples::bigint END, \
+        COALESCE(obj_description(c.oid, 'pg_class'), '') \
+        FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace WHERE {} ORDER BY n.nspname, c.relname",
+        filter
+    );
+    let rows: Vec<(String, String, String, Option<i64>, String)> = sqlx::query_as(&sql).bind(schemas).fetch_all(&mut *conn).await?;
+    let tables = rows
+        .into_iter()
+        .map(|(schema, name, kind, approx_rows, comment)| TableDetail {
+            schema,
+            name,
+            table_type: table_type(&kind),
+            comment,
+            approx_rows: if kind == "v" { None } else { approx_rows },
+            refreshed_at: chrono::Utc::now(),
+            ..Default::default()
+        })
+        .collect();
+
+    let sql = format!(
+        "SELECT n.nspname::text, c.relname::text, a.attname::text, format_type(a.atttypid, a.atttypmod), t.typname::text, NOT a.attnotnull, \
+        pg_get_expr(d.adbin, d.adrelid), COALESCE(col_description(a.attrelid, a.attnum), ''), \
+        EXISTS (SELECT 1 FROM pg_index i WHERE i.indrelid = c.oid AND i.indisprimary AND a.attnum = ANY(i.indkey)) \
+        FROM pg_attribute a JOIN pg_class c ON c.oid = a.attrelid JOIN pg_namespace n ON n.oid = c.relnamespace \
+        JOIN pg_type t ON t.oid = a.atttypid LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum \
+        WHERE {} AND a.attnum > 0 AND NOT a.attisdropped ORDER BY n.nspname, c.relname, a.attnum",
+        filter
+    );
+    let rows: Vec<(String, String, String, String, String, bool, Option<String>, String, bool)> =
+        sqlx::query_as(&sql).bind(schemas).fetch_all(&mut *conn).await?;
+    let columns = rows
+        .into_iter()
+        .map(|(schema, table, name, column_type, data_type, nullable, default_value, comment, primary)| {
+            (schema, table, ColumnInfo { name, column_type, data_type, nullable, default_value, comment }, primary)
+        })
+        .collect();
+    Ok(attach_columns(tables, columns))
+}
+
+// 将按表排序的列归入对应的表，主键列按列顺序记录
+fn attach_columns(mut tables: Vec<TableDetail>, columns: Vec<(String, String, ColumnInfo, bool)>) -> Vec<TableDetail> {
+    let positions: HashMap<(String, String), usize> = tables
+        .iter()
+        .enumerate()
+        .map(|(i, t)| ((t.schema.clone(), t.name.clone()), i))
+        .collect();
+    for (schema, table, column, primary) in columns {
+        if let Some(&i) = positions.get(&(schema, table)) {
+            if primary {
+                tables[i].primary_key.push(column.name.clone());
+            }
+            tables[i].columns.push(column);
+        }
+    }
+    tables
+}
+
+fn mysql_config(config: &serde_json::Value) -> Result<MysqlConnectionConfig, Error> {
+    serde_json::from_value::<MysqlConnectionConfig>(config.clone())
+        .map_err(|e| Error::InvalidValue(format!("invalid mysql connection config: {}", e)))
+}
+
+fn table_type(raw: &str) -> String {
+    match raw {
+        "VIEW" | "SYSTEM VIEW" | "v" | "m" => "view".to_string(),
+        _ => "table".to_string(),
+    }
+}
+
+// 按库分组表，没有表的库也保留
+fn group_schemas(schemas: Vec<String>, tables: Vec<(String, TableSummary)>) -> Vec<SchemaMeta> {
+    let mut grouped: Vec<SchemaMeta> = schemas.into_iter().map(|name| SchemaMeta { name, tables: Vec::new() }).collect();
+    for (schema, table) in tables {
+        if let Some(meta) = grouped.iter_mut().find(|s| s.name == schema) {
+            meta.tables.push(table);
+        }
+    }
+    grouped
+}
+
+async fn mysql_catalog(conn: &mut MySqlConnection) -> Result<Vec<SchemaMeta>, Error> {
+    let sql = format!(
+        "SELECT CAST(SCHEMA_NAME AS CHAR) FROM information_schema.SCHEMATA WHERE SCHEMA_NAME NOT IN ({}) ORDER BY SCHEMA_NAME",
+        MYSQL_SYSTEM_SCHEMAS
+    );
+    let schemas: Vec<String> = sqlx::query_scalar(&sql).fetch_all(&mut *conn).await?;
+
+    let sql = format!(
+        "SELECT CAST(TABLE_SCHEMA AS CHAR), CAST(TABLE_NAME AS CHAR), CAST(TABLE_TYPE AS CHAR), CAST(TABLE_ROWS AS SIGNED), CAST(COALESCE(TABLE_COMMENT, '') AS CHAR) \
+        FROM information_schema.TABLES WHERE TABLE_SCHEMA NOT IN ({}) ORDER BY TABLE_SCHEMA, TABLE_NAME",
+        MYSQL_SYSTEM_SCHEMAS
+    );
+    let rows: Vec<(String, String, String, Option<i64>, String)> = sqlx::query_as(&sql).fetch_all(&mut *conn).await?;
+    let tables = rows
+        .into_iter()
+        .map(|(schema, name, kind, rows, comment)| {
+            let table_type = table_type(&kind);
+            // 视图的注释固定为 VIEW，行数为空
+            let comment = if table_type == "view" { String::new() } else { comment };
+            (schema, TableSummary { name, table_type, comment, approx_rows: rows })
+        })
+        .collect();
+    Ok(group_schemas(schemas, tables))
+}
+
+async fn mysql_table(conn: &mut MySqlConnection, schema: &str, table: &str) -> Result<TableDetail, Error> {
+    let sql = "SELECT CAST(TABLE_TYPE AS CHAR), CAST(TABLE_ROWS AS SIGNED), CAST(COALESCE(TABLE_COMMENT, '') AS CHAR) \
+        FROM information_schema.TABLES WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?";
+    let row: Option<(String, Option<i64>, String)> = sqlx::query_as(sql).bind(schema).bind(table).fetch_optional(&mut *conn).await?;
+    let Some((kind, approx_rows, comment)) = row else {
+        return Err(Error::InvalidValue(format!("table {}.{} does not exist", schema, table)));
+    };
+    let table_type = table_type(&kind);
+
+    let sql = "SELECT CAST(COLUMN_NAME AS CHAR), CAST(COLUMN_TYPE AS CHAR), CAST(DATA_TYPE AS CHAR), CAST(IS_NULLABLE AS CHAR), \
+        CAST(COLUMN_DEFAULT AS CHAR), CAST(COLUMN_COMMENT AS CHAR) \
+        FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION";
+    let rows: Vec<(String, String, String, String, Option<String>, String)> =
+        sqlx::query_as(sql).bind(schema).bind(table).fetch_all(&mut *conn).await?;
+    let columns = rows
+        .into_iter()
+        .map(|(name, column_type, data_type, nullable, default_value, comment)| ColumnInfo {
+            name,
+            column_type: column_type.to_lowercase(),
+            data_type: data_type.to_lowercase(),
+            nullable: nullable.eq_ignore_ascii_case("YES"),
+            default_value,
+            comment,
+        })
+        .collect();
+
+    let sql = "SELECT CAST(INDEX_NAME AS CHAR), CAST(NON_UNIQUE AS SIGNED), CAST(COLUMN_NAME AS CHAR) \
+        FROM information_schema.STATISTICS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? ORDER BY INDEX_NAME, SEQ_IN_INDEX";
+    let rows: Vec<(String, i64, Option<String>)> = sqlx::query_as(sql).bind(schema).bind(table).fetch_all(&mut *conn).await?;
+    let mut indexes: Vec<IndexInfo> = Vec::new();
+    for (name, non_unique, column) in rows {
+        // 函数索引的列名为空
+        let column = column.unwrap_or_else(|| "(expression)".to_string());
+        match indexes.iter_mut().find(|i| i.name == name) {
+            Some(index) => index.columns.push(column),
+            None => indexes.push(IndexInfo {
+                primary: name == "PRIMARY",
+                unique: non_unique == 0,
+                name,
+                columns: vec![column],
+            }),
+        }
+    }
+    let primary_key = indexes.iter().find(|i| i.primary).map(|i| i.columns.clone()).unwrap_or_default();
+
+    Ok(TableDetail {
+        schema: schema.to_string(),
+        name: table.to_string(),
+        approx_rows: if table_type == "view" { None } else { approx_rows },
+        comment: if table_type == "view" { String::new() } else { comment },
+        table_type,
+        columns,
+        primary_key,
+        indexes,
+        refreshed_at: chrono::Utc::now(),
+    })
+}
+
+async fn pg_catalog(conn: &mut PgConnection) -> Result<Vec<SchemaMeta>, Error> {
+    let sql = format!(
+        "SELECT nspname::text FROM pg_namespace WHERE nspname NOT IN ({}) AND nspname NOT LIKE 'pg_temp_%' AND nspname NOT LIKE 'pg_toast_temp_%' ORDER BY nspname",
+        PG_SYSTEM_SCHEMAS
+    );
+    let schemas: Vec<String> = sqlx::query_scalar(&sql).fetch_all(&mut *conn).await?;
+
+    // reltuples 为 -1 表示从未统计
+    let sql = format!(
+        "SELECT n.nspname::text, c.relname::text, c.relkind::text, CASE WHEN c.reltuples < 0 THEN NULL ELSE c.reltuples::bigint END, \
+        COALESCE(obj_description(c.oid, 'pg_class'), '') \
+        FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace \
+        WHERE c.relkind IN ('r', 'p', 'v', 'm', 'f') AND NOT c.relispartition AND n.nspname NOT IN ({}) \
+        ORDER BY n.nspname, c.relname",
+        PG_SYSTEM_SCHEMAS
+    );
+    let rows: Vec<(String, String, String, Option<i64>, String)> = sqlx::query_as(&sql).fetch_all(&mut *conn).await?;
+    let tables = rows
+        .into_iter()
+        .map(|(schema, name, kind, rows, comment)| {
+            let table_type = table_type(&kind);
+            let approx_rows = if kind == "v" { None } else { rows };
+            (schema, TableSummary { name, table_type, comment, approx_rows })
+        })
+        .collect();
+    Ok(group_schemas(schemas, tables))
+}
+
+async fn pg_table(conn: &mut PgConnection, schema: &str, table: &str) -> Result<TableDetail, Error> {
+    let qualified = format!("{}.{}", postgres::quote_ident(schema), postgres::quote_ident(table));
+    let sql = "SELECT c.relkind::text, CASE WHEN c.reltuples < 0 THEN NULL ELSE c.reltuples::bigint END, \
+        COALESCE(obj_description(c.oid, 'pg_class'), '') FROM pg_class c WHERE c.oid = to_regclass($1)";
+    let row: Option<(String, Option<i64>, String)> = sqlx::query_as(sql).bind(&qualified).fetch_optional(&mut *conn).await?;
+    let Some((kind, approx_rows, comment)) = row else {
+        return Err(Error::InvalidValue(format!("table {}.{} does not exist", schema, table)));
+    };
+
+    let sql = "SELECT a.attname::text, format_type(a.atttypid, a.atttypmod), t.typname::text, NOT a.attnotnull, \
+        pg_get_expr(d.adbin, d.adrelid), COALESCE(col_description(a.attrelid, a.attnum), '') \
+        FROM pg_attribute a JOIN pg_type t ON t.oid = a.atttypid \
+        LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum \
+        WHERE a.attrelid = to_regclass($1) AND a.attnum > 0 AND NOT a.attisdropped ORDER BY a.attnum";
+    let rows: Vec<(String, String, String, bool, Option<String>, String)> = sqlx::query_as(sql).bind(&qualified).fetch_all(&mut *conn).await?;
+    let columns = rows
+        .into_iter()
+        .map(|(name, column_type, data_type, nullable, default_value, comment)| ColumnInfo {
+            name,
+            column_type,
+            data_type,
+            nullable,
+            default_value,
+            comment,
+        })
+        .collect();
+
+    // 表达式索引中的表达式列不在 pg_attribute 中，不会出现在列列表里
+    let sql = "SELECT ic.relname::text, i.indisunique, i.indisprimary, a.attname::text \
+        FROM pg_index i JOIN pg_class ic ON ic.oid = i.indexrelid \
+        CROSS JOIN LATERAL unnest(i.indkey::int2[]) WITH ORDINALITY AS k(attnum, ord) \
+        JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = k.attnum \
+        WHERE i.indrelid = to_regclass($1) ORDER BY ic.relname, k.ord";
+    let rows: Vec<(String, bool, bool, String)> = sqlx::query_as(sql).bind(&qualified).fetch_all(&mut *conn).await?;
+    let mut indexes: Vec<IndexInfo> = Vec::new();
+    for (name, unique, primary, column) in rows {
+        match indexes.iter_mut().find(|i| i.name == name) {
+            Some(index) => index.columns.push(column),
+            None => indexes.push(IndexInfo { name, columns: vec![column], unique, primary }),
+        }
+    }
+    let primary_key = indexes.iter().find(|i| i.primary).map(|i| i.columns.clone()).unwrap_or_default();
+
+    Ok(TableDetail {
+        schema: schema.to_string(),
+        name: table.to_string(),
+        table_type: table_type(&kind),
+        comment,
+        approx_rows: if kind == "v" { None } else { approx_rows },
+        columns,
+        primary_key,
+        indexes,
+        refreshed_at: chrono::Utc::now(),
+    })
+}
@@ -1,21 +1,67 @@
 pub mod project;
 pub mod resource;
 pub mod datasource;
+pub mod collection;
+pub mod probe;
+pub mod health_check;
+pub mod provision;
+pub mod task_registry;
+pub mod query_registry;
+pub mod collect;
+pub mod schedule;
+pub mod scheduler;
+pub mod task_run;
+pub mod run_log;
+pub mod pipeline;
+pub mod dag;
+pub mod transform;
+pub mod materialize;
+pub mod query;
+pub mod console;
+pub mod metadata;
+pub mod catalog;
+pub mod catalog_sync;
+pub mod lineage;
+pub mod user;
+pub mod auth;
 
 use async_trait::async_trait;
-use crate::models::project::Project;
+use crate::models::project::{Project, ProjectDeleteQuery, ProjectMember, ProjectMemberForm, ProjectRole};
 use crate::models::error::Error;
 use crate::models::web::PageQuery;
 use crate::models::resource::{Resource, ResourceReadOnly, ResourceCreateUpdate};
-use crate::models::datasource::{DataSource, DataSourceReadOnly, DataSourceCreateUpdate};
+use crate::models::datasource::{DataSource, DataSourceReadOnly, DataSourceCreateUpdate, PingDiagnostic};
+use crate::models::collection::{CollectionTask, CollectionTaskReadOnly, CollectionTaskCreateUpdate, CollectionWatermark, CollectionWatermarkReadOnly, CollectionCheckpoint, CollectionCheckpointReadOnly};
+use crate::models::task_run::{TaskRun, TaskRunReadOnly, TaskRunQuery, TaskRunLog, TaskRunLogQuery, TaskRunLogPage, TaskType};
+use crate::models::schedule::{TaskScheduleReadOnly, TaskScheduleCreateUpdate, SchedulePreviewQuery};
+use crate::models::pipeline::{PipelineReadOnly, PipelineCreateUpdate, PipelineNodeRun, PipelineRunDetail};
+use crate::models::transform::{TransformTask, TransformTaskReadOnly, TransformTaskCreateUpdate, TransformCheck};
+use crate::models::query::{QueryHistory, QueryRequest, QueryResult};
+use crate::models::metadata::{DataSourceCatalog, TableDetail, TableDetailQuery};
+use crate::models::catalog::{CatalogDrift, CatalogSearchHit, CatalogSearchQuery, CatalogSnapshot, CatalogSnapshotDetail, CatalogSourceType, SnapshotResult};
+use crate::models::lineage::{LineageDirection, LineageEdge, LineageGraph, LineageQuery};
+use crate::models::user::{PasswordChangeRequest, User, UserForm, UserOption};
+use crate::models::auth::IssuedRefreshToken;
 
 #[async_trait]
 pub trait ProjectService: Send {
-    async fn add_project(&self, project: Project) -> Result<String, Error>;
+    async fn add_project(&self, operator: String, project: Project) -> Result<String, Error>;
     async fn edit_project(&self, project: Project) -> Result<(), Error>;
-    async fn del_project(&self, code: String) -> Result<(), Error>;
+    async fn del_project(&self, code: String, params: ProjectDeleteQuery) -> Result<(), Error>;
     async fn get_project(&self, code: String) -> Result<Project, Error>;
-    async fn list_project(&self, params: PageQuery) -> Result<Vec<Project>, Error>;
+    async fn list_project(&self, operator: String, params: PageQuery) -> Result<Vec<Project>, Error>;
+    async fn list_all_project(&self) -> Result<Vec<Project>, Error>;
+    async fn provision_project(&self, code: String) -> Result<(), Error>;
+    async fn retry_provision(&self, code: String) -> Result<(), Error>;
+    // 按记录的删除方式删除项目库及项目记录，中断后可重复执行
+    async fn teardown_project(&self, code: String) -> Result<(), Error>;
+    async fn purge_expired_archive(&self) -> Result<usize, Error>;
+    // 用户在项目中的有效角色，系统管理员视为所有项目的负责人
+    async fn get_role(&self, user_id: String, code: String) -> Result<Option<ProjectRole>, Error>;
+    async fn switch_project(&self, code: String) -> Result<Project, Error>;
+    async fn list_member(&self, code: String) -> Result<Vec<ProjectMember>, Error>;
+    async fn save_member(&self, code: String, form: ProjectMemberForm) -> Result<(), Error>;
+    async fn del_member(&self, code: String, user_id: String) -> Result<(), Error>;
 }
 
 #[async_trait]
@@ -31,8 +77,139 @@ pub trait ResourceService: Send {
 pub trait DataSourceService: Send {
     async fn add_datasource(&self, project_code: String, datasource: DataSourceCreateUpdate) -> Result<String, Error>;
     async fn edit_datasource(&self, project_code: String, datasource: DataSourceCreateUpdate) -> Result<(), Error>;
-    async fn ping_datasource(&self, project_code: String, datasource: DataSourceCreateUpdate) -> Result<(), Error>;
+    async fn ping_datasource(&self, project_code: String, datasource: DataSourceCreateUpdate) -> Result<PingDiagnostic, Error>;
     async fn del_datasource(&self, project_code: String, id: String) -> Result<(), Error>;
     async fn get_datasource(&self, project_code: String, id: String) -> Result<DataSourceReadOnly, Error>;
     async fn list_datasource(&self, project_code: String, params: PageQuery) -> Result<Vec<DataSourceReadOnly>, Error>;
-}
\ No newline at end of file
+    async fn recheck_datasource(&self, project_code: String, id: String) -> Result<DataSourceReadOnly, Error>;
+    async fn check_project_datasource(&self, project_code: String) -> Result<usize, Error>;
+    async fn get_catalog(&self, project_code: String, id: String, refresh: bool) -> Result<DataSourceCatalog, Error>;
+    async fn get_table_detail(&self, project_code: String, id: String, params: TableDetailQuery) -> Result<TableDetail, Error>;
+}
+
+#[async_trait]
+pub trait CollectionService: Send {
+    async fn add_collection(&self, project_code: String, task: CollectionTaskCreateUpdate) -> Result<String, Error>;
+    async fn edit_collection(&self, project_code: String, task: CollectionTaskCreateUpdate) -> Result<(), Error>;
+    async fn del_collection(&self, project_code: String, id: String) -> Result<(), Error>;
+    async fn get_collection(&self, project_code: String, id: String) -> Result<CollectionTaskReadOnly, Error>;
+    async fn list_collection(&self, project_code: String, params: PageQuery) -> Result<Vec<CollectionTaskReadOnly>, Error>;
+    async fn run_collection(&self, project_code: String, id: String) -> Result<String, Error>;
+    async fn finish_collection_run(&self, project_code: String, run: TaskRun) -> Result<(), Error>;
+    async fn list_collection_run(&self, project_code: String, id: String) -> Result<Vec<TaskRunReadOnly>, Error>;
+    async fn list_watermark(&self, project_code: String, id: String) -> Result<Vec<CollectionWatermark>, Error>;
+    async fn list_collection_watermark(&self, project_code: String, id: String) -> Result<Vec<CollectionWatermarkReadOnly>, Error>;
+    async fn save_watermark(&self, project_code: String, watermark: CollectionWatermark) -> Result<(), Error>;
+    async fn reset_watermark(&self, project_code: String, id: String, source_table: Option<String>) -> Result<(), Error>;
+    async fn stop_collection(&self, project_code: String, id: String) -> Result<(), Error>;
+    async fn get_checkpoint(&self, project_code: String, id: String) -> Result<Option<CollectionCheckpoint>, Error>;
+    async fn get_collection_checkpoint(&self, project_code: String, id: String) -> Result<Option<CollectionCheckpointReadOnly>, Error>;
+    async fn save_checkpoint(&self, project_code: String, checkpoint: CollectionCheckpoint) -> Result<(), Error>;
+    async fn reset_collection_checkpoint(&self, project_code: String, id: String) -> Result<(), Error>;
+    async fn resume_stream_collection(&self, project_code: String) -> Result<usize, Error>;
+}
+
+#[async_trait]
+pub trait ScheduleService: Send {
+    async fn save_schedule(&self, project_code: String, task_id: String, schedule: TaskScheduleCreateUpdate) -> Result<(), Error>;
+    async fn del_schedule(&self, project_code: String, task_id: String) -> Result<(), Error>;
+    async fn get_schedule(&self, project_code: String, task_id: String) -> Result<Option<TaskScheduleReadOnly>, Error>;
+    async fn list_schedule(&self, project_code: String) -> Result<Vec<TaskScheduleReadOnly>, Error>;
+    async fn preview_schedule(&self, query: SchedulePreviewQuery) -> Result<Vec<String>, Error>;
+    async fn fire_due_schedule(&self, project_code: String) -> Result<usize, Error>;
+}
+
+#[async_trait]
+pub trait TaskRunService: Send {
+    async fn list_run(&self, project_code: String, query: TaskRunQuery) -> Result<Vec<TaskRunReadOnly>, Error>;
+    async fn get_run(&self, project_code: String, id: String) -> Result<TaskRunReadOnly, Error>;
+    async fn get_run_log(&self, project_code: String, id: String, query: TaskRunLogQuery) -> Result<TaskRunLogPage, Error>;
+    async fn cancel_run(&self, project_code: String, id: String) -> Result<(), Error>;
+    async fn add_run_log(&self, project_code: String, logs: Vec<TaskRunLog>) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait PipelineService: Send {
+    async fn add_pipeline(&self, project_code: String, pipeline: PipelineCreateUpdate) -> Result<String, Error>;
+    async fn edit_pipeline(&self, project_code: String, pipeline: PipelineCreateUpdate) -> Result<(), Error>;
+    async fn del_pipeline(&self, project_code: String, id: String) -> Result<(), Error>;
+    async fn get_pipeline(&self, project_code: String, id: String) -> Result<PipelineReadOnly, Error>;
+    async fn list_pipeline(&self, project_code: String, params: PageQuery) -> Result<Vec<PipelineReadOnly>, Error>;
+    async fn validate_pipeline(&self, project_code: String, pipeline: PipelineCreateUpdate) -> Result<Vec<String>, Error>;
+    async fn run_pipeline(&self, project_code: String, id: String) -> Result<String, Error>;
+    async fn stop_pipeline(&self, project_code: String, id: String) -> Result<(), Error>;
+    async fn list_pipeline_run(&self, project_code: String, id: String) -> Result<Vec<TaskRunReadOnly>, Error>;
+    async fn get_pipeline_run(&self, project_code: String, run_id: String) -> Result<PipelineRunDetail, Error>;
+    async fn save_node_run(&self, project_code: String, node_run: PipelineNodeRun) -> Result<(), Error>;
+    async fn finish_pipeline_run(&self, project_code: String, run: TaskRun) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait TransformService: Send {
+    async fn add_transform(&self, project_code: String, task: TransformTaskCreateUpdate) -> Result<String, Error>;
+    async fn edit_transform(&self, project_code: String, task: TransformTaskCreateUpdate) -> Result<(), Error>;
+    async fn del_transform(&self, project_code: String, id: String) -> Result<(), Error>;
+    async fn get_transform(&self, project_code: String, id: String) -> Result<TransformTaskReadOnly, Error>;
+    async fn list_transform(&self, project_code: String, params: PageQuery) -> Result<Vec<TransformTaskReadOnly>, Error>;
+    async fn check_transform(&self, project_code: String, task: TransformTaskCreateUpdate) -> Result<TransformCheck, Error>;
+    async fn run_transform(&self, project_code: String, id: String, biz_date: Option<String>) -> Result<String, Error>;
+    async fn stop_transform(&self, project_code: String, id: String) -> Result<(), Error>;
+    async fn list_transform_run(&self, project_code: String, id: String) -> Result<Vec<TaskRunReadOnly>, Error>;
+    async fn finish_transform_run(&self, project_code: String, run: TaskRun) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait QueryService: Send {
+    async fn execute_query(&self, project_code: String, user_name: String, request: QueryRequest) -> Result<QueryResult, Error>;
+    // 生成查询ID，前端在执行前申请，执行中可据此取消
+    async fn reserve_query(&self, project_code: String, user_name: String) -> Result<String, Error>;
+    async fn cancel_query(&self, project_code: String, user_name: String, query_id: String) -> Result<(), Error>;
+    async fn list_query_history(&self, project_code: String, user_name: String, params: PageQuery) -> Result<Vec<QueryHistory>, Error>;
+    async fn clear_query_history(&self, project_code: String, user_name: String) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait CatalogService: Send {
+    async fn snapshot_source(&self, project_code: String, source_type: CatalogSourceType, source_id: String) -> Result<SnapshotResult, Error>;
+    async fn snapshot_project(&self, project_code: String) -> Result<usize, Error>;
+    async fn list_latest_snapshot(&self, project_code: String) -> Result<Vec<CatalogSnapshot>, Error>;
+    async fn list_snapshot(&self, project_code: String, source_type: CatalogSourceType, source_id: String, params: PageQuery) -> Result<Vec<CatalogSnapshot>, Error>;
+    async fn get_snapshot(&self, project_code: String, id: String) -> Result<CatalogSnapshotDetail, Error>;
+    async fn list_drift(&self, project_code: String, source_type: CatalogSourceType, source_id: String, params: PageQuery) -> Result<Vec<CatalogDrift>, Error>;
+    async fn search_catalog(&self, project_code: String, query: CatalogSearchQuery) -> Result<Vec<CatalogSearchHit>, Error>;
+}
+
+#[async_trait]
+pub trait LineageService: Send {
+    async fn record_collection(&self, project_code: String, task: CollectionTask) -> Result<usize, Error>;
+    async fn record_transform(&self, project_code: String, task: TransformTask) -> Result<usize, Error>;
+    async fn remove_task_lineage(&self, project_code: String, task_type: TaskType, task_id: String) -> Result<(), Error>;
+    async fn rebuild_project(&self, project_code: String) -> Result<usize, Error>;
+    async fn list_task_lineage(&self, project_code: String, task_type: TaskType, task_id: String) -> Result<Vec<LineageEdge>, Error>;
+    async fn trace_lineage(&self, project_code: String, query: LineageQuery, direction: LineageDirection) -> Result<LineageGraph, Error>;
+}
+
+#[async_trait]
+pub trait UserService: Send {
+    async fn authenticate(&self, username: String, password: String) -> Result<User, Error>;
+    async fn ensure_admin(&self) -> Result<(), Error>;
+    async fn get_current_user(&self, user_id: String) -> Result<User, Error>;
+    async fn add_user(&self, operator: String, form: UserForm) -> Result<String, Error>;
+    async fn edit_user(&self, operator: String, form: UserForm) -> Result<(), Error>;
+    async fn del_user(&self, operator: String, id: String) -> Result<(), Error>;
+    async fn list_user(&self, operator: String, params: PageQuery) -> Result<Vec<User>, Error>;
+    async fn list_user_option(&self) -> Result<Vec<UserOption>, Error>;
+    async fn unlock_user(&self, operator: String, id: String) -> Result<(), Error>;
+    async fn change_password(&self, user_id: String, request: PasswordChangeRequest) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait AuthService: Send {
+    async fn create_refresh_token(&self, user_id: String, project_code: String, previous: Option<String>) -> Result<String, Error>;
+    async fn rotate_refresh_token(&self, token: String) -> Result<IssuedRefreshToken, Error>;
+    async fn revoke_refresh_token(&self, token: String) -> Result<(), Error>;
+    async fn revoke_access_token(&self, jti: String, user_id: String, expires_at: chrono::DateTime<chrono::Utc>) -> Result<(), Error>;
+    async fn revoke_user_session(&self, user_id: String) -> Result<(), Error>;
+    async fn is_revoked(&self, jti: String, user_id: String) -> Result<bool, Error>;
+    async fn purge_expired(&self) -> Result<u64, Error>;
+}
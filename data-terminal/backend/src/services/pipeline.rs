@@ -0,0 +1,164 @@
+use crate::repositories::{CollectionRepo, PipelineRepo, TaskRunRepo, TransformRepo};
+use crate::models::{Error, Validator};
+use crate::models::web::PageQuery;
+use crate::models::collection::CollectType;
+use crate::models::pipeline::{Pipeline, PipelineReadOnly, PipelineCreateUpdate, PipelineNodeRun, PipelineNodeRunReadOnly, PipelineRunDetail};
+use crate::models::task_run::{TaskRun, TaskRunReadOnly, TaskType};
+use shaku::Provider;
+use async_trait::async_trait;
+use super::PipelineService;
+use super::{dag, provision, task_registry};
+use uuid::Uuid;
+
+#[derive(Provider)]
+#[shaku(interface = PipelineService)]
+pub struct PipelineServiceImpl {
+    #[shaku(provide)]
+    repo: Box<dyn PipelineRepo>,
+    #[shaku(provide)]
+    collection_repo: Box<dyn CollectionRepo>,
+    #[shaku(provide)]
+    run_repo: Box<dyn TaskRunRepo>,
+    #[shaku(provide)]
+    transform_repo: Box<dyn TransformRepo>,
+}
+
+#[async_trait]
+impl PipelineService for PipelineServiceImpl {
+    async fn add_pipeline(&self, project_code: String, pipeline: PipelineCreateUpdate) -> Result<String, Error> {
+        let mut pipeline = Pipeline::from(pipeline);
+        pipeline.id = Uuid::new_v4().to_string();
+        pipeline.validate()?;
+        self.check_nodes(project_code.clone(), &pipeline).await?;
+        self.repo.add_pipeline(project_code, pipeline).await
+    }
+
+    async fn edit_pipeline(&self, project_code: String, pipeline: PipelineCreateUpdate) -> Result<(), Error> {
+        let existing = self.repo.get_pipeline(project_code.clone(), pipeline.id.clone()).await?;
+        let mut updated = Pipeline::from(pipeline);
+        updated.created_at = existing.created_at;
+        updated.validate()?;
+        self.check_nodes(project_code.clone(), &updated).await?;
+        self.repo.edit_pipeline(project_code, updated).await
+    }
+
+    async fn del_pipeline(&self, project_code: String, id: String) -> Result<(), Error> {
+        let pipeline = self.repo.get_pipeline(project_code.clone(), id).await?;
+        let _guard = task_registry::try_register(&project_code, &pipeline.id)
+            .ok_or_else(|| Error::InvalidValue(format!("pipeline {} is running, stop it first", pipeline.id)))?;
+        self.repo.del_pipeline(project_code, pipeline.id).await
+    }
+
+    async fn get_pipeline(&self, project_code: String, id: String) -> Result<PipelineReadOnly, Error> {
+        let pipeline = self.repo.get_pipeline(project_code, id).await?;
+        Ok(PipelineReadOnly::from(pipeline))
+    }
+
+    async fn list_pipeline(&self, project_code: String, params: PageQuery) -> Result<Vec<PipelineReadOnly>, Error> {
+        let pipelines = self.repo.list_pipeline(project_code, params).await?;
+        Ok(pipelines.into_iter().map(PipelineReadOnly::from).collect())
+    }
+
+    // 校验但不保存，返回节点的执行顺序
+    async fn validate_pipeline(&self, project_code: String, pipeline: PipelineCreateUpdate) -> Result<Vec<String>, Error> {
+        let mut pipeline = Pipeline::from(pipeline);
+        if pipeline.id.is_empty() {
+            pipeline.id = Uuid::new_v4().to_string();
+        }
+        pipeline.validate()?;
+        self.check_nodes(project_code, &pipeline).await?;
+        let order = pipeline.topo_order()?;
+        Ok(order.into_iter().map(|i| pipeline.nodes[i].id.clone()).collect())
+    }
+
+    async fn run_pipeline(&self, project_code: String, id: String) -> Result<String, Error> {
+        provision::check_project_active(&project_code).await?;
+        let pipeline = self.repo.get_pipeline(project_code.clone(), id).await?;
+        pipeline.validate()?;
+        self.check_nodes(project_code.clone(), &pipeline).await?;
+
+        let guard = task_registry::try_register(&project_code, &pipeline.id)
+            .ok_or_else(|| Error::InvalidValue(format!("pipeline {} is already running", pipeline.id)))?;
+        let run = TaskRun::start(pipeline.id.clone(), TaskType::Pipeline);
+        let run_id = self.run_repo.add_run(project_code.clone(), run.clone()).await?;
+
+        dag::spawn_run(project_code, pipeline, run, guard);
+        Ok(run_id)
+    }
+
+    async fn stop_pipeline(&self, project_code: String, id: String) -> Result<(), Error> {
+        let pipeline = self.repo.get_pipeline(project_code.clone(), id).await?;
+        if !task_registry::cancel(&project_code, &pipeline.id) {
+            return Err(Error::InvalidValue(format!("pipeline {} is not running", pipeline.id)));
+        }
+        Ok(())
+    }
+
+    async fn list_pipeline_run(&self, project_code: String, id: String) -> Result<Vec<TaskRunReadOnly>, Error> {
+        let runs = self.run_repo.list_run_by_task(project_code, id, 50).await?;
+        Ok(runs.into_iter().map(TaskRunReadOnly::from).collect())
+    }
+
+    async fn get_pipeline_run(&self, project_code: String, run_id: String) -> Result<PipelineRunDetail, Error> {
+        let run = self.run_repo.get_run(project_code.clone(), run_id).await?;
+        if run.task_type != TaskType::Pipeline {
+            return Err(Error::InvalidValue(format!("run {} is not a pipeline run", run.id)));
+        }
+        let nodes = self.repo.list_node_run(project_code.clone(), run.id.clone()).await?;
+        // 流水线已删除时不影响查看历史运行
+        let task_name = match self.repo.get_pipeline(project_code, run.task_id.clone()).await {
+            Ok(pipeline) => pipeline.name,
+            Err(Error::NotFound) => String::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(PipelineRunDetail {
+            run: TaskRunReadOnly { task_name, ..TaskRunReadOnly::from(run) },
+            nodes: nodes.into_iter().map(PipelineNodeRunReadOnly::from).collect(),
+        })
+    }
+
+    async fn save_node_run(&self, project_code: String, node_run: PipelineNodeRun) -> Result<(), Error> {
+        self.repo.save_node_run(project_code, node_run).await
+    }
+
+    async fn finish_pipeline_run(&self, project_code: String, run: TaskRun) -> Result<(), Error> {
+        self.run_repo.finish_run(project_code, run).await
+    }
+}
+
+impl PipelineServiceImpl {
+    // 校验节点引用的任务存在且可以在流水线中运行
+    async fn check_nodes(&self, project_code: String, pipeline: &Pipeline) -> Result<(), Error> {
+        for node in &pipeline.nodes {
+            match node.task_type {
+                TaskType::Collection => {
+                    let task = match self.collection_repo.get_collection(project_code.clone(), node.task_id.clone()).await {
+                        Ok(task) => task,
+                        Err(Error::NotFound) => {
+                            return Err(Error::InvalidValue(format!("collection task {} of node {} does not exist", node.task_id, node.id)));
+                        }
+                        Err(e) => return Err(e),
+                    };
+                    // 实时采集不会结束，下游永远无法执行
+                    if task.collect_type == CollectType::Stream {
+                        return Err(Error::InvalidValue(format!("stream collection of node {} runs continuously and cannot be part of a pipeline", node.id)));
+                    }
+                }
+                TaskType::Transform => match self.transform_repo.get_transform(project_code.clone(), node.task_id.clone()).await {
+                    Ok(_) => {}
+                    Err(Error::NotFound) => {
+                        return Err(Error::InvalidValue(format!("transform task {} of node {} does not exist", node.task_id, node.id)));
+                    }
+                    Err(e) => return Err(e),
+                },
+                TaskType::Pipeline => {
+                    return Err(Error::InvalidValue(format!("node {} cannot reference another pipeline", node.id)));
+                }
+                other => {
+                    return Err(Error::InvalidValue(format!("{} task of node {} cannot run in pipeline yet", other, node.id)));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,450 @@
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use sqlx::mysql::MySqlConnectOptions;
+use sqlx::postgres::PgConnectOptions;
+use sqlx::{ConnectOptions, Connection};
+
+use crate::models::datasource::{
+    ApiBodyType, ApiHttpMethod, DataSource, DataSourceType, MysqlConnectionConfig, PingDiagnostic,
+    PingErrorKind, PostgresConnectionConfig, QueryApiConnectionConfig, SubscribeApiConnectionConfig,
+};
+use crate::utils::config::Setting;
+
+// 单次探测的超时时间
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 按数据源类型探测连通性，探测失败不会返回 Err，而是体现在诊断结果中
+pub async fn probe_datasource(datasource: &DataSource) -> PingDiagnostic {
+    match datasource.datasource_type {
+        DataSourceType::Mysql => probe_mysql(&datasource.connection_config).await,
+        DataSourceType::Postgres => probe_postgres(&datasource.connection_config).await,
+        DataSourceType::QueryApi => probe_query_api(&datasource.connection_config, true).await,
+        DataSourceType::SubscribeApi => probe_subscribe_api(&datasource.connection_config).await,
+    }
+}
+
+/// 后台健康检查使用的探测，不能对外部系统产生副作用：查询 API 不携带请求体，非 GET 接口只发送 HEAD
+pub async fn check_datasource(datasource: &DataSource) -> PingDiagnostic {
+    match datasource.datasource_type {
+        DataSourceType::QueryApi => probe_query_api(&datasource.connection_config, false).await,
+        _ => probe_datasource(datasource).await,
+    }
+}
+
+async fn probe_mysql(config: &Value) -> PingDiagnostic {
+    let cfg = match serde_json::from_value::<MysqlConnectionConfig>(config.clone()) {
+        Ok(cfg) => cfg,
+        Err(e) => return PingDiagnostic::failed(PingErrorKind::InvalidConfig, 0, format!("invalid mysql connection config: {}", e)),
+    };
+    if cfg.host.trim().is_empty() {
+        return PingDiagnostic::failed(PingErrorKind::InvalidConfig, 0, "host is empty");
+    }
+
+    let options = MySqlConnectOptions::new()
+        .host(&cfg.host)
+        .port(cfg.port)
+        .username(&cfg.username)
+        .password(&cfg.password)
+        .database(&cfg.database);
+
+    let started = Instant::now();
+    let result = tokio::time::timeout(PROBE_TIMEOUT, async {
+        let mut conn = options.connect().await?;
+        sqlx::query("SELECT 1").execute(&mut conn).await?;
+        let version: String = sqlx::query_scalar("SELECT VERSION()").fetch_one(&mut conn).await?;
+        let _ = conn.close().await;
+        Ok::<String, sqlx::Error>(version)
+    })
+    .await;
+
+    finish_sql_probe(result, started)
+}
+
+async fn probe_postgres(config: &Value) -> PingDiagnostic {
+    let cfg = match serde_json::from_value::<PostgresConnectionConfig>(config.clone()) {
+        Ok(cfg) => cfg,
+        Err(e) => return PingDiagnostic::failed(PingErrorKind::InvalidConfig, 0, format!("invalid postgres connection config: {}", e)),
+    };
+    if cfg.host.trim().is_empty() {
+        return PingDiagnostic::failed(PingErrorKind::InvalidConfig, 0, "host is empty");
+    }
+
+    let options = PgConnectOptions::new()
+        .host(&cfg.host)
+        .port(cfg.port)
+        .username(&cfg.username)
+        .password(&cfg.password)
+        .database(&cfg.database);
+
+    let started = Instant::now();
+    let catalog = cfg.catalog.clone();
+    let result = tokio::time::timeout(PROBE_TIMEOUT, async {
+        let mut conn = options.connect().await?;
+        sqlx::query("SELECT 1").execute(&mut conn).await?;
+        let version: String = sqlx::query_scalar("SHOW server_version").fetch_one(&mut conn).await?;
+        if !catalog.is_empty() {
+            let exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_namespace WHERE nspname = $1)")
+                .bind(&catalog)
+                .fetch_one(&mut conn)
+                .await?;
+            if !exists {
+                let _ = conn.close().await;
+                return Err(sqlx::Error::Configuration(format!("schema \"{}\" does not exist", catalog).into()));
+            }
+        }
+        let _ = conn.close().await;
+        Ok::<String, sqlx::Error>(version)
+    })
+    .await;
+
+    finish_sql_probe(result, started)
+}
+
+fn finish_sql_probe(result: Result<Result<String, sqlx::Error>, tokio::time::error::Elapsed>, started: Instant) -> PingDiagnostic {
+    let latency_ms = started.elapsed().as_millis() as u64;
+    match result {
+        Ok(Ok(version)) => PingDiagnostic::ok(latency_ms, Some(version), "connection succeeded"),
+        Ok(Err(e)) => {
+            let kind = match &e {
+                // 探测时 Configuration 只会来自于 schema 校验
+                sqlx::Error::Configuration(_) => PingErrorKind::Database,
+                _ => classify_sqlx_error(&e),
+            };
+            PingDiagnostic::failed(kind, latency_ms, e.to_string())
+        }
+        Err(_) => PingDiagnostic::failed(
+            PingErrorKind::Timeout,
+            latency_ms,
+            format!("connection timed out after {}s", PROBE_TIMEOUT.as_secs()),
+        ),
+    }
+}
+
+fn classify_sqlx_error(e: &sqlx::Error) -> PingErrorKind {
+    match e {
+        sqlx::Error::Tls(_) => PingErrorKind::Tls,
+        sqlx::Error::PoolTimedOut => PingErrorKind::Timeout,
+        sqlx::Error::Configuration(_) => PingErrorKind::InvalidConfig,
+        sqlx::Error::Io(io) => classify_io_error(io),
+        sqlx::Error::Database(db) => match db.code().as_deref() {
+            // MySQL 1045 与 PostgreSQL 28P01 的 SQLSTATE
+            Some("28000") | Some("28P01") => PingErrorKind::Auth,
+            _ => PingErrorKind::Database,
+        },
+        _ => PingErrorKind::Unknown,
+    }
+}
+
+fn classify_io_error(e: &std::io::Error) -> PingErrorKind {
+    match e.kind() {
+        std::io::ErrorKind::TimedOut => PingErrorKind::Timeout,
+        std::io::ErrorKind::ConnectionRefused
+        | std::io::ErrorKind::ConnectionReset
+        | std::io::ErrorKind::ConnectionAborted
+        | std::io::ErrorKind::NotConnected => PingErrorKind::Connection,
+        _ if is_dns_message(&e.to_string()) => PingErrorKind::Dns,
+        _ => PingErrorKind::Connection,
+    }
+}
+
+fn is_dns_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("lookup") || message.contains("resolve") || message.contains("name or service not known")
+}
+
+// full 为 false 时只做无副作用的探测，由后台健康检查使用
+async fn probe_query_api(config: &Value, full: bool) -> PingDiagnostic {
+    let cfg = match serde_json::from_value::<QueryApiConnectionConfig>(config.clone()) {
+        Ok(cfg) => cfg,
+        Err(e) => return PingDiagnostic::failed(PingErrorKind::InvalidConfig, 0, format!("invalid query api config: {}", e)),
+    };
+    if cfg.domain.trim().is_empty() {
+        return PingDiagnostic::failed(PingErrorKind::InvalidConfig, 0, "domain is empty");
+    }
+    let schema = if cfg.response_schema.trim().is_empty() {
+        None
+    } else {
+        match serde_json::from_str::<Value>(&cfg.response_schema) {
+            Ok(schema) => Some(schema),
+            Err(e) => return PingDiagnostic::failed(PingErrorKind::InvalidConfig, 0, format!("invalid response schema: {}", e)),
+        }
+    };
+
+    let url = build_api_url(&cfg.domain, &cfg.path, &cfg.path_params);
+    let (client, parsed) = match guarded_client(&url, PROBE_TIMEOUT).await {
+        Ok(guarded) => guarded,
+        Err(diagnostic) => return diagnostic,
+    };
+    let method = match cfg.method {
+        ApiHttpMethod::Get => reqwest::Method::GET,
+        _ if !full => reqwest::Method::HEAD,
+        ApiHttpMethod::Post => reqwest::Method::POST,
+        ApiHttpMethod::Put => reqwest::Method::PUT,
+        ApiHttpMethod::Delete => reqwest::Method::DELETE,
+        ApiHttpMethod::Patch => reqwest::Method::PATCH,
+    };
+    let head_only = method == reqwest::Method::HEAD;
+    let mut request = client.request(method, parsed).query(&cfg.query_params);
+    for (key, value) in &cfg.headers {
+        request = request.header(key, value);
+    }
+    request = match cfg.body_type {
+        _ if !full => request,
+        ApiBodyType::None => request,
+        ApiBodyType::Json => request
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(cfg.body_content.clone()),
+        ApiBodyType::FormData => {
+            let form = serde_json::from_str::<std::collections::HashMap<String, String>>(&cfg.body_content).unwrap_or_default();
+            request.form(&form)
+        }
+    };
+
+    let started = Instant::now();
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            let latency_ms = started.elapsed().as_millis() as u64;
+            return PingDiagnostic::failed(classify_reqwest_error(&e), latency_ms, format!("{}: {}", url, error_chain(&e)));
+        }
+    };
+    let status = response.status();
+    let server = response
+        .headers()
+        .get(reqwest::header::SERVER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+    let body = response.text().await.unwrap_or_default();
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    // HEAD 只确认接口可达，接口不支持 HEAD 时也会返回 4xx，不校验状态码与响应结构
+    if head_only {
+        if status.is_server_error() {
+            return PingDiagnostic::failed(PingErrorKind::Http, latency_ms, format!("{} returned HTTP {}", url, status));
+        }
+        return PingDiagnostic::ok(latency_ms, server, format!("HEAD returned HTTP {}", status));
+    }
+
+    if !status.is_success() {
+        return PingDiagnostic::failed(PingErrorKind::Http, latency_ms, format!("{} returned HTTP {}", url, status));
+    }
+
+    if let Some(schema) = schema {
+        let value = match serde_json::from_str::<Value>(&body) {
+            Ok(value) => value,
+            Err(e) => return PingDiagnostic::failed(PingErrorKind::Schema, latency_ms, format!("response is not valid JSON: {}", e)),
+        };
+        if let Err(msg) = check_schema(&schema, &value, "$") {
+            return PingDiagnostic::failed(PingErrorKind::Schema, latency_ms, msg);
+        }
+    }
+
+    PingDiagnostic::ok(latency_ms, server, format!("HTTP {}", status))
+}
+
+async fn probe_subscribe_api(config: &Value) -> PingDiagnostic {
+    let cfg = match serde_json::from_value::<SubscribeApiConnectionConfig>(config.clone()) {
+        Ok(cfg) => cfg,
+        Err(e) => return PingDiagnostic::failed(PingErrorKind::InvalidConfig, 0, format!("invalid subscribe api config: {}", e)),
+    };
+    if cfg.path.trim().is_empty() {
+        return PingDiagnostic::failed(PingErrorKind::InvalidConfig, 0, "path is empty");
+    }
+
+    // 订阅 API 由外部推送数据，未配置回调地址时只校验配置本身
+    if cfg.webhook_url.trim().is_empty() {
+        return PingDiagnostic::ok(0, None, "subscribe api is passive, no webhook to probe");
+    }
+
+    let timeout = if cfg.timeout_seconds == 0 { PROBE_TIMEOUT } else { Duration::from_secs(cfg.timeout_seconds as u64) };
+    let (client, parsed) = match guarded_client(&cfg.webhook_url, timeout).await {
+        Ok(guarded) => guarded,
+        Err(diagnostic) => return diagnostic,
+    };
+
+    let started = Instant::now();
+    match client.head(parsed).send().await {
+        // 回调地址可达即可，不要求 HEAD 返回 2xx
+        Ok(response) if !response.status().is_server_error() => {
+            PingDiagnostic::ok(started.elapsed().as_millis() as u64, None, format!("webhook returned HTTP {}", response.status()))
+        }
+        Ok(response) => PingDiagnostic::failed(
+            PingErrorKind::Http,
+            started.elapsed().as_millis() as u64,
+            format!("webhook returned HTTP {}", response.status()),
+        ),
+        Err(e) => PingDiagnostic::failed(classify_reqwest_error(&e), started.elapsed().as_millis() as u64, error_chain(&e)),
+    }
+}
+
+/// 校验探测地址并构建客户端：只允许 http/https，不跟随重定向，未开启 probe.allow_private_network 时拒绝内网地址
+async fn guarded_client(url: &str, timeout: Duration) -> Result<(reqwest::Client, reqwest::Url), PingDiagnostic> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| PingDiagnostic::failed(PingErrorKind::InvalidConfig, 0, format!("invalid url {}: {}", url, e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(PingDiagnostic::failed(PingErrorKind::InvalidConfig, 0, format!("unsupported url scheme: {}", parsed.scheme())));
+    }
+    let Some(host) = parsed.host_str().map(|h| h.trim_start_matches('[').trim_end_matches(']').to_string()) else {
+        return Err(PingDiagnostic::failed(PingErrorKind::InvalidConfig, 0, format!("url {} has no host", url)));
+    };
+    let port = parsed.port_or_known_default().unwrap_or(80);
+
+    // 重定向可能指向内网地址，不跟随
+    let mut builder = reqwest::Client::builder().timeout(timeout).redirect(reqwest::redirect::Policy::none());
+    if !Setting::get().probe.allow_private_network {
+        let addrs: Vec<SocketAddr> = match tokio::net::lookup_host((host.as_str(), port)).await {
+            Ok(addrs) => addrs.collect(),
+            Err(e) => return Err(PingDiagnostic::failed(PingErrorKind::Dns, 0, format!("{}: {}", host, e))),
+        };
+        if addrs.is_empty() {
+            return Err(PingDiagnostic::failed(PingErrorKind::Dns, 0, format!("{} has no address", host)));
+        }
+        if let Some(addr) = addrs.iter().find(|addr| is_internal_ip(addr.ip())) {
+            return Err(PingDiagnostic::failed(
+                PingErrorKind::InvalidConfig,
+                0,
+                format!("{} resolves to internal address {}, which is not allowed", host, addr.ip()),
+            ));
+        }
+        // 固定使用校验过的地址，避免请求时再次解析到内网地址
+        builder = builder.resolve_to_addrs(&host, &addrs);
+    }
+
+    match builder.build() {
+        Ok(client) => Ok((client, parsed)),
+        Err(e) => Err(PingDiagnostic::failed(PingErrorKind::Unknown, 0, e.to_string())),
+    }
+}
+
+// 内网、回环、链路本地、组播等不应由探测访问的地址
+fn is_internal_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                // 100.64.0.0/10 运营商级 NAT
+                || (a == 100 && (b & 0xc0) == 64)
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_internal_ip(IpAddr::V4(v4)),
+            None => {
+                let first = v6.segments()[0];
+                v6.is_loopback()
+                    || v6.is_unspecified()
+                    || v6.is_multicast()
+                    // fc00::/7 唯一本地地址，fe80::/10 链路本地地址
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80
+            }
+        },
+    }
+}
+
+fn build_api_url(domain: &str, path: &str, path_params: &std::collections::HashMap<String, String>) -> String {
+    let mut path = path.to_string();
+    for (key, value) in path_params {
+        path = path.replace(&format!("{{{}}}", key), value);
+        path = path.replace(&format!(":{}", key), value);
+    }
+    if !path.is_empty() && !path.starts_with('/') {
+        path.insert(0, '/');
+    }
+    format!("{}{}", domain.trim_end_matches('/'), path)
+}
+
+fn classify_reqwest_error(e: &reqwest::Error) -> PingErrorKind {
+    if e.is_timeout() {
+        return PingErrorKind::Timeout;
+    }
+    if e.is_builder() {
+        return PingErrorKind::InvalidConfig;
+    }
+    let chain = error_chain(e).to_lowercase();
+    if e.is_connect() {
+        if is_dns_message(&chain) || chain.contains("dns") {
+            return PingErrorKind::Dns;
+        }
+        if chain.contains("certificate") || chain.contains("tls") || chain.contains("handshake") {
+            return PingErrorKind::Tls;
+        }
+        return PingErrorKind::Connection;
+    }
+    PingErrorKind::Unknown
+}
+
+// reqwest 的错误信息藏在 source 链中，拼接后才能看出 DNS/TLS 等具体原因
+fn error_chain(e: &dyn std::error::Error) -> String {
+    let mut message = e.to_string();
+    let mut source = e.source();
+    while let Some(inner) = source {
+        message.push_str(": ");
+        message.push_str(&inner.to_string());
+        source = inner.source();
+    }
+    message
+}
+
+/// 按 JSON Schema 的常用子集（type / properties / required / items）校验响应
+fn check_schema(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+
+    if let Some(expected) = schema.get("type").and_then(|t| t.as_str()) {
+        let matched = match expected {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            _ => true,
+        };
+        if !matched {
+            return Err(format!("{} expected {}, got {}", path, expected, json_type_name(value)));
+        }
+    }
+
+    if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+        for key in required.iter().filter_map(|k| k.as_str()) {
+            if value.get(key).is_none() {
+                return Err(format!("{}.{} is required but missing", path, key));
+            }
+        }
+    }
+
+    if let (Some(properties), Some(object)) = (schema.get("properties").and_then(|p| p.as_object()), value.as_object()) {
+        for (key, sub_schema) in properties {
+            if let Some(sub_value) = object.get(key) {
+                check_schema(sub_schema, sub_value, &format!("{}.{}", path, key))?;
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (index, item) in array.iter().enumerate() {
+            check_schema(items, item, &format!("{}[{}]", path, index))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
@@ -1,11 +1,16 @@
 
-use crate::repositories::{ProjectRepo};
+use crate::repositories::{ProjectRepo, UserRepo};
 use crate::models::{Error};
 use crate::models::web::PageQuery;
-use crate::models::project::{Project, CreateStatus};
+use crate::models::project::{Project, CreateStatus, DeleteMode, ProjectArchive, ProjectDeleteQuery, ProjectMember, ProjectMemberForm, ProjectRole};
+use crate::models::user::UserStatus;
+use crate::utils::config::Setting;
+use crate::utils::database::{drop_database, evict_project_db};
 use shaku::Provider;
 use async_trait::async_trait;
 use super::ProjectService;
+use super::provision;
+use super::task_registry;
 
 
 #[derive(Provider)]
@@ -13,18 +18,65 @@ use super::ProjectService;
 pub struct ProjectServiceImpl {
     #[shaku(provide)]
     repo: Box<dyn ProjectRepo>,
+    #[shaku(provide)]
+    user_repo: Box<dyn UserRepo>,
+}
+
+// 项目下仍有运行中的任务时不能删除
+fn close_project(code: &str) -> Result<task_registry::ProjectCloseGuard, Error> {
+    task_registry::try_close(code).map_err(|running| match running.len() {
+        0 => Error::InvalidValue(format!("project {} is being deleted", code)),
+        n => Error::InvalidValue(format!("project {} has {} running tasks", code, n)),
+    })
+}
+
+impl ProjectServiceImpl {
+    // 需要调用方已关闭项目，删除失败时保留删除中状态以便重试
+    async fn teardown(&self, code: String) -> Result<(), Error> {
+        let project = self.repo.get_project(code.clone()).await?;
+        if project.create_status != CreateStatus::Deleting {
+            return Err(Error::InvalidValue(format!("project {} is not being deleted", code)));
+        }
+
+        evict_project_db(&code).await;
+        let mode = project.delete_mode.unwrap_or_default();
+        if let Err(e) = provision::teardown_project_db(&code, mode, &project.archive_db).await {
+            // create_msg 字段长度为 255
+            let msg: String = e.to_string().chars().take(255).collect();
+            self.repo.update_create_status(code, CreateStatus::Deleting, msg).await?;
+            return Err(e);
+        }
+
+        self.repo.del_all_member(code.clone()).await?;
+        self.repo.del_project(code).await
+    }
+
+    // 降级或移除项目负责人后至少保留一个负责人
+    async fn ensure_other_owner(&self, code: &str, user_id: &str) -> Result<(), Error> {
+        let role = self.repo.get_member_role(code.to_string(), user_id.to_string()).await?;
+        if role == Some(ProjectRole::Owner) && self.repo.count_owner(code.to_string()).await? <= 1 {
+            return Err(Error::InvalidValue(format!("project {} requires at least one owner", code)));
+        }
+        Ok(())
+    }
 }
 
 
 #[async_trait]
 impl ProjectService for ProjectServiceImpl {
-    async fn add_project(&self, mut project: Project) -> Result<String, Error> {
+    async fn add_project(&self, operator: String, mut project: Project) -> Result<String, Error> {
         project.create_status = CreateStatus::Pending;
         project.create_msg = "".to_string();
         project.logo = "".to_string();
         let result = self.repo.add_project(project).await;
         match result {
-            Ok(project) => Ok(project),
+            Ok(code) => {
+                // 创建者成为项目负责人
+                self.repo.save_member(code.clone(), operator, ProjectRole::Owner).await?;
+                // 项目库在后台创建，进度通过 create_status 反映
+                provision::spawn_provision(code.clone());
+                Ok(code)
+            },
             Err(e) => Err(e),
         }
     }
@@ -37,12 +89,39 @@ impl ProjectService for ProjectServiceImpl {
         }
     }
 
-    async fn del_project(&self, code: String) -> Result<(), Error> {
-        let result = self.repo.del_project(code).await;
-        match result {
-            Ok(_) => Ok(()),
-            Err(e) => Err(e),
+    async fn del_project(&self, code: String, params: ProjectDeleteQuery) -> Result<(), Error> {
+        let project = self.repo.get_project(code.clone()).await?;
+        if project.create_status == CreateStatus::Running {
+            return Err(Error::InvalidValue(format!("project {} is being provisioned", code)));
         }
+        // 关闭项目后直到删除结束都不能再启动任务
+        let _closed = close_project(&code)?;
+
+        // 已处于删除中的项目沿用上次记录的删除方式继续删除
+        if project.create_status != CreateStatus::Deleting {
+            let archive_db = match params.mode {
+                DeleteMode::Drop => "".to_string(),
+                DeleteMode::Archive => {
+                    provision::check_archivable(&code).await?;
+                    let retention_days = params
+                        .retention_days
+                        .unwrap_or(Setting::get().project.archive_retention_days);
+                    let archive_db = provision::archive_db_name(&code);
+                    let archived_at = chrono::Utc::now();
+                    // 先登记归档库，中断后归档库也会在保留期过后被清理
+                    self.repo.add_archive(ProjectArchive {
+                        archive_db: archive_db.clone(),
+                        code: code.clone(),
+                        archived_at,
+                        expire_at: archived_at + chrono::Duration::days(retention_days as i64),
+                    }).await?;
+                    archive_db
+                }
+            };
+            self.repo.mark_deleting(code.clone(), params.mode, archive_db).await?;
+        }
+
+        self.teardown(code).await
     }
 
     async fn get_project(&self, code: String) -> Result<Project, Error> {
@@ -52,13 +131,122 @@ impl ProjectService for ProjectServiceImpl {
             Err(e) => Err(e),
         }
     }
-    async fn list_project(&self, params: PageQuery) -> Result<Vec<Project>, Error> {
-        let result = self.repo.list_project(params).await;
-        match result {
-            Ok(projects) => Ok(projects),
-            Err(e) => Err(e),
+    async fn list_project(&self, operator: String, params: PageQuery) -> Result<Vec<Project>, Error> {
+        // 系统管理员可以看到全部项目，其他用户只能看到自己加入的项目
+        let user = self.user_repo.get_user(operator.clone()).await?;
+        if user.is_admin {
+            let mut projects = self.repo.list_project(params).await?;
+            projects.iter_mut().for_each(|p| p.role = Some(ProjectRole::Owner));
+            return Ok(projects);
         }
+        self.repo.list_member_project(operator, params).await
+    }
+
+    async fn list_all_project(&self) -> Result<Vec<Project>, Error> {
+        self.repo.list_all_project().await
+    }
+
+    async fn provision_project(&self, code: String) -> Result<(), Error> {
+        self.repo.update_create_status(code.clone(), CreateStatus::Running, "".to_string()).await?;
+        match provision::init_project_db(&code).await {
+            Ok(_) => self.repo.update_create_status(code, CreateStatus::Success, "".to_string()).await,
+            Err(e) => {
+                // create_msg 字段长度为 255
+                let msg: String = e.to_string().chars().take(255).collect();
+                self.repo.update_create_status(code, CreateStatus::Fail, msg).await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn retry_provision(&self, code: String) -> Result<(), Error> {
+        let project = self.repo.get_project(code.clone()).await?;
+        match project.create_status {
+            CreateStatus::Success => Err(Error::InvalidValue(format!("project {} is already provisioned", code))),
+            CreateStatus::Running => Err(Error::InvalidValue(format!("project {} is being provisioned", code))),
+            CreateStatus::Deleting => Err(Error::InvalidValue(format!("project {} is being deleted", code))),
+            CreateStatus::Pending | CreateStatus::Fail => {
+                self.repo.update_create_status(code.clone(), CreateStatus::Pending, "".to_string()).await?;
+                provision::spawn_provision(code);
+                Ok(())
+            }
+        }
+    }
+
+    async fn teardown_project(&self, code: String) -> Result<(), Error> {
+        let _closed = close_project(&code)?;
+        self.teardown(code).await
+    }
+
+    // 单个归档库清理失败不影响其他归档库，留待下次重试
+    async fn purge_expired_archive(&self) -> Result<usize, Error> {
+        let archives = self.repo.list_expired_archive(chrono::Utc::now()).await?;
+        let mut count = 0;
+        for archive in archives {
+            let archive_db = archive.archive_db.clone();
+            let result = match drop_database(&archive.archive_db).await {
+                Ok(_) => self.repo.del_archive(archive.archive_db).await,
+                Err(e) => Err(Error::from(e)),
+            };
+            match result {
+                Ok(_) => count += 1,
+                Err(e) => tracing::warn!("failed to purge project archive {}: {}", archive_db, e),
+            }
+        }
+        Ok(count)
+    }
+
+    async fn get_role(&self, user_id: String, code: String) -> Result<Option<ProjectRole>, Error> {
+        let user = match self.user_repo.get_user(user_id.clone()).await {
+            Ok(user) => user,
+            Err(Error::NotFound) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        if user.status != UserStatus::Active || code.is_empty() {
+            return Ok(None);
+        }
+        // 项目已删除时任何人都没有权限
+        match self.repo.get_project(code.clone()).await {
+            Ok(_) => {}
+            Err(Error::NotFound) => return Ok(None),
+            Err(e) => return Err(e),
+        }
+        if user.is_admin {
+            return Ok(Some(ProjectRole::Owner));
+        }
+        self.repo.get_member_role(code, user_id).await
     }
-}
 
+    async fn switch_project(&self, code: String) -> Result<Project, Error> {
+        let project = self.repo.get_project(code.clone()).await?;
+        // 项目库创建完成后才能切换进入
+        if project.create_status != CreateStatus::Success {
+            return Err(Error::InvalidValue(format!("project {} is not ready: {}", code, project.create_status)));
+        }
+        Ok(project)
+    }
 
+    async fn list_member(&self, code: String) -> Result<Vec<ProjectMember>, Error> {
+        self.repo.list_member(code).await
+    }
+
+    async fn save_member(&self, code: String, form: ProjectMemberForm) -> Result<(), Error> {
+        if form.user_id.is_empty() {
+            return Err(Error::EmptyValue("user_id".to_string()));
+        }
+        self.repo.get_project(code.clone()).await?;
+        let user = self.user_repo.get_user(form.user_id.clone()).await?;
+        if user.status != UserStatus::Active {
+            return Err(Error::InvalidValue(format!("user {} is disabled", user.username)));
+        }
+        if form.role != ProjectRole::Owner {
+            self.ensure_other_owner(&code, &form.user_id).await?;
+        }
+        self.repo.save_member(code, form.user_id, form.role).await
+    }
+
+    async fn del_member(&self, code: String, user_id: String) -> Result<(), Error> {
+        self.ensure_other_owner(&code, &user_id).await?;
+        self.repo.del_member(code, user_id).await
+    }
+}
@@ -0,0 +1,203 @@
+use crate::autofac;
+use crate::models::Error;
+use crate::models::project::{CreateStatus, DeleteMode, Project};
+use crate::utils::database::{archive_database, create_project_db, database_exists, drop_database, get_migration_db, is_system_database, list_unarchivable_objects, project_db_name, TEMPLATE_PROJECT_CODE};
+use crate::utils::migration::{self, MigrationStatus, MigrationTarget};
+
+/// 创建项目库并执行项目级迁移脚本
+pub async fn init_project_db(code: &str) -> Result<(), Error> {
+    create_project_db(code)
+        .await
+        .map_err(|e| Error::InternalError(format!("create database failed: {}", e)))?;
+
+    migrate_project_db(code, false).await?;
+    Ok(())
+}
+
+/// 对单个项目库执行迁移，dry_run 时只返回待执行的脚本
+pub async fn migrate_project_db(code: &str, dry_run: bool) -> Result<Vec<MigrationStatus>, Error> {
+    let pool = get_migration_db(code)
+        .await
+        .map_err(|e| Error::InternalError(format!("connect project database failed: {}", e)))?;
+
+    migration::migrate(&pool, MigrationTarget::Project, dry_run)
+        .await
+        .map_err(|e| Error::InternalError(format!("migrate project {} failed: {}", code, e)))
+}
+
+/// 列出需要迁移的项目：模版库以及已初始化完成的项目库
+pub async fn migratable_projects() -> Result<Vec<String>, Error> {
+    let projects = autofac::get_global_app_state_ref().get_project_service().list_all_project().await?;
+    Ok(migratable_codes(projects))
+}
+
+// 模版库排在最前，保证新建项目使用最新的表结构
+fn migratable_codes(projects: Vec<Project>) -> Vec<String> {
+    let mut codes = vec![TEMPLATE_PROJECT_CODE.to_string()];
+    codes.extend(
+        projects
+            .into_iter()
+            .filter(|p| matches!(p.create_status, CreateStatus::Success))
+            .map(|p| p.code),
+    );
+    codes
+}
+
+/// 启动时升级所有项目库，单个项目失败不影响其他项目
+pub async fn migrate_all_projects() {
+    let codes = match migratable_projects().await {
+        Ok(codes) => codes,
+        Err(e) => {
+            tracing::warn!("failed to list projects for migration: {}", e);
+            return;
+        }
+    };
+
+    for code in codes {
+        match migrate_project_db(&code, false).await {
+            Ok(applied) if !applied.is_empty() => tracing::info!("project {} migrated, {} scripts applied", code, applied.len()),
+            Ok(_) => {}
+            Err(e) => tracing::error!("{}", e),
+        }
+    }
+}
+
+/// 删除中的项目不能再启动任务，采集、加工、流水线和调度在启动前调用
+pub async fn check_project_active(code: &str) -> Result<(), Error> {
+    let project = autofac::get_global_app_state_ref().get_project_service().get_project(code.to_string()).await?;
+    if project.create_status == CreateStatus::Deleting {
+        return Err(Error::InvalidValue(format!("project {} is being deleted", code)));
+    }
+    Ok(())
+}
+
+/// 在后台执行项目库初始化，状态变化由 ProjectService 记录
+pub fn spawn_provision(code: String) {
+    tokio::spawn(async move {
+        let result = autofac::get_global_app_state_ref().get_project_service().provision_project(code.clone()).await;
+        match result {
+            Ok(_) => tracing::info!("project {} provisioned", code),
+            Err(e) => tracing::error!("project {} provisioning failed: {}", code, e),
+        }
+    });
+}
+
+/// 服务重启后继续处理未完成的项目初始化
+pub async fn resume_unfinished() {
+    let projects = match autofac::get_global_app_state_ref().get_project_service().list_all_project().await {
+        Ok(projects) => projects,
+        Err(e) => {
+            tracing::warn!("failed to list projects for provisioning: {}", e);
+            return;
+        }
+    };
+
+    for project in projects {
+        match project.create_status {
+            CreateStatus::Pending | CreateStatus::Running => spawn_provision(project.code),
+            CreateStatus::Deleting => spawn_teardown(project.code),
+            _ => {}
+        }
+    }
+}
+
+/// 在后台继续中断的项目删除
+pub fn spawn_teardown(code: String) {
+    tokio::spawn(async move {
+        let result = autofac::get_global_app_state_ref().get_project_service().teardown_project(code.clone()).await;
+        match result {
+            Ok(_) => tracing::info!("project {} deleted", code),
+            Err(e) => tracing::error!("project {} deletion failed: {}", code, e),
+        }
+    });
+}
+
+/// 生成项目的归档库名
+pub fn archive_db_name(code: &str) -> String {
+    let prefix = &crate::utils::config::Setting::get().database.prefix;
+    let mut archive_db = format!("{}archive_{}_{}", prefix, chrono::Utc::now().format("%Y%m%d%H%M%S"), code);
+    // MySQL 库名最长 64 个字符
+    archive_db.truncate(64);
+    archive_db
+}
+
+/// 归档前检查项目库中是否有无法迁移的对象
+pub async fn check_archivable(code: &str) -> Result<(), Error> {
+    let objects = list_unarchivable_objects(&project_db_name(code)).await?;
+    if !objects.is_empty() {
+        return Err(Error::InvalidValue(format!(
+            "project {} contains objects that cannot be archived: {}, remove them or delete with drop mode",
+            code,
+            objects.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// 删除或归档项目库；项目库不存在时不做处理，可重复执行
+pub async fn teardown_project_db(code: &str, mode: DeleteMode, archive_db: &str) -> Result<(), Error> {
+    let db_name = project_db_name(code);
+    if is_system_database(&db_name) || is_system_database(archive_db) {
+        return Err(Error::InvalidProjectCode(code.to_string()));
+    }
+    if !database_exists(&db_name).await? {
+        return Ok(());
+    }
+
+    match mode {
+        DeleteMode::Drop => drop_database(&db_name).await?,
+        DeleteMode::Archive => archive_database(&db_name, archive_db).await?,
+    }
+    Ok(())
+}
+
+/// 定期清理超过保留期的归档库
+pub fn spawn_archive_purge() {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(3600));
+        loop {
+            ticker.tick().await;
+            let result = autofac::get_global_app_state_ref().get_project_service().purge_expired_archive().await;
+            match result {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("purged {} expired project archives", count),
+                Err(e) => tracing::warn!("failed to purge project archives: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::migratable_codes;
+    use crate::models::project::{CreateStatus, Project};
+    use crate::utils::config::Setting;
+    use crate::utils::database::{TEMPLATE_PROJECT_CODE, migration_db_name};
+
+    fn project(code: &str, create_status: CreateStatus) -> Project {
+        Project { code: code.to_string(), create_status, ..Default::default() }
+    }
+
+    #[test]
+    fn every_migratable_project_resolves_a_database() {
+        Setting::init_for_test();
+        let codes = migratable_codes(vec![
+            project("sales", CreateStatus::Success),
+            project("pending", CreateStatus::Pending),
+            project("failed", CreateStatus::Fail),
+            project("deleting", CreateStatus::Deleting),
+        ]);
+        assert_eq!(codes, vec![TEMPLATE_PROJECT_CODE.to_string(), "sales".to_string()]);
+
+        let names: Vec<String> = codes.iter().map(|code| migration_db_name(code).unwrap()).collect();
+        assert_eq!(names, vec!["data_factory_template".to_string(), "data_factory_sales".to_string()]);
+    }
+
+    #[test]
+    fn reserved_codes_only_resolve_through_template() {
+        Setting::init_for_test();
+        assert!(migration_db_name("config").is_err());
+        assert!(migration_db_name("Template").is_err());
+        assert!(migration_db_name("1abc").is_err());
+    }
+}
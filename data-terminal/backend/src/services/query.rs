@@ -0,0 +1,153 @@
+use std::time::{Duration, Instant};
+
+use crate::repositories::{DataSourceRepo, QueryHistoryRepo, ResourceRepo};
+use crate::models::{Error, Validator};
+use crate::models::web::PageQuery;
+use crate::models::datasource::{DataSourceType, PostgresConnectionConfig};
+use crate::models::resource::{DatabaseResourceConfig, ResourceType};
+use crate::models::query::{QueryHistory, QueryRequest, QueryResult, QueryTargetType, DEFAULT_MAX_ROWS, DEFAULT_TIMEOUT_SECS};
+use crate::models::task_run::RunStatus;
+use crate::utils::sql_parse;
+use shaku::Provider;
+use async_trait::async_trait;
+use super::QueryService;
+use super::collect::{error_detail, postgres};
+use super::console::{self, Endpoint, Limits};
+use super::query_registry;
+use uuid::Uuid;
+
+#[derive(Provider)]
+#[shaku(interface = QueryService)]
+pub struct QueryServiceImpl {
+    #[shaku(provide)]
+    repo: Box<dyn QueryHistoryRepo>,
+    #[shaku(provide)]
+    datasource_repo: Box<dyn DataSourceRepo>,
+    #[shaku(provide)]
+    resource_repo: Box<dyn ResourceRepo>,
+}
+
+#[async_trait]
+impl QueryService for QueryServiceImpl {
+    async fn execute_query(&self, project_code: String, user_name: String, request: QueryRequest) -> Result<QueryResult, Error> {
+        request.validate()?;
+        let endpoint = self.endpoint(&project_code, &request).await?;
+        let sql = sql_parse::parse_read_only(&request.sql, endpoint.dialect().as_ref())?;
+        let limits = Limits {
+            max_rows: request.max_rows.unwrap_or(DEFAULT_MAX_ROWS) as usize,
+            timeout: Duration::from_secs(request.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS) as u64),
+        };
+
+        // 查询ID 只能由申请者使用一次
+        let guard = query_registry::try_start(&project_code, &user_name, &request.query_id)
+            .ok_or_else(|| Error::InvalidValue(format!("query {} is not reserved or already running", request.query_id)))?;
+        let query_id = guard.query_id().to_string();
+        let executed_at = chrono::Utc::now();
+        let started = Instant::now();
+        let result = console::execute(&endpoint, &sql, &limits, &guard.cancel_signal()).await;
+        drop(guard);
+        let duration_ms = started.elapsed().as_millis() as i64;
+
+        let mut history = QueryHistory {
+            id: Uuid::new_v4().to_string(),
+            user_name,
+            target_type: request.target_type,
+            target_id: request.target_id,
+            sql_text: request.sql,
+            status: RunStatus::Success,
+            row_count: 0,
+            truncated: false,
+            duration_ms,
+            error_message: None,
+            executed_at,
+        };
+        let result = match result {
+            Ok(Some(rows)) => {
+                history.row_count = rows.rows.len() as i32;
+                history.truncated = rows.truncated;
+                Ok(QueryResult {
+                    query_id,
+                    columns: rows.columns,
+                    rows: rows.rows,
+                    truncated: rows.truncated,
+                    duration_ms,
+                })
+            }
+            Ok(None) => {
+                history.status = RunStatus::Cancelled;
+                Err(Error::InvalidValue(format!("query {} was cancelled", query_id)))
+            }
+            Err(e) => {
+                history.status = RunStatus::Failed;
+                history.error_message = Some(error_detail(&e));
+                Err(e)
+            }
+        };
+
+        // 历史保存失败不影响查询结果
+        if let Err(e) = self.repo.add_history(project_code, history).await {
+            tracing::warn!("failed to save query history: {}", error_detail(&e));
+        }
+        result
+    }
+
+    async fn reserve_query(&self, project_code: String, user_name: String) -> Result<String, Error> {
+        Ok(query_registry::reserve(&project_code, &user_name))
+    }
+
+    async fn cancel_query(&self, project_code: String, user_name: String, query_id: String) -> Result<(), Error> {
+        if !query_registry::cancel(&project_code, &user_name, &query_id) {
+            return Err(Error::InvalidValue(format!("query {} is not running", query_id)));
+        }
+        Ok(())
+    }
+
+    async fn list_query_history(&self, project_code: String, user_name: String, params: PageQuery) -> Result<Vec<QueryHistory>, Error> {
+        self.repo.list_history(project_code, user_name, params).await
+    }
+
+    async fn clear_query_history(&self, project_code: String, user_name: String) -> Result<(), Error> {
+        self.repo.clear_history(project_code, user_name).await
+    }
+}
+
+impl QueryServiceImpl {
+    // 查询对象的连接信息，只支持 MySQL、PostgreSQL 数据源与资源
+    async fn endpoint(&self, project_code: &str, request: &QueryRequest) -> Result<Endpoint, Error> {
+        match request.target_type {
+            QueryTargetType::Datasource => {
+                let datasource = match self.datasource_repo.get_datasource(project_code.to_string(), request.target_id.clone()).await {
+                    Ok(datasource) => datasource,
+                    Err(Error::NotFound) => return Err(Error::InvalidValue(format!("datasource {} does not exist", request.target_id))),
+                    Err(e) => return Err(e),
+                };
+                match datasource.datasource_type {
+                    DataSourceType::Mysql => Ok(Endpoint::MysqlDatasource(datasource.connection_config)),
+                    DataSourceType::Postgres => Ok(Endpoint::Postgres(postgres::parse_config(&datasource.connection_config)?)),
+                    other => Err(Error::InvalidValue(format!("cannot query {} datasource", other))),
+                }
+            }
+            QueryTargetType::Resource => {
+                let resource = match self.resource_repo.get_resource(request.target_id.clone()).await {
+                    Ok(resource) => resource,
+                    Err(Error::NotFound) => return Err(Error::InvalidValue(format!("resource {} does not exist", request.target_id))),
+                    Err(e) => return Err(e),
+                };
+                let config = serde_json::from_value::<DatabaseResourceConfig>(resource.config.clone())
+                    .map_err(|e| Error::InvalidValue(format!("invalid resource config: {}", e)))?;
+                match resource.resource_type {
+                    ResourceType::Mysql => Ok(Endpoint::MysqlResource(config)),
+                    ResourceType::Postgres => Ok(Endpoint::Postgres(PostgresConnectionConfig {
+                        database: config.default_database().unwrap_or_else(|| "postgres".to_string()),
+                        host: config.host,
+                        port: config.port,
+                        username: config.username,
+                        password: config.password,
+                        catalog: String::new(),
+                    })),
+                    other => Err(Error::InvalidValue(format!("cannot query {} resource", other))),
+                }
+            }
+        }
+    }
+}
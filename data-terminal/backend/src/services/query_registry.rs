@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::watch;
+use uuid::Uuid;
+
+// 申请后未开始执行的查询ID 的保留时间
+const RESERVE_TTL: Duration = Duration::from_secs(60);
+
+struct QueryEntry {
+    project_code: String,
+    owner: String,
+    reserved_at: Instant,
+    running: bool,
+    cancel: watch::Sender<bool>,
+}
+
+// 查询ID -> 所属项目、用户与取消信号，与任务登记表相互独立
+static QUERIES: LazyLock<Mutex<HashMap<String, QueryEntry>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 执行中查询的登记凭证，drop 时自动注销
+#[derive(Debug)]
+pub struct QueryGuard {
+    query_id: String,
+    cancelled: watch::Receiver<bool>,
+}
+
+impl QueryGuard {
+    pub fn query_id(&self) -> &str {
+        &self.query_id
+    }
+
+    /// 取消信号，值变为 true 时查询应尽快停止
+    pub fn cancel_signal(&self) -> watch::Receiver<bool> {
+        self.cancelled.clone()
+    }
+}
+
+impl Drop for QueryGuard {
+    fn drop(&mut self) {
+        QUERIES.lock().unwrap().remove(&self.query_id);
+    }
+}
+
+/// 为用户生成查询ID，执行前即可据此取消
+pub fn reserve(project_code: &str, owner: &str) -> String {
+    let mut queries = QUERIES.lock().unwrap();
+    // 清理申请后一直未执行的查询ID
+    queries.retain(|_, q| q.running || q.reserved_at.elapsed() < RESERVE_TTL);
+    let query_id = Uuid::new_v4().to_string();
+    let (sender, _) = watch::channel(false);
+    queries.insert(query_id.clone(), QueryEntry {
+        project_code: project_code.to_string(),
+        owner: owner.to_string(),
+        reserved_at: Instant::now(),
+        running: false,
+        cancel: sender,
+    });
+    query_id
+}
+
+/// 开始执行查询；查询ID 为空时重新生成，非本人申请或已在执行时返回 None
+pub fn try_start(project_code: &str, owner: &str, query_id: &str) -> Option<QueryGuard> {
+    let query_id = if query_id.is_empty() { reserve(project_code, owner) } else { query_id.to_string() };
+    let mut queries = QUERIES.lock().unwrap();
+    let entry = queries.get_mut(&query_id)?;
+    if entry.running || entry.project_code != project_code || entry.owner != owner {
+        return None;
+    }
+    entry.running = true;
+    Some(QueryGuard {
+        cancelled: entry.cancel.subscribe(),
+        query_id,
+    })
+}
+
+/// 取消本人的查询，查询不存在或不属于该用户时返回 false
+pub fn cancel(project_code: &str, owner: &str, query_id: &str) -> bool {
+    let queries = QUERIES.lock().unwrap();
+    match queries.get(query_id) {
+        Some(entry) if entry.project_code == project_code && entry.owner == owner => {
+            entry.cancel.send_replace(true);
+            true
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::autofac;
+use crate::models::task_run::{RunEvent, TaskRun, TaskRunLog, TaskRunLogReadOnly, TaskRunReadOnly};
+
+// 运行日志所在 span 的名称，span 上的 run_id 字段标识日志归属的运行记录
+const RUN_SPAN: &str = "task_run";
+
+// 单次运行最多保留的日志行数，实时采集长期运行时避免日志无限增长
+const MAX_LINES_PER_RUN: i64 = 20000;
+
+// 缓冲日志写入数据库的间隔
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+// 实时订阅的事件队列长度，订阅者落后超过该长度时需重新订阅
+const EVENT_CAPACITY: usize = 1024;
+
+// 运行ID -> 尚未写入数据库的日志
+static RUN_LOGS: LazyLock<Mutex<HashMap<String, RunLogBuffer>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// 写库互斥：结束采集时等待正在进行的写入完成，保证运行结束后日志已完整落库
+static SAVING: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+struct RunLogBuffer {
+    project_code: String,
+    next_seq: i64,
+    pending: Vec<TaskRunLog>,
+    events: broadcast::Sender<RunEvent>,
+}
+
+impl RunLogBuffer {
+    fn push(&mut self, run_id: &str, level: &str, message: String) {
+        if self.next_seq > MAX_LINES_PER_RUN {
+            return;
+        }
+        let (level, message) = if self.next_seq == MAX_LINES_PER_RUN {
+            ("warn", format!("log exceeds {} lines, the rest is discarded", MAX_LINES_PER_RUN))
+        } else {
+            (level, message)
+        };
+        let log = TaskRunLog {
+            run_id: run_id.to_string(),
+            seq: self.next_seq,
+            level: level.to_string(),
+            message,
+            logged_at: chrono::Utc::now(),
+        };
+        // 没有订阅者时发送失败，忽略即可
+        let _ = self.events.send(RunEvent::Log(TaskRunLogReadOnly::from(log.clone())));
+        self.pending.push(log);
+        self.next_seq += 1;
+    }
+}
+
+/// 开始采集一次运行的日志，返回的 span 内产生的 tracing 事件都会写入该运行的日志
+pub fn start(project_code: &str, run_id: &str) -> tracing::Span {
+    RUN_LOGS.lock().unwrap().insert(
+        run_id.to_string(),
+        RunLogBuffer {
+            project_code: project_code.to_string(),
+            next_seq: 1,
+            pending: Vec::new(),
+            events: broadcast::channel(EVENT_CAPACITY).0,
+        },
+    );
+    tracing::info_span!(RUN_SPAN, run_id = %run_id)
+}
+
+/// 结束日志采集：写入剩余的日志并向订阅者推送最终状态，应在保存运行结果之前调用
+pub async fn finish(run: &TaskRun) {
+    let _saving = SAVING.lock().await;
+    let buffer = RUN_LOGS.lock().unwrap().remove(&run.id);
+    if let Some(buffer) = buffer {
+        save(buffer.project_code, buffer.pending).await;
+        let _ = buffer.events.send(RunEvent::Status(TaskRunReadOnly::from(run.clone())));
+    }
+}
+
+/// 订阅运行中的事件，同时返回尚未落库的日志；运行不在采集日志或不属于该项目时返回 None。
+/// 运行结束后事件通道关闭，订阅者会先收到最终状态
+pub async fn subscribe(project_code: &str, run_id: &str) -> Option<(broadcast::Receiver<RunEvent>, Vec<TaskRunLog>)> {
+    // 等待正在进行的写入完成，之后读库加上返回的缓冲即可覆盖订阅之前的全部日志
+    let _saving = SAVING.lock().await;
+    let logs = RUN_LOGS.lock().unwrap();
+    let buffer = logs.get(run_id).filter(|b| b.project_code == project_code)?;
+    Some((buffer.events.subscribe(), buffer.pending.clone()))
+}
+
+fn append(run_id: &str, level: &str, message: String) {
+    let mut logs = RUN_LOGS.lock().unwrap();
+    if let Some(buffer) = logs.get_mut(run_id) {
+        buffer.push(run_id, level, message);
+    }
+}
+
+async fn save(project_code: String, logs: Vec<TaskRunLog>) {
+    if logs.is_empty() {
+        return;
+    }
+    let count = logs.len();
+    let saved = autofac::get_global_app_state_ref()
+        .get_task_run_service()
+        .add_run_log(project_code.clone(), logs)
+        .await;
+    if let Err(e) = saved {
+        tracing::warn!("failed to save {} run log lines of project {}: {}", count, project_code, e);
+    }
+}
+
+/// 启动后台任务，定期把缓冲的运行日志写入对应项目库
+pub fn spawn_flusher() {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let _saving = SAVING.lock().await;
+            let mut batches: HashMap<String, Vec<TaskRunLog>> = HashMap::new();
+            {
+                let mut logs = RUN_LOGS.lock().unwrap();
+                for buffer in logs.values_mut().filter(|b| !b.pending.is_empty()) {
+                    batches
+                        .entry(buffer.project_code.clone())
+                        .or_default()
+                        .append(&mut buffer.pending);
+                }
+            }
+            for (project_code, logs) in batches {
+                save(project_code, logs).await;
+            }
+        }
+    });
+}
+
+// span 扩展中保存的运行ID
+struct RunLogTarget(String);
+
+/// 把运行 span 内的 tracing 事件转存为运行日志
+pub struct RunLogLayer;
+
+impl<S> Layer<S> for RunLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != RUN_SPAN {
+            return;
+        }
+        let mut visitor = RunIdVisitor(None);
+        attrs.record(&mut visitor);
+        if let (Some(run_id), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(RunLogTarget(run_id));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let Some(scope) = ctx.event_scope(event) else {
+            return;
+        };
+        for span in scope {
+            let extensions = span.extensions();
+            if let Some(target) = extensions.get::<RunLogTarget>() {
+                let mut visitor = MessageVisitor::default();
+                event.record(&mut visitor);
+                append(&target.0, level_name(event.metadata().level()), visitor.finish());
+                return;
+            }
+        }
+    }
+}
+
+fn level_name(level: &Level) -> &'static str {
+    match *level {
+        Level::ERROR => "error",
+        Level::WARN => "warn",
+        Level::INFO => "info",
+        _ => "debug",
+    }
+}
+
+struct RunIdVisitor(Option<String>);
+
+impl Visit for RunIdVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "run_id" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "run_id" {
+            self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
+
+// 日志内容为 message 字段，其他字段以 key=value 追加在后面
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        if self.fields.is_empty() {
+            self.message
+        } else {
+            format!("{}{}", self.message, self.fields)
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
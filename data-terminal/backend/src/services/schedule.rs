@@ -0,0 +1,332 @@
+use crate::autofac;
+use crate::repositories::{CollectionRepo, PipelineRepo, ScheduleRepo, TransformRepo};
+use crate::models::Error;
+use crate::models::collection::CollectType;
+use crate::models::schedule::{MisfirePolicy, TaskSchedule, TaskScheduleReadOnly, TaskScheduleCreateUpdate, SchedulePreviewQuery};
+use crate::models::task_run::TaskType;
+use crate::utils::config::Setting;
+use shaku::Provider;
+use async_trait::async_trait;
+use super::ScheduleService;
+use super::{provision, scheduler, task_registry};
+
+// 详情中展示的触发时间个数
+const DETAIL_FIRE_TIMES: usize = 5;
+const MAX_PREVIEW_FIRE_TIMES: usize = 50;
+
+#[derive(Provider)]
+#[shaku(interface = ScheduleService)]
+pub struct ScheduleServiceImpl {
+    #[shaku(provide)]
+    repo: Box<dyn ScheduleRepo>,
+    #[shaku(provide)]
+    collection_repo: Box<dyn CollectionRepo>,
+    #[shaku(provide)]
+    pipeline_repo: Box<dyn PipelineRepo>,
+    #[shaku(provide)]
+    transform_repo: Box<dyn TransformRepo>,
+}
+
+#[async_trait]
+impl ScheduleService for ScheduleServiceImpl {
+    async fn save_schedule(&self, project_code: String, task_id: String, schedule: TaskScheduleCreateUpdate) -> Result<(), Error> {
+        self.check_task(project_code.clone(), &task_id, schedule.task_type).await?;
+        let cron = scheduler::parse_cron(&schedule.cron)?;
+        let timezone = scheduler::parse_timezone(&schedule.timezone)?;
+
+        let now = chrono::Utc::now();
+        let existing = self.repo.get_schedule(project_code.clone(), task_id.clone()).await?;
+        let next_fire_at = if schedule.enabled {
+            scheduler::next_fire_times(&cron, timezone, now, 1).first().map(|t| t.to_utc())
+        } else {
+            None
+        };
+        let schedule = TaskSchedule {
+            task_id,
+            task_type: schedule.task_type,
+            cron: schedule.cron.trim().to_string(),
+            timezone: schedule.timezone,
+            misfire_policy: schedule.misfire_policy,
+            enabled: schedule.enabled,
+            next_fire_at,
+            last_fire_at: existing.as_ref().and_then(|s| s.last_fire_at),
+            created_at: existing.as_ref().map(|s| s.created_at).unwrap_or(now),
+            updated_at: now,
+        };
+        self.repo.save_schedule(project_code, schedule).await
+    }
+
+    async fn del_schedule(&self, project_code: String, task_id: String) -> Result<(), Error> {
+        self.repo.del_schedule(project_code, task_id).await
+    }
+
+    async fn get_schedule(&self, project_code: String, task_id: String) -> Result<Option<TaskScheduleReadOnly>, Error> {
+        let schedule = self.repo.get_schedule(project_code, task_id).await?;
+        Ok(schedule.map(|s| read_only(s, DETAIL_FIRE_TIMES)))
+    }
+
+    async fn list_schedule(&self, project_code: String) -> Result<Vec<TaskScheduleReadOnly>, Error> {
+        let schedules = self.repo.list_schedule(project_code).await?;
+        Ok(schedules.into_iter().map(|s| read_only(s, 0)).collect())
+    }
+
+    async fn preview_schedule(&self, query: SchedulePreviewQuery) -> Result<Vec<String>, Error> {
+        let cron = scheduler::parse_cron(&query.cron)?;
+        let timezone = scheduler::parse_timezone(query.timezone.as_deref().unwrap_or("Asia/Shanghai"))?;
+        let count = query.count.unwrap_or(DETAIL_FIRE_TIMES).clamp(1, MAX_PREVIEW_FIRE_TIMES);
+        Ok(scheduler::next_fire_times(&cron, timezone, chrono::Utc::now(), count)
+            .iter()
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .collect())
+    }
+
+    // 先推进下次触发时间再触发任务，同一触发点不会被重复处理；错过的多次触发合并为一次
+    async fn fire_due_schedule(&self, project_code: String) -> Result<usize, Error> {
+        provision::check_project_active(&project_code).await?;
+        let now = chrono::Utc::now();
+        let grace = chrono::Duration::seconds(Setting::get().scheduler.misfire_grace_secs as i64);
+        let due = self.repo.list_due_schedule(project_code.clone(), now).await?;
+
+        let mut fired = 0;
+        for schedule in due {
+            let Some(fire_at) = schedule.next_fire_at else {
+                continue;
+            };
+            let next_fire_at = match next_fire_after(&schedule, now) {
+                Ok(next_fire_at) => next_fire_at,
+                Err(e) => {
+                    tracing::warn!("schedule of task {} is invalid and stopped: {}", schedule.task_id, e);
+                    None
+                }
+            };
+            self.repo
+                .update_fire_time(project_code.clone(), schedule.task_id.clone(), next_fire_at, None)
+                .await?;
+
+            if should_skip_misfire(fire_at, now, grace, schedule.misfire_policy) {
+                tracing::info!("task {} missed its fire time {}, skipped", schedule.task_id, fire_at);
+                continue;
+            }
+            // 上一次运行尚未结束时不重叠执行
+            if task_registry::is_running(&project_code, &schedule.task_id) {
+                tracing::info!("task {} is still running, fire time {} skipped", schedule.task_id, fire_at);
+                continue;
+            }
+
+            match self.trigger(project_code.clone(), &schedule).await {
+                Ok(run_id) => {
+                    tracing::info!("task {} triggered by schedule, run {}", schedule.task_id, run_id);
+                    self.repo
+                        .update_fire_time(project_code.clone(), schedule.task_id.clone(), next_fire_at, Some(now))
+                        .await?;
+                    fired += 1;
+                }
+                Err(e) => tracing::warn!("failed to trigger scheduled task {}: {}", schedule.task_id, e),
+            }
+        }
+        Ok(fired)
+    }
+}
+
+impl ScheduleServiceImpl {
+    // 目前可以调度采集任务、加工任务和流水线；实时采集持续运行，不需要调度
+    async fn check_task(&self, project_code: String, task_id: &str, task_type: TaskType) -> Result<(), Error> {
+        match task_type {
+            TaskType::Collection => {
+                let task = match self.collection_repo.get_collection(project_code, task_id.to_string()).await {
+                    Ok(task) => task,
+                    Err(Error::NotFound) => {
+                        return Err(Error::InvalidValue(format!("collection task {} does not exist", task_id)));
+                    }
+                    Err(e) => return Err(e),
+                };
+                if task.collect_type == CollectType::Stream {
+                    return Err(Error::InvalidValue("stream collection runs continuously and cannot be scheduled".to_string()));
+                }
+                Ok(())
+            }
+            TaskType::Transform => match self.transform_repo.get_transform(project_code, task_id.to_string()).await {
+                Ok(_) => Ok(()),
+                Err(Error::NotFound) => Err(Error::InvalidValue(format!("transform task {} does not exist", task_id))),
+                Err(e) => Err(e),
+            },
+            TaskType::Pipeline => match self.pipeline_repo.get_pipeline(project_code, task_id.to_string()).await {
+                Ok(_) => Ok(()),
+                Err(Error::NotFound) => Err(Error::InvalidValue(format!("pipeline {} does not exist", task_id))),
+                Err(e) => Err(e),
+            },
+            other => Err(Error::InvalidValue(format!("{} task cannot be scheduled yet", other))),
+        }
+    }
+
+    async fn trigger(&self, project_code: String, schedule: &TaskSchedule) -> Result<String, Error> {
+        match schedule.task_type {
+            TaskType::Collection => {
+                autofac::get_global_app_state_ref()
+                    .get_collection_service()
+                    .run_collection(project_code, schedule.task_id.clone())
+                    .await
+            }
+            TaskType::Transform => {
+                autofac::get_global_app_state_ref()
+                    .get_transform_service()
+                    .run_transform(project_code, schedule.task_id.clone(), None)
+                    .await
+            }
+            TaskType::Pipeline => {
+                autofac::get_global_app_state_ref()
+                    .get_pipeline_service()
+                    .run_pipeline(project_code, schedule.task_id.clone())
+                    .await
+            }
+            other => Err(Error::InvalidValue(format!("{} task cannot be scheduled yet", other))),
+        }
+    }
+}
+
+// now 之后按调度时区计算的下次触发时间，错过的多次触发不会逐个补算
+fn next_fire_after(schedule: &TaskSchedule, now: chrono::DateTime<chrono::Utc>) -> Result<Option<chrono::DateTime<chrono::Utc>>, Error> {
+    let cron = scheduler::parse_cron(&schedule.cron)?;
+    let timezone = scheduler::parse_timezone(&schedule.timezone)?;
+    Ok(scheduler::next_fire_times(&cron, timezone, now, 1).first().map(|t| t.to_utc()))
+}
+
+// 超过容忍时间且策略为跳过时，本次错过的触发不再执行
+fn should_skip_misfire(
+    fire_at: chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+    grace: chrono::Duration,
+    policy: MisfirePolicy,
+) -> bool {
+    now - fire_at > grace && policy == MisfirePolicy::Skip
+}
+
+// 时间按调度的时区展示
+fn read_only(schedule: TaskSchedule, count: usize) -> TaskScheduleReadOnly {
+    let timezone = scheduler::parse_timezone(&schedule.timezone).unwrap_or(chrono_tz::UTC);
+    let format = |t: chrono::DateTime<chrono::Utc>| t.with_timezone(&timezone).format("%Y-%m-%d %H:%M:%S").to_string();
+    let next_fire_times = match scheduler::parse_cron(&schedule.cron) {
+        Ok(cron) if schedule.enabled && count > 0 => scheduler::next_fire_times(&cron, timezone, chrono::Utc::now(), count)
+            .iter()
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .collect(),
+        _ => Vec::new(),
+    };
+    TaskScheduleReadOnly {
+        task_id: schedule.task_id,
+        task_type: schedule.task_type,
+        cron: schedule.cron,
+        timezone: schedule.timezone,
+        misfire_policy: schedule.misfire_policy,
+        enabled: schedule.enabled,
+        next_fire_at: schedule.next_fire_at.map(format).unwrap_or_default(),
+        last_fire_at: schedule.last_fire_at.map(format).unwrap_or_default(),
+        next_fire_times,
+        updated_at: schedule.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    use super::{next_fire_after, should_skip_misfire};
+    use crate::models::schedule::{MisfirePolicy, TaskSchedule};
+    use crate::services::scheduler;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn schedule(cron: &str, timezone: &str) -> TaskSchedule {
+        TaskSchedule { task_id: "t1".to_string(), cron: cron.to_string(), timezone: timezone.to_string(), ..Default::default() }
+    }
+
+    fn fire_times(cron: &str, timezone: &str, after: DateTime<Utc>, count: usize) -> Vec<DateTime<Utc>> {
+        let cron = scheduler::parse_cron(cron).unwrap();
+        let timezone = scheduler::parse_timezone(timezone).unwrap();
+        scheduler::next_fire_times(&cron, timezone, after, count).iter().map(|t| t.to_utc()).collect()
+    }
+
+    #[test]
+    fn five_field_cron_treats_zero_and_seven_as_sunday() {
+        // 2025-06-01 是周日
+        let after = utc(2025, 5, 30, 0, 0);
+        let sunday = vec![utc(2025, 6, 1, 8, 0)];
+        assert_eq!(fire_times("0 8 * * 0", "UTC", after, 1), sunday);
+        assert_eq!(fire_times("0 8 * * 7", "UTC", after, 1), sunday);
+        assert_eq!(fire_times("0 0 8 * * SUN", "UTC", after, 1), sunday);
+        assert!(scheduler::parse_cron("not a cron").is_err());
+        assert!(scheduler::parse_timezone("Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn fire_time_follows_schedule_timezone() {
+        // 上海时间每天 02:00 即 UTC 前一天 18:00
+        let next = next_fire_after(&schedule("0 2 * * *", "Asia/Shanghai"), utc(2025, 1, 1, 12, 0)).unwrap();
+        assert_eq!(next, Some(utc(2025, 1, 1, 18, 0)));
+        let next = next_fire_after(&schedule("0 2 * * *", "UTC"), utc(2025, 1, 1, 12, 0)).unwrap();
+        assert_eq!(next, Some(utc(2025, 1, 2, 2, 0)));
+        assert!(next_fire_after(&schedule("0 2 * * *", "Nowhere"), utc(2025, 1, 1, 12, 0)).is_err());
+    }
+
+    #[test]
+    fn wall_clock_time_is_kept_across_dst_transitions() {
+        // 纽约 2025-03-09 进入夏令时，2025-11-02 结束
+        let spring = fire_times("0 9 * * *", "America/New_York", utc(2025, 3, 7, 12, 0), 3);
+        assert_eq!(spring, vec![utc(2025, 3, 8, 14, 0), utc(2025, 3, 9, 13, 0), utc(2025, 3, 10, 13, 0)]);
+
+        let autumn = fire_times("0 9 * * *", "America/New_York", utc(2025, 10, 31, 12, 0), 3);
+        assert_eq!(autumn, vec![utc(2025, 11, 1, 13, 0), utc(2025, 11, 2, 14, 0), utc(2025, 11, 3, 14, 0)]);
+    }
+
+    #[test]
+    fn fire_times_stay_ordered_around_dst_gap() {
+        // 2025-03-09 本地 02:30 不存在，前后两天的触发时间不受影响且不会倒退
+        let times = fire_times("30 2 * * *", "America/New_York", utc(2025, 3, 7, 12, 0), 3);
+        assert!(times.windows(2).all(|w| w[0] < w[1]));
+        assert!(times.contains(&utc(2025, 3, 8, 7, 30)));
+        assert!(times.contains(&utc(2025, 3, 10, 6, 30)));
+
+        let new_york: Tz = "America/New_York".parse().unwrap();
+        for t in &times {
+            assert_eq!(t.with_timezone(&new_york).format("%M").to_string(), "30");
+        }
+    }
+
+    #[test]
+    fn next_fire_after_long_outage_is_in_future() {
+        // 停机期间错过的多次触发合并，下次触发时间从当前时间起算
+        let now = utc(2025, 3, 9, 15, 0);
+        let next = next_fire_after(&schedule("*/5 * * * *", "America/New_York"), now).unwrap().unwrap();
+        assert!(next > now);
+        assert_eq!(next, utc(2025, 3, 9, 15, 5));
+    }
+
+    #[test]
+    fn misfire_policy_applies_after_grace() {
+        let grace = Duration::seconds(60);
+        let fire_at = utc(2025, 1, 1, 2, 0);
+
+        assert!(!should_skip_misfire(fire_at, fire_at + Duration::seconds(30), grace, MisfirePolicy::Skip));
+        assert!(!should_skip_misfire(fire_at, fire_at + grace, grace, MisfirePolicy::Skip));
+        assert!(should_skip_misfire(fire_at, fire_at + Duration::hours(3), grace, MisfirePolicy::Skip));
+        assert!(!should_skip_misfire(fire_at, fire_at + Duration::hours(3), grace, MisfirePolicy::FireOnce));
+    }
+
+    #[test]
+    fn misfire_is_measured_in_utc_not_local_time() {
+        // 纽约秋季回拨时本地 01:30 出现两次，按 UTC 计算错过时长不会被回拨掩盖
+        let grace = Duration::seconds(60);
+        let fire_at = utc(2025, 11, 2, 5, 30);
+        let now = utc(2025, 11, 2, 6, 30);
+        let new_york: Tz = "America/New_York".parse().unwrap();
+        assert_eq!(
+            fire_at.with_timezone(&new_york).format("%H:%M").to_string(),
+            now.with_timezone(&new_york).format("%H:%M").to_string()
+        );
+        assert!(should_skip_misfire(fire_at, now, grace, MisfirePolicy::Skip));
+        assert!(!should_skip_misfire(fire_at, now, grace, MisfirePolicy::FireOnce));
+    }
+}
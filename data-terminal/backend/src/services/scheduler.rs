@@ -0,0 +1,83 @@
+use std::str::FromStr;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+use regex::{Captures, Regex};
+use tokio::time::MissedTickBehavior;
+
+use crate::autofac;
+use crate::models::Error;
+use crate::models::project::CreateStatus;
+use crate::utils::config::Setting;
+
+// 星期字段中的数字，斜杠后的步长不做转换
+static WEEKDAY_NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(/?)(\d+)").unwrap());
+
+/// 解析 cron 表达式，支持 5 段（分 时 日 月 周）、6 段（秒 分 时 日 月 周）、7 段（再加年）及 @daily 等别名；
+/// 5 段表达式按 crontab 习惯，星期中的 0 和 7 都表示周日
+pub fn parse_cron(expr: &str) -> Result<Schedule, Error> {
+    let expr = expr.trim();
+    let fields: Vec<&str> = expr.split_whitespace().collect();
+    let normalized = if fields.len() == 5 {
+        let weekday = WEEKDAY_NUMBER.replace_all(fields[4], |caps: &Captures| {
+            const NAMES: [&str; 8] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+            match caps[2].parse::<usize>() {
+                Ok(n) if caps[1].is_empty() && n < NAMES.len() => NAMES[n].to_string(),
+                _ => caps[0].to_string(),
+            }
+        });
+        format!("0 {} {} {} {} {}", fields[0], fields[1], fields[2], fields[3], weekday)
+    } else {
+        expr.to_string()
+    };
+    Schedule::from_str(&normalized).map_err(|e| Error::InvalidValue(format!("invalid cron expression {}: {}", expr, e)))
+}
+
+pub fn parse_timezone(name: &str) -> Result<Tz, Error> {
+    Tz::from_str(name).map_err(|_| Error::InvalidValue(format!("invalid timezone {}", name)))
+}
+
+/// after 之后的 count 个触发时间
+pub fn next_fire_times(schedule: &Schedule, timezone: Tz, after: DateTime<Utc>, count: usize) -> Vec<DateTime<Tz>> {
+    schedule.after(&after.with_timezone(&timezone)).take(count).collect()
+}
+
+/// 启动调度器，按配置的间隔检查所有项目下到期的调度计划
+pub fn spawn_scheduler() {
+    let setting = &Setting::get().scheduler;
+    if !setting.enabled {
+        tracing::info!("task scheduler is disabled");
+        return;
+    }
+
+    let interval = Duration::from_secs(setting.tick_secs.max(1));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            fire_due_schedules().await;
+        }
+    });
+}
+
+async fn fire_due_schedules() {
+    let projects = match autofac::get_global_app_state_ref().get_project_service().list_all_project().await {
+        Ok(projects) => projects,
+        Err(e) => {
+            tracing::warn!("scheduler failed to list projects: {}", e);
+            return;
+        }
+    };
+
+    for project in projects.into_iter().filter(|p| p.create_status == CreateStatus::Success) {
+        match autofac::get_global_app_state_ref().get_schedule_service().fire_due_schedule(project.code.clone()).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("scheduler triggered {} tasks in project {}", count, project.code),
+            Err(e) => tracing::warn!("scheduler failed to fire schedules of project {}: {}", project.code, e),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex};
+
+use tokio::sync::watch;
+
+// 项目编码 -> 正在运行的任务ID -> 取消信号
+static RUNNING_TASKS: LazyLock<Mutex<HashMap<String, HashMap<String, watch::Sender<bool>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// 正在删除的项目，不再接受任务登记；只在持有 RUNNING_TASKS 锁时访问
+static CLOSED_PROJECTS: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// 运行中任务的登记凭证，drop 时自动注销
+#[derive(Debug)]
+pub struct RunningTaskGuard {
+    project_code: String,
+    task_id: String,
+    cancelled: watch::Receiver<bool>,
+}
+
+impl RunningTaskGuard {
+    /// 取消信号，值变为 true 时任务应尽快停止
+    pub fn cancel_signal(&self) -> watch::Receiver<bool> {
+        self.cancelled.clone()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+}
+
+impl Drop for RunningTaskGuard {
+    fn drop(&mut self) {
+        let mut running = RUNNING_TASKS.lock().unwrap();
+        if let Some(tasks) = running.get_mut(&self.project_code) {
+            tasks.remove(&self.task_id);
+            if tasks.is_empty() {
+                running.remove(&self.project_code);
+            }
+        }
+    }
+}
+
+/// 项目关闭凭证，持有期间项目下不能登记新任务，drop 时自动解除
+#[derive(Debug)]
+pub struct ProjectCloseGuard {
+    project_code: String,
+}
+
+impl Drop for ProjectCloseGuard {
+    fn drop(&mut self) {
+        let _running = RUNNING_TASKS.lock().unwrap();
+        CLOSED_PROJECTS.lock().unwrap().remove(&self.project_code);
+    }
+}
+
+/// 关闭项目，检查与关闭在同一把锁内完成；项目下仍有运行中的任务时返回这些任务，已被关闭时返回空列表
+pub fn try_close(project_code: &str) -> Result<ProjectCloseGuard, Vec<String>> {
+    let running = RUNNING_TASKS.lock().unwrap();
+    if let Some(tasks) = running.get(project_code).filter(|tasks| !tasks.is_empty()) {
+        return Err(tasks.keys().cloned().collect());
+    }
+    if !CLOSED_PROJECTS.lock().unwrap().insert(project_code.to_string()) {
+        return Err(Vec::new());
+    }
+    Ok(ProjectCloseGuard { project_code: project_code.to_string() })
+}
+
+/// 登记一个运行中的任务，同一任务已在运行或项目已被关闭时返回 None
+pub fn try_register(project_code: &str, task_id: &str) -> Option<RunningTaskGuard> {
+    let mut running = RUNNING_TASKS.lock().unwrap();
+    if CLOSED_PROJECTS.lock().unwrap().contains(project_code) {
+        return None;
+    }
+    let tasks = running.entry(project_code.to_string()).or_default();
+    if tasks.contains_key(task_id) {
+        return None;
+    }
+    let (sender, receiver) = watch::channel(false);
+    tasks.insert(task_id.to_string(), sender);
+    Some(RunningTaskGuard {
+        project_code: project_code.to_string(),
+        task_id: task_id.to_string(),
+        cancelled: receiver,
+    })
+}
+
+/// 向运行中的任务发送取消信号，任务未在运行时返回 false
+pub fn cancel(project_code: &str, task_id: &str) -> bool {
+    let running = RUNNING_TASKS.lock().unwrap();
+    match running.get(project_code).and_then(|tasks| tasks.get(task_id)) {
+        Some(sender) => {
+            sender.send_replace(true);
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn is_running(project_code: &str, task_id: &str) -> bool {
+    let running = RUNNING_TASKS.lock().unwrap();
+    running.get(project_code).map(|tasks| tasks.contains_key(task_id)).unwrap_or(false)
+}
+
+/// 等待取消信号；信号源已释放时永不返回
+pub async fn cancelled(cancel: &mut watch::Receiver<bool>) {
+    if cancel.wait_for(|c| *c).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use crate::repositories::{CollectionRepo, PipelineRepo, TaskRunRepo, TransformRepo};
+use crate::models::Error;
+use crate::models::task_run::{TaskRunReadOnly, TaskRunQuery, TaskRunLog, TaskRunLogQuery, TaskRunLogReadOnly, TaskRunLogPage, TaskType, RunStatus};
+use shaku::Provider;
+use async_trait::async_trait;
+use super::TaskRunService;
+use super::task_registry;
+
+// 单次拉取日志的默认与最大行数
+const DEFAULT_LOG_LIMIT: u32 = 500;
+const MAX_LOG_LIMIT: u32 = 2000;
+
+#[derive(Provider)]
+#[shaku(interface = TaskRunService)]
+pub struct TaskRunServiceImpl {
+    #[shaku(provide)]
+    repo: Box<dyn TaskRunRepo>,
+    #[shaku(provide)]
+    collection_repo: Box<dyn CollectionRepo>,
+    #[shaku(provide)]
+    pipeline_repo: Box<dyn PipelineRepo>,
+    #[shaku(provide)]
+    transform_repo: Box<dyn TransformRepo>,
+}
+
+#[async_trait]
+impl TaskRunService for TaskRunServiceImpl {
+    async fn list_run(&self, project_code: String, query: TaskRunQuery) -> Result<Vec<TaskRunReadOnly>, Error> {
+        let runs = self.repo.list_run(project_code.clone(), query).await?;
+        let names = self.task_names(project_code).await?;
+        Ok(runs
+            .into_iter()
+            .map(|run| {
+                let task_name = names.get(&(run.task_type, run.task_id.clone())).cloned().unwrap_or_default();
+                TaskRunReadOnly { task_name, ..TaskRunReadOnly::from(run) }
+            })
+            .collect())
+    }
+
+    async fn get_run(&self, project_code: String, id: String) -> Result<TaskRunReadOnly, Error> {
+        let run = self.repo.get_run(project_code.clone(), id).await?;
+        let names = self.task_names(project_code).await?;
+        let task_name = names.get(&(run.task_type, run.task_id.clone())).cloned().unwrap_or_default();
+        Ok(TaskRunReadOnly { task_name, ..TaskRunReadOnly::from(run) })
+    }
+
+    async fn get_run_log(&self, project_code: String, id: String, query: TaskRunLogQuery) -> Result<TaskRunLogPage, Error> {
+        // 先取运行状态再取日志：运行结束前日志已全部落库，读到结束状态时日志一定完整
+        let run = self.repo.get_run(project_code.clone(), id).await?;
+        let after = query.after.unwrap_or(0).max(0);
+        let limit = query.limit.unwrap_or(DEFAULT_LOG_LIMIT).clamp(1, MAX_LOG_LIMIT);
+        let logs = self.repo.list_run_log(project_code, run.id, after, limit).await?;
+
+        let next = logs.last().map(|l| l.seq).unwrap_or(after);
+        let finished = run.status != RunStatus::Running && (logs.len() as u32) < limit;
+        Ok(TaskRunLogPage {
+            lines: logs.into_iter().map(TaskRunLogReadOnly::from).collect(),
+            next,
+            finished,
+        })
+    }
+
+    async fn cancel_run(&self, project_code: String, id: String) -> Result<(), Error> {
+        let mut run = self.repo.get_run(project_code.clone(), id).await?;
+        if run.status != RunStatus::Running {
+            return Err(Error::InvalidValue(format!("run {} is not running", run.id)));
+        }
+
+        // 取消信号按任务登记，只有任务最近一次运行才可能是正在执行的那次
+        let latest = self.repo.list_run_by_task(project_code.clone(), run.task_id.clone(), 1).await?;
+        let is_latest = latest.first().map(|r| r.id == run.id).unwrap_or(false);
+        if is_latest && task_registry::cancel(&project_code, &run.task_id) {
+            return Ok(());
+        }
+
+        // 服务重启等原因遗留的运行记录，没有对应的执行，直接标记为已取消
+        let now = chrono::Utc::now();
+        run.status = RunStatus::Cancelled;
+        run.error_message = Some("run is no longer active".to_string());
+        run.duration_ms = (now - run.started_at).num_milliseconds();
+        run.finished_at = Some(now);
+        self.repo.finish_run(project_code, run).await
+    }
+
+    async fn add_run_log(&self, project_code: String, logs: Vec<TaskRunLog>) -> Result<(), Error> {
+        self.repo.add_run_log(project_code, logs).await
+    }
+}
+
+impl TaskRunServiceImpl {
+    // (任务类型, 任务ID) -> 任务名称
+    async fn task_names(&self, project_code: String) -> Result<HashMap<(TaskType, String), String>, Error> {
+        let collections = self.collection_repo.list_all_collection(project_code.clone()).await?;
+        let pipelines = self.pipeline_repo.list_all_pipeline(project_code.clone()).await?;
+        let transforms = self.transform_repo.list_all_transform(project_code).await?;
+        Ok(collections
+            .into_iter()
+            .map(|task| ((TaskType::Collection, task.id), task.name))
+            .chain(pipelines.into_iter().map(|p| ((TaskType::Pipeline, p.id), p.name)))
+            .chain(transforms.into_iter().map(|t| ((TaskType::Transform, t.id), t.name)))
+            .collect())
+    }
+}
@@ -0,0 +1,158 @@
+use crate::repositories::{PipelineRepo, ResourceRepo, TaskRunRepo, TransformRepo};
+use crate::models::{Error, Validator};
+use crate::models::web::PageQuery;
+use crate::models::resource::{Resource, ResourceType};
+use crate::models::transform::{TransformTask, TransformTaskReadOnly, TransformTaskCreateUpdate, TransformCheck};
+use crate::models::task_run::{TaskRun, TaskRunReadOnly, TaskType};
+use crate::utils::sql_parse;
+use shaku::Provider;
+use async_trait::async_trait;
+use super::TransformService;
+use super::{materialize, provision, task_registry};
+use crate::autofac;
+use uuid::Uuid;
+
+#[derive(Provider)]
+#[shaku(interface = TransformService)]
+pub struct TransformServiceImpl {
+    #[shaku(provide)]
+    repo: Box<dyn TransformRepo>,
+    #[shaku(provide)]
+    resource_repo: Box<dyn ResourceRepo>,
+    #[shaku(provide)]
+    run_repo: Box<dyn TaskRunRepo>,
+    #[shaku(provide)]
+    pipeline_repo: Box<dyn PipelineRepo>,
+}
+
+#[async_trait]
+impl TransformService for TransformServiceImpl {
+    async fn add_transform(&self, project_code: String, task: TransformTaskCreateUpdate) -> Result<String, Error> {
+        let mut task = TransformTask::from(task);
+        task.id = Uuid::new_v4().to_string();
+        let check = self.check(&task).await?;
+        task.referenced_tables = check.referenced_tables;
+        let id = self.repo.add_transform(project_code.clone(), task.clone()).await?;
+        record_lineage(project_code, task).await;
+        Ok(id)
+    }
+
+    async fn edit_transform(&self, project_code: String, task: TransformTaskCreateUpdate) -> Result<(), Error> {
+        let existing = self.repo.get_transform(project_code.clone(), task.id.clone()).await?;
+        let mut updated = TransformTask::from(task);
+        updated.created_at = existing.created_at;
+        let check = self.check(&updated).await?;
+        updated.referenced_tables = check.referenced_tables;
+        self.repo.edit_transform(project_code.clone(), updated.clone()).await?;
+        record_lineage(project_code, updated).await;
+        Ok(())
+    }
+
+    async fn del_transform(&self, project_code: String, id: String) -> Result<(), Error> {
+        let task = self.repo.get_transform(project_code.clone(), id).await?;
+        let pipelines = self.pipeline_repo.list_all_pipeline(project_code.clone()).await?;
+        if let Some(pipeline) = pipelines
+            .iter()
+            .find(|p| p.nodes.iter().any(|n| n.task_type == TaskType::Transform && n.task_id == task.id))
+        {
+            return Err(Error::InvalidValue(format!("transform task {} is used by pipeline {}", task.id, pipeline.name)));
+        }
+        let _guard = task_registry::try_register(&project_code, &task.id)
+            .ok_or_else(|| Error::InvalidValue(format!("transform task {} is running, stop it first", task.id)))?;
+        self.repo.del_transform(project_code.clone(), task.id.clone()).await?;
+        let result = autofac::get_global_app_state_ref()
+            .get_lineage_service()
+            .remove_task_lineage(project_code.clone(), TaskType::Transform, task.id.clone())
+            .await;
+        if let Err(e) = result {
+            tracing::warn!("failed to remove lineage of transform task {} in project {}: {}", task.id, project_code, e);
+        }
+        Ok(())
+    }
+
+    async fn get_transform(&self, project_code: String, id: String) -> Result<TransformTaskReadOnly, Error> {
+        let task = self.repo.get_transform(project_code, id).await?;
+        Ok(TransformTaskReadOnly::from(task))
+    }
+
+    async fn list_transform(&self, project_code: String, params: PageQuery) -> Result<Vec<TransformTaskReadOnly>, Error> {
+        let tasks = self.repo.list_transform(project_code, params).await?;
+        Ok(tasks.into_iter().map(TransformTaskReadOnly::from).collect())
+    }
+
+    // 解析但不保存，返回语法错误或引用表
+    async fn check_transform(&self, _project_code: String, task: TransformTaskCreateUpdate) -> Result<TransformCheck, Error> {
+        let mut task = TransformTask::from(task);
+        if task.id.is_empty() {
+            task.id = Uuid::new_v4().to_string();
+        }
+        self.check(&task).await
+    }
+
+    async fn run_transform(&self, project_code: String, id: String, biz_date: Option<String>) -> Result<String, Error> {
+        provision::check_project_active(&project_code).await?;
+        let task = self.repo.get_transform(project_code.clone(), id).await?;
+        let resource = self.check_resource(&task).await?;
+        let biz_date = match biz_date.filter(|d| !d.is_empty()) {
+            Some(date) => sql_parse::parse_biz_date(&date)?,
+            None => sql_parse::default_biz_date(),
+        };
+        let check = materialize::analyze(&task, biz_date)?;
+
+        // 同一任务不允许并发运行
+        let guard = task_registry::try_register(&project_code, &task.id)
+            .ok_or_else(|| Error::InvalidValue(format!("transform task {} is already running", task.id)))?;
+        let run = TaskRun::start(task.id.clone(), TaskType::Transform);
+        let run_id = self.run_repo.add_run(project_code.clone(), run.clone()).await?;
+
+        materialize::spawn_run(project_code, task, resource, biz_date, check, run, guard);
+        Ok(run_id)
+    }
+
+    async fn stop_transform(&self, project_code: String, id: String) -> Result<(), Error> {
+        let task = self.repo.get_transform(project_code.clone(), id).await?;
+        if !task_registry::cancel(&project_code, &task.id) {
+            return Err(Error::InvalidValue(format!("transform task {} is not running", task.id)));
+        }
+        Ok(())
+    }
+
+    async fn list_transform_run(&self, project_code: String, id: String) -> Result<Vec<TaskRunReadOnly>, Error> {
+        let runs = self.run_repo.list_run_by_task(project_code, id, 50).await?;
+        Ok(runs.into_iter().map(TaskRunReadOnly::from).collect())
+    }
+
+    async fn finish_transform_run(&self, project_code: String, run: TaskRun) -> Result<(), Error> {
+        self.run_repo.finish_run(project_code, run).await
+    }
+}
+
+impl TransformServiceImpl {
+    // 校验任务字段、执行资源，并按默认业务日期解析 SQL
+    async fn check(&self, task: &TransformTask) -> Result<TransformCheck, Error> {
+        task.validate()?;
+        self.check_resource(task).await?;
+        materialize::analyze(task, sql_parse::default_biz_date())
+    }
+
+    async fn check_resource(&self, task: &TransformTask) -> Result<Resource, Error> {
+        let resource = match self.resource_repo.get_resource(task.resource_id.clone()).await {
+            Ok(resource) => resource,
+            Err(Error::NotFound) => return Err(Error::InvalidValue(format!("resource {} does not exist", task.resource_id))),
+            Err(e) => return Err(e),
+        };
+        if !matches!(resource.resource_type, ResourceType::Mysql | ResourceType::Doris) {
+            return Err(Error::InvalidValue(format!("transform task cannot run on {} resource", resource.resource_type)));
+        }
+        Ok(resource)
+    }
+}
+
+// 血缘依赖元数据快照展开 *，解析失败只记录日志，不影响任务保存
+async fn record_lineage(project_code: String, task: TransformTask) {
+    let task_id = task.id.clone();
+    let result = autofac::get_global_app_state_ref().get_lineage_service().record_transform(project_code.clone(), task).await;
+    if let Err(e) = result {
+        tracing::warn!("lineage of transform task {} in project {} not recorded: {}", task_id, project_code, e);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::repositories::{AuthRepo, UserRepo};
+use crate::models::Error;
+use crate::models::Validator;
+use crate::models::web::PageQuery;
+use crate::models::user::{PasswordChangeRequest, User, UserForm, UserOption, UserStatus};
+use crate::utils::config::Setting;
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use std::sync::LazyLock;
+use shaku::Provider;
+use async_trait::async_trait;
+use super::UserService;
+
+
+#[derive(Provider)]
+#[shaku(interface = UserService)]
+pub struct UserServiceImpl {
+    #[shaku(provide)]
+    repo: Box<dyn UserRepo>,
+    #[shaku(provide)]
+    auth_repo: Box<dyn AuthRepo>,
+}
+
+impl UserServiceImpl {
+    // 只有启用状态的管理员可以管理用户
+    async fn require_admin(&self, operator: &str) -> Result<User, Error> {
+        let user = self.repo.get_user(operator.to_string()).await.map_err(|e| match e {
+            Error::NotFound => Error::PermissionDenied("user does not exist".to_string()),
+            e => e,
+        })?;
+        if !user.is_admin || user.status != UserStatus::Active {
+            return Err(Error::PermissionDenied("administrator required".to_string()));
+        }
+        Ok(user)
+    }
+
+    // 禁用、降级或删除用户后至少保留一个启用的管理员
+    async fn ensure_other_admin(&self, user: &User) -> Result<(), Error> {
+        if user.is_admin && user.status == UserStatus::Active && self.repo.count_active_admin().await? <= 1 {
+            return Err(Error::InvalidValue("at least one active administrator is required".to_string()));
+        }
+        Ok(())
+    }
+}
+
+
+#[async_trait]
+impl UserService for UserServiceImpl {
+    async fn authenticate(&self, username: String, password: String) -> Result<User, Error> {
+        let Some(user) = self.repo.find_user_by_username(username).await? else {
+            // 用户不存在时同样校验一次密码，避免通过响应时间判断账号是否存在
+            verify_password(password, DUMMY_PASSWORD_HASH.clone()).await?;
+            return Err(Error::InvalidCredentials);
+        };
+        if user.status == UserStatus::Disabled {
+            return Err(Error::AccountDisabled);
+        }
+        if let Some(locked_until) = user.locked_until.filter(|_| user.is_locked()) {
+            return Err(Error::AccountLocked(locked_until.to_rfc3339()));
+        }
+
+        let now = chrono::Utc::now();
+        if verify_password(password, user.password_hash.clone()).await? {
+            self.repo.record_login_success(user.id.clone(), now).await?;
+            return Ok(user);
+        }
+
+        // 连续失败达到上限后锁定一段时间，计数清零；并发登录时由数据库原子累加
+        let auth = &Setting::get().auth;
+        let locked_until = now + chrono::Duration::minutes(auth.lock_minutes);
+        self.repo.record_login_failure(user.id.clone(), auth.max_failed_attempts as i32, locked_until).await?;
+        let user = self.repo.get_user(user.id).await?;
+        if let Some(locked_until) = user.locked_until.filter(|_| user.is_locked()) {
+            tracing::warn!("用户 {} 连续登录失败，锁定至 {}", user.username, locked_until);
+            return Err(Error::AccountLocked(locked_until.to_rfc3339()));
+        }
+        Err(Error::InvalidCredentials)
+    }
+
+    async fn ensure_admin(&self) -> Result<(), Error> {
+        if self.repo.count_user().await? > 0 {
+            return Ok(());
+        }
+        let admin = &Setting::get().admin;
+        let user = User {
+            username: admin.username.clone(),
+            display_name: admin.username.clone(),
+            password_hash: hash_password(admin.password.clone()).await?,
+            is_admin: true,
+            status: UserStatus::Active,
+            ..Default::default()
+        };
+        self.repo.add_user(user).await?;
+        tracing::info!("已按配置创建初始管理员 {}", admin.username);
+        Ok(())
+    }
+
+    async fn get_current_user(&self, user_id: String) -> Result<User, Error> {
+        self.repo.get_user(user_id).await
+    }
+
+    async fn add_user(&self, operator: String, form: UserForm) -> Result<String, Error> {
+        self.require_admin(&operator).await?;
+        let form = UserForm { id: String::new(), ..form };
+        form.validate()?;
+        if self.repo.find_user_by_username(form.username.clone()).await?.is_some() {
+            return Err(Error::InvalidValue(format!("username {} already exists", form.username)));
+        }
+
+        let user = User {
+            username: form.username,
+            display_name: form.display_name,
+            password_hash: hash_password(form.password).await?,
+            is_admin: form.is_admin,
+            status: form.status,
+            ..Default::default()
+        };
+        self.repo.add_user(user).await
+    }
+
+    async fn edit_user(&self, operator: String, form: UserForm) -> Result<(), Error> {
+        self.require_admin(&operator).await?;
+        if form.id.is_empty() {
+            return Err(Error::EmptyValue("id".to_string()));
+        }
+        form.validate()?;
+        let mut user = self.repo.get_user(form.id.clone()).await?;
+        if user.id == operator && (!form.is_admin || form.status != UserStatus::Active) {
+            return Err(Error::InvalidValue("cannot disable or demote yourself".to_string()));
+        }
+        if !form.is_admin || form.status != UserStatus::Active {
+            self.ensure_other_admin(&user).await?;
+        }
+
+        // 登录名创建后不允许修改
+        user.display_name = form.display_name;
+        user.is_admin = form.is_admin;
+        user.status = form.status;
+        self.repo.edit_user(user).await?;
+        let reset_password = !form.password.is_empty();
+        if reset_password {
+            self.repo.update_password(form.id.clone(), hash_password(form.password).await?).await?;
+        }
+        // 禁用或重置密码后需要重新登录
+        if form.status != UserStatus::Active || reset_password {
+            self.auth_repo.revoke_user_token(form.id).await?;
+        }
+        Ok(())
+    }
+
+    async fn del_user(&self, operator: String, id: String) -> Result<(), Error> {
+        self.require_admin(&operator).await?;
+        if id == operator {
+            return Err(Error::InvalidValue("cannot delete yourself".to_string()));
+        }
+        let user = self.repo.get_user(id.clone()).await?;
+        self.ensure_other_admin(&user).await?;
+        self.auth_repo.revoke_user_token(id.clone()).await?;
+        self.repo.del_user(id).await
+    }
+
+    async fn list_user(&self, operator: String, params: PageQuery) -> Result<Vec<User>, Error> {
+        self.require_admin(&operator).await?;
+        self.repo.list_user(params).await
+    }
+
+    async fn list_user_option(&self) -> Result<Vec<UserOption>, Error> {
+        self.repo.list_user_option().await
+    }
+
+    async fn unlock_user(&self, operator: String, id: String) -> Result<(), Error> {
+        self.require_admin(&operator).await?;
+        self.repo.get_user(id.clone()).await?;
+        self.repo.unlock_user(id).await
+    }
+
+    async fn change_password(&self, user_id: String, request: PasswordChangeRequest) -> Result<(), Error> {
+        request.validate()?;
+        let user = self.repo.get_user(user_id).await?;
+        if !verify_password(request.old_password, user.password_hash.clone()).await? {
+            return Err(Error::InvalidValue("old password is incorrect".to_string()));
+        }
+        self.repo.update_password(user.id.clone(), hash_password(request.new_password).await?).await?;
+        // 其他设备上的登录会话失效，当前会话在访问 token 过期后也需重新登录
+        self.auth_repo.revoke_user_token(user.id).await
+    }
+}
+
+// Argon2 计算较慢，放到阻塞线程池中执行
+// 与真实密码哈希使用相同参数，仅用于消除用户不存在时的时间差
+static DUMMY_PASSWORD_HASH: LazyLock<String> = LazyLock::new(|| {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(b"dummy-password", &salt)
+        .map(|hash| hash.to_string())
+        .expect("hash dummy password")
+});
+
+async fn hash_password(password: String) -> Result<String, Error> {
+    tokio::task::spawn_blocking(move || {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| Error::InternalError(format!("hash password failed: {}", e)))
+    })
+    .await
+    .map_err(|e| Error::InternalError(e.to_string()))?
+}
+
+async fn verify_password(password: String, password_hash: String) -> Result<bool, Error> {
+    tokio::task::spawn_blocking(move || {
+        let hash = PasswordHash::new(&password_hash).map_err(|e| Error::InternalError(format!("invalid password hash: {}", e)))?;
+        Ok(Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+    })
+    .await
+    .map_err(|e| Error::InternalError(e.to_string()))?
+}
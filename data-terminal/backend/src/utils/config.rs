@@ -8,6 +8,20 @@ pub struct Setting {
     pub database: Database,
     pub admin: Admin,
     pub jwt: Jwt,
+    #[serde(default)]
+    pub health_check: HealthCheck,
+    #[serde(default)]
+    pub project: ProjectSetting,
+    #[serde(default)]
+    pub scheduler: SchedulerSetting,
+    #[serde(default)]
+    pub catalog: CatalogSetting,
+    #[serde(default)]
+    pub auth: AuthSetting,
+    #[serde(default)]
+    pub cookie: CookieSetting,
+    #[serde(default)]
+    pub probe: ProbeSetting,
 }
 
 
@@ -21,6 +35,7 @@ pub struct Database {
     pub database: String,
 }
 
+// 初始管理员账号，仅在用户表为空时用于创建第一个管理员
 #[derive(Debug, Deserialize, Clone)]
 pub struct Admin {
     pub username: String,
@@ -30,7 +45,130 @@ pub struct Admin {
 #[derive(Debug, Deserialize, Clone)]
 pub struct Jwt {
     pub secret: String,
+    // 访问 token 有效期（秒）
     pub expires: i64,
+    // 刷新 token 有效期（秒），每次刷新轮换并重新计时
+    #[serde(default = "default_refresh_expires")]
+    pub refresh_expires: i64,
+}
+
+fn default_refresh_expires() -> i64 {
+    7 * 24 * 3600
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct HealthCheck {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    // 单个项目同时探测的数据源数量
+    pub concurrency: usize,
+}
+
+impl Default for HealthCheck {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 300,
+            concurrency: 8,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ProjectSetting {
+    pub archive_retention_days: u32,
+}
+
+impl Default for ProjectSetting {
+    fn default() -> Self {
+        Self {
+            archive_retention_days: 30,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SchedulerSetting {
+    pub enabled: bool,
+    // 检查到期调度的间隔
+    pub tick_secs: u64,
+    // 晚于计划时间超过该值的触发视为错过，按调度的错过策略处理
+    pub misfire_grace_secs: u64,
+}
+
+impl Default for SchedulerSetting {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            tick_secs: 1,
+            misfire_grace_secs: 60,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct CatalogSetting {
+    pub enabled: bool,
+    // 元数据快照的间隔
+    pub interval_secs: u64,
+}
+
+impl Default for CatalogSetting {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 3600,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct AuthSetting {
+    // 连续登录失败达到该次数后锁定账号
+    pub max_failed_attempts: u32,
+    // 账号锁定时长
+    pub lock_minutes: i64,
+    pub min_password_length: usize,
+}
+
+impl Default for AuthSetting {
+    fn default() -> Self {
+        Self {
+            max_failed_attempts: 5,
+            lock_minutes: 15,
+            min_password_length: 8,
+        }
+    }
+}
+
+// 登录 cookie 选项，token 与刷新 token 默认不允许脚本读取
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct CookieSetting {
+    pub http_only: bool,
+    // 通过 HTTPS 访问时应开启
+    pub secure: bool,
+}
+
+impl Default for CookieSetting {
+    fn default() -> Self {
+        Self {
+            http_only: true,
+            secure: false,
+        }
+    }
+}
+
+// API 数据源探测选项，默认禁止访问内网与回环地址
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ProbeSetting {
+    pub allow_private_network: bool,
 }
 
 static SETTING: OnceCell<Setting> = OnceCell::new();
@@ -51,6 +189,35 @@ impl Setting {
 
     }
 
+    /// 测试使用的固定配置，不读取配置文件和环境变量
+    #[cfg(test)]
+    pub fn init_for_test() {
+        const TEST_SETTING: &str = r#"
+            [database]
+            prefix = "data_factory_"
+            host = "127.0.0.1"
+            port = 3306
+            user = "root"
+            password = ""
+            database = "data_factory_config"
+
+            [admin]
+            username = "admin"
+            password = "admin"
+
+            [jwt]
+            secret = "test"
+            expires = 3600
+        "#;
+        SETTING.get_or_init(|| {
+            Config::builder()
+                .add_source(File::from_str(TEST_SETTING, config::FileFormat::Toml))
+                .build()
+                .and_then(|cfg| cfg.try_deserialize())
+                .expect("invalid test setting")
+        });
+    }
+
     /// 获取全局配置的引用
     pub fn get() -> &'static Setting {
         SETTING.get().expect("Setting is not initialized")
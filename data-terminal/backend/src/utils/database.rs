@@ -1,7 +1,7 @@
 use std::{collections::HashMap, sync::LazyLock};
 use tokio::sync::RwLock;
 
-use sqlx::{mysql::MySqlPoolOptions, MySqlPool};
+use sqlx::{mysql::MySqlPoolOptions, Executor, MySqlPool};
 use crate::utils::config::Setting;
 
 static DB_POOL_MAP: LazyLock<RwLock<HashMap<String, MySqlPool>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
@@ -44,16 +44,73 @@ pub async fn get_config_db() -> Result<MySqlPool, sqlx::Error> {
     Ok(pool)
 }
 
+// 项目库名：配置前缀 + 项目编码
+pub fn project_db_name(code: &str) -> String {
+    format!("{}{}", Setting::get().database.prefix, code)
+}
+
+// 模版库按项目库规则命名，项目库迁移时以该编码代表模版库
+pub const TEMPLATE_PROJECT_CODE: &str = "template";
+
+// 拼接后会指向配置库或模版库的项目编码
+const RESERVED_PROJECT_CODES: &[&str] = &["config", TEMPLATE_PROJECT_CODE];
+
+// 配置库与模版库不能被当作项目库使用或删除
+pub fn is_system_database(name: &str) -> bool {
+    name.eq_ignore_ascii_case(&Setting::get().database.database)
+        || name.eq_ignore_ascii_case(&project_db_name(TEMPLATE_PROJECT_CODE))
+}
+
+pub fn is_reserved_project_code(code: &str) -> bool {
+    RESERVED_PROJECT_CODES.iter().any(|c| c.eq_ignore_ascii_case(code)) || is_system_database(&project_db_name(code))
+}
+
+// 项目编码会拼接进库名，必须与 Project::validate 的规则一致
+fn check_project_code(code: &str) -> Result<(), sqlx::Error> {
+    let re = regex::Regex::new(r"^[a-zA-Z][a-zA-Z0-9_]*$").unwrap();
+    if !re.is_match(code) || is_reserved_project_code(code) {
+        return Err(sqlx::Error::Configuration(format!("invalid project code: {}", code).into()));
+    }
+    Ok(())
+}
+
+// 创建项目数据库（已存在时不做任何处理）
+pub async fn create_project_db(code: &str) -> Result<(), sqlx::Error> {
+    check_project_code(code)?;
+    let pool = get_config_db().await?;
+    let sql = format!(
+        "CREATE DATABASE IF NOT EXISTS `{}` DEFAULT CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci",
+        project_db_name(code)
+    );
+    pool.execute(sql.as_str()).await?;
+    Ok(())
+}
+
 // 获取项目数据库
-pub async fn get_project_db(_code: String) -> Result<MySqlPool, sqlx::Error> {
+pub async fn get_project_db(code: String) -> Result<MySqlPool, sqlx::Error> {
 
-    // 先写死使用模版库
-    let code = "template".to_string();
+    if code.is_empty() {
+        return Err(sqlx::Error::Configuration("no project selected".into()));
+    }
+    check_project_code(&code)?;
 
+    get_db(project_db_name(&code)).await
+}
 
-    let db_prefix = &Setting::get().database.prefix;
-    let db_name = format!("{}{}", db_prefix, code);
+// 项目级迁移作用的库名：模版编码对应模版库，其余按项目编码校验
+pub fn migration_db_name(code: &str) -> Result<String, sqlx::Error> {
+    if code != TEMPLATE_PROJECT_CODE {
+        check_project_code(code)?;
+    }
+    Ok(project_db_name(code))
+}
+
+// 获取项目级迁移作用的库，模版库与项目库统一由此获取
+pub async fn get_migration_db(code: &str) -> Result<MySqlPool, sqlx::Error> {
+    get_db(migration_db_name(code)?).await
+}
 
+async fn get_db(db_name: String) -> Result<MySqlPool, sqlx::Error> {
     // First, check if pool exists, otherwise spawn blocking and await connection creation
     {
         let db_pool_map = DB_POOL_MAP.read().await;
@@ -73,3 +130,92 @@ pub async fn get_project_db(_code: String) -> Result<MySqlPool, sqlx::Error> {
     db_pool_map.insert(db_name.clone(), pool.clone());
     Ok(pool)
 }
+
+// 移除并关闭缓存的项目库连接池
+pub async fn evict_project_db(code: &str) {
+    let pool = DB_POOL_MAP.write().await.remove(&project_db_name(code));
+    if let Some(pool) = pool {
+        pool.close().await;
+    }
+}
+
+// 库名只允许字母、数字、下划线，避免拼接 SQL 时被注入
+fn check_db_name(name: &str) -> Result<(), sqlx::Error> {
+    let re = regex::Regex::new(r"^[a-zA-Z0-9_]+$").unwrap();
+    if !re.is_match(name) || name.len() > 64 {
+        return Err(sqlx::Error::Configuration(format!("invalid database name: {}", name).into()));
+    }
+    Ok(())
+}
+
+pub async fn database_exists(name: &str) -> Result<bool, sqlx::Error> {
+    let pool = get_config_db().await?;
+    let exists: Option<String> = sqlx::query_scalar("SELECT CAST(SCHEMA_NAME AS CHAR) FROM information_schema.SCHEMATA WHERE SCHEMA_NAME = ?")
+        .bind(name)
+        .fetch_optional(&pool)
+        .await?;
+    Ok(exists.is_some())
+}
+
+pub async fn drop_database(name: &str) -> Result<(), sqlx::Error> {
+    check_db_name(name)?;
+    let pool = get_config_db().await?;
+    let sql = format!("DROP DATABASE IF EXISTS `{}`", name);
+    pool.execute(sql.as_str()).await?;
+    Ok(())
+}
+
+// RENAME TABLE 无法迁移的对象：视图、存储过程、函数、触发器、事件
+pub async fn list_unarchivable_objects(name: &str) -> Result<Vec<String>, sqlx::Error> {
+    let pool = get_config_db().await?;
+    let sql = "SELECT CAST(CONCAT('view ', TABLE_NAME) AS CHAR) FROM information_schema.VIEWS WHERE TABLE_SCHEMA = ? \
+        UNION ALL SELECT CAST(CONCAT(LOWER(ROUTINE_TYPE), ' ', ROUTINE_NAME) AS CHAR) FROM information_schema.ROUTINES WHERE ROUTINE_SCHEMA = ? \
+        UNION ALL SELECT CAST(CONCAT('trigger ', TRIGGER_NAME) AS CHAR) FROM information_schema.TRIGGERS WHERE TRIGGER_SCHEMA = ? \
+        UNION ALL SELECT CAST(CONCAT('event ', EVENT_NAME) AS CHAR) FROM information_schema.EVENTS WHERE EVENT_SCHEMA = ?";
+    sqlx::query_scalar(sql)
+        .bind(name)
+        .bind(name)
+        .bind(name)
+        .bind(name)
+        .fetch_all(&pool)
+        .await
+}
+
+// MySQL 不支持直接重命名库，通过逐表 RENAME TABLE 迁移到归档库后删除原库
+// 重复执行时只迁移剩余的表，可用于继续中断的归档
+pub async fn archive_database(source: &str, target: &str) -> Result<(), sqlx::Error> {
+    check_db_name(source)?;
+    check_db_name(target)?;
+    let pool = get_config_db().await?;
+
+    // 这些对象会随原库一起被删除，存在时拒绝归档
+    let objects = list_unarchivable_objects(source).await?;
+    if !objects.is_empty() {
+        return Err(sqlx::Error::Configuration(
+            format!("database {} contains objects that cannot be archived: {}", source, objects.join(", ")).into(),
+        ));
+    }
+
+    let sql = format!("CREATE DATABASE IF NOT EXISTS `{}` DEFAULT CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci", target);
+    pool.execute(sql.as_str()).await?;
+
+    let tables: Vec<String> = sqlx::query_scalar(
+        "SELECT CAST(TABLE_NAME AS CHAR) FROM information_schema.TABLES WHERE TABLE_SCHEMA = ? AND TABLE_TYPE = 'BASE TABLE'",
+    )
+    .bind(source)
+    .fetch_all(&pool)
+    .await?;
+
+    if !tables.is_empty() {
+        let renames = tables
+            .iter()
+            .map(|t| format!("`{}`.`{}` TO `{}`.`{}`", source, t, target, t))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!("RENAME TABLE {}", renames);
+        pool.execute(sql.as_str()).await?;
+    }
+
+    drop_database(source).await
+}
+
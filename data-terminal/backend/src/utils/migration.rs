@@ -0,0 +1,388 @@
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{Executor, MySqlPool};
+
+use crate::utils::sql_script::{is_database_statement, split_alter_clauses, split_statements};
+
+// 迁移脚本作用的数据库
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationTarget {
+    // 全局配置库 data_factory_config
+    Config,
+    // 每个项目库 data_factory_<code>
+    Project,
+}
+
+pub struct Migration {
+    pub version: &'static str,
+    pub script: &'static str,
+    pub target: MigrationTarget,
+    pub sql: &'static str,
+}
+
+impl Migration {
+    pub fn checksum(&self) -> String {
+        format!("{:x}", Sha256::digest(self.sql.as_bytes()))
+    }
+}
+
+// 内嵌的迁移脚本，按版本顺序执行；已发布的脚本不允许修改，否则校验和会不一致
+pub static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: "v1.0.0",
+        script: "init_config.sql",
+        target: MigrationTarget::Config,
+        sql: include_str!("../../../../migrations/v1.0.0/init_config.sql"),
+    },
+    Migration {
+        version: "v1.0.0",
+        script: "init_project.sql",
+        target: MigrationTarget::Project,
+        sql: include_str!("../../../../migrations/v1.0.0/init_project.sql"),
+    },
+    Migration {
+        version: "v1.0.1",
+        script: "upgrade_config.sql",
+        target: MigrationTarget::Config,
+        sql: include_str!("../../../../migrations/v1.0.1/upgrade_config.sql"),
+    },
+    Migration {
+        version: "v1.0.1",
+        script: "upgrade_project.sql",
+        target: MigrationTarget::Project,
+        sql: include_str!("../../../../migrations/v1.0.1/upgrade_project.sql"),
+    },
+    Migration {
+        version: "v1.0.2",
+        script: "upgrade_project.sql",
+        target: MigrationTarget::Project,
+        sql: include_str!("../../../../migrations/v1.0.2/upgrade_project.sql"),
+    },
+    Migration {
+        version: "v1.0.3",
+        script: "upgrade_project.sql",
+        target: MigrationTarget::Project,
+        sql: include_str!("../../../../migrations/v1.0.3/upgrade_project.sql"),
+    },
+    Migration {
+        version: "v1.0.4",
+        script: "upgrade_project.sql",
+        target: MigrationTarget::Project,
+        sql: include_str!("../../../../migrations/v1.0.4/upgrade_project.sql"),
+    },
+    Migration {
+        version: "v1.0.5",
+        script: "upgrade_project.sql",
+        target: MigrationTarget::Project,
+        sql: include_str!("../../../../migrations/v1.0.5/upgrade_project.sql"),
+    },
+    Migration {
+        version: "v1.0.6",
+        script: "upgrade_project.sql",
+        target: MigrationTarget::Project,
+        sql: include_str!("../../../../migrations/v1.0.6/upgrade_project.sql"),
+    },
+    Migration {
+        version: "v1.0.7",
+        script: "upgrade_project.sql",
+        target: MigrationTarget::Project,
+        sql: include_str!("../../../../migrations/v1.0.7/upgrade_project.sql"),
+    },
+    Migration {
+        version: "v1.0.8",
+        script: "upgrade_project.sql",
+        target: MigrationTarget::Project,
+        sql: include_str!("../../../../migrations/v1.0.8/upgrade_project.sql"),
+    },
+    Migration {
+        version: "v1.0.9",
+        script: "upgrade_project.sql",
+        target: MigrationTarget::Project,
+        sql: include_str!("../../../../migrations/v1.0.9/upgrade_project.sql"),
+    },
+    Migration {
+        version: "v1.0.10",
+        script: "upgrade_project.sql",
+        target: MigrationTarget::Project,
+        sql: include_str!("../../../../migrations/v1.0.10/upgrade_project.sql"),
+    },
+    Migration {
+        version: "v1.0.11",
+        script: "upgrade_project.sql",
+        target: MigrationTarget::Project,
+        sql: include_str!("../../../../migrations/v1.0.11/upgrade_project.sql"),
+    },
+    Migration {
+        version: "v1.0.12",
+        script: "upgrade_config.sql",
+        target: MigrationTarget::Config,
+        sql: include_str!("../../../../migrations/v1.0.12/upgrade_config.sql"),
+    },
+    Migration {
+        version: "v1.0.13",
+        script: "upgrade_config.sql",
+        target: MigrationTarget::Config,
+        sql: include_str!("../../../../migrations/v1.0.13/upgrade_config.sql"),
+    },
+    Migration {
+        version: "v1.0.14",
+        script: "upgrade_config.sql",
+        target: MigrationTarget::Config,
+        sql: include_str!("../../../../migrations/v1.0.14/upgrade_config.sql"),
+    },
+    Migration {
+        version: "v1.0.15",
+        script: "upgrade_config.sql",
+        target: MigrationTarget::Config,
+        sql: include_str!("../../../../migrations/v1.0.15/upgrade_config.sql"),
+    },
+    Migration {
+        version: "v1.0.16",
+        script: "upgrade_config.sql",
+        target: MigrationTarget::Config,
+        sql: include_str!("../../../../migrations/v1.0.16/upgrade_config.sql"),
+    },
+];
+
+// 手工执行过脚本的库没有版本记录，重复执行时忽略“表已存在”“字段已存在”
+const IGNORABLE_SQLSTATES: &[&str] = &["42S01", "42S21"];
+
+const CREATE_VERSION_TABLE: &str = "create table if not exists df_c_schema_version
+(
+    version      varchar(32)  not null comment '版本号',
+    script       varchar(128) not null comment '脚本名称',
+    checksum     char(64)     not null comment '脚本 SHA-256 校验和',
+    execution_ms bigint       not null default 0 comment '执行耗时（毫秒）',
+    applied_at   timestamp    not null default current_timestamp comment '执行时间',
+    primary key (version, script)
+) comment '数据库版本表' engine = InnoDB";
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    ChecksumMismatch,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MigrationStatus {
+    pub version: String,
+    pub script: String,
+    pub checksum: String,
+    pub state: MigrationState,
+    pub applied_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AppliedMigration {
+    version: String,
+    script: String,
+    checksum: String,
+    applied_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum MigrationError {
+    #[error("checksum mismatch for {version}/{script}, applied script has been modified")]
+    ChecksumMismatch { version: String, script: String },
+
+    #[error("{version}/{script} failed: {source}")]
+    ScriptFailed {
+        version: String,
+        script: String,
+        source: sqlx::Error,
+    },
+
+    #[error("database error: {0}")]
+    Db(#[from] sqlx::Error),
+}
+
+/// 查询目标库中每个迁移脚本的状态
+pub async fn status(pool: &MySqlPool, target: MigrationTarget) -> Result<Vec<MigrationStatus>, MigrationError> {
+    pool.execute(CREATE_VERSION_TABLE).await?;
+    let applied = sqlx::query_as::<_, AppliedMigration>("SELECT version, script, checksum, applied_at FROM df_c_schema_version")
+        .fetch_all(pool)
+        .await?;
+
+    let statuses = MIGRATIONS
+        .iter()
+        .filter(|m| m.target == target)
+        .map(|m| {
+            let checksum = m.checksum();
+            let record = applied.iter().find(|a| a.version == m.version && a.script == m.script);
+            let state = match record {
+                Some(a) if a.checksum == checksum => MigrationState::Applied,
+                Some(_) => MigrationState::ChecksumMismatch,
+                None => MigrationState::Pending,
+            };
+            MigrationStatus {
+                version: m.version.to_string(),
+                script: m.script.to_string(),
+                checksum,
+                state,
+                applied_at: record.map(|a| a.applied_at),
+            }
+        })
+        .collect();
+
+    Ok(statuses)
+}
+
+/// 执行目标库中尚未执行的迁移脚本，dry_run 时只返回待执行的脚本
+pub async fn migrate(pool: &MySqlPool, target: MigrationTarget, dry_run: bool) -> Result<Vec<MigrationStatus>, MigrationError> {
+    let statuses = status(pool, target).await?;
+
+    if let Some(mismatch) = statuses.iter().find(|s| s.state == MigrationState::ChecksumMismatch) {
+        return Err(MigrationError::ChecksumMismatch {
+            version: mismatch.version.clone(),
+            script: mismatch.script.clone(),
+        });
+    }
+
+    let pending: Vec<MigrationStatus> = statuses.into_iter().filter(|s| s.state == MigrationState::Pending).collect();
+    if dry_run {
+        return Ok(pending);
+    }
+
+    for item in &pending {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.target == target && m.version == item.version && m.script == item.script)
+            .expect("pending migration must exist");
+        let started = std::time::Instant::now();
+
+        // 脚本以固定库名编写，切换库的语句由连接本身替代
+        for statement in split_statements(migration.sql).iter().filter(|s| !is_database_statement(s)) {
+            if let Err(e) = execute_statement(pool, statement).await {
+                return Err(MigrationError::ScriptFailed {
+                    version: item.version.clone(),
+                    script: item.script.clone(),
+                    source: e,
+                });
+            }
+        }
+
+        sqlx::query("INSERT INTO df_c_schema_version (version, script, checksum, execution_ms) VALUES (?, ?, ?, ?)")
+            .bind(&item.version)
+            .bind(&item.script)
+            .bind(&item.checksum)
+            .bind(started.elapsed().as_millis() as i64)
+            .execute(pool)
+            .await?;
+        tracing::info!("applied migration {}/{} ({})", item.version, item.script, target);
+    }
+
+    Ok(pending)
+}
+
+fn is_ignorable(e: &sqlx::Error) -> bool {
+    matches!(e, sqlx::Error::Database(db)
+        if db.code().map(|c| IGNORABLE_SQLSTATES.contains(&c.as_ref())).unwrap_or(false))
+}
+
+// 多子句的 ALTER TABLE 整体失败不会生效任何子句，遇到可忽略的错误时逐个子句重试，
+// 只跳过已存在的字段，避免同一语句中新增的其他字段被一并跳过
+async fn execute_statement(pool: &MySqlPool, statement: &str) -> Result<(), sqlx::Error> {
+    let e = match pool.execute(statement).await {
+        Ok(_) => return Ok(()),
+        Err(e) if is_ignorable(&e) => e,
+        Err(e) => return Err(e),
+    };
+    let clauses = match split_alter_clauses(statement) {
+        Some(clauses) if clauses.len() > 1 => clauses,
+        _ => {
+            tracing::debug!("ignored migration statement error: {}", e);
+            return Ok(());
+        }
+    };
+    for clause in clauses {
+        match pool.execute(clause.as_str()).await {
+            Ok(_) => {}
+            Err(e) if is_ignorable(&e) => tracing::debug!("ignored migration statement error: {}", e),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// 对全局配置库执行迁移
+pub async fn migrate_config_db(dry_run: bool) -> Result<Vec<MigrationStatus>, MigrationError> {
+    let pool = crate::utils::database::get_config_db().await?;
+    migrate(&pool, MigrationTarget::Config, dry_run).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MIGRATIONS, Migration, MigrationTarget};
+    use crate::utils::sql_script::{is_database_statement, split_statements};
+
+    fn version_key(version: &str) -> Vec<u32> {
+        version.trim_start_matches('v').split('.').map(|n| n.parse().unwrap()).collect()
+    }
+
+    fn find(version: &str, script: &str) -> &'static Migration {
+        MIGRATIONS.iter().find(|m| m.version == version && m.script == script).unwrap()
+    }
+
+    #[test]
+    fn checksum_is_sha256_hex_of_script() {
+        let migration = Migration { version: "v0", script: "test.sql", target: MigrationTarget::Config, sql: "select 1;" };
+        assert_eq!(migration.checksum(), "354b7196c9ba5fb4b21cf615bb6ec4cd5c07503c34229feef033fc081a8c03f4");
+        for m in MIGRATIONS {
+            let checksum = m.checksum();
+            assert_eq!(checksum.len(), 64, "{}/{}", m.version, m.script);
+            assert!(checksum.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+            assert_eq!(checksum, m.checksum());
+        }
+    }
+
+    #[test]
+    fn released_scripts_are_unchanged() {
+        // 已执行过的库按校验和比对，修改已发布的脚本会导致所有库迁移失败
+        assert_eq!(
+            find("v1.0.0", "init_config.sql").checksum(),
+            "05a283719fcb08f61983f91f4ee227bcd735ae671aec4df67ec3cb4f85e5effc"
+        );
+        assert_eq!(
+            find("v1.0.0", "init_project.sql").checksum(),
+            "dd02c3cb36bff802d1f7ffa594b41f0ae4e131aab92ed4f0c4495d64ce87581b"
+        );
+    }
+
+    #[test]
+    fn migrations_are_unique_and_ordered() {
+        for target in [MigrationTarget::Config, MigrationTarget::Project] {
+            let scripts: Vec<&Migration> = MIGRATIONS.iter().filter(|m| m.target == target).collect();
+            assert!(!scripts.is_empty());
+            for (i, m) in scripts.iter().enumerate() {
+                assert!(
+                    scripts[i + 1..].iter().all(|n| (n.version, n.script) != (m.version, m.script)),
+                    "duplicate migration {}/{}",
+                    m.version,
+                    m.script
+                );
+            }
+            assert!(scripts.windows(2).all(|w| version_key(w[0].version) <= version_key(w[1].version)));
+        }
+        assert!(MIGRATIONS.windows(2).all(|w| version_key(w[0].version) <= version_key(w[1].version)));
+    }
+
+    #[test]
+    fn scripts_switch_to_their_target_database() {
+        for m in MIGRATIONS {
+            let expected = match m.target {
+                MigrationTarget::Config => "use data_factory_config",
+                MigrationTarget::Project => "use data_factory_template",
+            };
+            // 初始化脚本先建库再切换，升级脚本直接切换
+            let statements = split_statements(m.sql);
+            assert!(is_database_statement(&statements[0]), "{}/{}", m.version, m.script);
+            let switch = statements.iter().find(|s| s.to_lowercase().starts_with("use ")).unwrap();
+            assert_eq!(switch.to_lowercase(), expected, "{}/{}", m.version, m.script);
+        }
+    }
+}
@@ -1,3 +1,6 @@
 pub mod config;
 pub mod database;
 pub mod sqlx_helper;
+pub mod sql_script;
+pub mod sql_parse;
+pub mod migration;
@@ -0,0 +1,464 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::ControlFlow;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use sqlparser::ast::{Expr, ObjectName, Query, Select, SelectItem, SetExpr, Statement, TableFactor, Visit, Visitor};
+use sqlparser::dialect::{Dialect, MySqlDialect};
+use sqlparser::parser::Parser;
+
+use crate::models::Error;
+use crate::utils::sql_script::split_statements;
+
+static PARAM_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap());
+
+/// 内置参数：业务日期默认为运行日的前一天
+pub fn builtin_params(biz_date: chrono::NaiveDate) -> BTreeMap<String, String> {
+    let today = chrono::Local::now().date_naive();
+    BTreeMap::from([
+        ("biz_date".to_string(), biz_date.format("%Y-%m-%d").to_string()),
+        ("biz_date_nodash".to_string(), biz_date.format("%Y%m%d").to_string()),
+        ("run_date".to_string(), today.format("%Y-%m-%d").to_string()),
+    ])
+}
+
+pub fn default_biz_date() -> chrono::NaiveDate {
+    chrono::Local::now().date_naive() - chrono::Duration::days(1)
+}
+
+pub fn parse_biz_date(value: &str) -> Result<chrono::NaiveDate, Error> {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| Error::InvalidValue(format!("biz_date {} must be in yyyy-mm-dd format", value)))
+}
+
+/// 合并内置参数与自定义参数，自定义参数的值中可以引用内置参数，同名时自定义参数优先
+pub fn resolve_params(custom: &BTreeMap<String, String>, biz_date: chrono::NaiveDate) -> Result<BTreeMap<String, String>, Error> {
+    let builtin = builtin_params(biz_date);
+    let mut params = builtin.clone();
+    for (name, value) in custom {
+        let (value, _) = render(value, &builtin)
+            .map_err(|e| Error::InvalidValue(format!("parameter {}: {}", name, e)))?;
+        params.insert(name.clone(), value);
+    }
+    Ok(params)
+}
+
+/// 替换文本中的 ${参数}，返回替换结果和用到的参数名，引用未定义的参数时报错
+pub fn render(text: &str, params: &BTreeMap<String, String>) -> Result<(String, Vec<String>), Error> {
+    let mut used = BTreeSet::new();
+    let mut undefined = BTreeSet::new();
+    let rendered = PARAM_RE.replace_all(text, |caps: &regex::Captures| {
+        let name = &caps[1];
+        match params.get(name) {
+            Some(value) => {
+                used.insert(name.to_string());
+                value.clone()
+            }
+            None => {
+                undefined.insert(name.to_string());
+                caps[0].to_string()
+            }
+        }
+    });
+    if !undefined.is_empty() {
+        let names: Vec<String> = undefined.into_iter().map(|n| format!("${{{}}}", n)).collect();
+        return Err(Error::InvalidValue(format!("undefined parameter {}", names.join(", "))));
+    }
+    Ok((rendered.into_owned(), used.into_iter().collect()))
+}
+
+/// 解析单条 SELECT（可带 WITH）语句，返回去掉注释与结尾分号的 SQL 及语法树
+pub fn parse_select(sql: &str) -> Result<(String, Box<Query>), Error> {
+    match parse_single(sql, &MySqlDialect {})? {
+        (sql, Statement::Query(query)) => Ok((sql, query)),
+        _ => Err(Error::InvalidValue("only a SELECT statement is allowed".to_string())),
+    }
+}
+
+/// 校验单条只读语句（查询、EXPLAIN 查询、DESCRIBE、SHOW），返回去掉注释与结尾分号的 SQL
+pub fn parse_read_only(sql: &str, dialect: &dyn Dialect) -> Result<String, Error> {
+    let (sql, statement) = parse_single(sql, dialect)?;
+    let read_only = match &statement {
+        Statement::Query(query) => is_plain_query(query),
+        Statement::Explain { statement, .. } => matches!(statement.as_ref(), Statement::Query(query) if is_plain_query(query)),
+        Statement::ExplainTable { .. }
+        | Statement::ShowTables { .. }
+        | Statement::ShowColumns { .. }
+        | Statement::ShowDatabases { .. }
+        | Statement::ShowSchemas { .. }
+        | Statement::ShowViews { .. }
+        | Statement::ShowCreate { .. }
+        | Statement::ShowVariable { .. }
+        | Statement::ShowVariables { .. }
+        | Statement::ShowStatus { .. }
+        | Statement::ShowFunctions { .. }
+        | Statement::ShowCollation { .. } => true,
+        _ => false,
+    };
+    if !read_only {
+        return Err(Error::InvalidValue("only read-only statements (SELECT, EXPLAIN, DESCRIBE, SHOW) are allowed".to_string()));
+    }
+    Ok(sql)
+}
+
+// 排除 SELECT ... FOR UPDATE 与 SELECT ... INTO
+fn is_plain_query(query: &Query) -> bool {
+    query.locks.is_empty()
+        && match query.body.as_ref() {
+            SetExpr::Select(select) => select.into.is_none(),
+            _ => true,
+        }
+}
+
+fn parse_single(sql: &str, dialect: &dyn Dialect) -> Result<(String, Statement), Error> {
+    let statements = split_statements(sql);
+    let [statement] = statements.as_slice() else {
+        return Err(Error::InvalidValue(format!("expected exactly one SQL statement, found {}", statements.len())));
+    };
+    let mut parsed = Parser::parse_sql(dialect, statement)
+        .map_err(|e| Error::InvalidValue(format!("SQL syntax error: {}", e)))?;
+    match (parsed.pop(), parsed.is_empty()) {
+        (Some(parsed), true) => Ok((statement.clone(), parsed)),
+        _ => Err(Error::InvalidValue("expected exactly one SQL statement".to_string())),
+    }
+}
+
+// 收集 FROM/JOIN 中的表名与 WITH 定义的公用表表达式名
+#[derive(Default)]
+struct RelationCollector {
+    relations: Vec<String>,
+    ctes: HashSet<String>,
+}
+
+impl Visitor for RelationCollector {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                self.ctes.insert(cte.alias.name.value.to_lowercase());
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<Self::Break> {
+        self.relations.push(relation.to_string().replace(['`', '"'], ""));
+        ControlFlow::Continue(())
+    }
+}
+
+/// 查询引用的物理表，去重并排除公用表表达式，带库名时为 "库.表"
+pub fn referenced_tables(query: &Query) -> Vec<String> {
+    let mut collector = RelationCollector::default();
+    let _ = query.visit(&mut collector);
+    let tables: BTreeSet<String> = collector
+        .relations
+        .into_iter()
+        .filter(|name| name.contains('.') || !collector.ctes.contains(&name.to_lowercase()))
+        .collect();
+    tables.into_iter().collect()
+}
+
+/// 输出列引用的来源列，table 为 "库.表" 或 "表"，column 为 "*" 时表示整表展开
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ColumnRef {
+    pub table: String,
+    pub column: String,
+    // 未限定表名且 FROM 中有多张表时无法确定来源，需要结合表结构确认
+    pub ambiguous: bool,
+}
+
+/// 查询的一个输出列，name 为 "*" 时表示未展开的整表
+#[derive(Debug, Clone, Default)]
+pub struct OutputColumn {
+    pub name: String,
+    // 直接引用列时为空，否则为计算表达式
+    pub expression: String,
+    pub sources: Vec<ColumnRef>,
+}
+
+// FROM/JOIN 中的一个关系：物理表，或子查询与公用表表达式的输出列
+enum Relation {
+    Table(String),
+    Derived(Vec<OutputColumn>),
+}
+
+/// 解析查询各输出列引用的物理表列，子查询与公用表表达式会向下追溯到物理表
+pub fn column_lineage(query: &Query) -> Vec<OutputColumn> {
+    query_lineage(query, &HashMap::new())
+}
+
+fn query_lineage(query: &Query, ctes: &HashMap<String, Vec<OutputColumn>>) -> Vec<OutputColumn> {
+    let mut ctes = ctes.clone();
+    if let Some(with) = &query.with {
+        for cte in &with.cte_tables {
+            let columns = query_lineage(&cte.query, &ctes);
+            ctes.insert(cte.alias.name.value.to_lowercase(), columns);
+        }
+    }
+    set_expr_lineage(&query.body, &ctes)
+}
+
+fn set_expr_lineage(body: &SetExpr, ctes: &HashMap<String, Vec<OutputColumn>>) -> Vec<OutputColumn> {
+    match body {
+        SetExpr::Select(select) => select_lineage(select, ctes),
+        SetExpr::Query(query) => query_lineage(query, ctes),
+        // UNION 等集合运算按位置合并来源，列名取第一个分支；含 * 时无法对齐，直接拼接
+        SetExpr::SetOperation { left, right, .. } => {
+            let mut columns = set_expr_lineage(left, ctes);
+            let right = set_expr_lineage(right, ctes);
+            if columns.iter().chain(right.iter()).any(|c| c.name == "*") {
+                columns.extend(right);
+                return columns;
+            }
+            for (column, other) in columns.iter_mut().zip(right) {
+                column.sources.extend(other.sources);
+                column.sources.sort();
+                column.sources.dedup();
+                if column.expression.is_empty() {
+                    column.expression = other.expression;
+                }
+            }
+            columns
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn select_lineage(select: &Select, ctes: &HashMap<String, Vec<OutputColumn>>) -> Vec<OutputColumn> {
+    // (小写别名, 关系)，没有别名的物理表以表名作为别名
+    let mut scope = Vec::new();
+    for table in &select.from {
+        collect_relation(&table.relation, ctes, &mut scope);
+        for join in &table.joins {
+            collect_relation(&join.relation, ctes, &mut scope);
+        }
+    }
+
+    let mut columns = Vec::new();
+    for item in &select.projection {
+        match item {
+            SelectItem::UnnamedExpr(expr) => columns.push(OutputColumn {
+                name: expr_name(expr),
+                expression: expression_text(expr),
+                sources: resolve_expr(expr, &scope),
+            }),
+            SelectItem::ExprWithAlias { expr, alias } => columns.push(OutputColumn {
+                name: alias.value.clone(),
+                expression: expression_text(expr),
+                sources: resolve_expr(expr, &scope),
+            }),
+            SelectItem::Wildcard(_) => {
+                for (_, relation) in &scope {
+                    columns.extend(expand(relation));
+                }
+            }
+            SelectItem::QualifiedWildcard(..) => {
+                let text = item.to_string();
+                let qualifier = text.split_once(".*").map(|(q, _)| q).unwrap_or_default().replace(['`', '"'], "");
+                if let Some(relation) = find_relation(&scope, &qualifier) {
+                    columns.extend(expand(relation));
+                }
+            }
+        }
+    }
+    columns
+}
+
+fn collect_relation(factor: &TableFactor, ctes: &HashMap<String, Vec<OutputColumn>>, scope: &mut Vec<(String, Relation)>) {
+    match factor {
+        TableFactor::Table { name, alias, .. } => {
+            let name = name.to_string().replace(['`', '"'], "");
+            let relation = match ctes.get(&name.to_lowercase()) {
+                Some(columns) if !name.contains('.') => Relation::Derived(columns.clone()),
+                _ => Relation::Table(name.clone()),
+            };
+            let alias = alias.as_ref().map(|a| a.name.value.clone()).unwrap_or(name);
+            scope.push((alias.to_lowercase(), relation));
+        }
+        TableFactor::Derived { subquery, alias, .. } => {
+            let alias = alias.as_ref().map(|a| a.name.value.to_lowercase()).unwrap_or_default();
+            scope.push((alias, Relation::Derived(query_lineage(subquery, ctes))));
+        }
+        TableFactor::NestedJoin { table_with_joins, .. } => {
+            collect_relation(&table_with_joins.relation, ctes, scope);
+            for join in &table_with_joins.joins {
+                collect_relation(&join.relation, ctes, scope);
+            }
+        }
+        _ => {}
+    }
+}
+
+// 限定名可以是别名、"库.表" 或省略库名的表名
+fn find_relation<'a>(scope: &'a [(String, Relation)], qualifier: &str) -> Option<&'a Relation> {
+    let qualifier = qualifier.to_lowercase();
+    scope
+        .iter()
+        .find(|(alias, _)| *alias == qualifier || alias.rsplit('.').next() == Some(qualifier.as_str()))
+        .map(|(_, relation)| relation)
+}
+
+fn expand(relation: &Relation) -> Vec<OutputColumn> {
+    match relation {
+        Relation::Table(table) => vec![OutputColumn {
+            name: "*".to_string(),
+            expression: String::new(),
+            sources: vec![ColumnRef { table: table.clone(), column: "*".to_string(), ambiguous: false }],
+        }],
+        Relation::Derived(columns) => columns.clone(),
+    }
+}
+
+fn expr_name(expr: &Expr) -> String {
+    match expr {
+        Expr::Identifier(ident) => ident.value.clone(),
+        Expr::CompoundIdentifier(parts) => parts.last().map(|p| p.value.clone()).unwrap_or_default(),
+        _ => expr.to_string(),
+    }
+}
+
+fn expression_text(expr: &Expr) -> String {
+    match expr {
+        Expr::Identifier(_) | Expr::CompoundIdentifier(_) => String::new(),
+        _ => expr.to_string(),
+    }
+}
+
+// 收集表达式中的列引用：(限定名, 列名)
+#[derive(Default)]
+struct IdentCollector {
+    idents: Vec<(Option<String>, String)>,
+}
+
+impl Visitor for IdentCollector {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+        match expr {
+            Expr::Identifier(ident) => self.idents.push((None, ident.value.clone())),
+            Expr::CompoundIdentifier(parts) => {
+                if let Some((column, qualifier)) = parts.split_last() {
+                    let qualifier: Vec<&str> = qualifier.iter().map(|p| p.value.as_str()).collect();
+                    self.idents.push((Some(qualifier.join(".")).filter(|q| !q.is_empty()), column.value.clone()));
+                }
+            }
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+fn resolve_expr(expr: &Expr, scope: &[(String, Relation)]) -> Vec<ColumnRef> {
+    let mut collector = IdentCollector::default();
+    let _ = expr.visit(&mut collector);
+    let mut sources = Vec::new();
+    for (qualifier, column) in collector.idents {
+        match qualifier {
+            Some(qualifier) => {
+                if let Some(relation) = find_relation(scope, &qualifier) {
+                    sources.extend(resolve_column(relation, &column, false));
+                }
+            }
+            None => {
+                let ambiguous = scope.len() > 1;
+                for (_, relation) in scope {
+                    sources.extend(resolve_column(relation, &column, ambiguous));
+                }
+            }
+        }
+    }
+    sources.sort();
+    sources.dedup();
+    sources
+}
+
+fn resolve_column(relation: &Relation, column: &str, ambiguous: bool) -> Vec<ColumnRef> {
+    match relation {
+        Relation::Table(table) => vec![ColumnRef { table: table.clone(), column: column.to_string(), ambiguous }],
+        Relation::Derived(columns) => {
+            if let Some(output) = columns.iter().find(|c| c.name.eq_ignore_ascii_case(column)) {
+                return output.sources.clone();
+            }
+            // 子查询以 * 输出时按同名列继续追溯
+            let tables: Vec<&ColumnRef> = columns.iter().filter(|c| c.name == "*").flat_map(|c| c.sources.iter()).collect();
+            let ambiguous = ambiguous || tables.len() > 1;
+            tables
+                .into_iter()
+                .map(|source| ColumnRef { table: source.table.clone(), column: column.to_string(), ambiguous: ambiguous || source.ambiguous })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_select_accepts_cte_and_union() {
+        let (sql, query) = parse_select("-- 日汇总\nWITH d AS (SELECT id, amount FROM orders) SELECT id FROM d UNION ALL SELECT id FROM refunds;").unwrap();
+        assert!(sql.starts_with("WITH d AS"));
+        assert!(!sql.ends_with(';'));
+        assert_eq!(referenced_tables(&query), vec!["orders".to_string(), "refunds".to_string()]);
+    }
+
+    #[test]
+    fn parse_select_rejects_ddl_and_dml() {
+        for sql in [
+            "INSERT INTO t SELECT * FROM s",
+            "UPDATE t SET a = 1",
+            "DELETE FROM t",
+            "DROP TABLE t",
+            "CREATE TABLE t AS SELECT 1",
+        ] {
+            assert!(parse_select(sql).is_err(), "{} should be rejected", sql);
+        }
+    }
+
+    #[test]
+    fn parse_select_rejects_multiple_statements() {
+        let err = parse_select("SELECT 1; DROP TABLE t").unwrap_err().to_string();
+        assert!(err.contains("exactly one"), "{}", err);
+    }
+
+    #[test]
+    fn parse_read_only_accepts_queries_and_metadata_statements() {
+        let dialect = MySqlDialect {};
+        for sql in [
+            "SELECT 1",
+            "WITH a AS (SELECT 1 AS x) SELECT x FROM a",
+            "SELECT a FROM t UNION SELECT a FROM s",
+            "EXPLAIN SELECT * FROM t",
+            "DESCRIBE t",
+            "SHOW TABLES",
+            "SHOW CREATE TABLE t",
+        ] {
+            assert!(parse_read_only(sql, &dialect).is_ok(), "{} should be allowed", sql);
+        }
+    }
+
+    #[test]
+    fn parse_read_only_rejects_writes_and_locks() {
+        let dialect = MySqlDialect {};
+        for sql in [
+            "INSERT INTO t VALUES (1)",
+            "UPDATE t SET a = 1",
+            "DELETE FROM t",
+            "TRUNCATE TABLE t",
+            "DROP TABLE t",
+            "ALTER TABLE t ADD COLUMN b INT",
+            "SELECT * FROM t FOR UPDATE",
+            "EXPLAIN DELETE FROM t",
+        ] {
+            assert!(parse_read_only(sql, &dialect).is_err(), "{} should be rejected", sql);
+        }
+    }
+
+    #[test]
+    fn semicolon_inside_string_is_not_a_statement_break() {
+        let sql = parse_read_only("SELECT 'a;b' AS v", &MySqlDialect {}).unwrap();
+        assert_eq!(sql, "SELECT 'a;b' AS v");
+    }
+}
@@ -0,0 +1,193 @@
+/// 将 SQL 脚本拆分为单条语句，去掉注释与空语句
+///
+/// 仅处理迁移脚本中会出现的语法：单/双/反引号字符串、`--` 行注释与 `/* */` 块注释
+pub fn split_statements(script: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut chars = script.chars().peekable();
+    let mut quote: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            current.push(c);
+            if c == '\\' && q != '`' {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match c {
+            '\'' | '"' | '`' => {
+                quote = Some(c);
+                current.push(c);
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                // 行注释，跳到行尾
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        current.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for next in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+                current.push(' ');
+            }
+            ';' => {
+                let statement = current.trim();
+                if !statement.is_empty() {
+                    statements.push(statement.to_string());
+                }
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+
+    let statement = current.trim();
+    if !statement.is_empty() {
+        statements.push(statement.to_string());
+    }
+    statements
+}
+
+/// 判断语句是否为切换/创建库的语句，这类语句在指定库内执行脚本时需要跳过
+pub fn is_database_statement(statement: &str) -> bool {
+    let lower = statement.trim_start().to_lowercase();
+    lower.starts_with("use ") || lower.starts_with("create database") || lower.starts_with("create schema")
+}
+
+/// 将包含多个子句的 ALTER TABLE 拆分为每个子句一条语句，非 ALTER TABLE 语句返回 None
+///
+/// 用于逐个执行子句，以便只跳过其中已存在的字段
+pub fn split_alter_clauses(statement: &str) -> Option<Vec<String>> {
+    let re = regex::Regex::new(r"(?is)^\s*alter\s+table\s+((?:`[^`]+`|[\w$]+)(?:\.(?:`[^`]+`|[\w$]+))?)\s+(.+)$").unwrap();
+    let caps = re.captures(statement)?;
+    let table = &caps[1];
+    let body = &caps[2];
+
+    // 按顶层逗号拆分，忽略引号与括号内的逗号
+    let mut clauses = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            current.push(c);
+            if c == '\\' && q != '`' {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' | '`' => {
+                quote = Some(c);
+                current.push(c);
+            }
+            '(' => {
+                depth += 1;
+                current.push(c);
+            }
+            ')' => {
+                depth = depth.saturating_sub(1);
+                current.push(c);
+            }
+            ',' if depth == 0 => {
+                clauses.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    clauses.push(current.trim().to_string());
+
+    Some(
+        clauses
+            .into_iter()
+            .filter(|c| !c.is_empty())
+            .map(|c| format!("alter table {} {}", table, c))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_semicolons_and_drops_empty_statements() {
+        let statements = split_statements("use db;\n\ncreate table a (id int);;\n  select 1  ");
+        assert_eq!(statements, vec!["use db", "create table a (id int)", "select 1"]);
+    }
+
+    #[test]
+    fn keeps_semicolons_inside_quotes() {
+        let statements = split_statements(
+            "insert into t values ('a;b', \"c;d\");\nselect `x;y` from t;\ninsert into t values ('it\\'s;', 'o''k;')",
+        );
+        assert_eq!(
+            statements,
+            vec![
+                "insert into t values ('a;b', \"c;d\")",
+                "select `x;y` from t",
+                "insert into t values ('it\\'s;', 'o''k;')",
+            ]
+        );
+    }
+
+    #[test]
+    fn strips_line_and_block_comments() {
+        let statements = split_statements(
+            "-- 建表; 注释中的分号\ncreate table a (id int); /* 块注释;\n跨行 */ select 1; -- 结尾注释",
+        );
+        assert_eq!(statements, vec!["create table a (id int)", "select 1"]);
+    }
+
+    #[test]
+    fn comment_markers_inside_strings_are_kept() {
+        let statements = split_statements("select '-- not a comment', '/* nor this */' from t");
+        assert_eq!(statements, vec!["select '-- not a comment', '/* nor this */' from t"]);
+    }
+
+    #[test]
+    fn recognizes_database_statements() {
+        assert!(is_database_statement("use data_factory_config"));
+        assert!(is_database_statement("  CREATE DATABASE IF NOT EXISTS x"));
+        assert!(!is_database_statement("create table t (id int)"));
+    }
+
+    #[test]
+    fn splits_alter_table_clauses() {
+        let clauses = split_alter_clauses(
+            "alter table df_c_task add column a varchar(16) not null default 'x,y' comment 'a, b', add column b decimal(10, 2) null, add index idx_ab (a, b)",
+        )
+        .unwrap();
+        assert_eq!(
+            clauses,
+            vec![
+                "alter table df_c_task add column a varchar(16) not null default 'x,y' comment 'a, b'",
+                "alter table df_c_task add column b decimal(10, 2) null",
+                "alter table df_c_task add index idx_ab (a, b)",
+            ]
+        );
+        assert_eq!(split_alter_clauses("ALTER TABLE `db`.`t` ADD c int").unwrap(), vec!["alter table `db`.`t` ADD c int"]);
+        assert!(split_alter_clauses("create table t (a int, b int)").is_none());
+    }
+}
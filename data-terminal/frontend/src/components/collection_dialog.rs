@@ -0,0 +1,368 @@
+use dioxus::prelude::*;
+use crate::models::collection::{CollectionCategory, CollectType, CollectionTaskCreateUpdate, CollectionModalMode};
+use crate::models::datasource::{DataSource, DataSourceCategory, DataSourceType};
+use crate::models::metadata::{DataSourceCatalog, TableDetail};
+use crate::models::resource::Resource;
+use crate::components::datasource_schema_tree::{fetch_catalog, fetch_table_detail};
+
+// 将选中的表与列写入规则 JSON 的 tables，已存在的源表只更新列
+fn merge_table_rule(rule_text: &str, table: &str, columns: Vec<String>) -> Result<String, String> {
+    let mut rule = serde_json::from_str::<serde_json::Value>(rule_text).map_err(|_| "采集规则必须是合法的 JSON 对象".to_string())?;
+    let Some(rule_obj) = rule.as_object_mut() else {
+        return Err("采集规则必须是合法的 JSON 对象".to_string());
+    };
+    let tables = rule_obj.entry("tables").or_insert_with(|| serde_json::json!([]));
+    let Some(tables) = tables.as_array_mut() else {
+        return Err("采集规则中的 tables 必须是数组".to_string());
+    };
+    match tables.iter_mut().find(|t| t.get("source_table").and_then(|v| v.as_str()) == Some(table)) {
+        Some(existing) => existing["columns"] = serde_json::json!(columns),
+        None => tables.push(serde_json::json!({ "source_table": table, "columns": columns })),
+    }
+    serde_json::to_string_pretty(&rule).map_err(|e| e.to_string())
+}
+
+#[component]
+pub fn CollectionDialog(
+    mode: CollectionModalMode,
+    datasources: Vec<DataSource>,
+    resources: Vec<Resource>,
+    on_close: EventHandler<()>,
+    on_save: EventHandler<CollectionTaskCreateUpdate>,
+) -> Element {
+    let is_edit_mode = matches!(mode, CollectionModalMode::Edit(_));
+
+    // 初始化表单数据
+    let mut form = use_signal(|| match &mode {
+        CollectionModalMode::Add => CollectionTaskCreateUpdate {
+            id: String::new(),
+            name: String::new(),
+            description: String::new(),
+            category: CollectionCategory::Database,
+            collect_type: CollectType::Full,
+            datasource_id: String::new(),
+            resource_id: String::new(),
+            rule: serde_json::json!({}),
+        },
+        CollectionModalMode::Edit(task) => CollectionTaskCreateUpdate {
+            id: task.id.clone(),
+            name: task.name.clone(),
+            description: task.description.clone(),
+            category: task.category,
+            collect_type: task.collect_type,
+            datasource_id: task.datasource_id.clone(),
+            resource_id: task.resource_id.clone(),
+            rule: task.rule.clone(),
+        },
+    });
+    let mut rule_text = use_signal(|| serde_json::to_string_pretty(&form().rule).unwrap_or_else(|_| "{}".to_string()));
+    let mut error_msg = use_signal(String::new);
+
+    // 数据库数据源的库表目录，用于选择源表与列
+    let mut catalog = use_signal(|| None as Option<DataSourceCatalog>);
+    let mut pick_table = use_signal(String::new);
+    let mut pick_detail = use_signal(|| None as Option<TableDetail>);
+    let mut pick_columns = use_signal(Vec::<String>::new);
+    let selected_datasource = use_memo(move || form().datasource_id.clone());
+
+    use_effect({
+        let datasources = datasources.clone();
+        move || {
+            let datasource_id = selected_datasource();
+            let supported = datasources
+                .iter()
+                .any(|ds| ds.id == datasource_id && matches!(ds.datasource_type, DataSourceType::Mysql | DataSourceType::Postgres));
+            catalog.set(None);
+            pick_table.set(String::new());
+            pick_detail.set(None);
+            if !supported {
+                return;
+            }
+            spawn(async move {
+                match fetch_catalog(&datasource_id, false).await {
+                    Ok(data) => catalog.set(Some(data)),
+                    Err(e) => error_msg.set(format!("读取数据源库表失败: {}", e)),
+                }
+            });
+        }
+    });
+
+    let handle_pick_table = move |table: String| {
+        pick_table.set(table.clone());
+        pick_detail.set(None);
+        pick_columns.set(Vec::new());
+        let Some(schema) = catalog.peek().as_ref().map(|c| c.default_schema.clone()) else {
+            return;
+        };
+        if table.is_empty() {
+            return;
+        }
+        spawn(async move {
+            match fetch_table_detail(&selected_datasource.peek(), &schema, &table, false).await {
+                Ok(detail) => {
+                    pick_columns.set(detail.columns.iter().map(|c| c.name.clone()).collect());
+                    pick_detail.set(Some(detail));
+                }
+                Err(e) => error_msg.set(format!("读取表结构失败: {}", e)),
+            }
+        });
+    };
+
+    let handle_add_table = move |_| {
+        let Some(detail) = pick_detail() else {
+            return;
+        };
+        if pick_columns().is_empty() {
+            error_msg.set("请至少选择一列".to_string());
+            return;
+        }
+        // 按表中顺序输出列；全选时不列出列名，表示采集全部列
+        let columns: Vec<String> = if pick_columns().len() == detail.columns.len() {
+            Vec::new()
+        } else {
+            detail.columns.iter().filter(|c| pick_columns().contains(&c.name)).map(|c| c.name.clone()).collect()
+        };
+        match merge_table_rule(&rule_text(), &detail.name, columns) {
+            Ok(text) => {
+                rule_text.set(text);
+                error_msg.set(String::new());
+            }
+            Err(e) => error_msg.set(e),
+        }
+    };
+
+    // 按采集分类过滤可选数据源
+    let candidate_datasources: Vec<DataSource> = datasources
+        .iter()
+        .filter(|ds| match form().category {
+            CollectionCategory::Database => ds.category == DataSourceCategory::Database,
+            CollectionCategory::Api => ds.category == DataSourceCategory::Api,
+            CollectionCategory::Crawler => true,
+        })
+        .cloned()
+        .collect();
+
+    let handle_save = move |_| {
+        let mut data = form();
+        if data.name.trim().is_empty() {
+            error_msg.set("请输入任务名称".to_string());
+            return;
+        }
+        if data.datasource_id.is_empty() {
+            error_msg.set("请选择数据源".to_string());
+            return;
+        }
+        if data.resource_id.is_empty() {
+            error_msg.set("请选择目标资源".to_string());
+            return;
+        }
+        match serde_json::from_str::<serde_json::Value>(&rule_text()) {
+            Ok(rule) if rule.is_object() => data.rule = rule,
+            _ => {
+                error_msg.set("采集规则必须是合法的 JSON 对象".to_string());
+                return;
+            }
+        }
+        error_msg.set(String::new());
+        on_save.call(data);
+    };
+
+    rsx! {
+        dialog {
+            class: "modal modal-open",
+            div {
+                class: "modal-box max-w-2xl",
+                h3 {
+                    class: "text-lg font-bold mb-4",
+                    if is_edit_mode { "编辑采集任务" } else { "新建采集任务" }
+                }
+
+                div { class: "space-y-4",
+                    div { class: "form-control",
+                        label { class: "label", span { class: "label-text", "任务名称" } }
+                        input {
+                            class: "input input-bordered w-full",
+                            placeholder: "请输入任务名称",
+                            value: "{form().name}",
+                            oninput: move |e| form.with_mut(|f| f.name = e.value()),
+                        }
+                    }
+
+                    div { class: "form-control",
+                        label { class: "label", span { class: "label-text", "任务描述" } }
+                        textarea {
+                            class: "textarea textarea-bordered w-full",
+                            placeholder: "请输入任务描述",
+                            value: "{form().description}",
+                            oninput: move |e| form.with_mut(|f| f.description = e.value()),
+                        }
+                    }
+
+                    div { class: "grid grid-cols-2 gap-4",
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "采集分类" } }
+                            select {
+                                class: "select select-bordered w-full",
+                                onchange: move |e| form.with_mut(|f| {
+                                    f.category = match e.value().as_str() {
+                                        "api" => CollectionCategory::Api,
+                                        "crawler" => CollectionCategory::Crawler,
+                                        _ => CollectionCategory::Database,
+                                    };
+                                    // 分类变化后原数据源可能不再匹配
+                                    f.datasource_id = String::new();
+                                }),
+                                option { value: "database", selected: form().category == CollectionCategory::Database, "数据库" }
+                                option { value: "api", selected: form().category == CollectionCategory::Api, "API" }
+                                option { value: "crawler", selected: form().category == CollectionCategory::Crawler, "爬虫" }
+                            }
+                        }
+
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "采集类型" } }
+                            select {
+                                class: "select select-bordered w-full",
+                                onchange: move |e| form.with_mut(|f| {
+                                    f.collect_type = match e.value().as_str() {
+                                        "incremental" => CollectType::Incremental,
+                                        "stream" => CollectType::Stream,
+                                        _ => CollectType::Full,
+                                    };
+                                }),
+                                option { value: "full", selected: form().collect_type == CollectType::Full, "全量" }
+                                option { value: "incremental", selected: form().collect_type == CollectType::Incremental, "增量" }
+                                option { value: "stream", selected: form().collect_type == CollectType::Stream, "实时" }
+                            }
+                        }
+                    }
+
+                    div { class: "grid grid-cols-2 gap-4",
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "数据源" } }
+                            select {
+                                class: "select select-bordered w-full",
+                                onchange: move |e| form.with_mut(|f| f.datasource_id = e.value()),
+                                option { value: "", disabled: true, selected: form().datasource_id.is_empty(), "请选择数据源" }
+                                for ds in candidate_datasources.iter() {
+                                    option {
+                                        value: "{ds.id}",
+                                        selected: form().datasource_id == ds.id,
+                                        "{ds.name}"
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "目标资源" } }
+                            select {
+                                class: "select select-bordered w-full",
+                                onchange: move |e| form.with_mut(|f| f.resource_id = e.value()),
+                                option { value: "", disabled: true, selected: form().resource_id.is_empty(), "请选择目标资源" }
+                                for resource in resources.iter() {
+                                    option {
+                                        value: "{resource.id}",
+                                        selected: form().resource_id == resource.id,
+                                        "{resource.name}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(data) = catalog() {
+                        div { class: "form-control",
+                            label { class: "label",
+                                span { class: "label-text", "源表" }
+                                span { class: "label-text-alt", "{data.default_schema}" }
+                            }
+                            div { class: "flex gap-2",
+                                select {
+                                    class: "select select-bordered flex-1",
+                                    onchange: move |e| handle_pick_table(e.value()),
+                                    option { value: "", selected: pick_table().is_empty(), "请选择源表" }
+                                    for schema in data.schemas.iter().filter(|s| s.name == data.default_schema) {
+                                        for table in schema.tables.iter() {
+                                            option {
+                                                value: "{table.name}",
+                                                selected: pick_table() == table.name,
+                                                if table.comment.is_empty() { "{table.name}" } else { "{table.name}（{table.comment}）" }
+                                            }
+                                        }
+                                    }
+                                }
+                                button {
+                                    class: "btn btn-outline",
+                                    disabled: pick_detail().is_none(),
+                                    onclick: handle_add_table,
+                                    "加入规则"
+                                }
+                            }
+                            if let Some(detail) = pick_detail() {
+                                div { class: "flex flex-wrap gap-x-4 gap-y-1 mt-2 max-h-32 overflow-auto",
+                                    for column in detail.columns.iter() {
+                                        {
+                                            let name = column.name.clone();
+                                            let checked = pick_columns().contains(&column.name);
+                                            rsx! {
+                                                label { key: "{column.name}", class: "label cursor-pointer gap-1 p-0",
+                                                    input {
+                                                        r#type: "checkbox",
+                                                        class: "checkbox checkbox-xs",
+                                                        checked: checked,
+                                                        onchange: move |_| pick_columns.with_mut(|cols| {
+                                                            if let Some(pos) = cols.iter().position(|c| *c == name) {
+                                                                cols.remove(pos);
+                                                            } else {
+                                                                cols.push(name.clone());
+                                                            }
+                                                        }),
+                                                    }
+                                                    span { class: "label-text font-mono text-xs", title: "{column.column_type}", "{column.name}" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    div { class: "form-control",
+                        label { class: "label", span { class: "label-text", "采集规则 (JSON)" } }
+                        textarea {
+                            class: "textarea textarea-bordered w-full font-mono h-40",
+                            value: "{rule_text}",
+                            oninput: move |e| rule_text.set(e.value()),
+                        }
+                    }
+
+                    if !error_msg().is_empty() {
+                        p { class: "text-error text-sm", "{error_msg()}" }
+                    }
+                }
+
+                div {
+                    class: "modal-action",
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| on_close.call(()),
+                        "取消"
+                    }
+                    button {
+                        class: "btn btn-info",
+                        onclick: handle_save,
+                        "保存"
+                    }
+                }
+            }
+
+            form {
+                method: "dialog",
+                class: "modal-backdrop",
+                onclick: move |_| on_close.call(()),
+                button { "close" }
+            }
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use dioxus::prelude::*;
+use crate::models::collection::{CollectionTask, CollectionWatermark, CollectionCheckpoint, CollectType};
+use crate::models::{protocol::ApiResponse, task_run::TaskRun};
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+
+#[component]
+pub fn CollectionRunDialog(
+    task: CollectionTask,
+    on_close: EventHandler<()>,
+) -> Element {
+    let mut runs = use_signal(|| Vec::<TaskRun>::new());
+    let mut watermarks = use_signal(|| Vec::<CollectionWatermark>::new());
+    let mut error_msg = use_signal(String::new);
+    let task_id = task.id.clone();
+    let mut checkpoint = use_signal(|| None as Option<CollectionCheckpoint>);
+    let incremental = task.collect_type == CollectType::Incremental;
+    let stream = task.collect_type == CollectType::Stream;
+
+    // 获取运行记录
+    let fetch_runs = move || {
+        let task_id = task_id.clone();
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+            match client.get(&format!("/api/v1/collection/{}/runs", task_id), Some(req_config.clone())).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<Vec<TaskRun>>>(&response_text) {
+                    Ok(api_response) if api_response.result => runs.set(api_response.data),
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => error_msg.set(e.to_string()),
+            }
+            if stream {
+                match client.get(&format!("/api/v1/collection/{}/checkpoint", task_id), Some(req_config.clone())).await {
+                    Ok(response_text) => match serde_json::from_str::<ApiResponse<Option<CollectionCheckpoint>>>(&response_text) {
+                        Ok(api_response) if api_response.result => checkpoint.set(api_response.data),
+                        Ok(api_response) => error_msg.set(api_response.msg),
+                        Err(e) => error_msg.set(e.to_string()),
+                    },
+                    Err(e) => error_msg.set(e.to_string()),
+                }
+            }
+            if !incremental {
+                return;
+            }
+            match client.get(&format!("/api/v1/collection/{}/watermark", task_id), Some(req_config)).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<Vec<CollectionWatermark>>>(&response_text) {
+                    Ok(api_response) if api_response.result => watermarks.set(api_response.data),
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => error_msg.set(e.to_string()),
+            }
+        });
+    };
+
+    // 重置水位，source_table 为空时重置全部表
+    let reset_watermark = {
+        let task_id = task.id.clone();
+        let fetch_runs = fetch_runs.clone();
+        move |source_table: String| {
+            let task_id = task_id.clone();
+            let fetch_runs = fetch_runs.clone();
+            spawn(async move {
+                let client = crate::utils::request::create_client("http://localhost:3000");
+                let mut builder = RequestBuilder::new()
+                    .header("Content-Type", "application/json")
+                    .header("Cookie", &cookie::get_browser_cookies());
+                if !source_table.is_empty() {
+                    builder = builder.query_param("source_table", source_table);
+                }
+                match client.post(&format!("/api/v1/collection/{}/watermark/reset", task_id), Some(builder.build()), ()).await {
+                    Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                        Ok(api_response) if api_response.result => {
+                            error_msg.set(String::new());
+                            fetch_runs();
+                        }
+                        Ok(api_response) => error_msg.set(api_response.msg),
+                        Err(e) => error_msg.set(e.to_string()),
+                    },
+                    Err(e) => error_msg.set(e.to_string()),
+                }
+            });
+        }
+    };
+
+    // 重置消费位点，下次运行从源库当前位置开始
+    let reset_checkpoint = {
+        let task_id = task.id.clone();
+        let fetch_runs = fetch_runs.clone();
+        move |_: MouseEvent| {
+            let task_id = task_id.clone();
+            let fetch_runs = fetch_runs.clone();
+            spawn(async move {
+                let client = crate::utils::request::create_client("http://localhost:3000");
+                let req_config = RequestBuilder::new()
+                    .header("Content-Type", "application/json")
+                    .header("Cookie", &cookie::get_browser_cookies())
+                    .build();
+                match client.post(&format!("/api/v1/collection/{}/checkpoint/reset", task_id), Some(req_config), ()).await {
+                    Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                        Ok(api_response) if api_response.result => {
+                            error_msg.set(String::new());
+                            checkpoint.set(None);
+                            fetch_runs();
+                        }
+                        Ok(api_response) => error_msg.set(api_response.msg),
+                        Err(e) => error_msg.set(e.to_string()),
+                    },
+                    Err(e) => error_msg.set(e.to_string()),
+                }
+            });
+        }
+    };
+
+    let refresh = fetch_runs.clone();
+    use_effect(move || {
+        refresh();
+    });
+
+    rsx! {
+        dialog {
+            class: "modal modal-open",
+            div {
+                class: "modal-box max-w-4xl",
+                div { class: "flex justify-between items-center mb-4",
+                    h3 { class: "text-lg font-bold", "运行记录 - {task.name}" }
+                    button {
+                        class: "btn btn-ghost btn-sm",
+                        onclick: move |_| fetch_runs(),
+                        "刷新"
+                    }
+                }
+
+                if !error_msg().is_empty() {
+                    p { class: "text-error text-sm mb-2", "{error_msg()}" }
+                }
+
+                if stream {
+                    div { class: "mb-4",
+                        div { class: "flex justify-between items-center mb-2",
+                            h4 { class: "font-semibold", "消费位点" }
+                            button {
+                                class: "btn btn-ghost btn-xs text-error",
+                                disabled: checkpoint().is_none(),
+                                onclick: reset_checkpoint,
+                                "重置"
+                            }
+                        }
+                        if let Some(cp) = checkpoint() {
+                            div { class: "bg-base-200 rounded p-2 text-xs font-mono break-all",
+                                "{cp.checkpoint}"
+                                div { class: "text-base-content/60 mt-1", "更新时间：{cp.updated_at}" }
+                            }
+                        } else {
+                            div { class: "text-center text-base-content/60 py-4", "尚未记录位点，下次运行将从源库当前位置开始" }
+                        }
+                    }
+                }
+
+                if incremental {
+                    div { class: "mb-4",
+                        div { class: "flex justify-between items-center mb-2",
+                            h4 { class: "font-semibold", "采集水位" }
+                            button {
+                                class: "btn btn-ghost btn-xs text-error",
+                                disabled: watermarks().is_empty(),
+                                onclick: {
+                                    let reset_watermark = reset_watermark.clone();
+                                    move |_| reset_watermark(String::new())
+                                },
+                                "全部重置"
+                            }
+                        }
+                        table { class: "table table-sm",
+                            thead {
+                                tr {
+                                    th { "源表" }
+                                    th { "水位列" }
+                                    th { "水位值" }
+                                    th { "更新时间" }
+                                    th { "操作" }
+                                }
+                            }
+                            tbody {
+                                for watermark in watermarks().iter() {
+                                    tr { key: "{watermark.source_table}",
+                                        td { "{watermark.source_table}" }
+                                        td { "{watermark.watermark_column}" }
+                                        td { class: "break-all", "{watermark.value_text()}" }
+                                        td { "{watermark.updated_at}" }
+                                        td {
+                                            button {
+                                                class: "btn btn-ghost btn-xs",
+                                                onclick: {
+                                                    let reset_watermark = reset_watermark.clone();
+                                                    let source_table = watermark.source_table.clone();
+                                                    move |_| reset_watermark(source_table.clone())
+                                                },
+                                                "重置"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        if watermarks().is_empty() {
+                            div { class: "text-center text-base-content/60 py-4", "尚未记录水位，下次运行将从头采集" }
+                        }
+                    }
+                }
+
+                div { class: "overflow-x-auto max-h-96",
+                    table { class: "table table-sm",
+                        thead {
+                            tr {
+                                th { "开始时间" }
+                                th { "状态" }
+                                th { "读取行数" }
+                                th { "写入行数" }
+                                th { "耗时(ms)" }
+                                th { "失败原因" }
+                            }
+                        }
+                        tbody {
+                            for run in runs().iter() {
+                                tr { key: "{run.id}",
+                                    td { "{run.started_at}" }
+                                    td { span { class: run.status.badge_class(), "{run.status}" } }
+                                    td { "{run.rows_read}" }
+                                    td { "{run.rows_written}" }
+                                    td { "{run.duration_ms}" }
+                                    td { class: "text-error text-xs max-w-xs break-all", "{run.error_message}" }
+                                }
+                            }
+                        }
+                    }
+                    if runs().is_empty() {
+                        div { class: "text-center text-base-content/60 py-8", "暂无运行记录" }
+                    }
+                }
+
+                div {
+                    class: "modal-action",
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| on_close.call(()),
+                        "关闭"
+                    }
+                }
+            }
+
+            form {
+                method: "dialog",
+                class: "modal-backdrop",
+                onclick: move |_| on_close.call(()),
+                button { "close" }
+            }
+        }
+    }
+}
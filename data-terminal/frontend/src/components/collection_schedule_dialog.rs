@@ -0,0 +1,245 @@
+use dioxus::prelude::*;
+use crate::models::collection::CollectionTask;
+use crate::models::protocol::ApiResponse;
+use crate::models::schedule::{MisfirePolicy, TaskSchedule, TaskScheduleCreateUpdate, TIMEZONES};
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+
+#[component]
+pub fn CollectionScheduleDialog(
+    task: CollectionTask,
+    on_close: EventHandler<()>,
+    on_saved: EventHandler<()>,
+) -> Element {
+    let mut form = use_signal(TaskScheduleCreateUpdate::default);
+    let mut exists = use_signal(|| false);
+    let mut last_fire_at = use_signal(String::new);
+    let mut fire_times = use_signal(|| Vec::<String>::new());
+    let mut error_msg = use_signal(String::new);
+
+    // 加载已有的调度计划
+    let task_id = task.id.clone();
+    use_effect(move || {
+        let task_id = task_id.clone();
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+            match client.get(&format!("/api/v1/schedule/{}", task_id), Some(req_config)).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<Option<TaskSchedule>>>(&response_text) {
+                    Ok(api_response) if api_response.result => {
+                        if let Some(schedule) = api_response.data {
+                            exists.set(true);
+                            last_fire_at.set(schedule.last_fire_at.clone());
+                            fire_times.set(schedule.next_fire_times.clone());
+                            form.set(TaskScheduleCreateUpdate::from(schedule));
+                        }
+                    }
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => error_msg.set(e.to_string()),
+            }
+        });
+    });
+
+    // 预览接下来的触发时间，同时校验表达式
+    let preview = move |_: MouseEvent| {
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let cron = String::from(js_sys::encode_uri_component(&form().cron));
+            let timezone = String::from(js_sys::encode_uri_component(&form().timezone));
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .query_param("cron", cron)
+                .query_param("timezone", timezone)
+                .query_param("count", 5)
+                .build();
+            match client.get("/api/v1/schedule/preview", Some(req_config)).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<Vec<String>>>(&response_text) {
+                    Ok(api_response) if api_response.result => {
+                        error_msg.set(String::new());
+                        fire_times.set(api_response.data);
+                    }
+                    Ok(api_response) => {
+                        fire_times.set(Vec::new());
+                        error_msg.set(api_response.msg);
+                    }
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => error_msg.set(e.to_string()),
+            }
+        });
+    };
+
+    let handle_save = {
+        let task_id = task.id.clone();
+        move |_: MouseEvent| {
+            let task_id = task_id.clone();
+            if form().cron.trim().is_empty() {
+                error_msg.set("请输入 cron 表达式".to_string());
+                return;
+            }
+            spawn(async move {
+                let client = crate::utils::request::create_client("http://localhost:3000");
+                let req_config = RequestBuilder::new()
+                    .header("Content-Type", "application/json")
+                    .header("Cookie", &cookie::get_browser_cookies())
+                    .build();
+                match client.post(&format!("/api/v1/schedule/{}", task_id), Some(req_config), form()).await {
+                    Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                        Ok(api_response) if api_response.result => on_saved.call(()),
+                        Ok(api_response) => error_msg.set(api_response.msg),
+                        Err(e) => error_msg.set(e.to_string()),
+                    },
+                    Err(e) => error_msg.set(e.to_string()),
+                }
+            });
+        }
+    };
+
+    let handle_delete = {
+        let task_id = task.id.clone();
+        move |_: MouseEvent| {
+            let task_id = task_id.clone();
+            spawn(async move {
+                let client = crate::utils::request::create_client("http://localhost:3000");
+                let req_config = RequestBuilder::new()
+                    .header("Content-Type", "application/json")
+                    .header("Cookie", &cookie::get_browser_cookies())
+                    .build();
+                match client.delete(&format!("/api/v1/schedule/{}", task_id), Some(req_config)).await {
+                    Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                        Ok(api_response) if api_response.result => on_saved.call(()),
+                        Ok(api_response) => error_msg.set(api_response.msg),
+                        Err(e) => error_msg.set(e.to_string()),
+                    },
+                    Err(e) => error_msg.set(e.to_string()),
+                }
+            });
+        }
+    };
+
+    rsx! {
+        dialog {
+            class: "modal modal-open",
+            div {
+                class: "modal-box max-w-2xl",
+                h3 { class: "text-lg font-bold mb-4", "调度设置 - {task.name}" }
+
+                div { class: "space-y-4",
+                    div { class: "form-control",
+                        label { class: "label",
+                            span { class: "label-text", "Cron 表达式" }
+                            span { class: "label-text-alt", "分 时 日 月 周，或带秒的 6 段表达式" }
+                        }
+                        div { class: "flex gap-2",
+                            input {
+                                class: "input input-bordered w-full font-mono",
+                                placeholder: "0 2 * * *",
+                                value: "{form().cron}",
+                                oninput: move |e| form.with_mut(|f| f.cron = e.value()),
+                            }
+                            button {
+                                class: "btn btn-outline",
+                                onclick: preview,
+                                "预览"
+                            }
+                        }
+                    }
+
+                    div { class: "grid grid-cols-2 gap-4",
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "时区" } }
+                            select {
+                                class: "select select-bordered w-full",
+                                onchange: move |e| form.with_mut(|f| f.timezone = e.value()),
+                                for tz in TIMEZONES.iter() {
+                                    option { value: "{tz}", selected: form().timezone == *tz, "{tz}" }
+                                }
+                                if !TIMEZONES.contains(&form().timezone.as_str()) {
+                                    option { value: "{form().timezone}", selected: true, "{form().timezone}" }
+                                }
+                            }
+                        }
+
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "错过触发" } }
+                            select {
+                                class: "select select-bordered w-full",
+                                onchange: move |e| form.with_mut(|f| {
+                                    f.misfire_policy = match e.value().as_str() {
+                                        "fire_once" => MisfirePolicy::FireOnce,
+                                        _ => MisfirePolicy::Skip,
+                                    };
+                                }),
+                                option { value: "skip", selected: form().misfire_policy == MisfirePolicy::Skip, "{MisfirePolicy::Skip}" }
+                                option { value: "fire_once", selected: form().misfire_policy == MisfirePolicy::FireOnce, "{MisfirePolicy::FireOnce}" }
+                            }
+                        }
+                    }
+
+                    div { class: "form-control",
+                        label { class: "label cursor-pointer justify-start gap-2",
+                            input {
+                                r#type: "checkbox",
+                                class: "toggle toggle-info",
+                                checked: form().enabled,
+                                onchange: move |e| form.with_mut(|f| f.enabled = e.checked()),
+                            }
+                            span { class: "label-text", "启用调度" }
+                        }
+                    }
+
+                    div { class: "bg-base-200 rounded p-3 text-sm space-y-1",
+                        if !last_fire_at().is_empty() {
+                            div { "上次触发：{last_fire_at}" }
+                        }
+                        div { class: "font-medium", "接下来的触发时间" }
+                        if fire_times().is_empty() {
+                            div { class: "text-base-content/60", "-" }
+                        }
+                        for time in fire_times().iter() {
+                            div { class: "font-mono", "{time}" }
+                        }
+                    }
+
+                    if !error_msg().is_empty() {
+                        p { class: "text-error text-sm", "{error_msg()}" }
+                    }
+                }
+
+                div {
+                    class: "modal-action",
+                    if exists() {
+                        button {
+                            class: "btn btn-outline btn-error mr-auto",
+                            onclick: handle_delete,
+                            "删除调度"
+                        }
+                    }
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| on_close.call(()),
+                        "取消"
+                    }
+                    button {
+                        class: "btn btn-info",
+                        onclick: handle_save,
+                        "保存"
+                    }
+                }
+            }
+
+            form {
+                method: "dialog",
+                class: "modal-backdrop",
+                onclick: move |_| on_close.call(()),
+                button { "close" }
+            }
+        }
+    }
+}
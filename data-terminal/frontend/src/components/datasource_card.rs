@@ -84,6 +84,7 @@ pub fn DatasourceCard(
     datasource: DataSource,
     on_edit: EventHandler<(String, DataSourceType)>,
     on_delete: EventHandler<String>,
+    on_recheck: EventHandler<String>,
 ) -> Element {
     let id = Arc::new(datasource.id.clone());
     let ds_type = datasource.datasource_type.clone();
@@ -122,6 +123,20 @@ pub fn DatasourceCard(
                                             "编辑"
                                         }
                                     }
+                                    li {
+                                        button {
+                                            class: "btn btn-ghost btn-sm w-full justify-start",
+                                            onclick: {
+                                                let id = Arc::clone(&id);
+                                                move |_| {
+                                                    show_menu.set(false);
+                                                    on_recheck.call((*id).clone())
+                                                }
+                                            },
+                                            Icon { icon: HiRefresh, class: "w-4 h-4 mr-2" }
+                                            "重新检测"
+                                        }
+                                    }
                                     li {
                                         button {
                                             class: "btn btn-ghost btn-sm w-full justify-start text-error",
@@ -142,11 +157,19 @@ pub fn DatasourceCard(
                 }
                 
                 // 连接状态
-                div { class: "mt-4",
-                    div { class: "{get_connection_status_style(&datasource.connection_status)}",
+                div { class: "mt-4 flex items-center gap-2",
+                    div {
+                        class: "{get_connection_status_style(&datasource.connection_status)}",
+                        title: "{datasource.last_error}",
                         {get_connection_status_icon(&datasource.connection_status)}
                         "{get_connection_status_text(&datasource.connection_status)}"
                     }
+                    if !datasource.last_checked_at.is_empty() {
+                        span { class: "text-xs text-gray-400", "最近检测: {datasource.last_checked_at}" }
+                    }
+                }
+                if !datasource.last_error.is_empty() {
+                    p { class: "mt-1 text-xs text-error truncate", title: "{datasource.last_error}", "{datasource.last_error}" }
                 }
                 
                 // 创建时间
@@ -0,0 +1,273 @@
+use std::collections::HashSet;
+
+use dioxus::prelude::*;
+use crate::models::metadata::{approx_rows_text, DataSourceCatalog, TableDetail};
+use crate::models::protocol::ApiResponse;
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+use dioxus_free_icons::{icons::hi_outline_icons::*, Icon};
+use serde::de::DeserializeOwned;
+
+async fn fetch_api<T: DeserializeOwned>(path: &str, builder: RequestBuilder) -> Result<T, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = builder
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .build();
+    let response_text = client.get(path, Some(req_config)).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<T>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+// 数据源的库表目录，refresh 为 true 时后端忽略缓存
+pub async fn fetch_catalog(datasource_id: &str, refresh: bool) -> Result<DataSourceCatalog, String> {
+    let builder = RequestBuilder::new().query_param("refresh", refresh);
+    fetch_api(&format!("/api/v1/datasource/{}/catalog", datasource_id), builder).await
+}
+
+pub async fn fetch_table_detail(datasource_id: &str, schema: &str, table: &str, refresh: bool) -> Result<TableDetail, String> {
+    let builder = RequestBuilder::new()
+        .query_param("schema", String::from(js_sys::encode_uri_component(schema)))
+        .query_param("table", String::from(js_sys::encode_uri_component(table)))
+        .query_param("refresh", refresh);
+    fetch_api(&format!("/api/v1/datasource/{}/table", datasource_id), builder).await
+}
+
+// 数据源库表结构树：库/schema -> 表 -> 列与索引
+#[component]
+pub fn DatasourceSchemaTree(datasource_id: String) -> Element {
+    let mut catalog = use_signal(|| None as Option<DataSourceCatalog>);
+    let mut expanded = use_signal(HashSet::<String>::new);
+    let mut selected = use_signal(|| None as Option<(String, String)>);
+    let mut detail = use_signal(|| None as Option<TableDetail>);
+    let mut is_loading = use_signal(|| false);
+    let mut detail_loading = use_signal(|| false);
+    let mut error_msg = use_signal(String::new);
+
+    // 数据源ID放入信号，使下面的加载闭包可复制
+    let datasource_id = use_signal(|| datasource_id);
+
+    let mut load_catalog = move |refresh: bool| {
+        spawn(async move {
+            is_loading.set(true);
+            match fetch_catalog(&datasource_id.peek(), refresh).await {
+                Ok(data) => {
+                    // 默认展开连接配置中的库
+                    if expanded.peek().is_empty() {
+                        expanded.with_mut(|set| {
+                            set.insert(data.default_schema.clone());
+                        });
+                    }
+                    catalog.set(Some(data));
+                    error_msg.set(String::new());
+                }
+                Err(e) => error_msg.set(e),
+            }
+            is_loading.set(false);
+        });
+    };
+
+    let mut load_detail = move |schema: String, table: String, refresh: bool| {
+        selected.set(Some((schema.clone(), table.clone())));
+        spawn(async move {
+            detail_loading.set(true);
+            match fetch_table_detail(&datasource_id.peek(), &schema, &table, refresh).await {
+                Ok(data) => {
+                    detail.set(Some(data));
+                    error_msg.set(String::new());
+                }
+                Err(e) => {
+                    detail.set(None);
+                    error_msg.set(e);
+                }
+            }
+            detail_loading.set(false);
+        });
+    };
+
+    use_effect(move || load_catalog(false));
+
+    rsx! {
+        div { class: "space-y-3",
+            div { class: "flex items-center justify-between",
+                div { class: "text-sm text-base-content/60",
+                    if let Some(data) = catalog() {
+                        "更新于 {data.refreshed_at}"
+                    }
+                }
+                button {
+                    class: "btn btn-sm btn-ghost",
+                    disabled: is_loading(),
+                    onclick: move |_| {
+                        detail.set(None);
+                        selected.set(None);
+                        load_catalog(true);
+                    },
+                    if is_loading() {
+                        span { class: "loading loading-spinner loading-xs" }
+                    } else {
+                        Icon { icon: HiRefresh, class: "w-4 h-4" }
+                    }
+                    "刷新"
+                }
+            }
+
+            if !error_msg().is_empty() {
+                p { class: "text-error text-sm", "{error_msg()}" }
+            }
+
+            div { class: "grid grid-cols-1 md:grid-cols-3 gap-4",
+                // 库表树
+                div { class: "border border-base-300 rounded-lg p-2 max-h-[32rem] overflow-auto",
+                    if catalog().is_none() && is_loading() {
+                        span { class: "loading loading-spinner loading-sm" }
+                    }
+                    if let Some(data) = catalog() {
+                        if data.schemas.is_empty() {
+                            p { class: "text-sm text-base-content/60 p-2", "没有可访问的库" }
+                        }
+                        ul { class: "menu menu-sm w-full p-0",
+                            for schema in data.schemas.into_iter() {
+                                li { key: "{schema.name}",
+                                    {
+                                        let name = schema.name.clone();
+                                        let is_open = expanded().contains(&schema.name);
+                                        rsx! {
+                                            a {
+                                                onclick: move |_| expanded.with_mut(|set| {
+                                                    if !set.remove(&name) {
+                                                        set.insert(name.clone());
+                                                    }
+                                                }),
+                                                if is_open {
+                                                    Icon { icon: HiChevronDown, class: "w-3 h-3" }
+                                                } else {
+                                                    Icon { icon: HiChevronRight, class: "w-3 h-3" }
+                                                }
+                                                Icon { icon: HiDatabase, class: "w-4 h-4" }
+                                                span { class: "truncate", "{schema.name}" }
+                                                span { class: "badge badge-ghost badge-sm ml-auto", "{schema.tables.len()}" }
+                                            }
+                                            if is_open {
+                                                ul {
+                                                    for table in schema.tables.iter() {
+                                                        {
+                                                            let schema_name = schema.name.clone();
+                                                            let table_name = table.name.clone();
+                                                            let active = selected() == Some((schema_name.clone(), table_name.clone()));
+                                                            rsx! {
+                                                                li { key: "{schema_name}.{table_name}",
+                                                                    a {
+                                                                        class: if active { "active" } else { "" },
+                                                                        title: "{table.comment}",
+                                                                        onclick: move |_| load_detail(schema_name.clone(), table_name.clone(), false),
+                                                                        if table.table_type == "view" {
+                                                                            Icon { icon: HiEye, class: "w-4 h-4" }
+                                                                        } else {
+                                                                            Icon { icon: HiTable, class: "w-4 h-4" }
+                                                                        }
+                                                                        span { class: "truncate", "{table.name}" }
+                                                                        span { class: "text-xs text-base-content/50 ml-auto", "{approx_rows_text(table.approx_rows)}" }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // 表结构详情
+                div { class: "md:col-span-2 border border-base-300 rounded-lg p-3 max-h-[32rem] overflow-auto",
+                    if detail_loading() {
+                        span { class: "loading loading-spinner loading-sm" }
+                    } else if let Some(info) = detail() {
+                        div { class: "flex items-center justify-between mb-2",
+                            div {
+                                h5 { class: "font-semibold", "{info.schema}.{info.name}" }
+                                p { class: "text-xs text-base-content/60",
+                                    if info.table_type == "view" { "视图" } else { "表" }
+                                    " · {approx_rows_text(info.approx_rows)}"
+                                    if !info.comment.is_empty() {
+                                        " · {info.comment}"
+                                    }
+                                }
+                            }
+                            button {
+                                class: "btn btn-xs btn-ghost",
+                                onclick: {
+                                    let schema = info.schema.clone();
+                                    let name = info.name.clone();
+                                    move |_| load_detail(schema.clone(), name.clone(), true)
+                                },
+                                Icon { icon: HiRefresh, class: "w-3 h-3" }
+                            }
+                        }
+                        table { class: "table table-xs",
+                            thead {
+                                tr {
+                                    th { "列名" }
+                                    th { "类型" }
+                                    th { "可空" }
+                                    th { "默认值" }
+                                    th { "注释" }
+                                }
+                            }
+                            tbody {
+                                for column in info.columns.iter() {
+                                    tr { key: "{column.name}",
+                                        td { class: "font-mono",
+                                            "{column.name}"
+                                            if info.primary_key.contains(&column.name) {
+                                                span { class: "badge badge-primary badge-xs ml-1", "PK" }
+                                            }
+                                        }
+                                        td { class: "font-mono", "{column.column_type}" }
+                                        td { if column.nullable { "是" } else { "否" } }
+                                        td { class: "font-mono", {column.default_value.clone().unwrap_or_default()} }
+                                        td { "{column.comment}" }
+                                    }
+                                }
+                            }
+                        }
+                        if !info.indexes.is_empty() {
+                            h6 { class: "font-semibold text-sm mt-4 mb-1", "索引" }
+                            table { class: "table table-xs",
+                                thead {
+                                    tr {
+                                        th { "名称" }
+                                        th { "列" }
+                                        th { "类型" }
+                                    }
+                                }
+                                tbody {
+                                    for index in info.indexes.iter() {
+                                        tr { key: "{index.name}",
+                                            td { class: "font-mono", "{index.name}" }
+                                            td { class: "font-mono", {index.columns.join(", ")} }
+                                            td {
+                                                if index.primary { "主键" } else if index.unique { "唯一" } else { "普通" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    } else {
+                        p { class: "text-sm text-base-content/60", "选择左侧的表查看列与索引" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub mod navbar;
 pub mod project_panel;
+pub mod project_member_dialog;
 pub mod sidebar_left;
 pub mod sidebar_right;
 pub mod resource_dialog;
@@ -7,3 +8,13 @@ pub mod resource_delete_dialog;
 pub mod datasource_card;
 pub mod datasource_type_dialog;
 pub mod datasource_delete_dialog;
+pub mod collection_dialog;
+pub mod collection_delete_dialog;
+pub mod collection_run_dialog;
+pub mod collection_schedule_dialog;
+pub mod task_run_log_dialog;
+pub mod pipeline_delete_dialog;
+pub mod transform_delete_dialog;
+pub mod datasource_schema_tree;
+pub mod user_dialog;
+pub mod user_delete_dialog;
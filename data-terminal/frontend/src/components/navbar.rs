@@ -1,6 +1,7 @@
 use dioxus::prelude::*;
 use super::project_panel::ProjectPanel;
 use crate::routes::Route;
+use crate::utils::request::{create_client, HttpRequest, RequestBuilder};
 use dioxus_free_icons::{icons::{bs_icons::*, ld_icons::*}, Icon};
 
 
@@ -22,14 +23,14 @@ fn get_settings_menu_items() -> Vec<MenuItem> {
             icon: "database",
             route: Some(Route::ResourcePage {}),
         },
+        MenuItem {
+            id: "user_settings",
+            label: "用户设置",
+            icon: "user",
+            route: Some(Route::UserPage {}),
+        },
         // 可以在这里添加更多菜单项
         // MenuItem {
-        //     id: "user_settings",
-        //     label: "用户设置",
-        //     icon: "user",
-        //     route: Some(Route::UserSettings {}),
-        // },
-        // MenuItem {
         //     id: "system_settings",
         //     label: "系统设置",
         //     icon: "cog",
@@ -58,6 +59,19 @@ pub fn Navbar() -> Element {
         is_dropdown_open.set(!current);
     };
     
+    // 退出登录，服务端作废当前会话并清除 cookie
+    let handle_logout = move |_| {
+        is_dropdown_open.set(false);
+        spawn(async move {
+            let client = create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .build();
+            let _ = client.post("/api/v1/logout", Some(req_config), ()).await;
+            navigator.replace(Route::Login {});
+        });
+    };
+
     // 处理点击外部关闭下拉菜单
     let handle_click_outside = move |_| {
         is_dropdown_open.set(false);
@@ -128,6 +142,17 @@ pub fn Navbar() -> Element {
                                         }
                                     }
                                 }
+                                li { class: "border-t border-base-300",
+                                    button {
+                                        class: "w-full text-left px-4 py-2 text-sm text-error hover:bg-base-200 flex items-center gap-2",
+                                        onclick: handle_logout,
+                                        svg { class: "w-4 h-4", fill: "none", stroke: "currentColor", stroke_width: "2", view_box: "0 0 24 24", xmlns: "http://www.w3.org/2000/svg",
+                                            path { d: "M9 21H5a2 2 0 0 1-2-2V5a2 2 0 0 1 2-2h4", stroke_linecap: "round", stroke_linejoin: "round" }
+                                            path { d: "M16 17l5-5-5-5M21 12H9", stroke_linecap: "round", stroke_linejoin: "round" }
+                                        }
+                                        "退出登录"
+                                    }
+                                }
                             }
                         }
                     }
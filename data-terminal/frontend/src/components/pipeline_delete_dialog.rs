@@ -0,0 +1,67 @@
+use dioxus::prelude::*;
+use crate::models::pipeline::Pipeline;
+
+#[component]
+pub fn PipelineDeleteDialog(
+    pipeline: Pipeline,
+    on_confirm: EventHandler<Pipeline>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    let handle_confirm = {
+        let pipeline = pipeline.clone();
+        move |_| {
+            on_confirm.call(pipeline.clone());
+        }
+    };
+
+    rsx! {
+        dialog {
+            class: "modal modal-open",
+            div {
+                class: "modal-box",
+                h3 {
+                    class: "text-lg font-bold mb-4",
+                    "删除确认"
+                }
+
+                p {
+                    class: "py-4 text-base-content/80",
+                    "请确认是否删除流水线:"
+                }
+
+                div {
+                    class: "bg-base-200 p-3 rounded-lg mb-4",
+                    div { class: "font-medium", "{pipeline.name}" }
+                    div { class: "text-sm text-base-content/60", "{pipeline.description}" }
+                }
+
+                p {
+                    class: "text-warning text-sm mb-4",
+                    "警告: 删除流水线后其调度配置一并删除，节点引用的任务不受影响!"
+                }
+
+                div {
+                    class: "modal-action",
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| on_cancel.call(()),
+                        "取消"
+                    }
+
+                    button {
+                        class: "btn btn-error",
+                        onclick: handle_confirm,
+                        "确认删除"
+                    }
+                }
+            }
+
+            form {
+                method: "dialog",
+                class: "modal-backdrop",
+                onclick: move |_| on_cancel.call(()),
+                button { "close" }
+            }
+        }
+    }
+}
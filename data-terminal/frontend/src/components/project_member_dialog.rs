@@ -0,0 +1,245 @@
+use dioxus::prelude::*;
+use crate::models::project::{Project, ProjectMember, ProjectMemberForm, ProjectRole};
+use crate::models::protocol::ApiResponse;
+use crate::models::user::UserOption;
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+use serde::de::DeserializeOwned;
+
+async fn get_api<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .build();
+    let response_text = client.get(path, Some(req_config)).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<T>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+async fn save_member(project_code: &str, form: ProjectMemberForm) -> Result<String, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .build();
+    let path = format!("/api/v1/project/{}/member", project_code);
+    let response_text = client.post(&path, Some(req_config), form).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<String>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+async fn delete_member(project_code: &str, user_id: &str) -> Result<String, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .build();
+    let path = format!("/api/v1/project/{}/member/{}", project_code, user_id);
+    let response_text = client.delete(&path, Some(req_config)).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<String>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+// 项目成员管理弹窗，仅项目负责人可以打开
+#[component]
+pub fn ProjectMemberDialog(
+    project: Project,
+    on_close: EventHandler<()>,
+) -> Element {
+    let project_code = use_signal(|| project.code.clone());
+    let mut members = use_signal(|| Vec::<ProjectMember>::new());
+    let mut users = use_signal(|| Vec::<UserOption>::new());
+    let mut new_member = use_signal(ProjectMemberForm::default);
+    let mut error_msg = use_signal(String::new);
+
+    let mut refresh_members = move || {
+        spawn(async move {
+            match get_api::<Vec<ProjectMember>>(&format!("/api/v1/project/{}/member", project_code())).await {
+                Ok(list) => members.set(list),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    use_effect(move || {
+        refresh_members();
+        spawn(async move {
+            match get_api::<Vec<UserOption>>("/api/v1/user/options").await {
+                Ok(list) => users.set(list),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    });
+
+    let mut submit_member = move |form: ProjectMemberForm| {
+        error_msg.set(String::new());
+        spawn(async move {
+            match save_member(&project_code(), form).await {
+                Ok(_) => refresh_members(),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    let handle_add = move |_| {
+        if new_member().user_id.is_empty() {
+            error_msg.set("请选择用户".to_string());
+            return;
+        }
+        submit_member(new_member());
+        new_member.set(ProjectMemberForm::default());
+    };
+
+    let mut remove_member = move |user_id: String| {
+        error_msg.set(String::new());
+        spawn(async move {
+            match delete_member(&project_code(), &user_id).await {
+                Ok(_) => refresh_members(),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    // 已是成员的用户不再出现在候选列表中
+    let candidates: Vec<UserOption> = users()
+        .into_iter()
+        .filter(|u| !members().iter().any(|m| m.user_id == u.id))
+        .collect();
+
+    rsx! {
+        dialog {
+            class: "modal modal-open",
+            div {
+                class: "modal-box max-w-2xl",
+                h3 { class: "text-lg font-bold mb-4", "成员管理 - {project.name}" }
+
+                if !error_msg().is_empty() {
+                    div { class: "alert alert-error mb-4 text-sm",
+                        span { "{error_msg()}" }
+                    }
+                }
+
+                // 添加成员
+                div { class: "flex items-center gap-2 mb-4",
+                    select {
+                        class: "select select-bordered select-sm flex-1",
+                        value: "{new_member().user_id}",
+                        onchange: move |e| new_member.with_mut(|f| f.user_id = e.value()),
+                        option { value: "", "选择用户" }
+                        for user in candidates.into_iter() {
+                            option {
+                                key: "{user.id}",
+                                value: "{user.id}",
+                                if user.display_name.is_empty() {
+                                    "{user.username}"
+                                } else {
+                                    "{user.display_name} ({user.username})"
+                                }
+                            }
+                        }
+                    }
+                    select {
+                        class: "select select-bordered select-sm w-32",
+                        value: "{new_member().role.value()}",
+                        onchange: move |e| new_member.with_mut(|f| f.role = ProjectRole::from_value(&e.value())),
+                        for role in ProjectRole::ALL.into_iter() {
+                            option { value: "{role.value()}", "{role.label()}" }
+                        }
+                    }
+                    button {
+                        class: "btn btn-info btn-sm",
+                        onclick: handle_add,
+                        "添加"
+                    }
+                }
+
+                // 成员列表
+                div { class: "max-h-96 overflow-auto",
+                    table { class: "table table-sm",
+                        thead {
+                            tr {
+                                th { "成员" }
+                                th { "角色" }
+                                th { class: "text-right", "操作" }
+                            }
+                        }
+                        tbody {
+                            if members().is_empty() {
+                                tr {
+                                    td { colspan: "3", class: "text-center text-base-content/60", "暂无成员" }
+                                }
+                            }
+                            for member in members().into_iter() {
+                                {
+                                    let user_id = member.user_id.clone();
+                                    let remove_id = member.user_id.clone();
+                                    rsx! {
+                                        tr { key: "{member.user_id}",
+                                            td {
+                                                div { "{member.name()}" }
+                                                div { class: "text-xs text-base-content/60 font-mono", "{member.username}" }
+                                            }
+                                            td {
+                                                select {
+                                                    class: "select select-bordered select-xs",
+                                                    value: "{member.role.value()}",
+                                                    onchange: move |e| submit_member(ProjectMemberForm {
+                                                        user_id: user_id.clone(),
+                                                        role: ProjectRole::from_value(&e.value()),
+                                                    }),
+                                                    for role in ProjectRole::ALL.into_iter() {
+                                                        option {
+                                                            value: "{role.value()}",
+                                                            selected: role == member.role,
+                                                            "{role.label()}"
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            td { class: "text-right",
+                                                button {
+                                                    class: "btn btn-ghost btn-xs text-error",
+                                                    onclick: move |_| remove_member(remove_id.clone()),
+                                                    "移除"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div {
+                    class: "modal-action",
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| on_close.call(()),
+                        "关闭"
+                    }
+                }
+            }
+
+            form {
+                method: "dialog",
+                class: "modal-backdrop",
+                onclick: move |_| on_close.call(()),
+                button { "close" }
+            }
+        }
+    }
+}
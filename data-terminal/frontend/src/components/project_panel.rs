@@ -1,3 +1,4 @@
+use crate::components::project_member_dialog::ProjectMemberDialog;
 use crate::models::project::{Project, ProjectModalMode};
 use crate::models::protocol::ApiResponse;
 use crate::utils::{
@@ -18,6 +19,7 @@ pub fn ProjectPanel() -> Element {
     let mut modal_mode = use_signal(|| ProjectModalMode::Add); // 表示项目新增/编辑弹窗的类型
 
     let mut show_project_delete_modal = use_signal(|| false); // 表示项目删除确认弹窗是否显示
+    let mut show_project_member_modal = use_signal(|| false); // 表示项目成员管理弹窗是否显示
     let mut selected_project_for_action = use_signal(|| None as Option<Project>); // 表示选中的项目
     let mut show_action_menu = use_signal(|| None as Option<String>); // 表示操作菜单是否显示
     let mut menu_position = use_signal(|| "bottom" as &str); // 表示菜单显示位置：bottom 或 top
@@ -44,6 +46,10 @@ pub fn ProjectPanel() -> Element {
                         match result {
                             Ok(api_response) => {
                                 if api_response.result {
+                                    // 恢复登录态中已选择的项目
+                                    let current = cookie::get_cookie_value("project_code").unwrap_or_default();
+                                    let project = api_response.data.iter().find(|p| p.code == current).cloned();
+                                    selected_project.set(project);
                                     projects.set(api_response.data);
                                 } else {
                                     error_msg = api_response.msg;
@@ -79,6 +85,42 @@ pub fn ProjectPanel() -> Element {
         fetch_projects();
     });
 
+    // 切换当前项目，后端校验成员身份并重新签发 token 后刷新页面数据
+    let handle_select_project = move |project: Project| {
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+            let response = client
+                .post(&format!("/api/v1/project/{}/switch", project.code), Some(req_config), ())
+                .await;
+            let error_msg = match response {
+                Ok(result) => match serde_json::from_str::<ApiResponse<Project>>(&result) {
+                    Ok(api_response) if api_response.result => {
+                        selected_project.set(Some(api_response.data));
+                        if let Some(window) = web_sys::window() {
+                            let _ = window.location().reload();
+                        }
+                        return;
+                    }
+                    Ok(api_response) => api_response.msg,
+                    Err(e) => e.to_string(),
+                },
+                Err(e) => e.to_string(),
+            };
+            let _id = toast.write().popup(ToastInfo {
+                heading: Some("切换项目失败".into()),
+                context: error_msg,
+                allow_toast_close: true,
+                position: dioxus_toast::Position::TopRight,
+                icon: Some(Icon::Error),
+                hide_after: Some(3),
+            });
+        });
+    };
+
     // 处理项目添加或编辑确认
     let handle_project_add_or_edit_confirm = {
         let mut show_project_add_or_edit_modal = show_project_add_or_edit_modal.clone();
@@ -100,6 +142,7 @@ pub fn ProjectPanel() -> Element {
                     create_msg: String::new(),
                     created_at: String::new(),
                     updated_at: String::new(),
+                    role: None,
                 };
                 let mut error_msg = String::new();
                 match modal_mode() {
@@ -195,13 +238,14 @@ pub fn ProjectPanel() -> Element {
     let handle_delete_confirm = {
         let mut show_project_delete_modal = show_project_delete_modal.clone();
         let mut fetch_projects = fetch_projects.clone();
-        move |project: Project| {
+        move |(project, mode): (Project, String)| {
             spawn(async move {
                 let mut error_msg = String::new();
                 let client = crate::utils::request::create_client("http://localhost:3000");
                 let req_config = RequestBuilder::new()
                     .header("Accept", "application/json")
                     .header("Cookie", &cookie::get_browser_cookies())
+                    .query_param("mode", mode)
                     .build();
 
                 let response = client
@@ -331,13 +375,14 @@ pub fn ProjectPanel() -> Element {
                     ProjectList {
                         projects: projects.clone(),
                         loading: loading.clone(),
-                        selected_project: selected_project.clone(),
+                        on_select_project: handle_select_project,
                         show_dropdown: show_dropdown.clone(),
                         selected_project_for_action: selected_project_for_action.clone(),
                         show_action_menu: show_action_menu.clone(),
                         modal_mode: modal_mode.clone(),
                         show_project_add_or_edit_modal: show_project_add_or_edit_modal.clone(),
                         show_project_delete_modal: show_project_delete_modal.clone(),
+                        show_project_member_modal: show_project_member_modal.clone(),
                         menu_position: menu_position.clone(),
                     }
                 }
@@ -355,6 +400,16 @@ pub fn ProjectPanel() -> Element {
             }
         }
 
+        // 项目成员管理弹窗
+        if show_project_member_modal() {
+            if let Some(project) = selected_project_for_action() {
+                ProjectMemberDialog {
+                    project: project,
+                    on_close: move |_| show_project_member_modal.set(false),
+                }
+            }
+        }
+
         // 项目删除确认弹窗
         if show_project_delete_modal() {
             if let Some(project) = selected_project_for_action() {
@@ -398,7 +453,7 @@ pub fn ProjectItem(
     index: usize,
     project: Project,
     total_projects: usize,
-    selected_project: Signal<Option<Project>>,
+    on_select_project: Callback<Project>,
     show_dropdown: Signal<bool>,
     selected_project_for_action: Signal<Option<Project>>,
     show_action_menu: Signal<Option<String>>,
@@ -406,19 +461,23 @@ pub fn ProjectItem(
     modal_mode: Signal<ProjectModalMode>,
     show_project_add_or_edit_modal: Signal<bool>,
     show_project_delete_modal: Signal<bool>,
+    show_project_member_modal: Signal<bool>,
 ) -> Element {
 
     let project_for_selected = project.clone();
     let project_for_action = project.clone();
     let project_for_edit = project.clone();
     let project_for_delete = project.clone();
+    let project_for_member = project.clone();
+    // 只有项目负责人可以编辑、删除项目和管理成员
+    let is_owner = project.is_owner();
 
     rsx! {
         div {
             class: "flex items-center justify-between p-3 hover:bg-base-200 cursor-pointer relative",
             onclick: move |_| {
-                selected_project.set(Some(project_for_selected.clone()));
                 show_dropdown.set(false);
+                on_select_project.call(project_for_selected.clone());
             },
             div { class: "flex items-center gap-3",
                 div { class: "w-8 h-8 bg-primary/10 rounded-lg flex items-center justify-center",
@@ -432,82 +491,117 @@ pub fn ProjectItem(
                     }
                 }
                 div { class: "flex-1",
-                    div { class: "font-medium text-sm", "{project.name}" }
+                    div { class: "font-medium text-sm flex items-center gap-2",
+                        "{project.name}"
+                        // 项目库初始化状态
+                        match project.create_status.as_str() {
+                            "Pending" | "Running" => rsx! {
+                                span { class: "badge badge-info badge-xs", "初始化中" }
+                            },
+                            "Fail" => rsx! {
+                                span { class: "badge badge-error badge-xs", title: "{project.create_msg}", "初始化失败" }
+                            },
+                            "Deleting" => rsx! {
+                                span { class: "badge badge-warning badge-xs", title: "{project.create_msg}", "删除中" }
+                            },
+                            _ => rsx! {},
+                        }
+                    }
                     div { class: "text-xs text-base-content/60", "{project.description}" }
                 }
             }
             
-            // 水平三点按钮
-            button {
-                class: "btn btn-ghost btn-xs p-1 relative",
-                onclick: move |event| {
-                    event.stop_propagation();
-                    // 如果当前项目的菜单已经显示，则关闭；否则显示
-                    if show_action_menu() == Some(project_for_action.code.clone()) {
-                        show_action_menu.set(None);
-                    } else {
-                        // 判断菜单显示位置
-                        let position = if index > total_projects / 2 { "top" } else { "bottom" };
-                        menu_position.set(position);
+            // 水平三点按钮，仅项目负责人显示操作菜单
+            if is_owner {
+                button {
+                    class: "btn btn-ghost btn-xs p-1 relative",
+                    onclick: move |event| {
+                        event.stop_propagation();
+                        // 如果当前项目的菜单已经显示，则关闭；否则显示
+                        if show_action_menu() == Some(project_for_action.code.clone()) {
+                            show_action_menu.set(None);
+                        } else {
+                            // 判断菜单显示位置
+                            let position = if index > total_projects / 2 { "top" } else { "bottom" };
+                            menu_position.set(position);
                         
-                        selected_project_for_action.set(Some(project_for_action.clone()));
-                        show_action_menu.set(Some(project_for_action.code.clone()));
+                            selected_project_for_action.set(Some(project_for_action.clone()));
+                            show_action_menu.set(Some(project_for_action.code.clone()));
+                        }
+                    },
+                    svg {
+                        class: "w-4 h-4",
+                        fill: "none",
+                        stroke: "currentColor",
+                        stroke_width: "2",
+                        view_box: "0 0 24 24",
+                        path { d: "M4 6h16M4 12h16M4 18h16" }
                     }
-                },
-                svg {
-                    class: "w-4 h-4",
-                    fill: "none",
-                    stroke: "currentColor",
-                    stroke_width: "2",
-                    view_box: "0 0 24 24",
-                    path { d: "M4 6h16M4 12h16M4 18h16" }
-                }
 
-                // 操作菜单 - 根据位置显示在三点按钮上方或下方
-                if show_action_menu() == Some(project.code.clone()) {
-                    div {
-                        class: "absolute w-32 bg-base-100 border border-base-300 rounded-lg shadow-lg z-50",
-                        style: if menu_position() == "top" {
-                            "right: 0; bottom: 100%; margin-bottom: 0.25rem;"
-                        } else {
-                            "right: 0; top: 100%; margin-top: 0.25rem;"
-                        },
-                        div { class: "py-1",
-                            button {
-                                class: "w-full px-3 py-2 text-left text-sm hover:bg-base-200 flex items-center gap-2",
-                                onclick: move |event| {
-                                    event.stop_propagation();
-                                    show_action_menu.set(None);
-                                    modal_mode.set(ProjectModalMode::Edit(project_for_edit.clone()));
-                                    show_project_add_or_edit_modal.set(true);
-                                },
-                                svg {
-                                    class: "w-3 h-3",
-                                    fill: "none",
-                                    stroke: "currentColor",
-                                    stroke_width: "2",
-                                    view_box: "0 0 24 24",
-                                    path { d: "M11 5H6a2 2 0 00-2 2v11a2 2 0 002 2h11a2 2 0 002-2v-5m-1.414-9.414a2 2 0 112.828 2.828L11.828 15H9v-2.828l8.586-8.586z" }
+                    // 操作菜单 - 根据位置显示在三点按钮上方或下方
+                    if show_action_menu() == Some(project.code.clone()) {
+                        div {
+                            class: "absolute w-32 bg-base-100 border border-base-300 rounded-lg shadow-lg z-50",
+                            style: if menu_position() == "top" {
+                                "right: 0; bottom: 100%; margin-bottom: 0.25rem;"
+                            } else {
+                                "right: 0; top: 100%; margin-top: 0.25rem;"
+                            },
+                            div { class: "py-1",
+                                button {
+                                    class: "w-full px-3 py-2 text-left text-sm hover:bg-base-200 flex items-center gap-2",
+                                    onclick: move |event| {
+                                        event.stop_propagation();
+                                        show_action_menu.set(None);
+                                        modal_mode.set(ProjectModalMode::Edit(project_for_edit.clone()));
+                                        show_project_add_or_edit_modal.set(true);
+                                    },
+                                    svg {
+                                        class: "w-3 h-3",
+                                        fill: "none",
+                                        stroke: "currentColor",
+                                        stroke_width: "2",
+                                        view_box: "0 0 24 24",
+                                        path { d: "M11 5H6a2 2 0 00-2 2v11a2 2 0 002 2h11a2 2 0 002-2v-5m-1.414-9.414a2 2 0 112.828 2.828L11.828 15H9v-2.828l8.586-8.586z" }
+                                    }
+                                    "编辑"
                                 }
-                                "编辑"
-                            }
-                            button {
-                                class: "w-full px-3 py-2 text-left text-sm hover:bg-base-200 flex items-center gap-2 text-error",
-                                onclick: move |event| {
-                                    event.stop_propagation();
-                                    show_action_menu.set(None);
-                                    selected_project_for_action.set(Some(project_for_delete.clone()));
-                                    show_project_delete_modal.set(true);
-                                },
-                                svg {
-                                    class: "w-3 h-3",
-                                    fill: "none",
-                                    stroke: "currentColor",
-                                    stroke_width: "2",
-                                    view_box: "0 0 24 24",
-                                    path { d: "M19 7l-.867 12.142A2 2 0 0116.138 21H7.862a2 2 0 01-1.995-1.858L5 7m5 4v6m4-6v6m1-10V4a1 1 0 00-1-1h-4a1 1 0 00-1 1v3M4 7h16" }
+                                button {
+                                    class: "w-full px-3 py-2 text-left text-sm hover:bg-base-200 flex items-center gap-2",
+                                    onclick: move |event| {
+                                        event.stop_propagation();
+                                        show_action_menu.set(None);
+                                        selected_project_for_action.set(Some(project_for_member.clone()));
+                                        show_project_member_modal.set(true);
+                                    },
+                                    svg {
+                                        class: "w-3 h-3",
+                                        fill: "none",
+                                        stroke: "currentColor",
+                                        stroke_width: "2",
+                                        view_box: "0 0 24 24",
+                                        path { d: "M17 20h5v-2a3 3 0 00-5.356-1.857M17 20H7m10 0v-2c0-.656-.126-1.283-.356-1.857M7 20H2v-2a3 3 0 015.356-1.857M7 20v-2c0-.656.126-1.283.356-1.857m0 0a5.002 5.002 0 019.288 0M15 7a3 3 0 11-6 0 3 3 0 016 0z" }
+                                    }
+                                    "成员"
+                                }
+                                button {
+                                    class: "w-full px-3 py-2 text-left text-sm hover:bg-base-200 flex items-center gap-2 text-error",
+                                    onclick: move |event| {
+                                        event.stop_propagation();
+                                        show_action_menu.set(None);
+                                        selected_project_for_action.set(Some(project_for_delete.clone()));
+                                        show_project_delete_modal.set(true);
+                                    },
+                                    svg {
+                                        class: "w-3 h-3",
+                                        fill: "none",
+                                        stroke: "currentColor",
+                                        stroke_width: "2",
+                                        view_box: "0 0 24 24",
+                                        path { d: "M19 7l-.867 12.142A2 2 0 0116.138 21H7.862a2 2 0 01-1.995-1.858L5 7m5 4v6m4-6v6m1-10V4a1 1 0 00-1-1h-4a1 1 0 00-1 1v3M4 7h16" }
+                                    }
+                                    "删除"
                                 }
-                                "删除"
                             }
                         }
                     }
@@ -522,13 +616,14 @@ pub fn ProjectItem(
 pub fn ProjectList(
     projects: Signal<Vec<Project>>,
     loading: Signal<bool>,
-    selected_project: Signal<Option<Project>>,
+    on_select_project: Callback<Project>,
     show_dropdown: Signal<bool>,
     selected_project_for_action: Signal<Option<Project>>,
     show_action_menu: Signal<Option<String>>,
     modal_mode: Signal<ProjectModalMode>,
     show_project_add_or_edit_modal: Signal<bool>,
     show_project_delete_modal: Signal<bool>,
+    show_project_member_modal: Signal<bool>,
     menu_position: Signal<&'static str>,
 ) -> Element {
     let is_empty = projects().is_empty();
@@ -552,7 +647,7 @@ pub fn ProjectList(
                                 index: index,
                                 project: project.clone(),
                                 total_projects: total_projects,
-                                selected_project: selected_project.clone(),
+                                on_select_project: on_select_project,
                                 show_dropdown: show_dropdown.clone(),
                                 selected_project_for_action: selected_project_for_action.clone(),
                                 show_action_menu: show_action_menu.clone(),
@@ -560,6 +655,7 @@ pub fn ProjectList(
                                 modal_mode: modal_mode.clone(),
                                 show_project_add_or_edit_modal: show_project_add_or_edit_modal.clone(),
                                 show_project_delete_modal: show_project_delete_modal.clone(),
+                                show_project_member_modal: show_project_member_modal.clone(),
                             }
                         }
                     }
@@ -707,14 +803,17 @@ pub fn ProjectAddOrEdit(
 #[component]
 pub fn ProjectDel(
     project: Project,
-    on_confirm: Callback<Project>,
+    on_confirm: Callback<(Project, String)>,
     on_cancel: Callback<()>,
 ) -> Element {
+    // 项目库处理方式：archive 归档保留一段时间，drop 直接删除
+    let mut delete_mode = use_signal(|| "archive".to_string());
+
     let handle_confirm = {
         let on_confirm = on_confirm.clone();
         let project = project.clone();
         move |_| {
-            on_confirm.call(project.clone());
+            on_confirm.call((project.clone(), delete_mode()));
         }
     };
 
@@ -740,6 +839,29 @@ pub fn ProjectDel(
                     "请确认是否删除该项目"
                 }
 
+                div { class: "flex flex-col gap-2",
+                    label { class: "label cursor-pointer justify-start gap-3",
+                        input {
+                            r#type: "radio",
+                            name: "delete_mode",
+                            class: "radio radio-sm",
+                            checked: delete_mode() == "archive",
+                            onchange: move |_| delete_mode.set("archive".to_string()),
+                        }
+                        span { class: "label-text", "归档项目数据（保留期后自动清理）" }
+                    }
+                    label { class: "label cursor-pointer justify-start gap-3",
+                        input {
+                            r#type: "radio",
+                            name: "delete_mode",
+                            class: "radio radio-sm radio-error",
+                            checked: delete_mode() == "drop",
+                            onchange: move |_| delete_mode.set("drop".to_string()),
+                        }
+                        span { class: "label-text text-error", "立即删除项目数据（不可恢复）" }
+                    }
+                }
+
                 div {
                     class: "modal-action",
                     button {
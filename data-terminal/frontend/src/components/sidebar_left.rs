@@ -22,8 +22,11 @@ enum SubMenu {
     WideTableDevelopment,
     FileDevelopment,
     StreamDevelopment,
+    PipelineDevelopment,
     // 数据供应
     DataQuery,
+    MetadataCatalog,
+    DataLineage,
     DataSync,
     DataSubscription,
     // 运维监控
@@ -108,7 +111,10 @@ pub fn SidebarLeft() -> Element {
                     }
                     button {
                         class: tasks_class,
-                        onclick: move |_| selected.set(Some(SubMenu::CollectionTasks)),
+                        onclick: move |_| {
+                            selected.set(Some(SubMenu::CollectionTasks));
+                            navigator.push(Route::CollectionTaskPage { task_id: String::new() });
+                        },
                         Icon { icon: FaDownload, class: "w-4 h-4" }
                         "采集任务"
                     }
@@ -137,11 +143,21 @@ pub fn SidebarLeft() -> Element {
                 }
                 class
             };
+            let pipeline_class = {
+                let mut class = "btn btn-ghost text-sm font-medium flex items-center gap-3 w-full justify-start text-base-content/70 hover:text-base-content".to_string();
+                if selected() == Some(SubMenu::PipelineDevelopment) {
+                    class = "btn btn-ghost text-sm font-medium flex items-center gap-3 w-full justify-start text-primary bg-primary/10".to_string();
+                }
+                class
+            };
             rsx! {
                 div { class: "pl-6 flex flex-col gap-2",
                     button {
                         class: wide_class,
-                        onclick: move |_| selected.set(Some(SubMenu::WideTableDevelopment)),
+                        onclick: move |_| {
+                            selected.set(Some(SubMenu::WideTableDevelopment));
+                            navigator.push(Route::TransformPage { task_id: String::new() });
+                        },
                         Icon { icon: HiTable, class: "w-4 h-4" }
                         "宽表开发"
                     }
@@ -157,6 +173,15 @@ pub fn SidebarLeft() -> Element {
                         Icon { icon: MdStream, class: "w-4 h-4" }
                         "流开发"
                     }
+                    button {
+                        class: pipeline_class,
+                        onclick: move |_| {
+                            selected.set(Some(SubMenu::PipelineDevelopment));
+                            navigator.push(Route::PipelineDesignerPage {});
+                        },
+                        Icon { icon: HiShare, class: "w-4 h-4" }
+                        "流程编排"
+                    }
                 }
             }
         },
@@ -168,6 +193,20 @@ pub fn SidebarLeft() -> Element {
                 }
                 class
             };
+            let catalog_class = {
+                let mut class = "btn btn-ghost text-sm font-medium flex items-center gap-3 w-full justify-start text-base-content/70 hover:text-base-content".to_string();
+                if selected() == Some(SubMenu::MetadataCatalog) {
+                    class = "btn btn-ghost text-sm font-medium flex items-center gap-3 w-full justify-start text-primary bg-primary/10".to_string();
+                }
+                class
+            };
+            let lineage_class = {
+                let mut class = "btn btn-ghost text-sm font-medium flex items-center gap-3 w-full justify-start text-base-content/70 hover:text-base-content".to_string();
+                if selected() == Some(SubMenu::DataLineage) {
+                    class = "btn btn-ghost text-sm font-medium flex items-center gap-3 w-full justify-start text-primary bg-primary/10".to_string();
+                }
+                class
+            };
             let sync_class = {
                 let mut class = "btn btn-ghost text-sm font-medium flex items-center gap-3 w-full justify-start text-base-content/70 hover:text-base-content".to_string();
                 if selected() == Some(SubMenu::DataSync) {
@@ -186,10 +225,31 @@ pub fn SidebarLeft() -> Element {
                 div { class: "pl-6 flex flex-col gap-2",
                     button {
                         class: query_class,
-                        onclick: move |_| selected.set(Some(SubMenu::DataQuery)),
+                        onclick: move |_| {
+                            selected.set(Some(SubMenu::DataQuery));
+                            navigator.push(Route::QueryConsolePage {});
+                        },
                         Icon { icon: HiSearch, class: "w-4 h-4" }
                         "数据查询"
                     }
+                    button {
+                        class: catalog_class,
+                        onclick: move |_| {
+                            selected.set(Some(SubMenu::MetadataCatalog));
+                            navigator.push(Route::MetadataCatalogPage {});
+                        },
+                        Icon { icon: HiBookOpen, class: "w-4 h-4" }
+                        "元数据目录"
+                    }
+                    button {
+                        class: lineage_class,
+                        onclick: move |_| {
+                            selected.set(Some(SubMenu::DataLineage));
+                            navigator.push(Route::LineageGraphPage {});
+                        },
+                        Icon { icon: HiSwitchHorizontal, class: "w-4 h-4" }
+                        "数据血缘"
+                    }
                     button {
                         class: sync_class,
                         onclick: move |_| selected.set(Some(SubMenu::DataSync)),
@@ -238,13 +298,19 @@ pub fn SidebarLeft() -> Element {
                 div { class: "pl-6 flex flex-col gap-2",
                     button {
                         class: collection_class,
-                        onclick: move |_| selected.set(Some(SubMenu::CollectionTaskMonitoring)),
+                        onclick: move |_| {
+                            selected.set(Some(SubMenu::CollectionTaskMonitoring));
+                            navigator.push(Route::TaskRunMonitorPage { task_type: "collection".to_string() });
+                        },
                         Icon { icon: HiEye, class: "w-4 h-4" }
                         "采集任务监控"
                     }
                     button {
                         class: wide_class,
-                        onclick: move |_| selected.set(Some(SubMenu::WideTableTaskMonitoring)),
+                        onclick: move |_| {
+                            selected.set(Some(SubMenu::WideTableTaskMonitoring));
+                            navigator.push(Route::TaskRunMonitorPage { task_type: "transform".to_string() });
+                        },
                         Icon { icon: HiTable, class: "w-4 h-4" }
                         "宽表任务监控"
                     }
@@ -0,0 +1,164 @@
+use dioxus::prelude::*;
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{CloseEvent, Event, MessageEvent, WebSocket};
+use crate::models::task_run::{RunEvent, RunStatus, TaskRun, TaskRunLogLine};
+
+// 服务端因推送落后而断开时的关闭码
+const CLOSE_LAGGED: u16 = 1013;
+
+// 订阅运行日志：服务端先回放已有日志和当前状态，运行中则持续推送直到结束后关闭连接
+fn open_log_socket(
+    run_id: &str,
+    mut lines: Signal<Vec<TaskRunLogLine>>,
+    mut current: Signal<TaskRun>,
+    mut finished: Signal<bool>,
+    mut error_msg: Signal<String>,
+) -> Option<WebSocket> {
+    let ws = match WebSocket::new(&format!("ws://localhost:3000/api/v1/run/{}/ws", run_id)) {
+        Ok(ws) => ws,
+        Err(_) => {
+            error_msg.set("无法建立日志连接".to_string());
+            finished.set(true);
+            return None;
+        }
+    };
+
+    let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
+        let Some(text) = e.data().as_string() else {
+            return;
+        };
+        match serde_json::from_str::<RunEvent>(&text) {
+            Ok(RunEvent::Log(line)) => lines.with_mut(|l| l.push(line)),
+            Ok(RunEvent::Status(run)) => {
+                // 运行结束时推送的状态不带任务名称，沿用已有的
+                let task_name = current.peek().task_name.clone();
+                if run.task_name.is_empty() {
+                    current.set(TaskRun { task_name, ..run });
+                } else {
+                    current.set(run);
+                }
+            }
+            Err(e) => error_msg.set(e.to_string()),
+        }
+    });
+    ws.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    onmessage.forget();
+
+    let onclose = Closure::<dyn FnMut(CloseEvent)>::new(move |e: CloseEvent| {
+        finished.set(true);
+        if e.code() == CLOSE_LAGGED {
+            error_msg.set("日志输出过快，推送已断开，请重新打开查看".to_string());
+        } else if !e.reason().is_empty() {
+            error_msg.set(e.reason());
+        }
+    });
+    ws.set_onclose(Some(onclose.as_ref().unchecked_ref()));
+    onclose.forget();
+
+    let onerror = Closure::<dyn FnMut(Event)>::new(move |_: Event| {
+        error_msg.set("日志连接失败，请确认登录状态".to_string());
+    });
+    ws.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+    onerror.forget();
+
+    Some(ws)
+}
+
+#[component]
+pub fn TaskRunLogDialog(
+    run: TaskRun,
+    on_close: EventHandler<()>,
+) -> Element {
+    let current = use_signal(|| run.clone());
+    let lines = use_signal(|| Vec::<TaskRunLogLine>::new());
+    let finished = use_signal(|| false);
+    let error_msg = use_signal(String::new);
+
+    let run_id = run.id.clone();
+    let socket = use_hook(move || open_log_socket(&run_id, lines, current, finished, error_msg));
+
+    // 关闭对话框时断开连接，先移除回调避免写入已销毁的状态
+    use_drop(move || {
+        if let Some(ws) = &socket {
+            ws.set_onmessage(None);
+            ws.set_onclose(None);
+            ws.set_onerror(None);
+            let _ = ws.close();
+        }
+    });
+
+    rsx! {
+        dialog {
+            class: "modal modal-open",
+            div {
+                class: "modal-box max-w-5xl",
+                h3 { class: "text-lg font-bold mb-4",
+                    "运行日志 - "
+                    if current().task_name.is_empty() { "{current().task_id}" } else { "{current().task_name}" }
+                }
+
+                div { class: "flex flex-wrap gap-4 text-sm mb-4",
+                    span { class: current().status.badge_class(), "{current().status}" }
+                    span { "开始：{current().started_at}" }
+                    if !current().finished_at.is_empty() {
+                        span { "结束：{current().finished_at}" }
+                    }
+                    span { "耗时：{current().duration_text()}" }
+                    span { "读取 {current().rows_read} 行 / 写入 {current().rows_written} 行" }
+                    span { "数据量：{current().bytes_text()}" }
+                }
+
+                if current().status == RunStatus::Failed && !current().error_chain.is_empty() {
+                    div { class: "alert alert-error mb-4",
+                        div { class: "space-y-1 text-sm",
+                            for (i, cause) in current().error_chain.iter().enumerate() {
+                                div { class: "break-all",
+                                    if i > 0 { span { class: "opacity-60", "原因：" } }
+                                    "{cause}"
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "bg-base-300 rounded p-3 font-mono text-xs max-h-[28rem] overflow-auto",
+                    if lines().is_empty() {
+                        div { class: "text-base-content/60",
+                            if finished() { "暂无日志" } else { "等待日志..." }
+                        }
+                    }
+                    for line in lines().iter() {
+                        div { key: "{line.seq}", class: "whitespace-pre-wrap break-all {line.level_class()}",
+                            span { class: "opacity-60 mr-2", "{line.logged_at}" }
+                            span { class: "uppercase mr-2", "{line.level}" }
+                            "{line.message}"
+                        }
+                    }
+                    if !finished() && !lines().is_empty() {
+                        div { class: "text-base-content/60 mt-1", "..." }
+                    }
+                }
+
+                if !error_msg().is_empty() {
+                    p { class: "text-error text-sm mt-2", "{error_msg()}" }
+                }
+
+                div {
+                    class: "modal-action",
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| on_close.call(()),
+                        "关闭"
+                    }
+                }
+            }
+
+            form {
+                method: "dialog",
+                class: "modal-backdrop",
+                onclick: move |_| on_close.call(()),
+                button { "close" }
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use dioxus::prelude::*;
+use crate::models::transform::TransformTask;
+
+#[component]
+pub fn TransformDeleteDialog(
+    task: TransformTask,
+    on_confirm: EventHandler<TransformTask>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    let handle_confirm = {
+        let task = task.clone();
+        move |_| {
+            on_confirm.call(task.clone());
+        }
+    };
+
+    rsx! {
+        dialog {
+            class: "modal modal-open",
+            div {
+                class: "modal-box",
+                h3 {
+                    class: "text-lg font-bold mb-4",
+                    "删除确认"
+                }
+
+                p {
+                    class: "py-4 text-base-content/80",
+                    "请确认是否删除宽表任务:"
+                }
+
+                div {
+                    class: "bg-base-200 p-3 rounded-lg mb-4",
+                    div { class: "font-medium", "{task.name}" }
+                    div { class: "text-sm text-base-content/60", "{task.output_table}" }
+                }
+
+                p {
+                    class: "text-warning text-sm mb-4",
+                    "警告: 删除任务后其调度配置一并删除，已生成的输出表不受影响!"
+                }
+
+                div {
+                    class: "modal-action",
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| on_cancel.call(()),
+                        "取消"
+                    }
+
+                    button {
+                        class: "btn btn-error",
+                        onclick: handle_confirm,
+                        "确认删除"
+                    }
+                }
+            }
+
+            form {
+                method: "dialog",
+                class: "modal-backdrop",
+                onclick: move |_| on_cancel.call(()),
+                button { "close" }
+            }
+        }
+    }
+}
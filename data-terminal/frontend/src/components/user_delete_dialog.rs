@@ -0,0 +1,67 @@
+use dioxus::prelude::*;
+use crate::models::user::User;
+
+#[component]
+pub fn UserDeleteDialog(
+    user: User,
+    on_confirm: EventHandler<User>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    let handle_confirm = {
+        let user = user.clone();
+        move |_| {
+            on_confirm.call(user.clone());
+        }
+    };
+
+    rsx! {
+        dialog {
+            class: "modal modal-open",
+            div {
+                class: "modal-box",
+                h3 {
+                    class: "text-lg font-bold mb-4",
+                    "删除确认"
+                }
+
+                p {
+                    class: "py-4 text-base-content/80",
+                    "请确认是否删除用户:"
+                }
+
+                div {
+                    class: "bg-base-200 p-3 rounded-lg mb-4",
+                    div { class: "font-medium", "{user.name()}" }
+                    div { class: "text-sm text-base-content/60", "{user.username}" }
+                }
+
+                p {
+                    class: "text-warning text-sm mb-4",
+                    "警告: 删除后该用户将无法登录，如需暂时停用请改为禁用!"
+                }
+
+                div {
+                    class: "modal-action",
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| on_cancel.call(()),
+                        "取消"
+                    }
+
+                    button {
+                        class: "btn btn-error",
+                        onclick: handle_confirm,
+                        "确认删除"
+                    }
+                }
+            }
+
+            form {
+                method: "dialog",
+                class: "modal-backdrop",
+                onclick: move |_| on_cancel.call(()),
+                button { "close" }
+            }
+        }
+    }
+}
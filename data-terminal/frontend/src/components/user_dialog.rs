@@ -0,0 +1,164 @@
+use dioxus::prelude::*;
+use crate::models::protocol::ApiResponse;
+use crate::models::user::{User, UserForm, UserStatus};
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserModalMode {
+    Add,
+    Edit(User),
+}
+
+#[component]
+pub fn UserDialog(
+    mode: UserModalMode,
+    on_close: EventHandler<()>,
+    on_saved: EventHandler<()>,
+) -> Element {
+    let is_edit = matches!(mode, UserModalMode::Edit(_));
+    let mut form = use_signal(|| match &mode {
+        UserModalMode::Add => UserForm::default(),
+        UserModalMode::Edit(user) => UserForm::from(user),
+    });
+    let mut confirm_password = use_signal(String::new);
+    let mut error_msg = use_signal(String::new);
+
+    let handle_save = move |_: MouseEvent| {
+        if form().username.trim().is_empty() {
+            error_msg.set("请输入登录名".to_string());
+            return;
+        }
+        if !is_edit && form().password.is_empty() {
+            error_msg.set("请输入密码".to_string());
+            return;
+        }
+        if form().password != confirm_password() {
+            error_msg.set("两次输入的密码不一致".to_string());
+            return;
+        }
+        let path = if is_edit { "/api/v1/user/update" } else { "/api/v1/user/add" };
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+            match client.post(path, Some(req_config), form()).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                    Ok(api_response) if api_response.result => on_saved.call(()),
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => error_msg.set(e.to_string()),
+            }
+        });
+    };
+
+    rsx! {
+        dialog {
+            class: "modal modal-open",
+            div {
+                class: "modal-box",
+                h3 { class: "text-lg font-bold mb-4", if is_edit { "编辑用户" } else { "新增用户" } }
+
+                div { class: "space-y-4",
+                    div { class: "form-control",
+                        label { class: "label", span { class: "label-text", "登录名" } }
+                        input {
+                            class: "input input-bordered w-full",
+                            disabled: is_edit,
+                            placeholder: "英文、数字、下划线、点或横线",
+                            value: "{form().username}",
+                            oninput: move |e| form.with_mut(|f| f.username = e.value()),
+                        }
+                    }
+
+                    div { class: "form-control",
+                        label { class: "label", span { class: "label-text", "显示名称" } }
+                        input {
+                            class: "input input-bordered w-full",
+                            value: "{form().display_name}",
+                            oninput: move |e| form.with_mut(|f| f.display_name = e.value()),
+                        }
+                    }
+
+                    div { class: "grid grid-cols-2 gap-4",
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "密码" } }
+                            input {
+                                class: "input input-bordered w-full",
+                                r#type: "password",
+                                placeholder: if is_edit { "留空则不修改" } else { "至少 8 位" },
+                                value: "{form().password}",
+                                oninput: move |e| form.with_mut(|f| f.password = e.value()),
+                            }
+                        }
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "确认密码" } }
+                            input {
+                                class: "input input-bordered w-full",
+                                r#type: "password",
+                                value: "{confirm_password}",
+                                oninput: move |e| confirm_password.set(e.value()),
+                            }
+                        }
+                    }
+
+                    div { class: "grid grid-cols-2 gap-4",
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "状态" } }
+                            select {
+                                class: "select select-bordered w-full",
+                                onchange: move |e| form.with_mut(|f| {
+                                    f.status = match e.value().as_str() {
+                                        "disabled" => UserStatus::Disabled,
+                                        _ => UserStatus::Active,
+                                    };
+                                }),
+                                option { value: "active", selected: form().status == UserStatus::Active, "启用" }
+                                option { value: "disabled", selected: form().status == UserStatus::Disabled, "禁用" }
+                            }
+                        }
+                        div { class: "form-control justify-end",
+                            label { class: "label cursor-pointer justify-start gap-2",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "toggle toggle-info",
+                                    checked: form().is_admin,
+                                    onchange: move |e| form.with_mut(|f| f.is_admin = e.checked()),
+                                }
+                                span { class: "label-text", "管理员" }
+                            }
+                        }
+                    }
+
+                    if !error_msg().is_empty() {
+                        p { class: "text-error text-sm", "{error_msg()}" }
+                    }
+                }
+
+                div {
+                    class: "modal-action",
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| on_close.call(()),
+                        "取消"
+                    }
+                    button {
+                        class: "btn btn-info",
+                        onclick: handle_save,
+                        "保存"
+                    }
+                }
+            }
+
+            form {
+                method: "dialog",
+                class: "modal-backdrop",
+                onclick: move |_| on_close.call(()),
+                button { "close" }
+            }
+        }
+    }
+}
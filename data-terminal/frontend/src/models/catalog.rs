@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogSourceType {
+    #[default]
+    Datasource,
+    Resource,
+}
+
+impl std::fmt::Display for CatalogSourceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CatalogSourceType::Datasource => write!(f, "datasource"),
+            CatalogSourceType::Resource => write!(f, "resource"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftType {
+    TableAdded,
+    TableRemoved,
+    ColumnAdded,
+    ColumnRemoved,
+    #[default]
+    ColumnChanged,
+}
+
+impl DriftType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DriftType::TableAdded => "新增表",
+            DriftType::TableRemoved => "删除表",
+            DriftType::ColumnAdded => "新增列",
+            DriftType::ColumnRemoved => "删除列",
+            DriftType::ColumnChanged => "修改列",
+        }
+    }
+
+    pub fn badge_class(&self) -> &'static str {
+        match self {
+            DriftType::TableAdded | DriftType::ColumnAdded => "badge badge-success",
+            DriftType::TableRemoved | DriftType::ColumnRemoved => "badge badge-error",
+            DriftType::ColumnChanged => "badge badge-warning",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CatalogSnapshot {
+    pub id: String,
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+    pub source_name: String,
+    pub version: i32,
+    pub is_latest: bool,
+    pub table_count: i32,
+    pub column_count: i32,
+    pub drift_count: i32,
+    pub created_at: String,
+    pub checked_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CatalogDrift {
+    pub id: String,
+    pub snapshot_id: String,
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+    pub from_version: i32,
+    pub to_version: i32,
+    pub schema_name: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub drift_type: DriftType,
+    #[serde(default)]
+    pub before_value: Option<String>,
+    #[serde(default)]
+    pub after_value: Option<String>,
+    pub detected_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SnapshotResult {
+    pub snapshot: CatalogSnapshot,
+    pub changed: bool,
+    pub drifts: Vec<CatalogDrift>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CatalogSnapshotRequest {
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+}
+
+// 搜索结果，column_name 为空时表示命中的是表
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CatalogSearchHit {
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+    pub source_name: String,
+    pub version: i32,
+    pub schema_name: String,
+    pub table_name: String,
+    pub table_type: String,
+    pub column_name: String,
+    pub column_type: String,
+    pub comment: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CatalogColumn {
+    pub column_name: String,
+    pub column_type: String,
+    #[serde(default)]
+    pub comment: String,
+}
+
+// 快照中的一张表，后端把表字段与列表平铺在同一层
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CatalogTableDetail {
+    pub schema_name: String,
+    pub table_name: String,
+    pub table_type: String,
+    #[serde(default)]
+    pub comment: String,
+    #[serde(default)]
+    pub columns: Vec<CatalogColumn>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CatalogSnapshotDetail {
+    pub snapshot: CatalogSnapshot,
+    pub tables: Vec<CatalogTableDetail>,
+}
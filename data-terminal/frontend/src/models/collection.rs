@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// 采集分类枚举
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectionCategory {
+    Database,
+    Api,
+    Crawler,
+}
+
+impl fmt::Display for CollectionCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollectionCategory::Database => write!(f, "数据库"),
+            CollectionCategory::Api => write!(f, "API"),
+            CollectionCategory::Crawler => write!(f, "爬虫"),
+        }
+    }
+}
+
+// 采集类型枚举
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectType {
+    Full,
+    Incremental,
+    Stream,
+}
+
+impl fmt::Display for CollectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollectType::Full => write!(f, "全量"),
+            CollectType::Incremental => write!(f, "增量"),
+            CollectType::Stream => write!(f, "实时"),
+        }
+    }
+}
+
+// 采集任务结构体
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CollectionTask {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub category: CollectionCategory,
+    pub collect_type: CollectType,
+    pub datasource_id: String,
+    pub resource_id: String,
+    pub rule: serde_json::Value,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+// 创建/更新采集任务结构体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionTaskCreateUpdate {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub category: CollectionCategory,
+    pub collect_type: CollectType,
+    pub datasource_id: String,
+    pub resource_id: String,
+    pub rule: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollectionModalMode {
+    Add,
+    Edit(CollectionTask),
+}
+
+// 增量采集水位
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CollectionWatermark {
+    pub source_table: String,
+    pub watermark_column: String,
+    #[serde(default)]
+    pub watermark: serde_json::Value,
+    pub updated_at: String,
+}
+
+impl CollectionWatermark {
+    // 水位值以 {"type": .., "value": ..} 形式返回，仅展示 value
+    pub fn value_text(&self) -> String {
+        match self.watermark.get("value") {
+            Some(serde_json::Value::String(v)) => v.clone(),
+            Some(v) => v.to_string(),
+            None => "-".to_string(),
+        }
+    }
+}
+
+// 实时采集消费位点
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CollectionCheckpoint {
+    #[serde(default)]
+    pub checkpoint: serde_json::Value,
+    pub updated_at: String,
+}
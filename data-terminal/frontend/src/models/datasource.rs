@@ -68,6 +68,10 @@ pub struct DataSource {
     pub datasource_type: DataSourceType,
     pub connection_config: serde_json::Value,
     pub connection_status: ConnectionStatus,
+    #[serde(default)]
+    pub last_checked_at: String,
+    #[serde(default)]
+    pub last_error: String,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -92,3 +96,69 @@ pub struct DataSourceFormData {
     pub datasource_type: DataSourceType,
     pub connection_config: serde_json::Value,
 }
+
+// 连接探测失败原因
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PingErrorKind {
+    InvalidConfig,
+    Dns,
+    Connection,
+    Auth,
+    Tls,
+    Timeout,
+    Database,
+    Http,
+    Schema,
+    Unknown,
+}
+
+impl fmt::Display for PingErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PingErrorKind::InvalidConfig => write!(f, "配置无效"),
+            PingErrorKind::Dns => write!(f, "域名解析失败"),
+            PingErrorKind::Connection => write!(f, "无法建立连接"),
+            PingErrorKind::Auth => write!(f, "认证失败"),
+            PingErrorKind::Tls => write!(f, "TLS握手失败"),
+            PingErrorKind::Timeout => write!(f, "连接超时"),
+            PingErrorKind::Database => write!(f, "数据库错误"),
+            PingErrorKind::Http => write!(f, "HTTP状态异常"),
+            PingErrorKind::Schema => write!(f, "响应结构不匹配"),
+            PingErrorKind::Unknown => write!(f, "未知错误"),
+        }
+    }
+}
+
+// 连接探测结果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct PingDiagnostic {
+    #[serde(default)]
+    pub success: bool,
+    #[serde(default)]
+    pub latency_ms: u64,
+    #[serde(default)]
+    pub server_version: Option<String>,
+    #[serde(default)]
+    pub error_kind: Option<PingErrorKind>,
+    #[serde(default)]
+    pub message: String,
+}
+
+impl PingDiagnostic {
+    // 探测成功的提示文案
+    pub fn success_text(&self) -> String {
+        match &self.server_version {
+            Some(version) => format!("连接成功，耗时 {} ms，服务端版本 {}", self.latency_ms, version),
+            None => format!("连接成功，耗时 {} ms", self.latency_ms),
+        }
+    }
+
+    // 探测失败的提示文案，没有失败分类时使用后端返回的 msg
+    pub fn failure_text(&self, msg: &str) -> String {
+        match self.error_kind {
+            Some(kind) => format!("[{}] {}", kind, self.message),
+            None => msg.to_string(),
+        }
+    }
+}
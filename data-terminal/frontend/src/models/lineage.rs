@@ -0,0 +1,236 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use crate::models::catalog::CatalogSourceType;
+
+// 画布上表节点的尺寸与间距
+pub const TABLE_WIDTH: f64 = 220.0;
+pub const TABLE_HEADER: f64 = 44.0;
+pub const COLUMN_ROW: f64 = 24.0;
+const LAYER_GAP: f64 = 120.0;
+const TABLE_GAP: f64 = 32.0;
+const CANVAS_PADDING: f64 = 32.0;
+
+// 血缘节点：数据源或资源上的一张表或一列，column_name 为空时表示整张表
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct LineageNode {
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+    #[serde(default)]
+    pub source_name: String,
+    pub schema_name: String,
+    pub table_name: String,
+    #[serde(default)]
+    pub column_name: String,
+}
+
+impl LineageNode {
+    // 所属表的唯一键，库表名不区分大小写
+    pub fn table_key(&self) -> String {
+        format!(
+            "{}/{}/{}.{}",
+            self.source_type,
+            self.source_id,
+            self.schema_name.to_lowercase(),
+            self.table_name.to_lowercase()
+        )
+    }
+
+    pub fn table_label(&self) -> String {
+        if self.schema_name.is_empty() {
+            self.table_name.clone()
+        } else {
+            format!("{}.{}", self.schema_name, self.table_name)
+        }
+    }
+
+    // 同一张表的表级节点
+    pub fn table(&self) -> LineageNode {
+        LineageNode { column_name: String::new(), ..self.clone() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct LineageEdge {
+    pub id: String,
+    pub task_type: String,
+    pub task_id: String,
+    pub task_name: String,
+    pub source_type: CatalogSourceType,
+    pub source_id: String,
+    pub source_schema: String,
+    pub source_table: String,
+    pub source_column: String,
+    pub target_type: CatalogSourceType,
+    pub target_id: String,
+    pub target_schema: String,
+    pub target_table: String,
+    pub target_column: String,
+    #[serde(default)]
+    pub expression: String,
+    #[serde(default)]
+    pub created_at: String,
+}
+
+impl LineageEdge {
+    pub fn source_node(&self) -> LineageNode {
+        LineageNode {
+            source_type: self.source_type,
+            source_id: self.source_id.clone(),
+            source_name: String::new(),
+            schema_name: self.source_schema.clone(),
+            table_name: self.source_table.clone(),
+            column_name: self.source_column.clone(),
+        }
+    }
+
+    pub fn target_node(&self) -> LineageNode {
+        LineageNode {
+            source_type: self.target_type,
+            source_id: self.target_id.clone(),
+            source_name: String::new(),
+            schema_name: self.target_schema.clone(),
+            table_name: self.target_table.clone(),
+            column_name: self.target_column.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct LineageGraph {
+    pub root: LineageNode,
+    pub nodes: Vec<LineageNode>,
+    pub edges: Vec<LineageEdge>,
+}
+
+impl LineageGraph {
+    // 合并另一次追溯的结果，根节点保持不变
+    pub fn merge(&mut self, other: LineageGraph) {
+        for node in other.nodes {
+            if !self.nodes.iter().any(|n| n.table_key() == node.table_key() && n.column_name == node.column_name) {
+                self.nodes.push(node);
+            }
+        }
+        for edge in other.edges {
+            if !self.edges.iter().any(|e| e.id == edge.id) {
+                self.edges.push(edge);
+            }
+        }
+    }
+
+    // 数据源或资源名称，来自追溯结果中的节点
+    pub fn source_name(&self, node: &LineageNode) -> String {
+        self.nodes
+            .iter()
+            .find(|n| n.source_type == node.source_type && n.source_id == node.source_id && !n.source_name.is_empty())
+            .map(|n| n.source_name.clone())
+            .unwrap_or_default()
+    }
+}
+
+// 画布上的一张表及其参与血缘的列
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableBox {
+    pub key: String,
+    pub node: LineageNode,
+    pub columns: Vec<String>,
+    pub layer: i32,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl TableBox {
+    pub fn height(&self) -> f64 {
+        TABLE_HEADER + self.columns.len() as f64 * COLUMN_ROW
+    }
+
+    // 列所在行的中线，表级血缘连到表头
+    pub fn anchor_y(&self, column: &str) -> f64 {
+        match self.columns.iter().position(|c| c.eq_ignore_ascii_case(column)) {
+            Some(index) if !column.is_empty() => self.y + TABLE_HEADER + (index as f64 + 0.5) * COLUMN_ROW,
+            _ => self.y + TABLE_HEADER / 2.0,
+        }
+    }
+}
+
+/// 以根节点所在表为第 0 层，上游向左、下游向右分层排列；与根节点不连通的表不显示
+pub fn layout(root: &LineageNode, edges: &[LineageEdge]) -> Vec<TableBox> {
+    let root_key = root.table_key();
+    let mut tables: BTreeMap<String, (LineageNode, BTreeSet<String>)> = BTreeMap::new();
+    tables.insert(root_key.clone(), (root.table(), BTreeSet::new()));
+    if !root.column_name.is_empty() {
+        tables.entry(root_key.clone()).or_default().1.insert(root.column_name.clone());
+    }
+    let mut adjacency: HashMap<String, Vec<(String, i32)>> = HashMap::new();
+    for edge in edges {
+        let (source, target) = (edge.source_node(), edge.target_node());
+        let (source_key, target_key) = (source.table_key(), target.table_key());
+        for node in [source, target] {
+            let entry = tables.entry(node.table_key()).or_insert_with(|| (node.table(), BTreeSet::new()));
+            if !node.column_name.is_empty() {
+                entry.1.insert(node.column_name.clone());
+            }
+        }
+        adjacency.entry(source_key.clone()).or_default().push((target_key.clone(), 1));
+        adjacency.entry(target_key).or_default().push((source_key, -1));
+    }
+
+    let mut layers = HashMap::from([(root_key.clone(), 0)]);
+    let mut queue = VecDeque::from([root_key]);
+    while let Some(key) = queue.pop_front() {
+        let layer = layers[&key];
+        for (next, step) in adjacency.get(&key).into_iter().flatten() {
+            if !layers.contains_key(next) {
+                layers.insert(next.clone(), layer + step);
+                queue.push_back(next.clone());
+            }
+        }
+    }
+
+    let min_layer = layers.values().copied().min().unwrap_or(0);
+    let mut offsets: HashMap<i32, f64> = HashMap::new();
+    let mut boxes = Vec::new();
+    for (key, (node, columns)) in tables {
+        let Some(&layer) = layers.get(&key) else {
+            continue;
+        };
+        let y = offsets.entry(layer).or_insert(CANVAS_PADDING);
+        let table = TableBox {
+            key,
+            node,
+            columns: columns.into_iter().collect(),
+            layer,
+            x: CANVAS_PADDING + (layer - min_layer) as f64 * (TABLE_WIDTH + LAYER_GAP),
+            y: *y,
+        };
+        *y += table.height() + TABLE_GAP;
+        boxes.push(table);
+    }
+    boxes
+}
+
+/// 画布需要的宽高
+pub fn canvas_size(boxes: &[TableBox]) -> (f64, f64) {
+    let width = boxes.iter().map(|b| b.x + TABLE_WIDTH).fold(0.0, f64::max) + CANVAS_PADDING;
+    let height = boxes.iter().map(|b| b.y + b.height()).fold(0.0, f64::max) + CANVAS_PADDING;
+    (width, height)
+}
+
+/// 从上游列的右侧连到下游列的左侧
+pub fn lineage_path(from: &TableBox, from_column: &str, to: &TableBox, to_column: &str) -> String {
+    let (x1, y1) = (from.x + TABLE_WIDTH, from.anchor_y(from_column));
+    let (x2, y2) = (to.x, to.anchor_y(to_column));
+    let bend = ((x2 - x1).abs() / 2.0).max(40.0);
+    format!("M {} {} C {} {}, {} {}, {} {}", x1, y1, x1 + bend, y1, x2 - bend, y2, x2, y2)
+}
+
+/// 与某张表相连的边涉及的任务：(任务类型, 任务ID, 任务名称)
+pub fn table_tasks(key: &str, edges: &[LineageEdge]) -> Vec<(String, String, String)> {
+    let mut seen = HashSet::new();
+    edges
+        .iter()
+        .filter(|e| e.source_node().table_key() == key || e.target_node().table_key() == key)
+        .filter(|e| seen.insert((e.task_type.clone(), e.task_id.clone())))
+        .map(|e| (e.task_type.clone(), e.task_id.clone(), e.task_name.clone()))
+        .collect()
+}
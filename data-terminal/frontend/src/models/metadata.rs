@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct TableSummary {
+    pub name: String,
+    // table 或 view
+    pub table_type: String,
+    pub comment: String,
+    #[serde(default)]
+    pub approx_rows: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SchemaMeta {
+    pub name: String,
+    pub tables: Vec<TableSummary>,
+}
+
+// 数据源的库表目录，default_schema 为采集任务读取源表时使用的库或 schema
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct DataSourceCatalog {
+    pub datasource_id: String,
+    pub database: String,
+    pub default_schema: String,
+    pub schemas: Vec<SchemaMeta>,
+    pub refreshed_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ColumnInfo {
+    pub name: String,
+    pub column_type: String,
+    pub data_type: String,
+    pub nullable: bool,
+    #[serde(default)]
+    pub default_value: Option<String>,
+    pub comment: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct IndexInfo {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub unique: bool,
+    pub primary: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct TableDetail {
+    pub schema: String,
+    pub name: String,
+    pub table_type: String,
+    pub comment: String,
+    #[serde(default)]
+    pub approx_rows: Option<i64>,
+    pub columns: Vec<ColumnInfo>,
+    pub primary_key: Vec<String>,
+    pub indexes: Vec<IndexInfo>,
+    pub refreshed_at: String,
+}
+
+// 估算行数的展示文本
+pub fn approx_rows_text(rows: Option<i64>) -> String {
+    match rows {
+        Some(n) if n >= 100_000_000 => format!("≈{:.1}亿行", n as f64 / 100_000_000.0),
+        Some(n) if n >= 10_000 => format!("≈{:.1}万行", n as f64 / 10_000.0),
+        Some(n) => format!("≈{}行", n),
+        None => "-".to_string(),
+    }
+}
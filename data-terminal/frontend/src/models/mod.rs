@@ -2,4 +2,14 @@ pub mod protocol;
 pub mod project;
 pub mod resource;
 pub mod datasource;
-pub mod config;
\ No newline at end of file
+pub mod config;
+pub mod collection;
+pub mod task_run;
+pub mod schedule;
+pub mod pipeline;
+pub mod transform;
+pub mod query;
+pub mod metadata;
+pub mod catalog;
+pub mod lineage;
+pub mod user;
\ No newline at end of file
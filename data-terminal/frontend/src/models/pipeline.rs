@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+// 画布上节点的尺寸（像素）
+pub const NODE_WIDTH: f64 = 176.0;
+pub const NODE_HEIGHT: f64 = 56.0;
+
+fn default_retry_interval() -> u64 {
+    60
+}
+
+fn default_max_parallel() -> i32 {
+    4
+}
+
+// 流水线中的任务节点
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct PipelineNode {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    pub task_type: String,
+    pub task_id: String,
+    #[serde(default)]
+    pub retries: u32,
+    #[serde(default = "default_retry_interval")]
+    pub retry_interval_secs: u64,
+    #[serde(default)]
+    pub x: f64,
+    #[serde(default)]
+    pub y: f64,
+}
+
+// 依赖边：from 成功后执行 to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub struct PipelineEdge {
+    pub from: String,
+    pub to: String,
+}
+
+// 流水线
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Pipeline {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub nodes: Vec<PipelineNode>,
+    #[serde(default)]
+    pub edges: Vec<PipelineEdge>,
+    #[serde(default = "default_max_parallel")]
+    pub max_parallel: i32,
+    #[serde(default, skip_serializing)]
+    pub created_at: String,
+    #[serde(default, skip_serializing)]
+    pub updated_at: String,
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            name: String::new(),
+            description: String::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            max_parallel: default_max_parallel(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+}
+
+// 可拖入画布的任务
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskOption {
+    pub task_type: String,
+    pub task_id: String,
+    pub name: String,
+}
+
+// 设计器中的校验问题，node_id 为空表示针对整个流水线
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineProblem {
+    pub node_id: Option<String>,
+    pub message: String,
+}
+
+pub fn task_type_text(task_type: &str) -> &'static str {
+    match task_type {
+        "collection" => "采集任务",
+        "transform" => "宽表任务",
+        "sync" => "同步任务",
+        _ => "未知任务",
+    }
+}
+
+impl Pipeline {
+    pub fn node(&self, id: &str) -> Option<&PipelineNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    pub fn contains_task(&self, task_type: &str, task_id: &str) -> bool {
+        self.nodes.iter().any(|n| n.task_type == task_type && n.task_id == task_id)
+    }
+
+    // 生成未被占用的节点ID
+    pub fn next_node_id(&self) -> String {
+        (1..)
+            .map(|i| format!("node_{}", i))
+            .find(|id| self.node(id).is_none())
+            .unwrap_or_default()
+    }
+
+    // 删除节点及其相连的边
+    pub fn remove_node(&mut self, id: &str) {
+        self.nodes.retain(|n| n.id != id);
+        self.edges.retain(|e| e.from != id && e.to != id);
+    }
+
+    // 与后端保存时的校验保持一致，额外检查节点引用的任务是否还存在
+    pub fn problems(&self, tasks: &[TaskOption]) -> Vec<PipelineProblem> {
+        let mut problems = Vec::new();
+        let mut add = |node_id: Option<&str>, message: String| {
+            problems.push(PipelineProblem { node_id: node_id.map(|s| s.to_string()), message });
+        };
+
+        if self.name.trim().is_empty() {
+            add(None, "流水线名称不能为空".to_string());
+        }
+        if !(1..=32).contains(&self.max_parallel) {
+            add(None, "最大并行数需在 1 到 32 之间".to_string());
+        }
+        if self.nodes.is_empty() {
+            add(None, "请从左侧拖入至少一个任务".to_string());
+        }
+        for node in &self.nodes {
+            if !tasks.iter().any(|t| t.task_type == node.task_type && t.task_id == node.task_id) {
+                add(Some(node.id.as_str()), format!("节点 {} 引用的{}已不存在或不能编排", node.id, task_type_text(&node.task_type)));
+            }
+            if node.retries > 10 {
+                add(Some(node.id.as_str()), format!("节点 {} 的重试次数不能超过 10", node.id));
+            }
+            if node.retry_interval_secs > 3600 {
+                add(Some(node.id.as_str()), format!("节点 {} 的重试间隔不能超过 3600 秒", node.id));
+            }
+        }
+        for edge in &self.edges {
+            if self.node(&edge.from).is_none() || self.node(&edge.to).is_none() {
+                add(None, format!("连线 {} -> {} 的端点不存在", edge.from, edge.to));
+            }
+        }
+        if let Some(cycle) = self.find_cycle() {
+            let message = format!("存在循环依赖：{}", cycle.join(" -> "));
+            for id in cycle.iter().skip(1) {
+                add(Some(id.as_str()), message.clone());
+            }
+        }
+        problems
+    }
+
+    // 深度优先查找环，返回环上的节点ID（首尾相同）
+    pub fn find_cycle(&self) -> Option<Vec<String>> {
+        let mut downstream: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            downstream.entry(edge.from.as_str()).or_default().push(edge.to.as_str());
+        }
+
+        fn visit<'a>(
+            node: &'a str,
+            downstream: &HashMap<&'a str, Vec<&'a str>>,
+            done: &mut HashSet<&'a str>,
+            path: &mut Vec<&'a str>,
+        ) -> Option<Vec<String>> {
+            if let Some(start) = path.iter().position(|n| *n == node) {
+                let mut cycle: Vec<String> = path[start..].iter().map(|n| n.to_string()).collect();
+                cycle.push(node.to_string());
+                return Some(cycle);
+            }
+            if done.contains(node) {
+                return None;
+            }
+            path.push(node);
+            for next in downstream.get(node).into_iter().flatten() {
+                if let Some(cycle) = visit(next, downstream, done, path) {
+                    return Some(cycle);
+                }
+            }
+            path.pop();
+            done.insert(node);
+            None
+        }
+
+        let mut done = HashSet::new();
+        for node in &self.nodes {
+            let mut path = Vec::new();
+            if let Some(cycle) = visit(node.id.as_str(), &downstream, &mut done, &mut path) {
+                return Some(cycle);
+            }
+        }
+        None
+    }
+}
+
+// 连线的 SVG 路径：从上游节点右侧连到下游节点左侧
+pub fn edge_path(from: &PipelineNode, to: &PipelineNode) -> String {
+    let (x1, y1) = (from.x + NODE_WIDTH, from.y + NODE_HEIGHT / 2.0);
+    let (x2, y2) = (to.x, to.y + NODE_HEIGHT / 2.0);
+    let bend = ((x2 - x1).abs() / 2.0).max(40.0);
+    format!("M {} {} C {} {}, {} {}, {} {}", x1, y1, x1 + bend, y1, x2 - bend, y2, x2, y2)
+}
@@ -16,6 +16,73 @@ pub struct Project {
     pub created_at: String,
     #[serde(skip_serializing, default)]
     pub updated_at: String,
+    // 当前用户在项目中的角色
+    #[serde(skip_serializing, default)]
+    pub role: Option<ProjectRole>,
+}
+
+impl Project {
+    pub fn is_owner(&self) -> bool {
+        self.role == Some(ProjectRole::Owner)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectRole {
+    #[default]
+    Viewer,
+    Developer,
+    Owner,
+}
+
+impl ProjectRole {
+    pub const ALL: [ProjectRole; 3] = [ProjectRole::Owner, ProjectRole::Developer, ProjectRole::Viewer];
+
+    pub fn value(&self) -> &'static str {
+        match self {
+            ProjectRole::Viewer => "viewer",
+            ProjectRole::Developer => "developer",
+            ProjectRole::Owner => "owner",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProjectRole::Viewer => "只读成员",
+            ProjectRole::Developer => "开发者",
+            ProjectRole::Owner => "负责人",
+        }
+    }
+
+    pub fn from_value(value: &str) -> Self {
+        Self::ALL.into_iter().find(|r| r.value() == value).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ProjectMember {
+    pub project_code: String,
+    pub user_id: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub display_name: String,
+    pub role: ProjectRole,
+    #[serde(default)]
+    pub created_at: String,
+}
+
+impl ProjectMember {
+    pub fn name(&self) -> &str {
+        if self.display_name.is_empty() { &self.username } else { &self.display_name }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ProjectMemberForm {
+    pub user_id: String,
+    pub role: ProjectRole,
 }
 
 
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use crate::models::task_run::RunStatus;
+
+// 默认返回行数与语句超时，与后端保持一致
+pub const DEFAULT_MAX_ROWS: u32 = 1000;
+pub const DEFAULT_TIMEOUT_SECS: u32 = 30;
+
+// 查询对象类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryTargetType {
+    #[default]
+    Datasource,
+    Resource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct QueryRequest {
+    pub query_id: String,
+    pub target_type: QueryTargetType,
+    pub target_id: String,
+    pub sql: String,
+    pub max_rows: Option<u32>,
+    pub timeout_secs: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct QueryColumn {
+    pub name: String,
+    pub type_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct QueryResult {
+    pub query_id: String,
+    pub columns: Vec<QueryColumn>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+    pub truncated: bool,
+    pub duration_ms: i64,
+}
+
+impl QueryResult {
+    // 导出为 CSV，NULL 输出为空字段
+    pub fn to_csv(&self) -> String {
+        let mut lines = Vec::with_capacity(self.rows.len() + 1);
+        lines.push(self.columns.iter().map(|c| csv_field(&c.name)).collect::<Vec<_>>().join(","));
+        for row in self.rows.iter() {
+            lines.push(
+                row.iter()
+                    .map(|v| if v.is_null() { String::new() } else { csv_field(&cell_text(v)) })
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+        }
+        lines.join("\r\n")
+    }
+}
+
+// 单元格展示文本，字符串不带引号
+pub fn cell_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "NULL".to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+// 当前用户的查询历史
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QueryHistory {
+    pub id: String,
+    pub target_type: QueryTargetType,
+    pub target_id: String,
+    pub sql_text: String,
+    pub status: RunStatus,
+    pub row_count: i32,
+    #[serde(default)]
+    pub truncated: bool,
+    pub duration_ms: i64,
+    #[serde(default)]
+    pub error_message: Option<String>,
+    pub executed_at: String,
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// 错过触发时间后的处理方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MisfirePolicy {
+    #[default]
+    Skip,
+    FireOnce,
+}
+
+impl fmt::Display for MisfirePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MisfirePolicy::Skip => write!(f, "跳过错过的触发"),
+            MisfirePolicy::FireOnce => write!(f, "立即补跑一次"),
+        }
+    }
+}
+
+// 常用时区
+pub const TIMEZONES: &[&str] = &[
+    "Asia/Shanghai",
+    "Asia/Hong_Kong",
+    "Asia/Tokyo",
+    "Asia/Singapore",
+    "Europe/London",
+    "Europe/Berlin",
+    "America/New_York",
+    "America/Los_Angeles",
+    "UTC",
+];
+
+// 任务调度计划，时间按调度时区展示
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct TaskSchedule {
+    pub task_id: String,
+    pub task_type: String,
+    pub cron: String,
+    pub timezone: String,
+    pub misfire_policy: MisfirePolicy,
+    pub enabled: bool,
+    #[serde(default)]
+    pub next_fire_at: String,
+    #[serde(default)]
+    pub last_fire_at: String,
+    #[serde(default)]
+    pub next_fire_times: Vec<String>,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+// 用于创建和更新的调度计划
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskScheduleCreateUpdate {
+    pub task_type: String,
+    pub cron: String,
+    pub timezone: String,
+    pub misfire_policy: MisfirePolicy,
+    pub enabled: bool,
+}
+
+impl Default for TaskScheduleCreateUpdate {
+    fn default() -> Self {
+        Self {
+            task_type: "collection".to_string(),
+            cron: "0 2 * * *".to_string(),
+            timezone: "Asia/Shanghai".to_string(),
+            misfire_policy: MisfirePolicy::Skip,
+            enabled: true,
+        }
+    }
+}
+
+impl From<TaskSchedule> for TaskScheduleCreateUpdate {
+    fn from(schedule: TaskSchedule) -> Self {
+        Self {
+            task_type: schedule.task_type,
+            cron: schedule.cron,
+            timezone: schedule.timezone,
+            misfire_policy: schedule.misfire_policy,
+            enabled: schedule.enabled,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// 运行状态枚举
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    Running,
+    Success,
+    Failed,
+    Cancelled,
+}
+
+impl fmt::Display for RunStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunStatus::Running => write!(f, "运行中"),
+            RunStatus::Success => write!(f, "成功"),
+            RunStatus::Failed => write!(f, "失败"),
+            RunStatus::Cancelled => write!(f, "已取消"),
+        }
+    }
+}
+
+impl RunStatus {
+    pub fn badge_class(&self) -> &'static str {
+        match self {
+            RunStatus::Running => "badge badge-info",
+            RunStatus::Success => "badge badge-success",
+            RunStatus::Failed => "badge badge-error",
+            RunStatus::Cancelled => "badge badge-ghost",
+        }
+    }
+}
+
+// 任务运行记录
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskRun {
+    pub id: String,
+    pub task_id: String,
+    #[serde(default)]
+    pub task_name: String,
+    pub task_type: String,
+    pub status: RunStatus,
+    pub rows_read: i64,
+    pub rows_written: i64,
+    #[serde(default)]
+    pub bytes: i64,
+    pub duration_ms: i64,
+    #[serde(default)]
+    pub error_message: String,
+    #[serde(default)]
+    pub error_chain: Vec<String>,
+    #[serde(default)]
+    pub detail: serde_json::Value,
+    pub started_at: String,
+    #[serde(default)]
+    pub finished_at: String,
+}
+
+impl TaskRun {
+    // 耗时展示，运行中的记录显示为 -
+    pub fn duration_text(&self) -> String {
+        if self.status == RunStatus::Running {
+            return "-".to_string();
+        }
+        let seconds = self.duration_ms / 1000;
+        match seconds {
+            0 => format!("{} ms", self.duration_ms),
+            1..=59 => format!("{} 秒", seconds),
+            60..=3599 => format!("{} 分 {} 秒", seconds / 60, seconds % 60),
+            _ => format!("{} 时 {} 分", seconds / 3600, seconds % 3600 / 60),
+        }
+    }
+
+    // 数据量展示
+    pub fn bytes_text(&self) -> String {
+        const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+        let mut size = self.bytes as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            format!("{} B", self.bytes)
+        } else {
+            format!("{:.1} {}", size, UNITS[unit])
+        }
+    }
+}
+
+// 运行日志的一行
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct TaskRunLogLine {
+    pub seq: i64,
+    pub level: String,
+    pub message: String,
+    pub logged_at: String,
+}
+
+impl TaskRunLogLine {
+    pub fn level_class(&self) -> &'static str {
+        match self.level.as_str() {
+            "error" => "text-error",
+            "warn" => "text-warning",
+            _ => "",
+        }
+    }
+}
+
+// 增量拉取的运行日志，next 作为下一次请求的 after
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct TaskRunLogPage {
+    pub lines: Vec<TaskRunLogLine>,
+    pub next: i64,
+    pub finished: bool,
+}
+
+// WebSocket 推送的运行事件
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RunEvent {
+    Log(TaskRunLogLine),
+    Status(TaskRun),
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+// 物化方式枚举
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Materialization {
+    #[default]
+    Replace,
+    Append,
+    Merge,
+}
+
+impl fmt::Display for Materialization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Materialization::Replace => write!(f, "覆盖"),
+            Materialization::Append => write!(f, "追加"),
+            Materialization::Merge => write!(f, "按键合并"),
+        }
+    }
+}
+
+fn default_replication_num() -> i32 {
+    1
+}
+
+// 宽表（SQL 加工）任务，保存时与创建/更新共用
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TransformTask {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub resource_id: String,
+    #[serde(default)]
+    pub target_database: String,
+    pub output_table: String,
+    #[serde(default)]
+    pub materialization: Materialization,
+    #[serde(default)]
+    pub merge_keys: Vec<String>,
+    pub sql_text: String,
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+    #[serde(default = "default_replication_num")]
+    pub doris_replication_num: i32,
+    #[serde(default, skip_serializing)]
+    pub referenced_tables: Vec<String>,
+    #[serde(default, skip_serializing)]
+    pub created_at: String,
+    #[serde(default, skip_serializing)]
+    pub updated_at: String,
+}
+
+impl Default for TransformTask {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            name: String::new(),
+            description: String::new(),
+            resource_id: String::new(),
+            target_database: String::new(),
+            output_table: String::new(),
+            materialization: Materialization::Replace,
+            merge_keys: Vec::new(),
+            sql_text: "SELECT\n  *\nFROM ods_table\nWHERE dt = '${biz_date}'".to_string(),
+            params: BTreeMap::new(),
+            doris_replication_num: default_replication_num(),
+            referenced_tables: Vec::new(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+}
+
+// SQL 解析结果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct TransformCheck {
+    pub referenced_tables: Vec<String>,
+    pub params: Vec<String>,
+    pub rendered_sql: String,
+}
+
+// 内置参数及说明，与后端保持一致
+pub const BUILTIN_PARAMS: [(&str, &str); 3] = [
+    ("biz_date", "业务日期，默认运行日前一天，格式 yyyy-mm-dd"),
+    ("biz_date_nodash", "业务日期，格式 yyyymmdd"),
+    ("run_date", "运行日期，格式 yyyy-mm-dd"),
+];
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UserStatus {
+    #[default]
+    Active,
+    Disabled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct User {
+    pub id: String,
+    pub username: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub is_admin: bool,
+    #[serde(default)]
+    pub status: UserStatus,
+    #[serde(default)]
+    pub locked_until: Option<String>,
+    #[serde(default)]
+    pub last_login_at: Option<String>,
+    #[serde(default)]
+    pub created_at: String,
+}
+
+impl User {
+    // 锁定截止时间晚于当前时间视为锁定中
+    pub fn is_locked(&self) -> bool {
+        self.locked_until
+            .as_deref()
+            .is_some_and(|t| js_sys::Date::parse(t) > js_sys::Date::now())
+    }
+
+    pub fn name(&self) -> &str {
+        if self.display_name.is_empty() { &self.username } else { &self.display_name }
+    }
+}
+
+// 添加项目成员时可选的用户
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct UserOption {
+    pub id: String,
+    pub username: String,
+    #[serde(default)]
+    pub display_name: String,
+}
+
+// 新增或编辑用户，编辑时 password 为空表示不修改密码
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct UserForm {
+    pub id: String,
+    pub username: String,
+    pub display_name: String,
+    pub password: String,
+    pub is_admin: bool,
+    pub status: UserStatus,
+}
+
+impl From<&User> for UserForm {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id.clone(),
+            username: user.username.clone(),
+            display_name: user.display_name.clone(),
+            password: String::new(),
+            is_admin: user.is_admin,
+            status: user.status,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct PasswordChangeRequest {
+    pub old_password: String,
+    pub new_password: String,
+}
@@ -0,0 +1,442 @@
+use dioxus::prelude::*;
+use crate::components::collection_dialog::CollectionDialog;
+use crate::components::collection_delete_dialog::CollectionDeleteDialog;
+use crate::components::collection_run_dialog::CollectionRunDialog;
+use crate::components::collection_schedule_dialog::CollectionScheduleDialog;
+use crate::models::{collection::*, datasource::DataSource, resource::Resource, protocol::*, schedule::TaskSchedule};
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+use dioxus_free_icons::{icons::hi_outline_icons::*, Icon};
+use serde::de::DeserializeOwned;
+
+// 拉取列表接口，失败时返回错误信息
+async fn fetch_list<T: DeserializeOwned>(path: &str) -> Result<Vec<T>, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .query_param("page", 1)
+        .query_param("page_size", 100)
+        .build();
+    let response_text = client.get(path, Some(req_config)).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<Vec<T>>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+#[component]
+pub fn CollectionTaskPage(task_id: String) -> Element {
+    let mut tasks = use_signal(|| Vec::<CollectionTask>::new());
+    // 从血缘图等页面跳转时需要直接打开的任务
+    let mut focus_task = use_signal(|| task_id);
+    let mut datasources = use_signal(|| Vec::<DataSource>::new());
+    let mut resources = use_signal(|| Vec::<Resource>::new());
+    let mut error_msg = use_signal(String::new);
+
+    let mut show_dialog = use_signal(|| false);
+    let mut modal_mode = use_signal(|| CollectionModalMode::Add);
+    let mut selected_task = use_signal(|| None as Option<CollectionTask>);
+    let mut show_delete = use_signal(|| false);
+    let mut show_runs = use_signal(|| false);
+    let mut show_schedule = use_signal(|| false);
+    let mut schedules = use_signal(|| Vec::<TaskSchedule>::new());
+    let mut info_msg = use_signal(String::new);
+
+    // 搜索状态
+    let mut type_filter = use_signal(String::new);
+    let mut name_filter = use_signal(String::new);
+
+    let filtered_tasks = use_memo(move || {
+        let mut result = tasks().clone();
+        if !type_filter().is_empty() {
+            result.retain(|t| match type_filter().as_str() {
+                "incremental" => t.collect_type == CollectType::Incremental,
+                "stream" => t.collect_type == CollectType::Stream,
+                _ => t.collect_type == CollectType::Full,
+            });
+        }
+        if !name_filter().is_empty() {
+            result.retain(|t| t.name.to_lowercase().contains(&name_filter().to_lowercase()));
+        }
+        result
+    });
+
+    let refresh_tasks = move || {
+        spawn(async move {
+            match fetch_list::<CollectionTask>("/api/v1/collection/list").await {
+                Ok(list) => {
+                    let focus = focus_task.peek().clone();
+                    if !focus.is_empty() {
+                        if let Some(task) = list.iter().find(|t| t.id == focus) {
+                            modal_mode.set(CollectionModalMode::Edit(task.clone()));
+                            show_dialog.set(true);
+                        }
+                        focus_task.set(String::new());
+                    }
+                    tasks.set(list);
+                }
+                Err(e) => error_msg.set(e),
+            }
+            match fetch_list::<TaskSchedule>("/api/v1/schedule/list").await {
+                Ok(list) => schedules.set(list),
+                Err(e) => tracing::error!("fetch schedules failed: {}", e),
+            }
+        });
+    };
+
+    // 组件挂载时获取任务、数据源与资源列表
+    use_effect(move || {
+        refresh_tasks();
+        spawn(async move {
+            match fetch_list::<DataSource>("/api/v1/datasource/list").await {
+                Ok(list) => datasources.set(list),
+                Err(e) => tracing::error!("fetch datasources failed: {}", e),
+            }
+            match fetch_list::<Resource>("/api/v1/resource/list").await {
+                Ok(list) => resources.set(list),
+                Err(e) => tracing::error!("fetch resources failed: {}", e),
+            }
+        });
+    });
+
+    let datasource_name = move |id: &str| {
+        datasources().iter().find(|d| d.id == id).map(|d| d.name.clone()).unwrap_or_else(|| "-".to_string())
+    };
+    let resource_name = move |id: &str| {
+        resources().iter().find(|r| r.id == id).map(|r| r.name.clone()).unwrap_or_else(|| "-".to_string())
+    };
+
+    // 已启用调度的下次触发时间
+    let next_fire_at = move |id: &str| {
+        schedules()
+            .iter()
+            .find(|s| s.task_id == id && s.enabled && !s.next_fire_at.is_empty())
+            .map(|s| s.next_fire_at.clone())
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    let handle_save = move |task: CollectionTaskCreateUpdate| {
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+            let path = if task.id.is_empty() { "/api/v1/collection/add" } else { "/api/v1/collection/update" };
+            match client.post(path, Some(req_config), task).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                    Ok(api_response) if api_response.result => {
+                        show_dialog.set(false);
+                        error_msg.set(String::new());
+                        refresh_tasks();
+                    }
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => {
+                    tracing::error!("Save request failed: {}", e);
+                    error_msg.set(e.to_string());
+                }
+            }
+        });
+    };
+
+    let handle_confirm_delete = move |task: CollectionTask| {
+        show_delete.set(false);
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+            match client.delete(&format!("/api/v1/collection/{}", task.id), Some(req_config)).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                    Ok(api_response) if api_response.result => refresh_tasks(),
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => {
+                    tracing::error!("Delete request failed: {}", e);
+                    error_msg.set(e.to_string());
+                }
+            }
+        });
+    };
+
+    // 手动触发一次运行
+    let handle_run = move |task: CollectionTask| {
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+            match client.post(&format!("/api/v1/collection/{}/run", task.id), Some(req_config), ()).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                    Ok(api_response) if api_response.result => {
+                        error_msg.set(String::new());
+                        info_msg.set(format!("任务 {} 已开始运行", task.name));
+                    }
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => {
+                    tracing::error!("Run request failed: {}", e);
+                    error_msg.set(e.to_string());
+                }
+            }
+        });
+    };
+
+    // 停止运行中的实时采集
+    let handle_stop = move |task: CollectionTask| {
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+            match client.post(&format!("/api/v1/collection/{}/stop", task.id), Some(req_config), ()).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                    Ok(api_response) if api_response.result => {
+                        error_msg.set(String::new());
+                        info_msg.set(format!("任务 {} 正在停止", task.name));
+                    }
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => {
+                    tracing::error!("Stop request failed: {}", e);
+                    error_msg.set(e.to_string());
+                }
+            }
+        });
+    };
+
+    rsx! {
+        div { class: "p-6 space-y-6",
+            // 页面标题
+            div { class: "flex justify-between items-center",
+                h1 { class: "text-2xl font text-gray-800", "采集任务" }
+                div { class: "flex-1" }
+                button {
+                    class: "btn btn-info ml-auto",
+                    onclick: move |_| {
+                        modal_mode.set(CollectionModalMode::Add);
+                        show_dialog.set(true);
+                    },
+                    Icon { icon: HiPlus, class: "w-4 h-4 mr-2" }
+                    "新建采集任务"
+                }
+            }
+
+            // 搜索栏
+            div { class: "card bg-base-100 shadow-sm w-full",
+                div { class: "card-body",
+                    div { class: "flex items-end gap-4 justify-end w-full",
+                        div { class: "form-control",
+                            select {
+                                class: "select select-bordered w-full",
+                                value: "{type_filter}",
+                                onchange: move |e| type_filter.set(e.value().to_string()),
+                                option { value: "", disabled: true, selected: type_filter().is_empty(), "采集类型" }
+                                option { value: "", "全部类型" }
+                                option { value: "full", "全量" }
+                                option { value: "incremental", "增量" }
+                                option { value: "stream", "实时" }
+                            }
+                        }
+                        div { class: "form-control",
+                            input {
+                                class: "input input-bordered w-full",
+                                placeholder: "任务名称",
+                                value: "{name_filter}",
+                                oninput: move |e| name_filter.set(e.value().to_string())
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !error_msg().is_empty() {
+                div { class: "alert alert-error",
+                    span { "{error_msg()}" }
+                }
+            }
+            if !info_msg().is_empty() {
+                div { class: "alert alert-success",
+                    span { "{info_msg()}" }
+                }
+            }
+
+            // 任务列表
+            div { class: "card bg-base-100 shadow-sm",
+                div { class: "overflow-x-auto",
+                    table { class: "table",
+                        thead {
+                            tr {
+                                th { "任务名称" }
+                                th { "采集分类" }
+                                th { "采集类型" }
+                                th { "数据源" }
+                                th { "目标资源" }
+                                th { "下次运行" }
+                                th { "更新时间" }
+                                th { "操作" }
+                            }
+                        }
+                        tbody {
+                            for task in filtered_tasks().iter() {
+                                tr { key: "{task.id}",
+                                    td {
+                                        div { class: "font-medium", "{task.name}" }
+                                        div { class: "text-sm text-base-content/60", "{task.description}" }
+                                    }
+                                    td { "{task.category}" }
+                                    td {
+                                        span { class: "badge badge-outline", "{task.collect_type}" }
+                                    }
+                                    td { "{datasource_name(&task.datasource_id)}" }
+                                    td { "{resource_name(&task.resource_id)}" }
+                                    td { "{next_fire_at(&task.id)}" }
+                                    td { "{task.updated_at}" }
+                                    td {
+                                        div { class: "flex gap-2",
+                                            button {
+                                                class: "btn btn-ghost btn-xs text-info",
+                                                onclick: {
+                                                    let task = task.clone();
+                                                    move |_| handle_run(task.clone())
+                                                },
+                                                Icon { icon: HiPlay, class: "w-4 h-4" }
+                                                "运行"
+                                            }
+                                            if task.collect_type == CollectType::Stream {
+                                                button {
+                                                    class: "btn btn-ghost btn-xs text-warning",
+                                                    onclick: {
+                                                        let task = task.clone();
+                                                        move |_| handle_stop(task.clone())
+                                                    },
+                                                    Icon { icon: HiStop, class: "w-4 h-4" }
+                                                    "停止"
+                                                }
+                                            } else {
+                                                button {
+                                                    class: "btn btn-ghost btn-xs",
+                                                    onclick: {
+                                                        let task = task.clone();
+                                                        move |_| {
+                                                            selected_task.set(Some(task.clone()));
+                                                            show_schedule.set(true);
+                                                        }
+                                                    },
+                                                    Icon { icon: HiCalendar, class: "w-4 h-4" }
+                                                    "调度"
+                                                }
+                                            }
+                                            button {
+                                                class: "btn btn-ghost btn-xs",
+                                                onclick: {
+                                                    let task = task.clone();
+                                                    move |_| {
+                                                        selected_task.set(Some(task.clone()));
+                                                        show_runs.set(true);
+                                                    }
+                                                },
+                                                Icon { icon: HiClock, class: "w-4 h-4" }
+                                                "运行记录"
+                                            }
+                                            button {
+                                                class: "btn btn-ghost btn-xs",
+                                                onclick: {
+                                                    let task = task.clone();
+                                                    move |_| {
+                                                        modal_mode.set(CollectionModalMode::Edit(task.clone()));
+                                                        show_dialog.set(true);
+                                                    }
+                                                },
+                                                Icon { icon: HiPencil, class: "w-4 h-4" }
+                                                "编辑"
+                                            }
+                                            button {
+                                                class: "btn btn-ghost btn-xs text-error",
+                                                onclick: {
+                                                    let task = task.clone();
+                                                    move |_| {
+                                                        selected_task.set(Some(task.clone()));
+                                                        show_delete.set(true);
+                                                    }
+                                                },
+                                                Icon { icon: HiTrash, class: "w-4 h-4" }
+                                                "删除"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if filtered_tasks().is_empty() {
+                        div { class: "text-center text-base-content/60 py-12", "暂无采集任务" }
+                    }
+                }
+            }
+        }
+
+        if show_dialog() {
+            CollectionDialog {
+                mode: modal_mode(),
+                datasources: datasources(),
+                resources: resources(),
+                on_close: move |_| show_dialog.set(false),
+                on_save: handle_save,
+            }
+        }
+
+        if show_runs() {
+            if let Some(task) = selected_task() {
+                CollectionRunDialog {
+                    task: task,
+                    on_close: move |_| {
+                        show_runs.set(false);
+                        selected_task.set(None);
+                    },
+                }
+            }
+        }
+
+        if show_schedule() {
+            if let Some(task) = selected_task() {
+                CollectionScheduleDialog {
+                    task: task,
+                    on_close: move |_| {
+                        show_schedule.set(false);
+                        selected_task.set(None);
+                    },
+                    on_saved: move |_| {
+                        show_schedule.set(false);
+                        selected_task.set(None);
+                        refresh_tasks();
+                    },
+                }
+            }
+        }
+
+        if show_delete() {
+            if let Some(task) = selected_task() {
+                CollectionDeleteDialog {
+                    task: task,
+                    on_confirm: handle_confirm_delete,
+                    on_cancel: move |_| {
+                        show_delete.set(false);
+                        selected_task.set(None);
+                    },
+                }
+            }
+        }
+    }
+}
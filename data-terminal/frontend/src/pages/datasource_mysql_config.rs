@@ -7,6 +7,7 @@ use crate::utils::{
 use crate::routes::Route;
 use crate::models::datasource::*;
 use crate::models::protocol::ApiResponse;
+use crate::components::datasource_schema_tree::DatasourceSchemaTree;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MysqlConfig {
@@ -63,6 +64,7 @@ fn validate_config(cfg: &MysqlConfig) -> Vec<String> {
 pub fn DatasourceMysqlEdit(id: String) -> Element {
     let mut config = use_signal(MysqlConfig::default);
     let mut validation_errors = use_signal(|| Vec::<String>::new());
+    let mut ping_message = use_signal(|| None as Option<String>);
     let mut is_loading = use_signal(|| true);
     let navigator = use_navigator();
 
@@ -189,13 +191,16 @@ pub fn DatasourceMysqlEdit(id: String) -> Element {
                                 .await;
             match response {
                 Ok(result) => {
-                    match serde_json::from_str::<ApiResponse<String>>(&result) {
+                    match serde_json::from_str::<ApiResponse<Option<PingDiagnostic>>>(&result) {
                         Ok(result) => {
+                            let diagnostic = result.data.unwrap_or_default();
                             if result.result {
                                 validation_errors.set(Vec::new());
+                                ping_message.set(Some(diagnostic.success_text()));
                             } else {
+                                ping_message.set(None);
                                 let mut errs = errors.clone();
-                                errs.push(result.msg);
+                                errs.push(diagnostic.failure_text(&result.msg));
                                 validation_errors.set(errs);
                             }
                         },
@@ -413,6 +418,14 @@ pub fn DatasourceMysqlEdit(id: String) -> Element {
                         }
                     }
                 }
+
+                // Section 3: 库表结构（基于已保存的连接配置）
+                div { class: "card bg-base-100 shadow-sm",
+                    div { class: "card-body",
+                        h4 { class: "text-lg font-semibold mb-4", "库表结构" }
+                        DatasourceSchemaTree { datasource_id: id.clone() }
+                    }
+                }
             }
 
             // Error Display and Action Buttons (fixed position layout)
@@ -428,6 +441,11 @@ pub fn DatasourceMysqlEdit(id: String) -> Element {
                             }
                         }
                     }
+                    if validation_errors().is_empty() {
+                        if let Some(message) = ping_message() {
+                            p { class: "text-success", "{message}" }
+                        }
+                    }
                 }
 
                 // Action Buttons (right side)
@@ -455,6 +473,7 @@ pub fn DatasourceMysqlEdit(id: String) -> Element {
 pub fn DatasourceMysqlAdd() -> Element {
     let mut config = use_signal(MysqlConfig::default);
     let mut validation_errors = use_signal(|| Vec::<String>::new());
+    let mut ping_message = use_signal(|| None as Option<String>);
     let navigator = use_navigator();
 
     let handle_save = move |_| {
@@ -537,13 +556,16 @@ pub fn DatasourceMysqlAdd() -> Element {
                                 .await;
             match response {
                 Ok(result) => {
-                    match serde_json::from_str::<ApiResponse<String>>(&result) {
+                    match serde_json::from_str::<ApiResponse<Option<PingDiagnostic>>>(&result) {
                         Ok(result) => {
+                            let diagnostic = result.data.unwrap_or_default();
                             if result.result {
                                 validation_errors.set(Vec::new());
+                                ping_message.set(Some(diagnostic.success_text()));
                             } else {
+                                ping_message.set(None);
                                 let mut errs = errors.clone();
-                                errs.push(result.msg);
+                                errs.push(diagnostic.failure_text(&result.msg));
                                 validation_errors.set(errs);
                             }
                         },
@@ -768,6 +790,11 @@ pub fn DatasourceMysqlAdd() -> Element {
                             }
                         }
                     }
+                    if validation_errors().is_empty() {
+                        if let Some(message) = ping_message() {
+                            p { class: "text-success", "{message}" }
+                        }
+                    }
                 }
 
                 // Action Buttons (right side)
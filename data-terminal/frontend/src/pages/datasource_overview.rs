@@ -181,6 +181,43 @@ pub fn DatasourceOverViewPage() -> Element {
         });
     };
 
+    // 重新检测数据源连接状态
+    let handle_recheck = move |id: String| {
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+
+            let response = client.post(&format!("/api/v1/datasource/recheck/{}", id), Some(req_config), ()).await;
+            match response {
+                Ok(response_text) => {
+                    match serde_json::from_str::<ApiResponse<Option<DataSource>>>(&response_text) {
+                        Ok(api_response) => {
+                            if let (true, Some(checked)) = (api_response.result, api_response.data) {
+                                let mut list = datasources();
+                                if let Some(item) = list.iter_mut().find(|d| d.id == checked.id) {
+                                    *item = checked;
+                                }
+                                datasources.set(list);
+                            } else {
+                                error_msg.set(api_response.msg);
+                            }
+                        }
+                        Err(e) => {
+                            error_msg.set(e.to_string());
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Recheck request failed: {}", e);
+                    error_msg.set(e.to_string());
+                }
+            }
+        });
+    };
+
     // 取消删除
     let handle_cancel_delete = move |_| {
         show_delete.set(false);
@@ -311,6 +348,7 @@ pub fn DatasourceOverViewPage() -> Element {
                                 datasource: datasource.clone(),
                                 on_edit: handle_edit,
                                 on_delete: handle_delete,
+                                on_recheck: handle_recheck,
                             }
                         }
                     }
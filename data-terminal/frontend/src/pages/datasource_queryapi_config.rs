@@ -135,6 +135,7 @@ fn validate_config(cfg: &QueryApiConfig) -> Vec<String> {
 pub fn DatasourceQueryApiEdit(id: String) -> Element {
     let mut config = use_signal(QueryApiConfig::default);
     let mut validation_errors = use_signal(|| Vec::<String>::new());
+    let mut ping_message = use_signal(|| None as Option<String>);
     let mut is_loading = use_signal(|| true);
 
     // Signals for dynamic key-value pairs
@@ -265,13 +266,16 @@ pub fn DatasourceQueryApiEdit(id: String) -> Element {
                 .await;
             match response {
                 Ok(result) => {
-                    match serde_json::from_str::<ApiResponse<String>>(&result) {
+                    match serde_json::from_str::<ApiResponse<Option<PingDiagnostic>>>(&result) {
                         Ok(result) => {
+                            let diagnostic = result.data.unwrap_or_default();
                             if result.result {
                                 validation_errors.set(Vec::new());
+                                ping_message.set(Some(diagnostic.success_text()));
                             } else {
+                                ping_message.set(None);
                                 let mut errs = errors.clone();
-                                errs.push(result.msg);
+                                errs.push(diagnostic.failure_text(&result.msg));
                                 validation_errors.set(errs);
                             }
                         }
@@ -775,6 +779,11 @@ pub fn DatasourceQueryApiEdit(id: String) -> Element {
                             }
                         }
                     }
+                    if validation_errors().is_empty() {
+                        if let Some(message) = ping_message() {
+                            p { class: "text-success", "{message}" }
+                        }
+                    }
                 }
                 div {
                     class: "flex gap-4 flex-shrink-0",
@@ -800,6 +809,7 @@ pub fn DatasourceQueryApiEdit(id: String) -> Element {
 pub fn DatasourceQueryApiAdd() -> Element {
     let mut config = use_signal(QueryApiConfig::default);
     let mut validation_errors = use_signal(|| Vec::<String>::new());
+    let mut ping_message = use_signal(|| None as Option<String>);
 
     // Signals for dynamic key-value pairs
     let mut new_path_param_key = use_signal(String::new);
@@ -887,13 +897,16 @@ pub fn DatasourceQueryApiAdd() -> Element {
                 .await;
             match response {
                 Ok(result) => {
-                    match serde_json::from_str::<ApiResponse<String>>(&result) {
+                    match serde_json::from_str::<ApiResponse<Option<PingDiagnostic>>>(&result) {
                         Ok(result) => {
+                            let diagnostic = result.data.unwrap_or_default();
                             if result.result {
                                 validation_errors.set(Vec::new());
+                                ping_message.set(Some(diagnostic.success_text()));
                             } else {
+                                ping_message.set(None);
                                 let mut errs = errors.clone();
-                                errs.push(result.msg);
+                                errs.push(diagnostic.failure_text(&result.msg));
                                 validation_errors.set(errs);
                             }
                         }
@@ -1389,6 +1402,11 @@ pub fn DatasourceQueryApiAdd() -> Element {
                             }
                         }
                     }
+                    if validation_errors().is_empty() {
+                        if let Some(message) = ping_message() {
+                            p { class: "text-success", "{message}" }
+                        }
+                    }
                 }
                 div {
                     class: "flex gap-4 flex-shrink-0",
@@ -154,6 +154,7 @@ fn get_current_domain() -> String {
 pub fn DatasourceSubscribeApiEdit(id: String) -> Element {
     let mut config = use_signal(SubscribeApiConfig::default);
     let mut validation_errors = use_signal(|| Vec::<String>::new());
+    let mut ping_message = use_signal(|| None as Option<String>);
     let mut is_loading = use_signal(|| true);
 
     // Signals for dynamic key-value pairs
@@ -285,13 +286,16 @@ pub fn DatasourceSubscribeApiEdit(id: String) -> Element {
                 .await;
             match response {
                 Ok(result) => {
-                    match serde_json::from_str::<ApiResponse<String>>(&result) {
+                    match serde_json::from_str::<ApiResponse<Option<PingDiagnostic>>>(&result) {
                         Ok(result) => {
+                            let diagnostic = result.data.unwrap_or_default();
                             if result.result {
                                 validation_errors.set(Vec::new());
+                                ping_message.set(Some(diagnostic.success_text()));
                             } else {
+                                ping_message.set(None);
                                 let mut errs = errors.clone();
-                                errs.push(result.msg);
+                                errs.push(diagnostic.failure_text(&result.msg));
                                 validation_errors.set(errs);
                             }
                         }
@@ -834,6 +838,11 @@ pub fn DatasourceSubscribeApiEdit(id: String) -> Element {
                             }
                         }
                     }
+                    if validation_errors().is_empty() {
+                        if let Some(message) = ping_message() {
+                            p { class: "text-success", "{message}" }
+                        }
+                    }
                 }
                 div {
                     class: "flex gap-4 flex-shrink-0",
@@ -859,6 +868,7 @@ pub fn DatasourceSubscribeApiEdit(id: String) -> Element {
 pub fn DatasourceSubscribeApiAdd() -> Element {
     let mut config = use_signal(SubscribeApiConfig::default);
     let mut validation_errors = use_signal(|| Vec::<String>::new());
+    let mut ping_message = use_signal(|| None as Option<String>);
 
     // Signals for dynamic key-value pairs
     let mut new_path_param_key = use_signal(String::new);
@@ -947,13 +957,16 @@ pub fn DatasourceSubscribeApiAdd() -> Element {
                 .await;
             match response {
                 Ok(result) => {
-                    match serde_json::from_str::<ApiResponse<String>>(&result) {
+                    match serde_json::from_str::<ApiResponse<Option<PingDiagnostic>>>(&result) {
                         Ok(result) => {
+                            let diagnostic = result.data.unwrap_or_default();
                             if result.result {
                                 validation_errors.set(Vec::new());
+                                ping_message.set(Some(diagnostic.success_text()));
                             } else {
+                                ping_message.set(None);
                                 let mut errs = errors.clone();
-                                errs.push(result.msg);
+                                errs.push(diagnostic.failure_text(&result.msg));
                                 validation_errors.set(errs);
                             }
                         }
@@ -1458,6 +1471,11 @@ pub fn DatasourceSubscribeApiAdd() -> Element {
                             }
                         }
                     }
+                    if validation_errors().is_empty() {
+                        if let Some(message) = ping_message() {
+                            p { class: "text-success", "{message}" }
+                        }
+                    }
                 }
                 div {
                     class: "flex gap-4 flex-shrink-0",
@@ -0,0 +1,549 @@
+use dioxus::prelude::*;
+use crate::models::catalog::{CatalogSnapshot, CatalogSnapshotDetail, CatalogTableDetail};
+use crate::models::lineage::{canvas_size, layout, lineage_path, table_tasks, LineageGraph, LineageNode, COLUMN_ROW, TABLE_HEADER, TABLE_WIDTH};
+use crate::models::pipeline::task_type_text;
+use crate::models::protocol::ApiResponse;
+use crate::routes::Route;
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+use dioxus_free_icons::{icons::hi_outline_icons::*, Icon};
+use serde::de::DeserializeOwned;
+
+// 画布最小尺寸
+const CANVAS_MIN_WIDTH: f64 = 960.0;
+const CANVAS_MIN_HEIGHT: f64 = 560.0;
+
+async fn get_api<T: DeserializeOwned>(path: &str, builder: RequestBuilder) -> Result<T, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = builder
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .build();
+    let response_text = client.get(path, Some(req_config)).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<T>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+fn encode(value: &str) -> String {
+    String::from(js_sys::encode_uri_component(value))
+}
+
+// 从一张表或一列出发追溯血缘，direction 为 upstream、downstream 或 both
+async fn trace(node: LineageNode, direction: &str, depth: u32) -> Result<LineageGraph, String> {
+    let builder = || {
+        RequestBuilder::new()
+            .query_param("source_type", node.source_type)
+            .query_param("source_id", &node.source_id)
+            .query_param("schema_name", encode(&node.schema_name))
+            .query_param("table_name", encode(&node.table_name))
+            .query_param("column_name", encode(&node.column_name))
+            .query_param("depth", depth)
+    };
+    let mut graph = None as Option<LineageGraph>;
+    if direction != "downstream" {
+        graph = Some(get_api::<LineageGraph>("/api/v1/lineage/upstream", builder()).await?);
+    }
+    if direction != "upstream" {
+        let downstream = get_api::<LineageGraph>("/api/v1/lineage/downstream", builder()).await?;
+        match graph.as_mut() {
+            Some(graph) => graph.merge(downstream),
+            None => graph = Some(downstream),
+        }
+    }
+    Ok(graph.unwrap_or_default())
+}
+
+fn source_key(snapshot: &CatalogSnapshot) -> String {
+    format!("{}/{}", snapshot.source_type, snapshot.source_id)
+}
+
+#[component]
+pub fn LineageGraphPage() -> Element {
+    let navigator = use_navigator();
+    let mut sources = use_signal(|| Vec::<CatalogSnapshot>::new());
+    let mut selected_source = use_signal(String::new);
+    let mut tables = use_signal(|| Vec::<CatalogTableDetail>::new());
+    let mut selected_table = use_signal(|| None as Option<usize>);
+    let mut selected_column = use_signal(String::new);
+    let mut direction = use_signal(|| "both".to_string());
+    let mut depth = use_signal(|| 3u32);
+
+    let mut graph = use_signal(|| None as Option<LineageGraph>);
+    let mut show_collection = use_signal(|| true);
+    let mut show_transform = use_signal(|| true);
+    let mut focused_table = use_signal(|| None as Option<String>);
+    let mut focused_edge = use_signal(|| None as Option<String>);
+    let mut loading = use_signal(|| false);
+    let mut error_msg = use_signal(String::new);
+
+    use_effect(move || {
+        spawn(async move {
+            match get_api::<Vec<CatalogSnapshot>>("/api/v1/catalog/sources", RequestBuilder::new()).await {
+                Ok(list) => sources.set(list),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    });
+
+    let mut choose_source = move |key: String| {
+        selected_source.set(key.clone());
+        selected_table.set(None);
+        selected_column.set(String::new());
+        tables.set(Vec::new());
+        let Some(snapshot) = sources().into_iter().find(|s| source_key(s) == key) else {
+            return;
+        };
+        spawn(async move {
+            let path = format!("/api/v1/catalog/snapshot/{}", snapshot.id);
+            match get_api::<CatalogSnapshotDetail>(&path, RequestBuilder::new()).await {
+                Ok(detail) => tables.set(detail.tables),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    // 以指定节点为起点重新追溯
+    let mut start = move |root: LineageNode| {
+        loading.set(true);
+        focused_table.set(None);
+        focused_edge.set(None);
+        let direction = direction();
+        let depth = depth();
+        spawn(async move {
+            match trace(root, &direction, depth).await {
+                Ok(result) => {
+                    graph.set(Some(result));
+                    error_msg.set(String::new());
+                }
+                Err(e) => error_msg.set(e),
+            }
+            loading.set(false);
+        });
+    };
+
+    let handle_trace = move |_| {
+        let Some(snapshot) = sources().into_iter().find(|s| source_key(s) == selected_source()) else {
+            error_msg.set("请选择数据源或资源".to_string());
+            return;
+        };
+        let Some(table) = selected_table().and_then(|i| tables().get(i).cloned()) else {
+            error_msg.set("请选择表".to_string());
+            return;
+        };
+        start(LineageNode {
+            source_type: snapshot.source_type,
+            source_id: snapshot.source_id,
+            source_name: snapshot.source_name,
+            schema_name: table.schema_name,
+            table_name: table.table_name,
+            column_name: selected_column(),
+        });
+    };
+
+    // 点击表两侧的按钮时向上游或下游展开一层
+    let mut expand = move |node: LineageNode, direction: &'static str| {
+        loading.set(true);
+        spawn(async move {
+            match trace(node, direction, 1).await {
+                Ok(result) => {
+                    graph.with_mut(|g| {
+                        if let Some(g) = g.as_mut() {
+                            g.merge(result);
+                        }
+                    });
+                    error_msg.set(String::new());
+                }
+                Err(e) => error_msg.set(e),
+            }
+            loading.set(false);
+        });
+    };
+
+    let open_task = move |task_type: String, task_id: String| {
+        if task_type == "collection" {
+            navigator.push(Route::CollectionTaskPage { task_id });
+        } else if task_type == "transform" {
+            navigator.push(Route::TransformPage { task_id });
+        }
+    };
+
+    // 按任务类型过滤后的边与布局
+    let visible_edges = use_memo(move || {
+        graph()
+            .map(|g| g.edges)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|e| match e.task_type.as_str() {
+                "collection" => show_collection(),
+                "transform" => show_transform(),
+                _ => true,
+            })
+            .collect::<Vec<_>>()
+    });
+    let boxes = use_memo(move || match graph() {
+        Some(g) => layout(&g.root, &visible_edges()),
+        None => Vec::new(),
+    });
+
+    let (width, height) = canvas_size(&boxes());
+    let canvas_width = width.max(CANVAS_MIN_WIDTH);
+    let canvas_height = height.max(CANVAS_MIN_HEIGHT);
+    let root_key = graph().map(|g| g.root.table_key()).unwrap_or_default();
+    let table_columns = selected_table().and_then(|i| tables().get(i).cloned()).map(|t| t.columns).unwrap_or_default();
+
+    rsx! {
+        div { class: "p-6 space-y-4",
+            // 页面标题
+            div { class: "flex justify-between items-center",
+                h1 { class: "text-2xl font text-gray-800", "数据血缘" }
+                if loading() {
+                    span { class: "loading loading-spinner loading-sm" }
+                }
+            }
+
+            if !error_msg().is_empty() {
+                div { class: "alert alert-error",
+                    span { class: "whitespace-pre-wrap break-all", "{error_msg()}" }
+                }
+            }
+
+            // 起点选择
+            div { class: "card bg-base-100 shadow-sm",
+                div { class: "card-body p-4",
+                    div { class: "flex flex-wrap items-end gap-3",
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "数据源 / 资源" } }
+                            select {
+                                class: "select select-bordered select-sm w-56",
+                                value: "{selected_source}",
+                                onchange: move |e| choose_source(e.value()),
+                                option { value: "", disabled: true, "请选择" }
+                                for source in sources().iter() {
+                                    option { key: "{source.id}", value: "{source_key(source)}", "{source.source_name}" }
+                                }
+                            }
+                        }
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "表" } }
+                            select {
+                                class: "select select-bordered select-sm w-64",
+                                value: selected_table().map(|i| i.to_string()).unwrap_or_default(),
+                                onchange: move |e| {
+                                    selected_table.set(e.value().parse::<usize>().ok());
+                                    selected_column.set(String::new());
+                                },
+                                option { value: "", disabled: true, "请选择" }
+                                for (index, table) in tables().iter().enumerate() {
+                                    option { key: "{index}", value: "{index}", "{table.schema_name}.{table.table_name}" }
+                                }
+                            }
+                        }
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "列" } }
+                            select {
+                                class: "select select-bordered select-sm w-48",
+                                value: "{selected_column}",
+                                onchange: move |e| selected_column.set(e.value()),
+                                option { value: "", "整张表" }
+                                for column in table_columns.iter() {
+                                    option { key: "{column.column_name}", value: "{column.column_name}", "{column.column_name}" }
+                                }
+                            }
+                        }
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "方向" } }
+                            select {
+                                class: "select select-bordered select-sm",
+                                value: "{direction}",
+                                onchange: move |e| direction.set(e.value()),
+                                option { value: "both", "上下游" }
+                                option { value: "upstream", "上游" }
+                                option { value: "downstream", "下游" }
+                            }
+                        }
+                        div { class: "form-control",
+                            label { class: "label", span { class: "label-text", "层数" } }
+                            input {
+                                class: "input input-bordered input-sm w-20",
+                                r#type: "number",
+                                min: "1",
+                                max: "10",
+                                value: "{depth}",
+                                oninput: move |e| {
+                                    if let Ok(value) = e.value().parse::<u32>() {
+                                        depth.set(value.clamp(1, 10));
+                                    }
+                                },
+                            }
+                        }
+                        button {
+                            class: "btn btn-primary btn-sm",
+                            disabled: loading(),
+                            onclick: handle_trace,
+                            Icon { icon: HiSearch, class: "w-4 h-4" }
+                            "查看血缘"
+                        }
+                        div { class: "flex items-center gap-4 ml-auto",
+                            label { class: "label cursor-pointer gap-2",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "checkbox checkbox-sm checkbox-info",
+                                    checked: show_collection(),
+                                    onchange: move |e| show_collection.set(e.checked()),
+                                }
+                                span { class: "label-text", "采集任务" }
+                            }
+                            label { class: "label cursor-pointer gap-2",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "checkbox checkbox-sm checkbox-secondary",
+                                    checked: show_transform(),
+                                    onchange: move |e| show_transform.set(e.checked()),
+                                }
+                                span { class: "label-text", "宽表任务" }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "flex gap-4",
+                // 画布
+                div { class: "card bg-base-100 shadow-sm flex-1 min-w-0 overflow-auto max-h-[44rem]",
+                    div {
+                        class: "relative select-none",
+                        style: "width: {canvas_width}px; height: {canvas_height}px; background-image: radial-gradient(circle, rgba(0,0,0,0.08) 1px, transparent 1px); background-size: 20px 20px;",
+                        onclick: move |_| {
+                            focused_table.set(None);
+                            focused_edge.set(None);
+                        },
+
+                        svg {
+                            class: "absolute inset-0 pointer-events-none",
+                            width: "{canvas_width}",
+                            height: "{canvas_height}",
+                            defs {
+                                marker {
+                                    id: "lineage-arrow",
+                                    "viewBox": "0 0 10 10",
+                                    "refX": "10",
+                                    "refY": "5",
+                                    "markerWidth": "8",
+                                    "markerHeight": "8",
+                                    "orient": "auto-start-reverse",
+                                    path { d: "M 0 0 L 10 5 L 0 10 z", fill: "#9ca3af" }
+                                }
+                            }
+                            for edge in visible_edges().iter() {
+                                {
+                                    let from = boxes().into_iter().find(|b| b.key == edge.source_node().table_key());
+                                    let to = boxes().into_iter().find(|b| b.key == edge.target_node().table_key());
+                                    let selected = focused_edge() == Some(edge.id.clone());
+                                    let edge_id = edge.id.clone();
+                                    let color = match (selected, edge.task_type.as_str()) {
+                                        (true, _) => "#570df8",
+                                        (false, "transform") => "#c084fc",
+                                        (false, _) => "#60a5fa",
+                                    };
+                                    rsx! {
+                                        if let (Some(from), Some(to)) = (from, to) {
+                                            path {
+                                                key: "{edge.id}",
+                                                d: "{lineage_path(&from, &edge.source_column, &to, &edge.target_column)}",
+                                                fill: "none",
+                                                stroke: color,
+                                                "stroke-width": if selected { "3" } else { "1.5" },
+                                                "marker-end": "url(#lineage-arrow)",
+                                                "pointer-events": "stroke",
+                                                class: "cursor-pointer",
+                                                onclick: move |e| {
+                                                    e.stop_propagation();
+                                                    focused_table.set(None);
+                                                    focused_edge.set(Some(edge_id.clone()));
+                                                },
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        for table in boxes().into_iter() {
+                            {
+                                let key = table.key.clone();
+                                let upstream = table.node.clone();
+                                let downstream = table.node.clone();
+                                let border = if focused_table() == Some(table.key.clone()) {
+                                    "border-primary ring-2 ring-primary/30"
+                                } else if table.key == root_key {
+                                    "border-primary"
+                                } else {
+                                    "border-base-300"
+                                };
+                                let source_name = graph().map(|g| g.source_name(&table.node)).unwrap_or_default();
+                                let box_height = table.height();
+                                rsx! {
+                                    div {
+                                        key: "{table.key}",
+                                        class: "absolute rounded-lg border-2 bg-base-100 shadow-sm cursor-pointer {border}",
+                                        style: "left: {table.x}px; top: {table.y}px; width: {TABLE_WIDTH}px; height: {box_height}px;",
+                                        onclick: move |e| {
+                                            e.stop_propagation();
+                                            focused_edge.set(None);
+                                            focused_table.set(Some(key.clone()));
+                                        },
+                                        div {
+                                            class: "px-3 flex flex-col justify-center border-b border-base-200",
+                                            style: "height: {TABLE_HEADER}px;",
+                                            div { class: "text-sm font-medium truncate", title: "{table.node.table_label()}", "{table.node.table_label()}" }
+                                            div { class: "text-xs text-base-content/60 truncate", "{source_name}" }
+                                        }
+                                        for column in table.columns.iter() {
+                                            div {
+                                                key: "{column}",
+                                                class: "px-3 text-xs font-mono truncate flex items-center",
+                                                style: "height: {COLUMN_ROW}px;",
+                                                "{column}"
+                                            }
+                                        }
+                                        // 两侧按钮向上游、下游展开一层
+                                        button {
+                                            class: "absolute -left-3 top-2 btn btn-circle btn-xs btn-outline bg-base-100",
+                                            title: "展开上游",
+                                            disabled: loading(),
+                                            onclick: move |e| {
+                                                e.stop_propagation();
+                                                expand(upstream.clone(), "upstream");
+                                            },
+                                            "+"
+                                        }
+                                        button {
+                                            class: "absolute -right-3 top-2 btn btn-circle btn-xs btn-outline bg-base-100",
+                                            title: "展开下游",
+                                            disabled: loading(),
+                                            onclick: move |e| {
+                                                e.stop_propagation();
+                                                expand(downstream.clone(), "downstream");
+                                            },
+                                            "+"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        if graph().is_none() {
+                            div { class: "absolute inset-0 flex items-center justify-center text-base-content/40 pointer-events-none",
+                                "选择一张表或一列后查看血缘，点击表两侧的 + 继续展开"
+                            }
+                        } else if boxes().len() <= 1 {
+                            div { class: "absolute inset-x-0 bottom-6 text-center text-base-content/40 pointer-events-none",
+                                "没有找到血缘关系"
+                            }
+                        }
+                    }
+                }
+
+                // 详情面板
+                div { class: "card bg-base-100 shadow-sm w-80 shrink-0",
+                    div { class: "card-body p-4 gap-3",
+                        if let Some(edge) = focused_edge().and_then(|id| visible_edges().into_iter().find(|e| e.id == id)) {
+                            h2 { class: "font-medium", "血缘关系" }
+                            div { class: "text-sm space-y-1",
+                                div { class: "font-mono break-all",
+                                    "{edge.source_node().table_label()}"
+                                    if !edge.source_column.is_empty() { ".{edge.source_column}" }
+                                }
+                                div { class: "text-base-content/60", "↓" }
+                                div { class: "font-mono break-all",
+                                    "{edge.target_node().table_label()}"
+                                    if !edge.target_column.is_empty() { ".{edge.target_column}" }
+                                }
+                            }
+                            if !edge.expression.is_empty() {
+                                div { class: "text-xs text-base-content/60", "表达式" }
+                                pre { class: "text-xs bg-base-200 rounded p-2 whitespace-pre-wrap break-all", "{edge.expression}" }
+                            }
+                            div { class: "divider my-0" }
+                            div { class: "flex items-center justify-between gap-2",
+                                div { class: "min-w-0",
+                                    div { class: "text-sm font-medium truncate", "{edge.task_name}" }
+                                    div { class: "text-xs text-base-content/60", "{task_type_text(&edge.task_type)}" }
+                                }
+                                button {
+                                    class: "btn btn-ghost btn-xs",
+                                    onclick: move |_| open_task(edge.task_type.clone(), edge.task_id.clone()),
+                                    Icon { icon: HiExternalLink, class: "w-4 h-4" }
+                                    "打开任务"
+                                }
+                            }
+                        } else if let Some(table) = focused_table().and_then(|key| boxes().into_iter().find(|b| b.key == key)) {
+                            {
+                                let root = table.node.clone();
+                                let upstream = table.node.clone();
+                                let downstream = table.node.clone();
+                                let source_name = graph().map(|g| g.source_name(&table.node)).unwrap_or_default();
+                                rsx! {
+                                    h2 { class: "font-medium break-all", "{table.node.table_label()}" }
+                                    div { class: "text-xs text-base-content/60", "{source_name}" }
+                                    div { class: "flex flex-wrap gap-2",
+                                        button {
+                                            class: "btn btn-outline btn-xs",
+                                            disabled: loading(),
+                                            onclick: move |_| expand(upstream.clone(), "upstream"),
+                                            "展开上游"
+                                        }
+                                        button {
+                                            class: "btn btn-outline btn-xs",
+                                            disabled: loading(),
+                                            onclick: move |_| expand(downstream.clone(), "downstream"),
+                                            "展开下游"
+                                        }
+                                        button {
+                                            class: "btn btn-outline btn-xs",
+                                            disabled: loading(),
+                                            onclick: move |_| start(root.clone()),
+                                            "设为起点"
+                                        }
+                                    }
+                                    div { class: "divider my-0" }
+                                    div { class: "text-xs text-base-content/60", "相关任务" }
+                                    for (task_type, task_id, task_name) in table_tasks(&table.key, &visible_edges()) {
+                                        div { key: "{task_id}", class: "flex items-center justify-between gap-2",
+                                            div { class: "min-w-0",
+                                                div { class: "text-sm truncate", "{task_name}" }
+                                                div { class: "text-xs text-base-content/60", "{task_type_text(&task_type)}" }
+                                            }
+                                            button {
+                                                class: "btn btn-ghost btn-xs",
+                                                onclick: move |_| open_task(task_type.clone(), task_id.clone()),
+                                                Icon { icon: HiExternalLink, class: "w-4 h-4" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        } else {
+                            h2 { class: "font-medium", "图例" }
+                            div { class: "flex items-center gap-2 text-sm",
+                                span { class: "inline-block w-6 h-0.5 bg-blue-400" }
+                                "采集任务（数据源 → 资源）"
+                            }
+                            div { class: "flex items-center gap-2 text-sm",
+                                span { class: "inline-block w-6 h-0.5 bg-purple-400" }
+                                "宽表任务（SQL 加工）"
+                            }
+                            div { class: "text-xs text-base-content/60",
+                                "上游在左，下游在右。点击表查看相关任务，点击连线查看列的映射与表达式。"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use dioxus::prelude::*;
 use crate::routes::Route;
-use crate::utils::{cookie, request::{create_client, HttpRequest, RequestBuilder}, error::RequestError};
+use crate::utils::{request::{create_client, HttpRequest, RequestBuilder}, error::RequestError};
 use gloo::timers::future::sleep;
 
 #[component]
@@ -60,13 +60,8 @@ pub fn Login() -> Element {
                                             Ok(response_text) => {
                                                 let json: serde_json::Value = serde_json::from_str(&response_text).unwrap_or_default();
                                                 if json.get("result").and_then(|v| v.as_bool()).unwrap_or(false) {
-                                                    // 检查cookie是否设置成功
-                                                    let has_token = cookie::has_cookie("token");
-                                                    if has_token {
-                                                        navigator.replace(Route::Home {});
-                                                    } else {
-                                                        error_msg_signal.set("登录成功但Cookie设置失败".to_string());
-                                                    }
+                                                    // token 保存在 HttpOnly cookie 中，脚本无法读取，登录成功即跳转
+                                                    navigator.replace(Route::Home {});
                                                 } else {
                                                     let msg = json.get("msg").and_then(|v| v.as_str()).unwrap_or("Login failed");
                                                     error_msg_signal.set(msg.to_string());
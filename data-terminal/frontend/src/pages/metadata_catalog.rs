@@ -0,0 +1,335 @@
+use dioxus::prelude::*;
+use crate::models::catalog::{CatalogDrift, CatalogSearchHit, CatalogSnapshot, CatalogSnapshotRequest, CatalogSourceType, SnapshotResult};
+use crate::models::protocol::ApiResponse;
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+use dioxus_free_icons::{icons::hi_outline_icons::*, Icon};
+use serde::{de::DeserializeOwned, Serialize};
+
+async fn get_api<T: DeserializeOwned>(path: &str, builder: RequestBuilder) -> Result<T, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = builder
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .build();
+    let response_text = client.get(path, Some(req_config)).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<T>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+async fn post_api<B, T>(path: &str, body: B) -> Result<T, String>
+where
+    B: Serialize + Send + Sync,
+    T: DeserializeOwned,
+{
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .build();
+    let response_text = client.post(path, Some(req_config), body).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<T>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+fn source_label(source_type: CatalogSourceType) -> &'static str {
+    match source_type {
+        CatalogSourceType::Datasource => "数据源",
+        CatalogSourceType::Resource => "资源",
+    }
+}
+
+#[component]
+pub fn MetadataCatalogPage() -> Element {
+    let mut sources = use_signal(|| Vec::<CatalogSnapshot>::new());
+    let mut selected = use_signal(|| None as Option<CatalogSnapshot>);
+    let mut drifts = use_signal(|| Vec::<CatalogDrift>::new());
+    let mut keyword = use_signal(String::new);
+    let mut hits = use_signal(|| Vec::<CatalogSearchHit>::new());
+    // 正在快照的来源ID，全部快照时为 "*"
+    let mut snapshotting = use_signal(|| None as Option<String>);
+    let mut message = use_signal(String::new);
+    let mut error_msg = use_signal(String::new);
+
+    let refresh_sources = move || {
+        spawn(async move {
+            match get_api::<Vec<CatalogSnapshot>>("/api/v1/catalog/sources", RequestBuilder::new()).await {
+                Ok(list) => sources.set(list),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    let mut load_drifts = move |snapshot: CatalogSnapshot| {
+        let builder = RequestBuilder::new()
+            .query_param("source_type", snapshot.source_type)
+            .query_param("source_id", &snapshot.source_id)
+            .query_param("page", 1)
+            .query_param("page_size", 200);
+        selected.set(Some(snapshot));
+        spawn(async move {
+            match get_api::<Vec<CatalogDrift>>("/api/v1/catalog/drift", builder).await {
+                Ok(list) => drifts.set(list),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    let search = move || {
+        let builder = RequestBuilder::new()
+            .query_param("keyword", String::from(js_sys::encode_uri_component(&keyword())))
+            .query_param("page", 1)
+            .query_param("page_size", 100);
+        spawn(async move {
+            match get_api::<Vec<CatalogSearchHit>>("/api/v1/catalog/search", builder).await {
+                Ok(list) => hits.set(list),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    use_effect(move || refresh_sources());
+
+    use_effect(move || {
+        // 关键字变化时重新搜索
+        let _ = keyword();
+        search();
+    });
+
+    let handle_snapshot_all = move |_| {
+        snapshotting.set(Some("*".to_string()));
+        spawn(async move {
+            match post_api::<_, usize>("/api/v1/catalog/snapshot/all", ()).await {
+                Ok(count) => {
+                    message.set(format!("已检查 {} 个来源", count));
+                    error_msg.set(String::new());
+                }
+                Err(e) => error_msg.set(e),
+            }
+            snapshotting.set(None);
+            refresh_sources();
+            search();
+        });
+    };
+
+    let mut snapshot_one = move |source: CatalogSnapshot| {
+        snapshotting.set(Some(source.source_id.clone()));
+        let request = CatalogSnapshotRequest { source_type: source.source_type, source_id: source.source_id.clone() };
+        spawn(async move {
+            match post_api::<_, SnapshotResult>("/api/v1/catalog/snapshot", request).await {
+                Ok(result) => {
+                    if result.changed {
+                        message.set(format!("{} 生成版本 v{}，{} 处变更", result.snapshot.source_name, result.snapshot.version, result.drifts.len()));
+                    } else {
+                        message.set(format!("{} 结构未变化", result.snapshot.source_name));
+                    }
+                    error_msg.set(String::new());
+                    load_drifts(result.snapshot);
+                }
+                Err(e) => error_msg.set(e),
+            }
+            snapshotting.set(None);
+            refresh_sources();
+        });
+    };
+
+    rsx! {
+        div { class: "p-6 space-y-6",
+            // 页面标题
+            div { class: "flex justify-between items-center",
+                h1 { class: "text-2xl font text-gray-800", "元数据目录" }
+                button {
+                    class: "btn btn-info btn-sm",
+                    disabled: snapshotting().is_some(),
+                    onclick: handle_snapshot_all,
+                    if snapshotting() == Some("*".to_string()) {
+                        span { class: "loading loading-spinner loading-xs" }
+                    } else {
+                        Icon { icon: HiCamera, class: "w-4 h-4" }
+                    }
+                    "全部快照"
+                }
+            }
+
+            if !error_msg().is_empty() {
+                div { class: "alert alert-error",
+                    span { class: "whitespace-pre-wrap break-all", "{error_msg()}" }
+                }
+            }
+            if !message().is_empty() {
+                div { class: "alert alert-info",
+                    span { "{message()}" }
+                }
+            }
+
+            // 表与列搜索
+            div { class: "card bg-base-100 shadow-sm",
+                div { class: "card-body space-y-3",
+                    label { class: "input input-bordered flex items-center gap-2",
+                        Icon { icon: HiSearch, class: "w-4 h-4 opacity-60" }
+                        input {
+                            class: "grow",
+                            placeholder: "搜索表名、列名或注释",
+                            value: "{keyword}",
+                            oninput: move |e| keyword.set(e.value()),
+                        }
+                    }
+                    div { class: "overflow-auto max-h-96",
+                        table { class: "table table-sm table-pin-rows",
+                            thead {
+                                tr {
+                                    th { "来源" }
+                                    th { "表" }
+                                    th { "列" }
+                                    th { "类型" }
+                                    th { "注释" }
+                                }
+                            }
+                            tbody {
+                                for (index, hit) in hits().iter().enumerate() {
+                                    tr { key: "{index}",
+                                        td {
+                                            span { class: "badge badge-ghost badge-sm mr-1", "{source_label(hit.source_type)}" }
+                                            "{hit.source_name}"
+                                        }
+                                        td { class: "font-mono", "{hit.schema_name}.{hit.table_name}" }
+                                        td { class: "font-mono", "{hit.column_name}" }
+                                        td { class: "font-mono", if hit.column_name.is_empty() { "{hit.table_type}" } else { "{hit.column_type}" } }
+                                        td { class: "max-w-xs truncate", title: "{hit.comment}", "{hit.comment}" }
+                                    }
+                                }
+                            }
+                        }
+                        if hits().is_empty() {
+                            div { class: "text-center text-base-content/60 py-6", "没有匹配的表或列，请先生成快照" }
+                        }
+                    }
+                }
+            }
+
+            div { class: "grid grid-cols-1 lg:grid-cols-2 gap-6",
+                // 各来源的最新版本
+                div { class: "card bg-base-100 shadow-sm",
+                    div { class: "card-body",
+                        h2 { class: "font-semibold", "来源" }
+                        div { class: "overflow-auto",
+                            table { class: "table table-sm",
+                                thead {
+                                    tr {
+                                        th { "名称" }
+                                        th { "版本" }
+                                        th { "表/列" }
+                                        th { "最近检查" }
+                                        th {}
+                                    }
+                                }
+                                tbody {
+                                    for source in sources().iter() {
+                                        {
+                                            let clicked = source.clone();
+                                            let snapshot = source.clone();
+                                            let active = selected().is_some_and(|s| s.source_id == source.source_id);
+                                            let busy = snapshotting().as_deref() == Some(source.source_id.as_str());
+                                            rsx! {
+                                                tr {
+                                                    key: "{source.id}",
+                                                    class: if active { "bg-primary/10 cursor-pointer" } else { "hover cursor-pointer" },
+                                                    onclick: move |_| load_drifts(clicked.clone()),
+                                                    td {
+                                                        span { class: "badge badge-ghost badge-sm mr-1", "{source_label(source.source_type)}" }
+                                                        "{source.source_name}"
+                                                    }
+                                                    td { "v{source.version}" }
+                                                    td { "{source.table_count} / {source.column_count}" }
+                                                    td { class: "text-xs", "{source.checked_at}" }
+                                                    td {
+                                                        button {
+                                                            class: "btn btn-ghost btn-xs",
+                                                            disabled: snapshotting().is_some(),
+                                                            title: "立即快照",
+                                                            onclick: move |e| {
+                                                                e.stop_propagation();
+                                                                snapshot_one(snapshot.clone());
+                                                            },
+                                                            if busy {
+                                                                span { class: "loading loading-spinner loading-xs" }
+                                                            } else {
+                                                                Icon { icon: HiRefresh, class: "w-4 h-4" }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            if sources().is_empty() {
+                                div { class: "text-center text-base-content/60 py-6", "暂无快照" }
+                            }
+                        }
+                    }
+                }
+
+                // 选中来源的结构变更
+                div { class: "card bg-base-100 shadow-sm",
+                    div { class: "card-body",
+                        h2 { class: "font-semibold",
+                            "结构变更"
+                            if let Some(source) = selected() {
+                                span { class: "text-sm font-normal text-base-content/60 ml-2", "{source.source_name}" }
+                            }
+                        }
+                        div { class: "overflow-auto max-h-[32rem]",
+                            table { class: "table table-sm",
+                                thead {
+                                    tr {
+                                        th { "版本" }
+                                        th { "类型" }
+                                        th { "对象" }
+                                        th { "变更" }
+                                    }
+                                }
+                                tbody {
+                                    for drift in drifts().iter() {
+                                        tr { key: "{drift.id}",
+                                            td { "v{drift.from_version} → v{drift.to_version}" }
+                                            td { span { class: "{drift.drift_type.badge_class()} badge-sm", "{drift.drift_type.label()}" } }
+                                            td { class: "font-mono",
+                                                "{drift.schema_name}.{drift.table_name}"
+                                                if !drift.column_name.is_empty() {
+                                                    ".{drift.column_name}"
+                                                }
+                                            }
+                                            td { class: "font-mono text-xs",
+                                                if let Some(before) = drift.before_value.clone() {
+                                                    div { class: "text-error line-through", "{before}" }
+                                                }
+                                                if let Some(after) = drift.after_value.clone() {
+                                                    div { class: "text-success", "{after}" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            if drifts().is_empty() {
+                                div { class: "text-center text-base-content/60 py-6",
+                                    if selected().is_some() { "没有结构变更" } else { "选择左侧的来源查看结构变更" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -6,4 +6,12 @@ pub mod datasource_overview;
 pub mod datasource_mysql_config;
 pub mod datasource_postgres_config;
 pub mod datasource_queryapi_config;
-pub mod datasource_subscribeapi_config;
\ No newline at end of file
+pub mod datasource_subscribeapi_config;
+pub mod collection;
+pub mod task_run_monitor;
+pub mod pipeline_designer;
+pub mod transform;
+pub mod query_console;
+pub mod metadata_catalog;
+pub mod lineage_graph;
+pub mod user;
\ No newline at end of file
@@ -0,0 +1,649 @@
+use dioxus::prelude::*;
+use crate::components::pipeline_delete_dialog::PipelineDeleteDialog;
+use crate::models::collection::{CollectType, CollectionTask};
+use crate::models::pipeline::{edge_path, task_type_text, Pipeline, PipelineEdge, PipelineNode, TaskOption, NODE_HEIGHT, NODE_WIDTH};
+use crate::models::protocol::ApiResponse;
+use crate::models::transform::TransformTask;
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+use dioxus_free_icons::{icons::hi_outline_icons::*, Icon};
+use serde::{de::DeserializeOwned, Serialize};
+
+// 画布最小尺寸，以及节点之外预留的空白
+const CANVAS_MIN_WIDTH: f64 = 1200.0;
+const CANVAS_MIN_HEIGHT: f64 = 640.0;
+const CANVAS_MARGIN: f64 = 240.0;
+
+// 拖动节点时记录的起点
+#[derive(Clone, Copy, PartialEq)]
+struct MoveState {
+    index: usize,
+    start_x: f64,
+    start_y: f64,
+    origin_x: f64,
+    origin_y: f64,
+}
+
+// 拉取列表接口，失败时返回错误信息
+async fn fetch_list<T: DeserializeOwned>(path: &str) -> Result<Vec<T>, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .query_param("page", 1)
+        .query_param("page_size", 100)
+        .build();
+    let response_text = client.get(path, Some(req_config)).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<Vec<T>>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+async fn post_api<B, T>(path: &str, body: B) -> Result<T, String>
+where
+    B: Serialize + Send + Sync,
+    T: DeserializeOwned,
+{
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .build();
+    let response_text = client.post(path, Some(req_config), body).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<T>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+// 可编排的任务：实时采集不会结束，不能作为流水线节点
+async fn fetch_task_options() -> Result<Vec<TaskOption>, String> {
+    let collections = fetch_list::<CollectionTask>("/api/v1/collection/list").await?;
+    let transforms = fetch_list::<TransformTask>("/api/v1/transform/list").await?;
+    Ok(collections
+        .into_iter()
+        .filter(|t| t.collect_type != CollectType::Stream)
+        .map(|t| TaskOption { task_type: "collection".to_string(), task_id: t.id, name: t.name })
+        .chain(transforms.into_iter().map(|t| TaskOption { task_type: "transform".to_string(), task_id: t.id, name: t.name }))
+        .collect())
+}
+
+#[component]
+pub fn PipelineDesignerPage() -> Element {
+    let mut pipelines = use_signal(|| Vec::<Pipeline>::new());
+    let mut tasks = use_signal(|| Vec::<TaskOption>::new());
+    let mut pipeline = use_signal(Pipeline::default);
+    let mut error_msg = use_signal(String::new);
+    let mut info_msg = use_signal(String::new);
+    let mut show_delete = use_signal(|| false);
+
+    // 画布交互状态
+    let mut selected_node = use_signal(|| None as Option<String>);
+    let mut selected_edge = use_signal(|| None as Option<PipelineEdge>);
+    let mut connecting = use_signal(|| None as Option<String>);
+    let mut moving = use_signal(|| None as Option<MoveState>);
+    let mut dragging_task = use_signal(|| None as Option<TaskOption>);
+    let mut task_filter = use_signal(String::new);
+
+    let refresh_pipelines = move || {
+        spawn(async move {
+            match fetch_list::<Pipeline>("/api/v1/pipeline/list").await {
+                Ok(list) => pipelines.set(list),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    // 组件挂载时获取流水线与可编排的任务
+    use_effect(move || {
+        refresh_pipelines();
+        spawn(async move {
+            match fetch_task_options().await {
+                Ok(list) => tasks.set(list),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    });
+
+    let problems = use_memo(move || pipeline().problems(&tasks()));
+    let filtered_tasks = use_memo(move || {
+        let keyword = task_filter().to_lowercase();
+        tasks().into_iter().filter(|t| keyword.is_empty() || t.name.to_lowercase().contains(&keyword)).collect::<Vec<_>>()
+    });
+
+    // 画布随节点位置扩展
+    let canvas_size = use_memo(move || {
+        let p = pipeline();
+        let width = p.nodes.iter().map(|n| n.x + NODE_WIDTH + CANVAS_MARGIN).fold(CANVAS_MIN_WIDTH, f64::max);
+        let height = p.nodes.iter().map(|n| n.y + NODE_HEIGHT + CANVAS_MARGIN).fold(CANVAS_MIN_HEIGHT, f64::max);
+        (width, height)
+    });
+
+    let task_name = move |node: &PipelineNode| {
+        tasks()
+            .iter()
+            .find(|t| t.task_type == node.task_type && t.task_id == node.task_id)
+            .map(|t| t.name.clone())
+            .unwrap_or_else(|| node.task_id.clone())
+    };
+
+    let mut reset_selection = move || {
+        selected_node.set(None);
+        selected_edge.set(None);
+        connecting.set(None);
+    };
+
+    let mut load_pipeline = move |loaded: Pipeline| {
+        pipeline.set(loaded);
+        reset_selection();
+        error_msg.set(String::new());
+        info_msg.set(String::new());
+    };
+
+    let handle_drop = move |e: DragEvent| {
+        e.prevent_default();
+        let Some(task) = dragging_task.take() else {
+            return;
+        };
+        if pipeline.peek().contains_task(&task.task_type, &task.task_id) {
+            error_msg.set(format!("{} 已在流水线中", task.name));
+            return;
+        }
+        let point = e.element_coordinates();
+        pipeline.with_mut(|p| {
+            let id = p.next_node_id();
+            p.nodes.push(PipelineNode {
+                id: id.clone(),
+                name: task.name.clone(),
+                task_type: task.task_type.clone(),
+                task_id: task.task_id.clone(),
+                retries: 0,
+                retry_interval_secs: 60,
+                x: (point.x - NODE_WIDTH / 2.0).max(0.0).round(),
+                y: (point.y - NODE_HEIGHT / 2.0).max(0.0).round(),
+            });
+            selected_node.set(Some(id));
+        });
+        selected_edge.set(None);
+    };
+
+    // 点击节点：连线模式下连到该节点，否则选中
+    let mut handle_node_click = move |id: String| {
+        if let Some(from) = connecting.take() {
+            if from == id {
+                return;
+            }
+            let edge = PipelineEdge { from, to: id };
+            if pipeline.peek().edges.contains(&edge) {
+                error_msg.set(format!("连线 {} -> {} 已存在", edge.from, edge.to));
+                return;
+            }
+            pipeline.with_mut(|p| p.edges.push(edge.clone()));
+            selected_node.set(None);
+            selected_edge.set(Some(edge));
+        } else {
+            selected_edge.set(None);
+            selected_node.set(Some(id));
+        }
+    };
+
+    let handle_validate = move |_| {
+        spawn(async move {
+            info_msg.set(String::new());
+            match post_api::<Pipeline, Vec<String>>("/api/v1/pipeline/validate", pipeline()).await {
+                Ok(order) => {
+                    error_msg.set(String::new());
+                    info_msg.set(format!("校验通过，执行顺序：{}", order.join(" → ")));
+                }
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    let handle_save = move |_| {
+        if let Some(problem) = problems().first() {
+            error_msg.set(format!("请先修正校验问题：{}", problem.message));
+            return;
+        }
+        spawn(async move {
+            info_msg.set(String::new());
+            let current = pipeline();
+            let path = if current.id.is_empty() { "/api/v1/pipeline/add" } else { "/api/v1/pipeline/update" };
+            match post_api::<Pipeline, String>(path, current.clone()).await {
+                Ok(id) => {
+                    if current.id.is_empty() {
+                        pipeline.with_mut(|p| p.id = id);
+                    }
+                    error_msg.set(String::new());
+                    info_msg.set("流水线已保存".to_string());
+                    refresh_pipelines();
+                }
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    let handle_run = move |_| {
+        let id = pipeline().id;
+        if id.is_empty() {
+            error_msg.set("请先保存流水线".to_string());
+            return;
+        }
+        spawn(async move {
+            match post_api::<(), String>(&format!("/api/v1/pipeline/{}/run", id), ()).await {
+                Ok(run_id) => {
+                    error_msg.set(String::new());
+                    info_msg.set(format!("已开始运行，运行ID：{}", run_id));
+                }
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    let handle_confirm_delete = move |target: Pipeline| {
+        show_delete.set(false);
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+            match client.delete(&format!("/api/v1/pipeline/{}", target.id), Some(req_config)).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                    Ok(api_response) if api_response.result => {
+                        load_pipeline(Pipeline::default());
+                        info_msg.set("流水线已删除".to_string());
+                        refresh_pipelines();
+                    }
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => error_msg.set(e.to_string()),
+            }
+        });
+    };
+
+    let (canvas_width, canvas_height) = canvas_size();
+    let node_layer_class = if dragging_task().is_some() { "pointer-events-none" } else { "" };
+    let problem_nodes: Vec<String> = problems().iter().filter_map(|p| p.node_id.clone()).collect();
+
+    rsx! {
+        div { class: "p-6 space-y-4",
+            // 页面标题与操作
+            div { class: "flex items-center gap-2",
+                h1 { class: "text-2xl font text-gray-800 mr-4", "流程编排" }
+                select {
+                    class: "select select-bordered select-sm w-56",
+                    onchange: move |e| {
+                        let id = e.value();
+                        match pipelines().into_iter().find(|p| p.id == id) {
+                            Some(p) => load_pipeline(p),
+                            None => load_pipeline(Pipeline::default()),
+                        }
+                    },
+                    option { value: "", selected: pipeline().id.is_empty(), "新流水线" }
+                    for p in pipelines().iter() {
+                        option { key: "{p.id}", value: "{p.id}", selected: p.id == pipeline().id, "{p.name}" }
+                    }
+                }
+                div { class: "flex-1" }
+                button {
+                    class: "btn btn-outline btn-sm",
+                    onclick: move |_| load_pipeline(Pipeline::default()),
+                    Icon { icon: HiPlus, class: "w-4 h-4 mr-1" }
+                    "新建"
+                }
+                button {
+                    class: "btn btn-outline btn-sm",
+                    onclick: handle_validate,
+                    Icon { icon: HiShieldCheck, class: "w-4 h-4 mr-1" }
+                    "校验"
+                }
+                button {
+                    class: "btn btn-primary btn-sm",
+                    onclick: handle_save,
+                    Icon { icon: HiSave, class: "w-4 h-4 mr-1" }
+                    "保存"
+                }
+                button {
+                    class: "btn btn-outline btn-sm",
+                    disabled: pipeline().id.is_empty(),
+                    onclick: handle_run,
+                    Icon { icon: HiPlay, class: "w-4 h-4 mr-1" }
+                    "运行"
+                }
+                button {
+                    class: "btn btn-outline btn-error btn-sm",
+                    disabled: pipeline().id.is_empty(),
+                    onclick: move |_| show_delete.set(true),
+                    Icon { icon: HiTrash, class: "w-4 h-4 mr-1" }
+                    "删除"
+                }
+            }
+
+            if !error_msg().is_empty() {
+                div { class: "alert alert-error",
+                    span { "{error_msg()}" }
+                }
+            }
+            if !info_msg().is_empty() {
+                div { class: "alert alert-success",
+                    span { "{info_msg()}" }
+                }
+            }
+
+            div { class: "flex gap-4 items-stretch",
+                // 任务列表，拖入画布生成节点
+                div { class: "card bg-base-100 shadow-sm w-56 shrink-0",
+                    div { class: "card-body p-4 gap-3",
+                        h2 { class: "font-medium", "任务" }
+                        input {
+                            class: "input input-bordered input-sm w-full",
+                            placeholder: "搜索任务",
+                            value: "{task_filter}",
+                            oninput: move |e| task_filter.set(e.value()),
+                        }
+                        div { class: "flex flex-col gap-2 max-h-[36rem] overflow-auto",
+                            for task in filtered_tasks().iter() {
+                                {
+                                    let used = pipeline().contains_task(&task.task_type, &task.task_id);
+                                    let option = task.clone();
+                                    rsx! {
+                                        div {
+                                            key: "{task.task_type}-{task.task_id}",
+                                            class: if used { "p-2 rounded border border-base-300 text-base-content/40 cursor-not-allowed" } else { "p-2 rounded border border-base-300 hover:border-primary cursor-grab bg-base-100" },
+                                            draggable: if used { "false" } else { "true" },
+                                            ondragstart: move |_| dragging_task.set(Some(option.clone())),
+                                            ondragend: move |_| dragging_task.set(None),
+                                            div { class: "text-sm font-medium truncate", "{task.name}" }
+                                            div { class: "text-xs text-base-content/60", "{task_type_text(&task.task_type)}" }
+                                        }
+                                    }
+                                }
+                            }
+                            if filtered_tasks().is_empty() {
+                                div { class: "text-sm text-base-content/60 text-center py-6", "暂无可编排的任务" }
+                            }
+                        }
+                    }
+                }
+
+                // 画布
+                div { class: "card bg-base-100 shadow-sm flex-1 min-w-0 overflow-auto max-h-[44rem]",
+                    div {
+                        class: "relative select-none",
+                        style: "width: {canvas_width}px; height: {canvas_height}px; background-image: radial-gradient(circle, rgba(0,0,0,0.08) 1px, transparent 1px); background-size: 20px 20px;",
+                        ondragover: move |e| e.prevent_default(),
+                        ondrop: handle_drop,
+                        onmousemove: move |e| {
+                            let Some(state) = moving() else {
+                                return;
+                            };
+                            let point = e.client_coordinates();
+                            pipeline.with_mut(|p| {
+                                if let Some(node) = p.nodes.get_mut(state.index) {
+                                    node.x = (state.origin_x + point.x - state.start_x).max(0.0).round();
+                                    node.y = (state.origin_y + point.y - state.start_y).max(0.0).round();
+                                }
+                            });
+                        },
+                        onmouseup: move |_| moving.set(None),
+                        onmouseleave: move |_| moving.set(None),
+                        onclick: move |_| reset_selection(),
+
+                        svg {
+                            class: "absolute inset-0 pointer-events-none",
+                            width: "{canvas_width}",
+                            height: "{canvas_height}",
+                            defs {
+                                marker {
+                                    id: "pipeline-arrow",
+                                    "viewBox": "0 0 10 10",
+                                    "refX": "10",
+                                    "refY": "5",
+                                    "markerWidth": "8",
+                                    "markerHeight": "8",
+                                    "orient": "auto-start-reverse",
+                                    path { d: "M 0 0 L 10 5 L 0 10 z", fill: "#9ca3af" }
+                                }
+                            }
+                            for edge in pipeline().edges.iter() {
+                                if let (Some(from), Some(to)) = (pipeline().node(&edge.from).cloned(), pipeline().node(&edge.to).cloned()) {
+                                    {
+                                        let selected = selected_edge() == Some(edge.clone());
+                                        let target = edge.clone();
+                                        rsx! {
+                                            path {
+                                                key: "{edge.from}->{edge.to}",
+                                                d: "{edge_path(&from, &to)}",
+                                                fill: "none",
+                                                stroke: if selected { "#570df8" } else { "#9ca3af" },
+                                                "stroke-width": if selected { "3" } else { "2" },
+                                                "marker-end": "url(#pipeline-arrow)",
+                                                "pointer-events": if dragging_task().is_some() { "none" } else { "stroke" },
+                                                class: "cursor-pointer",
+                                                onclick: move |e| {
+                                                    e.stop_propagation();
+                                                    connecting.set(None);
+                                                    selected_node.set(None);
+                                                    selected_edge.set(Some(target.clone()));
+                                                },
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: node_layer_class,
+                            for (index, node) in pipeline().nodes.iter().enumerate() {
+                                {
+                                    let id = node.id.clone();
+                                    let port_id = node.id.clone();
+                                    let (x, y) = (node.x, node.y);
+                                    let border = if selected_node() == Some(node.id.clone()) {
+                                        "border-primary ring-2 ring-primary/30"
+                                    } else if problem_nodes.contains(&node.id) {
+                                        "border-error"
+                                    } else if connecting().is_some() {
+                                        "border-secondary"
+                                    } else {
+                                        "border-base-300"
+                                    };
+                                    let title = if node.name.is_empty() { task_name(node) } else { node.name.clone() };
+                                    rsx! {
+                                        div {
+                                            key: "{node.id}",
+                                            class: "absolute rounded-lg border-2 bg-base-100 shadow-sm px-3 py-2 cursor-move {border}",
+                                            style: "left: {x}px; top: {y}px; width: {NODE_WIDTH}px; height: {NODE_HEIGHT}px;",
+                                            onmousedown: move |e| {
+                                                let point = e.client_coordinates();
+                                                moving.set(Some(MoveState { index, start_x: point.x, start_y: point.y, origin_x: x, origin_y: y }));
+                                            },
+                                            onclick: move |e| {
+                                                e.stop_propagation();
+                                                handle_node_click(id.clone());
+                                            },
+                                            div { class: "text-sm font-medium truncate", title: "{title}", "{title}" }
+                                            div { class: "text-xs text-base-content/60 truncate", "{node.id} · {task_type_text(&node.task_type)}" }
+                                            // 输出端口：点击后再点击下游节点即可连线
+                                            div {
+                                                class: if connecting() == Some(node.id.clone()) { "absolute -right-2 top-1/2 -translate-y-1/2 w-4 h-4 rounded-full bg-secondary cursor-crosshair" } else { "absolute -right-2 top-1/2 -translate-y-1/2 w-4 h-4 rounded-full bg-primary cursor-crosshair" },
+                                                title: "连线到下游节点",
+                                                onmousedown: move |e| e.stop_propagation(),
+                                                onclick: move |e| {
+                                                    e.stop_propagation();
+                                                    selected_edge.set(None);
+                                                    selected_node.set(None);
+                                                    connecting.set(Some(port_id.clone()));
+                                                },
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        if pipeline().nodes.is_empty() {
+                            div { class: "absolute inset-0 flex items-center justify-center text-base-content/40 pointer-events-none",
+                                "从左侧拖入任务，点击节点右侧圆点后再点击下游节点建立依赖"
+                            }
+                        }
+                    }
+                }
+
+                // 属性面板
+                div { class: "card bg-base-100 shadow-sm w-72 shrink-0",
+                    div { class: "card-body p-4 gap-3",
+                        if let Some(node_id) = selected_node() {
+                            if let Some(node) = pipeline().node(&node_id).cloned() {
+                                h2 { class: "font-medium", "节点属性" }
+                                div { class: "text-xs text-base-content/60", "{node.id} · {task_type_text(&node.task_type)} · {task_name(&node)}" }
+                                div { class: "form-control",
+                                    label { class: "label", span { class: "label-text", "节点名称" } }
+                                    input {
+                                        class: "input input-bordered input-sm w-full",
+                                        value: "{node.name}",
+                                        oninput: {
+                                            let node_id = node_id.clone();
+                                            move |e: FormEvent| pipeline.with_mut(|p| {
+                                                if let Some(n) = p.nodes.iter_mut().find(|n| n.id == node_id) {
+                                                    n.name = e.value();
+                                                }
+                                            })
+                                        },
+                                    }
+                                }
+                                div { class: "form-control",
+                                    label { class: "label", span { class: "label-text", "失败重试次数" } }
+                                    input {
+                                        r#type: "number",
+                                        min: "0",
+                                        max: "10",
+                                        class: "input input-bordered input-sm w-full",
+                                        value: "{node.retries}",
+                                        oninput: {
+                                            let node_id = node_id.clone();
+                                            move |e: FormEvent| pipeline.with_mut(|p| {
+                                                if let Some(n) = p.nodes.iter_mut().find(|n| n.id == node_id) {
+                                                    n.retries = e.value().parse().unwrap_or(0);
+                                                }
+                                            })
+                                        },
+                                    }
+                                }
+                                div { class: "form-control",
+                                    label { class: "label", span { class: "label-text", "重试间隔（秒）" } }
+                                    input {
+                                        r#type: "number",
+                                        min: "0",
+                                        max: "3600",
+                                        class: "input input-bordered input-sm w-full",
+                                        value: "{node.retry_interval_secs}",
+                                        oninput: {
+                                            let node_id = node_id.clone();
+                                            move |e: FormEvent| pipeline.with_mut(|p| {
+                                                if let Some(n) = p.nodes.iter_mut().find(|n| n.id == node_id) {
+                                                    n.retry_interval_secs = e.value().parse().unwrap_or(0);
+                                                }
+                                            })
+                                        },
+                                    }
+                                }
+                                {
+                                    let upstream: Vec<String> = pipeline().edges.iter().filter(|e| e.to == node.id).map(|e| e.from.clone()).collect();
+                                    let upstream = if upstream.is_empty() { "无".to_string() } else { upstream.join(", ") };
+                                    rsx! {
+                                        div { class: "text-xs text-base-content/60", "上游节点：{upstream}" }
+                                    }
+                                }
+                                button {
+                                    class: "btn btn-outline btn-error btn-sm",
+                                    onclick: move |_| {
+                                        pipeline.with_mut(|p| p.remove_node(&node_id));
+                                        reset_selection();
+                                    },
+                                    Icon { icon: HiTrash, class: "w-4 h-4 mr-1" }
+                                    "删除节点"
+                                }
+                            }
+                        } else if let Some(edge) = selected_edge() {
+                            h2 { class: "font-medium", "依赖连线" }
+                            p { class: "text-sm", "{edge.from} 成功后执行 {edge.to}" }
+                            button {
+                                class: "btn btn-outline btn-error btn-sm",
+                                onclick: move |_| {
+                                    pipeline.with_mut(|p| p.edges.retain(|e| *e != edge));
+                                    reset_selection();
+                                },
+                                Icon { icon: HiTrash, class: "w-4 h-4 mr-1" }
+                                "删除连线"
+                            }
+                        } else {
+                            h2 { class: "font-medium", "流水线属性" }
+                            div { class: "form-control",
+                                label { class: "label", span { class: "label-text", "名称" } }
+                                input {
+                                    class: "input input-bordered input-sm w-full",
+                                    value: "{pipeline().name}",
+                                    oninput: move |e| pipeline.with_mut(|p| p.name = e.value()),
+                                }
+                            }
+                            div { class: "form-control",
+                                label { class: "label", span { class: "label-text", "描述" } }
+                                textarea {
+                                    class: "textarea textarea-bordered textarea-sm w-full",
+                                    value: "{pipeline().description}",
+                                    oninput: move |e| pipeline.with_mut(|p| p.description = e.value()),
+                                }
+                            }
+                            div { class: "form-control",
+                                label { class: "label", span { class: "label-text", "最大并行节点数" } }
+                                input {
+                                    r#type: "number",
+                                    min: "1",
+                                    max: "32",
+                                    class: "input input-bordered input-sm w-full",
+                                    value: "{pipeline().max_parallel}",
+                                    oninput: move |e| pipeline.with_mut(|p| p.max_parallel = e.value().parse().unwrap_or(0)),
+                                }
+                            }
+                            div { class: "text-xs text-base-content/60",
+                                "{pipeline().nodes.len()} 个节点，{pipeline().edges.len()} 条依赖"
+                            }
+                        }
+
+                        if connecting().is_some() {
+                            div { class: "alert alert-info text-sm py-2",
+                                span { "请点击下游节点完成连线，点击空白处取消" }
+                            }
+                        }
+
+                        // 校验问题
+                        div { class: "divider my-1" }
+                        h2 { class: "font-medium", "校验" }
+                        if problems().is_empty() {
+                            div { class: "text-sm text-success", "未发现问题" }
+                        }
+                        for (i, problem) in problems().iter().enumerate() {
+                            div { key: "{i}", class: "text-sm text-error break-all", "{problem.message}" }
+                        }
+                    }
+                }
+            }
+        }
+
+        if show_delete() {
+            PipelineDeleteDialog {
+                pipeline: pipeline(),
+                on_confirm: handle_confirm_delete,
+                on_cancel: move |_| show_delete.set(false),
+            }
+        }
+    }
+}
@@ -0,0 +1,488 @@
+use dioxus::prelude::*;
+use crate::models::datasource::{DataSource, DataSourceType};
+use crate::models::protocol::ApiResponse;
+use crate::models::query::{cell_text, QueryHistory, QueryRequest, QueryResult, QueryTargetType, DEFAULT_MAX_ROWS, DEFAULT_TIMEOUT_SECS};
+use crate::models::resource::{Resource, ResourceType};
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+use dioxus_free_icons::{icons::hi_outline_icons::*, Icon};
+use serde::{de::DeserializeOwned, Serialize};
+
+// 拉取列表接口，失败时返回错误信息
+async fn fetch_list<T: DeserializeOwned>(path: &str) -> Result<Vec<T>, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .query_param("page", 1)
+        .query_param("page_size", 100)
+        .build();
+    let response_text = client.get(path, Some(req_config)).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<Vec<T>>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+// 当前用户的查询历史，关键字匹配 SQL
+async fn fetch_history(keyword: String) -> Result<Vec<QueryHistory>, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let mut builder = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .query_param("page", 1)
+        .query_param("page_size", 50);
+    if !keyword.is_empty() {
+        builder = builder.query_param("keyword", String::from(js_sys::encode_uri_component(&keyword)));
+    }
+    let response_text = client.get("/api/v1/query/history", Some(builder.build())).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<Vec<QueryHistory>>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+async fn post_api<B, T>(path: &str, body: B) -> Result<T, String>
+where
+    B: Serialize + Send + Sync,
+    T: DeserializeOwned,
+{
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .build();
+    let response_text = client.post(path, Some(req_config), body).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<T>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+// 查询对象在下拉框中的取值，格式为 "类型:ID"
+fn target_value(target_type: QueryTargetType, id: &str) -> String {
+    match target_type {
+        QueryTargetType::Datasource => format!("datasource:{}", id),
+        QueryTargetType::Resource => format!("resource:{}", id),
+    }
+}
+
+fn parse_target(value: &str) -> Option<(QueryTargetType, String)> {
+    match value.split_once(':')? {
+        ("datasource", id) => Some((QueryTargetType::Datasource, id.to_string())),
+        ("resource", id) => Some((QueryTargetType::Resource, id.to_string())),
+        _ => None,
+    }
+}
+
+// 通过浏览器下载 CSV，带 BOM 便于 Excel 识别 UTF-8
+fn download_csv(file_name: &str, csv: String) {
+    let eval = document::eval(&format!(
+        r#"
+        const csv = await dioxus.recv();
+        const blob = new Blob(["\ufeff" + csv], {{ type: "text/csv;charset=utf-8" }});
+        const link = document.createElement("a");
+        link.href = URL.createObjectURL(blob);
+        link.download = "{}";
+        link.click();
+        URL.revokeObjectURL(link.href);
+        "#,
+        file_name
+    ));
+    if let Err(e) = eval.send(csv) {
+        tracing::error!("export csv failed: {:?}", e);
+    }
+}
+
+#[component]
+pub fn QueryConsolePage() -> Element {
+    let mut datasources = use_signal(|| Vec::<DataSource>::new());
+    let mut resources = use_signal(|| Vec::<Resource>::new());
+    let mut history = use_signal(|| Vec::<QueryHistory>::new());
+    let mut target = use_signal(String::new);
+    let mut sql = use_signal(|| "SELECT 1".to_string());
+    let mut max_rows = use_signal(|| DEFAULT_MAX_ROWS);
+    let mut timeout_secs = use_signal(|| DEFAULT_TIMEOUT_SECS);
+    let mut result = use_signal(|| None as Option<QueryResult>);
+    // 正在执行的查询ID，用于取消
+    let mut running = use_signal(|| None as Option<String>);
+    let mut history_filter = use_signal(String::new);
+    let mut error_msg = use_signal(String::new);
+
+    let refresh_history = move || {
+        spawn(async move {
+            match fetch_history(history_filter()).await {
+                Ok(list) => history.set(list),
+                Err(e) => tracing::error!("fetch query history failed: {}", e),
+            }
+        });
+    };
+
+    // 组件挂载时获取可查询的数据源、资源与查询历史，只支持 MySQL 与 PostgreSQL
+    use_effect(move || {
+        spawn(async move {
+            match fetch_list::<DataSource>("/api/v1/datasource/list").await {
+                Ok(list) => datasources.set(
+                    list.into_iter()
+                        .filter(|d| matches!(d.datasource_type, DataSourceType::Mysql | DataSourceType::Postgres))
+                        .collect(),
+                ),
+                Err(e) => error_msg.set(e),
+            }
+        });
+        spawn(async move {
+            match fetch_list::<Resource>("/api/v1/resource/list").await {
+                Ok(list) => resources.set(
+                    list.into_iter()
+                        .filter(|r| matches!(r.resource_type, ResourceType::Mysql | ResourceType::Postgres))
+                        .collect(),
+                ),
+                Err(e) => tracing::error!("fetch resources failed: {}", e),
+            }
+        });
+    });
+
+    use_effect(move || {
+        // 过滤条件变化时重新查询历史
+        let _ = history_filter();
+        refresh_history();
+    });
+
+    let mut execute = move || {
+        if running().is_some() {
+            return;
+        }
+        let Some((target_type, target_id)) = parse_target(&target()) else {
+            error_msg.set("请选择查询的数据源或资源".to_string());
+            return;
+        };
+        if sql().trim().is_empty() {
+            error_msg.set("请输入 SQL".to_string());
+            return;
+        }
+        let mut request = QueryRequest {
+            query_id: String::new(),
+            target_type,
+            target_id,
+            sql: sql(),
+            max_rows: Some(max_rows()),
+            timeout_secs: Some(timeout_secs()),
+        };
+        // 先占住执行状态，避免申请查询ID 期间重复提交
+        running.set(Some(String::new()));
+        error_msg.set(String::new());
+        spawn(async move {
+            // 查询ID 由后端生成，执行中可据此取消
+            match post_api::<_, String>("/api/v1/query/reserve", ()).await {
+                Ok(query_id) => {
+                    running.set(Some(query_id.clone()));
+                    request.query_id = query_id;
+                }
+                Err(e) => {
+                    error_msg.set(e);
+                    running.set(None);
+                    return;
+                }
+            }
+            match post_api::<_, QueryResult>("/api/v1/query/execute", request).await {
+                Ok(data) => result.set(Some(data)),
+                Err(e) => {
+                    result.set(None);
+                    error_msg.set(e);
+                }
+            }
+            running.set(None);
+            refresh_history();
+        });
+    };
+
+    let handle_cancel = move |_| {
+        let Some(query_id) = running().filter(|id| !id.is_empty()) else {
+            return;
+        };
+        spawn(async move {
+            if let Err(e) = post_api::<_, String>(&format!("/api/v1/query/{}/cancel", query_id), ()).await {
+                error_msg.set(e);
+            }
+        });
+    };
+
+    let handle_export = move |_| {
+        if let Some(data) = result() {
+            let file_name = format!("query_{}.csv", chrono::Local::now().format("%Y%m%d%H%M%S"));
+            download_csv(&file_name, data.to_csv());
+        }
+    };
+
+    let handle_clear_history = move |_| {
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+            match client.delete("/api/v1/query/history", Some(req_config)).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                    Ok(api_response) if api_response.result => history.set(Vec::new()),
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => {
+                    tracing::error!("Delete request failed: {}", e);
+                    error_msg.set(e.to_string());
+                }
+            }
+        });
+    };
+
+    let mut load_history = move |item: QueryHistory| {
+        target.set(target_value(item.target_type, &item.target_id));
+        sql.set(item.sql_text);
+    };
+
+    // 历史中展示查询对象名称，已删除的对象显示 ID
+    let target_name = move |target_type: QueryTargetType, id: &str| -> String {
+        let name = match target_type {
+            QueryTargetType::Datasource => datasources().iter().find(|d| d.id == id).map(|d| d.name.clone()),
+            QueryTargetType::Resource => resources().iter().find(|r| r.id == id).map(|r| r.name.clone()),
+        };
+        name.unwrap_or_else(|| id.to_string())
+    };
+
+    rsx! {
+        div { class: "p-6 space-y-6",
+            // 页面标题
+            div { class: "flex justify-between items-center",
+                h1 { class: "text-2xl font text-gray-800", "数据查询" }
+            }
+
+            if !error_msg().is_empty() {
+                div { class: "alert alert-error",
+                    span { class: "whitespace-pre-wrap break-all", "{error_msg()}" }
+                }
+            }
+
+            div { class: "flex gap-4 items-start",
+                div { class: "flex-1 min-w-0 space-y-4",
+                    // 查询编辑器
+                    div { class: "card bg-base-100 shadow-sm",
+                        div { class: "card-body space-y-3",
+                            div { class: "flex flex-wrap items-center gap-2",
+                                select {
+                                    class: "select select-bordered select-sm w-64",
+                                    onchange: move |e| target.set(e.value()),
+                                    option { value: "", disabled: true, selected: target().is_empty(), "请选择数据源或资源" }
+                                    optgroup { label: "数据源",
+                                        for datasource in datasources().iter() {
+                                            {
+                                                let value = target_value(QueryTargetType::Datasource, &datasource.id);
+                                                rsx! {
+                                                    option {
+                                                        key: "{value}",
+                                                        value: "{value}",
+                                                        selected: target() == value,
+                                                        "{datasource.name} ({datasource.datasource_type})"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                    optgroup { label: "资源",
+                                        for resource in resources().iter() {
+                                            {
+                                                let value = target_value(QueryTargetType::Resource, &resource.id);
+                                                rsx! {
+                                                    option {
+                                                        key: "{value}",
+                                                        value: "{value}",
+                                                        selected: target() == value,
+                                                        "{resource.name} ({resource.resource_type})"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                label { class: "text-sm text-base-content/60", "最多返回" }
+                                input {
+                                    class: "input input-bordered input-sm w-24",
+                                    r#type: "number",
+                                    min: "1",
+                                    max: "10000",
+                                    value: "{max_rows}",
+                                    oninput: move |e| max_rows.set(e.value().parse().unwrap_or(DEFAULT_MAX_ROWS)),
+                                }
+                                label { class: "text-sm text-base-content/60", "行，超时" }
+                                input {
+                                    class: "input input-bordered input-sm w-20",
+                                    r#type: "number",
+                                    min: "1",
+                                    max: "600",
+                                    value: "{timeout_secs}",
+                                    oninput: move |e| timeout_secs.set(e.value().parse().unwrap_or(DEFAULT_TIMEOUT_SECS)),
+                                }
+                                label { class: "text-sm text-base-content/60", "秒" }
+                                div { class: "flex-1" }
+                                button {
+                                    class: "btn btn-sm btn-info",
+                                    disabled: running().is_some(),
+                                    onclick: move |_| execute(),
+                                    if running().is_some() {
+                                        span { class: "loading loading-spinner loading-xs" }
+                                    } else {
+                                        Icon { icon: HiPlay, class: "w-4 h-4" }
+                                    }
+                                    "执行"
+                                }
+                                button {
+                                    class: "btn btn-sm btn-ghost text-warning",
+                                    disabled: running().is_none(),
+                                    onclick: handle_cancel,
+                                    Icon { icon: HiStop, class: "w-4 h-4" }
+                                    "取消"
+                                }
+                            }
+                            textarea {
+                                class: "textarea textarea-bordered w-full font-mono h-56",
+                                spellcheck: "false",
+                                placeholder: "只读语句：SELECT、EXPLAIN、DESCRIBE、SHOW，Ctrl+Enter 执行",
+                                value: "{sql}",
+                                oninput: move |e| sql.set(e.value()),
+                                onkeydown: move |e: KeyboardEvent| {
+                                    if e.key() == Key::Enter && e.modifiers().ctrl() {
+                                        e.prevent_default();
+                                        execute();
+                                    }
+                                },
+                            }
+                        }
+                    }
+
+                    // 查询结果
+                    if let Some(data) = result() {
+                        div { class: "card bg-base-100 shadow-sm",
+                            div { class: "card-body",
+                                div { class: "flex items-center gap-2",
+                                    h2 { class: "font-semibold", "查询结果" }
+                                    span { class: "text-sm text-base-content/60", "{data.rows.len()} 行，耗时 {data.duration_ms} ms" }
+                                    if data.truncated {
+                                        span { class: "badge badge-warning", "结果已截断，仅显示前 {data.rows.len()} 行" }
+                                    }
+                                    div { class: "flex-1" }
+                                    button {
+                                        class: "btn btn-ghost btn-sm",
+                                        disabled: data.columns.is_empty(),
+                                        onclick: handle_export,
+                                        Icon { icon: HiDownload, class: "w-4 h-4" }
+                                        "导出 CSV"
+                                    }
+                                }
+                                div { class: "overflow-auto max-h-[32rem]",
+                                    table { class: "table table-sm table-pin-rows",
+                                        thead {
+                                            tr {
+                                                th { class: "text-base-content/40", "#" }
+                                                for (index, column) in data.columns.iter().enumerate() {
+                                                    th { key: "{index}",
+                                                        div { class: "font-mono", "{column.name}" }
+                                                        div { class: "text-xs font-normal text-base-content/50", "{column.type_name}" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        tbody {
+                                            for (row_index, row) in data.rows.iter().enumerate() {
+                                                tr { key: "{row_index}",
+                                                    td { class: "text-base-content/40", "{row_index + 1}" }
+                                                    for (index, value) in row.iter().enumerate() {
+                                                        {
+                                                            let class = if value.is_null() {
+                                                                "italic text-base-content/40"
+                                                            } else if value.is_number() {
+                                                                "font-mono text-right"
+                                                            } else {
+                                                                "font-mono whitespace-pre max-w-md truncate"
+                                                            };
+                                                            rsx! {
+                                                                td { key: "{index}", class: class, title: cell_text(value), {cell_text(value)} }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                    if data.rows.is_empty() {
+                                        div { class: "text-center text-base-content/60 py-8", "查询结果为空" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // 查询历史
+                div { class: "card bg-base-100 shadow-sm w-80 shrink-0",
+                    div { class: "card-body p-3 gap-2",
+                        div { class: "flex items-center",
+                            h2 { class: "font-semibold", "查询历史" }
+                            div { class: "flex-1" }
+                            button {
+                                class: "btn btn-ghost btn-xs",
+                                onclick: move |_| refresh_history(),
+                                "刷新"
+                            }
+                            button {
+                                class: "btn btn-ghost btn-xs text-error",
+                                disabled: history().is_empty(),
+                                onclick: handle_clear_history,
+                                "清空"
+                            }
+                        }
+                        input {
+                            class: "input input-bordered input-sm w-full",
+                            placeholder: "搜索 SQL",
+                            value: "{history_filter}",
+                            oninput: move |e| history_filter.set(e.value()),
+                        }
+                        div { class: "flex flex-col gap-1 max-h-[40rem] overflow-auto",
+                            for item in history().iter() {
+                                {
+                                    let loaded = item.clone();
+                                    let name = target_name(item.target_type, &item.target_id);
+                                    rsx! {
+                                        button {
+                                            key: "{item.id}",
+                                            class: "btn btn-ghost btn-sm justify-start h-auto py-2 font-normal",
+                                            title: item.error_message.clone().unwrap_or_default(),
+                                            onclick: move |_| load_history(loaded.clone()),
+                                            div { class: "text-left w-full min-w-0",
+                                                div { class: "font-mono text-xs truncate", "{item.sql_text}" }
+                                                div { class: "flex items-center gap-1 text-xs text-base-content/60 mt-1",
+                                                    span { class: "{item.status.badge_class()} badge-xs", "{item.status}" }
+                                                    span { class: "truncate", "{name}" }
+                                                    div { class: "flex-1" }
+                                                    span { "{item.row_count} 行 · {item.duration_ms} ms" }
+                                                }
+                                                div { class: "text-xs text-base-content/40", "{item.executed_at}" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            if history().is_empty() {
+                                div { class: "text-center text-base-content/60 py-8 text-sm", "暂无查询历史" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use dioxus::prelude::*;
+use crate::components::task_run_log_dialog::TaskRunLogDialog;
+use crate::models::{protocol::ApiResponse, task_run::{RunStatus, TaskRun}};
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+use dioxus_free_icons::{icons::hi_outline_icons::*, Icon};
+
+// 每页运行记录数
+const PAGE_SIZE: usize = 20;
+
+// 运维监控页标题
+fn monitor_title(task_type: &str) -> &'static str {
+    match task_type {
+        "transform" => "宽表任务监控",
+        "sync" => "同步任务监控",
+        _ => "采集任务监控",
+    }
+}
+
+#[component]
+pub fn TaskRunMonitorPage(task_type: String) -> Element {
+    let mut runs = use_signal(|| Vec::<TaskRun>::new());
+    let mut error_msg = use_signal(String::new);
+    let mut info_msg = use_signal(String::new);
+    let mut selected_run = use_signal(|| None as Option<TaskRun>);
+
+    // 筛选条件
+    let mut status_filter = use_signal(String::new);
+    let mut date_from = use_signal(String::new);
+    let mut date_to = use_signal(String::new);
+    let mut name_filter = use_signal(String::new);
+    let mut page = use_signal(|| 1usize);
+    let mut reload = use_signal(|| 0u32);
+
+    // 任务类型、筛选条件或页码变化时重新查询
+    use_effect(use_reactive((&task_type,), move |(task_type,)| {
+        let status = status_filter();
+        let from = date_from();
+        let to = date_to();
+        let current_page = page();
+        let _ = reload();
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let mut builder = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .query_param("task_type", &task_type)
+                .query_param("page", current_page)
+                .query_param("page_size", PAGE_SIZE);
+            if !status.is_empty() {
+                builder = builder.query_param("status", status);
+            }
+            if !from.is_empty() {
+                builder = builder.query_param("started_from", String::from(js_sys::encode_uri_component(&format!("{} 00:00:00", from))));
+            }
+            if !to.is_empty() {
+                builder = builder.query_param("started_to", String::from(js_sys::encode_uri_component(&format!("{} 23:59:59", to))));
+            }
+            match client.get("/api/v1/run/list", Some(builder.build())).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<Vec<TaskRun>>>(&response_text) {
+                    Ok(api_response) if api_response.result => {
+                        error_msg.set(String::new());
+                        runs.set(api_response.data);
+                    }
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => error_msg.set(e.to_string()),
+            }
+        });
+    }));
+
+    let filtered_runs = use_memo(move || {
+        let mut result = runs().clone();
+        if !name_filter().is_empty() {
+            let keyword = name_filter().to_lowercase();
+            result.retain(|r| r.task_name.to_lowercase().contains(&keyword));
+        }
+        result
+    });
+
+    let handle_cancel = move |run: TaskRun| {
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+            match client.post(&format!("/api/v1/run/{}/cancel", run.id), Some(req_config), ()).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                    Ok(api_response) if api_response.result => {
+                        error_msg.set(String::new());
+                        info_msg.set("已发送取消请求".to_string());
+                        reload += 1;
+                    }
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => error_msg.set(e.to_string()),
+            }
+        });
+    };
+
+    rsx! {
+        div { class: "p-6 space-y-6",
+            // 页面标题
+            div { class: "flex justify-between items-center",
+                h1 { class: "text-2xl font text-gray-800", "{monitor_title(&task_type)}" }
+                div { class: "flex-1" }
+                button {
+                    class: "btn btn-outline ml-auto",
+                    onclick: move |_| {
+                        info_msg.set(String::new());
+                        reload += 1;
+                    },
+                    Icon { icon: HiRefresh, class: "w-4 h-4 mr-2" }
+                    "刷新"
+                }
+            }
+
+            // 搜索栏
+            div { class: "card bg-base-100 shadow-sm w-full",
+                div { class: "card-body",
+                    div { class: "flex items-end gap-4 justify-end w-full",
+                        div { class: "form-control",
+                            select {
+                                class: "select select-bordered w-full",
+                                onchange: move |e| {
+                                    status_filter.set(e.value());
+                                    page.set(1);
+                                },
+                                option { value: "", selected: status_filter().is_empty(), "全部状态" }
+                                option { value: "running", "{RunStatus::Running}" }
+                                option { value: "success", "{RunStatus::Success}" }
+                                option { value: "failed", "{RunStatus::Failed}" }
+                                option { value: "cancelled", "{RunStatus::Cancelled}" }
+                            }
+                        }
+                        div { class: "form-control",
+                            input {
+                                r#type: "date",
+                                class: "input input-bordered w-full",
+                                value: "{date_from}",
+                                onchange: move |e| {
+                                    date_from.set(e.value());
+                                    page.set(1);
+                                },
+                            }
+                        }
+                        span { class: "pb-3", "至" }
+                        div { class: "form-control",
+                            input {
+                                r#type: "date",
+                                class: "input input-bordered w-full",
+                                value: "{date_to}",
+                                onchange: move |e| {
+                                    date_to.set(e.value());
+                                    page.set(1);
+                                },
+                            }
+                        }
+                        div { class: "form-control",
+                            input {
+                                class: "input input-bordered w-full",
+                                placeholder: "任务名称",
+                                value: "{name_filter}",
+                                oninput: move |e| name_filter.set(e.value()),
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !error_msg().is_empty() {
+                div { class: "alert alert-error",
+                    span { "{error_msg()}" }
+                }
+            }
+            if !info_msg().is_empty() {
+                div { class: "alert alert-success",
+                    span { "{info_msg()}" }
+                }
+            }
+
+            // 运行记录列表
+            div { class: "card bg-base-100 shadow-sm",
+                div { class: "overflow-x-auto",
+                    table { class: "table",
+                        thead {
+                            tr {
+                                th { "任务名称" }
+                                th { "状态" }
+                                th { "开始时间" }
+                                th { "耗时" }
+                                th { "读取行数" }
+                                th { "写入行数" }
+                                th { "数据量" }
+                                th { "错误信息" }
+                                th { "操作" }
+                            }
+                        }
+                        tbody {
+                            for run in filtered_runs().iter() {
+                                tr { key: "{run.id}",
+                                    td {
+                                        div { class: "font-medium",
+                                            if run.task_name.is_empty() { "{run.task_id}" } else { "{run.task_name}" }
+                                        }
+                                        div { class: "text-xs text-base-content/60 font-mono", "{run.id}" }
+                                    }
+                                    td { span { class: run.status.badge_class(), "{run.status}" } }
+                                    td { "{run.started_at}" }
+                                    td { "{run.duration_text()}" }
+                                    td { "{run.rows_read}" }
+                                    td { "{run.rows_written}" }
+                                    td { "{run.bytes_text()}" }
+                                    td { class: "max-w-xs truncate text-error", title: "{run.error_message}", "{run.error_message}" }
+                                    td {
+                                        div { class: "flex gap-2",
+                                            button {
+                                                class: "btn btn-ghost btn-xs",
+                                                onclick: {
+                                                    let run = run.clone();
+                                                    move |_| selected_run.set(Some(run.clone()))
+                                                },
+                                                Icon { icon: HiDocumentText, class: "w-4 h-4" }
+                                                "日志"
+                                            }
+                                            if run.status == RunStatus::Running {
+                                                button {
+                                                    class: "btn btn-ghost btn-xs text-warning",
+                                                    onclick: {
+                                                        let run = run.clone();
+                                                        move |_| handle_cancel(run.clone())
+                                                    },
+                                                    Icon { icon: HiStop, class: "w-4 h-4" }
+                                                    "取消"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if filtered_runs().is_empty() {
+                        div { class: "text-center text-base-content/60 py-12", "暂无运行记录" }
+                    }
+                }
+            }
+
+            // 分页
+            div { class: "flex justify-end",
+                div { class: "join",
+                    button {
+                        class: "join-item btn btn-sm",
+                        disabled: page() <= 1,
+                        onclick: move |_| page -= 1,
+                        "«"
+                    }
+                    button { class: "join-item btn btn-sm", "第 {page} 页" }
+                    button {
+                        class: "join-item btn btn-sm",
+                        disabled: runs().len() < PAGE_SIZE,
+                        onclick: move |_| page += 1,
+                        "»"
+                    }
+                }
+            }
+        }
+
+        if let Some(run) = selected_run() {
+            TaskRunLogDialog {
+                run: run.clone(),
+                on_close: move |_| {
+                    selected_run.set(None);
+                    reload += 1;
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,632 @@
+use dioxus::prelude::*;
+use std::collections::BTreeMap;
+use crate::components::transform_delete_dialog::TransformDeleteDialog;
+use crate::models::protocol::ApiResponse;
+use crate::models::resource::{Resource, ResourceType};
+use crate::models::task_run::TaskRun;
+use crate::models::transform::{Materialization, TransformCheck, TransformTask, BUILTIN_PARAMS};
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+use dioxus_free_icons::{icons::hi_outline_icons::*, Icon};
+use serde::{de::DeserializeOwned, Serialize};
+
+// 拉取列表接口，失败时返回错误信息
+async fn fetch_list<T: DeserializeOwned>(path: &str) -> Result<Vec<T>, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .query_param("page", 1)
+        .query_param("page_size", 100)
+        .build();
+    let response_text = client.get(path, Some(req_config)).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<Vec<T>>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+async fn post_api<B, T>(path: &str, query: Option<(&str, String)>, body: B) -> Result<T, String>
+where
+    B: Serialize + Send + Sync,
+    T: DeserializeOwned,
+{
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let mut builder = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies());
+    if let Some((key, value)) = query {
+        builder = builder.query_param(key, value);
+    }
+    let response_text = client.post(path, Some(builder.build()), body).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<T>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+#[component]
+pub fn TransformPage(task_id: String) -> Element {
+    let mut tasks = use_signal(|| Vec::<TransformTask>::new());
+    // 从血缘图等页面跳转时需要直接打开的任务
+    let mut focus_task = use_signal(|| task_id);
+    let mut resources = use_signal(|| Vec::<Resource>::new());
+    let mut form = use_signal(TransformTask::default);
+    // 自定义参数按行编辑，保存时转换为 map
+    let mut params = use_signal(|| Vec::<(String, String)>::new());
+    let mut merge_keys = use_signal(String::new);
+    let mut check = use_signal(|| None as Option<TransformCheck>);
+    let mut runs = use_signal(|| Vec::<TaskRun>::new());
+    let mut biz_date = use_signal(String::new);
+    let mut task_filter = use_signal(String::new);
+    let mut error_msg = use_signal(String::new);
+    let mut info_msg = use_signal(String::new);
+    let mut show_delete = use_signal(|| false);
+
+    let refresh_tasks = move || {
+        spawn(async move {
+            match fetch_list::<TransformTask>("/api/v1/transform/list").await {
+                Ok(list) => tasks.set(list),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    let mut refresh_runs = move || {
+        let id = form().id;
+        if id.is_empty() {
+            runs.set(Vec::new());
+            return;
+        }
+        spawn(async move {
+            match fetch_list::<TaskRun>(&format!("/api/v1/transform/{}/runs", id)).await {
+                Ok(list) => runs.set(list),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    // 组件挂载时获取任务与资源列表，只有 MySQL 与 Doris 资源可以执行 SQL
+    use_effect(move || {
+        refresh_tasks();
+        spawn(async move {
+            match fetch_list::<Resource>("/api/v1/resource/list").await {
+                Ok(list) => resources.set(
+                    list.into_iter()
+                        .filter(|r| matches!(r.resource_type, ResourceType::Mysql | ResourceType::Doris))
+                        .collect(),
+                ),
+                Err(e) => tracing::error!("fetch resources failed: {}", e),
+            }
+        });
+    });
+
+    let filtered_tasks = use_memo(move || {
+        let keyword = task_filter().to_lowercase();
+        tasks()
+            .into_iter()
+            .filter(|t| keyword.is_empty() || t.name.to_lowercase().contains(&keyword) || t.output_table.to_lowercase().contains(&keyword))
+            .collect::<Vec<_>>()
+    });
+
+    let is_doris = use_memo(move || {
+        resources().iter().any(|r| r.id == form().resource_id && r.resource_type == ResourceType::Doris)
+    });
+
+    let mut load_task = move |task: TransformTask| {
+        params.set(task.params.clone().into_iter().collect());
+        merge_keys.set(task.merge_keys.join(", "));
+        form.set(task);
+        check.set(None);
+        error_msg.set(String::new());
+        info_msg.set(String::new());
+        refresh_runs();
+    };
+
+    // 任务列表加载后打开跳转时指定的任务
+    use_effect(move || {
+        let list = tasks();
+        let focus = focus_task.peek().clone();
+        if focus.is_empty() {
+            return;
+        }
+        if let Some(task) = list.into_iter().find(|t| t.id == focus) {
+            focus_task.set(String::new());
+            load_task(task);
+        }
+    });
+
+    // 合并参数行与键列，得到提交给后端的任务
+    let build = move || -> Result<TransformTask, String> {
+        let mut task = form();
+        if task.name.trim().is_empty() {
+            return Err("请输入任务名称".to_string());
+        }
+        if task.resource_id.is_empty() {
+            return Err("请选择执行资源".to_string());
+        }
+        if task.output_table.trim().is_empty() {
+            return Err("请输入输出表名".to_string());
+        }
+        task.merge_keys = merge_keys()
+            .split(',')
+            .map(|k| k.trim().to_string())
+            .filter(|k| !k.is_empty())
+            .collect();
+        if task.materialization == Materialization::Merge && task.merge_keys.is_empty() {
+            return Err("按键合并需要填写键列".to_string());
+        }
+        let mut map = BTreeMap::new();
+        for (name, value) in params() {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                continue;
+            }
+            if map.insert(name.clone(), value).is_some() {
+                return Err(format!("参数 {} 重复", name));
+            }
+        }
+        task.params = map;
+        Ok(task)
+    };
+
+    // 解析 SQL，展示语法错误或引用表
+    let handle_check = move |_| {
+        let task = match build() {
+            Ok(task) => task,
+            Err(e) => {
+                error_msg.set(e);
+                return;
+            }
+        };
+        spawn(async move {
+            match post_api::<_, TransformCheck>("/api/v1/transform/check", None, task).await {
+                Ok(result) => {
+                    error_msg.set(String::new());
+                    info_msg.set("SQL 校验通过".to_string());
+                    check.set(Some(result));
+                }
+                Err(e) => {
+                    info_msg.set(String::new());
+                    check.set(None);
+                    error_msg.set(e);
+                }
+            }
+        });
+    };
+
+    let handle_save = move |_| {
+        let task = match build() {
+            Ok(task) => task,
+            Err(e) => {
+                error_msg.set(e);
+                return;
+            }
+        };
+        spawn(async move {
+            let is_new = task.id.is_empty();
+            let path = if is_new { "/api/v1/transform/add" } else { "/api/v1/transform/update" };
+            match post_api::<_, String>(path, None, task).await {
+                Ok(id) => {
+                    if is_new {
+                        form.with_mut(|f| f.id = id);
+                    }
+                    error_msg.set(String::new());
+                    info_msg.set("宽表任务已保存".to_string());
+                    refresh_tasks();
+                }
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    // 手动运行，可指定业务日期补数
+    let handle_run = move |_| {
+        let task = form();
+        if task.id.is_empty() {
+            error_msg.set("请先保存任务".to_string());
+            return;
+        }
+        let date = biz_date();
+        spawn(async move {
+            let query = if date.is_empty() { None } else { Some(("biz_date", date)) };
+            match post_api::<_, String>(&format!("/api/v1/transform/{}/run", task.id), query, ()).await {
+                Ok(_) => {
+                    error_msg.set(String::new());
+                    info_msg.set(format!("任务 {} 已开始运行", task.name));
+                    refresh_runs();
+                }
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    let handle_stop = move |_| {
+        let task = form();
+        spawn(async move {
+            match post_api::<_, String>(&format!("/api/v1/transform/{}/stop", task.id), None, ()).await {
+                Ok(_) => {
+                    error_msg.set(String::new());
+                    info_msg.set(format!("任务 {} 正在停止", task.name));
+                }
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    let handle_confirm_delete = move |task: TransformTask| {
+        show_delete.set(false);
+        spawn(async move {
+            let client = crate::utils::request::create_client("http://localhost:3000");
+            let req_config = RequestBuilder::new()
+                .header("Content-Type", "application/json")
+                .header("Cookie", &cookie::get_browser_cookies())
+                .build();
+            match client.delete(&format!("/api/v1/transform/{}", task.id), Some(req_config)).await {
+                Ok(response_text) => match serde_json::from_str::<ApiResponse<String>>(&response_text) {
+                    Ok(api_response) if api_response.result => {
+                        load_task(TransformTask::default());
+                        refresh_tasks();
+                    }
+                    Ok(api_response) => error_msg.set(api_response.msg),
+                    Err(e) => error_msg.set(e.to_string()),
+                },
+                Err(e) => {
+                    tracing::error!("Delete request failed: {}", e);
+                    error_msg.set(e.to_string());
+                }
+            }
+        });
+    };
+
+    rsx! {
+        div { class: "p-6 space-y-6",
+            // 页面标题
+            div { class: "flex justify-between items-center",
+                h1 { class: "text-2xl font text-gray-800", "宽表开发" }
+                div { class: "flex-1" }
+                button {
+                    class: "btn btn-info ml-auto",
+                    onclick: move |_| load_task(TransformTask::default()),
+                    Icon { icon: HiPlus, class: "w-4 h-4 mr-2" }
+                    "新建宽表任务"
+                }
+            }
+
+            if !error_msg().is_empty() {
+                div { class: "alert alert-error",
+                    span { class: "whitespace-pre-wrap break-all", "{error_msg()}" }
+                }
+            }
+            if !info_msg().is_empty() {
+                div { class: "alert alert-success",
+                    span { "{info_msg()}" }
+                }
+            }
+
+            div { class: "flex gap-4 items-start",
+                // 任务列表
+                div { class: "card bg-base-100 shadow-sm w-64 shrink-0",
+                    div { class: "card-body p-3 gap-2",
+                        input {
+                            class: "input input-bordered input-sm w-full",
+                            placeholder: "任务名称或输出表",
+                            value: "{task_filter}",
+                            oninput: move |e| task_filter.set(e.value()),
+                        }
+                        for task in filtered_tasks().iter() {
+                            {
+                                let active = if form().id == task.id { "bg-primary/10 text-primary" } else { "" };
+                                let loaded = task.clone();
+                                rsx! {
+                                    button {
+                                        key: "{task.id}",
+                                        class: "btn btn-ghost btn-sm justify-start h-auto py-2 {active}",
+                                        onclick: move |_| load_task(loaded.clone()),
+                                        div { class: "text-left",
+                                            div { class: "font-medium", "{task.name}" }
+                                            div { class: "text-xs text-base-content/60", "{task.output_table} · {task.materialization}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        if filtered_tasks().is_empty() {
+                            div { class: "text-center text-base-content/60 py-8 text-sm", "暂无宽表任务" }
+                        }
+                    }
+                }
+
+                // 编辑区
+                div { class: "flex-1 min-w-0 space-y-4",
+                    div { class: "card bg-base-100 shadow-sm",
+                        div { class: "card-body space-y-2",
+                            div { class: "grid grid-cols-3 gap-4",
+                                div { class: "form-control",
+                                    label { class: "label", span { class: "label-text", "任务名称" } }
+                                    input {
+                                        class: "input input-bordered w-full",
+                                        placeholder: "请输入任务名称",
+                                        value: "{form().name}",
+                                        oninput: move |e| form.with_mut(|f| f.name = e.value()),
+                                    }
+                                }
+                                div { class: "form-control col-span-2",
+                                    label { class: "label", span { class: "label-text", "任务描述" } }
+                                    input {
+                                        class: "input input-bordered w-full",
+                                        placeholder: "请输入任务描述",
+                                        value: "{form().description}",
+                                        oninput: move |e| form.with_mut(|f| f.description = e.value()),
+                                    }
+                                }
+                                div { class: "form-control",
+                                    label { class: "label", span { class: "label-text", "执行资源" } }
+                                    select {
+                                        class: "select select-bordered w-full",
+                                        onchange: move |e| form.with_mut(|f| f.resource_id = e.value()),
+                                        option { value: "", disabled: true, selected: form().resource_id.is_empty(), "请选择 MySQL 或 Doris 资源" }
+                                        for resource in resources().iter() {
+                                            option {
+                                                value: "{resource.id}",
+                                                selected: form().resource_id == resource.id,
+                                                "{resource.name}"
+                                            }
+                                        }
+                                    }
+                                }
+                                div { class: "form-control",
+                                    label { class: "label", span { class: "label-text", "输出库" } }
+                                    input {
+                                        class: "input input-bordered w-full",
+                                        placeholder: "为空时使用资源的默认库",
+                                        value: "{form().target_database}",
+                                        oninput: move |e| form.with_mut(|f| f.target_database = e.value()),
+                                    }
+                                }
+                                div { class: "form-control",
+                                    label { class: "label", span { class: "label-text", "输出表" } }
+                                    input {
+                                        class: "input input-bordered w-full",
+                                        placeholder: "请输入输出表名",
+                                        value: "{form().output_table}",
+                                        oninput: move |e| form.with_mut(|f| f.output_table = e.value()),
+                                    }
+                                }
+                                div { class: "form-control",
+                                    label { class: "label", span { class: "label-text", "物化方式" } }
+                                    select {
+                                        class: "select select-bordered w-full",
+                                        onchange: move |e| form.with_mut(|f| {
+                                            f.materialization = match e.value().as_str() {
+                                                "append" => Materialization::Append,
+                                                "merge" => Materialization::Merge,
+                                                _ => Materialization::Replace,
+                                            };
+                                        }),
+                                        option { value: "replace", selected: form().materialization == Materialization::Replace, "覆盖" }
+                                        option { value: "append", selected: form().materialization == Materialization::Append, "追加" }
+                                        option { value: "merge", selected: form().materialization == Materialization::Merge, "按键合并" }
+                                    }
+                                }
+                                if form().materialization == Materialization::Merge {
+                                    div { class: "form-control",
+                                        label { class: "label", span { class: "label-text", "键列" } }
+                                        input {
+                                            class: "input input-bordered w-full font-mono",
+                                            placeholder: "多个键列用逗号分隔",
+                                            value: "{merge_keys}",
+                                            oninput: move |e| merge_keys.set(e.value()),
+                                        }
+                                    }
+                                }
+                                if is_doris() {
+                                    div { class: "form-control",
+                                        label { class: "label", span { class: "label-text", "Doris 副本数" } }
+                                        input {
+                                            class: "input input-bordered w-full",
+                                            r#type: "number",
+                                            min: "1",
+                                            value: "{form().doris_replication_num}",
+                                            oninput: move |e| form.with_mut(|f| f.doris_replication_num = e.value().parse().unwrap_or(1)),
+                                        }
+                                    }
+                                }
+                            }
+                            if form().materialization == Materialization::Merge && is_doris() {
+                                p { class: "text-xs text-base-content/60", "Doris 按键合并时，键列需位于查询结果的最前面" }
+                            }
+                        }
+                    }
+
+                    // SQL 与参数
+                    div { class: "card bg-base-100 shadow-sm",
+                        div { class: "card-body space-y-3",
+                            div { class: "flex items-center gap-2",
+                                h2 { class: "font-semibold", "SQL" }
+                                div { class: "flex-1" }
+                                button {
+                                    class: "btn btn-sm btn-outline",
+                                    onclick: handle_check,
+                                    Icon { icon: HiCheckCircle, class: "w-4 h-4" }
+                                    "校验"
+                                }
+                                button {
+                                    class: "btn btn-sm btn-info",
+                                    onclick: handle_save,
+                                    Icon { icon: HiSave, class: "w-4 h-4" }
+                                    "保存"
+                                }
+                                input {
+                                    class: "input input-bordered input-sm",
+                                    r#type: "date",
+                                    title: "业务日期，为空时为运行日前一天",
+                                    value: "{biz_date}",
+                                    oninput: move |e| biz_date.set(e.value()),
+                                }
+                                button {
+                                    class: "btn btn-sm btn-ghost text-info",
+                                    disabled: form().id.is_empty(),
+                                    onclick: handle_run,
+                                    Icon { icon: HiPlay, class: "w-4 h-4" }
+                                    "运行"
+                                }
+                                button {
+                                    class: "btn btn-sm btn-ghost text-warning",
+                                    disabled: form().id.is_empty(),
+                                    onclick: handle_stop,
+                                    Icon { icon: HiStop, class: "w-4 h-4" }
+                                    "停止"
+                                }
+                                button {
+                                    class: "btn btn-sm btn-ghost text-error",
+                                    disabled: form().id.is_empty(),
+                                    onclick: move |_| show_delete.set(true),
+                                    Icon { icon: HiTrash, class: "w-4 h-4" }
+                                    "删除"
+                                }
+                            }
+                            textarea {
+                                class: "textarea textarea-bordered w-full font-mono h-72",
+                                spellcheck: "false",
+                                placeholder: "只能是一条 SELECT 语句",
+                                value: "{form().sql_text}",
+                                oninput: move |e| form.with_mut(|f| f.sql_text = e.value()),
+                            }
+
+                            div { class: "flex items-center",
+                                h3 { class: "font-semibold text-sm", "自定义参数" }
+                                div { class: "flex-1" }
+                                button {
+                                    class: "btn btn-ghost btn-xs",
+                                    onclick: move |_| params.with_mut(|p| p.push((String::new(), String::new()))),
+                                    Icon { icon: HiPlus, class: "w-4 h-4" }
+                                    "添加参数"
+                                }
+                            }
+                            for (index, (name, value)) in params().into_iter().enumerate() {
+                                div { key: "{index}", class: "flex gap-2",
+                                    input {
+                                        class: "input input-bordered input-sm w-48 font-mono",
+                                        placeholder: "参数名",
+                                        value: "{name}",
+                                        oninput: move |e| params.with_mut(|p| p[index].0 = e.value()),
+                                    }
+                                    input {
+                                        class: "input input-bordered input-sm flex-1 font-mono",
+                                        placeholder: "参数值，可引用内置参数",
+                                        value: "{value}",
+                                        oninput: move |e| params.with_mut(|p| p[index].1 = e.value()),
+                                    }
+                                    button {
+                                        class: "btn btn-ghost btn-sm text-error",
+                                        onclick: move |_| {
+                                            params.with_mut(|p| {
+                                                p.remove(index);
+                                            });
+                                        },
+                                        Icon { icon: HiTrash, class: "w-4 h-4" }
+                                    }
+                                }
+                            }
+                            div { class: "text-xs text-base-content/60 space-y-1",
+                                for (name, description) in BUILTIN_PARAMS.iter() {
+                                    div { key: "{name}",
+                                        code { class: "font-mono mr-2", "${{{name}}}" }
+                                        "{description}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    // 校验结果
+                    if let Some(result) = check() {
+                        div { class: "card bg-base-100 shadow-sm",
+                            div { class: "card-body space-y-3",
+                                h2 { class: "font-semibold", "校验结果" }
+                                div { class: "flex flex-wrap items-center gap-2 text-sm",
+                                    span { class: "text-base-content/60", "引用表" }
+                                    for table in result.referenced_tables.iter() {
+                                        span { key: "{table}", class: "badge badge-outline font-mono", "{table}" }
+                                    }
+                                    if result.referenced_tables.is_empty() {
+                                        span { "-" }
+                                    }
+                                }
+                                div { class: "flex flex-wrap items-center gap-2 text-sm",
+                                    span { class: "text-base-content/60", "使用参数" }
+                                    for param in result.params.iter() {
+                                        span { key: "{param}", class: "badge badge-ghost font-mono", "{param}" }
+                                    }
+                                    if result.params.is_empty() {
+                                        span { "-" }
+                                    }
+                                }
+                                pre { class: "bg-base-200 rounded p-3 text-xs font-mono whitespace-pre-wrap break-all", "{result.rendered_sql}" }
+                            }
+                        }
+                    }
+
+                    // 运行记录
+                    if !form().id.is_empty() {
+                        div { class: "card bg-base-100 shadow-sm",
+                            div { class: "card-body",
+                                div { class: "flex items-center",
+                                    h2 { class: "font-semibold", "运行记录" }
+                                    div { class: "flex-1" }
+                                    button {
+                                        class: "btn btn-ghost btn-sm",
+                                        onclick: move |_| refresh_runs(),
+                                        "刷新"
+                                    }
+                                }
+                                div { class: "overflow-x-auto max-h-96",
+                                    table { class: "table table-sm",
+                                        thead {
+                                            tr {
+                                                th { "开始时间" }
+                                                th { "业务日期" }
+                                                th { "状态" }
+                                                th { "写入行数" }
+                                                th { "耗时" }
+                                                th { "失败原因" }
+                                            }
+                                        }
+                                        tbody {
+                                            for run in runs().iter() {
+                                                tr { key: "{run.id}",
+                                                    td { "{run.started_at}" }
+                                                    td { {run.detail.get("biz_date").and_then(|v| v.as_str()).unwrap_or("-")} }
+                                                    td { span { class: run.status.badge_class(), "{run.status}" } }
+                                                    td { "{run.rows_written}" }
+                                                    td { "{run.duration_text()}" }
+                                                    td { class: "text-error text-xs max-w-xs break-all", "{run.error_message}" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                    if runs().is_empty() {
+                                        div { class: "text-center text-base-content/60 py-8", "暂无运行记录" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if show_delete() {
+            TransformDeleteDialog {
+                task: form(),
+                on_confirm: handle_confirm_delete,
+                on_cancel: move |_| show_delete.set(false),
+            }
+        }
+    }
+}
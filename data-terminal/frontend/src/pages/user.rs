@@ -0,0 +1,358 @@
+use dioxus::prelude::*;
+use crate::components::{
+    user_dialog::{UserDialog, UserModalMode},
+    user_delete_dialog::UserDeleteDialog,
+};
+use crate::models::protocol::ApiResponse;
+use crate::models::user::{PasswordChangeRequest, User, UserStatus};
+use crate::routes::Route;
+use crate::utils::request::HttpRequest;
+use crate::utils::{cookie, request::RequestBuilder};
+use serde::{de::DeserializeOwned, Serialize};
+
+async fn get_api<T: DeserializeOwned>(path: &str, builder: RequestBuilder) -> Result<T, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = builder
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .build();
+    let response_text = client.get(path, Some(req_config)).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<T>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+async fn post_api<B>(path: &str, body: B) -> Result<String, String>
+where
+    B: Serialize + Send + Sync,
+{
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .build();
+    let response_text = client.post(path, Some(req_config), body).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<String>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+async fn delete_api(path: &str) -> Result<String, String> {
+    let client = crate::utils::request::create_client("http://localhost:3000");
+    let req_config = RequestBuilder::new()
+        .header("Content-Type", "application/json")
+        .header("Cookie", &cookie::get_browser_cookies())
+        .build();
+    let response_text = client.delete(path, Some(req_config)).await.map_err(|e| e.to_string())?;
+    let api_response = serde_json::from_str::<ApiResponse<String>>(&response_text).map_err(|e| e.to_string())?;
+    if api_response.result {
+        Ok(api_response.data)
+    } else {
+        Err(api_response.msg)
+    }
+}
+
+// 接口返回的 RFC 3339 时间只显示到秒
+fn format_time(time: &Option<String>) -> String {
+    match time {
+        Some(t) => t.chars().take(19).collect::<String>().replace('T', " "),
+        None => "-".to_string(),
+    }
+}
+
+#[component]
+pub fn UserPage() -> Element {
+    let navigator = use_navigator();
+    let mut current_user = use_signal(|| None as Option<User>);
+    let mut users = use_signal(|| Vec::<User>::new());
+    let mut keyword = use_signal(String::new);
+    let mut error_msg = use_signal(String::new);
+
+    let mut show_user_dialog = use_signal(|| false);
+    let mut user_modal_mode = use_signal(|| UserModalMode::Add);
+    let mut deleting_user = use_signal(|| None as Option<User>);
+
+    // 修改密码
+    let mut password_form = use_signal(PasswordChangeRequest::default);
+    let mut confirm_password = use_signal(String::new);
+    let mut password_msg = use_signal(|| None as Option<(bool, String)>);
+
+    let mut refresh_users = move || {
+        spawn(async move {
+            let builder = RequestBuilder::new()
+                .query_param("keyword", String::from(js_sys::encode_uri_component(&keyword())))
+                .query_param("page", 1)
+                .query_param("page_size", 200);
+            match get_api::<Vec<User>>("/api/v1/user/list", builder).await {
+                Ok(list) => users.set(list),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    use_effect(move || {
+        spawn(async move {
+            match get_api::<User>("/api/v1/user/me", RequestBuilder::new()).await {
+                Ok(user) => {
+                    let is_admin = user.is_admin;
+                    current_user.set(Some(user));
+                    if is_admin {
+                        refresh_users();
+                    }
+                }
+                Err(e) => error_msg.set(e),
+            }
+        });
+    });
+
+    let handle_change_password = move |_| {
+        let request = password_form();
+        if request.old_password.is_empty() || request.new_password.is_empty() {
+            password_msg.set(Some((false, "请输入当前密码和新密码".to_string())));
+            return;
+        }
+        if request.new_password != confirm_password() {
+            password_msg.set(Some((false, "两次输入的新密码不一致".to_string())));
+            return;
+        }
+        spawn(async move {
+            match post_api("/api/v1/user/password", request).await {
+                Ok(_) => {
+                    password_form.set(PasswordChangeRequest::default());
+                    confirm_password.set(String::new());
+                    password_msg.set(Some((true, "密码已修改".to_string())));
+                }
+                Err(e) => password_msg.set(Some((false, e))),
+            }
+        });
+    };
+
+    let unlock_user = move |id: String| {
+        spawn(async move {
+            match post_api(&format!("/api/v1/user/{}/unlock", id), ()).await {
+                Ok(_) => refresh_users(),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    let handle_delete_confirm = move |user: User| {
+        deleting_user.set(None);
+        spawn(async move {
+            match delete_api(&format!("/api/v1/user/{}", user.id)).await {
+                Ok(_) => refresh_users(),
+                Err(e) => error_msg.set(e),
+            }
+        });
+    };
+
+    let is_admin = current_user().is_some_and(|u| u.is_admin);
+    let current_id = current_user().map(|u| u.id).unwrap_or_default();
+
+    rsx! {
+        div { class: "flex flex-col h-screen bg-gray-50",
+            // 导航栏
+            div { class: "bg-white shadow-sm border-b border-gray-200 px-6 py-4 flex justify-between items-center",
+                div { class: "flex items-center space-x-4",
+                    button {
+                        class: "text-gray-600 hover:text-gray-900 flex items-center space-x-2",
+                        onclick: move |_| {
+                            navigator.push(Route::Home {});
+                        },
+                        span { "←" }
+                        span { "返回Home" }
+                    }
+                }
+                if is_admin {
+                    div { class: "flex items-center space-x-4",
+                        button {
+                            class: "btn btn-info",
+                            onclick: move |_| {
+                                user_modal_mode.set(UserModalMode::Add);
+                                show_user_dialog.set(true);
+                            },
+                            span { "➕" }
+                            span { "新增用户" }
+                        }
+                    }
+                }
+            }
+
+            div { class: "flex flex-1 overflow-hidden gap-6 p-6",
+                // 当前账号与修改密码
+                div { class: "w-80 shrink-0 space-y-4",
+                    div { class: "card bg-base-100 shadow-sm",
+                        div { class: "card-body p-4",
+                            h3 { class: "font-semibold", "我的账号" }
+                            if let Some(user) = current_user() {
+                                div { class: "text-sm space-y-1",
+                                    div { "登录名：{user.username}" }
+                                    div { "显示名称：{user.name()}" }
+                                    div { "角色：", if user.is_admin { "管理员" } else { "普通用户" } }
+                                    div { "最后登录：{format_time(&user.last_login_at)}" }
+                                }
+                            }
+                        }
+                    }
+                    div { class: "card bg-base-100 shadow-sm",
+                        div { class: "card-body p-4 space-y-2",
+                            h3 { class: "font-semibold", "修改密码" }
+                            input {
+                                class: "input input-bordered input-sm w-full",
+                                r#type: "password",
+                                placeholder: "当前密码",
+                                value: "{password_form().old_password}",
+                                oninput: move |e| password_form.with_mut(|f| f.old_password = e.value()),
+                            }
+                            input {
+                                class: "input input-bordered input-sm w-full",
+                                r#type: "password",
+                                placeholder: "新密码，至少 8 位",
+                                value: "{password_form().new_password}",
+                                oninput: move |e| password_form.with_mut(|f| f.new_password = e.value()),
+                            }
+                            input {
+                                class: "input input-bordered input-sm w-full",
+                                r#type: "password",
+                                placeholder: "确认新密码",
+                                value: "{confirm_password}",
+                                oninput: move |e| confirm_password.set(e.value()),
+                            }
+                            if let Some((ok, msg)) = password_msg() {
+                                p { class: if ok { "text-success text-sm" } else { "text-error text-sm" }, "{msg}" }
+                            }
+                            button {
+                                class: "btn btn-info btn-sm",
+                                onclick: handle_change_password,
+                                "保存"
+                            }
+                        }
+                    }
+                }
+
+                // 用户列表，仅管理员可见
+                div { class: "flex-1 min-w-0 overflow-auto",
+                    if !error_msg().is_empty() {
+                        div { class: "alert alert-error mb-4",
+                            span { "{error_msg()}" }
+                        }
+                    }
+                    if is_admin {
+                        div { class: "card bg-base-100 shadow-sm",
+                            div { class: "card-body p-4",
+                                div { class: "flex items-center justify-between mb-2",
+                                    h3 { class: "font-semibold", "用户管理" }
+                                    input {
+                                        class: "input input-bordered input-sm w-60",
+                                        placeholder: "搜索登录名或显示名称",
+                                        value: "{keyword}",
+                                        oninput: move |e| keyword.set(e.value()),
+                                        onkeydown: move |e| {
+                                            if e.key() == Key::Enter {
+                                                refresh_users();
+                                            }
+                                        },
+                                    }
+                                }
+                                table { class: "table table-sm",
+                                    thead {
+                                        tr {
+                                            th { "登录名" }
+                                            th { "显示名称" }
+                                            th { "角色" }
+                                            th { "状态" }
+                                            th { "最后登录" }
+                                            th { class: "text-right", "操作" }
+                                        }
+                                    }
+                                    tbody {
+                                        for user in users().into_iter() {
+                                            {
+                                                let edit_user = user.clone();
+                                                let delete_user = user.clone();
+                                                let unlock_id = user.id.clone();
+                                                let is_self = user.id == current_id;
+                                                rsx! {
+                                                    tr { key: "{user.id}",
+                                                        td { class: "font-mono", "{user.username}" }
+                                                        td { "{user.display_name}" }
+                                                        td {
+                                                            if user.is_admin {
+                                                                span { class: "badge badge-info badge-sm", "管理员" }
+                                                            } else {
+                                                                span { class: "badge badge-ghost badge-sm", "普通用户" }
+                                                            }
+                                                        }
+                                                        td {
+                                                            if user.status == UserStatus::Disabled {
+                                                                span { class: "badge badge-error badge-sm", "已禁用" }
+                                                            } else if user.is_locked() {
+                                                                span { class: "badge badge-warning badge-sm", title: "{format_time(&user.locked_until)}", "已锁定" }
+                                                            } else {
+                                                                span { class: "badge badge-success badge-sm", "启用" }
+                                                            }
+                                                        }
+                                                        td { class: "text-sm", "{format_time(&user.last_login_at)}" }
+                                                        td { class: "text-right space-x-1",
+                                                            if user.is_locked() {
+                                                                button {
+                                                                    class: "btn btn-ghost btn-xs",
+                                                                    onclick: move |_| unlock_user(unlock_id.clone()),
+                                                                    "解锁"
+                                                                }
+                                                            }
+                                                            button {
+                                                                class: "btn btn-ghost btn-xs",
+                                                                onclick: move |_| {
+                                                                    user_modal_mode.set(UserModalMode::Edit(edit_user.clone()));
+                                                                    show_user_dialog.set(true);
+                                                                },
+                                                                "编辑"
+                                                            }
+                                                            if !is_self {
+                                                                button {
+                                                                    class: "btn btn-ghost btn-xs text-error",
+                                                                    onclick: move |_| deleting_user.set(Some(delete_user.clone())),
+                                                                    "删除"
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if show_user_dialog() {
+                UserDialog {
+                    mode: user_modal_mode(),
+                    on_close: move |_| show_user_dialog.set(false),
+                    on_saved: move |_| {
+                        show_user_dialog.set(false);
+                        refresh_users();
+                    },
+                }
+            }
+
+            if let Some(user) = deleting_user() {
+                UserDeleteDialog {
+                    user,
+                    on_confirm: handle_delete_confirm,
+                    on_cancel: move |_| deleting_user.set(None),
+                }
+            }
+        }
+    }
+}
@@ -11,7 +11,15 @@ use crate::{pages::{
     datasource_mysql_config::{DatasourceMysqlAdd, DatasourceMysqlEdit},
     datasource_postgres_config::{DatasourcePostgresAdd, DatasourcePostgresEdit},
     datasource_queryapi_config::{DatasourceQueryApiAdd, DatasourceQueryApiEdit},
-    datasource_subscribeapi_config::{DatasourceSubscribeApiAdd, DatasourceSubscribeApiEdit, DatasourceSubscribeApiTokenManagement}
+    datasource_subscribeapi_config::{DatasourceSubscribeApiAdd, DatasourceSubscribeApiEdit, DatasourceSubscribeApiTokenManagement},
+    collection::CollectionTaskPage,
+    task_run_monitor::TaskRunMonitorPage,
+    pipeline_designer::PipelineDesignerPage,
+    transform::TransformPage,
+    query_console::QueryConsolePage,
+    metadata_catalog::MetadataCatalogPage,
+    lineage_graph::LineageGraphPage,
+    user::UserPage
 }};
 
 #[derive(Routable, Clone, PartialEq)]
@@ -55,11 +63,28 @@ pub enum Route {
                 DatasourceSubscribeApiTokenManagement { id: String },
             #[end_nest]
         #[end_nest]
+        #[route("/collection?:task_id")]
+        CollectionTaskPage { task_id: String },
+        #[route("/monitor/:task_type")]
+        TaskRunMonitorPage { task_type: String },
+        #[route("/pipeline")]
+        PipelineDesignerPage {},
+        #[route("/transform?:task_id")]
+        TransformPage { task_id: String },
+        #[route("/query")]
+        QueryConsolePage {},
+        #[route("/catalog")]
+        MetadataCatalogPage {},
+        #[route("/lineage")]
+        LineageGraphPage {},
     #[end_layout]
 
     #[route("/resource")]
     ResourcePage {},
 
+    #[route("/user")]
+    UserPage {},
+
     #[route("/:..route")]
     PageNotFound { route: Vec<String> },
 }
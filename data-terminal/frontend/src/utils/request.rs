@@ -4,6 +4,9 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::collections::HashMap;
 use crate::utils::error::{RequestError, RequestResult};
 
+/// 登录与刷新 token 接口的路径前缀
+const AUTH_PATH: &str = "/api/v1/login";
+
 /// HTTP 请求方法枚举
 #[derive(Debug, Clone, Copy)]
 pub enum HttpMethod {
@@ -246,12 +249,48 @@ impl HttpRequest for HttpClient {
         let full_url = self.build_url(url);
         let full_url = self.apply_query_params(&full_url, &config);
 
+        // 请求体先序列化，访问 token 过期重试时复用
+        let json_body = match body {
+            Some(body_data) => Some(serde_json::to_string(&body_data)
+                .map_err(|e| RequestError::serialization_error(format!("JSON serialization error: {}", e)))?),
+            None => None,
+        };
+
+        let mut response = self.send(method, &full_url, &config, json_body.as_deref()).await?;
+
+        // 访问 token 过期时用刷新 token 换取新 token 后重试一次，登录相关接口除外
+        if response.status() == 401 && !url.starts_with(AUTH_PATH) && self.refresh_token().await {
+            response = self.send(method, &full_url, &config, json_body.as_deref()).await?;
+        }
+
+        if response.status() >= 400 {
+            return Err(RequestError::http_error(
+                response.status(),
+                response.status_text()
+            ));
+        }
+
+        response
+            .text()
+            .await
+            .map_err(|e| RequestError::response_read_error(format!("Failed to read response: {}", e)))
+    }
+}
+
+impl HttpClient {
+    async fn send(
+        &self,
+        method: HttpMethod,
+        full_url: &str,
+        config: &RequestConfig,
+        json_body: Option<&str>,
+    ) -> RequestResult<gloo::net::http::Response> {
         let mut request_builder = match method {
-            HttpMethod::GET => GlooRequest::get(&full_url),
-            HttpMethod::POST => GlooRequest::post(&full_url),
-            HttpMethod::PUT => GlooRequest::put(&full_url),
-            HttpMethod::DELETE => GlooRequest::delete(&full_url),
-            HttpMethod::PATCH => GlooRequest::patch(&full_url),
+            HttpMethod::GET => GlooRequest::get(full_url),
+            HttpMethod::POST => GlooRequest::post(full_url),
+            HttpMethod::PUT => GlooRequest::put(full_url),
+            HttpMethod::DELETE => GlooRequest::delete(full_url),
+            HttpMethod::PATCH => GlooRequest::patch(full_url),
         };
 
         // 设置credentials模式为include，确保能接收cookie
@@ -275,10 +314,8 @@ impl HttpRequest for HttpClient {
         }
 
         // 添加请求体
-        let request = if let Some(body_data) = body {
-            let json_body = serde_json::to_string(&body_data)
-                .map_err(|e| RequestError::serialization_error(format!("JSON serialization error: {}", e)))?;
-            request_builder.body(json_body)
+        let request = if let Some(json_body) = json_body {
+            request_builder.body(json_body.to_string())
                 .map_err(|e| RequestError::request_build_error(format!("Request build error: {}", e)))?
         } else {
             request_builder.build()
@@ -286,22 +323,23 @@ impl HttpRequest for HttpClient {
         };
 
         // 发送请求
-        let response = request
+        request
             .send()
             .await
-            .map_err(|e| RequestError::network_error(format!("Request failed: {}", e)))?;
+            .map_err(|e| RequestError::network_error(format!("Request failed: {}", e)))
+    }
 
-        if response.status() >= 400 {
-            return Err(RequestError::http_error(
-                response.status(),
-                response.status_text()
-            ));
+    /// 使用 HttpOnly 的刷新 token cookie 换取新的访问 token
+    async fn refresh_token(&self) -> bool {
+        let url = self.build_url(&format!("{}/refresh", AUTH_PATH));
+        let request = GlooRequest::post(&url)
+            .credentials(web_sys::RequestCredentials::Include)
+            .build();
+        match request {
+            // 409 表示其他请求刚完成刷新，浏览器已持有新的 cookie
+            Ok(request) => request.send().await.is_ok_and(|response| response.ok() || response.status() == 409),
+            Err(_) => false,
         }
-
-        response
-            .text()
-            .await
-            .map_err(|e| RequestError::response_read_error(format!("Failed to read response: {}", e)))
     }
 }
 
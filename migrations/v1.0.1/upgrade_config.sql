@@ -0,0 +1,11 @@
+use data_factory_config;
+
+-- 项目归档表
+create table if not exists df_c_project_archive
+(
+    archive_db  varchar(64) not null comment '归档库名',
+    code        varchar(64) not null comment '项目编码',
+    archived_at timestamp   not null default current_timestamp comment '归档时间',
+    expire_at   timestamp   not null comment '过期时间，过期后归档库会被删除',
+    primary key (archive_db)
+) comment '项目归档表' engine = InnoDB;
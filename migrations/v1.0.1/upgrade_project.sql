@@ -0,0 +1,6 @@
+use data_factory_template;
+
+-- 数据源健康检查
+alter table df_c_datasource
+    add column last_checked_at timestamp null default null comment '最近一次健康检查时间' after connection_status,
+    add column last_error      varchar(1024) not null default '' comment '最近一次健康检查的错误信息' after last_checked_at;
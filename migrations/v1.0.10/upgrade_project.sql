@@ -0,0 +1,74 @@
+use data_factory_template;
+
+-- 元数据目录快照，结构发生变化时生成新版本，未变化时只更新检查时间
+create table if not exists df_c_catalog_snapshot
+(
+    id           char(36) not null comment '主键',
+    source_type  varchar(16) not null comment '来源类型: datasource, resource',
+    source_id    char(36) not null comment '数据源或资源ID',
+    source_name  varchar(128) not null default '' comment '数据源或资源名称',
+    version      int not null comment '版本号，从 1 开始递增',
+    is_latest    tinyint(1) not null default 1 comment '是否为该来源的最新版本',
+    table_count  int not null default 0 comment '表数量',
+    column_count int not null default 0 comment '列数量',
+    drift_count  int not null default 0 comment '相对上一版本的变更数量',
+    created_at   timestamp not null default current_timestamp comment '生成时间',
+    checked_at   timestamp not null default current_timestamp comment '最近一次确认结构未变化的时间',
+    primary key (id),
+    unique key uk_source_version (source_type, source_id, version),
+    key idx_latest (is_latest, source_type, source_id)
+) COMMENT '元数据快照表' engine = InnoDB;
+
+create table if not exists df_c_catalog_table
+(
+    id          char(36) not null comment '主键',
+    snapshot_id char(36) not null comment '快照ID',
+    schema_name varchar(128) not null comment '库或 schema',
+    table_name  varchar(128) not null comment '表名',
+    table_type  varchar(16) not null default 'table' comment '类型: table, view',
+    comment     varchar(2048) not null default '' comment '表注释',
+    approx_rows bigint null comment '估算行数',
+    primary key (id),
+    key idx_snapshot (snapshot_id, schema_name, table_name),
+    key idx_table_name (table_name)
+) COMMENT '元数据快照中的表' engine = InnoDB;
+
+create table if not exists df_c_catalog_column
+(
+    id            char(36) not null comment '主键',
+    snapshot_id   char(36) not null comment '快照ID',
+    schema_name   varchar(128) not null comment '库或 schema',
+    table_name    varchar(128) not null comment '表名',
+    column_name   varchar(128) not null comment '列名',
+    ordinal       int not null comment '列序号，从 1 开始',
+    column_type   varchar(256) not null comment '完整类型',
+    data_type     varchar(64) not null comment '基础类型',
+    nullable      tinyint(1) not null default 1 comment '是否可空',
+    default_value varchar(1024) null comment '默认值',
+    comment       varchar(1024) not null default '' comment '列注释',
+    primary_key   tinyint(1) not null default 0 comment '是否为主键列',
+    primary key (id),
+    key idx_snapshot (snapshot_id, schema_name, table_name),
+    key idx_column_name (column_name)
+) COMMENT '元数据快照中的列' engine = InnoDB;
+
+-- 相邻两个快照版本之间的结构变更
+create table if not exists df_c_catalog_drift
+(
+    id           char(36) not null comment '主键',
+    snapshot_id  char(36) not null comment '产生变更的快照ID',
+    source_type  varchar(16) not null comment '来源类型: datasource, resource',
+    source_id    char(36) not null comment '数据源或资源ID',
+    from_version int not null comment '上一版本',
+    to_version   int not null comment '当前版本',
+    schema_name  varchar(128) not null comment '库或 schema',
+    table_name   varchar(128) not null comment '表名',
+    column_name  varchar(128) not null default '' comment '列名，表级变更为空',
+    drift_type   varchar(32) not null comment '变更类型: table_added, table_removed, column_added, column_removed, column_changed',
+    before_value varchar(2048) null comment '变更前的列定义',
+    after_value  varchar(2048) null comment '变更后的列定义',
+    detected_at  timestamp not null default current_timestamp comment '发现时间',
+    primary key (id),
+    key idx_source (source_type, source_id, detected_at),
+    key idx_snapshot (snapshot_id)
+) COMMENT '元数据结构变更表' engine = InnoDB;
@@ -0,0 +1,26 @@
+use data_factory_template;
+
+-- 列级血缘边，保存采集任务与加工任务时按任务整体重建
+create table if not exists df_c_lineage_edge
+(
+    id            char(36) not null comment '主键',
+    task_type     varchar(16) not null comment '产生血缘的任务类型: collection, transform',
+    task_id       char(36) not null comment '任务ID',
+    task_name     varchar(64) not null default '' comment '任务名称',
+    source_type   varchar(16) not null comment '上游来源类型: datasource, resource',
+    source_id     char(36) not null comment '上游数据源或资源ID',
+    source_schema varchar(128) not null default '' comment '上游库或 schema',
+    source_table  varchar(128) not null comment '上游表',
+    source_column varchar(128) not null default '' comment '上游列，表级血缘为空',
+    target_type   varchar(16) not null comment '下游来源类型: datasource, resource',
+    target_id     char(36) not null comment '下游数据源或资源ID',
+    target_schema varchar(128) not null default '' comment '下游库或 schema',
+    target_table  varchar(128) not null comment '下游表',
+    target_column varchar(128) not null default '' comment '下游列，表级血缘为空',
+    expression    varchar(2048) not null default '' comment '下游列的计算表达式，直接映射为空',
+    created_at    timestamp not null default current_timestamp comment '生成时间',
+    primary key (id),
+    key idx_task (task_type, task_id),
+    key idx_source (source_type, source_id, source_table),
+    key idx_target (target_type, target_id, target_table)
+) COMMENT '数据血缘表' engine = InnoDB;
@@ -0,0 +1,19 @@
+use data_factory_config;
+
+-- 用户表，首次启动时按配置文件中的 admin 账号创建管理员
+create table if not exists df_c_user
+(
+    id              char(36)     not null comment '主键',
+    username        varchar(64)  not null comment '登录名',
+    display_name    varchar(64)  not null default '' comment '显示名称',
+    password_hash   varchar(255) not null comment 'Argon2 密码哈希',
+    is_admin        tinyint(1)   not null default 0 comment '是否管理员',
+    status          enum ('active', 'disabled') not null default 'active' comment '账号状态',
+    failed_attempts int          not null default 0 comment '连续登录失败次数',
+    locked_until    timestamp    null comment '锁定截止时间',
+    last_login_at   timestamp    null comment '最后登录时间',
+    created_at      timestamp    not null default current_timestamp comment '创建时间',
+    updated_at      timestamp    not null default current_timestamp on update current_timestamp comment '更新时间',
+    primary key (id),
+    unique key uk_username (username)
+) comment '用户表' engine = InnoDB;
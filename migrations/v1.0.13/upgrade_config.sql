@@ -0,0 +1,13 @@
+use data_factory_config;
+
+-- 项目成员表，系统管理员不需要加入即可管理所有项目
+create table if not exists df_c_project_member
+(
+    project_code varchar(64) not null comment '项目编码',
+    user_id      char(36)    not null comment '用户ID',
+    role         enum ('owner', 'developer', 'viewer') not null default 'viewer' comment '项目角色',
+    created_at   timestamp   not null default current_timestamp comment '创建时间',
+    updated_at   timestamp   not null default current_timestamp on update current_timestamp comment '更新时间',
+    primary key (project_code, user_id),
+    key idx_user (user_id)
+) comment '项目成员表' engine = InnoDB;
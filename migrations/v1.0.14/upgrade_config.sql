@@ -0,0 +1,31 @@
+use data_factory_config;
+
+-- 刷新 token 表，只保存 token 的 SHA-256 摘要，每次刷新轮换并作废旧 token
+create table if not exists df_c_refresh_token
+(
+    id           char(36)     not null comment '主键ID',
+    user_id      char(36)     not null comment '用户ID',
+    family_id    char(36)     not null comment '同一次登录轮换出的 token 属于同一族',
+    token_hash   char(64)     not null comment 'token 的 SHA-256 摘要',
+    project_code varchar(64)  not null default '' comment '当前选择的项目',
+    expires_at   timestamp    not null comment '过期时间',
+    revoked_at   timestamp    null comment '作废时间',
+    replaced_by  char(36)     null comment '轮换后的新 token ID',
+    created_at   timestamp    not null default current_timestamp comment '创建时间',
+    primary key (id),
+    unique key uk_token_hash (token_hash),
+    key idx_user (user_id),
+    key idx_family (family_id),
+    key idx_expires (expires_at)
+) comment '刷新token表' engine = InnoDB;
+
+-- 已注销的访问 token，过期后清理
+create table if not exists df_c_revoked_token
+(
+    jti        char(36)  not null comment '访问 token ID',
+    user_id    char(36)  not null comment '用户ID',
+    expires_at timestamp not null comment '访问 token 过期时间',
+    created_at timestamp not null default current_timestamp comment '注销时间',
+    primary key (jti),
+    key idx_expires (expires_at)
+) comment '已注销访问token表' engine = InnoDB;
@@ -0,0 +1,6 @@
+use data_factory_config;
+
+-- 删除项目前先记录删除状态与方式，中断后可按记录继续删除
+alter table df_c_project modify create_status enum ('pending', 'running', 'success', 'fail', 'deleting') default 'pending' comment '项目状态';
+alter table df_c_project add column delete_mode enum ('drop', 'archive') null comment '删除方式';
+alter table df_c_project add column archive_db varchar(64) not null default '' comment '归档库名';
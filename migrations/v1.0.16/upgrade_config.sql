@@ -0,0 +1,4 @@
+use data_factory_config;
+
+-- 轮换后的刷新 token 只允许在宽限期内被并发请求再使用一次，再次使用时作废整族
+alter table df_c_refresh_token add column reused_at timestamp null comment '作废后首次被再次使用的时间';
@@ -0,0 +1,19 @@
+use data_factory_template;
+
+-- 任务运行记录
+create table if not exists df_c_task_run
+(
+    id            char(36) not null comment '主键',
+    task_id       char(36) not null comment '任务ID',
+    task_type     varchar(32) not null comment '任务类型: collection, sync, transform',
+    status        varchar(16) not null comment '运行状态: running, success, failed, cancelled',
+    rows_read     bigint not null default 0 comment '读取行数',
+    rows_written  bigint not null default 0 comment '写入行数',
+    duration_ms   bigint not null default 0 comment '耗时（毫秒）',
+    error_message text null comment '失败原因',
+    detail        json null comment '运行明细（按表统计等）',
+    started_at    timestamp not null default current_timestamp comment '开始时间',
+    finished_at   timestamp null default null comment '结束时间',
+    primary key (id),
+    index idx_task_run_task (task_id, started_at)
+) COMMENT '任务运行记录表' engine = InnoDB;
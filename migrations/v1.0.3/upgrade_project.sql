@@ -0,0 +1,12 @@
+use data_factory_template;
+
+-- 增量采集水位
+create table if not exists df_c_collection_watermark
+(
+    task_id          char(36) not null comment '采集任务ID',
+    source_table     varchar(128) not null comment '源表名',
+    watermark_column varchar(64) not null comment '水位列',
+    watermark        json not null comment '已采集到的最大水位值',
+    updated_at       timestamp not null default current_timestamp on update current_timestamp comment '更新时间',
+    primary key (task_id, source_table)
+) COMMENT '增量采集水位表' engine = InnoDB;
@@ -0,0 +1,13 @@
+use data_factory_template;
+
+-- 实时采集消费位点
+create table if not exists df_c_collection_checkpoint
+(
+    task_id    char(36) not null comment '采集任务ID',
+    checkpoint json not null comment '消费位点，如 binlog 文件名与位置、GTID 集合',
+    updated_at timestamp not null default current_timestamp on update current_timestamp comment '更新时间',
+    primary key (task_id)
+) COMMENT '实时采集位点表' engine = InnoDB;
+
+-- 实时采集任务使用的采集类型
+alter table df_c_collection modify collect_type enum('full', 'incremental', 'stream') not null comment '采集类型';
@@ -0,0 +1,18 @@
+use data_factory_template;
+
+-- 任务调度计划
+create table if not exists df_c_task_schedule
+(
+    task_id        char(36) not null comment '任务ID',
+    task_type      varchar(32) not null comment '任务类型: collection, sync, transform',
+    cron           varchar(128) not null comment 'cron 表达式',
+    timezone       varchar(64) not null default 'Asia/Shanghai' comment '时区，如 Asia/Shanghai',
+    misfire_policy varchar(16) not null default 'skip' comment '错过触发的处理: skip, fire_once',
+    enabled        tinyint(1) not null default 1 comment '是否启用',
+    next_fire_at   timestamp null default null comment '下次触发时间',
+    last_fire_at   timestamp null default null comment '上次触发时间',
+    created_at     timestamp not null default current_timestamp comment '创建时间',
+    updated_at     timestamp not null default current_timestamp on update current_timestamp comment '更新时间',
+    primary key (task_id),
+    index idx_task_schedule_next (enabled, next_fire_at)
+) COMMENT '任务调度表' engine = InnoDB;
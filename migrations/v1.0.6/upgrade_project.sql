@@ -0,0 +1,19 @@
+use data_factory_template;
+
+-- 运行记录补充传输字节数与错误链
+alter table df_c_task_run
+    add column bytes       bigint not null default 0 comment '传输字节数（按行数据估算）' after rows_written,
+    add column error_chain json null comment '错误链，由外到内' after error_message,
+    add index idx_task_run_started (started_at),
+    add index idx_task_run_status (status, started_at);
+
+-- 任务运行日志
+create table if not exists df_c_task_run_log
+(
+    run_id    char(36) not null comment '运行记录ID',
+    seq       bigint not null comment '日志序号，从 1 开始',
+    level     varchar(8) not null comment '日志级别: error, warn, info, debug',
+    message   text not null comment '日志内容',
+    logged_at timestamp(3) not null default current_timestamp(3) comment '记录时间',
+    primary key (run_id, seq)
+) COMMENT '任务运行日志表' engine = InnoDB;
@@ -0,0 +1,31 @@
+use data_factory_template;
+
+-- DAG 流水线
+create table if not exists df_c_pipeline
+(
+    id           char(36) not null comment '主键',
+    name         varchar(64) not null comment '流水线名称',
+    description  varchar(255) not null default '' comment '描述',
+    nodes        json not null comment '任务节点',
+    edges        json not null comment '依赖边',
+    max_parallel int not null default 4 comment '最大并行节点数',
+    created_at   timestamp not null default current_timestamp comment '创建时间',
+    updated_at   timestamp not null default current_timestamp on update current_timestamp comment '更新时间',
+    primary key (id)
+) COMMENT '流水线表' engine = InnoDB;
+
+-- 流水线运行中每个节点的状态
+create table if not exists df_c_pipeline_node_run
+(
+    run_id        char(36) not null comment '流水线运行记录ID',
+    node_id       varchar(64) not null comment '节点ID',
+    task_type     varchar(32) not null comment '任务类型: collection, sync, transform',
+    task_id       char(36) not null comment '任务ID',
+    status        varchar(16) not null comment '节点状态: pending, running, success, failed, skipped, cancelled',
+    attempts      int not null default 0 comment '已执行次数',
+    task_run_id   char(36) null default null comment '最近一次执行的任务运行记录ID',
+    error_message text null comment '失败或跳过原因',
+    started_at    timestamp null default null comment '开始时间',
+    finished_at   timestamp null default null comment '结束时间',
+    primary key (run_id, node_id)
+) COMMENT '流水线节点运行表' engine = InnoDB;
@@ -0,0 +1,21 @@
+use data_factory_template;
+
+-- SQL 加工（宽表）任务
+create table if not exists df_c_transform
+(
+    id                    char(36) not null comment '主键',
+    name                  varchar(64) not null comment '任务名称',
+    description           varchar(255) not null default '' comment '描述',
+    resource_id           char(36) not null comment '执行 SQL 的资源ID（MySQL 或 Doris）',
+    target_database       varchar(64) not null default '' comment '输出表所在库，为空时使用资源配置中的第一个库',
+    output_table          varchar(64) not null comment '输出表名',
+    materialization       varchar(16) not null comment '物化方式: replace, append, merge',
+    merge_keys            json not null comment 'merge 方式下的键列',
+    sql_text              mediumtext not null comment 'SELECT 语句，可包含 ${参数}',
+    params                json not null comment '自定义参数',
+    referenced_tables     json not null comment '保存时解析出的引用表',
+    doris_replication_num int not null default 1 comment '目标为 Doris 时建表使用的副本数',
+    created_at            timestamp not null default current_timestamp comment '创建时间',
+    updated_at            timestamp not null default current_timestamp on update current_timestamp comment '更新时间',
+    primary key (id)
+) COMMENT 'SQL 加工任务表' engine = InnoDB;
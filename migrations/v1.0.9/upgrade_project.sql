@@ -0,0 +1,19 @@
+use data_factory_template;
+
+-- SQL 查询控制台的查询历史，按用户记录
+create table if not exists df_c_query_history
+(
+    id            char(36) not null comment '主键',
+    user_name     varchar(128) not null comment '执行查询的用户',
+    target_type   varchar(16) not null comment '查询对象类型: datasource, resource',
+    target_id     char(36) not null comment '数据源或资源ID',
+    sql_text      mediumtext not null comment '执行的 SQL',
+    status        varchar(16) not null comment '执行结果: success, failed, cancelled',
+    row_count     int not null default 0 comment '返回行数',
+    truncated     tinyint(1) not null default 0 comment '结果是否被行数上限截断',
+    duration_ms   bigint not null default 0 comment '耗时（毫秒）',
+    error_message text null comment '失败原因',
+    executed_at   timestamp not null default current_timestamp comment '执行时间',
+    primary key (id),
+    key idx_user_executed (user_name, executed_at)
+) COMMENT 'SQL 查询历史表' engine = InnoDB;